@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, net::SocketAddr};
+use std::{marker::PhantomData, net::SocketAddr, time::Duration};
 
 use bevy_ecs::{
     entity::Entity,
@@ -6,12 +6,17 @@ use bevy_ecs::{
 };
 
 use naia_bevy_shared::{
-    Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
-    GlobalEntity, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
+    Channel, ConnectionStats, EntityAndGlobalEntityConverter, EntityAuthStatus,
+    EntityDoesNotExistError, GlobalEntity, Message, PendingRequest, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, Tick, WaitlistEntry, WaitlistStats,
 };
 use naia_client::{
-    shared::{GameInstant, SocketConfig}, transport::Socket, Client as NaiaClient, ConnectionStatus,
-    NaiaClientError,
+    shared::{
+        DisconnectReason, EntityDespawnHook, GameInstant, GlobalRequestId, IdentityToken,
+        ResponseReceiveError, SocketConfig,
+    },
+    transport::Socket,
+    Client as NaiaClient, ConnectionStatus, NaiaClientError,
 };
 
 use crate::ReplicationConfig;
@@ -50,22 +55,34 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.auth_headers(headers);
     }
 
-    pub fn connect<S: Into<Box<dyn Socket>>>(&mut self, socket: S) {
-        self.client.client.connect(socket);
+    pub fn set_entity_despawn_hook<H: EntityDespawnHook<Entity> + 'static>(&mut self, hook: H) {
+        self.client.client.set_entity_despawn_hook(hook);
     }
 
-    pub fn disconnect(&mut self) {
-        self.client.client.disconnect();
+    pub fn connect<S: Into<Box<dyn Socket>>>(&mut self, socket: S) -> Result<(), NaiaClientError> {
+        self.client.client.connect(socket)
+    }
+
+    pub fn disconnect(&mut self) -> Result<(), NaiaClientError> {
+        self.client.client.disconnect()
     }
 
     pub fn connection_status(&self) -> ConnectionStatus {
         self.client.client.connection_status()
     }
 
+    pub fn disconnect_reason(&self) -> Option<&DisconnectReason> {
+        self.client.client.disconnect_reason()
+    }
+
     pub fn server_address(&self) -> Result<SocketAddr, NaiaClientError> {
         self.client.client.server_address()
     }
 
+    pub fn identity_token(&self) -> Option<IdentityToken> {
+        self.client.client.identity_token()
+    }
+
     pub fn rtt(&self) -> f32 {
         self.client.client.rtt()
     }
@@ -74,20 +91,45 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.jitter()
     }
 
+    pub fn clock_offset(&self) -> Option<f32> {
+        self.client.client.clock_offset()
+    }
+
+    pub fn server_time(&self) -> Option<GameInstant> {
+        self.client.client.server_time()
+    }
+
     // Config
     pub fn socket_config(&self) -> &SocketConfig {
         self.client.client.socket_config()
     }
 
     //// Messages ////
-    pub fn send_message<C: Channel, M: Message>(&mut self, message: &M) {
-        self.client.client.send_message::<C, M>(message);
+    pub fn send_message<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
+        self.client.client.send_message::<C, M>(message)
+    }
+
+    pub fn send_message_with_key<C: Channel, M: Message>(
+        &mut self,
+        key: u64,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
+        self.client
+            .client
+            .send_message_with_key::<C, M>(key, message)
     }
 
-    pub fn send_tick_buffer_message<C: Channel, M: Message>(&mut self, tick: &Tick, message: &M) {
+    pub fn send_tick_buffer_message<C: Channel, M: Message>(
+        &mut self,
+        tick: &Tick,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
         self.client
             .client
-            .send_tick_buffer_message::<C, M>(tick, message);
+            .send_tick_buffer_message::<C, M>(tick, message)
     }
 
     /// Requests ///
@@ -98,21 +140,51 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.send_request::<C, Q>(request)
     }
 
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaClientError> {
+        self.client
+            .client
+            .send_request_with_timeout::<C, Q>(request, timeout)
+    }
+
+    pub fn cancel_request(&mut self, request_id: GlobalRequestId) -> bool {
+        self.client.client.cancel_request(request_id)
+    }
+
     pub fn send_response<S: Response>(
         &mut self,
         response_key: &ResponseSendKey<S>,
         response: &S,
-    ) -> bool {
+    ) -> Result<(), NaiaClientError> {
         self.client.client.send_response(response_key, response)
     }
 
     pub fn receive_response<S: Response>(
         &mut self,
         response_key: &ResponseReceiveKey<S>,
-    ) -> Option<S> {
+    ) -> Option<Result<S, ResponseReceiveError>> {
         self.client.client.receive_response(response_key)
     }
 
+    pub fn pending_requests(&self) -> Vec<PendingRequest> {
+        self.client.client.pending_requests()
+    }
+
+    pub fn local_entities_awaiting_resolution(&self) -> Vec<WaitlistEntry> {
+        self.client.client.local_entities_awaiting_resolution()
+    }
+
+    pub fn entity_waitlist_stats(&self) -> Option<WaitlistStats> {
+        self.client.client.entity_waitlist_stats()
+    }
+
+    pub fn connection_stats(&mut self) -> Option<ConnectionStats> {
+        self.client.client.connection_stats()
+    }
+
     //// Ticks ////
 
     pub fn client_tick(&self) -> Option<Tick> {
@@ -2,11 +2,11 @@ use std::marker::PhantomData;
 
 use bevy_ecs::{
     entity::Entity,
-    system::EntityCommands,
+    system::{Commands, EntityCommands},
     world::{Mut, World, Command as BevyCommand},
 };
 
-use naia_bevy_shared::{EntityAuthStatus, HostOwned, WorldMutType, WorldProxyMut};
+use naia_bevy_shared::{ComponentKind, EntityAuthStatus, HostOwned, WorldMutType, WorldProxyMut};
 use naia_client::ReplicationConfig;
 
 use crate::{client::ClientWrapper, Client};
@@ -14,6 +14,10 @@ use crate::{client::ClientWrapper, Client};
 // Bevy Commands Extension
 pub trait CommandsExt<'a> {
     fn local_duplicate(&'a mut self) -> Entity;
+    /// Spawns a new Entity with copies of only the given kinds of replicated
+    /// Component. An empty list of kinds spawns a bare Entity. Markers such as
+    /// `HostOwned` are never copied
+    fn local_duplicate_filtered(&'a mut self, component_kinds: &[ComponentKind]) -> Entity;
     fn configure_replication<T: Send + Sync + 'static>(
         &'a mut self,
         config: ReplicationConfig,
@@ -54,6 +58,16 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
         new_entity
     }
 
+    fn local_duplicate_filtered(&'a mut self, component_kinds: &[ComponentKind]) -> Entity {
+        let old_entity = self.id();
+        let mut commands = self.commands();
+        let new_entity = commands.spawn_empty().id();
+        let command =
+            LocalDuplicateComponents::filtered(new_entity, old_entity, component_kinds.to_vec());
+        commands.add(command);
+        new_entity
+    }
+
     fn enable_replication<T: Send + Sync + 'static>(
         &'a mut self,
         client: &mut Client<T>,
@@ -114,10 +128,34 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
     }
 }
 
+// Bevy Commands Extension, for working with many Entities at once
+pub trait BatchCommandsExt {
+    /// Spawns a copy of each of the given Entities, with all of their
+    /// replicated Components. Returns the new Entities in the same order as
+    /// the given ones
+    fn local_duplicate_many(&mut self, entities: &[Entity]) -> Vec<Entity>;
+}
+
+impl<'w, 's> BatchCommandsExt for Commands<'w, 's> {
+    fn local_duplicate_many(&mut self, entities: &[Entity]) -> Vec<Entity> {
+        let new_entities: Vec<Entity> = entities.iter().map(|_| self.spawn_empty().id()).collect();
+        let command = LocalDuplicateManyComponents::new(
+            new_entities
+                .iter()
+                .copied()
+                .zip(entities.iter().copied())
+                .collect(),
+        );
+        self.add(command);
+        new_entities
+    }
+}
+
 //// LocalDuplicateComponents Command ////
 pub(crate) struct LocalDuplicateComponents {
     mutable_entity: Entity,
     immutable_entity: Entity,
+    component_kinds: Option<Vec<ComponentKind>>,
 }
 
 impl LocalDuplicateComponents {
@@ -125,17 +163,67 @@ impl LocalDuplicateComponents {
         Self {
             mutable_entity: new_entity,
             immutable_entity: old_entity,
+            component_kinds: None,
+        }
+    }
+
+    pub fn filtered(
+        new_entity: Entity,
+        old_entity: Entity,
+        component_kinds: Vec<ComponentKind>,
+    ) -> Self {
+        Self {
+            mutable_entity: new_entity,
+            immutable_entity: old_entity,
+            component_kinds: Some(component_kinds),
         }
     }
 }
 
 impl BevyCommand for LocalDuplicateComponents {
     fn apply(self, world: &mut World) {
-        WorldMutType::<Entity>::local_duplicate_components(
-            &mut world.proxy_mut(),
-            &self.mutable_entity,
-            &self.immutable_entity,
-        );
+        match self.component_kinds {
+            Some(component_kinds) => {
+                WorldMutType::<Entity>::local_duplicate_components_of_kinds(
+                    &mut world.proxy_mut(),
+                    &self.mutable_entity,
+                    &self.immutable_entity,
+                    &component_kinds,
+                );
+            }
+            None => {
+                WorldMutType::<Entity>::local_duplicate_components(
+                    &mut world.proxy_mut(),
+                    &self.mutable_entity,
+                    &self.immutable_entity,
+                );
+            }
+        }
+    }
+}
+
+// LocalDuplicateManyComponents Command
+pub(crate) struct LocalDuplicateManyComponents {
+    // (new Entity, old Entity)
+    entity_pairs: Vec<(Entity, Entity)>,
+}
+
+impl LocalDuplicateManyComponents {
+    pub fn new(entity_pairs: Vec<(Entity, Entity)>) -> Self {
+        Self { entity_pairs }
+    }
+}
+
+impl BevyCommand for LocalDuplicateManyComponents {
+    fn apply(self, world: &mut World) {
+        let mut world_proxy = world.proxy_mut();
+        for (new_entity, old_entity) in &self.entity_pairs {
+            WorldMutType::<Entity>::local_duplicate_components(
+                &mut world_proxy,
+                new_entity,
+                old_entity,
+            );
+        }
     }
 }
 
@@ -8,7 +8,23 @@ use naia_bevy_shared::{
     Channel, ChannelKind, ComponentKind, Message, MessageContainer, MessageKind, Replicate,
     Request, ResponseSendKey, Tick,
 };
-use naia_client::shared::GlobalResponseId;
+use naia_client::shared::{GlobalResponseId, IdentityToken, RejectReason};
+
+// AuthenticatedEvent
+#[derive(Event)]
+pub struct AuthenticatedEvent<T> {
+    pub identity_token: IdentityToken,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> AuthenticatedEvent<T> {
+    pub fn new(identity_token: IdentityToken) -> Self {
+        Self {
+            identity_token,
+            phantom_t: PhantomData,
+        }
+    }
+}
 
 // ConnectEvent
 #[derive(Event)]
@@ -41,12 +57,14 @@ impl<T> DisconnectEvent<T> {
 // RejectEvent
 #[derive(Event)]
 pub struct RejectEvent<T> {
+    pub reason: RejectReason,
     phantom_t: PhantomData<T>,
 }
 
 impl<T> RejectEvent<T> {
-    pub fn new() -> Self {
+    pub fn new(reason: RejectReason) -> Self {
         Self {
+            reason,
             phantom_t: PhantomData,
         }
     }
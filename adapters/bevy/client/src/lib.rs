@@ -3,8 +3,8 @@ pub use naia_bevy_shared::{
     ResponseSendKey, Tick, Timer, GameInstant,
 };
 pub use naia_client::{
-    shared::{default_channels, Instant, Message, ResponseReceiveKey},
-    transport, ClientConfig, CommandHistory, NaiaClientError, ReplicationConfig,
+    shared::{default_channels, Instant, Message, RejectReason, ResponseReceiveKey},
+    transport, ClientConfig, ClientError, CommandHistory, NaiaClientError, ReplicationConfig,
 };
 
 pub mod events;
@@ -13,10 +13,12 @@ mod client;
 mod commands;
 pub mod component_events;
 mod components;
+mod local_identity;
 mod plugin;
 mod systems;
 
 pub use client::Client;
-pub use commands::CommandsExt;
+pub use commands::{BatchCommandsExt, CommandsExt};
 pub use components::{ClientOwned, ServerOwned};
+pub use local_identity::LocalIdentity;
 pub use plugin::Plugin;
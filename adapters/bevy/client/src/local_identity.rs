@@ -0,0 +1,33 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::system::Resource;
+
+use naia_client::shared::IdentityToken;
+
+/// Holds the IdentityToken issued by the Server once the Client has
+/// authenticated, or None if it has not been received yet
+#[derive(Resource)]
+pub struct LocalIdentity<T: Send + Sync + 'static> {
+    token: Option<IdentityToken>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static> Default for LocalIdentity<T> {
+    fn default() -> Self {
+        Self {
+            token: None,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> LocalIdentity<T> {
+    /// Get the IdentityToken, if it has been received
+    pub fn token(&self) -> Option<&IdentityToken> {
+        self.token.as_ref()
+    }
+
+    pub(crate) fn set(&mut self, token: Option<IdentityToken>) {
+        self.token = token;
+    }
+}
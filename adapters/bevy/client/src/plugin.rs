@@ -10,11 +10,13 @@ use naia_client::{Client, ClientConfig};
 use super::{
     client::ClientWrapper,
     events::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvents,
-        MessageEvents, PublishEntityEvent, RejectEvent, RemoveComponentEvents, ServerTickEvent,
-        SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        AuthenticatedEvent, ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent,
+        EntityAuthDeniedEvent, EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        InsertComponentEvents, MessageEvents, PublishEntityEvent, RejectEvent,
+        RemoveComponentEvents, ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
+        UpdateComponentEvents,
     },
+    local_identity::LocalIdentity,
     systems::before_receive_events,
 };
 
@@ -68,7 +70,9 @@ impl<T: Sync + Send + 'static> PluginType for Plugin<T> {
             .add_plugins(SharedPlugin::<T>::new())
             // RESOURCES //
             .insert_resource(client)
+            .init_resource::<LocalIdentity<T>>()
             // EVENTS //
+            .add_event::<AuthenticatedEvent<T>>()
             .add_event::<ConnectEvent<T>>()
             .add_event::<DisconnectEvent<T>>()
             .add_event::<RejectEvent<T>>()
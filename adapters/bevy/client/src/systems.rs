@@ -12,22 +12,23 @@ use naia_bevy_shared::{HostOwned, HostSyncEvent, WorldMutType, WorldProxyMut};
 
 mod naia_events {
     pub use naia_client::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, PublishEntityEvent, RejectEvent,
-        ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
+        AuthenticatedEvent, ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent,
+        EntityAuthDeniedEvent, EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        PublishEntityEvent, RejectEvent, ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
     };
 }
 
 mod bevy_events {
     pub use crate::events::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvents,
-        MessageEvents, PublishEntityEvent, RejectEvent, RemoveComponentEvents, RequestEvents,
-        ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvents,
+        AuthenticatedEvent, ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent,
+        EntityAuthDeniedEvent, EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        InsertComponentEvents, MessageEvents, PublishEntityEvent, RejectEvent,
+        RemoveComponentEvents, RequestEvents, ServerTickEvent, SpawnEntityEvent,
+        UnpublishEntityEvent, UpdateComponentEvents,
     };
 }
 
-use crate::{client::ClientWrapper, ServerOwned};
+use crate::{client::ClientWrapper, LocalIdentity, ServerOwned};
 
 pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
     let host_id = TypeId::of::<T>();
@@ -79,6 +80,22 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
         // Receive Events
         let mut events = client.client.receive(world.proxy_mut());
         if !events.is_empty() {
+            // Authenticated Event
+            if events.has::<naia_events::AuthenticatedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Events<bevy_events::AuthenticatedEvent<T>>>()
+                    .unwrap();
+                let mut last_token = None;
+                for identity_token in events.read::<naia_events::AuthenticatedEvent>() {
+                    last_token = Some(identity_token.clone());
+                    event_writer.send(bevy_events::AuthenticatedEvent::<T>::new(identity_token));
+                }
+                world
+                    .get_resource_mut::<LocalIdentity<T>>()
+                    .unwrap()
+                    .set(last_token);
+            }
+
             if events.has::<naia_events::ConnectEvent>() {
                 // Connect Event
                 let mut event_writer = world
@@ -97,6 +114,10 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 for _ in events.read::<naia_events::DisconnectEvent>() {
                     event_writer.send(bevy_events::DisconnectEvent::<T>::new());
                 }
+                world
+                    .get_resource_mut::<LocalIdentity<T>>()
+                    .unwrap()
+                    .set(None);
             }
 
             // Reject Event
@@ -104,8 +125,8 @@ pub fn before_receive_events<T: Send + Sync + 'static>(world: &mut World) {
                 let mut event_writer = world
                     .get_resource_mut::<Events<bevy_events::RejectEvent<T>>>()
                     .unwrap();
-                for _ in events.read::<naia_events::RejectEvent>() {
-                    event_writer.send(bevy_events::RejectEvent::<T>::new());
+                for reason in events.read::<naia_events::RejectEvent>() {
+                    event_writer.send(bevy_events::RejectEvent::<T>::new(reason));
                 }
             }
 
@@ -4,6 +4,8 @@ use bevy_ecs::{
     world::{Mut, World, Command as BevyCommand},
 };
 
+use log::warn;
+
 use naia_bevy_shared::{EntityAuthStatus, HostOwned, WorldProxyMut};
 use naia_server::{ReplicationConfig, UserKey};
 
@@ -25,6 +27,7 @@ pub trait CommandsExt<'a> {
     fn authority(&'a self, server: &Server) -> Option<EntityAuthStatus>;
     fn pause_replication(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a>;
     fn resume_replication(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a>;
+    fn replication_is_paused(&'a self, server: &Server) -> Option<bool>;
 }
 
 impl<'a> CommandsExt<'a> for EntityCommands<'a> {
@@ -57,10 +60,13 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
 
     fn give_authority(
         &'a mut self,
-        _server: &mut Server,
-        _user_key: &UserKey,
+        server: &mut Server,
+        user_key: &UserKey,
     ) -> &'a mut EntityCommands<'a> {
-        todo!()
+        if let Err(err) = server.grant_authority(&self.id(), user_key) {
+            warn!("Unable to give authority over entity: {}", err);
+        }
+        return self;
     }
 
     fn take_authority(&'a mut self, server: &mut Server) -> &'a mut EntityCommands<'a> {
@@ -81,6 +87,10 @@ impl<'a> CommandsExt<'a> for EntityCommands<'a> {
         server.resume_replication(&self.id());
         return self;
     }
+
+    fn replication_is_paused(&'a self, server: &Server) -> Option<bool> {
+        server.replication_is_paused(&self.id())
+    }
 }
 
 //// ConfigureReplicationCommand Command ////
@@ -5,8 +5,12 @@ pub use naia_server::{
         FileBitWriter, ResponseReceiveKey, SerdeErr, SignedInteger, SignedVariableInteger,
         UnsignedInteger, UnsignedVariableInteger,
     },
-    transport, ReplicationConfig, RoomKey, SerdeBevy as Serde, ServerConfig, UserKey,
+    transport, ChannelMetrics, ComponentInsertValidator, ConnectTokenConfig, HandshakeMetrics,
+    HandshakeObserver, HandshakeOutcome, ReplicationConfig, RoomKey, SerdeBevy as Serde,
+    ServerConfig, ServerMetrics, UserKey,
 };
+#[cfg(feature = "connect_tokens")]
+pub use naia_server::{ConnectToken, ConnectTokenError};
 
 pub mod events;
 
@@ -6,14 +6,17 @@ use bevy_ecs::{
 };
 
 use naia_server::{
-    shared::SocketConfig, transport::Socket, NaiaServerError, ReplicationConfig, RoomKey, RoomMut,
-    RoomRef, Server as NaiaServer, TickBufferMessages, UserKey, UserMut, UserRef, UserScopeMut,
-    UserScopeRef,
+    shared::{RejectReason, SocketConfig},
+    transport::Socket,
+    ComponentInsertValidator, HandshakeMetrics, HandshakeObserver, NaiaServerError,
+    ReplicationConfig, RoomKey, RoomMut, RoomRef, Server as NaiaServer, ServerMetrics,
+    TickBufferMessages, UserKey, UserMut, UserRef, UserScopeMut, UserScopeRef,
 };
 
 use naia_bevy_shared::{
     Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
-    GlobalEntity, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
+    GlobalEntity, Message, MessageDependency, PendingRequest, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, Tick,
 };
 
 #[derive(Resource)]
@@ -47,21 +50,103 @@ impl<'w> Server<'w> {
         self.server.0.reject_connection(user_key);
     }
 
+    pub fn reject_connection_with_reason(&mut self, user_key: &UserKey, reason: RejectReason) {
+        self.server
+            .0
+            .reject_connection_with_reason(user_key, reason);
+    }
+
     // Config
     pub fn socket_config(&self) -> &SocketConfig {
         self.server.0.socket_config()
     }
 
+    pub fn set_component_insert_validator<V: ComponentInsertValidator + 'static>(
+        &mut self,
+        validator: V,
+    ) {
+        self.server.0.set_component_insert_validator(validator);
+    }
+
+    pub fn handshake_metrics(&self) -> HandshakeMetrics {
+        self.server.0.handshake_metrics()
+    }
+
+    pub fn metrics(&self) -> ServerMetrics {
+        self.server.0.metrics()
+    }
+
+    pub fn set_handshake_observer<O: HandshakeObserver + 'static>(&mut self, observer: O) {
+        self.server.0.set_handshake_observer(observer);
+    }
+
     //// Messages ////
-    pub fn send_message<C: Channel, M: Message>(&mut self, user_key: &UserKey, message: &M) {
+    pub fn send_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<(), NaiaServerError> {
         self.server.0.send_message::<C, M>(user_key, message)
     }
 
+    /// Sends a message to the user which currently controls the given entity
+    pub fn send_message_to_entity_owner<C: Channel, M: Message>(
+        &mut self,
+        entity: &Entity,
+        message: &M,
+    ) -> Result<(), NaiaServerError> {
+        self.server
+            .0
+            .send_message_to_entity_owner::<C, M>(entity, message)
+    }
+
+    /// Sends a message to a user, returning a handle to it which can be passed
+    /// to `send_message_ordered_after()`. The channel must be reliable
+    pub fn send_tracked_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<MessageDependency, NaiaServerError> {
+        self.server
+            .0
+            .send_tracked_message::<C, M>(user_key, message)
+    }
+
+    /// Sends a message to a user once the message identified by `after` has
+    /// been delivered to them
+    pub fn send_message_ordered_after<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+        after: &MessageDependency,
+    ) -> Result<(), NaiaServerError> {
+        self.server
+            .0
+            .send_message_ordered_after::<C, M>(user_key, message, after)
+    }
+
     /// Sends a message to all connected users using a given channel
     pub fn broadcast_message<C: Channel, M: Message>(&mut self, message: &M) {
         self.server.0.broadcast_message::<C, M>(message);
     }
 
+    /// Sends a message to all connected users but one using a given channel
+    pub fn broadcast_message_except<C: Channel, M: Message>(
+        &mut self,
+        except_user_key: &UserKey,
+        message: &M,
+    ) {
+        self.server
+            .0
+            .broadcast_message_except::<C, M>(except_user_key, message);
+    }
+
+    /// Returns the user which currently controls the given entity: the user
+    /// holding authority over it if it is delegated, otherwise its owner
+    pub fn entity_controlling_user(&self, entity: &Entity) -> Option<UserKey> {
+        self.server.0.entity_controlling_user(entity)
+    }
+
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
         self.server.0.receive_tick_buffer_messages(tick)
     }
@@ -90,12 +175,20 @@ impl<'w> Server<'w> {
         self.server.0.receive_response(response_key)
     }
 
+    pub fn pending_requests(&self, user_key: &UserKey) -> Vec<PendingRequest> {
+        self.server.0.pending_requests(user_key)
+    }
+
     //// Updates ////
 
     pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, Entity)> {
         self.server.0.scope_checks()
     }
 
+    pub fn resync_entity(&mut self, user_key: &UserKey, entity: &Entity) -> bool {
+        self.server.0.resync_entity(user_key, entity)
+    }
+
     //// Users ////
 
     pub fn user_exists(&self, user_key: &UserKey) -> bool {
@@ -180,10 +273,30 @@ impl<'w> Server<'w> {
         self.server.0.resume_entity_replication(entity);
     }
 
+    pub(crate) fn replication_is_paused(&self, entity: &Entity) -> Option<bool> {
+        self.server.0.entity_replication_is_paused(entity)
+    }
+
     pub(crate) fn replication_config(&self, entity: &Entity) -> Option<ReplicationConfig> {
         self.server.0.entity_replication_config(entity)
     }
 
+    /// Gives authority over a delegated entity to the given user, taking it
+    /// from any current holder. See `naia_server::Server::grant_authority()`
+    pub fn grant_authority(
+        &mut self,
+        entity: &Entity,
+        user_key: &UserKey,
+    ) -> Result<(), NaiaServerError> {
+        self.server.0.grant_authority(entity, user_key)
+    }
+
+    /// Takes authority over a delegated entity back from any current holder.
+    /// See `naia_server::Server::revoke_authority()`
+    pub fn revoke_authority(&mut self, entity: &Entity) -> Result<(), NaiaServerError> {
+        self.server.0.revoke_authority(entity)
+    }
+
     pub(crate) fn entity_take_authority(&mut self, entity: &Entity) {
         self.server.0.entity_take_authority(entity);
     }
@@ -1,16 +1,16 @@
 pub use naia_shared::{
     sequence_greater_than, sequence_less_than, wrapping_diff, BitReader, BitWrite, BitWriter,
-    Channel, ChannelDirection, ChannelKind, ChannelMode, ComponentFieldUpdate, ComponentKind,
+    ChangeThreshold, Channel, ChannelDirection, ChannelKind, ChannelMode, ComponentFieldUpdate, ComponentKind,
     ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask, EntityAndGlobalEntityConverter,
     EntityAuthAccessor, EntityAuthStatus, EntityDoesNotExistError, EntityProperty,
     FakeEntityConverter, GlobalEntity, HostEntity, HostEntityAuthStatus, LinkConditionerConfig,
     LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut,
-    MessageBevy as Message, MessageBuilder, MessageContainer, MessageKind, MessageKinds, Named,
-    OwnedBitReader, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
+    MessageBevy as Message, MessageBuilder, MessageContainer, MessageDependency, MessageKind, MessageKinds, Named,
+    OrderedUnreliableSettings, OwnedBitReader, PendingRequest, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
     RemoteEntity, ReplicaDynMut, ReplicaDynRef, ReplicateBevy as Replicate, ReplicateBuilder,
     Request, Response, ResponseReceiveKey, ResponseSendKey, SerdeBevyShared as Serde, SerdeErr,
     SerdeIntegerConversion, SignedInteger, SignedVariableInteger, Tick, TickBufferSettings, Timer,
-    UnsignedInteger, UnsignedVariableInteger, WorldMutType, WorldRefType, MTU_SIZE_BYTES, Instant, GameInstant,
+    UnsignedInteger, UnsignedVariableInteger, WaitlistEntry, WaitlistStats, WorldMutType, WorldRefType, MTU_SIZE_BYTES, Instant, GameInstant, ConnectionStats,
 };
 
 mod change_detection;
@@ -65,6 +65,10 @@ impl<'w> WorldRefType<Entity> for WorldRef<'w> {
         has_component_of_kind(self.world, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: ReplicatedComponent>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component(self.world, entity)
     }
@@ -107,6 +111,10 @@ impl<'w> WorldRefType<Entity> for WorldMut<'w> {
         has_component_of_kind(self.world, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: ReplicatedComponent>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component(self.world, entity)
     }
@@ -139,7 +147,25 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
     }
 
     fn local_duplicate_components(&mut self, mutable_entity: &Entity, immutable_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, immutable_entity) {
+        let component_kinds = WorldRefType::<Entity>::component_kinds(self, immutable_entity);
+        WorldMutType::<Entity>::local_duplicate_components_of_kinds(
+            self,
+            mutable_entity,
+            immutable_entity,
+            &component_kinds,
+        );
+    }
+
+    fn local_duplicate_components_of_kinds(
+        &mut self,
+        mutable_entity: &Entity,
+        immutable_entity: &Entity,
+        component_kinds: &[ComponentKind],
+    ) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, immutable_entity) {
+            if !component_kinds.contains(&component_kind) {
+                continue;
+            }
             let mut component_copy_opt: Option<Box<dyn Replicate>> = None;
             if let Some(component) = self.component_of_kind(immutable_entity, &component_kind) {
                 component_copy_opt = Some(component.copy_to_box());
@@ -158,30 +184,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         self.world.despawn(*entity);
     }
 
-    fn component_kinds(&mut self, entity: &Entity) -> Vec<ComponentKind> {
-        let mut kinds = Vec::new();
-
-        let world_data = world_data(&self.world);
-
-        let components = self.world.components();
-
-        for component_id in self.world.entity(*entity).archetype().components() {
-            let component_info = components
-                .get_info(component_id)
-                .expect("Components need info to instantiate");
-            let type_id = component_info
-                .type_id()
-                .expect("Components need type_id to instantiate");
-            let component_kind = ComponentKind::from(type_id);
-
-            if world_data.has_kind(&component_kind) {
-                kinds.push(component_kind);
-            }
-        }
-
-        kinds
-    }
-
     fn component_mut<R: ReplicatedComponent>(&mut self, entity: &Entity) -> Option<ReplicaMutWrapper<R>> {
         if let Some(bevy_mut) = self.world.get_mut::<R>(*entity) {
             let wrapper = ComponentMut(bevy_mut);
@@ -243,7 +245,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
     }
 
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, old_entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, old_entity) {
             WorldMutType::<Entity>::mirror_components(
                 self,
                 new_entity,
@@ -309,7 +311,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         global_world_manager: &dyn GlobalWorldManagerType<Entity>,
         entity: &Entity,
     ) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
             WorldMutType::<Entity>::component_publish(
                 self,
                 global_world_manager,
@@ -335,7 +337,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
     }
 
     fn entity_unpublish(&mut self, entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
             WorldMutType::<Entity>::component_unpublish(self, entity, &component_kind);
         }
     }
@@ -355,7 +357,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         global_world_manager: &dyn GlobalWorldManagerType<Entity>,
         entity: &Entity,
     ) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
             WorldMutType::<Entity>::component_enable_delegation(
                 self,
                 global_world_manager,
@@ -381,7 +383,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
     }
 
     fn entity_disable_delegation(&mut self, entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
             WorldMutType::<Entity>::component_disable_delegation(self, entity, &component_kind);
         }
     }
@@ -418,6 +420,30 @@ fn has_component_of_kind(world: &World, entity: &Entity, component_kind: &Compon
         .contains_type_id(<ComponentKind as Into<TypeId>>::into(*component_kind));
 }
 
+fn component_kinds(world: &World, entity: &Entity) -> Vec<ComponentKind> {
+    let mut kinds = Vec::new();
+
+    let world_data = world_data(world);
+
+    let components = world.components();
+
+    for component_id in world.entity(*entity).archetype().components() {
+        let component_info = components
+            .get_info(component_id)
+            .expect("Components need info to instantiate");
+        let type_id = component_info
+            .type_id()
+            .expect("Components need type_id to instantiate");
+        let component_kind = ComponentKind::from(type_id);
+
+        if world_data.has_kind(&component_kind) {
+            kinds.push(component_kind);
+        }
+    }
+
+    kinds
+}
+
 fn component<'a, R: ReplicatedComponent>(
     world: &'a World,
     entity: &Entity,
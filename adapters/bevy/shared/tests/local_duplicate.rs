@@ -0,0 +1,108 @@
+use bevy_ecs::{component::Component, world::World};
+
+use naia_bevy_shared::{
+    ComponentKind, HostOwned, Property, Protocol, Replicate, WorldMutType, WorldProxyMut,
+};
+
+#[derive(Component, Replicate)]
+pub struct Position {
+    pub x: Property<u16>,
+    pub y: Property<u16>,
+}
+
+impl Position {
+    pub fn new(x: u16, y: u16) -> Self {
+        Self::new_complete(x, y)
+    }
+}
+
+#[derive(Component, Replicate)]
+pub struct Health {
+    pub value: Property<u8>,
+}
+
+impl Health {
+    pub fn new(value: u8) -> Self {
+        Self::new_complete(value)
+    }
+}
+
+struct Marker;
+
+fn setup_world() -> World {
+    let mut protocol = Protocol::builder();
+    protocol
+        .add_component::<Position>()
+        .add_component::<Health>();
+    let world_data = protocol.take_world_data();
+
+    let mut world = World::new();
+    world.insert_resource(world_data);
+    world
+}
+
+#[test]
+fn duplicates_only_given_component_kinds() {
+    let mut world = setup_world();
+    let old_entity = world
+        .spawn((
+            Position::new(3, 4),
+            Health::new(10),
+            HostOwned::new::<Marker>(),
+        ))
+        .id();
+    let new_entity = world.spawn_empty().id();
+
+    WorldMutType::local_duplicate_components_of_kinds(
+        &mut world.proxy_mut(),
+        &new_entity,
+        &old_entity,
+        &[ComponentKind::of::<Position>()],
+    );
+
+    let position = world.get::<Position>(new_entity).unwrap();
+    assert_eq!(*position.x, 3);
+    assert_eq!(*position.y, 4);
+    assert!(world.get::<Health>(new_entity).is_none());
+    assert!(world.get::<HostOwned>(new_entity).is_none());
+}
+
+#[test]
+fn empty_filter_duplicates_nothing() {
+    let mut world = setup_world();
+    let old_entity = world.spawn((Position::new(3, 4), Health::new(10))).id();
+    let new_entity = world.spawn_empty().id();
+
+    WorldMutType::local_duplicate_components_of_kinds(
+        &mut world.proxy_mut(),
+        &new_entity,
+        &old_entity,
+        &[],
+    );
+
+    assert!(world.get::<Position>(new_entity).is_none());
+    assert!(world.get::<Health>(new_entity).is_none());
+}
+
+#[test]
+fn duplicates_many_entities_in_order() {
+    let mut world = setup_world();
+    let old_entities = [
+        world.spawn(Health::new(1)).id(),
+        world.spawn(Health::new(2)).id(),
+        world.spawn(Health::new(3)).id(),
+    ];
+    let new_entities: Vec<_> = old_entities
+        .iter()
+        .map(|_| world.spawn_empty().id())
+        .collect();
+
+    let mut world_proxy = world.proxy_mut();
+    for (new_entity, old_entity) in new_entities.iter().zip(old_entities.iter()) {
+        WorldMutType::local_duplicate_components(&mut world_proxy, new_entity, old_entity);
+    }
+
+    for (new_entity, value) in new_entities.iter().zip([1, 2, 3]) {
+        assert_eq!(*world.get::<Health>(*new_entity).unwrap().value, value);
+    }
+}
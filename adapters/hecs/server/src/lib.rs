@@ -1,5 +1,7 @@
 pub use naia_hecs_shared::{Protocol, Random, WorldProxy, WorldProxyMut, WorldWrapper};
 pub use naia_server::{
-    transport, AuthEvent, ConnectEvent, DisconnectEvent, ErrorEvent, RoomKey, Server, ServerConfig,
-    TickEvent,
+    transport, AuthEvent, ConnectEvent, ConnectTokenConfig, DisconnectEvent, ErrorEvent, RoomKey,
+    Server, ServerConfig, TickEvent,
 };
+#[cfg(feature = "connect_tokens")]
+pub use naia_server::{ConnectToken, ConnectTokenError};
@@ -1,12 +1,13 @@
 pub use naia_shared::{
-    BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelMode, ComponentFieldUpdate,
-    ComponentKind, ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask, EntityAuthAccessor,
-    EntityProperty, GlobalEntity, HostEntity, LinkConditionerConfig,
+    BitReader, BitWrite, BitWriter, ChangeThreshold, Channel, ChannelDirection, ChannelMode,
+    ComponentFieldUpdate, ComponentKind, ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask,
+    EntityAuthAccessor, EntityProperty, GlobalEntity, HostEntity, LinkConditionerConfig,
     LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, MessageBuilder,
-    MessageContainer, MessageHecs as Message, MessageKind, MessageKinds, Named, OwnedBitReader,
-    OwnedLocalEntity, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
-    RemoteEntity, ReplicaDynMut, ReplicaDynRef, ReplicateBuilder, ReplicateHecs as Replicate,
-    SerdeErr, SerdeHecs as Serde, TickBufferSettings, UnsignedInteger,
+    MessageContainer, MessageHecs as Message, MessageKind, MessageKinds, Named,
+    OrderedUnreliableSettings, OwnedBitReader, OwnedLocalEntity, Property, PropertyMutate,
+    PropertyMutator, Random, ReliableSettings, RemoteEntity, ReplicaDynMut, ReplicaDynRef,
+    ReplicateBuilder, ReplicateHecs as Replicate, SerdeErr, SerdeHecs as Serde, TickBufferSettings,
+    UnsignedInteger,
 };
 
 mod component_access;
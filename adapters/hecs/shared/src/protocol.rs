@@ -82,6 +82,11 @@ impl Protocol {
         self
     }
 
+    pub fn name_channel<C: Channel>(&mut self, name: &str) -> &mut Self {
+        self.inner.name_channel::<C>(name);
+        self
+    }
+
     pub fn add_message<M: Message>(&mut self) -> &mut Self {
         self.inner.add_message::<M>();
         self
@@ -96,6 +101,15 @@ impl Protocol {
         self
     }
 
+    pub fn add_server_authoritative_component<C: Replicate>(&mut self) -> &mut Self {
+        self.inner.add_server_authoritative_component::<C>();
+        self.world_data
+            .as_mut()
+            .expect("shouldn't happen")
+            .put_kind::<C>(&ComponentKind::of::<C>());
+        self
+    }
+
     pub fn lock(&mut self) {
         self.inner.lock();
     }
@@ -68,6 +68,10 @@ impl<'w, 'd> WorldRefType<Entity> for WorldRef<'w, 'd> {
         has_component_of_kind(self.world, self.world_data, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component::<R>(self.world, entity)
     }
@@ -114,6 +118,10 @@ impl<'w, 'd> WorldRefType<Entity> for WorldMut<'w, 'd> {
         has_component_of_kind(self.world, self.world_data, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component::<R>(self.world, entity)
     }
@@ -141,7 +149,25 @@ impl<'w, 'd> WorldMutType<Entity> for WorldMut<'w, 'd> {
     }
 
     fn local_duplicate_components(&mut self, mutable_entity: &Entity, immutable_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, immutable_entity) {
+        let component_kinds = WorldRefType::<Entity>::component_kinds(self, immutable_entity);
+        WorldMutType::<Entity>::local_duplicate_components_of_kinds(
+            self,
+            mutable_entity,
+            immutable_entity,
+            &component_kinds,
+        );
+    }
+
+    fn local_duplicate_components_of_kinds(
+        &mut self,
+        mutable_entity: &Entity,
+        immutable_entity: &Entity,
+        component_kinds: &[ComponentKind],
+    ) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, immutable_entity) {
+            if !component_kinds.contains(&component_kind) {
+                continue;
+            }
             let mut component_copy_opt: Option<Box<dyn Replicate>> = None;
             if let Some(component) = self.component_of_kind(immutable_entity, &component_kind) {
                 component_copy_opt = Some(component.copy_to_box());
@@ -158,18 +184,6 @@ impl<'w, 'd> WorldMutType<Entity> for WorldMut<'w, 'd> {
             .expect("error despawning Entity");
     }
 
-    fn component_kinds(&mut self, entity: &Entity) -> Vec<ComponentKind> {
-        let mut kinds = Vec::new();
-
-        if let Ok(entity_ref) = self.world.entity(*entity) {
-            for component_type in entity_ref.component_types() {
-                kinds.push(ComponentKind::from(component_type));
-            }
-        }
-
-        kinds
-    }
-
     fn component_mut<R: Replicate>(&mut self, entity: &Entity) -> Option<ReplicaMutWrapper<R>> {
         if let Ok(hecs_mut) = self.world.get::<&mut R>(*entity) {
             let wrapper = ComponentMut(hecs_mut);
@@ -223,7 +237,7 @@ impl<'w, 'd> WorldMutType<Entity> for WorldMut<'w, 'd> {
     }
 
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, old_entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, old_entity) {
             WorldMutType::<Entity>::mirror_components(
                 self,
                 new_entity,
@@ -354,6 +368,18 @@ fn has_component_of_kind(
     return component_of_kind(world, world_data, entity, component_kind).is_some();
 }
 
+fn component_kinds(world: &World, entity: &Entity) -> Vec<ComponentKind> {
+    let mut kinds = Vec::new();
+
+    if let Ok(entity_ref) = world.entity(*entity) {
+        for component_type in entity_ref.component_types() {
+            kinds.push(ComponentKind::from(component_type));
+        }
+    }
+
+    kinds
+}
+
 fn component<'a, R: Replicate>(
     world: &'a World,
     entity: &Entity,
@@ -69,6 +69,10 @@ impl WorldRefType<Entity> for &WorldWrapper {
         has_component_of_kind(&self.inner, &self.data, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(&self.inner, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component::<R>(&self.inner, entity)
     }
@@ -99,6 +103,10 @@ impl WorldRefType<Entity> for &mut WorldWrapper {
         has_component_of_kind(&self.inner, &self.data, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(&self.inner, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component::<R>(&self.inner, entity)
     }
@@ -126,7 +134,25 @@ impl WorldMutType<Entity> for &mut WorldWrapper {
     }
 
     fn local_duplicate_components(&mut self, mutable_entity: &Entity, immutable_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, immutable_entity) {
+        let component_kinds = WorldRefType::<Entity>::component_kinds(self, immutable_entity);
+        WorldMutType::<Entity>::local_duplicate_components_of_kinds(
+            self,
+            mutable_entity,
+            immutable_entity,
+            &component_kinds,
+        );
+    }
+
+    fn local_duplicate_components_of_kinds(
+        &mut self,
+        mutable_entity: &Entity,
+        immutable_entity: &Entity,
+        component_kinds: &[ComponentKind],
+    ) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, immutable_entity) {
+            if !component_kinds.contains(&component_kind) {
+                continue;
+            }
             let mut component_copy_opt: Option<Box<dyn Replicate>> = None;
             if let Some(component) = self.component_of_kind(immutable_entity, &component_kind) {
                 component_copy_opt = Some(component.copy_to_box());
@@ -143,18 +169,6 @@ impl WorldMutType<Entity> for &mut WorldWrapper {
             .expect("error despawning Entity");
     }
 
-    fn component_kinds(&mut self, entity: &Entity) -> Vec<ComponentKind> {
-        let mut kinds = Vec::new();
-
-        if let Ok(entity_ref) = self.inner.entity(*entity) {
-            for component_type in entity_ref.component_types() {
-                kinds.push(ComponentKind::from(component_type));
-            }
-        }
-
-        kinds
-    }
-
     fn component_mut<R: Replicate>(&mut self, entity: &Entity) -> Option<ReplicaMutWrapper<R>> {
         if let Ok(hecs_mut) = self.inner.get::<&mut R>(*entity) {
             let wrapper = ComponentMut(hecs_mut);
@@ -208,7 +222,7 @@ impl WorldMutType<Entity> for &mut WorldWrapper {
     }
 
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, old_entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, old_entity) {
             WorldMutType::<Entity>::mirror_components(
                 self,
                 new_entity,
@@ -340,6 +354,18 @@ fn has_component_of_kind(
     return component_of_kind(world, world_data, entity, component_kind).is_some();
 }
 
+fn component_kinds(world: &World, entity: &Entity) -> Vec<ComponentKind> {
+    let mut kinds = Vec::new();
+
+    if let Ok(entity_ref) = world.entity(*entity) {
+        for component_type in entity_ref.component_types() {
+            kinds.push(ComponentKind::from(component_type));
+        }
+    }
+
+    kinds
+}
+
 fn component<'a, R: Replicate>(
     world: &'a World,
     entity: &Entity,
@@ -1,8 +1,24 @@
-use std::{any::Any, collections::VecDeque, hash::Hash, net::SocketAddr};
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    net::SocketAddr,
+    time::Duration,
+};
 
 use log::{info, warn};
-use naia_client_socket::IdentityReceiverResult;
-use naia_shared::{BitWriter, Channel, ChannelKind, ComponentKind, EntityAndGlobalEntityConverter, EntityAndLocalEntityConverter, EntityAuthStatus, EntityConverterMut, EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent, FakeEntityConverter, GameInstant, GlobalEntity, GlobalRequestId, GlobalResponseId, GlobalWorldManagerType, Instant, Message, MessageContainer, PacketType, Protocol, RemoteEntity, Replicate, ReplicatedComponent, Request, Response, ResponseReceiveKey, ResponseSendKey, Serde, SharedGlobalWorldManager, SocketConfig, StandardHeader, SystemChannel, Tick, WorldMutType, WorldRefType};
+use naia_shared::{
+    handshake::HandshakeHeader, BitWriter, Channel, ChannelKind, ChannelMode, ComponentKind,
+    ConnectionStats, DisconnectReason, EntityAndGlobalEntityConverter,
+    EntityAndLocalEntityConverter, EntityAuthStatus, EntityConverterMut, EntityDespawnHook,
+    EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent, FakeEntityConverter,
+    FileTransferReceiver, GameInstant, GlobalEntity, GlobalRequestId, GlobalResponseId,
+    GlobalWorldManagerType, IdempotentMessage, IdentityReceiverResult, IdentityToken, Instant,
+    Message, MessageContainer, PacketType, PendingRequest, Protocol, RemoteEntity, Replicate,
+    ReplicatedComponent, Request, Response, ResponseReceiveError, ResponseReceiveKey,
+    ResponseSendKey, Serde, SharedGlobalWorldManager, SocketConfig, StandardHeader, SystemChannel,
+    Tick, WaitlistEntry, WaitlistStats, WorldMutType, WorldRefType,
+};
 
 use super::{client_config::ClientConfig, error::NaiaClientError, events::Events};
 use crate::{
@@ -10,7 +26,10 @@ use crate::{
     handshake::{HandshakeManager, HandshakeResult, Handshaker},
     transport::Socket,
     world::{
-        entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
+        authority_debouncer::{AuthorityChange, AuthorityDebouncer},
+        entity_mut::EntityMut,
+        entity_owner::EntityOwner,
+        entity_ref::EntityRef,
         global_world_manager::GlobalWorldManager,
     },
     ReplicationConfig,
@@ -25,13 +44,20 @@ pub struct Client<E: Copy + Eq + Hash + Send + Sync> {
     // Connection
     auth_message: Option<Vec<u8>>,
     auth_headers: Option<Vec<(String, String)>>,
+    identity_token: Option<IdentityToken>,
     io: Io,
     server_connection: Option<Connection<E>>,
     handshake_manager: Box<dyn Handshaker>,
+    entity_despawn_hook: Option<Box<dyn EntityDespawnHook<E>>>,
     manual_disconnect: bool,
-    waitlist_messages: VecDeque<(ChannelKind, Box<dyn Message>)>,
+    // the reason the Server gave for closing the most recent connection
+    disconnect_reason: Option<DisconnectReason>,
+    waitlist_messages: VecDeque<(ChannelKind, Option<u64>, Box<dyn Message>)>,
+    // kept across connections, so that interrupted file transfers can resume
+    file_transfer_receiver: FileTransferReceiver,
     // World
     global_world_manager: GlobalWorldManager<E>,
+    authority_debouncer: AuthorityDebouncer<E>,
     // Events
     incoming_events: Events<E>,
     // Hacky
@@ -59,16 +85,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             // Connection
             auth_message: None,
             auth_headers: None,
+            identity_token: None,
             io: Io::new(
                 &client_config.connection.bandwidth_measure_duration,
                 &compression_config,
             ),
             server_connection: None,
             handshake_manager: Box::new(handshake_manager),
+            entity_despawn_hook: None,
             manual_disconnect: false,
+            disconnect_reason: None,
             waitlist_messages: VecDeque::new(),
+            file_transfer_receiver: FileTransferReceiver::new(),
             // World
             global_world_manager: GlobalWorldManager::new(),
+            authority_debouncer: AuthorityDebouncer::new(client_config.authority_debounce),
             // Events
             incoming_events: Events::new(),
             // Hacky
@@ -89,16 +120,34 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.auth_message = Some(auth_bytes.to_vec());
     }
 
+    /// Set a connect token, signed by the game's backend, to present to the
+    /// Server in place of an auth object. The Server validates it itself when
+    /// configured with the backend's key
+    pub fn connect_token(&mut self, token: Vec<u8>) {
+        self.auth_message = Some(token);
+    }
+
     pub fn auth_headers(&mut self, headers: Vec<(String, String)>) {
         self.auth_headers = Some(headers);
     }
 
-    /// Connect to the given server address
-    pub fn connect<S: Into<Box<dyn Socket>>>(&mut self, socket: S) {
+    /// Set a hook which is run whenever a replicated Entity is about to be
+    /// despawned, because the Server despawned it or it left this Client's
+    /// scope. The hook is given the Entity and its Components' final values,
+    /// before any of them are removed from the World
+    pub fn set_entity_despawn_hook<H: EntityDespawnHook<E> + 'static>(&mut self, hook: H) {
+        self.entity_despawn_hook = Some(Box::new(hook));
+    }
+
+    /// Connect to the given server address. Returns an error if a connection
+    /// has already been initiated
+    pub fn connect<S: Into<Box<dyn Socket>>>(&mut self, socket: S) -> Result<(), NaiaClientError> {
         if !self.is_disconnected() {
-            panic!("Client has already initiated a connection, cannot initiate a new one. TIP: Check client.is_disconnected() before calling client.connect()");
+            return Err(NaiaClientError::AlreadyConnected);
         }
 
+        self.disconnect_reason = None;
+
         if let Some(auth_bytes) = &self.auth_message {
             if let Some(auth_headers) = &self.auth_headers {
                 // connect with auth & headers
@@ -127,6 +176,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 self.io.load(id_receiver, packet_sender, packet_receiver);
             }
         }
+
+        Ok(())
     }
 
     /// Returns client's current connection status
@@ -161,7 +212,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
     /// Returns whether or not the client is disconnecting
     fn is_disconnecting(&self) -> bool {
         if let Some(connection) = &self.server_connection {
-            connection.base.should_drop() || self.manual_disconnect
+            connection.base.should_drop()
+                || self.manual_disconnect
+                || self.disconnect_reason.is_some()
         } else {
             false
         }
@@ -172,10 +225,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         !self.io.is_loaded()
     }
 
-    /// Disconnect from Server
-    pub fn disconnect(&mut self) {
+    /// Disconnect from Server. Returns an error if the Client is not
+    /// connected
+    pub fn disconnect(&mut self) -> Result<(), NaiaClientError> {
         if !self.is_connected() {
-            panic!("Trying to disconnect Client which is not connected yet!")
+            return Err(NaiaClientError::NotConnected);
         }
 
         for _ in 0..10 {
@@ -187,6 +241,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
 
         self.manual_disconnect = true;
+
+        Ok(())
+    }
+
+    /// Returns the reason the Server gave for closing the most recent
+    /// connection, if it closed it. Check `DisconnectReason::should_reconnect()`
+    /// before reconnecting after a `DisconnectEvent`
+    pub fn disconnect_reason(&self) -> Option<&DisconnectReason> {
+        self.disconnect_reason.as_ref()
+    }
+
+    /// Discards the bytes received so far of a file the Server was sending.
+    /// These are otherwise kept after a disconnect, so that the Server can
+    /// resume the transfer once the Client reconnects
+    pub fn discard_file_transfer(&mut self, name: &str) {
+        self.file_transfer_receiver.discard(name);
     }
 
     /// Returns socket config
@@ -206,6 +276,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
         self.send_queued_auth_release_messages();
 
+        self.apply_debounced_authority_changes();
+
         let mut response_events = None;
 
         // all other operations
@@ -237,6 +309,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     &mut world,
                     &now,
                     &mut self.incoming_events,
+                    self.entity_despawn_hook.as_deref(),
+                    &mut self.file_transfer_receiver,
                 ));
 
                 let mut index_tick = prev_receiving_tick.wrapping_add(1);
@@ -272,6 +346,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     &world,
                     &self.global_world_manager,
                 );
+                for (channel_kind, message) in
+                    connection.base.message_manager.take_expired_messages()
+                {
+                    self.incoming_events
+                        .push_expired_message(&channel_kind, message);
+                }
+                for request_id in connection.base.message_manager.take_timed_out_requests() {
+                    connection
+                        .global_request_manager
+                        .time_out_request(&request_id);
+                }
 
                 // insert tick events in total range
                 let mut index_tick = prev_sending_tick.wrapping_add(1);
@@ -304,16 +389,92 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
     // Messages
 
-    /// Queues up an Message to be sent to the Server
-    pub fn send_message<C: Channel, M: Message>(&mut self, message: &M) {
+    /// Queues up an Message to be sent to the Server. Returns an error if the
+    /// Channel's send buffer is full, see `ReliableSettings::message_capacity`
+    pub fn send_message<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
+        let cloned_message = M::clone_box(message);
+        self.send_message_inner(&ChannelKind::of::<C>(), None, cloned_message)
+    }
+
+    /// Queues up an Message to be sent to the Server, tagged with an
+    /// application-defined key. The Server drops any Message carrying a key
+    /// it has recently processed from this Client, so a logical command can
+    /// be safely retried with the same key. See
+    /// `ServerConfig::idempotency_key_capacity` for how many keys are kept
+    pub fn send_message_with_key<C: Channel, M: Message>(
+        &mut self,
+        key: u64,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
         let cloned_message = M::clone_box(message);
-        self.send_message_inner(&ChannelKind::of::<C>(), cloned_message);
+        self.send_message_inner(&ChannelKind::of::<C>(), Some(key), cloned_message)
     }
 
-    fn send_message_inner(&mut self, channel_kind: &ChannelKind, message_box: Box<dyn Message>) {
+    /// Queues up an Message to be sent to the Server, which stops being
+    /// retransmitted if the Server has not acknowledged it within `ttl`. It
+    /// is then returned through `MessageExpiredEvent`. The Channel must be
+    /// `ChannelMode::ExpiringReliable`. Returns an error if the Client is not
+    /// connected
+    pub fn send_message_with_ttl<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+        ttl: Duration,
+    ) -> Result<(), NaiaClientError> {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+        if !channel_settings.can_send_to_server() {
+            panic!(
+                "Cannot send message to Server on Channel `{}`",
+                self.protocol.channel_kinds.kind_to_name(&channel_kind)
+            );
+        }
+        if !matches!(channel_settings.mode, ChannelMode::ExpiringReliable(_)) {
+            return Err(NaiaClientError::from_message(
+                "Cannot send a Message with a time to live over a Channel which is not ExpiringReliable",
+            ));
+        }
+
+        let Some(connection) = self.server_connection.as_mut() else {
+            return Err(NaiaClientError::NotConnected);
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        connection
+            .base
+            .message_manager
+            .send_message_with_ttl(
+                &self.protocol.message_kinds,
+                &mut converter,
+                &channel_kind,
+                message,
+                ttl,
+            )
+            .map_err(NaiaClientError::from)
+    }
+
+    // SystemChannel is unbounded, so queueing on it never fails
+    fn send_system_message(&mut self, message: &EntityEventMessage) {
+        let _ = self.send_message::<SystemChannel, EntityEventMessage>(message);
+    }
+
+    fn send_message_inner(
+        &mut self,
+        channel_kind: &ChannelKind,
+        key_opt: Option<u64>,
+        message_box: Box<dyn Message>,
+    ) -> Result<(), NaiaClientError> {
         let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
         if !channel_settings.can_send_to_server() {
-            panic!("Cannot send message to Server on this Channel");
+            panic!(
+                "Cannot send message to Server on Channel `{}`",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
         }
 
         if channel_settings.tick_buffered() {
@@ -325,17 +486,26 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 &self.global_world_manager,
                 &mut connection.base.local_world_manager,
             );
-            let message = MessageContainer::from_write(message_box, &mut converter);
-            connection.base.message_manager.send_message(
-                &self.protocol.message_kinds,
-                &mut converter,
-                channel_kind,
-                message,
-            );
+            let message = match key_opt {
+                Some(key) => IdempotentMessage::wrap(&mut converter, key, message_box),
+                None => MessageContainer::from_write(message_box, &mut converter),
+            };
+            connection
+                .base
+                .message_manager
+                .send_message(
+                    &self.protocol.message_kinds,
+                    &mut converter,
+                    channel_kind,
+                    message,
+                )
+                .map_err(NaiaClientError::from)?;
         } else {
             self.waitlist_messages
-                .push_back((channel_kind.clone(), message_box));
+                .push_back((*channel_kind, key_opt, message_box));
         }
+
+        Ok(())
     }
 
     //
@@ -345,7 +515,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
     ) -> Result<ResponseReceiveKey<Q::Response>, NaiaClientError> {
         let cloned_request = Q::clone_box(request);
         // let response_type_id = TypeId::of::<Q::Response>();
-        let id = self.send_request_inner(&ChannelKind::of::<C>(), cloned_request)?;
+        let id = self.send_request_inner(&ChannelKind::of::<C>(), cloned_request, None)?;
+        Ok(ResponseReceiveKey::new(id))
+    }
+
+    /// Sends a Request to the Server which gives up on a Response after
+    /// `timeout`. `receive_response()` then yields
+    /// `ResponseReceiveError::Timeout`, and any Response which arrives later
+    /// is discarded. The Request stops being retransmitted if the channel is
+    /// ExpiringReliable
+    pub fn send_request_with_timeout<C: Channel, Q: Request>(
+        &mut self,
+        request: &Q,
+        timeout: Duration,
+    ) -> Result<ResponseReceiveKey<Q::Response>, NaiaClientError> {
+        let cloned_request = Q::clone_box(request);
+        let id = self.send_request_inner(&ChannelKind::of::<C>(), cloned_request, Some(timeout))?;
         Ok(ResponseReceiveKey::new(id))
     }
 
@@ -354,18 +539,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         channel_kind: &ChannelKind,
         // response_type_id: TypeId,
         request_box: Box<dyn Message>,
+        timeout: Option<Duration>,
     ) -> Result<GlobalRequestId, NaiaClientError> {
         let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
 
         if !channel_settings.can_request_and_respond() {
-            std::panic!("Requests can only be sent over Bidirectional, Reliable Channels");
+            std::panic!(
+                "Requests can only be sent over Bidirectional, Reliable Channels, which `{}` is not",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
         }
 
         let Some(connection) = &mut self.server_connection else {
-            warn!("currently not connected to server");
-            return Err(NaiaClientError::Message(
-                "currently not connected to server".to_string(),
-            ));
+            return Err(NaiaClientError::NotConnected);
         };
         let mut converter = EntityConverterMut::new(
             &self.global_world_manager,
@@ -374,23 +560,43 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
         let request_id = connection.global_request_manager.create_request_id();
         let message = MessageContainer::from_write(request_box, &mut converter);
-        connection.base.message_manager.send_request(
-            &self.protocol.message_kinds,
-            &mut converter,
-            channel_kind,
-            request_id,
-            message,
-        );
+        connection
+            .base
+            .message_manager
+            .send_request(
+                &self.protocol.message_kinds,
+                &mut converter,
+                channel_kind,
+                request_id,
+                message,
+                timeout,
+            )
+            .map_err(NaiaClientError::from)?;
 
         return Ok(request_id);
     }
 
-    /// Sends a Response for a given Request. Returns whether or not was successful.
+    /// Stops waiting for a Response to a Request, freeing its id. Any
+    /// Response which arrives later is discarded, and the Request stops being
+    /// retransmitted if the channel is ExpiringReliable. Returns whether the
+    /// Request was still awaiting a Response
+    pub fn cancel_request(&mut self, request_id: GlobalRequestId) -> bool {
+        let Some(connection) = &mut self.server_connection else {
+            return false;
+        };
+        connection.base.message_manager.cancel_request(&request_id);
+        connection
+            .global_request_manager
+            .cancel_request(&request_id)
+    }
+
+    /// Sends a Response for a given Request. Returns an error if the Request
+    /// is unknown or the Response could not be queued
     pub fn send_response<S: Response>(
         &mut self,
         response_key: &ResponseSendKey<S>,
         response: &S,
-    ) -> bool {
+    ) -> Result<(), NaiaClientError> {
         let response_id = response_key.response_id();
 
         let cloned_response = S::clone_box(response);
@@ -398,20 +604,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.send_response_inner(&response_id, cloned_response)
     }
 
-    // returns whether was successful
     fn send_response_inner(
         &mut self,
         response_id: &GlobalResponseId,
         response_box: Box<dyn Message>,
-    ) -> bool {
+    ) -> Result<(), NaiaClientError> {
         let Some(connection) = &mut self.server_connection else {
-            return false;
+            return Err(NaiaClientError::NotConnected);
         };
         let Some((channel_kind, local_response_id)) = connection
             .global_response_manager
             .destroy_response_id(response_id)
         else {
-            return false;
+            return Err(NaiaClientError::UnknownResponse);
         };
         let mut converter = EntityConverterMut::new(
             &self.global_world_manager,
@@ -419,49 +624,114 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         );
 
         let response = MessageContainer::from_write(response_box, &mut converter);
-        connection.base.message_manager.send_response(
-            &self.protocol.message_kinds,
-            &mut converter,
-            &channel_kind,
-            local_response_id,
-            response,
-        );
-        return true;
+        connection
+            .base
+            .message_manager
+            .send_response(
+                &self.protocol.message_kinds,
+                &mut converter,
+                &channel_kind,
+                local_response_id,
+                response,
+            )
+            .map_err(NaiaClientError::from)
     }
 
+    /// Returns the Response to a Request once it has arrived, or
+    /// `ResponseReceiveError::Timeout` if the Request was sent with a timeout
+    /// which passed first. Returns None while the Request is still waiting
     pub fn receive_response<S: Response>(
         &mut self,
         response_key: &ResponseReceiveKey<S>,
-    ) -> Option<S> {
+    ) -> Option<Result<S, ResponseReceiveError>> {
         let Some(connection) = &mut self.server_connection else {
             return None;
         };
         let request_id = response_key.request_id();
-        let Some(container) = connection
+        let result = connection
             .global_request_manager
-            .destroy_request_id(&request_id)
-        else {
-            return None;
+            .destroy_request_id(&request_id)?;
+        let container = match result {
+            Ok(container) => container,
+            Err(err) => return Some(Err(err)),
         };
         let response: S = Box::<dyn Any + 'static>::downcast::<S>(container.to_boxed_any())
             .ok()
             .map(|boxed_s| *boxed_s)
             .unwrap();
-        return Some(response);
+        return Some(Ok(response));
+    }
+
+    /// Returns the Requests sent to the Server which are still awaiting a
+    /// Response, i.e. for displaying in a debug overlay
+    pub fn pending_requests(&self) -> Vec<PendingRequest> {
+        let Some(connection) = &self.server_connection else {
+            return Vec::new();
+        };
+        connection.base.message_manager.pending_requests()
+    }
+
+    /// Returns the received messages & component data which are being held
+    /// until the Entities they reference come into scope, along with which
+    /// Entities each is still waiting on, i.e. for troubleshooting a message
+    /// which never seems to arrive
+    pub fn local_entities_awaiting_resolution(&self) -> Vec<WaitlistEntry> {
+        let Some(connection) = &self.server_connection else {
+            return Vec::new();
+        };
+        connection
+            .base
+            .remote_world_manager
+            .entity_waitlist
+            .waiting_entries()
+    }
+
+    /// Gets the network conditions measured over the connection to the
+    /// Server, or None if not connected
+    pub fn connection_stats(&mut self) -> Option<ConnectionStats> {
+        let connection = self.server_connection.as_ref()?;
+        let (received, sent) = self.io.byte_counters();
+        Some(ConnectionStats {
+            rtt: connection.time_manager.rtt(),
+            jitter: connection.time_manager.jitter(),
+            loss: connection.base.packet_loss(),
+            bytes_in: received.total_bytes(),
+            bytes_out: sent.total_bytes(),
+            bytes_in_per_second: received.bytes_per_second(),
+            bytes_out_per_second: sent.bytes_per_second(),
+            queued_messages: connection.base.message_manager.queued_message_count(),
+            update_backlog: connection.base.host_world_manager.update_backlog(),
+        })
+    }
+
+    /// Gets how many received items are held while waiting on Entities to
+    /// come into scope, and how many have been dropped because the waitlist
+    /// was full. See `ConnectionConfig::entity_waitlist_capacity`
+    pub fn entity_waitlist_stats(&self) -> Option<WaitlistStats> {
+        let connection = self.server_connection.as_ref()?;
+        Some(connection.base.remote_world_manager.entity_waitlist.stats())
     }
     //
 
     fn on_connect(&mut self) {
         // send queued messages
         let messages = std::mem::take(&mut self.waitlist_messages);
-        for (channel_kind, message_box) in messages {
-            self.send_message_inner(&channel_kind, message_box);
+        for (channel_kind, key_opt, message_box) in messages {
+            if let Err(err) = self.send_message_inner(&channel_kind, key_opt, message_box) {
+                warn!("Unable to send queued message: {}", err);
+            }
         }
     }
 
-    pub fn send_tick_buffer_message<C: Channel, M: Message>(&mut self, tick: &Tick, message: &M) {
+    /// Queues up a Message to be sent to the Server, to be processed on the
+    /// given Tick. Returns an error if the Client is not connected
+    pub fn send_tick_buffer_message<C: Channel, M: Message>(
+        &mut self,
+        tick: &Tick,
+        message: &M,
+    ) -> Result<(), NaiaClientError> {
         let cloned_message = M::clone_box(message);
-        self.send_tick_buffer_message_inner(tick, &ChannelKind::of::<C>(), cloned_message);
+        self.send_tick_buffer_message_inner(tick, &ChannelKind::of::<C>(), cloned_message)
     }
 
     fn send_tick_buffer_message_inner(
@@ -469,27 +739,33 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         tick: &Tick,
         channel_kind: &ChannelKind,
         message_box: Box<dyn Message>,
-    ) {
+    ) -> Result<(), NaiaClientError> {
         let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
 
         if !channel_settings.can_send_to_server() {
-            panic!("Cannot send message to Server on this Channel");
+            panic!(
+                "Cannot send message to Server on Channel `{}`",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
         }
 
         if !channel_settings.tick_buffered() {
             panic!("Can only use `Client.send_tick_buffer_message()` on a Channel that is configured for it.");
         }
 
-        if let Some(connection) = self.server_connection.as_mut() {
-            let mut converter = EntityConverterMut::new(
-                &self.global_world_manager,
-                &mut connection.base.local_world_manager,
-            );
-            let message = MessageContainer::from_write(message_box, &mut converter);
-            connection
-                .tick_buffer
-                .send_message(tick, channel_kind, message);
-        }
+        let Some(connection) = self.server_connection.as_mut() else {
+            return Err(NaiaClientError::NotConnected);
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(message_box, &mut converter);
+        connection
+            .tick_buffer
+            .send_message(tick, channel_kind, message);
+
+        Ok(())
     }
 
     // Entities
@@ -580,6 +856,55 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         config: ReplicationConfig,
     ) {
         self.check_client_authoritative_allowed();
+        let prev_config = self.check_entity_replication_config(entity, config);
+        if prev_config == config {
+            panic!(
+                "Entity replication config is already set to {:?}. Should not set twice.",
+                config
+            );
+        }
+        self.apply_entity_replication_config(world, entity, prev_config, config);
+    }
+
+    /// Configures the replication of many Entities in one pass, i.e. when a
+    /// Room's visibility changes. Every change is checked before any is
+    /// applied, so an invalid change leaves all Entities untouched. An Entity
+    /// listed more than once takes its last config, and Entities which already
+    /// have their given config are skipped, so no redundant replication events
+    /// are sent.
+    pub fn configure_entities_replication<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        configs: &[(E, ReplicationConfig)],
+    ) {
+        self.check_client_authoritative_allowed();
+        let mut changes: Vec<(E, ReplicationConfig, ReplicationConfig)> = Vec::new();
+        let mut change_indices: HashMap<E, usize> = HashMap::new();
+        for (entity, config) in configs {
+            let prev_config = self.check_entity_replication_config(entity, *config);
+            if let Some(index) = change_indices.get(entity) {
+                changes[*index].2 = *config;
+            } else {
+                change_indices.insert(*entity, changes.len());
+                changes.push((*entity, prev_config, *config));
+            }
+        }
+
+        for (entity, prev_config, next_config) in changes {
+            if prev_config == next_config {
+                continue;
+            }
+            self.apply_entity_replication_config(world, &entity, prev_config, next_config);
+        }
+    }
+
+    // Panics if the Entity cannot be given this config, otherwise returns its
+    // current config
+    fn check_entity_replication_config(
+        &self,
+        entity: &E,
+        config: ReplicationConfig,
+    ) -> ReplicationConfig {
         if !self.global_world_manager.has_entity(entity) {
             panic!("Entity is not yet replicating. Be sure to call `enable_replication` or `spawn_entity` on the Client, before configuring replication.");
         }
@@ -592,53 +917,40 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         if !client_owned {
             panic!("Client cannot configure replication strategy of Entities it does not own.");
         }
-        let next_config = config;
         let prev_config = self
             .global_world_manager
             .entity_replication_config(entity)
             .unwrap();
-        if prev_config == config {
-            panic!(
-                "Entity replication config is already set to {:?}. Should not set twice.",
-                config
-            );
+        if prev_config == ReplicationConfig::Delegated && config != ReplicationConfig::Delegated {
+            panic!("Delegated Entities are always ultimately Server-owned. Client cannot modify.")
         }
-        match prev_config {
-            ReplicationConfig::Private => {
-                match next_config {
-                    ReplicationConfig::Private => {
-                        panic!("This should not be possible.");
-                    }
-                    ReplicationConfig::Public => {
-                        // private -> public
-                        self.publish_entity(entity, true);
-                    }
-                    ReplicationConfig::Delegated => {
-                        // private -> delegated
-                        self.publish_entity(entity, true);
-                        self.entity_enable_delegation(world, entity, true);
-                    }
-                }
+
+        prev_config
+    }
+
+    fn apply_entity_replication_config<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        prev_config: ReplicationConfig,
+        next_config: ReplicationConfig,
+    ) {
+        match (prev_config, next_config) {
+            (ReplicationConfig::Private, ReplicationConfig::Public) => {
+                self.publish_entity(entity, true);
             }
-            ReplicationConfig::Public => {
-                match next_config {
-                    ReplicationConfig::Private => {
-                        // public -> private
-                        self.unpublish_entity(entity, true);
-                    }
-                    ReplicationConfig::Public => {
-                        panic!("This should not be possible.");
-                    }
-                    ReplicationConfig::Delegated => {
-                        // public -> delegated
-                        self.entity_enable_delegation(world, entity, true);
-                    }
-                }
+            (ReplicationConfig::Private, ReplicationConfig::Delegated) => {
+                self.publish_entity(entity, true);
+                self.entity_enable_delegation(world, entity, true);
             }
-            ReplicationConfig::Delegated => {
-                panic!(
-                    "Delegated Entities are always ultimately Server-owned. Client cannot modify."
-                )
+            (ReplicationConfig::Public, ReplicationConfig::Private) => {
+                self.unpublish_entity(entity, true);
+            }
+            (ReplicationConfig::Public, ReplicationConfig::Delegated) => {
+                self.entity_enable_delegation(world, entity, true);
+            }
+            _ => {
+                panic!("This should not be possible.");
             }
         }
     }
@@ -653,7 +965,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
     /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
     pub fn entity_request_authority(&mut self, entity: &E) {
         self.check_client_authoritative_allowed();
+        if self.authority_debouncer.is_enabled() {
+            self.queue_authority_change(entity, AuthorityChange::Request);
+        } else {
+            self.apply_request_authority(entity);
+        }
+    }
 
+    fn apply_request_authority(&mut self, entity: &E) {
         // 1. Set local authority status for Entity
         let success = self.global_world_manager.entity_request_authority(entity);
         if success {
@@ -672,14 +991,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 entity,
                 new_host_entity,
             );
-            self.send_message::<SystemChannel, EntityEventMessage>(&message);
+            self.send_system_message(&message);
         }
     }
 
     /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
     pub fn entity_release_authority(&mut self, entity: &E) {
         self.check_client_authoritative_allowed();
+        if self.authority_debouncer.is_enabled() {
+            self.queue_authority_change(entity, AuthorityChange::Release);
+        } else {
+            self.apply_release_authority(entity);
+        }
+    }
 
+    fn apply_release_authority(&mut self, entity: &E) {
         // 1. Set local authority status for Entity
         let success = self.global_world_manager.entity_release_authority(entity);
         if success {
@@ -697,10 +1023,42 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
+    fn queue_authority_change(&mut self, entity: &E, change: AuthorityChange) {
+        if self
+            .global_world_manager
+            .entity_authority_status(entity)
+            .is_none()
+        {
+            panic!("Can only request or release authority for an Entity that is Delegated!");
+        }
+        self.authority_debouncer
+            .queue(entity, change, &Instant::now());
+    }
+
+    fn apply_debounced_authority_changes(&mut self) {
+        let changes = self.authority_debouncer.take_ready(&Instant::now());
+        for (entity, change) in changes {
+            // the Entity may have been despawned or undelegated in the meantime
+            if self
+                .global_world_manager
+                .entity_authority_status(&entity)
+                .is_none()
+            {
+                continue;
+            }
+            // a request for authority which is already held or pending, or a
+            // release of authority which was never granted, does nothing
+            match change {
+                AuthorityChange::Request => self.apply_request_authority(&entity),
+                AuthorityChange::Release => self.apply_release_authority(&entity),
+            }
+        }
+    }
+
     fn send_entity_release_auth_message(&mut self, entity: &E) {
         // 3. Send request to Server
         let message = EntityEventMessage::new_release_authority(&self.global_world_manager, entity);
-        self.send_message::<SystemChannel, EntityEventMessage>(&message);
+        self.send_system_message(&message);
     }
 
     // Connection
@@ -710,6 +1068,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.io.server_addr()
     }
 
+    /// Get the IdentityToken issued by the Server after authentication, if
+    /// it has been received
+    pub fn identity_token(&self) -> Option<IdentityToken> {
+        self.identity_token.clone()
+    }
+
     /// Gets the average Round Trip Time measured to the Server
     pub fn rtt(&self) -> f32 {
         self.server_connection
@@ -726,6 +1090,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             .time_manager.jitter()
     }
 
+    /// Gets the estimated number of milliseconds the Server's clock is ahead
+    /// of the Client's game clock. The game clock is aligned with the Server's
+    /// during the handshake, so this measures drift since connecting
+    pub fn clock_offset(&self) -> Option<f32> {
+        if let Some(connection) = &self.server_connection {
+            return Some(connection.time_manager.clock_offset());
+        }
+        return None;
+    }
+
+    /// Gets the current time on the Server's clock, as estimated by the Client
+    pub fn server_time(&self) -> Option<GameInstant> {
+        if let Some(connection) = &self.server_connection {
+            return Some(connection.time_manager.server_time());
+        }
+        return None;
+    }
+
     // Ticks
 
     /// Gets the current tick of the Client
@@ -778,6 +1160,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.io.incoming_bandwidth()
     }
 
+    /// Gets how much of the outgoing bandwidth budget to the Server is spent,
+    /// from 0.0 to 1.0, so that the game can send less as it fills. Returns
+    /// None if not connected, or no limit is set. See
+    /// `ConnectionConfig::outgoing_bandwidth_limit`
+    pub fn bandwidth_utilization(&self) -> Option<f32> {
+        self.server_connection
+            .as_ref()?
+            .base
+            .bandwidth_utilization()
+    }
+
     // Crate-Public methods
 
     /// Despawns the Entity, if it exists.
@@ -822,6 +1215,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             connection.base.host_world_manager.despawn_entity(entity);
         }
 
+        // Drop any authority change still waiting out its debounce window
+        self.authority_debouncer.remove(entity);
+
         // Remove from ECS Record
         self.global_world_manager.host_despawn_entity(entity);
     }
@@ -916,7 +1312,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         if client_is_origin {
             // warn!("sending publish entity message");
             let message = EntityEventMessage::new_publish(&self.global_world_manager, entity);
-            self.send_message::<SystemChannel, EntityEventMessage>(&message);
+            self.send_system_message(&message);
         } else {
             if self.global_world_manager.entity_replication_config(entity)
                 != Some(ReplicationConfig::Private)
@@ -931,7 +1327,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
     pub(crate) fn unpublish_entity(&mut self, entity: &E, client_is_origin: bool) {
         if client_is_origin {
             let message = EntityEventMessage::new_unpublish(&self.global_world_manager, entity);
-            self.send_message::<SystemChannel, EntityEventMessage>(&message);
+            self.send_system_message(&message);
         } else {
             if self.global_world_manager.entity_replication_config(entity)
                 != Some(ReplicationConfig::Public)
@@ -958,7 +1354,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             // warn!("sending enable delegation for entity message");
             let message =
                 EntityEventMessage::new_enable_delegation(&self.global_world_manager, entity);
-            self.send_message::<SystemChannel, EntityEventMessage>(&message);
+            self.send_system_message(&message);
         } else {
             self.entity_complete_delegation(world, entity);
             self.global_world_manager
@@ -1028,6 +1424,35 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 // push outgoing event
                 self.incoming_events.push_auth_grant(*entity);
             }
+            (EntityAuthStatus::Available, EntityAuthStatus::Granted) => {
+                // Server gave us Authority without a Request
+
+                let Some(connection) = &mut self.server_connection else {
+                    return;
+                };
+                // Migrate Entity from Remote -> Host connection
+                let new_host_entity = connection
+                    .base
+                    .local_world_manager
+                    .host_reserve_entity(entity);
+                let component_kinds = self.global_world_manager.component_kinds(entity).unwrap();
+                connection.base.host_world_manager.track_remote_entity(
+                    &mut connection.base.local_world_manager,
+                    entity,
+                    component_kinds,
+                );
+
+                // Let the Server know which Host Entity will carry our updates
+                let message = EntityEventMessage::new_request_authority(
+                    &self.global_world_manager,
+                    entity,
+                    new_host_entity,
+                );
+                self.send_system_message(&message);
+
+                // push outgoing event
+                self.incoming_events.push_auth_grant(*entity);
+            }
             (EntityAuthStatus::Releasing, EntityAuthStatus::Available)
             | (EntityAuthStatus::Granted, EntityAuthStatus::Available) => {
                 // Lost Authority
@@ -1048,6 +1473,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 // push outgoing event
                 self.incoming_events.push_auth_deny(*entity);
             }
+            (EntityAuthStatus::Requested, EntityAuthStatus::Denied) => {
+                // authority went to someone else before our request arrived,
+                // and the Server will deny it
+
+                // get rid of reserved host entity
+                if let Some(connection) = &mut self.server_connection {
+                    connection
+                        .base
+                        .local_world_manager
+                        .remove_reserved_host_entity(entity);
+                }
+
+                // push outgoing event
+                self.incoming_events.push_auth_deny(*entity);
+            }
             (EntityAuthStatus::Denied, EntityAuthStatus::Available) => {
                 // push outgoing event
                 self.incoming_events.push_auth_reset(*entity);
@@ -1104,11 +1544,25 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         if !self.io.is_authenticated() {
             match self.io.recv_auth() {
                 IdentityReceiverResult::Success(id_token) => {
+                    self.incoming_events.push_authentication(&id_token);
+                    self.identity_token = Some(id_token.clone());
                     self.handshake_manager.set_identity_token(id_token);
                 }
                 IdentityReceiverResult::Waiting => {
                     return;
                 }
+                IdentityReceiverResult::Rejected(reason) => {
+                    // reset connection
+                    self.io = Io::new(
+                        &self.client_config.connection.bandwidth_measure_duration,
+                        &self.protocol.compression,
+                    );
+
+                    // push out rejection
+                    self.incoming_events.push_rejection(reason);
+
+                    return;
+                }
                 IdentityReceiverResult::ErrorResponseCode(code) => {
                     // warn!("Authentication error status code: {}", code);
 
@@ -1118,14 +1572,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                         &self.protocol.compression,
                     );
 
-                    if code == 401 {
-                        // push out rejection
-                        self.incoming_events.push_rejection();
-                    } else {
-                        // push out error
-                        self.incoming_events
-                            .push_error(NaiaClientError::IdError(code));
-                    }
+                    // push out error
+                    self.incoming_events
+                        .push_error(NaiaClientError::IdError(code));
 
                     return;
                 }
@@ -1149,6 +1598,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
                             let server_addr = self.server_address_unwrapped();
                             self.incoming_events.push_connection(&server_addr);
+
+                            // leave any packets after the connect response to the new
+                            // Connection, rather than dropping them here
+                            break;
                         }
                         // Some(HandshakeResult::Rejected) => {
                         //     let server_addr = self.server_address_unwrapped();
@@ -1200,9 +1653,15 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                             // continue, these packet types are allowed when
                             // connection is established
                         }
-                        _ => {
-                            // short-circuit, do not need to handle other packet types at this
-                            // point
+                        PacketType::Handshake => {
+                            // the Server may close the connection, telling us why
+                            if let Ok(HandshakeHeader::ServerDisconnect) =
+                                HandshakeHeader::de(&mut reader)
+                            {
+                                if let Ok(reason) = DisconnectReason::de(&mut reader) {
+                                    self.disconnect_reason = Some(reason);
+                                }
+                            }
                             continue;
                         }
                     }
@@ -1326,6 +1785,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
     fn disconnect_reset_connection(&mut self) {
         self.server_connection = None;
+        self.identity_token = None;
+        self.file_transfer_receiver.disconnect();
 
         self.io = Io::new(
             &self.client_config.connection.bandwidth_measure_duration,
@@ -1340,6 +1801,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
         self.manual_disconnect = false;
         self.global_world_manager = GlobalWorldManager::new();
+        self.authority_debouncer.clear();
         self.queued_entity_auth_release_messages = Vec::new();
     }
 
@@ -1406,7 +1868,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                         &self.global_world_manager,
                         &entity,
                     );
-                    self.send_message::<SystemChannel, EntityEventMessage>(&message);
+                    self.send_system_message(&message);
                 }
                 EntityResponseEvent::EnableDelegationEntityResponse(_) => {
                     panic!("Client should never receive an EnableDelegationEntityResponse event");
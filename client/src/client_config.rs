@@ -16,7 +16,14 @@ pub struct ClientConfig {
     /// Increase this for greater accuracy of network statistics, at the cost of the handshake
     /// taking longer. Keep in mind that the network measurements affect how likely commands
     /// are able to arrive at the server before processing.
+    /// This is also the number of recent samples whose median is used to estimate the offset
+    /// between the Client's and the Server's clocks.
     pub handshake_pings: u8,
+    /// Requests & releases of an Entity's authority issued within this window
+    /// of the first one are coalesced, and only the final intended state is
+    /// sent to the Server once the window has passed. Set to zero to send
+    /// every request & release immediately
+    pub authority_debounce: Duration,
 }
 
 impl Default for ClientConfig {
@@ -26,6 +33,7 @@ impl Default for ClientConfig {
             send_handshake_interval: Duration::from_millis(250),
             ping_interval: Duration::from_secs(1),
             handshake_pings: 10,
+            authority_debounce: Duration::from_millis(50),
         }
     }
 }
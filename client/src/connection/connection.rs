@@ -4,9 +4,11 @@ use log::warn;
 
 use naia_shared::{
     BaseConnection, BitReader, BitWriter, ChannelKind, ChannelKinds, ConnectionConfig,
-    EntityEventMessage, EntityEventMessageAction, EntityResponseEvent, HostType, HostWorldEvents,
-    Instant, OwnedBitReader, PacketType, Protocol, Serde, SerdeErr, StandardHeader, SystemChannel,
-    Tick, WorldMutType, WorldRefType,
+    EntityDespawnHook, EntityEventMessage, EntityEventMessageAction, EntityResponseEvent,
+    FakeEntityConverter, FileTransferAction, FileTransferChannel, FileTransferId,
+    FileTransferMessage, FileTransferReceiveEvent, FileTransferReceiver, HostType, HostWorldEvents,
+    Instant, MessageContainer, OwnedBitReader, PacketType, Protocol, Serde, SerdeErr,
+    StandardHeader, SystemChannel, Tick, WorldMutType, WorldRefType,
 };
 
 use crate::request::GlobalRequestManager;
@@ -119,6 +121,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         world: &mut W,
         now: &Instant,
         incoming_events: &mut Events<E>,
+        entity_despawn_hook: Option<&dyn EntityDespawnHook<E>>,
+        file_transfer_receiver: &mut FileTransferReceiver,
     ) -> Vec<EntityResponseEvent<E>> {
         let mut response_events = Vec::new();
         // Receive Message Events
@@ -158,12 +162,36 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                         }
                     };
                 }
+            } else if channel_kind == ChannelKind::of::<FileTransferChannel>() {
+                for message in messages {
+                    let Some(file_message) = Box::<dyn Any + 'static>::downcast::<
+                        FileTransferMessage,
+                    >(message.to_boxed_any())
+                    .ok()
+                    .map(|boxed_m| *boxed_m) else {
+                        panic!("Received unknown message over FileTransferChannel!");
+                    };
+                    self.receive_file_message(protocol, file_transfer_receiver, file_message);
+                }
             } else {
                 for message in messages {
                     incoming_events.push_message(&channel_kind, message);
                 }
             }
         }
+        for (id, offset) in file_transfer_receiver.take_receipts() {
+            self.send_file_message(protocol, id, FileTransferAction::Received { offset });
+        }
+        for event in file_transfer_receiver.take_events() {
+            match event {
+                FileTransferReceiveEvent::Progress(name, received, size) => {
+                    incoming_events.push_file_transfer_progress(name, received, size);
+                }
+                FileTransferReceiveEvent::Complete(name, data) => {
+                    incoming_events.push_file_transfer_complete(name, data);
+                }
+            }
+        }
 
         // Receive Request and Response Events
         let (requests, responses) = self.base.message_manager.receive_requests_and_responses();
@@ -191,11 +219,60 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             world,
             now,
             remote_events,
+            entity_despawn_hook,
         );
         response_events.extend(incoming_events.receive_world_events(world_events));
         response_events
     }
 
+    fn receive_file_message(
+        &mut self,
+        protocol: &Protocol,
+        file_transfer_receiver: &mut FileTransferReceiver,
+        file_message: FileTransferMessage,
+    ) {
+        let id = file_message.id;
+        match file_message.action {
+            FileTransferAction::Start {
+                name,
+                size,
+                checksum,
+            } => {
+                let offset = file_transfer_receiver.start(id, &name, size, checksum);
+                self.send_file_message(protocol, id, FileTransferAction::Accept { offset });
+            }
+            FileTransferAction::Chunk { offset, bytes } => {
+                file_transfer_receiver.receive_chunk(&id, offset, &bytes);
+            }
+            action => {
+                warn!(
+                    "Received `{:?}` over FileTransferChannel, but the Server never receives files",
+                    action
+                );
+            }
+        }
+    }
+
+    // FileTransferChannel is unbounded, so queueing on it never fails
+    fn send_file_message(
+        &mut self,
+        protocol: &Protocol,
+        id: FileTransferId,
+        action: FileTransferAction,
+    ) {
+        let mut converter = FakeEntityConverter;
+        let message = MessageContainer::from_write(
+            Box::new(FileTransferMessage::new(id, action)),
+            &mut converter,
+        );
+        let _ = self.base.message_manager.send_message(
+            &protocol.message_kinds,
+            &mut converter,
+            &ChannelKind::of::<FileTransferChannel>(),
+            message,
+        );
+    }
+
     // Outgoing data
 
     /// Collect and send any outgoing packets from client to server
@@ -207,6 +284,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         world: &W,
         global_world_manager: &GlobalWorldManager<E>,
     ) {
+        if !self.base.should_send_packets(now) {
+            return;
+        }
         let rtt_millis = self.time_manager.rtt();
         self.base.collect_messages(now, &rtt_millis);
         self.tick_buffer.collect_messages(
@@ -218,7 +298,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             global_world_manager,
             now,
             &rtt_millis,
+            &self.time_manager.client_sending_tick,
         );
+        self.base.limit_outgoing_events(now, &mut host_world_events);
 
         let mut any_sent = false;
         loop {
@@ -250,6 +332,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         global_world_manager: &GlobalWorldManager<E>,
         host_world_events: &mut HostWorldEvents<E>,
     ) -> bool {
+        if !self.base.can_send_packet() {
+            return false;
+        }
         if host_world_events.has_events()
             || self.base.message_manager.has_outgoing_messages()
             || self.tick_buffer.has_messages()
@@ -263,7 +348,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             );
 
             // send packet
-            if io.send_packet(writer.to_packet()).is_err() {
+            let packet = writer.to_packet();
+            self.base.record_sent_packet(packet.slice().len());
+            if io.send_packet(packet).is_err() {
                 // TODO: pass this on and handle above
                 warn!("Client Error: Cannot send data packet to Server");
             }
@@ -1,8 +1,8 @@
 use std::{net::SocketAddr, time::Duration};
 
-use naia_client_socket::IdentityReceiverResult;
 use naia_shared::{
-    BandwidthMonitor, BitReader, CompressionConfig, Decoder, Encoder, OutgoingPacket,
+    BandwidthMonitor, BitReader, ByteCounter, CompressionConfig, Decoder, Encoder,
+    IdentityReceiverResult, OutgoingPacket,
 };
 
 use crate::{
@@ -10,6 +10,10 @@ use crate::{
     transport::{IdentityReceiver, PacketReceiver, PacketSender, ServerAddr},
 };
 
+// The window over which bytes per second are measured, unless bandwidth
+// monitoring sets one
+const DEFAULT_BYTE_COUNT_WINDOW: Duration = Duration::from_secs(1);
+
 pub struct Io {
     authenticated: bool,
     id_receiver: Option<Box<dyn IdentityReceiver>>,
@@ -19,6 +23,8 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    bytes_received: ByteCounter,
+    bytes_sent: ByteCounter,
 }
 
 impl Io {
@@ -26,6 +32,7 @@ impl Io {
         bandwidth_measure_duration: &Option<Duration>,
         compression_config: &Option<CompressionConfig>,
     ) -> Self {
+        let byte_count_window = bandwidth_measure_duration.unwrap_or(DEFAULT_BYTE_COUNT_WINDOW);
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
 
@@ -51,6 +58,8 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            bytes_received: ByteCounter::new(byte_count_window),
+            bytes_sent: ByteCounter::new(byte_count_window),
         }
     }
 
@@ -105,6 +114,7 @@ impl Io {
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
             monitor.record_packet(payload.len());
         }
+        self.bytes_sent.record_packet(payload.len());
 
         self.packet_sender
             .as_mut()
@@ -125,6 +135,7 @@ impl Io {
             if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                 monitor.record_packet(payload.len());
             }
+            self.bytes_received.record_packet(payload.len());
 
             // Decompression
             if let Some(decoder) = &mut self.incoming_decoder {
@@ -144,13 +155,18 @@ impl Io {
             if let ServerAddr::Found(server_addr) = packet_sender.server_addr() {
                 Ok(server_addr)
             } else {
-                Err(NaiaClientError::from_message("Connection has not yet been established! Make sure you call Client.connect() before calling this."))
+                Err(NaiaClientError::NotConnected)
             }
         } else {
-            Err(NaiaClientError::from_message("Connection has not yet been established! Make sure you call Client.connect() before calling this."))
+            Err(NaiaClientError::NotConnected)
         }
     }
 
+    /// Gets the counters of bytes (received, sent) since this Io was created
+    pub fn byte_counters(&mut self) -> (&mut ByteCounter, &mut ByteCounter) {
+        (&mut self.bytes_received, &mut self.bytes_sent)
+    }
+
     pub fn outgoing_bandwidth(&mut self) -> f32 {
         return self
             .outgoing_bandwidth_monitor
@@ -3,9 +3,11 @@ use std::{cmp::Ordering, collections::BinaryHeap};
 use naia_shared::{sequence_greater_than, Tick};
 
 /// A queue for items marked by tick, will only ever pop items from the queue if
-/// the tick has elapsed
+/// the tick has elapsed. Items marked by the same tick are popped in the order
+/// they were added
 pub struct TickQueue<T> {
     queue: BinaryHeap<ItemContainer<T>>,
+    next_sequence: u64,
 }
 
 impl<T> TickQueue<T> {
@@ -13,12 +15,18 @@ impl<T> TickQueue<T> {
     pub fn new() -> Self {
         TickQueue {
             queue: BinaryHeap::new(),
+            next_sequence: 0,
         }
     }
 
     /// Adds an item to the queue marked by tick
     pub fn add_item(&mut self, tick: Tick, item: T) {
-        self.queue.push(ItemContainer { tick, item });
+        self.queue.push(ItemContainer {
+            tick,
+            sequence: self.next_sequence,
+            item,
+        });
+        self.next_sequence = self.next_sequence.wrapping_add(1);
     }
 
     /// Returns whether or not there is an item that is ready to be returned
@@ -45,12 +53,13 @@ impl<T> TickQueue<T> {
 
 pub struct ItemContainer<T> {
     pub tick: Tick,
+    sequence: u64,
     pub item: T,
 }
 
 impl<T> PartialEq for ItemContainer<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.tick == other.tick
+        self.tick == other.tick && self.sequence == other.sequence
     }
 }
 
@@ -59,7 +68,8 @@ impl<T> Eq for ItemContainer<T> {}
 impl<T> Ord for ItemContainer<T> {
     fn cmp(&self, other: &ItemContainer<T>) -> Ordering {
         if self.tick == other.tick {
-            return Ordering::Equal;
+            // the item added first is the greatest, so that it is popped first
+            return other.sequence.cmp(&self.sequence);
         }
         if sequence_greater_than(other.tick, self.tick) {
             Ordering::Greater
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_shared::{
     sequence_greater_than, sequence_less_than, wrapping_diff, BitReader, GameInstant, Instant,
@@ -18,6 +18,10 @@ pub struct TimeManager {
     pruned_rtt_avg: f32,
     raw_rtt_avg: f32,
     rtt_stdv: f32,
+    /// Most recent clock offset samples, measured against the local clock
+    /// after it was aligned with the Server's during the handshake
+    offset_samples: VecDeque<f32>,
+    offset_sample_count: usize,
 
     // Ticks
     accumulator: f32,
@@ -47,6 +51,7 @@ impl TimeManager {
         pruned_rtt_avg: f32,
         rtt_stdv: f32,
         offset_stdv: f32,
+        offset_sample_count: u8,
     ) -> Self {
         let now = base.game_time_now();
         let latency_ms = (pruned_rtt_avg / 2.0) as u32;
@@ -90,6 +95,8 @@ impl TimeManager {
             pruned_rtt_avg,
             raw_rtt_avg: pruned_rtt_avg,
             rtt_stdv,
+            offset_samples: VecDeque::new(),
+            offset_sample_count: offset_sample_count.max(1) as usize,
 
             accumulator: 0.0,
 
@@ -147,6 +154,11 @@ impl TimeManager {
         self.offset_stdv = ((0.9 * self.offset_stdv.powi(2)) + (0.1 * offset_diff.powi(2))).sqrt();
         self.rtt_stdv = ((0.9 * self.rtt_stdv.powi(2)) + (0.1 * rtt_diff.powi(2))).sqrt();
 
+        self.offset_samples.push_back(offset_sample);
+        if self.offset_samples.len() > self.offset_sample_count {
+            self.offset_samples.pop_front();
+        }
+
         if offset_diff.abs() < self.offset_stdv && rtt_diff.abs() < self.rtt_stdv {
             self.pruned_offset_avg = (0.9 * self.pruned_offset_avg) + (0.1 * offset_sample);
             self.pruned_rtt_avg = (0.9 * self.pruned_rtt_avg) + (0.1 * rtt_sample);
@@ -161,6 +173,24 @@ impl TimeManager {
         self.base.game_time_now()
     }
 
+    /// The estimated number of milliseconds the Server's clock is ahead of
+    /// the local game clock, taken as the median of recent samples. The local
+    /// game clock is aligned with the Server's during the handshake, so this
+    /// only reflects drift since then
+    pub(crate) fn clock_offset(&self) -> f32 {
+        if self.offset_samples.is_empty() {
+            return 0.0;
+        }
+        let mut samples: Vec<f32> = self.offset_samples.iter().copied().collect();
+        median(&mut samples)
+    }
+
+    /// The current time on the Server's clock, as estimated locally
+    pub(crate) fn server_time(&self) -> GameInstant {
+        self.game_time_now()
+            .add_signed_millis(self.clock_offset().round() as i32)
+    }
+
     // pub fn game_time_since(&self, previous_instant: &GameInstant) -> GameDuration {
     //     self.base.game_time_since(previous_instant)
     // }
@@ -369,6 +399,18 @@ impl TimeManager {
     }
 }
 
+/// Returns the median of the given values, sorting them in place. The values
+/// must not be empty
+pub(crate) fn median(values: &mut [f32]) -> f32 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
 fn adjust_time(
     server_tick: &Tick,
     server_tick_instant: &GameInstant,
@@ -500,3 +542,24 @@ mod offset_to_speed_tests {
         assert_eq!(offset_to_speed(offset), 0.8);
     }
 }
+
+#[cfg(test)]
+mod median_tests {
+    use crate::connection::time_manager::median;
+
+    #[test]
+    fn odd_count() {
+        assert_eq!(median(&mut [5.0, -1.0, 300.0]), 5.0);
+    }
+
+    #[test]
+    fn even_count() {
+        assert_eq!(median(&mut [4.0, 1.0, 2.0, 3.0]), 2.5);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let mut samples = [20.0, 21.0, 19.0, 20.0, 500.0];
+        assert_eq!(median(&mut samples), 20.0);
+    }
+}
@@ -1,5 +1,9 @@
 use std::{error::Error, fmt};
 
+use naia_shared::MessageSendError;
+
+/// The error type returned by the Client's connect, send & receive methods,
+/// and emitted through `ErrorEvent`
 #[derive(Debug)]
 pub enum NaiaClientError {
     Message(String),
@@ -7,8 +11,24 @@ pub enum NaiaClientError {
     SendError,
     RecvError,
     IdError(u16),
+    /// The Client has no established connection to the Server
+    NotConnected,
+    /// `Client::connect()` was called while a connection was already being
+    /// established or was open
+    AlreadyConnected,
+    /// The Channel's send buffer is full, see
+    /// `ReliableSettings::message_capacity`
+    ChannelFull,
+    /// The Message is larger than the Channel's max message size, see
+    /// `Protocol::set_max_message_size()`
+    MessageTooLarge,
+    /// The Response was for a Request which is unknown, or already answered
+    UnknownResponse,
 }
 
+/// Shorthand for [`NaiaClientError`]
+pub type ClientError = NaiaClientError;
+
 impl NaiaClientError {
     pub fn from_message(message: &str) -> Self {
         Self::Message(message.to_string())
@@ -23,6 +43,28 @@ impl fmt::Display for NaiaClientError {
             Self::SendError => write!(f, "Naia Client Error: Send Error"),
             Self::RecvError => write!(f, "Naia Client Error: Recv Error"),
             Self::IdError(code) => write!(f, "Naia Client Error: Id Error: {}", code),
+            Self::NotConnected => write!(f, "Naia Client Error: Not connected to Server"),
+            Self::AlreadyConnected => write!(
+                f,
+                "Naia Client Error: Client has already initiated a connection"
+            ),
+            Self::ChannelFull => write!(f, "Naia Client Error: Channel send buffer is full"),
+            Self::MessageTooLarge => write!(
+                f,
+                "Naia Client Error: Message is larger than the Channel's max message size"
+            ),
+            Self::UnknownResponse => {
+                write!(f, "Naia Client Error: No pending Request for this Response")
+            }
+        }
+    }
+}
+
+impl From<MessageSendError> for NaiaClientError {
+    fn from(err: MessageSendError) -> Self {
+        match err {
+            MessageSendError::ChannelFull => Self::ChannelFull,
+            MessageSendError::MessageTooLarge => Self::MessageTooLarge,
         }
     }
 }
@@ -2,20 +2,25 @@ use std::{collections::HashMap, marker::PhantomData, mem, net::SocketAddr, vec::
 
 use naia_shared::{
     Channel, ChannelKind, ComponentKind, EntityEvent, EntityResponseEvent, GlobalResponseId,
-    Message, MessageContainer, MessageKind, Replicate, Request, ResponseSendKey, Tick,
+    IdentityToken, Message, MessageContainer, MessageKind, RejectReason, Replicate, Request,
+    ResponseSendKey, Tick,
 };
 
 use crate::NaiaClientError;
 
 pub struct Events<E: Copy> {
+    authentications: Vec<IdentityToken>,
     connections: Vec<SocketAddr>,
-    rejections: Vec<()>,
+    rejections: Vec<RejectReason>,
     disconnections: Vec<SocketAddr>,
     client_ticks: Vec<Tick>,
     server_ticks: Vec<Tick>,
     errors: Vec<NaiaClientError>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
+    expired_messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
     requests: HashMap<ChannelKind, HashMap<MessageKind, Vec<(GlobalResponseId, MessageContainer)>>>,
+    file_transfer_progresses: Vec<(String, u32, u32)>,
+    file_transfer_completions: Vec<(String, Vec<u8>)>,
     spawns: Vec<E>,
     despawns: Vec<E>,
     publishes: Vec<E>,
@@ -38,6 +43,7 @@ impl<E: Copy> Default for Events<E> {
 impl<E: Copy> Events<E> {
     pub(crate) fn new() -> Self {
         Self {
+            authentications: Vec::new(),
             connections: Vec::new(),
             rejections: Vec::new(),
             disconnections: Vec::new(),
@@ -45,7 +51,10 @@ impl<E: Copy> Events<E> {
             server_ticks: Vec::new(),
             errors: Vec::new(),
             messages: HashMap::new(),
+            expired_messages: HashMap::new(),
             requests: HashMap::new(),
+            file_transfer_progresses: Vec::new(),
+            file_transfer_completions: Vec::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             publishes: Vec::new(),
@@ -130,13 +139,18 @@ impl<E: Copy> Events<E> {
 
     // Crate-public
 
+    pub(crate) fn push_authentication(&mut self, identity_token: &IdentityToken) {
+        self.authentications.push(identity_token.clone());
+        self.empty = false;
+    }
+
     pub(crate) fn push_connection(&mut self, socket_addr: &SocketAddr) {
         self.connections.push(*socket_addr);
         self.empty = false;
     }
 
-    pub(crate) fn push_rejection(&mut self) {
-        self.rejections.push(());
+    pub(crate) fn push_rejection(&mut self, reason: RejectReason) {
+        self.rejections.push(reason);
         self.empty = false;
     }
 
@@ -146,17 +160,26 @@ impl<E: Copy> Events<E> {
     }
 
     pub(crate) fn push_message(&mut self, channel_kind: &ChannelKind, message: MessageContainer) {
-        if !self.messages.contains_key(&channel_kind) {
-            self.messages.insert(*channel_kind, HashMap::new());
-        }
-        let channel_map = self.messages.get_mut(&channel_kind).unwrap();
+        push_message_impl(&mut self.messages, channel_kind, message);
+        self.empty = false;
+    }
 
-        let message_kind: MessageKind = message.kind();
-        if !channel_map.contains_key(&message_kind) {
-            channel_map.insert(message_kind, Vec::new());
-        }
-        let list = channel_map.get_mut(&message_kind).unwrap();
-        list.push(message);
+    pub(crate) fn push_expired_message(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+    ) {
+        push_message_impl(&mut self.expired_messages, channel_kind, message);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_file_transfer_progress(&mut self, name: String, received: u32, size: u32) {
+        self.file_transfer_progresses.push((name, received, size));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_file_transfer_complete(&mut self, name: String, data: Vec<u8>) {
+        self.file_transfer_completions.push((name, data));
         self.empty = false;
     }
 
@@ -293,6 +316,7 @@ impl<E: Copy> Events<E> {
     }
 
     pub(crate) fn clear(&mut self) {
+        self.authentications.clear();
         self.connections.clear();
         self.rejections.clear();
         self.disconnections.clear();
@@ -300,7 +324,10 @@ impl<E: Copy> Events<E> {
         self.server_ticks.clear();
         self.errors.clear();
         self.messages.clear();
+        self.expired_messages.clear();
         self.requests.clear();
+        self.file_transfer_progresses.clear();
+        self.file_transfer_completions.clear();
         self.spawns.clear();
         self.despawns.clear();
         self.publishes.clear();
@@ -324,6 +351,21 @@ pub trait Event<E: Copy> {
     fn has(events: &Events<E>) -> bool;
 }
 
+// AuthenticatedEvent
+pub struct AuthenticatedEvent;
+impl<E: Copy> Event<E> for AuthenticatedEvent {
+    type Iter = IntoIter<IdentityToken>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.authentications);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.authentications.is_empty()
+    }
+}
+
 // ConnectEvent
 pub struct ConnectEvent;
 impl<E: Copy> Event<E> for ConnectEvent {
@@ -342,7 +384,7 @@ impl<E: Copy> Event<E> for ConnectEvent {
 // RejectEvent
 pub struct RejectEvent;
 impl<E: Copy> Event<E> for RejectEvent {
-    type Iter = IntoIter<()>;
+    type Iter = IntoIter<RejectReason>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let list = std::mem::take(&mut events.rejections);
@@ -423,27 +465,74 @@ impl<E: Copy, C: Channel, M: Message> Event<E> for MessageEvent<C, M> {
     type Iter = IntoIter<M>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
+        let output = read_channel_messages::<C, M>(&mut events.messages);
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
         let channel_kind: ChannelKind = ChannelKind::of::<C>();
-        if let Some(channel_map) = events.messages.get_mut(&channel_kind) {
+        if let Some(channel_map) = events.messages.get(&channel_kind) {
             let message_kind: MessageKind = MessageKind::of::<M>();
-            if let Some(boxed_list) = channel_map.remove(&message_kind) {
-                let mut output_list: Vec<M> = Vec::new();
-
-                for boxed_message in boxed_list {
-                    let boxed_any = boxed_message.to_boxed_any();
-                    let message = boxed_any.downcast::<M>().unwrap();
-                    output_list.push(*message);
-                }
+            return channel_map.contains_key(&message_kind);
+        }
+        return false;
+    }
+}
 
-                return IntoIterator::into_iter(output_list);
+fn read_channel_messages<C: Channel, M: Message>(
+    messages: &mut HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
+) -> Vec<M> {
+    let channel_kind: ChannelKind = ChannelKind::of::<C>();
+    let mut output_list: Vec<M> = Vec::new();
+    if let Some(channel_map) = messages.get_mut(&channel_kind) {
+        let message_kind: MessageKind = MessageKind::of::<M>();
+        if let Some(boxed_list) = channel_map.remove(&message_kind) {
+            for boxed_message in boxed_list {
+                let boxed_any = boxed_message.to_boxed_any();
+                let message = boxed_any.downcast::<M>().unwrap();
+                output_list.push(*message);
             }
         }
-        return IntoIterator::into_iter(Vec::new());
+    }
+    output_list
+}
+
+fn push_message_impl(
+    messages: &mut HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
+    channel_kind: &ChannelKind,
+    message: MessageContainer,
+) {
+    if !messages.contains_key(&channel_kind) {
+        messages.insert(*channel_kind, HashMap::new());
+    }
+    let channel_map = messages.get_mut(&channel_kind).unwrap();
+
+    let message_kind: MessageKind = message.kind();
+    if !channel_map.contains_key(&message_kind) {
+        channel_map.insert(message_kind, Vec::new());
+    }
+    let list = channel_map.get_mut(&message_kind).unwrap();
+    list.push(message);
+}
+
+// Message Expired Event
+/// A Message sent with `Client::send_message_with_ttl()` which the Server
+/// had not acknowledged before its time to live passed
+pub struct MessageExpiredEvent<C: Channel, M: Message> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, C: Channel, M: Message> Event<E> for MessageExpiredEvent<C, M> {
+    type Iter = IntoIter<M>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let output = read_channel_messages::<C, M>(&mut events.expired_messages);
+        return IntoIterator::into_iter(output);
     }
 
     fn has(events: &Events<E>) -> bool {
         let channel_kind: ChannelKind = ChannelKind::of::<C>();
-        if let Some(channel_map) = events.messages.get(&channel_kind) {
+        if let Some(channel_map) = events.expired_messages.get(&channel_kind) {
             let message_kind: MessageKind = MessageKind::of::<M>();
             return channel_map.contains_key(&message_kind);
         }
@@ -596,6 +685,11 @@ impl<E: Copy> Event<E> for EntityAuthDeniedEvent {
 }
 
 // Insert Component Event
+/// Emitted when a Component is inserted into a replicated Entity. When an
+/// Entity spawns with several Components, they are inserted into the World in
+/// the order they were registered with the Protocol, and all of them are
+/// present before any of these events is read. A Component which refers to an
+/// Entity that has not arrived yet is inserted once that Entity arrives
 pub struct InsertComponentEvent<C: Replicate> {
     phantom_c: PhantomData<C>,
 }
@@ -668,3 +762,37 @@ impl<E: Copy, C: Replicate> Event<E> for RemoveComponentEvent<C> {
         events.removes.contains_key(&component_kind)
     }
 }
+
+// File Transfer Progress Event
+/// Reports the name of a file the Server is sending, how many of its bytes
+/// have been received, and its size, whenever that changes
+pub struct FileTransferProgressEvent;
+impl<E: Copy> Event<E> for FileTransferProgressEvent {
+    type Iter = IntoIter<(String, u32, u32)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.file_transfer_progresses);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.file_transfer_progresses.is_empty()
+    }
+}
+
+// File Transfer Complete Event
+/// The name and contents of a file the Server sent, once it has been received
+/// in full and verified
+pub struct FileTransferCompleteEvent;
+impl<E: Copy> Event<E> for FileTransferCompleteEvent {
+    type Iter = IntoIter<(String, Vec<u8>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.file_transfer_completions);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.file_transfer_completions.is_empty()
+    }
+}
@@ -1,11 +1,11 @@
 use std::time::Duration;
 
 use log::warn;
-use naia_client_socket::shared::IdentityToken;
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, OutgoingPacket, PacketType, Serde,
-    StandardHeader, Timer, Timestamp as stamp_time,
+    handshake::{HandshakeHeader, ProofOfWork},
+    BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType, Serde, StandardHeader, Timer,
+    Timestamp as stamp_time,
 };
 
 use crate::{
@@ -51,6 +51,7 @@ pub struct HandshakeManager {
     identity_token: Option<IdentityToken>,
     pre_connection_timestamp: Timestamp,
     pre_connection_digest: Option<Vec<u8>>,
+    pre_connection_nonce: u64,
 }
 
 impl Handshaker for HandshakeManager {
@@ -131,7 +132,8 @@ impl Handshaker for HandshakeManager {
                     HandshakeHeader::ClientChallengeRequest
                     | HandshakeHeader::ClientValidateRequest
                     | HandshakeHeader::ClientConnectRequest
-                    | HandshakeHeader::Disconnect => {
+                    | HandshakeHeader::Disconnect
+                    | HandshakeHeader::ServerDisconnect => {
                         return None;
                     }
                 }
@@ -186,6 +188,7 @@ impl HandshakeManager {
             identity_token: None,
             pre_connection_timestamp,
             pre_connection_digest: None,
+            pre_connection_nonce: 0,
             connection_state: HandshakeState::AwaitingChallengeResponse,
             ping_interval,
             handshake_pings,
@@ -219,6 +222,12 @@ impl HandshakeManager {
                     return;
                 }
                 let digest_bytes = digest_bytes_result.unwrap();
+
+                let Ok(difficulty) = u8::de(reader) else {
+                    return;
+                };
+                // solve the Server's proof-of-work challenge before validating
+                self.pre_connection_nonce = ProofOfWork::solve(&digest_bytes, difficulty);
                 self.pre_connection_digest = Some(digest_bytes);
 
                 self.connection_state = HandshakeState::AwaitingValidateResponse;
@@ -236,6 +245,9 @@ impl HandshakeManager {
         // write timestamp & digest into payload
         self.write_signed_timestamp(&mut writer);
 
+        // write proof-of-work nonce
+        self.pre_connection_nonce.ser(&mut writer);
+
         writer
     }
 
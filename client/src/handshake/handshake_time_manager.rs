@@ -2,7 +2,10 @@ use std::time::Duration;
 
 use naia_shared::{BitReader, BitWriter, GameInstant, Serde, SerdeErr, Tick, GAME_TIME_LIMIT};
 
-use crate::connection::{base_time_manager::BaseTimeManager, time_manager::TimeManager};
+use crate::connection::{
+    base_time_manager::BaseTimeManager,
+    time_manager::{median, TimeManager},
+};
 
 pub struct HandshakeTimeManager {
     base: BaseTimeManager,
@@ -101,37 +104,21 @@ impl HandshakeTimeManager {
         let offset_stdv = offset_diff_mean.sqrt();
         let rtt_stdv = rtt_diff_mean.sqrt();
 
-        // Prune out any pong values outside the standard deviation (mitigation)
-        let mut pruned_pongs = Vec::new();
-        for (time_offset_millis, rtt_millis) in pongs {
-            let offset_diff = (time_offset_millis - offset_mean).abs();
-            let rtt_diff = (rtt_millis - rtt_mean).abs();
-            if offset_diff < offset_stdv && rtt_diff < rtt_stdv {
-                pruned_pongs.push((time_offset_millis, rtt_millis));
-            }
-        }
-
-        // Find the mean of the pruned pongs
-        let pruned_sample_count = pruned_pongs.len() as f32;
-        let mut pruned_offset_mean = 0.0;
-        let mut pruned_rtt_mean = 0.0;
-
-        for (time_offset_millis, rtt_millis) in pruned_pongs {
-            pruned_offset_mean += time_offset_millis;
-            pruned_rtt_mean += rtt_millis;
-        }
-
-        pruned_offset_mean /= pruned_sample_count;
-        pruned_rtt_mean /= pruned_sample_count;
+        // Take the median of the samples, so that outliers (e.g. a pong which
+        // was delayed by a resend) don't skew the result
+        let mut offsets: Vec<f32> = pongs.iter().map(|(offset, _)| *offset).collect();
+        let mut rtts: Vec<f32> = pongs.iter().map(|(_, rtt)| *rtt).collect();
+        let median_offset = median(&mut offsets);
+        let median_rtt = median(&mut rtts);
 
         // Get values we were looking for
 
         // Set internal time to match offset
-        if pruned_offset_mean < 0.0 {
-            let offset_ms = (pruned_offset_mean * -1.0) as u32;
+        if median_offset < 0.0 {
+            let offset_ms = (-median_offset) as u32;
             self.base.start_instant.subtract_millis(offset_ms);
         } else {
-            let offset_ms = pruned_offset_mean as u32;
+            let offset_ms = median_offset as u32;
             // start_instant should only be able to go BACK in time, otherwise `.elapsed()` might not work
             self.base
                 .start_instant
@@ -148,9 +135,10 @@ impl HandshakeTimeManager {
             self.server_tick_instant,
             self.server_tick_duration_avg,
             self.server_speedup_potential,
-            pruned_rtt_mean,
+            median_rtt,
             rtt_stdv,
             offset_stdv,
+            self.handshake_pings,
         )
     }
 }
@@ -113,7 +113,8 @@ impl Handshaker for HandshakeManager {
                     }
                     HandshakeHeader::ClientIdentifyRequest
                     | HandshakeHeader::ClientConnectRequest
-                    | HandshakeHeader::Disconnect => {
+                    | HandshakeHeader::Disconnect
+                    | HandshakeHeader::ServerDisconnect => {
                         return None;
                     }
                 }
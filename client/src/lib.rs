@@ -16,8 +16,11 @@ extern crate cfg_if;
 pub mod transport;
 pub mod shared {
     pub use naia_shared::{
-        default_channels, sequence_greater_than, GlobalRequestId, GlobalResponseId, Instant,
-        Message, Protocol, Random, ResponseReceiveKey, SocketConfig, Tick, GameInstant,
+        default_channels, sequence_greater_than, ConnectionStats, DataChannelConfig,
+        DataChannelConfigError, DisconnectReason, EntityDespawnHook, GameInstant, GlobalRequestId,
+        GlobalResponseId, IceServerConfig, IdentityToken, Instant, Message, PendingRequest,
+        Protocol, Random, RejectReason, ResponseReceiveError, ResponseReceiveKey, SocketConfig,
+        Tick, WaitlistEntry, WaitlistStats,
     };
 }
 
@@ -34,11 +37,12 @@ mod world;
 pub use client::{Client, ConnectionStatus};
 pub use client_config::ClientConfig;
 pub use command_history::CommandHistory;
-pub use error::NaiaClientError;
+pub use error::{ClientError, NaiaClientError};
 pub use events::{
-    ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-    EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, Events, InsertComponentEvent,
-    MessageEvent, PublishEntityEvent, RejectEvent, RemoveComponentEvent, RequestEvent,
+    AuthenticatedEvent, ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent,
+    EntityAuthDeniedEvent, EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, Events,
+    FileTransferCompleteEvent, FileTransferProgressEvent, InsertComponentEvent, MessageEvent,
+    MessageExpiredEvent, PublishEntityEvent, RejectEvent, RemoveComponentEvent, RequestEvent,
     ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent, UpdateComponentEvent,
 };
 pub use world::{
@@ -2,11 +2,12 @@ use std::collections::HashMap;
 
 use naia_shared::{
     ChannelKind, GlobalRequestId, GlobalResponseId, LocalResponseId, MessageContainer,
+    ResponseReceiveError,
 };
 
 // GlobalRequestManager
 pub struct GlobalRequestManager {
-    map: HashMap<GlobalRequestId, Option<MessageContainer>>,
+    map: HashMap<GlobalRequestId, Option<Result<MessageContainer, ResponseReceiveError>>>,
     next_id: u64,
 }
 
@@ -30,7 +31,7 @@ impl GlobalRequestManager {
     pub(crate) fn destroy_request_id(
         &mut self,
         request_id: &GlobalRequestId,
-    ) -> Option<MessageContainer> {
+    ) -> Option<Result<MessageContainer, ResponseReceiveError>> {
         let Some(response_opt) = self.map.get(request_id) else {
             return None;
         };
@@ -46,8 +47,23 @@ impl GlobalRequestManager {
         request_id: &GlobalRequestId,
         response: MessageContainer,
     ) {
-        let response_opt = self.map.get_mut(request_id).unwrap();
-        *response_opt = Some(response);
+        let Some(response_opt) = self.map.get_mut(request_id) else {
+            return;
+        };
+        *response_opt = Some(Ok(response));
+    }
+
+    pub(crate) fn time_out_request(&mut self, request_id: &GlobalRequestId) {
+        let Some(response_opt) = self.map.get_mut(request_id) else {
+            return;
+        };
+        *response_opt = Some(Err(ResponseReceiveError::Timeout));
+    }
+
+    // Forgets a Request along with any Response to it which has not been
+    // read. Returns whether the Request was still awaiting a Response
+    pub(crate) fn cancel_request(&mut self, request_id: &GlobalRequestId) -> bool {
+        matches!(self.map.remove(request_id), Some(None))
     }
 }
 
@@ -0,0 +1,134 @@
+use naia_shared::{IdentityReceiverResult, LoopbackClient};
+
+pub use naia_shared::LoopbackTransport;
+
+use super::{
+    IdentityReceiver, PacketReceiver, PacketSender, RecvError, SendError, ServerAddr,
+    Socket as TransportSocket,
+};
+
+/// Connects to a Server in the same process through a `LoopbackTransport`,
+/// without binding any ports
+pub struct Socket {
+    transport: LoopbackTransport,
+}
+
+impl Socket {
+    pub fn new(transport: &LoopbackTransport) -> Self {
+        Self {
+            transport: transport.clone(),
+        }
+    }
+
+    fn connect_inner(
+        &self,
+        auth_bytes: Option<Vec<u8>>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let client = self.transport.connect(auth_bytes.as_deref());
+        (
+            Box::new(LoopbackIdentityReceiver(client.clone())),
+            Box::new(LoopbackPacketSender(client.clone())),
+            Box::new(LoopbackPacketReceiver {
+                client,
+                buffer: Box::new([]),
+            }),
+        )
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect_inner(None)
+    }
+    fn connect_with_auth(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect_inner(Some(auth_bytes))
+    }
+    // there are no headers to send in-process
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect_inner(None)
+    }
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect_inner(Some(auth_bytes))
+    }
+}
+
+#[derive(Clone)]
+struct LoopbackIdentityReceiver(LoopbackClient);
+
+impl IdentityReceiver for LoopbackIdentityReceiver {
+    fn receive(&mut self) -> IdentityReceiverResult {
+        self.0.receive_identity()
+    }
+}
+
+#[derive(Clone)]
+struct LoopbackPacketSender(LoopbackClient);
+
+impl PacketSender for LoopbackPacketSender {
+    fn send(&self, payload: &[u8]) -> Result<(), SendError> {
+        self.0.send(payload).map_err(|_| SendError)
+    }
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(self.0.server_addr())
+    }
+}
+
+#[derive(Clone)]
+struct LoopbackPacketReceiver {
+    client: LoopbackClient,
+    // holds the last packet received, which is lent out until the next
+    buffer: Box<[u8]>,
+}
+
+impl PacketReceiver for LoopbackPacketReceiver {
+    fn receive(&mut self) -> Result<Option<&[u8]>, RecvError> {
+        match self.client.receive() {
+            Some(payload) => {
+                self.buffer = payload;
+                Ok(Some(&self.buffer))
+            }
+            None => Ok(None),
+        }
+    }
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(self.client.server_addr())
+    }
+}
@@ -6,7 +6,17 @@ cfg_if! {
 cfg_if! {
     if #[cfg(feature = "transport_udp")] {
         pub mod udp;
-        mod conditioner;
+    } else {}
+}
+cfg_if! {
+    if #[cfg(feature = "transport_loopback")] {
+        pub mod loopback;
+    } else {}
+}
+cfg_if! {
+    if #[cfg(feature = "transport_tap")] {
+        pub mod replay;
+        pub mod tap;
     } else {}
 }
 
@@ -14,9 +24,10 @@ mod server_addr;
 pub use server_addr::ServerAddr;
 
 pub use inner::{IdentityReceiver, PacketReceiver, PacketSender, RecvError, SendError, Socket};
+pub use naia_shared::IdentityReceiverResult;
 
 mod inner {
-    use naia_client_socket::IdentityReceiverResult;
+    use naia_shared::IdentityReceiverResult;
 
     use super::ServerAddr;
 
@@ -24,6 +35,11 @@ mod inner {
 
     pub struct RecvError;
 
+    /// A transport the Client exchanges packets with the Server over, given to
+    /// `Client::connect`. Implement it, along with the sender & receiver
+    /// traits below, to use a transport other than the built-in ones, e.g.
+    /// Steam Networking Sockets or an in-memory loopback for tests. The
+    /// IdentityReceiver resolves once the Server has accepted the session
     pub trait Socket {
         fn connect(
             self: Box<Self>,
@@ -59,13 +75,33 @@ mod inner {
         );
     }
 
-    pub trait PacketSender: Send + Sync {
+    /// Clones of a PacketSender all send through the same underlying
+    /// connection, so separate systems can each hold their own handle
+    pub trait PacketSender: PacketSenderClone + Send + Sync {
         /// Sends a packet from the Client Socket
         fn send(&self, payload: &[u8]) -> Result<(), SendError>;
         /// Get the Server's Socket address
         fn server_addr(&self) -> ServerAddr;
     }
 
+    /// Used to clone Box<dyn PacketSender>
+    pub trait PacketSenderClone {
+        /// Clone the boxed PacketSender
+        fn clone_box(&self) -> Box<dyn PacketSender>;
+    }
+
+    impl<T: 'static + PacketSender + Clone> PacketSenderClone for T {
+        fn clone_box(&self) -> Box<dyn PacketSender> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl Clone for Box<dyn PacketSender> {
+        fn clone(&self) -> Box<dyn PacketSender> {
+            PacketSenderClone::clone_box(self.as_ref())
+        }
+    }
+
     pub trait PacketReceiver: PacketReceiverClone + Send + Sync {
         /// Receives a packet from the Client Socket
         fn receive(&mut self) -> Result<Option<&[u8]>, RecvError>;
@@ -94,7 +130,8 @@ mod inner {
     // Identity
 
     pub trait IdentityReceiver: IdentityReceiverClone + Send + Sync {
-        ///
+        /// Receives the IdentityToken the Server sent, or why it refused the
+        /// session, once it has answered
         fn receive(&mut self) -> IdentityReceiverResult;
     }
 
@@ -0,0 +1,240 @@
+use std::{
+    collections::VecDeque,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use naia_shared::{IdentityReceiverResult, IdentityToken, Instant, PacketDirection, PacketRecord};
+
+use super::{
+    IdentityReceiver, PacketReceiver, PacketSender, RecvError, SendError, ServerAddr,
+    Socket as TransportSocket,
+};
+
+/// A Socket which replays the incoming packets of a recorded session, such as
+/// one captured with a `tap::Socket` and a `PacketRecorder`.
+/// Outgoing packets are discarded, but each incoming packet is only replayed
+/// once as many packets have been sent as were sent before it in the
+/// recording, so that a response isn't replayed ahead of its request.
+pub struct Socket {
+    // each incoming record, with the number of outgoing records before it
+    records: VecDeque<(usize, PacketRecord)>,
+    identity_token: IdentityToken,
+    speed: f32,
+}
+
+impl Socket {
+    /// Create a Socket which replays the incoming packets of the given records,
+    /// given the IdentityToken the Server gave the recorded session, see
+    /// `Client::identity_token()`.
+    /// A `speed` of 1.0 replays at the original timing, 2.0 replays twice as
+    /// fast, and 0.0 (or less, or a speed which isn't finite) replays every
+    /// packet as soon as possible.
+    pub fn new(records: Vec<PacketRecord>, identity_token: IdentityToken, speed: f32) -> Self {
+        let mut sent = 0;
+        let mut incoming = VecDeque::new();
+        for record in records {
+            match record.direction {
+                PacketDirection::Outgoing => sent += 1,
+                PacketDirection::Incoming => incoming.push_back((sent, record)),
+            }
+        }
+        Self {
+            records: incoming,
+            identity_token,
+            speed: if speed.is_finite() {
+                speed.max(0.0)
+            } else {
+                0.0
+            },
+        }
+    }
+
+    fn open(
+        self,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let server_addr = ServerAddr::Found(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0));
+        let sent = Arc::new(AtomicUsize::new(0));
+        (
+            Box::new(ReplayIdentityReceiver {
+                identity_token: Some(self.identity_token),
+            }),
+            Box::new(ReplayPacketSender {
+                server_addr,
+                sent: sent.clone(),
+            }),
+            Box::new(ReplayPacketReceiver {
+                server_addr,
+                start: Instant::now(),
+                speed: self.speed,
+                sent,
+                records: self.records,
+                last_payload: None,
+            }),
+        )
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (*self).open()
+    }
+    fn connect_with_auth(
+        self: Box<Self>,
+        _auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (*self).open()
+    }
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (*self).open()
+    }
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        _auth_bytes: Vec<u8>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (*self).open()
+    }
+}
+
+#[derive(Clone)]
+struct ReplayIdentityReceiver {
+    identity_token: Option<IdentityToken>,
+}
+
+impl IdentityReceiver for ReplayIdentityReceiver {
+    /// Returns the recorded session's IdentityToken, once
+    fn receive(&mut self) -> IdentityReceiverResult {
+        match self.identity_token.take() {
+            Some(identity_token) => IdentityReceiverResult::Success(identity_token),
+            None => IdentityReceiverResult::Waiting,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ReplayPacketSender {
+    server_addr: ServerAddr,
+    sent: Arc<AtomicUsize>,
+}
+
+impl PacketSender for ReplayPacketSender {
+    /// Discards the packet, the recorded session has already happened, but
+    /// counts it towards replaying the packets which followed it
+    fn send(&self, _payload: &[u8]) -> Result<(), SendError> {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        self.server_addr
+    }
+}
+
+#[derive(Clone)]
+struct ReplayPacketReceiver {
+    server_addr: ServerAddr,
+    start: Instant,
+    speed: f32,
+    sent: Arc<AtomicUsize>,
+    records: VecDeque<(usize, PacketRecord)>,
+    last_payload: Option<Box<[u8]>>,
+}
+
+impl PacketReceiver for ReplayPacketReceiver {
+    /// Receives the next recorded packet, once its time has come
+    fn receive(&mut self) -> Result<Option<&[u8]>, RecvError> {
+        let Some((sent_before, record)) = self.records.front() else {
+            return Ok(None);
+        };
+        if self.sent.load(Ordering::Relaxed) < *sent_before {
+            return Ok(None);
+        }
+
+        if self.speed > 0.0 {
+            // a delay too long to represent is never due
+            let due = Duration::try_from_secs_f32(record.elapsed.as_secs_f32() / self.speed)
+                .unwrap_or(Duration::MAX);
+            if self.start.elapsed(&Instant::now()) < due {
+                return Ok(None);
+            }
+        }
+
+        let (_, record) = self.records.pop_front().unwrap();
+        self.last_payload = Some(record.payload);
+        Ok(Some(self.last_payload.as_ref().unwrap()))
+    }
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        self.server_addr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use naia_shared::{IdentityReceiverResult, PacketDirection, PacketRecord};
+
+    use super::Socket;
+    use crate::transport::Socket as TransportSocket;
+
+    #[test]
+    fn replays_the_recorded_identity_token_once() {
+        let records = vec![PacketRecord {
+            elapsed: Duration::from_secs(1),
+            direction: PacketDirection::Incoming,
+            address: None,
+            payload: Box::new([1]),
+        }];
+        let socket: Box<dyn TransportSocket> =
+            Box::new(Socket::new(records, "token".to_string(), f32::MIN_POSITIVE));
+        let (mut identity_receiver, _, mut receiver) = socket.connect();
+
+        assert!(matches!(
+            identity_receiver.receive(),
+            IdentityReceiverResult::Success(token) if token == "token"
+        ));
+        assert!(matches!(
+            identity_receiver.receive(),
+            IdentityReceiverResult::Waiting
+        ));
+        // a delay too long to represent is never due
+        assert!(matches!(receiver.receive(), Ok(None)));
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use naia_shared::{PacketDirection, PacketRecorder};
+
+use super::{
+    IdentityReceiver, PacketReceiver, PacketSender, RecvError, SendError, ServerAddr,
+    Socket as TransportSocket,
+};
+
+/// Observes the raw bytes of every packet passing through a tapped Socket
+pub trait PacketTap: Send + Sync {
+    /// Called with each outgoing packet's bytes, immediately before it is sent
+    fn on_outgoing(&self, payload: &[u8]);
+    /// Called with each incoming packet's bytes, immediately after it is
+    /// received, and before any header parsing or decompression
+    fn on_incoming(&self, payload: &[u8]);
+}
+
+impl PacketTap for PacketRecorder {
+    fn on_outgoing(&self, payload: &[u8]) {
+        self.record(PacketDirection::Outgoing, None, payload);
+    }
+    fn on_incoming(&self, payload: &[u8]) {
+        self.record(PacketDirection::Incoming, None, payload);
+    }
+}
+
+/// Wraps another Socket, invoking a PacketTap for every packet sent or
+/// received through it
+pub struct Socket {
+    inner: Box<dyn TransportSocket>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl Socket {
+    pub fn new<S: Into<Box<dyn TransportSocket>>, T: PacketTap + 'static>(
+        inner: S,
+        tap: T,
+    ) -> Self {
+        Self {
+            inner: inner.into(),
+            tap: Arc::new(tap),
+        }
+    }
+
+    fn wrap(
+        tap: Arc<dyn PacketTap>,
+        (id_receiver, packet_sender, packet_receiver): (
+            Box<dyn IdentityReceiver>,
+            Box<dyn PacketSender>,
+            Box<dyn PacketReceiver>,
+        ),
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (
+            id_receiver,
+            Box::new(TappedPacketSender {
+                inner: packet_sender,
+                tap: tap.clone(),
+            }),
+            Box::new(TappedPacketReceiver {
+                inner: packet_receiver,
+                tap,
+            }),
+        )
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let Self { inner, tap } = *self;
+        Self::wrap(tap, inner.connect())
+    }
+    fn connect_with_auth(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let Self { inner, tap } = *self;
+        Self::wrap(tap, inner.connect_with_auth(auth_bytes))
+    }
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let Self { inner, tap } = *self;
+        Self::wrap(tap, inner.connect_with_auth_headers(auth_headers))
+    }
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let Self { inner, tap } = *self;
+        Self::wrap(
+            tap,
+            inner.connect_with_auth_and_headers(auth_bytes, auth_headers),
+        )
+    }
+}
+
+#[derive(Clone)]
+struct TappedPacketSender {
+    inner: Box<dyn PacketSender>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl PacketSender for TappedPacketSender {
+    /// Sends a packet from the Client Socket
+    fn send(&self, payload: &[u8]) -> Result<(), SendError> {
+        self.tap.on_outgoing(payload);
+        self.inner.send(payload)
+    }
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        self.inner.server_addr()
+    }
+}
+
+#[derive(Clone)]
+struct TappedPacketReceiver {
+    inner: Box<dyn PacketReceiver>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl PacketReceiver for TappedPacketReceiver {
+    /// Receives a packet from the Client Socket
+    fn receive(&mut self) -> Result<Option<&[u8]>, RecvError> {
+        let result = self.inner.receive();
+        if let Ok(Some(payload)) = &result {
+            self.tap.on_incoming(payload);
+        }
+        result
+    }
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        self.inner.server_addr()
+    }
+}
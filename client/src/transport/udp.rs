@@ -1,41 +1,28 @@
-use std::{
-    io::ErrorKind,
-    net::{SocketAddr, UdpSocket},
-    sync::{Arc, Mutex},
-};
+use std::net::SocketAddr;
 
-use naia_shared::LinkConditionerConfig;
+use naia_shared::{LinkConditionerConfig, SocketConfig, Transport};
 
 use super::{
-    conditioner::ConditionedPacketReceiver, PacketReceiver as TransportReceiver,
-    PacketSender as TransportSender, RecvError, SendError, ServerAddr as TransportAddr,
+    webrtc::Socket as WebrtcSocket, IdentityReceiver, PacketReceiver, PacketSender,
     Socket as TransportSocket,
 };
 
-// Socket
+/// Exchanges packets with the Server over plain UDP, using the session
+/// protocol of `Transport::Udp`. Auth is sent the same way as over WebRTC,
+/// so Client code is the same for both transports
 pub struct Socket {
-    server_addr: SocketAddr,
-    socket: Arc<Mutex<UdpSocket>>,
-    config: Option<LinkConditionerConfig>,
+    inner: WebrtcSocket,
 }
 
 impl Socket {
     pub fn new(server_addr: &SocketAddr, config: Option<LinkConditionerConfig>) -> Self {
-        let client_ip_address =
-            find_my_ip_address().expect("cannot find host's current IP address");
-
-        let socket = Arc::new(Mutex::new(UdpSocket::bind((client_ip_address, 0)).unwrap()));
-        socket
-            .as_ref()
-            .lock()
-            .unwrap()
-            .set_nonblocking(true)
-            .expect("can't set socket to non-blocking!");
-
+        let socket_config = SocketConfig {
+            transport: Transport::Udp,
+            link_condition: config,
+            ..Default::default()
+        };
         return Self {
-            server_addr: *server_addr,
-            socket,
-            config,
+            inner: WebrtcSocket::new(&format!("http://{}", server_addr), &socket_config),
         };
     }
 }
@@ -47,125 +34,44 @@ impl Into<Box<dyn TransportSocket>> for Socket {
 }
 
 impl TransportSocket for Socket {
-    fn connect(self: Box<Self>) -> (Box<dyn TransportSender>, Box<dyn TransportReceiver>) {
-        let sender = Box::new(PacketSender::new(self.socket.clone(), self.server_addr));
-
-        let receiver: Box<dyn TransportReceiver> = {
-            let inner_receiver =
-                Box::new(PacketReceiver::new(self.socket.clone(), self.server_addr));
-            if let Some(config) = &self.config {
-                Box::new(ConditionedPacketReceiver::new(inner_receiver, config))
-            } else {
-                inner_receiver
-            }
-        };
-
-        return (sender, receiver);
-    }
-}
-
-// Packet Sender
-struct PacketSender {
-    socket: Arc<Mutex<UdpSocket>>,
-    server_addr: SocketAddr,
-}
-
-impl PacketSender {
-    pub fn new(socket: Arc<Mutex<UdpSocket>>, server_addr: SocketAddr) -> Self {
-        return Self {
-            socket,
-            server_addr,
-        };
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        Box::new(self.inner).connect()
     }
-}
-
-impl TransportSender for PacketSender {
-    /// Sends a packet from the Client Socket
-    fn send(&self, payload: &[u8]) -> Result<(), SendError> {
-        if self
-            .socket
-            .as_ref()
-            .lock()
-            .unwrap()
-            .send_to(payload, self.server_addr)
-            .is_err()
-        {
-            return Err(SendError);
-        }
-        return Ok(());
+    fn connect_with_auth(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        Box::new(self.inner).connect_with_auth(auth_bytes)
     }
-    /// Get the Server's Socket address
-    fn server_addr(&self) -> TransportAddr {
-        TransportAddr::Found(self.server_addr)
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        Box::new(self.inner).connect_with_auth_headers(auth_headers)
     }
-}
-
-// Packet Receiver
-#[derive(Clone)]
-struct PacketReceiver {
-    socket: Arc<Mutex<UdpSocket>>,
-    server_addr: SocketAddr,
-    buffer: [u8; 1472],
-}
-
-impl PacketReceiver {
-    pub fn new(socket: Arc<Mutex<UdpSocket>>, server_addr: SocketAddr) -> Self {
-        return Self {
-            socket,
-            server_addr,
-            buffer: [0; 1472],
-        };
-    }
-}
-
-impl TransportReceiver for PacketReceiver {
-    /// Receives a packet from the Client Socket
-    fn receive(&mut self) -> Result<Option<&[u8]>, RecvError> {
-        match self
-            .socket
-            .as_ref()
-            .lock()
-            .unwrap()
-            .recv_from(&mut self.buffer)
-        {
-            Ok((recv_len, address)) => {
-                if address == self.server_addr {
-                    Ok(Some(&self.buffer[..recv_len]))
-                } else {
-                    Err(RecvError)
-                }
-            }
-            Err(ref e) => {
-                let kind = e.kind();
-                match kind {
-                    ErrorKind::WouldBlock => {
-                        //just didn't receive anything this time
-                        return Ok(None);
-                    }
-                    _ => {
-                        return Err(RecvError);
-                    }
-                }
-            }
-        }
-    }
-    /// Get the Server's Socket address
-    fn server_addr(&self) -> TransportAddr {
-        TransportAddr::Found(self.server_addr)
-    }
-}
-
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-
-/// Helper method to find local IP address, if possible
-pub fn find_my_ip_address() -> Option<IpAddr> {
-    let ip = local_ipaddress::get().unwrap_or_default();
-
-    if let Ok(addr) = ip.parse::<Ipv4Addr>() {
-        Some(IpAddr::V4(addr))
-    } else if let Ok(addr) = ip.parse::<Ipv6Addr>() {
-        Some(IpAddr::V6(addr))
-    } else {
-        None
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        auth_bytes: Vec<u8>,
+        auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        Box::new(self.inner).connect_with_auth_and_headers(auth_bytes, auth_headers)
     }
 }
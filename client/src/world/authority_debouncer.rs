@@ -0,0 +1,125 @@
+use std::{collections::HashMap, hash::Hash, time::Duration};
+
+use naia_shared::Instant;
+
+// AuthorityChange
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuthorityChange {
+    Request,
+    Release,
+}
+
+// PendingChange
+struct PendingChange {
+    change: AuthorityChange,
+    since: Instant,
+}
+
+// AuthorityDebouncer
+/// Holds back authority requests & releases for an Entity until the debounce
+/// window has passed since the first of them, so that a burst of calls only
+/// results in its final intended state being applied
+pub struct AuthorityDebouncer<E: Copy + Eq + Hash> {
+    window: Duration,
+    pending: HashMap<E, PendingChange>,
+}
+
+impl<E: Copy + Eq + Hash> AuthorityDebouncer<E> {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    /// Records the latest intended authority change for an Entity
+    pub fn queue(&mut self, entity: &E, change: AuthorityChange, now: &Instant) {
+        if let Some(pending) = self.pending.get_mut(entity) {
+            pending.change = change;
+        } else {
+            self.pending.insert(
+                *entity,
+                PendingChange {
+                    change,
+                    since: now.clone(),
+                },
+            );
+        }
+    }
+
+    /// Takes the changes whose debounce window has passed
+    pub fn take_ready(&mut self, now: &Instant) -> Vec<(E, AuthorityChange)> {
+        let mut output = Vec::new();
+        self.pending.retain(|entity, pending| {
+            if pending.since.elapsed(now) < self.window {
+                return true;
+            }
+            output.push((*entity, pending.change));
+            false
+        });
+        output
+    }
+
+    pub fn remove(&mut self, entity: &E) {
+        self.pending.remove(entity);
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use naia_shared::Instant;
+
+    use super::{AuthorityChange, AuthorityDebouncer};
+
+    #[test]
+    fn burst_is_coalesced_into_final_change() {
+        let mut debouncer = AuthorityDebouncer::<u32>::new(Duration::from_millis(50));
+        let mut now = Instant::now();
+
+        debouncer.queue(&1, AuthorityChange::Request, &now);
+        now.add_millis(10);
+        debouncer.queue(&1, AuthorityChange::Release, &now);
+        now.add_millis(10);
+        debouncer.queue(&1, AuthorityChange::Request, &now);
+        assert!(debouncer.take_ready(&now).is_empty());
+
+        // the window is measured from the first call, so continued tapping
+        // cannot hold the change back forever
+        now.add_millis(30);
+        debouncer.queue(&1, AuthorityChange::Release, &now);
+        assert_eq!(
+            debouncer.take_ready(&now),
+            vec![(1, AuthorityChange::Release)]
+        );
+        assert!(debouncer.take_ready(&now).is_empty());
+    }
+
+    #[test]
+    fn entities_are_debounced_independently() {
+        let mut debouncer = AuthorityDebouncer::<u32>::new(Duration::from_millis(50));
+        let mut now = Instant::now();
+
+        debouncer.queue(&1, AuthorityChange::Request, &now);
+        now.add_millis(40);
+        debouncer.queue(&2, AuthorityChange::Request, &now);
+        now.add_millis(10);
+
+        assert_eq!(
+            debouncer.take_ready(&now),
+            vec![(1, AuthorityChange::Request)]
+        );
+        debouncer.remove(&2);
+        now.add_millis(50);
+        assert!(debouncer.take_ready(&now).is_empty());
+    }
+}
@@ -1,10 +1,19 @@
 use std::hash::Hash;
 
-use naia_shared::{EntityAuthStatus, ReplicaMutWrapper, ReplicatedComponent, WorldMutType};
+use naia_shared::{
+    ComponentKind, EntityAuthStatus, ReplicaDynMutWrapper, ReplicaMutWrapper, ReplicatedComponent,
+    WorldMutType,
+};
 
 use crate::{Client, ReplicationConfig};
 
 // EntityMut
+/// A handle for mutating a single entity.
+///
+/// Every operation is applied to the world and to the replication state as
+/// soon as it is called; nothing is buffered inside the handle. Dropping an
+/// `EntityMut` at any point, including part-way through a chain of calls,
+/// therefore never leaves partially registered components behind.
 pub struct EntityMut<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> {
     client: &'s mut Client<E>,
     world: W,
@@ -38,6 +47,20 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self.world.component_mut::<R>(&self.entity)
     }
 
+    /// Returns the kinds of all of this Entity's replicated Components, so
+    /// that they can be accessed without naming their types
+    pub fn component_kinds(&self) -> Vec<ComponentKind> {
+        self.world.component_kinds(&self.entity)
+    }
+
+    pub fn component_of_kind(
+        &mut self,
+        component_kind: &ComponentKind,
+    ) -> Option<ReplicaDynMutWrapper<'_>> {
+        self.world
+            .component_mut_of_kind(&self.entity, component_kind)
+    }
+
     pub fn insert_component<R: ReplicatedComponent>(&mut self, component_ref: R) -> &mut Self {
         self.client
             .insert_component(&mut self.world, &self.entity, component_ref);
@@ -79,3 +102,78 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use naia_demo_world::{Entity, World, WorldRefType};
+    use naia_shared::{Property, Protocol, Replicate, ReplicatedComponent};
+
+    use crate::{Client, ClientConfig, ReplicationConfig};
+
+    #[derive(Replicate)]
+    #[cfg_attr(feature = "bevy_support", derive(bevy_ecs::component::Component))]
+    pub struct Position {
+        pub x: Property<i16>,
+    }
+
+    impl Position {
+        pub fn new(x: i16) -> Self {
+            Self::new_complete(x)
+        }
+    }
+
+    impl ReplicatedComponent for Position {}
+
+    #[derive(Replicate)]
+    #[cfg_attr(feature = "bevy_support", derive(bevy_ecs::component::Component))]
+    pub struct Color {
+        pub index: Property<u8>,
+    }
+
+    impl Color {
+        pub fn new(index: u8) -> Self {
+            Self::new_complete(index)
+        }
+    }
+
+    impl ReplicatedComponent for Color {}
+
+    fn client() -> Client<Entity> {
+        let protocol = Protocol::builder()
+            .add_component::<Position>()
+            .add_component::<Color>()
+            .enable_client_authoritative_entities()
+            .build();
+        Client::new(ClientConfig::default(), protocol)
+    }
+
+    #[test]
+    fn abandoned_entity_mut_leaves_no_partial_state() {
+        let mut client = client();
+        let mut world = World::default();
+
+        // build an entity, then drop the handle before finishing
+        let entity = {
+            let mut entity_mut = client.spawn_entity(world.proxy_mut());
+            entity_mut.insert_component(Position::new(4));
+            entity_mut.id()
+        };
+
+        assert!(world.proxy().has_component::<Position>(&entity));
+        assert!(!world.proxy().has_component::<Color>(&entity));
+        assert_eq!(
+            client.entity_replication_config(&entity),
+            Some(ReplicationConfig::Private)
+        );
+
+        // a later handle picks up where the abandoned one left off
+        client
+            .entity_mut(world.proxy_mut(), &entity)
+            .insert_component(Color::new(2));
+        assert!(world.proxy().has_component::<Color>(&entity));
+
+        client.entity_mut(world.proxy_mut(), &entity).despawn();
+        assert!(!world.proxy().has_entity(&entity));
+        assert_eq!(client.entity_replication_config(&entity), None);
+    }
+}
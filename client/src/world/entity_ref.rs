@@ -1,6 +1,9 @@
 use std::hash::Hash;
 
-use naia_shared::{EntityAuthStatus, ReplicaRefWrapper, ReplicatedComponent, WorldRefType};
+use naia_shared::{
+    ComponentKind, EntityAuthStatus, ReplicaDynRefWrapper, ReplicaRefWrapper, ReplicatedComponent,
+    WorldRefType,
+};
 
 use crate::{Client, ReplicationConfig};
 
@@ -32,6 +35,19 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>> EntityRef<'s, E,
         self.world.component::<R>(&self.entity)
     }
 
+    /// Returns the kinds of all of this Entity's replicated Components, so
+    /// that they can be accessed without naming their types
+    pub fn component_kinds(&self) -> Vec<ComponentKind> {
+        self.world.component_kinds(&self.entity)
+    }
+
+    pub fn component_of_kind(
+        &self,
+        component_kind: &ComponentKind,
+    ) -> Option<ReplicaDynRefWrapper<'_>> {
+        self.world.component_of_kind(&self.entity, component_kind)
+    }
+
     pub fn replication_config(&self) -> Option<ReplicationConfig> {
         self.client.entity_replication_config(&self.entity)
     }
@@ -40,3 +56,77 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>> EntityRef<'s, E,
         self.client.entity_authority_status(&self.entity)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use naia_demo_world::{Entity, World};
+    use naia_shared::{ComponentKind, Property, Protocol, Replicate, ReplicatedComponent};
+
+    use crate::{Client, ClientConfig};
+
+    #[derive(Replicate)]
+    #[cfg_attr(feature = "bevy_support", derive(bevy_ecs::component::Component))]
+    pub struct Position {
+        pub x: Property<i16>,
+    }
+
+    impl Position {
+        pub fn new(x: i16) -> Self {
+            Self::new_complete(x)
+        }
+    }
+
+    impl ReplicatedComponent for Position {}
+
+    #[derive(Replicate)]
+    #[cfg_attr(feature = "bevy_support", derive(bevy_ecs::component::Component))]
+    pub struct Color {
+        pub index: Property<u8>,
+    }
+
+    impl Color {
+        pub fn new(index: u8) -> Self {
+            Self::new_complete(index)
+        }
+    }
+
+    impl ReplicatedComponent for Color {}
+
+    fn client() -> Client<Entity> {
+        let protocol = Protocol::builder()
+            .add_component::<Position>()
+            .add_component::<Color>()
+            .enable_client_authoritative_entities()
+            .build();
+        Client::new(ClientConfig::default(), protocol)
+    }
+
+    #[test]
+    fn iterates_components_without_naming_their_types() {
+        let mut client = client();
+        let mut world = World::default();
+
+        let entity = client
+            .spawn_entity(world.proxy_mut())
+            .insert_component(Position::new(4))
+            .insert_component(Color::new(2))
+            .id();
+
+        let entity_ref = client.entity(world.proxy(), &entity);
+        let component_kinds = entity_ref.component_kinds();
+        assert_eq!(component_kinds.len(), 2);
+        assert!(component_kinds.contains(&ComponentKind::of::<Position>()));
+        assert!(component_kinds.contains(&ComponentKind::of::<Color>()));
+
+        for component_kind in &component_kinds {
+            let component = entity_ref.component_of_kind(component_kind).unwrap();
+            assert_eq!(component.kind(), *component_kind);
+        }
+
+        let position = entity_ref
+            .component_of_kind(&ComponentKind::of::<Position>())
+            .unwrap();
+        let position = position.to_any().downcast_ref::<Position>().unwrap();
+        assert_eq!(*position.x, 4);
+    }
+}
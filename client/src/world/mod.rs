@@ -1,3 +1,4 @@
+pub mod authority_debouncer;
 pub mod entity_mut;
 pub mod entity_owner;
 pub mod entity_ref;
@@ -40,7 +40,9 @@ impl App {
         let auth = Auth::new("charlie", "12345");
         client.auth(auth);
 
-        client.connect(socket);
+        client
+            .connect(socket)
+            .expect("a new Client cannot already be connected");
 
         App {
             client,
@@ -60,18 +62,17 @@ impl App {
         for server_address in events.read::<ConnectEvent>() {
             info!("Client connected to: {}", server_address);
         }
-        for server_address in events.read::<RejectEvent>() {
-            info!(
-                "Client received unauthorized response from: {}",
-                server_address
-            );
+        for reason in events.read::<RejectEvent>() {
+            info!("Client rejected by Server: {:?}", reason);
 
             // Now give the correct username / password
             let auth = Auth::new("charlie", "12345");
             self.client.auth(auth);
 
             let socket = webrtc::Socket::new("http://127.0.0.1:14191", &self.socket_config);
-            self.client.connect(socket);
+            if let Err(err) = self.client.connect(socket) {
+                info!("Unable to reconnect: {}", err);
+            }
         }
         for server_address in events.read::<DisconnectEvent>() {
             info!("Client disconnected from: {}", server_address);
@@ -135,7 +135,8 @@ impl App {
                         let new_message =
                             StringMessage::<MyMarker>::new(new_message_contents, MyMarker);
                         self.server
-                            .send_message::<UnorderedReliableChannel, _>(&user_key, &new_message);
+                            .send_message::<UnorderedReliableChannel, _>(&user_key, &new_message)
+                            .unwrap();
                     }
                 }
 
@@ -71,8 +71,11 @@ pub fn connect_events(
 }
 
 pub fn reject_events(mut event_reader: EventReader<RejectEvent<Main>>) {
-    for _ in event_reader.read() {
-        info!("Client rejected from connecting to Server");
+    for event in event_reader.read() {
+        info!(
+            "Client rejected from connecting to Server: {:?}",
+            event.reason
+        );
     }
 }
 
@@ -173,7 +176,9 @@ pub fn request_events(
             info!("Client received Request <- Server: {:?}", request);
             let response = BasicResponse::new("ClientResponse".to_string(), request.index);
             info!("Client sending Response -> Server: {:?}", response);
-            client.send_response(&response_send_key, &response);
+            if let Err(err) = client.send_response(&response_send_key, &response) {
+                info!("Unable to send Response: {}", err);
+            }
         }
     }
 }
@@ -181,9 +186,16 @@ pub fn request_events(
 pub fn response_events(mut client: Client<Main>, mut global: ResMut<Global>) {
     let mut finished_response_keys = Vec::new();
     for response_key in &global.response_keys {
-        if let Some(response) = client.receive_response(response_key) {
-            info!("Client received Response <- Server: {:?}", response);
-            finished_response_keys.push(response_key.clone());
+        match client.receive_response(response_key) {
+            Some(Ok(response)) => {
+                info!("Client received Response <- Server: {:?}", response);
+                finished_response_keys.push(response_key.clone());
+            }
+            Some(Err(err)) => {
+                info!("Client received no Response <- Server: {}", err);
+                finished_response_keys.push(response_key.clone());
+            }
+            None => {}
         }
     }
     for response_key in finished_response_keys {
@@ -412,7 +424,11 @@ pub fn tick_events(
         global.command_history.insert(client_tick, command.clone());
 
         // Send command
-        client.send_tick_buffer_message::<PlayerCommandChannel, KeyCommand>(&client_tick, &command);
+        if let Err(err) = client
+            .send_tick_buffer_message::<PlayerCommandChannel, KeyCommand>(&client_tick, &command)
+        {
+            info!("Unable to send command: {}", err);
+        }
 
         if let Ok(mut position) = position_query.get_mut(predicted_entity) {
             // Apply command
@@ -18,7 +18,9 @@ pub fn init(
 
     client.auth(Auth::new("charlie", "12345"));
     let socket = webrtc::Socket::new("http://127.0.0.1:14191", client.socket_config());
-    client.connect(socket);
+    client
+        .connect(socket)
+        .expect("a new Client cannot already be connected");
 
     // Setup Camera
     commands.spawn(Camera2dBundle::default());
@@ -84,10 +84,12 @@ pub fn connect_events(
         let mut assignment_message = EntityAssignment::new(true);
         assignment_message.entity.set(&server, &entity);
 
-        server.send_message::<EntityAssignmentChannel, EntityAssignment>(
-            user_key,
-            &assignment_message,
-        );
+        server
+            .send_message::<EntityAssignmentChannel, EntityAssignment>(
+                user_key,
+                &assignment_message,
+            )
+            .unwrap();
     }
 }
 
@@ -84,6 +84,10 @@ impl<'w> WorldRefType<Entity> for WorldRef<'w> {
         has_component_of_type(self.world, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component(self.world, entity)
     }
@@ -114,6 +118,10 @@ impl<'w> WorldRefType<Entity> for WorldMut<'w> {
         has_component_of_type(self.world, entity, component_kind)
     }
 
+    fn component_kinds(&self, entity: &Entity) -> Vec<ComponentKind> {
+        component_kinds(self.world, entity)
+    }
+
     fn component<R: Replicate>(&self, entity: &Entity) -> Option<ReplicaRefWrapper<R>> {
         component(self.world, entity)
     }
@@ -142,7 +150,20 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
     }
 
     fn local_duplicate_components(&mut self, new_entity: &Entity, old_entity: &Entity) {
+        let component_kinds = self.component_kinds(old_entity);
+        self.local_duplicate_components_of_kinds(new_entity, old_entity, &component_kinds);
+    }
+
+    fn local_duplicate_components_of_kinds(
+        &mut self,
+        new_entity: &Entity,
+        old_entity: &Entity,
+        component_kinds: &[ComponentKind],
+    ) {
         for component_kind in self.component_kinds(old_entity) {
+            if !component_kinds.contains(&component_kind) {
+                continue;
+            }
             let mut boxed_option: Option<Box<dyn Replicate>> = None;
             if let Some(component) = self.component_of_kind(old_entity, &component_kind) {
                 boxed_option = Some(component.copy_to_box());
@@ -159,18 +180,6 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         self.world.entities.remove(entity);
     }
 
-    fn component_kinds(&mut self, entity: &Entity) -> Vec<ComponentKind> {
-        let mut output: Vec<ComponentKind> = Vec::new();
-
-        if let Some(component_map) = self.world.entities.get(entity) {
-            for component_kind in component_map.keys() {
-                output.push(*component_kind);
-            }
-        }
-
-        output
-    }
-
     fn component_mut<R: Replicate>(&mut self, entity: &Entity) -> Option<ReplicaMutWrapper<R>> {
         if let Some(component_map) = self.world.entities.get_mut(entity) {
             if let Some(boxed_component) = component_map.get_mut(&ComponentKind::of::<R>()) {
@@ -305,7 +314,7 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         global_world_manager: &dyn GlobalWorldManagerType<Entity>,
         entity: &Entity,
     ) {
-        for component_kind in WorldMutType::<Entity>::component_kinds(self, entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
             WorldMutType::<Entity>::component_publish(
                 self,
                 global_world_manager,
@@ -334,37 +343,71 @@ impl<'w> WorldMutType<Entity> for WorldMut<'w> {
         }
     }
 
-    fn entity_unpublish(&mut self, _entity: &Entity) {
-        todo!()
+    fn entity_unpublish(&mut self, entity: &Entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_unpublish(self, entity, &component_kind);
+        }
     }
 
-    fn component_unpublish(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
+    fn component_unpublish(&mut self, entity: &Entity, component_kind: &ComponentKind) {
+        if let Some(component_map) = self.world.entities.get_mut(entity) {
+            if let Some(component) = component_map.get_mut(component_kind) {
+                component.unpublish();
+            }
+        }
     }
 
     fn entity_enable_delegation(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
     ) {
-        todo!()
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_enable_delegation(
+                self,
+                global_world_manager,
+                entity,
+                &component_kind,
+            );
+        }
     }
 
     fn component_enable_delegation(
         &mut self,
-        _global_world_manager: &dyn GlobalWorldManagerType<Entity>,
-        _entity: &Entity,
-        _component_kind: &ComponentKind,
+        global_world_manager: &dyn GlobalWorldManagerType<Entity>,
+        entity: &Entity,
+        component_kind: &ComponentKind,
     ) {
-        todo!()
+        if let Some(component_map) = self.world.entities.get_mut(entity) {
+            if let Some(component) = component_map.get_mut(component_kind) {
+                let accessor = global_world_manager.get_entity_auth_accessor(entity);
+                if global_world_manager.entity_needs_mutator_for_delegation(entity) {
+                    let diff_mask_size = component.diff_mask_size();
+                    let mutator = global_world_manager.register_component(
+                        entity,
+                        component_kind,
+                        diff_mask_size,
+                    );
+                    component.enable_delegation(&accessor, Some(&mutator));
+                } else {
+                    component.enable_delegation(&accessor, None);
+                }
+            }
+        }
     }
 
-    fn entity_disable_delegation(&mut self, _entity: &Entity) {
-        todo!()
+    fn entity_disable_delegation(&mut self, entity: &Entity) {
+        for component_kind in WorldRefType::<Entity>::component_kinds(self, entity) {
+            WorldMutType::<Entity>::component_disable_delegation(self, entity, &component_kind);
+        }
     }
 
-    fn component_disable_delegation(&mut self, _entity: &Entity, _component_kind: &ComponentKind) {
-        todo!()
+    fn component_disable_delegation(&mut self, entity: &Entity, component_kind: &ComponentKind) {
+        if let Some(component_map) = self.world.entities.get_mut(entity) {
+            if let Some(component) = component_map.get_mut(component_kind) {
+                component.disable_delegation();
+            }
+        }
     }
 }
 
@@ -400,6 +443,18 @@ fn has_component_of_type(world: &World, entity: &Entity, component_kind: &Compon
     false
 }
 
+fn component_kinds(world: &World, entity: &Entity) -> Vec<ComponentKind> {
+    let mut output: Vec<ComponentKind> = Vec::new();
+
+    if let Some(component_map) = world.entities.get(entity) {
+        for component_kind in component_map.keys() {
+            output.push(*component_kind);
+        }
+    }
+
+    output
+}
+
 fn component<'a, R: Replicate>(
     world: &'a World,
     entity: &Entity,
@@ -19,7 +19,9 @@ pub fn app_init(
     let socket = webrtc::Socket::new(server_addr, protocol.socket_config());
     let mut client = Client::new(client_config, protocol);
     client.auth(auth);
-    client.connect(socket);
+    client
+        .connect(socket)
+        .expect("a new Client cannot already be connected");
 
     App {
         client,
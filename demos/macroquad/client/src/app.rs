@@ -62,7 +62,9 @@ impl App {
         let socket = webrtc::Socket::new("http://127.0.0.1:14191", &protocol.socket);
         let mut client = Client::new(ClientConfig::default(), protocol);
         client.auth(Auth::new("charlie", "12345"));
-        client.connect(socket);
+        client
+            .connect(socket)
+            .expect("a new Client cannot already be connected");
 
         App {
             client,
@@ -251,8 +253,12 @@ impl App {
             self.command_history.insert(client_tick, command.clone());
 
             // Send command
-            self.client
-                .send_tick_buffer_message::<PlayerCommandChannel, _>(&client_tick, &command);
+            if let Err(err) = self
+                .client
+                .send_tick_buffer_message::<PlayerCommandChannel, _>(&client_tick, &command)
+            {
+                info!("Unable to send command: {}", err);
+            }
 
             // Apply command
             if let Some(mut position) = self
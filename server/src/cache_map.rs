@@ -20,6 +20,7 @@ impl<K: Eq + Hash + Clone, V: Clone> CacheMap<K, V> {
         self.map.contains_key(key)
     }
 
+    #[cfg(feature = "advanced_handshake")]
     pub fn get_unchecked(&self, key: &K) -> &V {
         self.map
             .get(key)
@@ -36,4 +37,10 @@ impl<K: Eq + Hash + Clone, V: Clone> CacheMap<K, V> {
         self.keys.push_back(key.clone());
         self.map.insert(key, value);
     }
+
+    #[cfg(feature = "advanced_handshake")]
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.keys.clear();
+    }
 }
@@ -4,12 +4,15 @@ use log::warn;
 
 use naia_shared::{
     BaseConnection, BigMapKey, BitReader, BitWriter, ChannelKind, ChannelKinds, ConnectionConfig,
-    EntityEventMessage, EntityResponseEvent, HostType, HostWorldEvents, Instant, PacketType,
-    Protocol, Serde, SerdeErr, StandardHeader, SystemChannel, Tick, WorldMutType, WorldRefType,
+    EntityEvent, EntityEventMessage, EntityResponseEvent, FileTransferAction, FileTransferChannel,
+    FileTransferMessage, FileTransferSender, HostType, HostWorldEvents, IdempotentMessage, Instant,
+    MessageContainer, MessageKind, PacketType, Protocol, Serde, SerdeErr, StandardHeader,
+    SystemChannel, Tick, WorldMutType, WorldRefType,
 };
 
 use crate::request::{GlobalRequestManager, GlobalResponseManager};
 use crate::{
+    cache_map::CacheMap,
     connection::{
         io::Io, ping_config::PingConfig, tick_buffer_messages::TickBufferMessages,
         tick_buffer_receiver::TickBufferReceiver,
@@ -17,7 +20,10 @@ use crate::{
     events::Events,
     time_manager::TimeManager,
     user::UserKey,
-    world::global_world_manager::GlobalWorldManager,
+    world::{
+        component_validator::ComponentInsertValidator, global_world_manager::GlobalWorldManager,
+        server_auth_handler::AuthOwner,
+    },
 };
 
 use super::ping_manager::PingManager;
@@ -27,6 +33,7 @@ pub struct Connection<E: Copy + Eq + Hash + Send + Sync> {
     pub user_key: UserKey,
     pub base: BaseConnection<E>,
     pub ping_manager: PingManager,
+    pub file_transfer_sender: FileTransferSender,
     tick_buffer: TickBufferReceiver,
 }
 
@@ -51,10 +58,31 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                 global_world_manager,
             ),
             ping_manager: PingManager::new(ping_config),
+            file_transfer_sender: FileTransferSender::new(),
             tick_buffer: TickBufferReceiver::new(channel_kinds),
         }
     }
 
+    /// Tears the Connection down once its User has disconnected, consuming
+    /// it so that its channels, buffered messages, and Entity waitlist are
+    /// dropped together. Releases the authority the User held over Delegated
+    /// Entities, along with any grants it had yet to reply to, and returns the
+    /// Entities released so that other Users can be told they are Available
+    pub fn teardown(self, global_world_manager: &mut GlobalWorldManager<E>) -> Vec<E> {
+        let owner = AuthOwner::Client(self.user_key);
+        let held_entities: Vec<E> = global_world_manager
+            .user_all_owned_entities(&self.user_key)
+            .map(|entities| entities.iter().copied().collect())
+            .unwrap_or_default();
+        let released_entities = held_entities
+            .into_iter()
+            .filter(|entity| global_world_manager.client_release_authority(entity, &owner))
+            .collect();
+        global_world_manager.remove_user_authority_grants(&self.user_key);
+
+        released_entities
+    }
+
     // Incoming Data
 
     pub fn process_incoming_header(&mut self, header: &StandardHeader) {
@@ -100,8 +128,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         global_world_manager: &mut GlobalWorldManager<E>,
         global_request_manager: &mut GlobalRequestManager,
         global_response_manager: &mut GlobalResponseManager,
+        component_insert_validator: Option<&dyn ComponentInsertValidator>,
         world: &mut W,
         incoming_events: &mut Events<E>,
+        processed_message_keys: &mut CacheMap<u64, ()>,
     ) -> Vec<EntityResponseEvent<E>> {
         let mut response_events = Vec::new();
         // Receive Message Events
@@ -134,8 +164,46 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
                         }
                     };
                 }
+            } else if channel_kind == ChannelKind::of::<FileTransferChannel>() {
+                for message in messages {
+                    let Some(file_message) = Box::<dyn Any + 'static>::downcast::<
+                        FileTransferMessage,
+                    >(message.to_boxed_any())
+                    .ok()
+                    .map(|boxed_m| *boxed_m) else {
+                        warn!(
+                            "Received unknown message over FileTransferChannel from {}",
+                            &self.address
+                        );
+                        continue;
+                    };
+                    match file_message.action {
+                        FileTransferAction::Accept { offset } => {
+                            self.file_transfer_sender.accept(&file_message.id, offset);
+                        }
+                        FileTransferAction::Received { offset } => {
+                            self.file_transfer_sender.receive(&file_message.id, offset);
+                        }
+                        action => {
+                            warn!(
+                                "Received `{:?}` over FileTransferChannel from {}, but Clients may not send files",
+                                action, &self.address
+                            );
+                        }
+                    }
+                }
             } else {
                 for message in messages {
+                    let message = if message.kind() == MessageKind::of::<IdempotentMessage>() {
+                        let Some(message) =
+                            Self::receive_idempotent_message(processed_message_keys, message)
+                        else {
+                            continue;
+                        };
+                        message
+                    } else {
+                        message
+                    };
                     incoming_events.push_message(&self.user_key, &channel_kind, message);
                 }
             }
@@ -161,20 +229,54 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         }
         // Responses
         for (global_request_id, response) in responses {
+            incoming_events.push_response(&self.user_key, global_request_id, response.kind());
             global_request_manager.receive_response(&global_request_id, response);
         }
 
         // Receive World Events
         if protocol.client_authoritative_entities {
-            let remote_events = self.base.remote_world_reader.take_incoming_events();
-            let world_events = self.base.remote_world_manager.process_world_events(
+            let mut remote_events = self.base.remote_world_reader.take_incoming_events();
+            remote_events.incoming_updates.retain(|(_, _, update)| {
+                if protocol.component_is_server_authoritative(&update.kind) {
+                    warn!(
+                        "Rejected update to server-authoritative Component `{}` from {}",
+                        protocol.component_kinds.kind_to_name(&update.kind),
+                        &self.address
+                    );
+                    return false;
+                }
+                true
+            });
+            let mut world_events = self.base.remote_world_manager.process_world_events(
                 global_world_manager,
                 &mut self.base.local_world_manager,
                 &protocol.component_kinds,
                 world,
                 now,
                 remote_events,
+                None,
             );
+            if let Some(validator) = component_insert_validator {
+                world_events.retain(|event| {
+                    let EntityEvent::InsertComponent(entity, component_kind) = event else {
+                        return true;
+                    };
+                    let accepted = world
+                        .component_of_kind(entity, component_kind)
+                        .map(|component| validator.validate(&self.user_key, &*component))
+                        .unwrap_or(true);
+                    if !accepted {
+                        warn!(
+                            "Rejected insert of Component `{}` from {:?} at {}",
+                            protocol.component_kinds.kind_to_name(component_kind),
+                            &self.user_key,
+                            &self.address
+                        );
+                        world.remove_component_of_kind(entity, component_kind);
+                    }
+                    accepted
+                });
+            }
             response_events
                 .extend(incoming_events.receive_entity_events(&self.user_key, world_events));
         }
@@ -182,6 +284,40 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         return response_events;
     }
 
+    /// Unwraps a keyed Message, or returns None if a Message with the same
+    /// key has already been processed. Keys are remembered by the Server
+    /// rather than each Connection, so a Message retried after reconnecting
+    /// is still recognized
+    fn receive_idempotent_message(
+        processed_message_keys: &mut CacheMap<u64, ()>,
+        message: MessageContainer,
+    ) -> Option<MessageContainer> {
+        let idempotent_message = message
+            .to_boxed_any()
+            .downcast::<IdempotentMessage>()
+            .unwrap();
+        let key = idempotent_message.key();
+        if processed_message_keys.contains_key(&key) {
+            return None;
+        }
+        processed_message_keys.insert(key, ());
+        Some(idempotent_message.unwrap())
+    }
+
+    /// Queues the next chunks of any file transfers the Client has accepted,
+    /// and reports their progress
+    pub fn send_file_chunks(&mut self, protocol: &Protocol, incoming_events: &mut Events<E>) {
+        let progress = self
+            .file_transfer_sender
+            .send_chunks(&protocol.message_kinds, &mut self.base.message_manager);
+        for (id, received, size) in progress {
+            incoming_events.push_file_transfer_progress(&self.user_key, &id, received, size);
+            if received == size {
+                incoming_events.push_file_transfer_complete(&self.user_key, &id);
+            }
+        }
+    }
+
     pub fn tick_buffer_messages(&mut self, tick: &Tick, messages: &mut TickBufferMessages) {
         let channel_messages = self.tick_buffer.receive_messages(tick);
         for (channel_kind, received_messages) in channel_messages {
@@ -201,6 +337,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         global_world_manager: &GlobalWorldManager<E>,
         time_manager: &TimeManager,
     ) {
+        if !self.base.should_send_packets(now) {
+            return;
+        }
         let rtt_millis = self.ping_manager.rtt_average;
         self.base.collect_messages(now, &rtt_millis);
         let mut host_world_events = self.base.host_world_manager.take_outgoing_events(
@@ -208,7 +347,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             global_world_manager,
             now,
             &rtt_millis,
+            &time_manager.current_tick(),
         );
+        self.base.limit_outgoing_events(now, &mut host_world_events);
 
         let mut any_sent = false;
         loop {
@@ -243,6 +384,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
         time_manager: &TimeManager,
         host_world_events: &mut HostWorldEvents<E>,
     ) -> bool {
+        if !self.base.can_send_packet() {
+            return false;
+        }
         if host_world_events.has_events() || self.base.message_manager.has_outgoing_messages() {
             let writer = self.write_packet(
                 protocol,
@@ -254,7 +398,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             );
 
             // send packet
-            if io.send_packet(&self.address, writer.to_packet()).is_err() {
+            let packet = writer.to_packet();
+            self.base.record_sent_packet(packet.slice().len());
+            if io.send_packet(&self.address, packet).is_err() {
                 // TODO: pass this on and handle above
                 warn!("Server Error: Cannot send data packet to {}", &self.address);
             }
@@ -1,6 +1,8 @@
-use std::{net::SocketAddr, panic, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, panic, time::Duration};
 
-use naia_shared::{CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader};
+use naia_shared::{
+    ByteCounter, CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader,
+};
 
 use super::bandwidth_monitor::BandwidthMonitor;
 use crate::{
@@ -8,6 +10,10 @@ use crate::{
     transport::{PacketReceiver, PacketSender},
 };
 
+// The window over which bytes per second are measured for each connection,
+// unless bandwidth monitoring sets one
+const DEFAULT_BYTE_COUNT_WINDOW: Duration = Duration::from_secs(1);
+
 pub struct Io {
     packet_sender: Option<Box<dyn PacketSender>>,
     packet_receiver: Option<Box<dyn PacketReceiver>>,
@@ -15,6 +21,9 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    // bytes (received, sent) over each connection
+    byte_counters: HashMap<SocketAddr, (ByteCounter, ByteCounter)>,
+    byte_count_window: Duration,
 }
 
 impl Io {
@@ -45,6 +54,8 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            byte_counters: HashMap::new(),
+            byte_count_window: bandwidth_measure_duration.unwrap_or(DEFAULT_BYTE_COUNT_WINDOW),
         }
     }
 
@@ -82,6 +93,9 @@ impl Io {
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
             monitor.record_packet(address, payload.len());
         }
+        if let Some((_, sent)) = self.byte_counters.get_mut(address) {
+            sent.record_packet(payload.len());
+        }
 
         self.packet_sender
             .as_ref()
@@ -103,6 +117,9 @@ impl Io {
                 if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                     monitor.record_packet(&address, payload.len());
                 }
+                if let Some((received, _)) = self.byte_counters.get_mut(&address) {
+                    received.record_packet(payload.len());
+                }
 
                 // Decompression
                 if let Some(decoder) = &mut self.incoming_decoder {
@@ -116,6 +133,29 @@ impl Io {
         }
     }
 
+    /// Starts counting the bytes sent to and received from the address
+    pub fn count_bytes(&mut self, address: &SocketAddr) {
+        self.byte_counters.insert(
+            *address,
+            (
+                ByteCounter::new(self.byte_count_window),
+                ByteCounter::new(self.byte_count_window),
+            ),
+        );
+    }
+
+    pub fn stop_counting_bytes(&mut self, address: &SocketAddr) {
+        self.byte_counters.remove(address);
+    }
+
+    /// Gets the counters of bytes (received, sent) for the address
+    pub fn byte_counters(
+        &mut self,
+        address: &SocketAddr,
+    ) -> Option<&mut (ByteCounter, ByteCounter)> {
+        self.byte_counters.get_mut(address)
+    }
+
     pub fn bandwidth_monitor_enabled(&self) -> bool {
         self.outgoing_bandwidth_monitor.is_some() && self.incoming_bandwidth_monitor.is_some()
     }
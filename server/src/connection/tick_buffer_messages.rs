@@ -27,6 +27,11 @@ impl TickBufferMessages {
         self.empty = false;
     }
 
+    /// How many Messages were received over each Channel
+    pub(crate) fn message_counts(&self) -> impl Iterator<Item = (&ChannelKind, usize)> {
+        events::channel_message_counts(&self.messages)
+    }
+
     pub fn read<C: Channel, M: Message>(&mut self) -> Vec<(UserKey, M)> {
         return events::read_channel_messages::<C, M>(&mut self.messages);
     }
@@ -3,8 +3,9 @@ use std::{any::Any, collections::HashMap, marker::PhantomData, mem, vec::IntoIte
 use log::warn;
 
 use naia_shared::{
-    Channel, ChannelKind, ComponentKind, EntityEvent, EntityResponseEvent, GlobalResponseId,
-    Message, MessageContainer, MessageKind, Replicate, Request, ResponseSendKey, Tick,
+    Channel, ChannelKind, ComponentKind, EntityEvent, EntityResponseEvent, FileTransferId,
+    GlobalRequestId, GlobalResponseId, Message, MessageContainer, MessageKind, Replicate, Request,
+    Response, ResponseReceiveKey, ResponseSendKey, Tick,
 };
 
 use super::user::{User, UserKey};
@@ -17,10 +18,14 @@ pub struct Events<E: Copy> {
     errors: Vec<NaiaServerError>,
     auths: HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
+    expired_messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
     requests: HashMap<
         ChannelKind,
         HashMap<MessageKind, Vec<(UserKey, GlobalResponseId, MessageContainer)>>,
     >,
+    responses: HashMap<MessageKind, Vec<(UserKey, GlobalRequestId)>>,
+    file_transfer_progresses: Vec<(UserKey, FileTransferId, u32, u32)>,
+    file_transfer_completions: Vec<(UserKey, FileTransferId)>,
     spawns: Vec<(UserKey, E)>,
     despawns: Vec<(UserKey, E)>,
     publishes: Vec<(UserKey, E)>,
@@ -43,7 +48,11 @@ impl<E: Copy> Events<E> {
             errors: Vec::new(),
             auths: HashMap::new(),
             messages: HashMap::new(),
+            expired_messages: HashMap::new(),
             requests: HashMap::new(),
+            responses: HashMap::new(),
+            file_transfer_progresses: Vec::new(),
+            file_transfer_completions: Vec::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             publishes: Vec::new(),
@@ -173,6 +182,21 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    /// How many Messages were received over each Channel
+    pub(crate) fn message_counts(&self) -> impl Iterator<Item = (&ChannelKind, usize)> {
+        channel_message_counts(&self.messages)
+    }
+
+    pub(crate) fn push_expired_message(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+    ) {
+        push_message_impl(&mut self.expired_messages, user_key, channel_kind, message);
+        self.empty = false;
+    }
+
     pub(crate) fn push_request(
         &mut self,
         user_key: &UserKey,
@@ -194,6 +218,37 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_response(
+        &mut self,
+        user_key: &UserKey,
+        global_request_id: GlobalRequestId,
+        response_kind: MessageKind,
+    ) {
+        self.responses
+            .entry(response_kind)
+            .or_default()
+            .push((*user_key, global_request_id));
+
+        self.empty = false;
+    }
+
+    pub(crate) fn push_file_transfer_progress(
+        &mut self,
+        user_key: &UserKey,
+        id: &FileTransferId,
+        received: u32,
+        size: u32,
+    ) {
+        self.file_transfer_progresses
+            .push((*user_key, *id, received, size));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_file_transfer_complete(&mut self, user_key: &UserKey, id: &FileTransferId) {
+        self.file_transfer_completions.push((*user_key, *id));
+        self.empty = false;
+    }
+
     pub(crate) fn push_tick(&mut self, tick: Tick) {
         self.ticks.push(tick);
         self.empty = false;
@@ -500,6 +555,39 @@ pub(crate) fn push_message_impl(
     list.push((*user_key, message));
 }
 
+pub(crate) fn channel_message_counts(
+    messages: &HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
+) -> impl Iterator<Item = (&ChannelKind, usize)> {
+    messages
+        .iter()
+        .map(|(channel_kind, channel_map)| (channel_kind, channel_map.values().map(Vec::len).sum()))
+}
+
+// Message Expired Event
+/// A Message sent with `Server::send_message_with_ttl()` which the User
+/// had not acknowledged before its time to live passed
+pub struct MessageExpiredEvent<C: Channel, M: Message> {
+    phantom_c: PhantomData<C>,
+    phantom_m: PhantomData<M>,
+}
+impl<E: Copy, C: Channel, M: Message> Event<E> for MessageExpiredEvent<C, M> {
+    type Iter = IntoIter<(UserKey, M)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let output = read_channel_messages::<C, M>(&mut events.expired_messages);
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        if let Some(channel_map) = events.expired_messages.get(&channel_kind) {
+            let message_kind: MessageKind = MessageKind::of::<M>();
+            return channel_map.contains_key(&message_kind);
+        }
+        return false;
+    }
+}
+
 // Request Event
 pub struct RequestEvent<C: Channel, Q: Request> {
     phantom_c: PhantomData<C>,
@@ -542,6 +630,36 @@ impl<E: Copy, C: Channel, Q: Request> Event<E> for RequestEvent<C, Q> {
     }
 }
 
+// Response Event
+/// Occurs when a Client answers a Request sent with `Server::send_request()`.
+/// Yields the key the Request was sent with, which collects the Response
+/// through `Server::receive_response()`
+pub struct ResponseEvent<S: Response> {
+    phantom_s: PhantomData<S>,
+}
+impl<E: Copy, S: Response> Event<E> for ResponseEvent<S> {
+    type Iter = IntoIter<(UserKey, ResponseReceiveKey<S>)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let message_kind: MessageKind = MessageKind::of::<S>();
+        let Some(responses) = events.responses.remove(&message_kind) else {
+            return IntoIterator::into_iter(Vec::new());
+        };
+
+        let output_list: Vec<(UserKey, ResponseReceiveKey<S>)> = responses
+            .into_iter()
+            .map(|(user_key, request_id)| (user_key, ResponseReceiveKey::new(request_id)))
+            .collect();
+
+        return IntoIterator::into_iter(output_list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let message_kind: MessageKind = MessageKind::of::<S>();
+        return events.responses.contains_key(&message_kind);
+    }
+}
+
 // Spawn Entity Event
 pub struct SpawnEntityEvent;
 impl<E: Copy> Event<E> for SpawnEntityEvent {
@@ -720,3 +838,36 @@ impl<E: Copy, C: Replicate> Event<E> for RemoveComponentEvent<C> {
         events.removes.contains_key(&component_kind)
     }
 }
+
+// File Transfer Progress Event
+/// Reports how many bytes of a file sent with `Server::send_file()` the User
+/// has received, out of the file's size, whenever that changes
+pub struct FileTransferProgressEvent;
+impl<E: Copy> Event<E> for FileTransferProgressEvent {
+    type Iter = IntoIter<(UserKey, FileTransferId, u32, u32)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.file_transfer_progresses);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.file_transfer_progresses.is_empty()
+    }
+}
+
+// File Transfer Complete Event
+/// A file sent with `Server::send_file()` which the User has received in full
+pub struct FileTransferCompleteEvent;
+impl<E: Copy> Event<E> for FileTransferCompleteEvent {
+    type Iter = IntoIter<(UserKey, FileTransferId)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.file_transfer_completions);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.file_transfer_completions.is_empty()
+    }
+}
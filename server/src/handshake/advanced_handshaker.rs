@@ -1,30 +1,68 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use log::warn;
-use ring::{hmac, rand};
+use ring::{constant_time, hmac, rand};
 
-use naia_server_socket::shared::IdentityToken;
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, OutgoingPacket, PacketType, Serde, SerdeErr,
+    handshake::{HandshakeHeader, ProofOfWork},
+    BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType, Serde, SerdeErr,
     StandardHeader,
 };
 
 use crate::{
-    handshake::{cache_map::CacheMap, HandshakeAction, Handshaker},
+    cache_map::CacheMap,
+    handshake::{
+        clock::{HandshakeClock, SystemClock},
+        HandshakeAction, HandshakeMetrics, HandshakeObserver, HandshakeOutcome, HandshakeStats,
+        Handshaker,
+    },
     UserKey,
 };
 
 type Timestamp = u64;
 
+/// The outcome of validating a Client's validate request
+#[derive(Debug, PartialEq, Eq)]
+pub enum HandshakeResult {
+    Success,
+    // the timestamp digest was not signed by this Server, or could not be read
+    InvalidDigest,
+    // the timestamp lies outside the Server's freshness window
+    StaleTimestamp,
+    // the proof-of-work nonce is missing or does not meet the difficulty
+    InvalidProofOfWork,
+}
+
 pub struct HandshakeManager {
     authenticated_and_identified_users: HashMap<SocketAddr, UserKey>,
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
+    stats: HandshakeStats,
     been_handshaked_users: HashMap<SocketAddr, UserKey>,
 
     connection_hash_key: hmac::Key,
-    address_to_timestamp_map: HashMap<SocketAddr, Timestamp>,
-    timestamp_digest_map: CacheMap<Timestamp, Vec<u8>>,
+    // the key in use before the last rotation, kept so that handshakes which
+    // were in flight during the rotation can still be validated
+    previous_connection_hash_key: Option<hmac::Key>,
+    // interval, in seconds, at which the connection hash key is rotated
+    key_rotation_interval: Option<Timestamp>,
+    // if set, connection hash keys are derived from this rather than
+    // generated, so that every Server sharing it agrees on them
+    key_seed: Option<hmac::Key>,
+    key_rotated_at: Timestamp,
+    // the timestamp & digest each Client validated with, which it signs its
+    // Disconnect with, however many times the key has rotated since
+    address_to_digest_map: HashMap<SocketAddr, (Timestamp, Vec<u8>)>,
+    timestamp_digest_map: CacheMap<(Timestamp, SocketAddr), Vec<u8>>,
+    clock: Box<dyn HandshakeClock>,
+    // maximum age, in seconds, of a Client timestamp in a validate request
+    timestamp_ttl: Option<Timestamp>,
+    // number of leading zero bits required of a Client's proof-of-work
+    difficulty: u8,
 }
 
 impl Handshaker for HandshakeManager {
@@ -44,7 +82,7 @@ impl Handshaker for HandshakeManager {
         if let Some(address) = address_opt {
             self.authenticated_and_identified_users.remove(&address);
             self.been_handshaked_users.remove(&address);
-            self.address_to_timestamp_map.remove(&address);
+            self.address_to_digest_map.remove(&address);
         }
     }
 
@@ -54,12 +92,16 @@ impl Handshaker for HandshakeManager {
         reader: &mut BitReader,
         has_connection: bool,
     ) -> Result<HandshakeAction, SerdeErr> {
+        self.rotate_key_if_expired();
+
         let handshake_header = HandshakeHeader::de(reader)?;
 
         // Handshake stuff
         match handshake_header {
             HandshakeHeader::ClientChallengeRequest => {
                 if let Ok((timestamp, id_token)) = self.recv_challenge_request(reader) {
+                    self.stats
+                        .record(address, HandshakeOutcome::ChallengeRequest);
                     if let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
                     {
                         // remove identity token from map
@@ -77,7 +119,9 @@ impl Handshaker for HandshakeManager {
                         return Ok(HandshakeAction::None);
                     }
 
-                    let identify_response = self.write_challenge_response(&timestamp).to_packet();
+                    let identify_response = self
+                        .write_challenge_response(address, &timestamp)
+                        .to_packet();
 
                     return Ok(HandshakeAction::SendPacket(identify_response));
                 } else {
@@ -85,7 +129,16 @@ impl Handshaker for HandshakeManager {
                 }
             }
             HandshakeHeader::ClientValidateRequest => {
-                if self.recv_validate_request(address, reader) {
+                let validate_result = self.recv_validate_request(address, reader);
+                self.stats.record(
+                    address,
+                    if validate_result == HandshakeResult::Success {
+                        HandshakeOutcome::ValidateSuccess
+                    } else {
+                        HandshakeOutcome::ValidateFailure
+                    },
+                );
+                if validate_result == HandshakeResult::Success {
                     if self.been_handshaked_users.contains_key(address) {
                         // send validate response
                         let writer = self.write_validate_response();
@@ -132,6 +185,8 @@ impl Handshaker for HandshakeManager {
                         .expect("should be a user by now, from validation step");
                     return Ok(HandshakeAction::DisconnectUser(user_key));
                 } else {
+                    self.stats
+                        .record(address, HandshakeOutcome::DisconnectVerifyFailure);
                     return Ok(HandshakeAction::None);
                 }
             }
@@ -144,25 +199,138 @@ impl Handshaker for HandshakeManager {
             }
         }
     }
+
+    fn metrics(&self) -> HandshakeMetrics {
+        self.stats.metrics()
+    }
+
+    fn set_observer(&mut self, observer: Box<dyn HandshakeObserver>) {
+        self.stats.set_observer(observer);
+    }
 }
 
 impl HandshakeManager {
-    pub fn new() -> Self {
-        let connection_hash_key =
-            hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
+    pub fn new(difficulty: u8, key_rotation_interval: Option<Duration>) -> Self {
+        Self::with_clock(
+            Box::new(SystemClock),
+            None,
+            difficulty,
+            key_rotation_interval.map(|interval| interval.as_secs()),
+        )
+    }
+
+    /// Create a HandshakeManager whose keys are derived from `key_material`
+    /// instead of generated at random, so that several Servers behind a load
+    /// balancer can validate each other's handshakes. `key_material` should be
+    /// a secret of at least 32 random bytes, shared by every Server in the
+    /// fleet. If `key_rotation_interval` is set, keys are rotated at the same
+    /// wall-clock instants on every Server
+    pub fn with_key(
+        key_material: &[u8],
+        difficulty: u8,
+        key_rotation_interval: Option<Duration>,
+    ) -> Self {
+        let mut manager = Self::new(difficulty, key_rotation_interval);
+        manager.key_seed = Some(hmac::Key::new(hmac::HMAC_SHA256, key_material));
+        manager.rotate_key();
+        manager.key_rotated_at = manager.key_epoch_start();
+        manager
+    }
+
+    /// Create a HandshakeManager which reads the current time from the given
+    /// clock, and which rejects validate requests carrying a timestamp more
+    /// than `timestamp_ttl` seconds away from it, or a proof-of-work which
+    /// does not meet `difficulty`. If `key_rotation_interval` is set, the key
+    /// used to sign Client timestamps is replaced every that many seconds
+    pub fn with_clock(
+        clock: Box<dyn HandshakeClock>,
+        timestamp_ttl: Option<Timestamp>,
+        difficulty: u8,
+        key_rotation_interval: Option<Timestamp>,
+    ) -> Self {
+        let connection_hash_key = Self::generate_key();
+        let key_rotated_at = clock.now();
 
         Self {
             authenticated_and_identified_users: HashMap::new(),
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
+            stats: HandshakeStats::default(),
             been_handshaked_users: HashMap::new(),
 
             connection_hash_key,
-            address_to_timestamp_map: HashMap::new(),
+            previous_connection_hash_key: None,
+            key_rotation_interval,
+            key_seed: None,
+            key_rotated_at,
+            address_to_digest_map: HashMap::new(),
             timestamp_digest_map: CacheMap::with_capacity(64),
+            clock,
+            timestamp_ttl,
+            difficulty,
+        }
+    }
+
+    fn generate_key() -> hmac::Key {
+        hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap()
+    }
+
+    fn derive_key(key_seed: &hmac::Key, epoch: Timestamp) -> hmac::Key {
+        let tag = hmac::sign(key_seed, &epoch.to_le_bytes());
+        hmac::Key::new(hmac::HMAC_SHA256, tag.as_ref())
+    }
+
+    // The number of key rotation intervals elapsed since the Unix epoch
+    fn key_epoch(&self) -> Timestamp {
+        self.key_rotation_interval
+            .map_or(0, |interval| self.clock.now() / interval.max(1))
+    }
+
+    fn key_epoch_start(&self) -> Timestamp {
+        self.key_rotation_interval
+            .map_or(self.clock.now(), |interval| {
+                self.key_epoch() * interval.max(1)
+            })
+    }
+
+    fn rotate_key_if_expired(&mut self) {
+        let Some(key_rotation_interval) = self.key_rotation_interval else {
+            return;
+        };
+        let now = self.clock.now();
+        if now.saturating_sub(self.key_rotated_at) >= key_rotation_interval {
+            self.rotate_key();
+            self.key_rotated_at = if self.key_seed.is_some() {
+                self.key_epoch_start()
+            } else {
+                now
+            };
         }
     }
 
+    // Replaces the current connection hash key with a fresh one. Digests
+    // signed with the replaced key remain valid until the next rotation.
+    fn rotate_key(&mut self) {
+        if let Some(key_seed) = &self.key_seed {
+            // derive the keys of the current and previous rotations, so they
+            // match those of other Servers sharing the seed
+            let epoch = self.key_epoch();
+            let key = Self::derive_key(key_seed, epoch);
+            let previous_key = epoch
+                .checked_sub(1)
+                .map(|epoch| Self::derive_key(key_seed, epoch));
+            self.connection_hash_key = key;
+            self.previous_connection_hash_key = previous_key;
+        } else {
+            let previous_key =
+                std::mem::replace(&mut self.connection_hash_key, Self::generate_key());
+            self.previous_connection_hash_key = Some(previous_key);
+        }
+
+        // cached digests were signed with the replaced key
+        self.timestamp_digest_map.clear();
+    }
+
     // Step 1 of Handshake
     fn recv_challenge_request(
         &mut self,
@@ -175,39 +343,71 @@ impl HandshakeManager {
     }
 
     // Step 2 of Handshake
-    fn write_challenge_response(&mut self, timestamp: &Timestamp) -> BitWriter {
+    fn write_challenge_response(
+        &mut self,
+        address: &SocketAddr,
+        timestamp: &Timestamp,
+    ) -> BitWriter {
         let mut writer = BitWriter::new();
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ServerChallengeResponse.ser(&mut writer);
         timestamp.ser(&mut writer);
 
-        if !self.timestamp_digest_map.contains_key(timestamp) {
-            let tag = hmac::sign(&self.connection_hash_key, &timestamp.to_le_bytes());
+        let digest_key = (*timestamp, *address);
+        if !self.timestamp_digest_map.contains_key(&digest_key) {
+            let tag = hmac::sign(
+                &self.connection_hash_key,
+                &Self::digest_input(timestamp, address),
+            );
             let tag_vec: Vec<u8> = Vec::from(tag.as_ref());
-            self.timestamp_digest_map.insert(*timestamp, tag_vec);
+            self.timestamp_digest_map.insert(digest_key, tag_vec);
         }
 
         //write timestamp digest
         self.timestamp_digest_map
-            .get_unchecked(timestamp)
+            .get_unchecked(&digest_key)
             .ser(&mut writer);
 
+        // write proof-of-work difficulty
+        self.difficulty.ser(&mut writer);
+
         writer
     }
 
     // Step 3 of Handshake
-    fn recv_validate_request(&mut self, address: &SocketAddr, reader: &mut BitReader) -> bool {
+    fn recv_validate_request(
+        &mut self,
+        address: &SocketAddr,
+        reader: &mut BitReader,
+    ) -> HandshakeResult {
         // Verify that timestamp hash has been written by this
         // server instance
-        let Some(timestamp) = self.timestamp_validate(reader) else {
+        let Some((timestamp, digest)) = self.timestamp_validate(address, reader) else {
             warn!("Handshake Error from {}: Invalid timestamp hash", address);
-            return false;
+            return HandshakeResult::InvalidDigest;
         };
         // Timestamp hash is valid
 
-        self.address_to_timestamp_map.insert(*address, timestamp);
+        if !self.timestamp_is_fresh(&timestamp) {
+            warn!("Handshake Error from {}: Stale timestamp", address);
+            return HandshakeResult::StaleTimestamp;
+        }
+
+        // Verify that the Client has solved the challenge issued for its
+        // timestamp
+        let Ok(nonce) = u64::de(reader) else {
+            warn!("Handshake Error from {}: Missing proof-of-work", address);
+            return HandshakeResult::InvalidProofOfWork;
+        };
+        if !ProofOfWork::verify(&digest, self.difficulty, nonce) {
+            warn!("Handshake Error from {}: Invalid proof-of-work", address);
+            return HandshakeResult::InvalidProofOfWork;
+        }
 
-        return true;
+        self.address_to_digest_map
+            .insert(*address, (timestamp, digest));
+
+        HandshakeResult::Success
     }
 
     // Step 4 of Handshake
@@ -227,17 +427,21 @@ impl HandshakeManager {
     }
 
     fn verify_disconnect_request(&mut self, address: &SocketAddr, reader: &mut BitReader) -> bool {
-        // Verify that timestamp hash has been written by this
-        // server instance
-        if let Some(new_timestamp) = self.timestamp_validate(reader) {
-            if let Some(old_timestamp) = self.address_to_timestamp_map.get(address) {
-                if *old_timestamp == new_timestamp {
-                    return true;
-                }
-            }
-        }
+        // Verify that the Client signed with the timestamp & digest it
+        // validated with. These are compared rather than re-verified, as the
+        // key they were signed with may have since been rotated out
+        let Ok(timestamp) = Timestamp::de(reader) else {
+            return false;
+        };
+        let Ok(digest) = Vec::<u8>::de(reader) else {
+            return false;
+        };
+        let Some((old_timestamp, old_digest)) = self.address_to_digest_map.get(address) else {
+            return false;
+        };
 
-        false
+        *old_timestamp == timestamp
+            && constant_time::verify_slices_are_equal(old_digest, &digest).is_ok()
     }
 
     // fn write_reject_response(&self) -> BitWriter {
@@ -246,7 +450,24 @@ impl HandshakeManager {
     //     writer
     // }
 
-    fn timestamp_validate(&self, reader: &mut BitReader) -> Option<Timestamp> {
+    // Builds the bytes signed for a Client's timestamp. The Client's address
+    // is included so that a digest is only valid from the address it was
+    // issued to
+    fn digest_input(timestamp: &Timestamp, address: &SocketAddr) -> Vec<u8> {
+        let mut input = timestamp.to_le_bytes().to_vec();
+        match address.ip() {
+            IpAddr::V4(ip) => input.extend_from_slice(&ip.octets()),
+            IpAddr::V6(ip) => input.extend_from_slice(&ip.octets()),
+        }
+        input.extend_from_slice(&address.port().to_le_bytes());
+        input
+    }
+
+    fn timestamp_validate(
+        &self,
+        address: &SocketAddr,
+        reader: &mut BitReader,
+    ) -> Option<(Timestamp, Vec<u8>)> {
         // Read timestamp
         let timestamp_result = Timestamp::de(reader);
         if timestamp_result.is_err() {
@@ -262,18 +483,29 @@ impl HandshakeManager {
         let digest_bytes = digest_bytes_result.unwrap();
 
         // Verify that timestamp hash has been written by this server instance
-        let validation_result = hmac::verify(
-            &self.connection_hash_key,
-            &timestamp.to_le_bytes(),
-            &digest_bytes,
-        );
-        if validation_result.is_err() {
-            None
+        // for this address, with either the current key or the one it replaced
+        let digest_input = Self::digest_input(&timestamp, address);
+        let signed_by_current_key =
+            hmac::verify(&self.connection_hash_key, &digest_input, &digest_bytes).is_ok();
+        let signed_by_previous_key = || {
+            self.previous_connection_hash_key
+                .as_ref()
+                .is_some_and(|key| hmac::verify(key, &digest_input, &digest_bytes).is_ok())
+        };
+        if signed_by_current_key || signed_by_previous_key() {
+            Some((timestamp, digest_bytes))
         } else {
-            Some(timestamp)
+            None
         }
     }
 
+    fn timestamp_is_fresh(&self, timestamp: &Timestamp) -> bool {
+        let Some(timestamp_ttl) = self.timestamp_ttl else {
+            return true;
+        };
+        self.clock.now().abs_diff(*timestamp) <= timestamp_ttl
+    }
+
     fn user_finish_handshake(&mut self, addr: &SocketAddr, user_key: &UserKey) -> OutgoingPacket {
         // send validate response
         let writer = self.write_validate_response();
@@ -284,3 +516,424 @@ impl HandshakeManager {
         packet
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use naia_shared::{
+        handshake::HandshakeHeader, BigMapKey, BitReader, BitWriter, IdentityToken, Serde,
+        StandardHeader,
+    };
+
+    use naia_shared::handshake::ProofOfWork;
+
+    use super::{HandshakeManager, HandshakeResult, Timestamp};
+    use crate::{
+        handshake::{
+            clock::HandshakeClock, HandshakeAction, HandshakeMetrics, HandshakeOutcome, Handshaker,
+        },
+        UserKey,
+    };
+
+    const START_TIME: Timestamp = 1_000_000;
+    const TIMESTAMP_TTL: Timestamp = 10;
+    const KEY_ROTATION_INTERVAL: Timestamp = 5;
+
+    #[derive(Clone)]
+    struct TestClock(Arc<AtomicU64>);
+
+    impl TestClock {
+        fn advance(&self, seconds: u64) {
+            self.0.fetch_add(seconds, Ordering::SeqCst);
+        }
+    }
+
+    impl HandshakeClock for TestClock {
+        fn now(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    fn setup() -> (HandshakeManager, TestClock, SocketAddr) {
+        setup_with_difficulty(0)
+    }
+
+    fn setup_with_difficulty(difficulty: u8) -> (HandshakeManager, TestClock, SocketAddr) {
+        let clock = TestClock(Arc::new(AtomicU64::new(START_TIME)));
+        let manager = HandshakeManager::with_clock(
+            Box::new(clock.clone()),
+            Some(TIMESTAMP_TTL),
+            difficulty,
+            None,
+        );
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        (manager, clock, address)
+    }
+
+    // Runs the challenge step, returning the digest signed by the server
+    fn challenge(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        timestamp: Timestamp,
+    ) -> Vec<u8> {
+        let identity_token: IdentityToken = "identity_token".to_string();
+        manager.authenticate_user(&identity_token, &UserKey::from_u64(0));
+
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientChallengeRequest.ser(&mut writer);
+        timestamp.ser(&mut writer);
+        identity_token.ser(&mut writer);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        let Ok(HandshakeAction::SendPacket(packet)) =
+            manager.maintain_handshake(address, &mut reader, false)
+        else {
+            panic!("expected a challenge response");
+        };
+
+        let mut reader = BitReader::new(packet.slice());
+        StandardHeader::de(&mut reader).unwrap();
+        assert_eq!(
+            HandshakeHeader::de(&mut reader).unwrap(),
+            HandshakeHeader::ServerChallengeResponse
+        );
+        assert_eq!(Timestamp::de(&mut reader).unwrap(), timestamp);
+        let digest = Vec::<u8>::de(&mut reader).unwrap();
+        assert_eq!(u8::de(&mut reader).unwrap(), manager.difficulty);
+        digest
+    }
+
+    fn validate(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        timestamp: Timestamp,
+        digest: &Vec<u8>,
+    ) -> bool {
+        let nonce = ProofOfWork::solve(digest, manager.difficulty);
+        validate_with_nonce(manager, address, timestamp, digest, nonce)
+    }
+
+    fn validate_with_nonce(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        timestamp: Timestamp,
+        digest: &Vec<u8>,
+        nonce: u64,
+    ) -> bool {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::ClientValidateRequest.ser(&mut writer);
+        timestamp.ser(&mut writer);
+        digest.ser(&mut writer);
+        nonce.ser(&mut writer);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        matches!(
+            manager.maintain_handshake(address, &mut reader, false),
+            Ok(HandshakeAction::SendPacket(_))
+        )
+    }
+
+    // Reads a validate request's payload directly, returning why it was
+    // accepted or rejected
+    fn validate_result(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        timestamp: Timestamp,
+        digest: &Vec<u8>,
+        nonce: u64,
+    ) -> HandshakeResult {
+        let mut writer = BitWriter::new();
+        timestamp.ser(&mut writer);
+        digest.ser(&mut writer);
+        nonce.ser(&mut writer);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        manager.recv_validate_request(address, &mut reader)
+    }
+
+    #[test]
+    fn fresh_validate_request_accepted() {
+        let (mut manager, clock, address) = setup();
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        clock.advance(TIMESTAMP_TTL);
+
+        assert!(validate(&mut manager, &address, START_TIME, &digest));
+    }
+
+    #[test]
+    fn stale_validate_request_rejected() {
+        let (mut manager, clock, address) = setup();
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        clock.advance(TIMESTAMP_TTL + 1);
+
+        assert!(!validate(&mut manager, &address, START_TIME, &digest));
+    }
+
+    #[test]
+    fn future_validate_request_rejected() {
+        let (mut manager, _clock, address) = setup();
+        let timestamp = START_TIME + TIMESTAMP_TTL + 1;
+        let digest = challenge(&mut manager, &address, timestamp);
+
+        assert!(!validate(&mut manager, &address, timestamp, &digest));
+    }
+
+    #[test]
+    fn forged_digest_rejected() {
+        let (mut manager, _clock, address) = setup();
+        let mut digest = challenge(&mut manager, &address, START_TIME);
+        digest[0] ^= 1;
+
+        assert!(!validate(&mut manager, &address, START_TIME, &digest));
+    }
+
+    #[test]
+    fn digest_rejected_from_other_address() {
+        let (mut manager, _clock, address) = setup();
+        let other_address: SocketAddr = "127.0.0.2:14191".parse().unwrap();
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        assert_eq!(
+            validate_result(&mut manager, &other_address, START_TIME, &digest, 0),
+            HandshakeResult::InvalidDigest
+        );
+        assert_eq!(
+            validate_result(&mut manager, &address, START_TIME, &digest, 0),
+            HandshakeResult::Success
+        );
+    }
+
+    #[test]
+    fn solved_proof_of_work_accepted() {
+        let (mut manager, _clock, address) = setup_with_difficulty(8);
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        assert!(validate(&mut manager, &address, START_TIME, &digest));
+    }
+
+    #[test]
+    fn unsolved_proof_of_work_rejected() {
+        let (mut manager, _clock, address) = setup_with_difficulty(8);
+        let digest = challenge(&mut manager, &address, START_TIME);
+        let nonce = (0..)
+            .find(|nonce| !ProofOfWork::verify(&digest, 8, *nonce))
+            .unwrap();
+
+        assert!(!validate_with_nonce(
+            &mut manager,
+            &address,
+            START_TIME,
+            &digest,
+            nonce
+        ));
+    }
+
+    #[test]
+    fn proof_of_work_bound_to_challenge() {
+        let (mut manager, _clock, address) = setup_with_difficulty(8);
+        let digest = challenge(&mut manager, &address, START_TIME);
+        let other_digest = challenge(&mut manager, &address, START_TIME + 1);
+        let nonce = (0..)
+            .find(|nonce| {
+                ProofOfWork::verify(&other_digest, 8, *nonce)
+                    && !ProofOfWork::verify(&digest, 8, *nonce)
+            })
+            .unwrap();
+
+        assert!(!validate_with_nonce(
+            &mut manager,
+            &address,
+            START_TIME,
+            &digest,
+            nonce
+        ));
+    }
+
+    #[test]
+    fn validate_request_accepted_after_key_rotation() {
+        let clock = TestClock(Arc::new(AtomicU64::new(START_TIME)));
+        let mut manager = HandshakeManager::with_clock(
+            Box::new(clock.clone()),
+            Some(TIMESTAMP_TTL),
+            0,
+            Some(KEY_ROTATION_INTERVAL),
+        );
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        // key rotates as the validate request is received
+        clock.advance(KEY_ROTATION_INTERVAL);
+
+        assert!(validate(&mut manager, &address, START_TIME, &digest));
+        assert!(manager.previous_connection_hash_key.is_some());
+    }
+
+    #[test]
+    fn validate_request_rejected_after_second_key_rotation() {
+        let (mut manager, _clock, address) = setup();
+        let digest = challenge(&mut manager, &address, START_TIME);
+
+        manager.rotate_key();
+        manager.rotate_key();
+
+        assert!(!validate(&mut manager, &address, START_TIME, &digest));
+    }
+
+    // Sends a Disconnect signed with the given timestamp & digest, returning
+    // whether the server disconnects the User
+    fn disconnect(
+        manager: &mut HandshakeManager,
+        address: &SocketAddr,
+        timestamp: Timestamp,
+        digest: &Vec<u8>,
+    ) -> bool {
+        let mut writer = BitWriter::new();
+        HandshakeHeader::Disconnect.ser(&mut writer);
+        timestamp.ser(&mut writer);
+        digest.ser(&mut writer);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        matches!(
+            manager.maintain_handshake(address, &mut reader, true),
+            Ok(HandshakeAction::DisconnectUser(_))
+        )
+    }
+
+    #[test]
+    fn disconnect_accepted_after_second_key_rotation() {
+        let (mut manager, _clock, address) = setup();
+        let digest = challenge(&mut manager, &address, START_TIME);
+        assert!(validate(&mut manager, &address, START_TIME, &digest));
+
+        manager.rotate_key();
+        manager.rotate_key();
+
+        let mut forged_digest = digest.clone();
+        forged_digest[0] ^= 1;
+        assert!(!disconnect(
+            &mut manager,
+            &address,
+            START_TIME,
+            &forged_digest
+        ));
+        assert!(disconnect(&mut manager, &address, START_TIME, &digest));
+    }
+
+    #[test]
+    fn challenge_response_signed_with_rotated_key() {
+        let (mut manager, _clock, address) = setup();
+        let old_digest = challenge(&mut manager, &address, START_TIME);
+
+        manager.rotate_key();
+
+        let new_digest = challenge(&mut manager, &address, START_TIME);
+        assert_ne!(old_digest, new_digest);
+        assert!(validate(&mut manager, &address, START_TIME, &new_digest));
+    }
+
+    #[test]
+    fn managers_with_same_key_cross_validate() {
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let mut manager_1 = HandshakeManager::with_key(&[7; 32], 0, None);
+        let mut manager_2 = HandshakeManager::with_key(&[7; 32], 0, None);
+        let mut other_manager = HandshakeManager::with_key(&[8; 32], 0, None);
+
+        let digest = challenge(&mut manager_1, &address, START_TIME);
+
+        assert_eq!(
+            validate_result(&mut manager_2, &address, START_TIME, &digest, 0),
+            HandshakeResult::Success
+        );
+        assert_eq!(
+            validate_result(&mut other_manager, &address, START_TIME, &digest, 0),
+            HandshakeResult::InvalidDigest
+        );
+    }
+
+    #[test]
+    fn rejection_reasons_distinguished() {
+        let (mut manager, clock, address) = setup_with_difficulty(8);
+        let digest = challenge(&mut manager, &address, START_TIME);
+        let nonce = ProofOfWork::solve(&digest, 8);
+        let unsolved_nonce = (0..)
+            .find(|nonce| !ProofOfWork::verify(&digest, 8, *nonce))
+            .unwrap();
+        let mut forged_digest = digest.clone();
+        forged_digest[0] ^= 1;
+
+        assert_eq!(
+            validate_result(&mut manager, &address, START_TIME, &forged_digest, nonce),
+            HandshakeResult::InvalidDigest
+        );
+        assert_eq!(
+            validate_result(&mut manager, &address, START_TIME, &digest, unsolved_nonce),
+            HandshakeResult::InvalidProofOfWork
+        );
+        assert_eq!(
+            validate_result(&mut manager, &address, START_TIME, &digest, nonce),
+            HandshakeResult::Success
+        );
+
+        clock.advance(TIMESTAMP_TTL + 1);
+
+        assert_eq!(
+            validate_result(&mut manager, &address, START_TIME, &digest, nonce),
+            HandshakeResult::StaleTimestamp
+        );
+    }
+
+    #[test]
+    fn outcomes_counted_and_observed() {
+        let (mut manager, _clock, address) = setup();
+        let outcomes = Arc::new(Mutex::new(Vec::new()));
+        let observed = outcomes.clone();
+        manager.set_observer(Box::new(
+            move |_: &SocketAddr, outcome: HandshakeOutcome| {
+                observed.lock().unwrap().push(outcome);
+            },
+        ));
+
+        let digest = challenge(&mut manager, &address, START_TIME);
+        assert!(validate(&mut manager, &address, START_TIME, &digest));
+
+        let mut forged_digest = digest.clone();
+        forged_digest[0] ^= 1;
+        assert!(!validate(
+            &mut manager,
+            &address,
+            START_TIME,
+            &forged_digest
+        ));
+
+        assert_eq!(
+            manager.metrics(),
+            HandshakeMetrics {
+                challenge_requests: 1,
+                validate_successes: 1,
+                validate_failures: 1,
+                disconnect_verify_failures: 0,
+            }
+        );
+        assert_eq!(
+            *outcomes.lock().unwrap(),
+            vec![
+                HandshakeOutcome::ChallengeRequest,
+                HandshakeOutcome::ValidateSuccess,
+                HandshakeOutcome::ValidateFailure,
+            ]
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use naia_shared::Timestamp;
+
+/// Source of the current time for the HandshakeManager, in seconds since the
+/// Unix epoch, matching the timestamps sent by Clients
+pub trait HandshakeClock: Send + Sync {
+    fn now(&self) -> u64;
+}
+
+/// Reads the current time from the system clock
+pub struct SystemClock;
+
+impl HandshakeClock for SystemClock {
+    fn now(&self) -> u64 {
+        Timestamp::now()
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ring::hmac;
+
+use crate::ConnectTokenConfig;
+
+const CONNECT_TOKEN_VERSION: u8 = 1;
+const TAG_LENGTH: usize = 32;
+
+/// Lets a Client connect without the Server app answering its auth. The
+/// game's backend signs one for each Client with a key shared with the
+/// Server, which the Client presents with `Client::connect_token` in place of
+/// an auth message. Each token is accepted only once, and not while a User
+/// with its client id is connected.
+///
+/// Signed tokens are laid out, with integers big-endian, as a version byte
+/// (1), the client id (u64), the expiry in seconds since the UNIX epoch
+/// (u64), the server's address family (4 or 6), its IP (4 or 16 bytes) and
+/// port (u16), followed by the HMAC-SHA256 of everything before it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConnectToken {
+    /// Identifies the Client to the Server app, see `UserRef::client_id`
+    pub client_id: u64,
+    /// When the token stops being accepted, in seconds since the UNIX epoch
+    pub expires_at: u64,
+    /// The address of the Server the token may be used with
+    pub server_addr: SocketAddr,
+}
+
+/// Why a connect token was not accepted
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectTokenError {
+    /// The bytes are not a signed connect token
+    Malformed,
+    /// The token was not signed with the Server's key
+    BadSignature,
+    Expired,
+    /// The token was signed for a different Server
+    WrongServer,
+    /// The token has already been used to connect
+    AlreadyUsed,
+    /// A User with the token's client id is already connected
+    ClientIdInUse,
+}
+
+impl ConnectToken {
+    /// Serializes the token and signs it with the given key
+    pub fn sign(&self, key: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(36 + TAG_LENGTH);
+        bytes.push(CONNECT_TOKEN_VERSION);
+        bytes.extend_from_slice(&self.client_id.to_be_bytes());
+        bytes.extend_from_slice(&self.expires_at.to_be_bytes());
+        match self.server_addr.ip() {
+            IpAddr::V4(ip) => {
+                bytes.push(4);
+                bytes.extend_from_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                bytes.push(6);
+                bytes.extend_from_slice(&ip.octets());
+            }
+        }
+        bytes.extend_from_slice(&self.server_addr.port().to_be_bytes());
+
+        let tag = hmac::sign(&hmac_key(key), &bytes);
+        bytes.extend_from_slice(tag.as_ref());
+        bytes
+    }
+
+    /// Reads a signed token, checking only its signature
+    pub fn verify(signed: &[u8], key: &[u8]) -> Result<Self, ConnectTokenError> {
+        Self::verify_with(signed, &hmac_key(key))
+    }
+
+    fn verify_with(signed: &[u8], key: &hmac::Key) -> Result<Self, ConnectTokenError> {
+        if signed.len() < TAG_LENGTH {
+            return Err(ConnectTokenError::Malformed);
+        }
+        let (bytes, tag) = signed.split_at(signed.len() - TAG_LENGTH);
+        hmac::verify(key, bytes, tag).map_err(|_| ConnectTokenError::BadSignature)?;
+
+        let mut reader = bytes;
+        if take::<1>(&mut reader)? != [CONNECT_TOKEN_VERSION] {
+            return Err(ConnectTokenError::Malformed);
+        }
+        let client_id = u64::from_be_bytes(take(&mut reader)?);
+        let expires_at = u64::from_be_bytes(take(&mut reader)?);
+        let ip: IpAddr = match take::<1>(&mut reader)? {
+            [4] => Ipv4Addr::from(take::<4>(&mut reader)?).into(),
+            [6] => Ipv6Addr::from(take::<16>(&mut reader)?).into(),
+            _ => return Err(ConnectTokenError::Malformed),
+        };
+        let port = u16::from_be_bytes(take(&mut reader)?);
+        if !reader.is_empty() {
+            return Err(ConnectTokenError::Malformed);
+        }
+
+        Ok(Self {
+            client_id,
+            expires_at,
+            server_addr: SocketAddr::new(ip, port),
+        })
+    }
+}
+
+/// Checks the connect tokens Clients present against the Server's config
+pub(crate) struct ConnectTokenValidator {
+    key: hmac::Key,
+    server_addr: SocketAddr,
+    // the signatures of tokens already used, with when each expires, so that
+    // a token can only be used once
+    used_tokens: HashMap<Vec<u8>, u64>,
+}
+
+impl ConnectTokenValidator {
+    pub fn new(config: &ConnectTokenConfig) -> Self {
+        Self {
+            key: hmac_key(&config.key),
+            server_addr: config.server_addr,
+            used_tokens: HashMap::new(),
+        }
+    }
+
+    /// Checks the token, which is then used up if accepted. A token is also
+    /// refused if `client_id_in_use` reports its client id as connected
+    pub fn validate(
+        &mut self,
+        signed: &[u8],
+        client_id_in_use: impl Fn(u64) -> bool,
+    ) -> Result<ConnectToken, ConnectTokenError> {
+        let token = ConnectToken::verify_with(signed, &self.key)?;
+        if token.server_addr != self.server_addr {
+            return Err(ConnectTokenError::WrongServer);
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        if token.expires_at <= now {
+            return Err(ConnectTokenError::Expired);
+        }
+        if client_id_in_use(token.client_id) {
+            return Err(ConnectTokenError::ClientIdInUse);
+        }
+
+        // expired tokens are refused anyway, so needn't be remembered
+        self.used_tokens.retain(|_, expires_at| *expires_at > now);
+        let tag = signed[signed.len() - TAG_LENGTH..].to_vec();
+        if self.used_tokens.insert(tag, token.expires_at).is_some() {
+            return Err(ConnectTokenError::AlreadyUsed);
+        }
+        Ok(token)
+    }
+}
+
+fn hmac_key(key: &[u8]) -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, key)
+}
+
+fn take<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], ConnectTokenError> {
+    if reader.len() < N {
+        return Err(ConnectTokenError::Malformed);
+    }
+    let (taken, rest) = reader.split_at(N);
+    *reader = rest;
+    Ok(taken.try_into().expect("split at N"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{ConnectToken, ConnectTokenError, ConnectTokenValidator};
+    use crate::ConnectTokenConfig;
+
+    const KEY: &[u8] = b"a key shared with the game backend";
+
+    fn validator() -> ConnectTokenValidator {
+        ConnectTokenValidator::new(&ConnectTokenConfig {
+            key: KEY.to_vec(),
+            server_addr: "203.0.113.7:14191".parse().unwrap(),
+        })
+    }
+
+    fn token(expires_in: i64) -> ConnectToken {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        ConnectToken {
+            client_id: 42,
+            expires_at: now.saturating_add_signed(expires_in),
+            server_addr: "203.0.113.7:14191".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn signed_token_is_validated() {
+        let token = token(30);
+        assert_eq!(
+            validator().validate(&token.sign(KEY), |_| false),
+            Ok(token.clone())
+        );
+
+        let ipv6_token = ConnectToken {
+            server_addr: "[2001:db8::1]:14191".parse().unwrap(),
+            ..token
+        };
+        assert_eq!(
+            ConnectToken::verify(&ipv6_token.sign(KEY), KEY),
+            Ok(ipv6_token)
+        );
+    }
+
+    #[test]
+    fn invalid_tokens_are_refused() {
+        let mut validator = validator();
+
+        let mut tampered = token(30).sign(KEY);
+        tampered[8] ^= 1;
+        assert_eq!(
+            validator.validate(&tampered, |_| false),
+            Err(ConnectTokenError::BadSignature)
+        );
+        assert_eq!(
+            validator.validate(&token(30).sign(b"some other key"), |_| false),
+            Err(ConnectTokenError::BadSignature)
+        );
+        assert_eq!(
+            validator.validate(b"short", |_| false),
+            Err(ConnectTokenError::Malformed)
+        );
+        assert_eq!(
+            validator.validate(&token(-1).sign(KEY), |_| false),
+            Err(ConnectTokenError::Expired)
+        );
+
+        let elsewhere = ConnectToken {
+            server_addr: "203.0.113.8:14191".parse().unwrap(),
+            ..token(30)
+        };
+        assert_eq!(
+            validator.validate(&elsewhere.sign(KEY), |_| false),
+            Err(ConnectTokenError::WrongServer)
+        );
+    }
+
+    #[test]
+    fn used_token_is_refused() {
+        let mut validator = validator();
+        let signed = token(30).sign(KEY);
+
+        assert!(validator.validate(&signed, |_| false).is_ok());
+        assert_eq!(
+            validator.validate(&signed, |_| false),
+            Err(ConnectTokenError::AlreadyUsed)
+        );
+    }
+
+    #[test]
+    fn token_for_connected_client_is_refused() {
+        let mut validator = validator();
+        let signed = token(30).sign(KEY);
+
+        assert_eq!(
+            validator.validate(&signed, |client_id| client_id == 42),
+            Err(ConnectTokenError::ClientIdInUse)
+        );
+        // the refused token wasn't used up
+        assert!(validator.validate(&signed, |_| false).is_ok());
+    }
+}
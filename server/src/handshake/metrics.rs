@@ -0,0 +1,69 @@
+use std::net::SocketAddr;
+
+/// The outcome of a single step of a Client's handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakeOutcome {
+    /// A Client requested a challenge (or identified itself, when the
+    /// underlying transport performs its own handshake)
+    ChallengeRequest,
+    /// A Client's validate request was accepted
+    ValidateSuccess,
+    /// A Client's validate request was rejected, for example because its
+    /// timestamp was forged or stale
+    ValidateFailure,
+    /// A disconnect request could not be verified as coming from the Client
+    DisconnectVerifyFailure,
+}
+
+/// Counts of each HandshakeOutcome seen by the Server since it started
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HandshakeMetrics {
+    pub challenge_requests: u64,
+    pub validate_successes: u64,
+    pub validate_failures: u64,
+    pub disconnect_verify_failures: u64,
+}
+
+/// Notified of every HandshakeOutcome, so that it can be forwarded to an
+/// external metrics system
+pub trait HandshakeObserver: Send + Sync {
+    fn on_outcome(&self, address: &SocketAddr, outcome: HandshakeOutcome);
+}
+
+impl<F: Fn(&SocketAddr, HandshakeOutcome) + Send + Sync> HandshakeObserver for F {
+    fn on_outcome(&self, address: &SocketAddr, outcome: HandshakeOutcome) {
+        self(address, outcome)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct HandshakeStats {
+    metrics: HandshakeMetrics,
+    observer: Option<Box<dyn HandshakeObserver>>,
+}
+
+impl HandshakeStats {
+    pub fn metrics(&self) -> HandshakeMetrics {
+        self.metrics
+    }
+
+    pub fn set_observer(&mut self, observer: Box<dyn HandshakeObserver>) {
+        self.observer = Some(observer);
+    }
+
+    pub fn record(&mut self, address: &SocketAddr, outcome: HandshakeOutcome) {
+        let counter = match outcome {
+            HandshakeOutcome::ChallengeRequest => &mut self.metrics.challenge_requests,
+            HandshakeOutcome::ValidateSuccess => &mut self.metrics.validate_successes,
+            HandshakeOutcome::ValidateFailure => &mut self.metrics.validate_failures,
+            HandshakeOutcome::DisconnectVerifyFailure => {
+                &mut self.metrics.disconnect_verify_failures
+            }
+        };
+        *counter += 1;
+
+        if let Some(observer) = &self.observer {
+            observer.on_outcome(address, outcome);
+        }
+    }
+}
@@ -4,9 +4,21 @@ use naia_shared::{BitReader, IdentityToken, OutgoingPacket, SerdeErr};
 
 use crate::UserKey;
 
+mod metrics;
+use metrics::HandshakeStats;
+pub use metrics::{HandshakeMetrics, HandshakeObserver, HandshakeOutcome};
+
+cfg_if! {
+    if #[cfg(feature = "connect_tokens")] {
+        mod connect_token;
+        pub(crate) use connect_token::ConnectTokenValidator;
+        pub use connect_token::{ConnectToken, ConnectTokenError};
+    } else {}
+}
+
 cfg_if! {
-    if #[cfg(feature = "transport_udp")] {
-        mod cache_map;
+    if #[cfg(feature = "advanced_handshake")] {
+        mod clock;
 
         mod advanced_handshaker;
         pub use advanced_handshaker::HandshakeManager;
@@ -28,6 +40,10 @@ pub trait Handshaker: Send + Sync {
         reader: &mut BitReader,
         has_connection: bool,
     ) -> Result<HandshakeAction, SerdeErr>;
+
+    fn metrics(&self) -> HandshakeMetrics;
+
+    fn set_observer(&mut self, observer: Box<dyn HandshakeObserver>);
 }
 
 pub enum HandshakeAction {
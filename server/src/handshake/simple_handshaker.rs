@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{collections::HashMap, net::SocketAddr, time::Duration};
 
 use log::warn;
 
@@ -8,14 +8,21 @@ use naia_shared::{
 };
 
 use crate::{
-    handshake::{HandshakeAction, Handshaker},
+    handshake::{
+        HandshakeAction, HandshakeMetrics, HandshakeObserver, HandshakeOutcome, HandshakeStats,
+        Handshaker,
+    },
     UserKey,
 };
 
 pub struct HandshakeManager {
     authenticated_and_identified_users: HashMap<SocketAddr, UserKey>,
+    // the identity token each address identified with, which the Client
+    // repeats when it disconnects
+    identified_tokens: HashMap<SocketAddr, IdentityToken>,
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
+    stats: HandshakeStats,
 }
 
 impl Handshaker for HandshakeManager {
@@ -34,6 +41,7 @@ impl Handshaker for HandshakeManager {
         }
         if let Some(address) = address_opt {
             self.authenticated_and_identified_users.remove(&address);
+            self.identified_tokens.remove(&address);
         }
     }
 
@@ -49,6 +57,8 @@ impl Handshaker for HandshakeManager {
         match handshake_header {
             HandshakeHeader::ClientIdentifyRequest => {
                 if let Ok(id_token) = self.recv_identify_request(reader) {
+                    self.stats
+                        .record(address, HandshakeOutcome::ChallengeRequest);
                     if let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
                     {
                         // remove identity token from map
@@ -59,6 +69,7 @@ impl Handshaker for HandshakeManager {
                         // User is authenticated
                         self.authenticated_and_identified_users
                             .insert(*address, user_key);
+                        self.identified_tokens.insert(*address, id_token);
                     } else {
                         // commented out because it's pretty common to get multiple ClientIdentifyRequests which would trigger this
                         //warn!("Server Error: User not authenticated for: {:?}, with token: {}", address, identity_token);
@@ -84,10 +95,15 @@ impl Handshaker for HandshakeManager {
                     let Some(user_key) = self.authenticated_and_identified_users.get(address)
                     else {
                         warn!("Server Error: User not authenticated for: {:?}", address);
+                        self.stats
+                            .record(address, HandshakeOutcome::ValidateFailure);
                         return Ok(HandshakeAction::None);
                     };
+                    let user_key = *user_key;
+                    self.stats
+                        .record(address, HandshakeOutcome::ValidateSuccess);
 
-                    return Ok(HandshakeAction::FinalizeConnection(*user_key, packet));
+                    return Ok(HandshakeAction::FinalizeConnection(user_key, packet));
                 }
             }
             HandshakeHeader::Disconnect => {
@@ -98,6 +114,8 @@ impl Handshaker for HandshakeManager {
                         .expect("Server Error: User not authenticated for disconnect request. Shouldn't be possible.");
                     return Ok(HandshakeAction::DisconnectUser(user_key));
                 } else {
+                    self.stats
+                        .record(address, HandshakeOutcome::DisconnectVerifyFailure);
                     return Ok(HandshakeAction::None);
                 }
             }
@@ -110,17 +128,37 @@ impl Handshaker for HandshakeManager {
             }
         }
     }
+
+    fn metrics(&self) -> HandshakeMetrics {
+        self.stats.metrics()
+    }
+
+    fn set_observer(&mut self, observer: Box<dyn HandshakeObserver>) {
+        self.stats.set_observer(observer);
+    }
 }
 
 impl HandshakeManager {
-    pub fn new() -> Self {
+    // the underlying transport performs its own handshake, so no
+    // proof-of-work or key rotation is needed here
+    pub fn new(_difficulty: u8, _key_rotation_interval: Option<Duration>) -> Self {
         Self {
             authenticated_and_identified_users: HashMap::new(),
+            identified_tokens: HashMap::new(),
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
+            stats: HandshakeStats::default(),
         }
     }
 
+    pub fn with_key(
+        _key_material: &[u8],
+        difficulty: u8,
+        key_rotation_interval: Option<Duration>,
+    ) -> Self {
+        Self::new(difficulty, key_rotation_interval)
+    }
+
     // Step 1 of Handshake
     fn recv_identify_request(&mut self, reader: &mut BitReader) -> Result<IdentityToken, SerdeErr> {
         IdentityToken::de(reader)
@@ -143,15 +181,13 @@ impl HandshakeManager {
         writer
     }
 
-    fn verify_disconnect_request(
-        &mut self,
-        _address: &SocketAddr,
-        _reader: &mut BitReader,
-    ) -> bool {
-        // To verify that timestamp hash has been written by this
-        // server instance
-
-        todo!()
+    fn verify_disconnect_request(&mut self, address: &SocketAddr, reader: &mut BitReader) -> bool {
+        // Verify that the Client disconnecting is the one which identified
+        // from this address
+        let Ok(identity_token) = IdentityToken::de(reader) else {
+            return false;
+        };
+        self.identified_tokens.get(address) == Some(&identity_token)
     }
 
     // fn write_reject_response(&self) -> BitWriter {
@@ -16,19 +16,24 @@ extern crate cfg_if;
 pub mod transport;
 pub mod shared {
     pub use naia_shared::{
-        default_channels, BigMap, BigMapKey, BitReader, BitWrite, BitWriter, ConstBitLength,
-        FileBitWriter, GlobalResponseId, Random, ResponseReceiveKey, Serde, SerdeErr,
-        SignedInteger, SignedVariableInteger, SocketConfig, UnsignedInteger,
-        UnsignedVariableInteger,
+        default_channels, BigMap, BigMapKey, BitReader, BitWrite, BitWriter, ConnectionStats,
+        ConstBitLength, DisconnectReason, FileBitWriter, FileTransferId, GlobalResponseId,
+        MessageDependency, PendingRequest, Random, RejectReason, ResponseReceiveKey, Serde,
+        SerdeErr, SignedInteger, SignedVariableInteger, SocketConfig, UnsignedInteger,
+        UnsignedVariableInteger, WaitlistStats,
     };
 }
 
 pub use naia_shared::SerdeBevyServer as SerdeBevy;
 
+mod cache_map;
 mod connection;
 mod error;
 mod events;
 mod handshake;
+mod metrics;
+#[cfg(feature = "relay")]
+mod relay;
 mod request;
 mod room;
 mod server;
@@ -42,15 +47,23 @@ pub use connection::tick_buffer_messages::TickBufferMessages;
 pub use error::NaiaServerError;
 pub use events::{
     AuthEvent, ConnectEvent, DelegateEntityEvent, DespawnEntityEvent, DisconnectEvent,
-    EntityAuthGrantEvent, EntityAuthResetEvent, ErrorEvent, Events, InsertComponentEvent,
-    MessageEvent, PublishEntityEvent, RemoveComponentEvent, RequestEvent, SpawnEntityEvent,
+    EntityAuthGrantEvent, EntityAuthResetEvent, ErrorEvent, Events, FileTransferCompleteEvent,
+    FileTransferProgressEvent, InsertComponentEvent, MessageEvent, MessageExpiredEvent,
+    PublishEntityEvent, RemoveComponentEvent, RequestEvent, ResponseEvent, SpawnEntityEvent,
     TickEvent, UnpublishEntityEvent, UpdateComponentEvent,
 };
+#[cfg(feature = "connect_tokens")]
+pub use handshake::{ConnectToken, ConnectTokenError};
+pub use handshake::{HandshakeMetrics, HandshakeObserver, HandshakeOutcome};
+pub use metrics::{ChannelMetrics, ServerMetrics};
+#[cfg(feature = "relay")]
+pub use relay::Relay;
 pub use room::{RoomKey, RoomMut, RoomRef};
 pub use server::Server;
-pub use server_config::ServerConfig;
+pub use server_config::{ConnectTokenConfig, ServerConfig};
 pub use user::{User, UserKey, UserMut, UserRef};
 pub use user_scope::{UserScopeMut, UserScopeRef};
 pub use world::{
-    entity_mut::EntityMut, entity_owner::EntityOwner, replication_config::ReplicationConfig,
+    component_validator::ComponentInsertValidator, entity_mut::EntityMut,
+    entity_owner::EntityOwner, replication_config::ReplicationConfig,
 };
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use naia_shared::{ChannelKind, ChannelKinds};
+
+use crate::handshake::HandshakeMetrics;
+
+/// How many Messages have been sent & received over a single Channel since
+/// the Server started
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelMetrics {
+    /// The Channel's name, see `Protocol::name_channel()`
+    pub channel: String,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+}
+
+/// Counts kept by the Server itself. A transport which serves metrics, such
+/// as the WebRTC transport with `SocketConfig::metrics_enabled`, is sent
+/// these every time the Server receives
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServerMetrics {
+    /// Users with an established connection
+    pub active_users: usize,
+    pub handshake: HandshakeMetrics,
+    /// Packets the Server discarded because they could not be read, or came
+    /// from an address with no connection
+    pub packets_dropped: u64,
+    /// One entry for every Channel a Message has been sent or received over,
+    /// ordered by name
+    pub channels: Vec<ChannelMetrics>,
+}
+
+#[derive(Default)]
+pub(crate) struct ServerStats {
+    // (sent, received) for each Channel
+    channel_messages: HashMap<ChannelKind, (u64, u64)>,
+    packets_dropped: u64,
+}
+
+impl ServerStats {
+    pub fn message_sent(&mut self, channel_kind: &ChannelKind) {
+        self.channel_messages.entry(*channel_kind).or_default().0 += 1;
+    }
+
+    pub fn messages_received(&mut self, channel_kind: &ChannelKind, count: usize) {
+        self.channel_messages.entry(*channel_kind).or_default().1 += count as u64;
+    }
+
+    pub fn packet_dropped(&mut self) {
+        self.packets_dropped += 1;
+    }
+
+    pub fn metrics(
+        &self,
+        channel_kinds: &ChannelKinds,
+        active_users: usize,
+        handshake: HandshakeMetrics,
+    ) -> ServerMetrics {
+        let mut channels: Vec<ChannelMetrics> = self
+            .channel_messages
+            .iter()
+            .map(|(channel_kind, (sent, received))| ChannelMetrics {
+                channel: channel_kinds.kind_to_name(channel_kind),
+                messages_sent: *sent,
+                messages_received: *received,
+            })
+            .collect();
+        channels.sort_by(|a, b| a.channel.cmp(&b.channel));
+        ServerMetrics {
+            active_users,
+            handshake,
+            packets_dropped: self.packets_dropped,
+            channels,
+        }
+    }
+}
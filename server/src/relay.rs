@@ -0,0 +1,202 @@
+use std::{collections::HashMap, hash::Hash};
+
+use naia_client::{
+    transport::Socket as ClientSocket, Client, ClientConfig, DespawnEntityEvent,
+    Events as ClientEvents, NaiaClientError, SpawnEntityEvent,
+};
+use naia_shared::{
+    BitReader, ComponentKind, ComponentKinds, EntityAndGlobalEntityConverter, FileBitWriter,
+    Message, Protocol, Replicate, WorldMutType, WorldRefType,
+};
+
+use crate::{world::world_snapshot::SnapshotEntityConverter, Server};
+
+/// Connects a Server to another, upstream Server as a Client, and mirrors the
+/// Entities the upstream Server replicates to it into the Server's own
+/// World, so they are replicated on to the Server's own Clients. Which
+/// Entities are relayed is up to the upstream Server's scoping.
+///
+/// Mirrored Entities are new Entities of the Server, so any EntityProperty
+/// referring to an upstream Entity is translated to its mirror. Messages from
+/// the upstream Server are left in the Events returned by `receive()`, to be
+/// forwarded by the app, with `local_entity()` to translate the Entities they
+/// refer to
+pub struct Relay<E: Copy + Eq + Hash + Send + Sync> {
+    client: Client<E>,
+    // upstream Entity -> mirrored Entity
+    local_entities: HashMap<E, E>,
+    // mirrored Entity -> upstream Entity
+    upstream_entities: HashMap<E, E>,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> Relay<E> {
+    /// Creates a Relay, which connects with the given Protocol. It must match
+    /// both the upstream Server's Protocol and the relaying Server's
+    pub fn new<P: Into<Protocol>>(client_config: ClientConfig, protocol: P) -> Self {
+        Self {
+            client: Client::new(client_config, protocol),
+            local_entities: HashMap::new(),
+            upstream_entities: HashMap::new(),
+        }
+    }
+
+    /// Set the auth object to use when connecting to the upstream Server
+    pub fn auth<M: Message>(&mut self, auth: M) {
+        self.client.auth(auth);
+    }
+
+    /// Connects to the upstream Server over the given transport
+    pub fn connect<S: Into<Box<dyn ClientSocket>>>(
+        &mut self,
+        socket: S,
+    ) -> Result<(), NaiaClientError> {
+        self.client.connect(socket)
+    }
+
+    /// The Client connected to the upstream Server, e.g. to send it Messages
+    pub fn client(&self) -> &Client<E> {
+        &self.client
+    }
+
+    /// The Client connected to the upstream Server, e.g. to send it Messages
+    pub fn client_mut(&mut self) -> &mut Client<E> {
+        &mut self.client
+    }
+
+    /// Receives from the upstream Server into the upstream World, which must
+    /// be kept apart from the Server's own World. Pass the returned Events to
+    /// `mirror_entities()`
+    pub fn receive<W: WorldMutType<E>>(&mut self, upstream_world: W) -> ClientEvents<E> {
+        self.client.receive(upstream_world)
+    }
+
+    /// Applies the Entity & Component changes in the given Events to the
+    /// mirrored Entities in the Server's World, taking those changes out of
+    /// the Events. Returns the Entities mirrored for the first time, which
+    /// must be added to a Room to be replicated to the Server's Clients
+    pub fn mirror_entities<U: WorldRefType<E>, W: WorldMutType<E>>(
+        &mut self,
+        server: &mut Server<E>,
+        events: &mut ClientEvents<E>,
+        upstream_world: U,
+        mut world: W,
+    ) -> Vec<E> {
+        let mut spawned = Vec::new();
+        for upstream_entity in events.read::<SpawnEntityEvent>() {
+            let entity = world.spawn_entity();
+            server.enable_entity_replication(&entity);
+            self.local_entities.insert(upstream_entity, entity);
+            self.upstream_entities.insert(entity, upstream_entity);
+            spawned.push(entity);
+        }
+
+        // both sides list every mirrored Entity at the same index, so that an
+        // EntityProperty written against the upstream Entities is read back
+        // against their mirrors
+        let (upstream_global_entities, global_entities) = self
+            .local_entities
+            .iter()
+            .filter_map(|(upstream_entity, entity)| {
+                let upstream_global_entity =
+                    self.client.entity_to_global_entity(upstream_entity).ok()?;
+                let global_entity = server.entity_to_global_entity(entity).ok()?;
+                Some((upstream_global_entity, global_entity))
+            })
+            .unzip();
+        let mut upstream_converter = SnapshotEntityConverter::new(upstream_global_entities);
+        let converter = SnapshotEntityConverter::new(global_entities);
+        let component_kinds = server.component_kinds();
+
+        let mut mirror_component = |upstream_entity: &E, component_kind: &ComponentKind| {
+            let entity = self.local_entities.get(upstream_entity)?;
+            let component = upstream_world.component_of_kind(upstream_entity, component_kind)?;
+            let component = translate_component(
+                &*component,
+                component_kinds,
+                &mut upstream_converter,
+                &converter,
+            )?;
+            Some((*entity, component))
+        };
+
+        let mut inserts = Vec::new();
+        for (component_kind, upstream_entities) in events.take_inserts().unwrap_or_default() {
+            for upstream_entity in upstream_entities {
+                inserts.extend(mirror_component(&upstream_entity, &component_kind));
+            }
+        }
+        let mut updates = Vec::new();
+        for (component_kind, upstream_entities) in events.take_updates().unwrap_or_default() {
+            for (_, upstream_entity) in upstream_entities {
+                updates.extend(mirror_component(&upstream_entity, &component_kind));
+            }
+        }
+
+        for (entity, mut component) in inserts {
+            let component_kind = component.kind();
+            if world.has_component_of_kind(&entity, &component_kind) {
+                updates.push((entity, component));
+                continue;
+            }
+            component.host_own();
+            server.insert_component_worldless(&entity, component.as_mut());
+            world.insert_boxed_component(&entity, component);
+        }
+        for (entity, component) in updates {
+            if let Some(mut local_component) =
+                world.component_mut_of_kind(&entity, &component.kind())
+            {
+                local_component.mirror(component.as_ref());
+            }
+        }
+
+        for (component_kind, removals) in events.take_removes().unwrap_or_default() {
+            for (upstream_entity, _) in removals {
+                let Some(entity) = self.local_entities.get(&upstream_entity) else {
+                    continue;
+                };
+                server.remove_component_worldless(entity, &component_kind);
+                world.remove_component_of_kind(entity, &component_kind);
+            }
+        }
+
+        for upstream_entity in events.read::<DespawnEntityEvent>() {
+            let Some(entity) = self.local_entities.remove(&upstream_entity) else {
+                continue;
+            };
+            self.upstream_entities.remove(&entity);
+            server.despawn_entity_worldless(&entity);
+            world.despawn_entity(&entity);
+        }
+
+        spawned
+    }
+
+    /// The Server's mirror of an Entity in the upstream World
+    pub fn local_entity(&self, upstream_entity: &E) -> Option<E> {
+        self.local_entities.get(upstream_entity).copied()
+    }
+
+    /// The Entity in the upstream World which the Server's Entity mirrors
+    pub fn upstream_entity(&self, entity: &E) -> Option<E> {
+        self.upstream_entities.get(entity).copied()
+    }
+}
+
+// Copies a Component from the upstream World, with its EntityProperties
+// pointing at the mirrors of the Entities they pointed at upstream
+fn translate_component(
+    component: &dyn Replicate,
+    component_kinds: &ComponentKinds,
+    upstream_converter: &mut SnapshotEntityConverter,
+    converter: &SnapshotEntityConverter,
+) -> Option<Box<dyn Replicate>> {
+    // a received Component can't be written, but a copy of it is host-owned
+    let upstream_component = component.copy_to_box();
+
+    let mut writer = FileBitWriter::new();
+    upstream_component.write(component_kinds, &mut writer, upstream_converter);
+    let bytes = writer.to_vec();
+    let mut reader = BitReader::new(&bytes);
+    component_kinds.read(&mut reader, converter).ok()
+}
@@ -51,6 +51,16 @@ impl GlobalRequestManager {
         let (_, response_opt) = self.map.get_mut(request_id).unwrap();
         *response_opt = Some(response);
     }
+
+    pub(crate) fn remove_request_id(&mut self, request_id: &GlobalRequestId) {
+        self.map.remove(request_id);
+    }
+
+    // Discards all Requests sent to a User, as they will never be answered
+    pub(crate) fn remove_user(&mut self, user_key: &UserKey) {
+        self.map
+            .retain(|_, (request_user_key, _)| request_user_key != user_key);
+    }
 }
 
 // GlobalResponseManager
@@ -94,4 +104,50 @@ impl GlobalResponseManager {
     ) -> Option<(UserKey, ChannelKind, LocalResponseId)> {
         self.map.remove(global_response_id)
     }
+
+    // Discards all Requests received from a User, as they can no longer be
+    // answered
+    pub(crate) fn remove_user(&mut self, user_key: &UserKey) {
+        self.map
+            .retain(|_, (response_user_key, _, _)| response_user_key != user_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{BigMapKey, ChannelKind, LocalRequestId, SystemChannel};
+
+    use super::{GlobalRequestManager, GlobalResponseManager};
+    use crate::UserKey;
+
+    #[test]
+    fn disconnect_discards_user_requests() {
+        let mut manager = GlobalRequestManager::new();
+        let user_a = UserKey::from_u64(0);
+        let user_b = UserKey::from_u64(1);
+        manager.create_request_id(&user_a);
+        manager.create_request_id(&user_a);
+        let request_b = manager.create_request_id(&user_b);
+
+        manager.remove_user(&user_a);
+
+        assert_eq!(manager.map.len(), 1);
+        assert!(manager.map.contains_key(&request_b));
+    }
+
+    #[test]
+    fn disconnect_discards_user_responses() {
+        let mut manager = GlobalResponseManager::new();
+        let user_a = UserKey::from_u64(0);
+        let user_b = UserKey::from_u64(1);
+        let channel_kind = ChannelKind::of::<SystemChannel>();
+        let local_response_id = LocalRequestId::from(0).receive_from_remote();
+        let response_a = manager.create_response_id(&user_a, &channel_kind, &local_response_id);
+        let response_b = manager.create_response_id(&user_b, &channel_kind, &local_response_id);
+
+        manager.remove_user(&user_a);
+
+        assert!(manager.destroy_response_id(&response_a).is_none());
+        assert!(manager.destroy_response_id(&response_b).is_some());
+    }
 }
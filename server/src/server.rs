@@ -9,7 +9,17 @@ use std::{
 
 use log::{info, warn};
 
-use naia_shared::{BigMap, BitReader, BitWriter, Channel, ChannelKind, ComponentKind, EntityAndGlobalEntityConverter, EntityAndLocalEntityConverter, EntityAuthStatus, EntityConverterMut, EntityDoesNotExistError, EntityEventMessage, EntityResponseEvent, FakeEntityConverter, GlobalEntity, GlobalRequestId, GlobalResponseId, GlobalWorldManagerType, Instant, Message, MessageContainer, PacketType, Protocol, RemoteEntity, Replicate, ReplicatedComponent, Request, Response, ResponseReceiveKey, ResponseSendKey, Serde, SerdeErr, SharedGlobalWorldManager, SocketConfig, StandardHeader, SystemChannel, Tick, Timer, WorldMutType, WorldRefType};
+use naia_shared::{
+    handshake::HandshakeHeader, BigMap, BitReader, BitWriter, Channel, ChannelKind, ChannelMode,
+    ComponentKind, ConnectionStats, DisconnectReason, EntityAndGlobalEntityConverter,
+    EntityAndLocalEntityConverter, EntityAuthStatus, EntityConverterMut, EntityDoesNotExistError,
+    EntityEventMessage, EntityResponseEvent, FakeEntityConverter, FileBitWriter, FileTransferId,
+    GlobalEntity, GlobalRequestId, GlobalResponseId, GlobalWorldManagerType, Instant, Message,
+    MessageContainer, MessageDependency, PacketType, PendingRequest, Protocol, RejectReason,
+    RemoteEntity, Replicate, ReplicatedComponent, Request, Response, ResponseReceiveKey,
+    ResponseSendKey, Serde, SerdeErr, SharedGlobalWorldManager, SocketConfig, StandardHeader,
+    SystemChannel, Tick, Timer, UnsignedVariableInteger, WaitlistStats, WorldMutType, WorldRefType,
+};
 
 use super::{
     error::NaiaServerError,
@@ -19,16 +29,29 @@ use super::{
     user::{User, UserKey, UserMut, UserRef},
     user_scope::{UserScopeMut, UserScopeRef},
 };
+#[cfg(feature = "connect_tokens")]
+use crate::handshake::ConnectTokenValidator;
 use crate::{
+    cache_map::CacheMap,
     connection::{connection::Connection, io::Io, tick_buffer_messages::TickBufferMessages},
-    handshake::{HandshakeAction, HandshakeManager, Handshaker},
+    handshake::{
+        HandshakeAction, HandshakeManager, HandshakeMetrics, HandshakeObserver, Handshaker,
+    },
+    metrics::{ServerMetrics, ServerStats},
     request::{GlobalRequestManager, GlobalResponseManager},
     time_manager::TimeManager,
-    transport::{AuthReceiver, AuthSender, Socket},
+    transport::{AuthReceiver, AuthSender, MetricsSink, Socket},
     world::{
-        entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
-        entity_room_map::EntityRoomMap, entity_scope_map::EntityScopeMap,
-        global_world_manager::GlobalWorldManager, server_auth_handler::AuthOwner,
+        component_scope_map::ComponentScopeMap,
+        component_validator::ComponentInsertValidator,
+        entity_mut::EntityMut,
+        entity_owner::EntityOwner,
+        entity_ref::EntityRef,
+        entity_room_map::EntityRoomMap,
+        entity_scope_map::EntityScopeMap,
+        global_world_manager::GlobalWorldManager,
+        server_auth_handler::AuthOwner,
+        world_snapshot::{SnapshotEntityConverter, WORLD_SNAPSHOT_VERSION},
     },
     ReplicationConfig,
 };
@@ -46,6 +69,11 @@ pub struct Server<E: Copy + Eq + Hash + Send + Sync> {
     timeout_timer: Timer,
     ping_timer: Timer,
     handshake_manager: Box<dyn Handshaker>,
+    #[cfg(feature = "connect_tokens")]
+    connect_token_validator: Option<ConnectTokenValidator>,
+    component_insert_validator: Option<Box<dyn ComponentInsertValidator>>,
+    stats: ServerStats,
+    metrics_sink: Option<Box<dyn MetricsSink>>,
     // Users
     users: BigMap<UserKey, User>,
     user_connections: HashMap<SocketAddr, Connection<E>>,
@@ -54,9 +82,13 @@ pub struct Server<E: Copy + Eq + Hash + Send + Sync> {
     // Entities
     entity_room_map: EntityRoomMap<E>,
     entity_scope_map: EntityScopeMap<E>,
+    component_scope_map: ComponentScopeMap<E>,
     global_world_manager: GlobalWorldManager<E>,
     // Events
     incoming_events: Events<E>,
+    // keys of the Messages sent with `Client::send_message_with_key()` which
+    // have been processed, shared by every Connection
+    processed_message_keys: CacheMap<u64, ()>,
     // Requests/Responses
     global_request_manager: GlobalRequestManager,
     global_response_manager: GlobalResponseManager,
@@ -87,7 +119,25 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             heartbeat_timer: Timer::new(server_config.connection.heartbeat_interval),
             timeout_timer: Timer::new(server_config.connection.disconnection_timeout_duration),
             ping_timer: Timer::new(server_config.ping.ping_interval),
-            handshake_manager: Box::new(HandshakeManager::new()),
+            handshake_manager: Box::new(match &server_config.handshake_key {
+                Some(key_material) => HandshakeManager::with_key(
+                    key_material,
+                    server_config.handshake_difficulty,
+                    server_config.handshake_key_rotation_interval,
+                ),
+                None => HandshakeManager::new(
+                    server_config.handshake_difficulty,
+                    server_config.handshake_key_rotation_interval,
+                ),
+            }),
+            #[cfg(feature = "connect_tokens")]
+            connect_token_validator: server_config
+                .connect_token
+                .as_ref()
+                .map(ConnectTokenValidator::new),
+            component_insert_validator: None,
+            stats: ServerStats::default(),
+            metrics_sink: None,
             // Users
             users: BigMap::new(),
             user_connections: HashMap::new(),
@@ -96,9 +146,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             // Entities
             entity_room_map: EntityRoomMap::new(),
             entity_scope_map: EntityScopeMap::new(),
+            component_scope_map: ComponentScopeMap::new(),
             global_world_manager: GlobalWorldManager::new(),
             // Events
             incoming_events: Events::new(),
+            processed_message_keys: CacheMap::with_capacity(
+                server_config.idempotency_key_capacity.max(1),
+            ),
             // Requests/Responses
             global_request_manager: GlobalRequestManager::new(),
             global_response_manager: GlobalResponseManager::new(),
@@ -110,6 +164,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Listen at the given addresses
     pub fn listen<S: Into<Box<dyn Socket>>>(&mut self, socket: S) {
         let boxed_socket: Box<dyn Socket> = socket.into();
+        self.metrics_sink = boxed_socket.metrics_sink();
         let (auth_sender, auth_receiver, packet_sender, packet_receiver) = boxed_socket.listen();
 
         self.io.load(packet_sender, packet_receiver);
@@ -128,6 +183,43 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         &self.protocol.socket
     }
 
+    #[cfg(feature = "relay")]
+    pub(crate) fn component_kinds(&self) -> &naia_shared::ComponentKinds {
+        &self.protocol.component_kinds
+    }
+
+    /// Sets the validator consulted whenever a client inserts a Component into
+    /// an Entity it has authority over. Components it rejects are removed
+    /// from the Entity, and the rejection is logged
+    pub fn set_component_insert_validator<V: ComponentInsertValidator + 'static>(
+        &mut self,
+        validator: V,
+    ) {
+        self.component_insert_validator = Some(Box::new(validator));
+    }
+
+    /// Returns how many times each step of the Client handshake has
+    /// succeeded or failed since the Server started
+    pub fn handshake_metrics(&self) -> HandshakeMetrics {
+        self.handshake_manager.metrics()
+    }
+
+    /// Returns the counts kept by the Server itself, such as how many Users
+    /// are connected and how many Messages have been sent over each Channel
+    pub fn metrics(&self) -> ServerMetrics {
+        self.stats.metrics(
+            &self.protocol.channel_kinds,
+            self.user_connections.len(),
+            self.handshake_manager.metrics(),
+        )
+    }
+
+    /// Sets the observer notified of the outcome of every step of the Client
+    /// handshake, such as to forward them to an external metrics system
+    pub fn set_handshake_observer<O: HandshakeObserver + 'static>(&mut self, observer: O) {
+        self.handshake_manager.set_observer(Box::new(observer));
+    }
+
     /// Must be called regularly, maintains connection to and receives messages
     /// from all Clients
     pub fn receive<W: WorldMutType<E>>(&mut self, world: W) -> Events<E> {
@@ -143,6 +235,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 .push_tick(self.time_manager.current_tick());
         }
 
+        for (channel_kind, count) in self.incoming_events.message_counts() {
+            self.stats.messages_received(channel_kind, count);
+        }
+        if let Some(metrics_sink) = &self.metrics_sink {
+            metrics_sink.record(&self.metrics());
+        }
+
         // return all received messages and reset the buffer
         std::mem::replace(&mut self.incoming_events, Events::<E>::new())
     }
@@ -180,6 +279,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Rejects an incoming Client User, terminating their attempt to establish
     /// a connection with the Server
     pub fn reject_connection(&mut self, user_key: &UserKey) {
+        self.reject_connection_with_reason(user_key, RejectReason::Unauthorized);
+    }
+
+    /// Rejects an incoming Client User, terminating their attempt to establish
+    /// a connection with the Server, and telling the Client why
+    pub fn reject_connection_with_reason(&mut self, user_key: &UserKey, reason: RejectReason) {
         if let Some(user) = self.users.get_mut(user_key) {
             let auth_addr = user.take_auth_address();
 
@@ -188,7 +293,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 .auth_io
                 .as_mut()
                 .expect("Auth should be set up by this point");
-            if auth_sender.reject(&auth_addr).is_err() {
+            if auth_sender.reject(&auth_addr, &reason).is_err() {
                 warn!(
                     "Server Error: Cannot send auth reject message to {:?}",
                     &auth_addr
@@ -200,6 +305,43 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    /// Gracefully disconnects every connected User, telling their Clients that
+    /// the Server is closing (along with an optional message, i.e.
+    /// "maintenance") so that they neither wait to time out nor try to
+    /// reconnect. Call this before tearing down the Server
+    pub fn shutdown<W: WorldMutType<E>>(&mut self, mut world: W, message: Option<String>) {
+        let reason = DisconnectReason::ServerClosed(message);
+
+        let user_connections: Vec<(SocketAddr, UserKey)> = self
+            .user_connections
+            .iter()
+            .map(|(address, connection)| (*address, connection.user_key))
+            .collect();
+        for (address, user_key) in user_connections {
+            // the Connection is dropped right after, so it cannot resend a
+            // lost packet. Send several copies instead, as Clients do
+            for _ in 0..10 {
+                let writer = Self::write_server_disconnect(&reason);
+                if self.io.send_packet(&address, writer.to_packet()).is_err() {
+                    // TODO: pass this on and handle above
+                    warn!(
+                        "Server Error: Cannot send disconnect packet to {}",
+                        &address
+                    );
+                }
+            }
+            self.user_disconnect(&user_key, &mut world);
+        }
+    }
+
+    fn write_server_disconnect(reason: &DisconnectReason) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
+        HandshakeHeader::ServerDisconnect.ser(&mut writer);
+        reason.ser(&mut writer);
+        writer
+    }
+
     fn finalize_connection(&mut self, user_key: &UserKey, user_address: &SocketAddr) {
         let Some(user) = self.users.get_mut(user_key) else {
             warn!("unknown user is finalizing connection...");
@@ -216,6 +358,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         );
 
         self.user_connections.insert(user.address(), new_connection);
+        self.io.count_bytes(&user.address());
         if self.io.bandwidth_monitor_enabled() {
             self.io.register_client(&user.address());
         }
@@ -225,10 +368,171 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     // Messages
 
     /// Queues up an Message to be sent to the Client associated with a given
-    /// UserKey
-    pub fn send_message<C: Channel, M: Message>(&mut self, user_key: &UserKey, message: &M) {
+    /// UserKey. Returns an error if the Channel's send buffer is full, see
+    /// `ReliableSettings::message_capacity`
+    pub fn send_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<(), NaiaServerError> {
         let cloned_message = M::clone_box(message);
-        self.send_message_inner(user_key, &ChannelKind::of::<C>(), cloned_message);
+        self.send_message_inner(
+            user_key,
+            &ChannelKind::of::<C>(),
+            cloned_message,
+            None,
+            None,
+        )
+    }
+
+    /// Queues up an Message to be sent to the Client associated with a given
+    /// UserKey, like `send_message()`, and returns a handle to it which can be
+    /// passed to `send_message_ordered_after()`. The Channel must be reliable
+    pub fn send_tracked_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Result<MessageDependency, NaiaServerError> {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+        if !channel_settings.reliable() {
+            return Err(NaiaServerError::from_message(
+                "Cannot track a Message sent over an unreliable Channel",
+            ));
+        }
+        self.send_message::<C, M>(user_key, message)?;
+        let Some(connection) = self.user_connection(user_key) else {
+            return Err(NaiaServerError::from_message("User is not connected"));
+        };
+        connection
+            .base
+            .message_manager
+            .last_message_dependency(&channel_kind)
+            .ok_or_else(|| {
+                NaiaServerError::from_message(
+                    "Cannot track a Message sent over a Channel which drops its oldest Messages",
+                )
+            })
+    }
+
+    /// Queues up an Message to be sent to the Client associated with a given
+    /// UserKey, which is held back until the Message identified by `after`
+    /// has been delivered to that Client. `after` must come from a
+    /// `send_tracked_message()` call for the same User. This gives a causal
+    /// ordering between Messages on different Channels, at the cost of a
+    /// round trip of latency when `after` is still in flight
+    pub fn send_message_ordered_after<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+        after: &MessageDependency,
+    ) -> Result<(), NaiaServerError> {
+        let cloned_message = M::clone_box(message);
+        self.send_message_inner(
+            user_key,
+            &ChannelKind::of::<C>(),
+            cloned_message,
+            Some(after),
+            None,
+        )
+    }
+
+    /// Queues up an Message to be sent to the Client associated with a given
+    /// UserKey, which stops being retransmitted if the Client has not
+    /// acknowledged it within `ttl`. It is then returned through
+    /// `MessageExpiredEvent`. The Channel must be
+    /// `ChannelMode::ExpiringReliable`
+    pub fn send_message_with_ttl<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+        ttl: Duration,
+    ) -> Result<(), NaiaServerError> {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+        if !matches!(channel_settings.mode, ChannelMode::ExpiringReliable(_)) {
+            return Err(NaiaServerError::from_message(
+                "Cannot send a Message with a time to live over a Channel which is not ExpiringReliable",
+            ));
+        }
+        let cloned_message = M::clone_box(message);
+        self.send_message_inner(user_key, &channel_kind, cloned_message, None, Some(ttl))
+    }
+
+    /// Sends a file to the Client associated with a given UserKey over the
+    /// built-in `FileTransferChannel`, split into chunks which are only sent
+    /// as fast as the Client acknowledges them. Progress is reported through
+    /// `FileTransferProgressEvent`, and `FileTransferCompleteEvent` once the
+    /// Client holds the whole file. If the Client disconnects part way, it
+    /// keeps the bytes it received, and sending the same file under the same
+    /// name once it reconnects resumes the transfer from there
+    pub fn send_file(
+        &mut self,
+        user_key: &UserKey,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<FileTransferId, NaiaServerError> {
+        if data.len() > u32::MAX as usize {
+            return Err(NaiaServerError::from_message(
+                "Cannot send a file larger than 4 GiB",
+            ));
+        }
+        let Some(user) = self.users.get(user_key) else {
+            return Err(NaiaServerError::from_message("user does not exist"));
+        };
+        if !user.has_address() {
+            return Err(NaiaServerError::from_message("User is not connected"));
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return Err(NaiaServerError::from_message("User is not connected"));
+        };
+        Ok(connection.file_transfer_sender.start(
+            &self.protocol.message_kinds,
+            &mut connection.base.message_manager,
+            name,
+            data.into_boxed_slice(),
+        ))
+    }
+
+    fn user_connection(&self, user_key: &UserKey) -> Option<&Connection<E>> {
+        let user = self.users.get(user_key)?;
+        if !user.has_address() {
+            return None;
+        }
+        self.user_connections.get(&user.address())
+    }
+
+    /// Queues up a Message to be sent to the Client which currently controls
+    /// the given Entity. For a Delegated Entity this is the Client holding
+    /// authority over it, and for a Client-owned Entity it is the owner.
+    /// Returns an error if no Client controls the Entity, which is always the
+    /// case for a Server-owned Entity that is not Delegated
+    pub fn send_message_to_entity_owner<C: Channel, M: Message>(
+        &mut self,
+        entity: &E,
+        message: &M,
+    ) -> Result<(), NaiaServerError> {
+        let Some(user_key) = self.entity_controlling_user(entity) else {
+            return Err(NaiaServerError::from_message(
+                "Cannot send message to Entity owner: no Client controls this Entity",
+            ));
+        };
+        self.send_message::<C, M>(&user_key, message)
+    }
+
+    /// Returns the User which currently controls the given Entity, if any.
+    /// For a Delegated Entity this is the User holding authority over it,
+    /// and None both when the Server holds authority and when no one does;
+    /// use `entity(..).authority()` to tell those apart, as it reports
+    /// `Granted` while the Server holds authority. For any other Entity this
+    /// is the Client which owns it. See `send_message_to_entity_owner()`
+    pub fn entity_controlling_user(&self, entity: &E) -> Option<UserKey> {
+        self.global_world_manager.entity_controlling_user(entity)
+    }
+
+    // SystemChannel is unbounded, so queueing on it never fails
+    fn send_system_message(&mut self, user_key: &UserKey, message: &EntityEventMessage) {
+        let _ = self.send_message::<SystemChannel, EntityEventMessage>(user_key, message);
     }
 
     /// Queues up an Message to be sent to the Client associated with a given
@@ -238,16 +542,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         user_key: &UserKey,
         channel_kind: &ChannelKind,
         message_box: Box<dyn Message>,
-    ) {
+        after: Option<&MessageDependency>,
+        ttl: Option<Duration>,
+    ) -> Result<(), NaiaServerError> {
         let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
 
         if !channel_settings.can_send_to_client() {
-            panic!("Cannot send message to Client on this Channel");
+            panic!(
+                "Cannot send message to Client on Channel `{}`",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
         }
 
         if let Some(user) = self.users.get(user_key) {
             if !user.has_address() {
-                return;
+                return Ok(());
             }
             if let Some(connection) = self.user_connections.get_mut(&user.address()) {
                 let mut converter = EntityConverterMut::new(
@@ -255,33 +564,122 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     &mut connection.base.local_world_manager,
                 );
                 let message = MessageContainer::from_write(message_box, &mut converter);
-                connection.base.message_manager.send_message(
-                    &self.protocol.message_kinds,
-                    &mut converter,
-                    channel_kind,
-                    message,
-                );
+                let message_manager = &mut connection.base.message_manager;
+                let result = match (after, ttl) {
+                    (Some(dependency), _) => message_manager.send_message_ordered_after(
+                        &self.protocol.message_kinds,
+                        &mut converter,
+                        channel_kind,
+                        message,
+                        dependency,
+                    ),
+                    (None, Some(ttl)) => message_manager.send_message_with_ttl(
+                        &self.protocol.message_kinds,
+                        &mut converter,
+                        channel_kind,
+                        message,
+                        ttl,
+                    ),
+                    (None, None) => message_manager.send_message(
+                        &self.protocol.message_kinds,
+                        &mut converter,
+                        channel_kind,
+                        message,
+                    ),
+                };
+                result.map_err(|err| NaiaServerError::Wrapped(Box::new(err)))?;
+                self.stats.message_sent(channel_kind);
             }
         }
+
+        Ok(())
     }
 
     /// Sends a message to all connected users using a given channel
     pub fn broadcast_message<C: Channel, M: Message>(&mut self, message: &M) {
         let cloned_message = M::clone_box(message);
-        self.broadcast_message_inner(&ChannelKind::of::<C>(), cloned_message);
+        let user_keys = self.user_keys();
+        self.send_message_to_users(&ChannelKind::of::<C>(), &user_keys, cloned_message);
+    }
+
+    /// Sends a message to all connected users but one using a given channel,
+    /// i.e. to relay something a User did to everyone else
+    pub fn broadcast_message_except<C: Channel, M: Message>(
+        &mut self,
+        except_user_key: &UserKey,
+        message: &M,
+    ) {
+        let cloned_message = M::clone_box(message);
+        let mut user_keys = self.user_keys();
+        user_keys.retain(|user_key| user_key != except_user_key);
+        self.send_message_to_users(&ChannelKind::of::<C>(), &user_keys, cloned_message);
     }
 
-    fn broadcast_message_inner(
+    // Queues up a Message to be sent to each of the given Users. A Message
+    // which references no Entities is serialized once and its bits shared
+    // between every connection, rather than serialized for each User
+    fn send_message_to_users(
         &mut self,
         channel_kind: &ChannelKind,
+        user_keys: &[UserKey],
         message_box: Box<dyn Message>,
     ) {
-        self.user_keys().iter().for_each(|user_key| {
-            self.send_message_inner(user_key, channel_kind, message_box.clone())
-        })
+        if message_box.has_entity_properties() {
+            for user_key in user_keys {
+                if let Err(err) =
+                    self.send_message_inner(user_key, channel_kind, message_box.clone(), None, None)
+                {
+                    warn!(
+                        "Unable to broadcast message to user {:?}: {}",
+                        user_key, err
+                    );
+                }
+            }
+            return;
+        }
+
+        let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
+        if !channel_settings.can_send_to_client() {
+            panic!(
+                "Cannot send message to Client on Channel `{}`",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
+        }
+
+        let message =
+            MessageContainer::from_write_shared(message_box, &self.protocol.message_kinds);
+        for user_key in user_keys {
+            let Some(user) = self.users.get(user_key) else {
+                continue;
+            };
+            if !user.has_address() {
+                continue;
+            }
+            let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                continue;
+            };
+            if let Err(err) = connection.base.message_manager.send_message(
+                &self.protocol.message_kinds,
+                &mut FakeEntityConverter,
+                channel_kind,
+                message.clone(),
+            ) {
+                warn!(
+                    "Unable to broadcast message to user {:?}: {}",
+                    user_key, err
+                );
+                continue;
+            }
+            self.stats.message_sent(channel_kind);
+        }
     }
 
-    //
+    /// Queues up a Request to be sent to the Client associated with a given
+    /// UserKey. The Client receives it as a `RequestEvent` and answers with
+    /// `Client::send_response()`. Once the answer arrives a `ResponseEvent`
+    /// yields the returned key, and `receive_response()` collects the
+    /// Response with it. If the User disconnects first, the Request is
+    /// discarded and `receive_response()` will never yield a Response
     pub fn send_request<C: Channel, Q: Request>(
         &mut self,
         user_key: &UserKey,
@@ -301,11 +699,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
 
         if !channel_settings.can_request_and_respond() {
-            panic!("Requests can only be sent over Bidirectional, Reliable Channels");
+            panic!(
+                "Requests can only be sent over Bidirectional, Reliable Channels, which `{}` is not",
+                self.protocol.channel_kinds.kind_to_name(channel_kind)
+            );
         }
 
-        let request_id = self.global_request_manager.create_request_id(user_key);
-
         let Some(user) = self.users.get(user_key) else {
             warn!("user does not exist");
             return Err(NaiaServerError::Message("user does not exist".to_string()));
@@ -327,14 +726,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             &mut connection.base.local_world_manager,
         );
 
+        let request_id = self.global_request_manager.create_request_id(user_key);
         let message = MessageContainer::from_write(request_box, &mut converter);
-        connection.base.message_manager.send_request(
+        if let Err(err) = connection.base.message_manager.send_request(
             &self.protocol.message_kinds,
             &mut converter,
             channel_kind,
             request_id,
             message,
-        );
+            None,
+        ) {
+            self.global_request_manager.remove_request_id(&request_id);
+            return Err(NaiaServerError::Wrapped(Box::new(err)));
+        }
 
         return Ok(request_id);
     }
@@ -379,16 +783,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             &mut connection.base.local_world_manager,
         );
         let response = MessageContainer::from_write(response_box, &mut converter);
-        connection.base.message_manager.send_response(
+        if let Err(err) = connection.base.message_manager.send_response(
             &self.protocol.message_kinds,
             &mut converter,
             &channel_kind,
             local_response_id,
             response,
-        );
+        ) {
+            warn!("Unable to send response: {}", err);
+            return false;
+        }
         return true;
     }
 
+    /// Returns the Response to a Request sent with `send_request()`, along
+    /// with the User who sent it, or None if it has not arrived yet
     pub fn receive_response<S: Response>(
         &mut self,
         response_key: &ResponseReceiveKey<S>,
@@ -405,6 +814,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .unwrap();
         return Some((user_key, response));
     }
+
+    /// Returns the Requests sent to the Client associated with a given UserKey
+    /// which are still awaiting a Response, i.e. for displaying in a debug
+    /// overlay
+    pub fn pending_requests(&self, user_key: &UserKey) -> Vec<PendingRequest> {
+        let Some(user) = self.users.get(user_key) else {
+            return Vec::new();
+        };
+        if !user.has_address() {
+            return Vec::new();
+        }
+        let Some(connection) = self.user_connections.get(&user.address()) else {
+            return Vec::new();
+        };
+        connection.base.message_manager.pending_requests()
+    }
     //
 
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
@@ -413,6 +838,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             // receive messages from anyone
             connection.tick_buffer_messages(tick, &mut tick_buffer_messages);
         }
+        for (channel_kind, count) in tick_buffer_messages.message_counts() {
+            self.stats.messages_received(channel_kind, count);
+        }
         tick_buffer_messages
     }
 
@@ -461,6 +889,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         for user_address in user_addresses {
             let connection = self.user_connections.get_mut(&user_address).unwrap();
 
+            connection.send_file_chunks(&self.protocol, &mut self.incoming_events);
+
             connection.send_packets(
                 &self.protocol,
                 &now,
@@ -469,6 +899,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 &self.global_world_manager,
                 &self.time_manager,
             );
+
+            for (channel_kind, message) in connection.base.message_manager.take_expired_messages() {
+                self.incoming_events.push_expired_message(
+                    &connection.user_key,
+                    &channel_kind,
+                    message,
+                );
+            }
         }
     }
 
@@ -500,19 +938,140 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.despawn_entity_worldless(entity);
     }
 
+    /// Stops sending updates for an Entity, while leaving it spawned on
+    /// Clients at its last replicated state. Changes made in the meantime
+    /// are held, and sent once replication is resumed
     pub fn pause_entity_replication(&mut self, entity: &E) {
         self.global_world_manager.pause_entity_replication(entity);
     }
 
+    /// Resumes sending updates for an Entity whose replication was paused
     pub fn resume_entity_replication(&mut self, entity: &E) {
         self.global_world_manager.resume_entity_replication(entity);
     }
 
+    /// Gets whether updates for an Entity are currently paused
+    pub fn entity_replication_is_paused(&self, entity: &E) -> Option<bool> {
+        self.global_world_manager
+            .entity_replication_is_paused(entity)
+    }
+
+    /// Sets how strongly an Entity's updates are favored when a connection's
+    /// `entity_update_bits_per_tick` budget cannot fit every pending update.
+    /// Entities which miss out on a tick accumulate their priority, so even a
+    /// low priority Entity is eventually sent. The default priority is 1.0
+    pub fn set_entity_priority(&mut self, entity: &E, priority: f32) {
+        if priority < 0.0 {
+            panic!("Entity priority must not be negative!");
+        }
+        self.global_world_manager
+            .set_entity_priority(entity, priority);
+    }
+
+    /// Gets the replication priority of an Entity
+    pub fn entity_priority(&self, entity: &E) -> Option<f32> {
+        self.global_world_manager.entity_priority(entity)
+    }
+
+    /// Sets how many ticks apart an Entity's updates are sent. Pending
+    /// changes are held until the Entity's next scheduled tick, so a large
+    /// interval trades latency for bandwidth. The default interval is 1,
+    /// sending updates every tick
+    pub fn set_entity_replication_tick_interval(&mut self, entity: &E, interval: u16) {
+        if interval == 0 {
+            panic!("Entity replication tick interval must be at least 1!");
+        }
+        self.global_world_manager
+            .set_entity_replication_tick_interval(entity, interval);
+    }
+
+    /// Gets how many ticks apart an Entity's updates are sent
+    pub fn entity_replication_tick_interval(&self, entity: &E) -> Option<u16> {
+        self.global_world_manager
+            .entity_replication_tick_interval(entity)
+    }
+
+    /// Offsets the ticks on which an Entity's updates are sent by a fixed
+    /// number of ticks. Giving many Entities with the same tick interval
+    /// different offsets spreads their updates across the interval, rather
+    /// than sending them all on the same tick. The default offset is 0
+    pub fn set_entity_replication_tick_offset(&mut self, entity: &E, offset: u16) {
+        self.global_world_manager
+            .set_entity_replication_tick_offset(entity, offset);
+    }
+
+    /// Gets the offset applied to the ticks on which an Entity's updates are
+    /// sent
+    pub fn entity_replication_tick_offset(&self, entity: &E) -> Option<u16> {
+        self.global_world_manager
+            .entity_replication_tick_offset(entity)
+    }
+
     /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
     pub fn entity_replication_config(&self, entity: &E) -> Option<ReplicationConfig> {
         self.global_world_manager.entity_replication_config(entity)
     }
 
+    /// Gives authority over a Delegated Entity to the given User, taking it
+    /// from any current holder, as though the User had requested it. Every
+    /// User with the Entity in scope is told of the change, so the given
+    /// User's Client emits an `AuthGrantEvent`, as does the Server. Returns an
+    /// error if the Entity is not Delegated, or if the User is not connected
+    /// or does not have the Entity in scope
+    pub fn grant_authority(
+        &mut self,
+        entity: &E,
+        user_key: &UserKey,
+    ) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.entity_is_delegated(entity) {
+            return Err(NaiaServerError::from_message(
+                "Can only grant authority over an Entity that is Delegated",
+            ));
+        }
+        let Some(connection) = self.user_connection(user_key) else {
+            return Err(NaiaServerError::from_message("User is not connected"));
+        };
+        if !connection.base.host_world_manager.host_has_entity(entity) {
+            return Err(NaiaServerError::from_message(
+                "Can only grant authority over an Entity to a User that has it in scope",
+            ));
+        }
+        if self.user_holds_authority(user_key, entity) {
+            return Ok(());
+        }
+
+        // take authority from any current holder first
+        self.entity_take_authority(entity);
+        self.global_world_manager
+            .server_give_authority(entity, user_key);
+
+        // the User will reply with the Host Entity it uses for this Entity,
+        // see `client_request_authority()`
+        self.send_grant_authority_messages(user_key, entity);
+        self.incoming_events.push_auth_grant(user_key, entity);
+        Ok(())
+    }
+
+    /// Takes authority over a Delegated Entity back from whichever User holds
+    /// it, leaving it Available. Every User with the Entity in scope is told
+    /// of the change, so the former holder's Client emits an `AuthResetEvent`,
+    /// as does the Server. Returns an error if the Entity is not Delegated
+    pub fn revoke_authority(&mut self, entity: &E) -> Result<(), NaiaServerError> {
+        if !self.global_world_manager.entity_is_delegated(entity) {
+            return Err(NaiaServerError::from_message(
+                "Can only revoke authority over an Entity that is Delegated",
+            ));
+        }
+        self.entity_take_authority(entity);
+        Ok(())
+    }
+
+    fn user_holds_authority(&self, user_key: &UserKey, entity: &E) -> bool {
+        self.global_world_manager
+            .user_all_owned_entities(user_key)
+            .is_some_and(|entities| entities.contains(entity))
+    }
+
     /// This is used only for Hecs/Bevy adapter crates, do not use otherwise!
     pub fn entity_take_authority(&mut self, entity: &E) {
         let did_change = self.global_world_manager.server_take_authority(entity);
@@ -555,7 +1114,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             }
         }
         for (user_key, message) in messages_to_send {
-            self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+            self.send_system_message(&user_key, &message);
         }
     }
 
@@ -565,94 +1124,131 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         entity: &E,
         config: ReplicationConfig,
     ) {
+        let prev_config = self.check_entity_replication_config(entity, config);
+        if prev_config == config {
+            panic!(
+                "Entity replication config is already set to {:?}. Should not set twice.",
+                config
+            );
+        }
+        self.apply_entity_replication_config(world, entity, prev_config, config);
+    }
+
+    /// Configures the replication of many Entities in one pass, i.e. when a
+    /// Room's visibility changes. Every change is checked before any is
+    /// applied, so an invalid change leaves all Entities untouched. An Entity
+    /// listed more than once takes its last config, and Entities which already
+    /// have their given config are skipped, so no redundant replication events
+    /// are sent.
+    pub fn configure_entities_replication<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        configs: &[(E, ReplicationConfig)],
+    ) {
+        let mut changes: Vec<(E, ReplicationConfig, ReplicationConfig)> = Vec::new();
+        let mut change_indices: HashMap<E, usize> = HashMap::new();
+        for (entity, config) in configs {
+            let prev_config = self.check_entity_replication_config(entity, *config);
+            if let Some(index) = change_indices.get(entity) {
+                changes[*index].2 = *config;
+            } else {
+                change_indices.insert(*entity, changes.len());
+                changes.push((*entity, prev_config, *config));
+            }
+        }
+
+        for (entity, prev_config, next_config) in changes {
+            if prev_config == next_config {
+                continue;
+            }
+            self.apply_entity_replication_config(world, &entity, prev_config, next_config);
+        }
+    }
+
+    // Panics if the Entity cannot be given this config, otherwise returns its
+    // current config
+    fn check_entity_replication_config(
+        &self,
+        entity: &E,
+        config: ReplicationConfig,
+    ) -> ReplicationConfig {
         if !self.global_world_manager.has_entity(entity) {
             panic!("Entity is not yet replicating. Be sure to call `enable_replication` or `spawn_entity` on the Server, before configuring replication.");
         }
         let entity_owner = self.global_world_manager.entity_owner(entity).unwrap();
         let server_owned: bool = entity_owner.is_server();
         let client_owned: bool = entity_owner.is_client();
-        let next_config = config;
         let prev_config = self
             .global_world_manager
             .entity_replication_config(entity)
             .unwrap();
-        if prev_config == config {
-            panic!(
-                "Entity replication config is already set to {:?}. Should not set twice.",
-                config
-            );
-        }
 
         match prev_config {
             ReplicationConfig::Private => {
                 if server_owned {
                     panic!("Server-owned entity should never be private");
                 }
-                match next_config {
-                    ReplicationConfig::Private => {
-                        panic!("Should not be able to happen");
-                    }
-                    ReplicationConfig::Public => {
-                        // private -> public
-                        self.publish_entity(world, entity, true);
-                    }
-                    ReplicationConfig::Delegated => {
-                        // private -> delegated
-                        if client_owned {
-                            panic!("Cannot downgrade Client's ownership of Entity to Delegated. Do this Client-side if needed.");
-                            // The reasoning here is that the Client's ownership should be respected.
-                            // Yes the Server typically has authority over all things, but I believe this will enforce better standards.
-                        }
-                        self.publish_entity(world, entity, true);
-                        self.entity_enable_delegation(world, entity, None);
-                    }
+            }
+            ReplicationConfig::Delegated => {
+                if client_owned {
+                    panic!("Client-owned entity should never be delegated");
                 }
             }
-            ReplicationConfig::Public => {
-                match next_config {
-                    ReplicationConfig::Private => {
-                        // public -> private
-                        if server_owned {
-                            panic!("Cannot unpublish a Server-owned Entity (doing so would disable replication entirely, just use a local entity instead)");
-                        }
-                        self.unpublish_entity(world, entity, true);
-                    }
-                    ReplicationConfig::Public => {
-                        panic!("Should not be able to happen");
-                    }
-                    ReplicationConfig::Delegated => {
-                        // public -> delegated
-                        if client_owned {
-                            panic!("Cannot downgrade Client's ownership of Entity to Delegated. Do this Client-side if needed.");
-                            // The reasoning here is that the Client's ownership should be respected.
-                            // Yes the Server typically has authority over all things, but I believe this will enforce better standards.
-                        }
-                        self.entity_enable_delegation(world, entity, None);
-                    }
+            ReplicationConfig::Public => {}
+        }
+        if prev_config == config {
+            return prev_config;
+        }
+        match config {
+            ReplicationConfig::Private => {
+                if server_owned {
+                    panic!("Cannot unpublish a Server-owned Entity (doing so would disable replication entirely, just use a local entity instead)");
                 }
             }
             ReplicationConfig::Delegated => {
                 if client_owned {
-                    panic!("Client-owned entity should never be delegated");
-                }
-                match next_config {
-                    ReplicationConfig::Private => {
-                        // delegated -> private
-                        if server_owned {
-                            panic!("Cannot unpublish a Server-owned Entity (doing so would disable replication entirely, just use a local entity instead)");
-                        }
-                        self.entity_disable_delegation(world, entity);
-                        self.unpublish_entity(world, entity, true);
-                    }
-                    ReplicationConfig::Public => {
-                        // delegated -> public
-                        self.entity_disable_delegation(world, entity);
-                    }
-                    ReplicationConfig::Delegated => {
-                        panic!("Should not be able to happen");
-                    }
+                    panic!("Cannot downgrade Client's ownership of Entity to Delegated. Do this Client-side if needed.");
+                    // The reasoning here is that the Client's ownership should be respected.
+                    // Yes the Server typically has authority over all things, but I believe this will enforce better standards.
                 }
             }
+            ReplicationConfig::Public => {}
+        }
+
+        prev_config
+    }
+
+    fn apply_entity_replication_config<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        prev_config: ReplicationConfig,
+        next_config: ReplicationConfig,
+    ) {
+        match (prev_config, next_config) {
+            (ReplicationConfig::Private, ReplicationConfig::Public) => {
+                self.publish_entity(world, entity, true);
+            }
+            (ReplicationConfig::Private, ReplicationConfig::Delegated) => {
+                self.publish_entity(world, entity, true);
+                self.entity_enable_delegation(world, entity, None);
+            }
+            (ReplicationConfig::Public, ReplicationConfig::Private) => {
+                self.unpublish_entity(world, entity, true);
+            }
+            (ReplicationConfig::Public, ReplicationConfig::Delegated) => {
+                self.entity_enable_delegation(world, entity, None);
+            }
+            (ReplicationConfig::Delegated, ReplicationConfig::Private) => {
+                self.entity_disable_delegation(world, entity);
+                self.unpublish_entity(world, entity, true);
+            }
+            (ReplicationConfig::Delegated, ReplicationConfig::Public) => {
+                self.entity_disable_delegation(world, entity);
+            }
+            _ => {
+                panic!("Should not be able to happen");
+            }
         }
     }
 
@@ -663,6 +1259,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         world_entity: &E,
         remote_entity: &RemoteEntity,
     ) {
+        if let Some(is_latest_grant) = self
+            .global_world_manager
+            .take_grant_reply(world_entity, origin_user)
+        {
+            // the user is replying to authority given by the Server with the
+            // Host Entity it migrated the Entity to. If authority has since
+            // been revoked or given again, the user has been told so, and a
+            // later reply will follow
+            if is_latest_grant && self.user_holds_authority(origin_user, world_entity) {
+                self.add_redundant_remote_entity_to_host(origin_user, world_entity, remote_entity);
+            }
+            return;
+        }
+
         let requester = AuthOwner::Client(*origin_user);
         let success = self
             .global_world_manager
@@ -671,51 +1281,56 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             // entity authority was granted for origin user
 
             self.add_redundant_remote_entity_to_host(origin_user, world_entity, remote_entity);
+            self.send_grant_authority_messages(origin_user, world_entity);
 
-            // for any users that have this entity in scope, send an `update_authority_status` message
+            self.incoming_events
+                .push_auth_grant(origin_user, &world_entity);
+        } else {
+            // someone else took authority first, and the user has been told
+            // it is Denied
+            warn!("Denied a request for authority over an Entity which is not Available");
+        }
+    }
 
-            // TODO: we can make this more efficient in the future by caching which Entities
-            // are in each User's scope
-            let mut messages_to_send = Vec::new();
-            for (user_key, user) in self.users.iter() {
-                if !user.has_address() {
-                    continue;
-                }
-                if let Some(connection) = self.user_connections.get(&user.address()) {
-                    if connection
-                        .base
-                        .host_world_manager
-                        .host_has_entity(world_entity)
-                    {
-                        let mut new_status: EntityAuthStatus = EntityAuthStatus::Denied;
-                        if *origin_user == user_key {
-                            new_status = EntityAuthStatus::Granted;
-                        }
+    fn send_grant_authority_messages(&mut self, origin_user: &UserKey, world_entity: &E) {
+        // for any users that have this entity in scope, send an `update_authority_status` message
 
-                        // if new_status == EntityAuthStatus::Denied {
-                        //     warn!("Denying status of entity to user: `{:?}`", user_key);
-                        // } else {
-                        //     warn!("Granting status of entity to user: `{:?}`", user_key);
-                        // }
+        // TODO: we can make this more efficient in the future by caching which Entities
+        // are in each User's scope
+        let mut messages_to_send = Vec::new();
+        for (user_key, user) in self.users.iter() {
+            if !user.has_address() {
+                continue;
+            }
+            if let Some(connection) = self.user_connections.get(&user.address()) {
+                if connection
+                    .base
+                    .host_world_manager
+                    .host_has_entity(world_entity)
+                {
+                    let mut new_status: EntityAuthStatus = EntityAuthStatus::Denied;
+                    if *origin_user == user_key {
+                        new_status = EntityAuthStatus::Granted;
+                    }
 
-                        let message = EntityEventMessage::new_update_auth_status(
-                            &self.global_world_manager,
-                            world_entity,
-                            new_status,
-                        );
+                    // if new_status == EntityAuthStatus::Denied {
+                    //     warn!("Denying status of entity to user: `{:?}`", user_key);
+                    // } else {
+                    //     warn!("Granting status of entity to user: `{:?}`", user_key);
+                    // }
 
-                        messages_to_send.push((user_key, message));
-                    }
+                    let message = EntityEventMessage::new_update_auth_status(
+                        &self.global_world_manager,
+                        world_entity,
+                        new_status,
+                    );
+
+                    messages_to_send.push((user_key, message));
                 }
             }
-            for (user_key, message) in messages_to_send {
-                self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
-            }
-
-            self.incoming_events
-                .push_auth_grant(origin_user, &world_entity);
-        } else {
-            panic!("Failed to request authority for entity");
+        }
+        for (user_key, message) in messages_to_send {
+            self.send_system_message(&user_key, &message);
         }
     }
 
@@ -725,13 +1340,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             else {
                 panic!("Entity should have an Auth status if it is delegated..")
             };
+            // a User given authority before this response arrived has already
+            // been told it is Granted
+            if self.user_holds_authority(user_key, entity) {
+                return;
+            }
             if auth_status != EntityAuthStatus::Available {
                 let message = EntityEventMessage::new_update_auth_status(
                     &self.global_world_manager,
                     entity,
                     auth_status,
                 );
-                self.send_message::<SystemChannel, EntityEventMessage>(user_key, &message);
+                self.send_system_message(&user_key, &message);
             }
         }
     }
@@ -829,6 +1449,132 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return EntityOwner::Local;
     }
 
+    /// Serializes every Server-owned Entity in the World, along with all of
+    /// its Components, into a byte blob which can be persisted and later
+    /// restored with `deserialize_world()`. The blob is tagged with a
+    /// fingerprint of the Protocol's Components, so it can't be loaded against
+    /// an incompatible Protocol. Entities owned by clients are not included
+    pub fn serialize_world<W: WorldRefType<E>>(&self, world: W) -> Vec<u8> {
+        let entities: Vec<E> = world
+            .entities()
+            .into_iter()
+            .filter(|entity| {
+                self.global_world_manager.entity_owner(entity) == Some(EntityOwner::Server)
+            })
+            .collect();
+        let global_entities = entities
+            .iter()
+            .map(|entity| {
+                self.global_world_manager
+                    .entity_to_global_entity(entity)
+                    .expect("replicated entity should have a global entity")
+            })
+            .collect();
+        let mut converter = SnapshotEntityConverter::new(global_entities);
+        let component_kinds = &self.protocol.component_kinds;
+
+        let mut writer = FileBitWriter::new();
+        WORLD_SNAPSHOT_VERSION.ser(&mut writer);
+        component_kinds.fingerprint().ser(&mut writer);
+        UnsignedVariableInteger::<7>::new(entities.len() as u64).ser(&mut writer);
+
+        for entity in &entities {
+            let components: Vec<_> = self
+                .global_world_manager
+                .component_kinds(entity)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|component_kind| world.component_of_kind(entity, component_kind))
+                .collect();
+            UnsignedVariableInteger::<3>::new(components.len() as u64).ser(&mut writer);
+            for component in components {
+                component.kind().ser(component_kinds, &mut writer);
+                component.write(component_kinds, &mut writer, &mut converter);
+            }
+        }
+
+        writer.to_vec()
+    }
+
+    /// Restores a byte blob created by `serialize_world()` into the World,
+    /// spawning a new replicated Entity for each Entity in the blob. Returns
+    /// the spawned Entities, in the order they were serialized. Nothing is
+    /// spawned if the blob is malformed or was written with an incompatible
+    /// Protocol
+    pub fn deserialize_world<W: WorldMutType<E>>(
+        &mut self,
+        mut world: W,
+        bytes: &[u8],
+    ) -> Result<Vec<E>, NaiaServerError> {
+        let malformed = |_: SerdeErr| NaiaServerError::from_message("malformed world snapshot");
+
+        let mut reader = BitReader::new(bytes);
+        let version = u8::de(&mut reader).map_err(malformed)?;
+        if version != WORLD_SNAPSHOT_VERSION {
+            return Err(NaiaServerError::from_message(
+                "unsupported world snapshot version",
+            ));
+        }
+        let fingerprint = u64::de(&mut reader).map_err(malformed)?;
+        if fingerprint != self.protocol.component_kinds.fingerprint() {
+            return Err(NaiaServerError::from_message(
+                "world snapshot was written with an incompatible Protocol",
+            ));
+        }
+        let entity_count = UnsignedVariableInteger::<7>::de(&mut reader)
+            .map_err(malformed)?
+            .get() as usize;
+        if entity_count > bytes.len() * 8 {
+            return Err(malformed(SerdeErr));
+        }
+
+        // spawn all entities up front, so that EntityProperties can refer to them
+        let entities: Vec<E> = (0..entity_count)
+            .map(|_| {
+                let entity = world.spawn_entity();
+                self.spawn_entity_inner(&entity);
+                entity
+            })
+            .collect();
+        let global_entities = entities
+            .iter()
+            .map(|entity| {
+                self.global_world_manager
+                    .entity_to_global_entity(entity)
+                    .expect("spawned entity should have a global entity")
+            })
+            .collect();
+        let converter = SnapshotEntityConverter::new(global_entities);
+
+        let mut entity_components = Vec::with_capacity(entity_count);
+        for _ in 0..entity_count {
+            let result = UnsignedVariableInteger::<3>::de(&mut reader).and_then(|count| {
+                (0..count.get())
+                    .map(|_| self.protocol.component_kinds.read(&mut reader, &converter))
+                    .collect::<Result<Vec<_>, SerdeErr>>()
+            });
+            match result {
+                Ok(components) => entity_components.push(components),
+                Err(err) => {
+                    for entity in &entities {
+                        self.despawn_entity(&mut world, entity);
+                    }
+                    return Err(malformed(err));
+                }
+            }
+        }
+
+        for (entity, components) in entities.iter().zip(entity_components) {
+            for mut component in components {
+                component.host_own();
+                self.insert_component_worldless(entity, component.as_mut());
+                world.insert_boxed_component(entity, component);
+            }
+        }
+
+        Ok(entities)
+    }
+
     // Users
 
     /// Returns whether or not a User exists for the given RoomKey
@@ -975,6 +1721,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.io.incoming_bandwidth_from_client(address)
     }
 
+    /// Gets how much of the outgoing bandwidth budget to the given User is
+    /// spent, from 0.0 to 1.0, so that the game can send less as it fills.
+    /// Returns None if the User isn't connected, or no limit is set. See
+    /// `ConnectionConfig::outgoing_bandwidth_limit`
+    pub fn bandwidth_utilization(&self, user_key: &UserKey) -> Option<f32> {
+        self.user_connection(user_key)?.base.bandwidth_utilization()
+    }
+
     // Ping
     /// Gets the average Round Trip Time measured to the given User's Client
     pub fn rtt(&self, user_key: &UserKey) -> Option<f32> {
@@ -1003,6 +1757,53 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         None
     }
 
+    /// Gets the network conditions measured over the connection to the given
+    /// User's Client
+    pub fn connection_stats(&mut self, user_key: &UserKey) -> Option<ConnectionStats> {
+        let user = self.users.get(user_key)?;
+        let address = user.address_opt()?;
+        let connection = self.user_connections.get(&address)?;
+        let (received, sent) = self.io.byte_counters(&address)?;
+        Some(ConnectionStats {
+            rtt: connection.ping_manager.rtt_average,
+            jitter: connection.ping_manager.jitter_average,
+            loss: connection.base.packet_loss(),
+            bytes_in: received.total_bytes(),
+            bytes_out: sent.total_bytes(),
+            bytes_in_per_second: received.bytes_per_second(),
+            bytes_out_per_second: sent.bytes_per_second(),
+            queued_messages: connection.base.message_manager.queued_message_count(),
+            update_backlog: connection.base.host_world_manager.update_backlog(),
+        })
+    }
+
+    /// Gets how many received items the connection to the given User holds
+    /// while waiting on Entities to come into scope, and how many have been
+    /// dropped because the waitlist was full. See
+    /// `ConnectionConfig::entity_waitlist_capacity`
+    pub fn entity_waitlist_stats(&self, user_key: &UserKey) -> Option<WaitlistStats> {
+        let connection = self.user_connection(user_key)?;
+        Some(connection.base.remote_world_manager.entity_waitlist.stats())
+    }
+
+    /// Re-sends the complete current state of every Component of the given
+    /// Entity to a single User, overwriting whatever the Client holds. This
+    /// is a repair tool for a Client whose copy of the Entity has fallen out
+    /// of sync. Returns false if the Entity is not currently spawned for the
+    /// User
+    pub fn resync_entity(&mut self, user_key: &UserKey, entity: &E) -> bool {
+        let Some(user) = self.users.get(user_key) else {
+            return false;
+        };
+        if !user.has_address() {
+            return false;
+        }
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return false;
+        };
+        connection.base.host_world_manager.resync_entity(entity)
+    }
+
     // Crate-Public methods
 
     //// Entities
@@ -1035,6 +1836,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
         // Delete scope
         self.entity_scope_map.remove_entity(entity);
+        self.component_scope_map.remove_entity(entity);
 
         // Delete room cache entry
         if let Some(room_keys) = self.entity_room_map.remove_from_all_rooms(entity) {
@@ -1066,6 +1868,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     /// Remove all entities from a User's scope
     pub(crate) fn user_scope_remove_user(&mut self, user_key: &UserKey) {
         self.entity_scope_map.remove_user(user_key);
+        self.component_scope_map.remove_user(user_key);
     }
 
     pub(crate) fn user_scope_set_entity(
@@ -1086,6 +1889,57 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    pub(crate) fn user_scope_set_component(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        component_kind: &ComponentKind,
+        is_contained: bool,
+    ) {
+        self.component_scope_map
+            .set(*user_key, *entity, *component_kind, is_contained);
+
+        // if the Entity is already in the User's scope, add or remove the
+        // Component right away. Otherwise it is filtered out once the Entity
+        // comes into scope
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        let Some(user_addr) = user.address_opt() else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user_addr) else {
+            return;
+        };
+        let host_world_manager = &mut connection.base.host_world_manager;
+        if !host_world_manager.host_has_entity(entity) {
+            return;
+        }
+        let currently_contained = host_world_manager.host_has_component(entity, component_kind);
+        if is_contained && !currently_contained {
+            if self
+                .global_world_manager
+                .has_component_record(entity, component_kind)
+            {
+                host_world_manager.insert_component(entity, component_kind);
+            }
+        } else if !is_contained && currently_contained {
+            host_world_manager.remove_component(entity, component_kind);
+        }
+    }
+
+    pub(crate) fn user_scope_has_component(
+        &self,
+        user_key: &UserKey,
+        entity: &E,
+        component_kind: &ComponentKind,
+    ) -> bool {
+        self.user_scope_has_entity(user_key, entity)
+            && self
+                .component_scope_map
+                .is_included(user_key, entity, component_kind)
+    }
+
     //// Components
 
     /// Adds a Component to an Entity
@@ -1176,7 +2030,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             }
 
             // insert component into user's connection
-            if connection.base.host_world_manager.host_has_entity(entity) {
+            if connection.base.host_world_manager.host_has_entity(entity)
+                && self.component_scope_map.is_included(
+                    &connection.user_key,
+                    entity,
+                    component_kind,
+                )
+            {
                 connection
                     .base
                     .host_world_manager
@@ -1216,7 +2076,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         // TODO: should be able to make this more efficient by caching for every Entity
         // which scopes they are part of
         for (_, connection) in self.user_connections.iter_mut() {
-            if connection.base.host_world_manager.host_has_entity(entity) {
+            // the component may have been excluded from the user's scope
+            if connection
+                .base
+                .host_world_manager
+                .host_has_component(entity, component_kind)
+            {
                 // remove component from user connection
                 connection
                     .base
@@ -1244,7 +2109,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 );
             };
             let message = EntityEventMessage::new_publish(&self.global_world_manager, entity);
-            self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+            self.send_system_message(&user_key, &message);
         }
 
         let result = self.global_world_manager.entity_publish(&entity);
@@ -1267,7 +2132,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 panic!("Entity is not owned by a Client or is Private. Cannot publish entity. Owner is: {:?}", entity_owner);
             };
             let message = EntityEventMessage::new_unpublish(&self.global_world_manager, entity);
-            self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+            self.send_system_message(&user_key, &message);
         }
 
         world.entity_unpublish(&entity);
@@ -1305,7 +2170,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 }
             }
             for (user_key, message) in messages_to_send {
-                self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+                self.send_system_message(&user_key, &message);
             }
         }
 
@@ -1387,7 +2252,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             &entity,
             new_host_entity,
         );
-        self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+        self.send_system_message(&user_key, &message);
 
         self.global_world_manager.entity_enable_delegation(&entity);
         world.entity_enable_delegation(&self.global_world_manager, &entity);
@@ -1428,7 +2293,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 }
             }
             for (user_key, message) in messages_to_send {
-                self.send_message::<SystemChannel, EntityEventMessage>(&user_key, &message);
+                self.send_system_message(&user_key, &message);
             }
         }
 
@@ -1457,6 +2322,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     }
 
     /// Get an count of how many Rooms the given User is inside
+    pub(crate) fn user_client_id(&self, user_key: &UserKey) -> Option<u64> {
+        self.users.get(user_key).and_then(User::client_id)
+    }
+
     pub(crate) fn user_rooms_count(&self, user_key: &UserKey) -> Option<usize> {
         if let Some(user) = self.users.get(user_key) {
             return Some(user.room_count());
@@ -1464,6 +2333,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return None;
     }
 
+    /// Tears down everything associated with a connected User: despawns the
+    /// entities they own, tears down their Connection (see
+    /// `Connection::teardown()`), tells other Users that the Entities they
+    /// held authority over are Available, and emits an `AuthResetEvent` for
+    /// each of those Entities along with the disconnect event
     pub(crate) fn user_disconnect<W: WorldMutType<E>>(
         &mut self,
         user_key: &UserKey,
@@ -1471,13 +2345,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     ) {
         if self.protocol.client_authoritative_entities {
             self.despawn_all_remote_entities(user_key, world);
-            if let Some(all_owned_entities) =
-                self.global_world_manager.user_all_owned_entities(user_key)
-            {
-                let copied_entities = all_owned_entities.clone();
-                for entity in copied_entities {
-                    self.entity_release_authority(Some(user_key), &entity);
-                }
+        }
+        let connection = self
+            .users
+            .get(user_key)
+            .and_then(|user| user.address_opt())
+            .and_then(|address| self.user_connections.remove(&address));
+        if let Some(connection) = connection {
+            for entity in connection.teardown(&mut self.global_world_manager) {
+                self.send_reset_authority_messages(&entity);
+                self.incoming_events.push_auth_reset(&entity);
             }
         }
         let user = self.user_delete(user_key);
@@ -1523,6 +2400,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
 
         self.entity_scope_map.remove_user(user_key);
+        self.component_scope_map.remove_user(user_key);
+
+        // the User can no longer answer or receive Responses
+        self.global_request_manager.remove_user(user_key);
+        self.global_response_manager.remove_user(user_key);
 
         self.handshake_manager
             .delete_user(user_key, user.address_opt());
@@ -1539,6 +2421,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         if self.io.bandwidth_monitor_enabled() {
             self.io.deregister_client(&user.address());
         }
+        if let Some(address) = user.address_opt() {
+            self.io.stop_counting_bytes(&address);
+        }
 
         return user;
     }
@@ -1634,9 +2519,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     ) {
         if let Some(room) = self.rooms.get(room_key) {
             let user_keys: Vec<UserKey> = room.user_keys().cloned().collect();
-            for user_key in &user_keys {
-                self.send_message_inner(user_key, channel_kind, message_box.clone())
-            }
+            self.send_message_to_users(channel_kind, &user_keys, message_box);
         }
     }
 
@@ -1704,6 +2587,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         let mut addresses: HashSet<SocketAddr> = HashSet::new();
 
         // receive auth events
+        #[cfg(feature = "connect_tokens")]
+        let mut connect_token_results = Vec::new();
         if let Some((_, auth_receiver)) = self.auth_io.as_mut() {
             loop {
                 match auth_receiver.receive() {
@@ -1711,6 +2596,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         // create new user
                         let user_key = self.users.insert(User::new(auth_addr));
 
+                        // a connect token is answered here, rather than by the app
+                        #[cfg(feature = "connect_tokens")]
+                        if let Some(validator) = &mut self.connect_token_validator {
+                            let users = &self.users;
+                            let result = validator.validate(auth_bytes, |client_id| {
+                                users
+                                    .iter()
+                                    .any(|(_, user)| user.client_id() == Some(client_id))
+                            });
+                            if let Ok(token) = &result {
+                                if let Some(user) = self.users.get_mut(&user_key) {
+                                    user.set_client_id(token.client_id);
+                                }
+                            }
+                            connect_token_results.push((user_key, result));
+                            continue;
+                        }
+
                         // convert bytes into auth object
                         let mut reader = BitReader::new(auth_bytes);
                         let Ok(auth_message) = self
@@ -1735,6 +2638,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 }
             }
         }
+        #[cfg(feature = "connect_tokens")]
+        for (user_key, result) in connect_token_results {
+            match result {
+                Ok(_) => self.accept_connection(&user_key),
+                Err(err) => {
+                    warn!("Server Error: connect token refused, {:?}", err);
+                    self.reject_connection(&user_key);
+                }
+            }
+        }
 
         // receive socket events
         loop {
@@ -1747,6 +2660,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     let Ok(header) = StandardHeader::de(&mut reader) else {
                         // Received a malformed packet
                         // TODO: increase suspicion against packet sender
+                        self.stats.packet_dropped();
                         continue;
                     };
 
@@ -1759,6 +2673,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                                 .is_err()
                             {
                                 warn!("Server Error: cannot read malformed packet");
+                                self.stats.packet_dropped();
                                 continue;
                             }
                         }
@@ -1830,6 +2745,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                                 }
                                 Err(_err) => {
                                     warn!("Server Error: cannot read malformed packet");
+                                    self.stats.packet_dropped();
                                 }
                             }
                         }
@@ -1859,6 +2775,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     ) -> Result<(), SerdeErr> {
         // Packets requiring established connection
         let Some(connection) = self.user_connections.get_mut(address) else {
+            self.stats.packet_dropped();
             return Ok(());
         };
 
@@ -1909,8 +2826,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     &mut self.global_world_manager,
                     &mut self.global_request_manager,
                     &mut self.global_response_manager,
+                    self.component_insert_validator.as_deref(),
                     world,
                     &mut self.incoming_events,
+                    &mut self.processed_message_keys,
                 ),
             )
         };
@@ -2258,8 +3177,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         if currently_in_scope {
                             continue;
                         }
-                        let component_kinds =
-                            self.global_world_manager.component_kinds(entity).unwrap();
+                        let component_kinds = self
+                            .global_world_manager
+                            .component_kinds(entity)
+                            .unwrap()
+                            .into_iter()
+                            .filter(|component_kind| {
+                                self.component_scope_map.is_included(
+                                    user_key,
+                                    entity,
+                                    component_kind,
+                                )
+                            })
+                            .collect();
                         // add entity & components to the connections local scope
                         connection.base.host_world_manager.init_entity(
                             &mut connection.base.local_world_manager,
@@ -2282,7 +3212,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         let channel_kind = ChannelKind::of::<SystemChannel>();
                         let message =
                             MessageContainer::from_write(Box::new(event_message), &mut converter);
-                        connection.base.message_manager.send_message(
+                        // SystemChannel is unbounded, so queueing on it never fails
+                        let _ = connection.base.message_manager.send_message(
                             &self.protocol.message_kinds,
                             &mut converter,
                             &channel_kind,
@@ -1,4 +1,4 @@
-use std::default::Default;
+use std::{default::Default, net::SocketAddr, time::Duration};
 
 use naia_shared::ConnectionConfig;
 
@@ -14,6 +14,43 @@ pub struct ServerConfig {
     pub require_auth: bool,
     /// Configuration used to monitor the ping & jitter on the network
     pub ping: PingConfig,
+    /// The number of leading zero bits a Client's proof-of-work must have
+    /// before its handshake is validated. Each additional bit doubles the
+    /// expected work for a connecting Client. Only enforced when the
+    /// `advanced_handshake` feature is enabled. Set to zero to disable
+    pub handshake_difficulty: u8,
+    /// How often to replace the key used to sign Client handshakes, limiting
+    /// how long a leaked key remains useful. Handshakes signed with the
+    /// previous key are still accepted until the following rotation. Only
+    /// applies when the `advanced_handshake` feature is enabled. Set to
+    /// `None` to keep one key for the lifetime of the Server
+    pub handshake_key_rotation_interval: Option<Duration>,
+    /// A secret from which to derive the keys used to sign Client handshakes,
+    /// so that every Server behind a load balancer sharing it can validate
+    /// the others' handshakes. Should be at least 32 random bytes. Only
+    /// applies when the `advanced_handshake` feature is enabled. Set to
+    /// `None` to generate a random key for each Server
+    pub handshake_key: Option<Vec<u8>>,
+    /// How many keys from `Client::send_message_with_key` to remember. A
+    /// keyed Message is dropped if its key is among the most recent keys
+    /// processed. Keys are shared by all Users and kept across reconnects, so
+    /// that a command retried on a new connection is still recognized
+    pub idempotency_key_capacity: usize,
+    /// Accepts Clients presenting a connect token signed by the game's
+    /// backend, without an `AuthEvent` for the Server app to answer. Only
+    /// applies when the `connect_tokens` feature is enabled. Set to `None` to
+    /// treat every Client's auth as an auth message
+    pub connect_token: Option<ConnectTokenConfig>,
+}
+
+/// How to validate the connect tokens Clients present, see `ConnectToken`
+#[derive(Clone)]
+pub struct ConnectTokenConfig {
+    /// The key shared with the backend signing tokens. Should be at least 32
+    /// random bytes
+    pub key: Vec<u8>,
+    /// The Server's public address, which tokens must be signed for
+    pub server_addr: SocketAddr,
 }
 
 impl Default for ServerConfig {
@@ -22,6 +59,11 @@ impl Default for ServerConfig {
             connection: ConnectionConfig::default(),
             require_auth: true,
             ping: PingConfig::default(),
+            handshake_difficulty: 0,
+            handshake_key_rotation_interval: None,
+            handshake_key: None,
+            idempotency_key_capacity: 256,
+            connect_token: None,
         }
     }
 }
@@ -0,0 +1,121 @@
+use std::net::SocketAddr;
+
+use naia_shared::{IdentityToken, LoopbackServer, RejectReason};
+
+pub use naia_shared::LoopbackTransport;
+
+use super::{
+    AuthReceiver, AuthSender, PacketReceiver, PacketSender, RecvError, SendError,
+    Socket as TransportSocket,
+};
+use crate::user::UserAuthAddr;
+
+/// Serves Clients in the same process through a `LoopbackTransport`, without
+/// binding any ports
+pub struct Socket {
+    transport: LoopbackTransport,
+}
+
+impl Socket {
+    pub fn new(transport: &LoopbackTransport) -> Self {
+        Self {
+            transport: transport.clone(),
+        }
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn listen(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn AuthSender>,
+        Box<dyn AuthReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let server = self.transport.server();
+        (
+            Box::new(LoopbackAuthSender(server.clone())),
+            Box::new(LoopbackAuthReceiver {
+                server: server.clone(),
+                buffer: Box::new([]),
+            }),
+            Box::new(LoopbackPacketSender(server.clone())),
+            Box::new(LoopbackPacketReceiver {
+                server,
+                buffer: Box::new([]),
+            }),
+        )
+    }
+}
+
+struct LoopbackAuthSender(LoopbackServer);
+
+impl AuthSender for LoopbackAuthSender {
+    fn accept(
+        &self,
+        address: &UserAuthAddr,
+        identity_token: &IdentityToken,
+    ) -> Result<(), SendError> {
+        self.0
+            .accept(&address.addr(), identity_token)
+            .map_err(|_| SendError)
+    }
+    fn reject(&self, address: &UserAuthAddr, reason: &RejectReason) -> Result<(), SendError> {
+        self.0
+            .reject(&address.addr(), reason)
+            .map_err(|_| SendError)
+    }
+}
+
+#[derive(Clone)]
+struct LoopbackAuthReceiver {
+    server: LoopbackServer,
+    // holds the last auth received, which is lent out until the next
+    buffer: Box<[u8]>,
+}
+
+impl AuthReceiver for LoopbackAuthReceiver {
+    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError> {
+        match self.server.receive_auth() {
+            Some((address, auth_bytes)) => {
+                self.buffer = auth_bytes;
+                Ok(Some((UserAuthAddr::new(address), &self.buffer)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct LoopbackPacketSender(LoopbackServer);
+
+impl PacketSender for LoopbackPacketSender {
+    fn send(&self, address: &SocketAddr, payload: &[u8]) -> Result<(), SendError> {
+        self.0.send(address, payload).map_err(|_| SendError)
+    }
+}
+
+#[derive(Clone)]
+struct LoopbackPacketReceiver {
+    server: LoopbackServer,
+    // holds the last packet received, which is lent out until the next
+    buffer: Box<[u8]>,
+}
+
+impl PacketReceiver for LoopbackPacketReceiver {
+    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, RecvError> {
+        match self.server.receive() {
+            Some((address, payload)) => {
+                self.buffer = payload;
+                Ok(Some((address, &self.buffer)))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -6,27 +6,45 @@ cfg_if! {
 cfg_if! {
     if #[cfg(feature = "transport_udp")] {
         pub mod udp;
-        mod conditioner;
+    } else {}
+}
+cfg_if! {
+    if #[cfg(feature = "transport_loopback")] {
+        pub mod loopback;
+    } else {}
+}
+cfg_if! {
+    if #[cfg(feature = "transport_tap")] {
+        pub mod replay;
+        pub mod tap;
     } else {}
 }
 
+pub use crate::user::UserAuthAddr;
 pub use inner::{
-    AuthReceiver, AuthSender, PacketReceiver, PacketSender, RecvError, SendError, Socket,
+    AuthReceiver, AuthSender, MetricsSink, PacketReceiver, PacketSender, RecvError, SendError,
+    Socket,
 };
 
 mod inner {
 
     use std::net::SocketAddr;
 
-    use naia_shared::IdentityToken;
+    use naia_shared::{IdentityToken, RejectReason};
 
-    use crate::user::UserAuthAddr;
+    use crate::{metrics::ServerMetrics, user::UserAuthAddr};
 
     pub struct SendError;
 
     pub struct RecvError;
 
+    /// A transport the Server exchanges packets with Clients over, given to
+    /// `Server::listen`. Implement it, along with the sender & receiver
+    /// traits below, to use a transport other than the built-in ones, e.g.
+    /// Steam Networking Sockets or an in-memory loopback for tests
     pub trait Socket {
+        /// Starts listening, returning the halves the Server answers auth
+        /// requests and exchanges packets with
         fn listen(
             self: Box<Self>,
         ) -> (
@@ -35,6 +53,20 @@ mod inner {
             Box<dyn PacketSender>,
             Box<dyn PacketReceiver>,
         );
+
+        /// Where the Server should publish its own metrics, if this transport
+        /// serves metrics. Called once, before `listen()`
+        fn metrics_sink(&self) -> Option<Box<dyn MetricsSink>> {
+            None
+        }
+    }
+
+    // Metrics
+
+    pub trait MetricsSink: Send + Sync {
+        /// Publishes the Server's current metrics, replacing those published
+        /// before
+        fn record(&self, metrics: &ServerMetrics);
     }
 
     // Packet
@@ -70,18 +102,20 @@ mod inner {
     // Auth
 
     pub trait AuthSender: Send + Sync {
-        ///
+        /// Tells the Client at the given address it may connect, sending it
+        /// its IdentityToken
         fn accept(
             &self,
             address: &UserAuthAddr,
             identity_token: &IdentityToken,
         ) -> Result<(), SendError>;
-        ///
-        fn reject(&self, address: &UserAuthAddr) -> Result<(), SendError>;
+        /// Tells the Client at the given address it may not connect
+        fn reject(&self, address: &UserAuthAddr, reason: &RejectReason) -> Result<(), SendError>;
     }
 
     pub trait AuthReceiver: AuthReceiverClone + Send + Sync {
-        ///
+        /// Receives the auth bytes a Client sent with its session request,
+        /// if any have arrived
         fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError>;
     }
 
@@ -0,0 +1,204 @@
+use std::{
+    collections::VecDeque,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use naia_shared::{IdentityToken, Instant, PacketDirection, PacketRecord, RejectReason};
+
+use super::{
+    AuthReceiver, AuthSender, PacketReceiver, PacketSender, RecvError, SendError,
+    Socket as TransportSocket,
+};
+use crate::user::UserAuthAddr;
+
+/// A Socket which replays the incoming packets of a recorded session, such as
+/// one captured with a `tap::Socket` and a `PacketRecorder`.
+/// Outgoing packets are discarded, but each incoming packet is only replayed
+/// once as many packets have been sent as were sent before it in the
+/// recording, so that a response isn't replayed ahead of its request.
+pub struct Socket {
+    // each incoming record, with the number of outgoing records before it
+    records: VecDeque<(usize, PacketRecord)>,
+    speed: f32,
+}
+
+impl Socket {
+    /// Create a Socket which replays the incoming packets of the given records.
+    /// A `speed` of 1.0 replays at the original timing, 2.0 replays twice as
+    /// fast, and 0.0 (or less, or a speed which isn't finite) replays every
+    /// packet as soon as possible.
+    pub fn new(records: Vec<PacketRecord>, speed: f32) -> Self {
+        let mut sent = 0;
+        let mut incoming = VecDeque::new();
+        for record in records {
+            match record.direction {
+                PacketDirection::Outgoing => sent += 1,
+                PacketDirection::Incoming => incoming.push_back((sent, record)),
+            }
+        }
+        Self {
+            records: incoming,
+            speed: if speed.is_finite() {
+                speed.max(0.0)
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn listen(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn AuthSender>,
+        Box<dyn AuthReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let sent = Arc::new(AtomicUsize::new(0));
+        (
+            Box::new(ReplayAuthSender),
+            Box::new(ReplayAuthReceiver),
+            Box::new(ReplayPacketSender { sent: sent.clone() }),
+            Box::new(ReplayPacketReceiver {
+                start: Instant::now(),
+                speed: self.speed,
+                sent,
+                records: self.records,
+                last_payload: None,
+            }),
+        )
+    }
+}
+
+struct ReplayAuthSender;
+
+impl AuthSender for ReplayAuthSender {
+    fn accept(
+        &self,
+        _address: &UserAuthAddr,
+        _identity_token: &IdentityToken,
+    ) -> Result<(), SendError> {
+        Ok(())
+    }
+    fn reject(&self, _address: &UserAuthAddr, _reason: &RejectReason) -> Result<(), SendError> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ReplayAuthReceiver;
+
+impl AuthReceiver for ReplayAuthReceiver {
+    fn receive(&mut self) -> Result<Option<(UserAuthAddr, &[u8])>, RecvError> {
+        Ok(None)
+    }
+}
+
+struct ReplayPacketSender {
+    sent: Arc<AtomicUsize>,
+}
+
+impl PacketSender for ReplayPacketSender {
+    /// Discards the packet, the recorded session has already happened, but
+    /// counts it towards replaying the packets which followed it
+    fn send(&self, _address: &SocketAddr, _payload: &[u8]) -> Result<(), SendError> {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ReplayPacketReceiver {
+    start: Instant,
+    speed: f32,
+    sent: Arc<AtomicUsize>,
+    records: VecDeque<(usize, PacketRecord)>,
+    last_payload: Option<Box<[u8]>>,
+}
+
+impl PacketReceiver for ReplayPacketReceiver {
+    /// Receives the next recorded packet, once its time has come
+    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, RecvError> {
+        let Some((sent_before, record)) = self.records.front() else {
+            return Ok(None);
+        };
+        if self.sent.load(Ordering::Relaxed) < *sent_before {
+            return Ok(None);
+        }
+
+        if self.speed > 0.0 {
+            // a delay too long to represent is never due
+            let due = Duration::try_from_secs_f32(record.elapsed.as_secs_f32() / self.speed)
+                .unwrap_or(Duration::MAX);
+            if self.start.elapsed(&Instant::now()) < due {
+                return Ok(None);
+            }
+        }
+
+        let (_, record) = self.records.pop_front().unwrap();
+        let address = record
+            .address
+            .unwrap_or_else(|| SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0));
+        self.last_payload = Some(record.payload);
+        Ok(Some((address, self.last_payload.as_ref().unwrap())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use naia_shared::{PacketDirection, PacketRecord};
+
+    use super::Socket;
+    use crate::transport::Socket as TransportSocket;
+
+    fn record(direction: PacketDirection, elapsed: Duration, payload: u8) -> PacketRecord {
+        PacketRecord {
+            elapsed,
+            direction,
+            address: None,
+            payload: Box::new([payload]),
+        }
+    }
+
+    #[test]
+    fn incoming_packet_waits_for_the_packets_sent_before_it() {
+        let records = vec![
+            record(PacketDirection::Incoming, Duration::ZERO, 1),
+            record(PacketDirection::Outgoing, Duration::ZERO, 2),
+            record(PacketDirection::Incoming, Duration::ZERO, 3),
+        ];
+        let socket: Box<dyn TransportSocket> = Box::new(Socket::new(records, f32::NAN));
+        let (_, _, sender, mut receiver) = socket.listen();
+        let address: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+
+        assert!(matches!(receiver.receive(), Ok(Some((_, &[1])))));
+        assert!(matches!(receiver.receive(), Ok(None)));
+        assert!(sender.send(&address, &[2]).is_ok());
+        assert!(matches!(receiver.receive(), Ok(Some((_, &[3])))));
+        assert!(matches!(receiver.receive(), Ok(None)));
+    }
+
+    #[test]
+    fn packet_too_late_to_represent_is_never_replayed() {
+        let records = vec![record(PacketDirection::Incoming, Duration::from_secs(1), 1)];
+        let socket: Box<dyn TransportSocket> = Box::new(Socket::new(records, f32::MIN_POSITIVE));
+        let (_, _, _, mut receiver) = socket.listen();
+
+        assert!(matches!(receiver.receive(), Ok(None)));
+    }
+}
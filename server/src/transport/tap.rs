@@ -0,0 +1,107 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use naia_shared::{PacketDirection, PacketRecorder};
+
+use super::{
+    AuthReceiver, AuthSender, PacketReceiver, PacketSender, RecvError, SendError,
+    Socket as TransportSocket,
+};
+
+/// Observes the raw bytes of every packet passing through a tapped Socket
+pub trait PacketTap: Send + Sync {
+    /// Called with each outgoing packet's bytes, immediately before it is sent
+    fn on_outgoing(&self, address: &SocketAddr, payload: &[u8]);
+    /// Called with each incoming packet's bytes, immediately after it is
+    /// received, and before any header parsing or decompression
+    fn on_incoming(&self, address: &SocketAddr, payload: &[u8]);
+}
+
+impl PacketTap for PacketRecorder {
+    fn on_outgoing(&self, address: &SocketAddr, payload: &[u8]) {
+        self.record(PacketDirection::Outgoing, Some(address), payload);
+    }
+    fn on_incoming(&self, address: &SocketAddr, payload: &[u8]) {
+        self.record(PacketDirection::Incoming, Some(address), payload);
+    }
+}
+
+/// Wraps another Socket, invoking a PacketTap for every packet sent or
+/// received through it
+pub struct Socket {
+    inner: Box<dyn TransportSocket>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl Socket {
+    pub fn new<S: Into<Box<dyn TransportSocket>>, T: PacketTap + 'static>(
+        inner: S,
+        tap: T,
+    ) -> Self {
+        Self {
+            inner: inner.into(),
+            tap: Arc::new(tap),
+        }
+    }
+}
+
+impl From<Socket> for Box<dyn TransportSocket> {
+    fn from(socket: Socket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for Socket {
+    fn listen(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn AuthSender>,
+        Box<dyn AuthReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let Self { inner, tap } = *self;
+        let (auth_sender, auth_receiver, packet_sender, packet_receiver) = inner.listen();
+        (
+            auth_sender,
+            auth_receiver,
+            Box::new(TappedPacketSender {
+                inner: packet_sender,
+                tap: tap.clone(),
+            }),
+            Box::new(TappedPacketReceiver {
+                inner: packet_receiver,
+                tap,
+            }),
+        )
+    }
+}
+
+struct TappedPacketSender {
+    inner: Box<dyn PacketSender>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl PacketSender for TappedPacketSender {
+    /// Sends a packet to the Server Socket
+    fn send(&self, address: &SocketAddr, payload: &[u8]) -> Result<(), SendError> {
+        self.tap.on_outgoing(address, payload);
+        self.inner.send(address, payload)
+    }
+}
+
+#[derive(Clone)]
+struct TappedPacketReceiver {
+    inner: Box<dyn PacketReceiver>,
+    tap: Arc<dyn PacketTap>,
+}
+
+impl PacketReceiver for TappedPacketReceiver {
+    /// Receives a packet from the Server Socket
+    fn receive(&mut self) -> Result<Option<(SocketAddr, &[u8])>, RecvError> {
+        let result = self.inner.receive();
+        if let Ok(Some((address, payload))) = &result {
+            self.tap.on_incoming(address, payload);
+        }
+        result
+    }
+}
@@ -1,19 +1,23 @@
 use std::net::SocketAddr;
 
-use naia_shared::{IdentityToken, SocketConfig};
+use naia_shared::{IdentityToken, RejectReason, SocketConfig};
 
 use naia_server_socket::{
-    AuthReceiver, AuthSender, PacketReceiver, PacketSender, Socket as ServerSocket,
+    AuthReceiver, AuthSender, MetricsEndpoint, PacketReceiver, PacketSender, Socket as ServerSocket,
 };
 
-pub use naia_server_socket::ServerAddrs;
+#[cfg(feature = "transport_webrtc_tls")]
+pub use naia_server_socket::SessionTlsConfig;
+#[cfg(feature = "transport_webtransport")]
+pub use naia_server_socket::WebTransportConfig;
+pub use naia_server_socket::{ServerAddrs, SocketMetrics, SocketShutdown};
 
 use super::{
-    AuthReceiver as TransportAuthReceiver, AuthSender as TransportAuthSender,
+    AuthReceiver as TransportAuthReceiver, AuthSender as TransportAuthSender, MetricsSink,
     PacketReceiver as TransportReceiver, PacketSender as TransportSender, RecvError, SendError,
     Socket as TransportSocket,
 };
-use crate::user::UserAuthAddr;
+use crate::{metrics::ServerMetrics, user::UserAuthAddr};
 
 pub struct Socket {
     server_addrs: ServerAddrs,
@@ -55,8 +59,10 @@ impl TransportAuthSender for Box<dyn AuthSender> {
             .map_err(|_| SendError)
     }
     ///
-    fn reject(&self, address: &UserAuthAddr) -> Result<(), SendError> {
-        self.as_ref().reject(&address.addr()).map_err(|_| SendError)
+    fn reject(&self, address: &UserAuthAddr, reason: &RejectReason) -> Result<(), SendError> {
+        self.as_ref()
+            .reject(&address.addr(), reason)
+            .map_err(|_| SendError)
     }
 }
 
@@ -79,6 +85,55 @@ impl TransportAuthReceiver for Box<dyn AuthReceiver> {
     }
 }
 
+impl MetricsSink for SocketMetrics {
+    fn record(&self, metrics: &ServerMetrics) {
+        self.set_gauge("naia_active_users", metrics.active_users as f64);
+
+        let handshake = &metrics.handshake;
+        let help = "Steps of the Client handshake, by outcome";
+        for (outcome, count) in [
+            ("challenge_request", handshake.challenge_requests),
+            ("validate_success", handshake.validate_successes),
+            ("validate_failure", handshake.validate_failures),
+            (
+                "disconnect_verify_failure",
+                handshake.disconnect_verify_failures,
+            ),
+        ] {
+            self.set_counter(
+                "naia_handshakes_total",
+                help,
+                &[("outcome", outcome)],
+                count,
+            );
+        }
+
+        self.set_counter(
+            "naia_server_packets_dropped_total",
+            "Packets the Server discarded because they could not be read, or came from an \
+             address with no connection",
+            &[],
+            metrics.packets_dropped,
+        );
+
+        for channel in &metrics.channels {
+            let labels = [("channel", channel.channel.as_str())];
+            self.set_counter(
+                "naia_messages_sent_total",
+                "Messages sent to clients over each Channel",
+                &labels,
+                channel.messages_sent,
+            );
+            self.set_counter(
+                "naia_messages_received_total",
+                "Messages received from clients over each Channel",
+                &labels,
+                channel.messages_received,
+            );
+        }
+    }
+}
+
 impl Into<Box<dyn TransportSocket>> for Socket {
     fn into(self) -> Box<dyn TransportSocket> {
         Box::new(self)
@@ -103,4 +158,50 @@ impl TransportSocket for Socket {
             Box::new(inner_packet_receiver),
         );
     }
+
+    fn metrics_sink(&self) -> Option<Box<dyn MetricsSink>> {
+        if !MetricsEndpoint::new(&self.server_addrs, &self.config).is_enabled() {
+            return None;
+        }
+        Some(Box::new(self.server_addrs.metrics.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_server_socket::SocketMetrics;
+
+    use super::MetricsSink;
+    use crate::{
+        metrics::{ChannelMetrics, ServerMetrics},
+        HandshakeMetrics,
+    };
+
+    #[test]
+    fn server_metrics_are_served_with_the_socket_counters() {
+        let socket_metrics = SocketMetrics::new();
+        socket_metrics.record(&ServerMetrics {
+            active_users: 2,
+            handshake: HandshakeMetrics {
+                challenge_requests: 3,
+                validate_successes: 2,
+                validate_failures: 1,
+                disconnect_verify_failures: 0,
+            },
+            packets_dropped: 4,
+            channels: vec![ChannelMetrics {
+                channel: "chat".to_string(),
+                messages_sent: 5,
+                messages_received: 6,
+            }],
+        });
+
+        let output = socket_metrics.to_prometheus();
+        assert!(output.contains("naia_active_users 2\n"));
+        assert!(output.contains("naia_handshakes_total{outcome=\"challenge_request\"} 3\n"));
+        assert!(output.contains("naia_handshakes_total{outcome=\"validate_failure\"} 1\n"));
+        assert!(output.contains("naia_server_packets_dropped_total 4\n"));
+        assert!(output.contains("naia_messages_sent_total{channel=\"chat\"} 5\n"));
+        assert!(output.contains("naia_messages_received_total{channel=\"chat\"} 6\n"));
+    }
 }
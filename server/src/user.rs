@@ -44,6 +44,7 @@ impl UserAuthAddr {
 pub struct User {
     auth_addr: Option<UserAuthAddr>,
     data_addr: Option<SocketAddr>,
+    client_id: Option<u64>,
     rooms_cache: HashSet<RoomKey>,
 }
 
@@ -52,6 +53,7 @@ impl User {
         Self {
             auth_addr: Some(auth_addr),
             data_addr: None,
+            client_id: None,
             rooms_cache: HashSet::new(),
         }
     }
@@ -68,6 +70,16 @@ impl User {
         self.data_addr
     }
 
+    /// The client id from the connect token the User presented, if any
+    pub fn client_id(&self) -> Option<u64> {
+        self.client_id
+    }
+
+    #[cfg(feature = "connect_tokens")]
+    pub(crate) fn set_client_id(&mut self, client_id: u64) {
+        self.client_id = Some(client_id);
+    }
+
     pub(crate) fn take_auth_address(&mut self) -> UserAuthAddr {
         self.auth_addr.take().unwrap()
     }
@@ -109,6 +121,11 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserRef<'s, E> {
         self.key
     }
 
+    /// The client id from the connect token the User presented, if any
+    pub fn client_id(&self) -> Option<u64> {
+        self.server.user_client_id(&self.key)
+    }
+
     pub fn address(&self) -> SocketAddr {
         self.server.user_address(&self.key).unwrap()
     }
@@ -1,5 +1,7 @@
 use std::hash::Hash;
 
+use naia_shared::{ComponentKind, Replicate};
+
 use super::{server::Server, user::UserKey};
 
 pub struct UserScopeRef<'s, E: Copy + Eq + Hash + Send + Sync> {
@@ -16,6 +18,13 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserScopeRef<'s, E> {
     pub fn has(&self, entity: &E) -> bool {
         self.server.user_scope_has_entity(&self.key, entity)
     }
+
+    /// Returns true if the User's scope contains the Entity, and the Component
+    /// has not been excluded from it
+    pub fn has_component<R: Replicate>(&self, entity: &E) -> bool {
+        self.server
+            .user_scope_has_component(&self.key, entity, &ComponentKind::of::<R>())
+    }
 }
 
 pub struct UserScopeMut<'s, E: Copy + Eq + Hash + Send + Sync> {
@@ -47,7 +56,33 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserScopeMut<'s, E> {
         self
     }
 
-    /// Removes all Entities from the User's scope
+    /// Returns true if the User's scope contains the Entity, and the Component
+    /// has not been excluded from it
+    pub fn has_component<R: Replicate>(&self, entity: &E) -> bool {
+        self.server
+            .user_scope_has_component(&self.key, entity, &ComponentKind::of::<R>())
+    }
+
+    /// Adds a Component back into the User's scope, after it has been
+    /// excluded. Components are in scope by default whenever their Entity is
+    pub fn include_component<R: Replicate>(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_component(&self.key, entity, &ComponentKind::of::<R>(), true);
+
+        self
+    }
+
+    /// Removes a Component from the User's scope, while the rest of the
+    /// Entity keeps replicating. This allows the User to see only some of
+    /// an Entity's Components, for example detailed state only when nearby
+    pub fn exclude_component<R: Replicate>(&mut self, entity: &E) -> &mut Self {
+        self.server
+            .user_scope_set_component(&self.key, entity, &ComponentKind::of::<R>(), false);
+
+        self
+    }
+
+    /// Removes all Entities, and Component exclusions, from the User's scope
     pub fn clear(&mut self) -> &mut Self {
         self.server.user_scope_remove_user(&self.key);
 
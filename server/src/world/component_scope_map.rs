@@ -0,0 +1,129 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use naia_shared::ComponentKind;
+
+use crate::user::UserKey;
+
+/// Tracks which Components of an in-scope Entity have been excluded from a
+/// User's scope. Components are in scope by default, so only exclusions are
+/// stored.
+pub struct ComponentScopeMap<E: Copy + Eq + Hash> {
+    excluded: HashMap<UserKey, HashMap<E, HashSet<ComponentKind>>>,
+}
+
+impl<E: Copy + Eq + Hash> ComponentScopeMap<E> {
+    pub fn new() -> Self {
+        Self {
+            excluded: HashMap::new(),
+        }
+    }
+
+    pub fn is_included(
+        &self,
+        user_key: &UserKey,
+        entity: &E,
+        component_kind: &ComponentKind,
+    ) -> bool {
+        let Some(entities) = self.excluded.get(user_key) else {
+            return true;
+        };
+        let Some(component_kinds) = entities.get(entity) else {
+            return true;
+        };
+        !component_kinds.contains(component_kind)
+    }
+
+    pub fn set(
+        &mut self,
+        user_key: UserKey,
+        entity: E,
+        component_kind: ComponentKind,
+        is_included: bool,
+    ) {
+        if !is_included {
+            self.excluded
+                .entry(user_key)
+                .or_default()
+                .entry(entity)
+                .or_default()
+                .insert(component_kind);
+            return;
+        }
+
+        let Some(entities) = self.excluded.get_mut(&user_key) else {
+            return;
+        };
+        let Some(component_kinds) = entities.get_mut(&entity) else {
+            return;
+        };
+        component_kinds.remove(&component_kind);
+        if component_kinds.is_empty() {
+            entities.remove(&entity);
+        }
+        if entities.is_empty() {
+            self.excluded.remove(&user_key);
+        }
+    }
+
+    pub fn remove_user(&mut self, user_key: &UserKey) {
+        self.excluded.remove(user_key);
+    }
+
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.excluded.retain(|_, entities| {
+            entities.remove(entity);
+            !entities.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use naia_shared::{BigMapKey, ComponentKind};
+
+    use super::ComponentScopeMap;
+    use crate::UserKey;
+
+    struct Position;
+    struct Inventory;
+
+    #[test]
+    fn components_are_included_until_excluded() {
+        let mut map = ComponentScopeMap::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        let position = ComponentKind::from(TypeId::of::<Position>());
+        let inventory = ComponentKind::from(TypeId::of::<Inventory>());
+
+        map.set(user_key, 1, inventory, false);
+        assert!(map.is_included(&user_key, &1, &position));
+        assert!(!map.is_included(&user_key, &1, &inventory));
+        assert!(map.is_included(&user_key, &2, &inventory));
+        assert!(map.is_included(&UserKey::from_u64(1), &1, &inventory));
+
+        map.set(user_key, 1, inventory, true);
+        assert!(map.is_included(&user_key, &1, &inventory));
+        assert!(map.excluded.is_empty());
+    }
+
+    #[test]
+    fn removing_entity_or_user_clears_exclusions() {
+        let mut map = ComponentScopeMap::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        let inventory = ComponentKind::from(TypeId::of::<Inventory>());
+
+        map.set(user_key, 1, inventory, false);
+        map.set(user_key, 2, inventory, false);
+
+        map.remove_entity(&1);
+        assert!(map.is_included(&user_key, &1, &inventory));
+        assert!(!map.is_included(&user_key, &2, &inventory));
+
+        map.remove_user(&user_key);
+        assert!(map.is_included(&user_key, &2, &inventory));
+    }
+}
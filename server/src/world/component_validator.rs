@@ -0,0 +1,19 @@
+use naia_shared::Replicate;
+
+use crate::UserKey;
+
+/// Inspects Components that clients insert into Entities, giving the Server
+/// a veto over them
+pub trait ComponentInsertValidator: Send + Sync {
+    /// Returns whether a Component inserted by the given User should be kept.
+    /// A rejected Component is removed from its Entity (which otherwise stays
+    /// as-is) and no InsertComponentEvent is emitted for it. Implementors that
+    /// want to flag misbehaving Users can record the `user_key` here
+    fn validate(&self, user_key: &UserKey, component: &dyn Replicate) -> bool;
+}
+
+impl<F: Fn(&UserKey, &dyn Replicate) -> bool + Send + Sync> ComponentInsertValidator for F {
+    fn validate(&self, user_key: &UserKey, component: &dyn Replicate) -> bool {
+        self(user_key, component)
+    }
+}
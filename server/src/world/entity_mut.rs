@@ -1,10 +1,19 @@
 use std::hash::Hash;
 
-use naia_shared::{EntityAuthStatus, ReplicaMutWrapper, ReplicatedComponent, WorldMutType};
+use naia_shared::{
+    ComponentKind, EntityAuthStatus, ReplicaDynMutWrapper, ReplicaMutWrapper, ReplicatedComponent,
+    WorldMutType,
+};
 
-use crate::{room::RoomKey, server::Server, ReplicationConfig};
+use crate::{room::RoomKey, server::Server, NaiaServerError, ReplicationConfig, UserKey};
 
 // EntityMut
+/// A handle for mutating a single entity.
+///
+/// Every operation is applied to the world and to the replication state as
+/// soon as it is called; nothing is buffered inside the handle. Dropping an
+/// `EntityMut` at any point, including part-way through a chain of calls,
+/// therefore never leaves partially registered components behind.
 pub struct EntityMut<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> {
     server: &'s mut Server<E>,
     world: W,
@@ -38,6 +47,20 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self.world.component_mut::<R>(&self.entity)
     }
 
+    /// Returns the kinds of all of this Entity's replicated Components, so
+    /// that they can be accessed without naming their types
+    pub fn component_kinds(&self) -> Vec<ComponentKind> {
+        self.world.component_kinds(&self.entity)
+    }
+
+    pub fn component_of_kind(
+        &mut self,
+        component_kind: &ComponentKind,
+    ) -> Option<ReplicaDynMutWrapper<'_>> {
+        self.world
+            .component_mut_of_kind(&self.entity, component_kind)
+    }
+
     pub fn insert_component<R: ReplicatedComponent>(&mut self, component_ref: R) -> &mut Self {
         self.server
             .insert_component(&mut self.world, &self.entity, component_ref);
@@ -45,7 +68,10 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self
     }
 
-    pub fn insert_components<R: ReplicatedComponent>(&mut self, mut component_refs: Vec<R>) -> &mut Self {
+    pub fn insert_components<R: ReplicatedComponent>(
+        &mut self,
+        mut component_refs: Vec<R>,
+    ) -> &mut Self {
         while let Some(component_ref) = component_refs.pop() {
             self.insert_component(component_ref);
         }
@@ -71,10 +97,68 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>> EntityMut<'s, E,
         self.server.entity_replication_config(&self.entity)
     }
 
+    /// Stops sending this Entity's updates, keeping it spawned on Clients at
+    /// its current state until replication is unfrozen
+    pub fn freeze_replication(&mut self) -> &mut Self {
+        self.server.pause_entity_replication(&self.entity);
+
+        self
+    }
+
+    /// Resumes sending this Entity's updates, starting from its current state
+    pub fn unfreeze_replication(&mut self) -> &mut Self {
+        self.server.resume_entity_replication(&self.entity);
+
+        self
+    }
+
+    pub fn replication_is_frozen(&self) -> Option<bool> {
+        self.server.entity_replication_is_paused(&self.entity)
+    }
+
+    pub fn set_priority(&mut self, priority: f32) -> &mut Self {
+        self.server.set_entity_priority(&self.entity, priority);
+
+        self
+    }
+
+    pub fn priority(&self) -> Option<f32> {
+        self.server.entity_priority(&self.entity)
+    }
+
+    /// Sends this Entity's updates only every `interval` ticks
+    pub fn set_replication_tick_interval(&mut self, interval: u16) -> &mut Self {
+        self.server
+            .set_entity_replication_tick_interval(&self.entity, interval);
+
+        self
+    }
+
+    /// Shifts the ticks this Entity's updates are sent on by `offset` ticks,
+    /// to stagger it against other Entities sharing its tick interval
+    pub fn set_replication_tick_offset(&mut self, offset: u16) -> &mut Self {
+        self.server
+            .set_entity_replication_tick_offset(&self.entity, offset);
+
+        self
+    }
+
     pub fn authority(&self) -> Option<EntityAuthStatus> {
         self.server.entity_authority_status(&self.entity)
     }
 
+    /// Gives authority over this Delegated Entity to the given User, taking it
+    /// from any current holder. See `Server::grant_authority()`
+    pub fn grant_authority(&mut self, user_key: &UserKey) -> Result<(), NaiaServerError> {
+        self.server.grant_authority(&self.entity, user_key)
+    }
+
+    /// Takes authority over this Delegated Entity back from any current
+    /// holder. See `Server::revoke_authority()`
+    pub fn revoke_authority(&mut self) -> Result<(), NaiaServerError> {
+        self.server.revoke_authority(&self.entity)
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
@@ -10,6 +10,9 @@ pub struct GlobalEntityRecord {
     pub owner: EntityOwner,
     pub replication_config: ReplicationConfig,
     pub is_replicating: bool,
+    pub priority: f32,
+    pub replication_tick_interval: u16,
+    pub replication_tick_offset: u16,
 }
 
 impl GlobalEntityRecord {
@@ -30,6 +33,9 @@ impl GlobalEntityRecord {
             owner,
             replication_config,
             is_replicating: true,
+            priority: 1.0,
+            replication_tick_interval: 1,
+            replication_tick_offset: 0,
         }
     }
 }
@@ -9,7 +9,7 @@ use log::warn;
 use naia_shared::{
     BigMap, BigMapKey, ComponentKind, EntityAndGlobalEntityConverter, EntityAuthAccessor,
     EntityAuthStatus, EntityDoesNotExistError, GlobalDiffHandler, GlobalEntity,
-    GlobalWorldManagerType, MutChannelType, PropertyMutator, Replicate,
+    GlobalWorldManagerType, MutChannelType, PropertyMutator, Replicate, Tick,
 };
 
 use super::global_entity_record::GlobalEntityRecord;
@@ -278,10 +278,36 @@ impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManager<E> {
         self.auth_handler.client_release_authority(entity, releaser)
     }
 
+    /// Returns the User which currently controls the Entity: the Client
+    /// holding authority over a Delegated Entity, or the Client which owns a
+    /// Client-owned Entity
+    pub(crate) fn entity_controlling_user(&self, entity: &E) -> Option<UserKey> {
+        let record = self.entity_records.get(entity)?;
+        if record.replication_config == ReplicationConfig::Delegated {
+            return self.auth_handler.authority_holder(entity);
+        }
+        match record.owner {
+            EntityOwner::Client(user_key) | EntityOwner::ClientPublic(user_key) => Some(user_key),
+            _ => None,
+        }
+    }
+
     pub(crate) fn user_all_owned_entities(&self, user_key: &UserKey) -> Option<&HashSet<E>> {
         self.auth_handler.user_all_owned_entities(user_key)
     }
 
+    pub(crate) fn server_give_authority(&mut self, entity: &E, user_key: &UserKey) -> bool {
+        self.auth_handler.server_give_authority(entity, user_key)
+    }
+
+    pub(crate) fn take_grant_reply(&mut self, entity: &E, user_key: &UserKey) -> Option<bool> {
+        self.auth_handler.take_grant_reply(entity, user_key)
+    }
+
+    pub(crate) fn remove_user_authority_grants(&mut self, user_key: &UserKey) {
+        self.auth_handler.remove_user(user_key);
+    }
+
     pub(crate) fn pause_entity_replication(&mut self, entity: &E) {
         let Some(record) = self.entity_records.get_mut(entity) else {
             panic!("entity record does not exist!");
@@ -295,6 +321,50 @@ impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManager<E> {
         };
         record.is_replicating = true;
     }
+
+    pub(crate) fn entity_replication_is_paused(&self, entity: &E) -> Option<bool> {
+        let record = self.entity_records.get(entity)?;
+        Some(!record.is_replicating)
+    }
+
+    pub(crate) fn set_entity_priority(&mut self, entity: &E, priority: f32) {
+        let Some(record) = self.entity_records.get_mut(entity) else {
+            panic!("entity record does not exist!");
+        };
+        record.priority = priority;
+    }
+
+    pub(crate) fn entity_priority(&self, entity: &E) -> Option<f32> {
+        self.entity_records
+            .get(entity)
+            .map(|record| record.priority)
+    }
+
+    pub(crate) fn set_entity_replication_tick_interval(&mut self, entity: &E, interval: u16) {
+        let Some(record) = self.entity_records.get_mut(entity) else {
+            panic!("entity record does not exist!");
+        };
+        record.replication_tick_interval = interval;
+    }
+
+    pub(crate) fn entity_replication_tick_interval(&self, entity: &E) -> Option<u16> {
+        self.entity_records
+            .get(entity)
+            .map(|record| record.replication_tick_interval)
+    }
+
+    pub(crate) fn set_entity_replication_tick_offset(&mut self, entity: &E, offset: u16) {
+        let Some(record) = self.entity_records.get_mut(entity) else {
+            panic!("entity record does not exist!");
+        };
+        record.replication_tick_offset = offset;
+    }
+
+    pub(crate) fn entity_replication_tick_offset(&self, entity: &E) -> Option<u16> {
+        self.entity_records
+            .get(entity)
+            .map(|record| record.replication_tick_offset)
+    }
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManagerType<E> for GlobalWorldManager<E> {
@@ -360,6 +430,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> GlobalWorldManagerType<E> for GlobalWorl
         };
         return record.is_replicating;
     }
+
+    fn entity_replication_priority(&self, entity: &E) -> f32 {
+        self.entity_priority(entity).unwrap_or(1.0)
+    }
+
+    fn entity_update_is_due(&self, entity: &E, tick: &Tick) -> bool {
+        let Some(record) = self.entity_records.get(entity) else {
+            return true;
+        };
+        tick.wrapping_sub(record.replication_tick_offset) % record.replication_tick_interval == 0
+    }
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> EntityAndGlobalEntityConverter<E>
@@ -1,3 +1,5 @@
+pub mod component_scope_map;
+pub mod component_validator;
 pub mod entity_mut;
 pub mod entity_owner;
 pub mod entity_ref;
@@ -8,3 +10,4 @@ pub mod global_world_manager;
 pub mod mut_channel;
 pub mod replication_config;
 pub mod server_auth_handler;
+pub mod world_snapshot;
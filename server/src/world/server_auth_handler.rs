@@ -27,6 +27,9 @@ pub struct ServerAuthHandler<E: Copy + Eq + Hash + Send + Sync> {
     host_auth_handler: HostAuthHandler<E>,
     entity_auth_map: HashMap<E, AuthOwner>,
     user_to_entity_map: HashMap<UserKey, HashSet<E>>,
+    // how many times the Server has given a User authority over an Entity
+    // without the User yet replying with the Host Entity it migrated it to
+    grant_replies: HashMap<(UserKey, E), usize>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
@@ -35,6 +38,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
             host_auth_handler: HostAuthHandler::new(),
             entity_auth_map: HashMap::new(),
             user_to_entity_map: HashMap::new(),
+            grant_replies: HashMap::new(),
         }
     }
 
@@ -51,6 +55,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
     pub fn deregister_entity(&mut self, entity: &E) {
         self.host_auth_handler.deregister_entity(entity);
         self.entity_auth_map.remove(&entity);
+        self.grant_replies
+            .retain(|(_, reply_entity), _| reply_entity != entity);
     }
 
     pub(crate) fn authority_status(&self, entity: &E) -> Option<EntityAuthStatus> {
@@ -59,6 +65,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
             .map(|host_status| host_status.status())
     }
 
+    /// Returns the User currently holding authority over the Entity, if any
+    pub(crate) fn authority_holder(&self, entity: &E) -> Option<UserKey> {
+        match self.entity_auth_map.get(entity) {
+            Some(AuthOwner::Client(user_key)) => Some(*user_key),
+            _ => None,
+        }
+    }
+
     pub(crate) fn client_request_authority(&mut self, entity: &E, requester: &AuthOwner) -> bool {
         let Some(owner) = self.entity_auth_map.get_mut(entity) else {
             panic!("Entity not registered with ServerAuthHandler");
@@ -129,7 +143,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
             let mut remove_user = false;
             if let Some(entities) = self.user_to_entity_map.get_mut(&user_key) {
                 entities.remove(entity);
-                remove_user = true;
+                remove_user = entities.is_empty();
             }
             if remove_user {
                 self.user_to_entity_map.remove(&user_key);
@@ -148,4 +162,138 @@ impl<E: Copy + Eq + Hash + Send + Sync> ServerAuthHandler<E> {
         }
         return None;
     }
+
+    /// Gives authority over an Available Entity to a User without it being
+    /// requested. The User then replies with the Host Entity it migrated the
+    /// Entity to, see `take_grant_reply()`. Returns whether the Entity was
+    /// Available
+    pub(crate) fn server_give_authority(&mut self, entity: &E, user_key: &UserKey) -> bool {
+        if !self.client_request_authority(entity, &AuthOwner::Client(*user_key)) {
+            return false;
+        }
+        *self.grant_replies.entry((*user_key, *entity)).or_insert(0) += 1;
+        true
+    }
+
+    /// Matches an authority request from a User against the times the Server
+    /// gave it authority over the Entity. Returns None if the request is not a
+    /// reply to such a grant, Some(true) if it replies to the latest one, or
+    /// Some(false) if it replies to an earlier grant which has since been
+    /// superseded
+    pub(crate) fn take_grant_reply(&mut self, entity: &E, user_key: &UserKey) -> Option<bool> {
+        let key = (*user_key, *entity);
+        let replies = self.grant_replies.get_mut(&key)?;
+        *replies -= 1;
+        if *replies > 0 {
+            return Some(false);
+        }
+        self.grant_replies.remove(&key);
+        Some(true)
+    }
+
+    /// Forgets every grant of authority a User has not yet replied to
+    pub(crate) fn remove_user(&mut self, user_key: &UserKey) {
+        self.grant_replies
+            .retain(|(reply_user_key, _), _| reply_user_key != user_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{BigMapKey, EntityAuthStatus};
+
+    use super::{AuthOwner, ServerAuthHandler};
+    use crate::UserKey;
+
+    #[test]
+    fn disconnect_releases_all_user_authority() {
+        let mut handler = ServerAuthHandler::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        let owner = AuthOwner::Client(user_key);
+        for entity in [1, 2, 3] {
+            handler.register_entity(&entity);
+            assert!(handler.client_request_authority(&entity, &owner));
+        }
+
+        // releasing one entity must not forget the user's other entities
+        assert!(handler.client_release_authority(&1, &owner));
+        let mut held: Vec<u32> = handler
+            .user_all_owned_entities(&user_key)
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        held.sort();
+        assert_eq!(held, vec![2, 3]);
+
+        // on disconnect, the Server releases everything the user still holds
+        for entity in held {
+            assert!(handler.client_release_authority(&entity, &owner));
+        }
+
+        assert!(handler.user_all_owned_entities(&user_key).is_none());
+        for entity in [1, 2, 3] {
+            assert_eq!(
+                handler.authority_status(&entity),
+                Some(EntityAuthStatus::Available)
+            );
+        }
+
+        // another client may now claim authority
+        let other_owner = AuthOwner::Client(UserKey::from_u64(1));
+        assert!(handler.client_request_authority(&2, &other_owner));
+    }
+
+    #[test]
+    fn authority_holder_follows_client_authority() {
+        let mut handler = ServerAuthHandler::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        handler.register_entity(&1);
+        assert_eq!(handler.authority_holder(&1), None);
+
+        assert!(handler.client_request_authority(&1, &AuthOwner::Client(user_key)));
+        assert_eq!(handler.authority_holder(&1), Some(user_key));
+
+        // the Server taking authority back leaves no Client in control
+        assert!(handler.server_take_authority(&1));
+        assert!(handler.client_request_authority(&1, &AuthOwner::Server));
+        assert_eq!(handler.authority_holder(&1), None);
+        // .. which the status distinguishes from the Entity being Available
+        assert_eq!(
+            handler.authority_status(&1),
+            Some(EntityAuthStatus::Granted)
+        );
+    }
+
+    #[test]
+    fn only_reply_to_latest_grant_is_current() {
+        let mut handler = ServerAuthHandler::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        handler.register_entity(&1);
+
+        // a request which does not answer a grant is a fresh request
+        assert_eq!(handler.take_grant_reply(&1, &user_key), None);
+
+        // authority is given, revoked before the User replies, then given again
+        assert!(handler.server_give_authority(&1, &user_key));
+        assert!(handler.server_take_authority(&1));
+        assert!(handler.server_give_authority(&1, &user_key));
+        assert!(!handler.server_give_authority(&1, &UserKey::from_u64(1)));
+
+        // the reply to the first grant is stale, the second is current
+        assert_eq!(handler.take_grant_reply(&1, &user_key), Some(false));
+        assert_eq!(handler.take_grant_reply(&1, &user_key), Some(true));
+        assert_eq!(handler.take_grant_reply(&1, &user_key), None);
+    }
+
+    #[test]
+    fn removed_user_has_no_grant_replies() {
+        let mut handler = ServerAuthHandler::<u32>::new();
+        let user_key = UserKey::from_u64(0);
+        handler.register_entity(&1);
+        assert!(handler.server_give_authority(&1, &user_key));
+
+        handler.remove_user(&user_key);
+        assert_eq!(handler.take_grant_reply(&1, &user_key), None);
+    }
 }
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use naia_shared::{
+    EntityDoesNotExistError, GlobalEntity, HostEntity, LocalEntityAndGlobalEntityConverter,
+    LocalEntityAndGlobalEntityConverterMut, OwnedLocalEntity, RemoteEntity,
+};
+
+/// Bumped whenever the layout of a World snapshot changes
+pub(crate) const WORLD_SNAPSHOT_VERSION: u8 = 1;
+
+/// Maps every Entity in a World snapshot to its index within the snapshot, so
+/// that EntityProperties can refer to each other independently of the
+/// GlobalEntities of the Server which wrote or reads the snapshot.
+/// Only the first `u16::MAX` Entities of a snapshot can be referred to
+pub(crate) struct SnapshotEntityConverter {
+    global_entities: Vec<GlobalEntity>,
+    indices: HashMap<GlobalEntity, u16>,
+}
+
+impl SnapshotEntityConverter {
+    pub fn new(global_entities: Vec<GlobalEntity>) -> Self {
+        let indices = global_entities
+            .iter()
+            .take(u16::MAX as usize)
+            .enumerate()
+            .map(|(index, global_entity)| (*global_entity, index as u16))
+            .collect();
+        Self {
+            global_entities,
+            indices,
+        }
+    }
+
+    fn index(&self, global_entity: &GlobalEntity) -> Result<u16, EntityDoesNotExistError> {
+        self.indices
+            .get(global_entity)
+            .copied()
+            .ok_or(EntityDoesNotExistError)
+    }
+
+    fn global_entity(&self, index: u16) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        self.global_entities
+            .get(index as usize)
+            .copied()
+            .ok_or(EntityDoesNotExistError)
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverter for SnapshotEntityConverter {
+    fn global_entity_to_host_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        Ok(HostEntity::new(self.index(global_entity)?))
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Ok(RemoteEntity::new(self.index(global_entity)?))
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        Ok(OwnedLocalEntity::Host(self.index(global_entity)?))
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        host_entity: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        self.global_entity(host_entity.value())
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        remote_entity: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        self.global_entity(remote_entity.value())
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverterMut for SnapshotEntityConverter {
+    fn get_or_reserve_entity(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_owned_entity(global_entity)
+    }
+}
@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use naia_shared::{BitWriter, FakeEntityConverter, Message, MessageContainer, MessageKinds};
+
+const CONNECTIONS: usize = 100;
+
+#[derive(Message)]
+struct ChatMessage {
+    sender: String,
+    text: String,
+    channel: u8,
+}
+
+fn chat_message() -> ChatMessage {
+    ChatMessage {
+        sender: String::from("player_042"),
+        text: String::from("gg everyone, meet back at the spawn point for the next round"),
+        channel: 3,
+    }
+}
+
+// Writes a Message into one packet for each connection, serializing it for
+// every connection
+fn write_per_connection(message_kinds: &MessageKinds, message: &ChatMessage) {
+    for _ in 0..CONNECTIONS {
+        let container =
+            MessageContainer::from_write(Box::new(message.clone()), &mut FakeEntityConverter);
+        let mut writer = BitWriter::new();
+        container.write(message_kinds, &mut writer, &mut FakeEntityConverter);
+        black_box(writer.to_bytes());
+    }
+}
+
+// Writes a Message into one packet for each connection, serializing it once
+fn write_shared(message_kinds: &MessageKinds, message: &ChatMessage) {
+    let container = MessageContainer::from_write_shared(Box::new(message.clone()), message_kinds);
+    for _ in 0..CONNECTIONS {
+        let container = container.clone();
+        let mut writer = BitWriter::new();
+        container.write(message_kinds, &mut writer, &mut FakeEntityConverter);
+        black_box(writer.to_bytes());
+    }
+}
+
+fn message_write(c: &mut Criterion) {
+    let mut message_kinds = MessageKinds::new();
+    message_kinds.add_message::<ChatMessage>();
+    let message = chat_message();
+
+    let mut group = c.benchmark_group("broadcast_100_connections");
+    group.bench_function("serialized_per_connection", |b| {
+        b.iter(|| write_per_connection(&message_kinds, black_box(&message)))
+    });
+    group.bench_function("serialized_once", |b| {
+        b.iter(|| write_shared(&message_kinds, black_box(&message)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, message_write);
+criterion_main!(benches);
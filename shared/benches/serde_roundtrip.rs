@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use naia_shared::{bench_roundtrip, Serde, UnsignedVariableInteger};
+
+#[derive(Clone, PartialEq, Serde)]
+struct Position {
+    x: i16,
+    y: i16,
+    velocity: UnsignedVariableInteger<5>,
+    name: String,
+}
+
+fn bench<T: Serde>(c: &mut Criterion, name: &str, value: T) {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(bench_roundtrip(&value) as u64));
+    group.bench_function("roundtrip", |b| {
+        b.iter(|| bench_roundtrip(black_box(&value)))
+    });
+    group.finish();
+}
+
+fn serde_roundtrip(c: &mut Criterion) {
+    bench(c, "u32", 123_456u32);
+    bench(
+        c,
+        "unsigned_variable_integer",
+        UnsignedVariableInteger::<7>::new(123_456u64),
+    );
+    bench(c, "string", String::from("Hello world!"));
+    bench(c, "vec_u16", (0..64u16).collect::<Vec<u16>>());
+    bench(
+        c,
+        "struct",
+        Position {
+            x: -120,
+            y: 340,
+            velocity: UnsignedVariableInteger::new(12u64),
+            name: String::from("player"),
+        },
+    );
+}
+
+criterion_group!(benches, serde_roundtrip);
+criterion_main!(benches);
@@ -17,21 +17,25 @@ use replicate::replicate_impl;
 // Replicate
 
 /// Derives the Replicate trait for a given struct
-#[proc_macro_derive(Replicate)]
+///
+/// A numeric Property may be marked with `#[replicate_threshold(x)]`, so that
+/// assignments through `Property::set` only queue an update once the value
+/// has moved more than `x` from the value last queued
+#[proc_macro_derive(Replicate, attributes(replicate_threshold))]
 pub fn replicate_derive_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_shared };
     replicate_impl(input, shared_crate_name)
 }
 
 /// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateBevy)]
+#[proc_macro_derive(ReplicateBevy, attributes(replicate_threshold))]
 pub fn replicate_derive_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_bevy_shared };
     replicate_impl(input, shared_crate_name)
 }
 
 /// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateHecs)]
+#[proc_macro_derive(ReplicateHecs, attributes(replicate_threshold))]
 pub fn replicate_derive_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_hecs_shared };
     replicate_impl(input, shared_crate_name)
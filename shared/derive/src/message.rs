@@ -41,6 +41,7 @@ pub fn message_impl(
 
     // Methods
     let clone_method = get_clone_method(&fields, &struct_type);
+    let has_entity_properties_method = get_has_entity_properties_method(&fields);
     let relations_waiting_method = get_relations_waiting_method(&fields, &struct_type);
     let relations_complete_method = get_relations_complete_method(&fields, &struct_type);
     let bit_length_method = get_bit_length_method(&fields, &struct_type);
@@ -84,6 +85,7 @@ pub fn message_impl(
                 #is_request_method
                 #bit_length_method
                 #builder_create_method
+                #has_entity_properties_method
                 #relations_waiting_method
                 #relations_complete_method
                 #write_method
@@ -161,23 +163,23 @@ fn get_clone_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
     }
 }
 
-// fn get_has_entity_propertys_method(fields: &[Field]) -> TokenStream {
-//     for field in fields.iter() {
-//         if let Field::EntityProperty(_) = field {
-//             return quote! {
-//                 fn has_entity_propertys(&self) -> bool {
-//                     return true;
-//                 }
-//             };
-//         }
-//     }
-//
-//     quote! {
-//         fn has_entity_propertys(&self) -> bool {
-//             return false;
-//         }
-//     }
-// }
+fn get_has_entity_properties_method(fields: &[Field]) -> TokenStream {
+    for field in fields.iter() {
+        if let Field::EntityProperty(_) = field {
+            return quote! {
+                fn has_entity_properties(&self) -> bool {
+                    return true;
+                }
+            };
+        }
+    }
+
+    quote! {
+        fn has_entity_properties(&self) -> bool {
+            return false;
+        }
+    }
+}
 
 // fn get_entities_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
 //     let mut body = quote! {};
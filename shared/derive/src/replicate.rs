@@ -1,8 +1,8 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, Index, LitStr, Member,
-    PathArguments, Type,
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, GenericArgument, Ident, Index,
+    LitStr, Member, PathArguments, Type,
 };
 
 use crate::{
@@ -17,6 +17,7 @@ pub struct NormalProperty {
     pub inner_type: Type,
     pub uppercase_variable_name: Ident,
     pub index: usize,
+    pub change_threshold: Option<Expr>,
 }
 
 pub struct EntityProperty {
@@ -105,6 +106,7 @@ pub fn replicate_impl(
         get_enable_delegation_method(&enum_name, &properties, &struct_type);
     let disable_delegation_method = get_disable_delegation_method(&properties, &struct_type);
     let localize_method = get_localize_method(&properties, &struct_type);
+    let host_own_method = get_host_own_method(&enum_name, &properties, &struct_type);
     let read_apply_update_method = get_read_apply_update_method(&properties, &struct_type);
     let read_apply_field_update_method =
         get_read_apply_field_update_method(&properties, &struct_type);
@@ -124,6 +126,7 @@ pub fn replicate_impl(
                 ReplicaDynRef, ReplicaDynMut, LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, ComponentKind, Named,
                 BitReader, BitWrite, BitWriter, OwnedBitReader, SerdeErr, Serde, EntityAuthAccessor, RemoteEntity,
                 EntityProperty, GlobalEntity, Replicate, Property, ComponentKinds, ReplicateBuilder, ComponentFieldUpdate,
+                ChangeThreshold,
             };
             use super::*;
 
@@ -176,6 +179,7 @@ pub fn replicate_impl(
                 #enable_delegation_method
                 #disable_delegation_method
                 #localize_method
+                #host_own_method
                 #set_mutator_method
                 #write_method
                 #write_update_method
@@ -216,11 +220,17 @@ fn get_field_name(property: &Property, struct_type: &StructType) -> Member {
 }
 
 impl Property {
-    pub fn normal(index: usize, variable_name: Ident, inner_type: Type) -> Self {
+    pub fn normal(
+        index: usize,
+        variable_name: Ident,
+        inner_type: Type,
+        change_threshold: Option<Expr>,
+    ) -> Self {
         Self::Normal(NormalProperty {
             index,
             variable_name: variable_name.clone(),
             inner_type,
+            change_threshold,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -308,6 +318,7 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                                                 fields.len(),
                                                 variable_name.clone(),
                                                 inner_type.clone(),
+                                                get_change_threshold(&field.attrs),
                                             ));
                                             continue;
                                         }
@@ -320,6 +331,12 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                                     ));
                                 }
                             }
+                        } else {
+                            // Non-replicated Property of non-path type, i.e. `[T; N]`
+                            fields.push(Property::nonreplicated(
+                                variable_name.clone(),
+                                field.ty.clone(),
+                            ));
                         }
                     }
                 }
@@ -344,6 +361,7 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                                         fields.len(),
                                         variable_name,
                                         inner_type.clone(),
+                                        get_change_threshold(&field.attrs),
                                     ));
                                     continue;
                                 }
@@ -361,6 +379,17 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
     fields
 }
 
+/// Reads the threshold from a `#[replicate_threshold(..)]` field attribute
+fn get_change_threshold(attrs: &[Attribute]) -> Option<Expr> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("replicate_threshold"))?;
+    match attr.parse_args::<Expr>() {
+        Ok(threshold) => Some(threshold),
+        Err(_) => panic!("`replicate_threshold` expects a single numeric threshold, i.e. `#[replicate_threshold(0.01)]`"),
+    }
+}
+
 fn get_property_enum_definition(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     if properties.is_empty() {
         return quote! {
@@ -632,6 +661,33 @@ fn get_localize_method(properties: &[Property], struct_type: &StructType) -> Tok
     }
 }
 
+fn get_host_own_method(
+    enum_name: &Ident,
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter().filter(|p| p.is_replicated()) {
+        let field_name = get_field_name(property, struct_type);
+        let uppercase_variant_name = property.uppercase_variable_name();
+        let new_output_right = quote! {
+                self.#field_name.host_own(#enum_name::#uppercase_variant_name as u8);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn host_own(&mut self) {
+            #output
+        }
+    }
+}
+
 pub fn get_new_complete_method(
     enum_name: &Ident,
     properties: &[Property],
@@ -679,16 +735,22 @@ pub fn get_new_complete_method(
                 let field_name = &property.variable_name;
                 let field_type = &property.inner_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
+                let change_threshold = match &property.change_threshold {
+                    Some(threshold) => quote! {
+                        .with_change_threshold(|value, baseline| ChangeThreshold::exceeds_threshold(value, baseline, (#threshold) as f64))
+                    },
+                    None => quote! {},
+                };
 
                 match *struct_type {
                     StructType::Struct => {
                         quote! {
-                            #field_name: Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                            #field_name: Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)#change_threshold
                         }
                     }
                     StructType::TupleStruct => {
                         quote! {
-                            Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                            Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)#change_threshold
                         }
                     }
                     _ => {
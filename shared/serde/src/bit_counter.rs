@@ -1,4 +1,4 @@
-use crate::BitWrite;
+use crate::{BitWrite, Serde};
 
 // BitCounter
 pub struct BitCounter {
@@ -39,3 +39,30 @@ impl BitWrite for BitCounter {
         true
     }
 }
+
+/// Returns the number of bits `value` takes up once serialized. The value is
+/// serialized into a BitCounter, so no bytes are actually written. Useful for
+/// keeping data under the MTU, or for measuring bandwidth per type
+pub fn serialized_bit_len<T: Serde>(value: &T) -> u32 {
+    let mut counter = BitCounter::new(0, 0, u32::MAX);
+    value.ser(&mut counter);
+    counter.bits_needed()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_counter::serialized_bit_len, bit_writer::BitWriter, serde::Serde};
+
+    #[test]
+    fn matches_written_length() {
+        let value = (true, 42u16, Some(String::from("Hello world!")));
+
+        let mut writer = BitWriter::new();
+        value.ser(&mut writer);
+        let written_bits = writer.bits_free();
+
+        let counted_bits = serialized_bit_len(&value);
+        assert_eq!(counted_bits, BitWriter::new().bits_free() - written_bits);
+        assert_eq!(counted_bits, value.bit_length());
+    }
+}
@@ -10,6 +10,18 @@ pub trait BitWrite {
 
     fn is_counter(&self) -> bool;
     fn count_bits(&mut self, bits: u32);
+
+    /// Writes the first `bit_length` bits of the given bytes, as packed by
+    /// `BitWriter::to_bytes()`
+    fn write_bits(&mut self, bytes: &[u8], bit_length: u32) {
+        let whole_bytes = (bit_length / 8) as usize;
+        for byte in &bytes[..whole_bytes] {
+            self.write_byte(*byte);
+        }
+        for bit_index in 0..(bit_length % 8) {
+            self.write_bit((bytes[whole_bytes] >> bit_index) & 1 != 0);
+        }
+    }
 }
 
 // BitWriter
@@ -115,11 +127,24 @@ impl BitWrite for BitWriter {
     }
 
     fn write_byte(&mut self, byte: u8) {
-        let mut temp = byte;
-        for _ in 0..8 {
-            self.write_bit(temp & 1 != 0);
-            temp >>= 1;
+        if self.current_bits + 8 > self.max_bits {
+            panic!("Write overflow!");
         }
+        self.current_bits += 8;
+
+        if self.scratch_index == 0 {
+            self.buffer[self.buffer_index] = byte;
+            self.buffer_index += 1;
+            return;
+        }
+
+        // the scratch holds its bits in reverse, so flip them to line up with
+        // the byte, whose first bit is its least significant
+        let pending_bits = self.scratch.reverse_bits() >> (8 - self.scratch_index);
+        self.buffer[self.buffer_index] = pending_bits | (byte << self.scratch_index);
+        self.buffer_index += 1;
+        self.scratch =
+            (byte >> (8 - self.scratch_index)).reverse_bits() >> (8 - self.scratch_index);
     }
 
     fn count_bits(&mut self, _: u32) {
@@ -352,4 +377,36 @@ mod tests {
         assert_eq!(34, reader.read_byte().unwrap());
         assert_eq!(2, reader.read_byte().unwrap());
     }
+
+    #[test]
+    fn read_write_unaligned_bytes() {
+        use crate::{
+            bit_reader::BitReader,
+            bit_writer::{BitWrite, BitWriter},
+        };
+
+        for offset in 1..8 {
+            let mut writer = BitWriter::new();
+
+            for _ in 0..offset {
+                writer.write_bit(true);
+            }
+            writer.write_byte(151);
+            writer.write_byte(34);
+            writer.write_bit(false);
+            writer.write_bit(true);
+
+            let buffer = writer.to_bytes();
+
+            let mut reader = BitReader::new(&buffer);
+
+            for _ in 0..offset {
+                assert!(reader.read_bit().unwrap());
+            }
+            assert_eq!(151, reader.read_byte().unwrap());
+            assert_eq!(34, reader.read_byte().unwrap());
+            assert!(!reader.read_bit().unwrap());
+            assert!(reader.read_bit().unwrap());
+        }
+    }
 }
@@ -86,4 +86,57 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_write_floats() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let mut in_1: [f32; 16] = [0.0; 16];
+        for (index, item) in in_1.iter_mut().enumerate() {
+            *item = (index as f32) * 1.5 - 4.0;
+        }
+        let in_2: [f64; 2] = [-0.25, 1024.5];
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: [f32; 16] = Serde::de(&mut reader).unwrap();
+        let out_2: [f64; 2] = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
+    #[test]
+    fn read_write_nested() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: [[u8; 3]; 2] = [[1, 2, 3], [4, 5, 6]];
+        let in_2: [String; 2] = ["hello".to_string(), "world".to_string()];
+        let in_3: [u16; 0] = [];
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: [[u8; 3]; 2] = Serde::de(&mut reader).unwrap();
+        let out_2: [String; 2] = Serde::de(&mut reader).unwrap();
+        let out_3: [u16; 0] = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
 }
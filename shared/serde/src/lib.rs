@@ -11,9 +11,10 @@ mod file_bit_writer;
 mod impls;
 mod integer;
 mod outgoing_packet;
+mod roundtrip;
 mod serde;
 
-pub use bit_counter::BitCounter;
+pub use bit_counter::{serialized_bit_len, BitCounter};
 pub use bit_reader::{BitReader, OwnedBitReader};
 pub use bit_writer::{BitWrite, BitWriter};
 pub use constants::{MTU_SIZE_BITS, MTU_SIZE_BYTES};
@@ -24,6 +25,7 @@ pub use integer::{
     UnsignedVariableInteger,
 };
 pub use outgoing_packet::OutgoingPacket;
+pub use roundtrip::bench_roundtrip;
 pub use serde::{
     ConstBitLength, Serde, Serde as SerdeInternal, Serde as SerdeBevyShared,
     Serde as SerdeBevyClient, Serde as SerdeBevyServer, Serde as SerdeHecs,
@@ -0,0 +1,29 @@
+use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+
+/// Serializes `value` and then deserializes the result, as a single unit of
+/// work to measure in a benchmark (i.e. inside criterion's `Bencher::iter`).
+/// Returns the number of bytes written, to report as the benchmark's
+/// throughput. `value` must fit within a single packet, see `MTU_SIZE_BYTES`
+pub fn bench_roundtrip<T: Serde>(value: &T) -> usize {
+    let mut writer = BitWriter::new();
+    value.ser(&mut writer);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let output = T::de(&mut reader).expect("a value should deserialize from its own bytes");
+    std::hint::black_box(output);
+
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{roundtrip::bench_roundtrip, UnsignedVariableInteger};
+
+    #[test]
+    fn reports_written_bytes() {
+        // 1 bit for the Option, 32 for the u32
+        assert_eq!(bench_roundtrip(&Some(42u32)), 5);
+        assert_eq!(bench_roundtrip(&UnsignedVariableInteger::<7>::new(3u64)), 1);
+    }
+}
@@ -12,6 +12,9 @@ use super::{
 
 pub const REDUNDANT_PACKET_ACKS_SIZE: u16 = 32;
 const DEFAULT_SEND_PACKETS_SIZE: usize = 256;
+// Weight of each newly delivered or dropped packet in the packet loss
+// average, which then reflects roughly the last 50 packets
+const PACKET_LOSS_SMOOTHING: f32 = 0.02;
 
 /// Keeps track of sent & received packets, and contains ack information that is
 /// copied into the standard header on each outgoing packet
@@ -26,6 +29,8 @@ pub struct AckManager {
     // However, we can only reasonably ack up to `REDUNDANT_PACKET_ACKS_SIZE + 1` packets on each
     // message we send so this should be that large.
     received_packets: SequenceBuffer<ReceivedPacket>,
+    // Moving average of the share of sent packets which were dropped
+    packet_loss: f32,
 }
 
 impl AckManager {
@@ -35,9 +40,15 @@ impl AckManager {
             last_recv_packet_index: u16::MAX,
             sent_packets: HashMap::with_capacity(DEFAULT_SEND_PACKETS_SIZE),
             received_packets: SequenceBuffer::with_capacity(REDUNDANT_PACKET_ACKS_SIZE + 1),
+            packet_loss: 0.0,
         }
     }
 
+    /// Get the share of sent packets recently dropped, from 0.0 to 1.0
+    pub fn packet_loss(&self) -> f32 {
+        self.packet_loss
+    }
+
     /// Get the index of the next outgoing packet
     pub fn next_sender_packet_index(&self) -> PacketIndex {
         self.next_packet_index
@@ -79,6 +90,7 @@ impl AckManager {
             }
 
             self.sent_packets.remove(&sender_ack_index);
+            self.record_packet_outcome(false);
         }
 
         // The `sender_ack_bitfield` is going to include whether or not the past 32
@@ -99,8 +111,10 @@ impl AckManager {
                     }
 
                     self.sent_packets.remove(&sent_packet_index);
+                    self.record_packet_outcome(false);
                 } else {
                     self.sent_packets.remove(&sent_packet_index);
+                    self.record_packet_outcome(true);
                 }
             }
 
@@ -108,6 +122,11 @@ impl AckManager {
         }
     }
 
+    fn record_packet_outcome(&mut self, dropped: bool) {
+        let sample = if dropped { 1.0 } else { 0.0 };
+        self.packet_loss += (sample - self.packet_loss) * PACKET_LOSS_SMOOTHING;
+    }
+
     /// Records the packet with the given packet index
     fn track_packet(&mut self, packet_type: PacketType, packet_index: PacketIndex) {
         self.sent_packets
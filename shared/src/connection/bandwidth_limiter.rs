@@ -0,0 +1,112 @@
+use naia_socket_shared::Instant;
+
+// How many milliseconds of the budget may be saved up while idle, and then
+// spent at once
+const MAX_BURST_MILLIS: u32 = 250;
+// The share of the saved-up budget held back for messages. Entity updates
+// only spend what is available beyond it, so they are scaled down as the
+// budget runs low, before messages have to wait
+const MESSAGE_RESERVE: f32 = 0.25;
+
+/// Limits the bytes sent over a connection to a budget per second, refilled
+/// continuously and spent as packets are sent
+pub struct BandwidthLimiter {
+    bytes_per_second: u32,
+    capacity_bytes: f32,
+    // may go below zero when a packet is larger than what was left
+    available_bytes: f32,
+    last_refill: Instant,
+}
+
+impl BandwidthLimiter {
+    pub fn new(bytes_per_second: u32) -> Self {
+        let capacity_bytes = bytes_per_second as f32 * MAX_BURST_MILLIS as f32 / 1000.0;
+        Self {
+            bytes_per_second,
+            capacity_bytes,
+            available_bytes: capacity_bytes,
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub fn bytes_per_second(&self) -> u32 {
+        self.bytes_per_second
+    }
+
+    /// Adds the budget accrued since last refilled
+    pub fn refill(&mut self, now: &Instant) {
+        let elapsed = self.last_refill.elapsed(now).as_secs_f32();
+        self.last_refill = now.clone();
+        self.available_bytes = (self.available_bytes + elapsed * self.bytes_per_second as f32)
+            .min(self.capacity_bytes);
+    }
+
+    /// Whether any of the budget is left to send another packet
+    pub fn can_send(&self) -> bool {
+        self.available_bytes > 0.0
+    }
+
+    /// How many bits of entity updates may be written before only messages
+    /// can be sent
+    pub fn update_bits_allowed(&self) -> u32 {
+        let reserve_bytes = self.capacity_bytes * MESSAGE_RESERVE;
+        ((self.available_bytes - reserve_bytes).max(0.0) * 8.0) as u32
+    }
+
+    pub fn record_sent(&mut self, bytes: usize) {
+        self.available_bytes -= bytes as f32;
+    }
+
+    /// How much of the budget is spent, from 0.0 when none of it has been
+    /// used recently, to 1.0 when nothing more can be sent for now
+    pub fn utilization(&self) -> f32 {
+        if self.capacity_bytes <= 0.0 {
+            return 1.0;
+        }
+        (1.0 - self.available_bytes / self.capacity_bytes).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_socket_shared::Instant;
+
+    use super::BandwidthLimiter;
+
+    #[test]
+    fn updates_are_limited_before_packets_are_held_back() {
+        // 250 bytes may be saved up, with 62.5 of them held back for messages
+        let mut limiter = BandwidthLimiter::new(1000);
+        assert_eq!(limiter.utilization(), 0.0);
+        assert_eq!(limiter.update_bits_allowed(), 1500);
+
+        limiter.record_sent(150);
+        assert!(limiter.can_send());
+        assert_eq!(limiter.update_bits_allowed(), 300);
+
+        limiter.record_sent(50);
+        assert!(limiter.can_send());
+        assert_eq!(limiter.update_bits_allowed(), 0);
+
+        limiter.record_sent(100);
+        assert!(!limiter.can_send());
+        assert_eq!(limiter.utilization(), 1.0);
+    }
+
+    #[test]
+    fn budget_refills_over_time_up_to_burst() {
+        let mut limiter = BandwidthLimiter::new(1000);
+        limiter.record_sent(300);
+        assert!(!limiter.can_send());
+
+        let mut now = Instant::now();
+        now.add_millis(100);
+        limiter.refill(&now);
+        assert!(limiter.can_send());
+        assert!((limiter.utilization() - 0.8).abs() < 0.05);
+
+        now.add_millis(10_000);
+        limiter.refill(&now);
+        assert_eq!(limiter.utilization(), 0.0);
+    }
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 pub struct BandwidthMonitor {
     time_queue: ExpiringTimeQueue<usize>,
-    total_bytes: u16,
+    total_bytes: usize,
     to_kbps_factor: f32,
 }
 
@@ -15,10 +15,15 @@ impl BandwidthMonitor {
         }
     }
 
+    /// Returns the average bytes per second over the measured duration
+    pub fn bytes_per_second(&mut self) -> f32 {
+        self.bandwidth() * 125.0
+    }
+
     pub fn record_packet(&mut self, bytes: usize) {
         self.clear_expired_packets();
 
-        self.total_bytes += bytes as u16;
+        self.total_bytes += bytes;
         self.time_queue.add_item(bytes);
     }
 
@@ -31,11 +36,40 @@ impl BandwidthMonitor {
     fn clear_expired_packets(&mut self) {
         let now = Instant::now();
         while let Some(bytes) = self.time_queue.pop_item(&now) {
-            self.total_bytes -= bytes as u16;
+            self.total_bytes -= bytes;
         }
     }
 }
 
+/// Counts the bytes passing one way over a connection, both in total and
+/// per second over a sliding window
+pub struct ByteCounter {
+    total_bytes: u64,
+    monitor: BandwidthMonitor,
+}
+
+impl ByteCounter {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            total_bytes: 0,
+            monitor: BandwidthMonitor::new(window),
+        }
+    }
+
+    pub fn record_packet(&mut self, bytes: usize) {
+        self.total_bytes += bytes as u64;
+        self.monitor.record_packet(bytes);
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    pub fn bytes_per_second(&mut self) -> f32 {
+        self.monitor.bytes_per_second()
+    }
+}
+
 ////
 
 use naia_socket_shared::{Instant, TimeQueue};
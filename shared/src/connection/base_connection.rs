@@ -17,8 +17,9 @@ use crate::{
 };
 
 use super::{
-    ack_manager::AckManager, connection_config::ConnectionConfig,
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, standard_header::StandardHeader,
+    ack_manager::AckManager, bandwidth_limiter::BandwidthLimiter,
+    connection_config::ConnectionConfig, packet_notifiable::PacketNotifiable,
+    packet_type::PacketType, send_rate::SendRateController, standard_header::StandardHeader,
 };
 
 /// Represents a connection to a remote host, and provides functionality to
@@ -32,6 +33,9 @@ pub struct BaseConnection<E: Copy + Eq + Hash + Send + Sync> {
     heartbeat_timer: Timer,
     timeout_timer: Timer,
     ack_manager: AckManager,
+    compact_headers: bool,
+    bandwidth_limiter: Option<BandwidthLimiter>,
+    send_rate_controller: Option<SendRateController>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
@@ -48,14 +52,95 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
             heartbeat_timer: Timer::new(connection_config.heartbeat_interval),
             timeout_timer: Timer::new(connection_config.disconnection_timeout_duration),
             ack_manager: AckManager::new(),
+            compact_headers: connection_config.compact_headers,
+            bandwidth_limiter: connection_config
+                .outgoing_bandwidth_limit
+                .map(BandwidthLimiter::new),
+            send_rate_controller: connection_config
+                .adaptive_send_rate
+                .as_ref()
+                .map(SendRateController::new),
             message_manager: MessageManager::new(host_type, channel_kinds),
-            host_world_manager: HostWorldManager::new(address, global_world_manager),
-            remote_world_manager: RemoteWorldManager::new(),
+            host_world_manager: HostWorldManager::new(
+                address,
+                global_world_manager,
+                connection_config.prioritize_initial_replication,
+                connection_config.entity_update_bits_per_tick,
+            ),
+            remote_world_manager: RemoteWorldManager::new(
+                connection_config.entity_waitlist_capacity,
+            ),
             remote_world_reader: RemoteWorldReader::new(),
             local_world_manager: LocalWorldManager::new(user_key),
         }
     }
 
+    // Bandwidth Limiting
+
+    /// Refills the outgoing bandwidth budget, and scales down the entity
+    /// updates to be written to what is left of it
+    pub fn limit_outgoing_events(
+        &mut self,
+        now: &Instant,
+        host_world_events: &mut HostWorldEvents<E>,
+    ) {
+        let Some(limiter) = &mut self.bandwidth_limiter else {
+            return;
+        };
+        limiter.refill(now);
+        let update_bits_allowed = limiter.update_bits_allowed();
+        if update_bits_allowed == 0 {
+            // remaining updates stay in their diff masks, and will be
+            // collected again next tick
+            host_world_events.next_send_updates.clear();
+        }
+        host_world_events.update_bits_remaining = Some(
+            host_world_events
+                .update_bits_remaining
+                .map_or(update_bits_allowed, |bits| bits.min(update_bits_allowed)),
+        );
+    }
+
+    /// Returns whether the outgoing bandwidth budget allows another packet
+    /// to be sent. Messages left unsent wait for a later tick
+    pub fn can_send_packet(&self) -> bool {
+        self.bandwidth_limiter
+            .as_ref()
+            .is_none_or(BandwidthLimiter::can_send)
+    }
+
+    /// Spends the outgoing bandwidth budget on a sent packet
+    pub fn record_sent_packet(&mut self, bytes: usize) {
+        if let Some(limiter) = &mut self.bandwidth_limiter {
+            limiter.record_sent(bytes);
+        }
+    }
+
+    /// How much of the outgoing bandwidth budget is spent, from 0.0 to 1.0,
+    /// if limited
+    pub fn bandwidth_utilization(&self) -> Option<f32> {
+        self.bandwidth_limiter
+            .as_ref()
+            .map(BandwidthLimiter::utilization)
+    }
+
+    // Send Rate
+
+    /// Returns whether outgoing packets are due, given the adaptive send rate
+    /// and the packet loss measured so far
+    pub fn should_send_packets(&mut self, now: &Instant) -> bool {
+        let packet_loss = self.ack_manager.packet_loss();
+        self.send_rate_controller
+            .as_mut()
+            .is_none_or(|controller| controller.should_send(now, packet_loss))
+    }
+
+    /// Gets the share of packets sent to the remote host which were recently
+    /// dropped, from 0.0 to 1.0
+    pub fn packet_loss(&self) -> f32 {
+        self.ack_manager.packet_loss()
+    }
+
     // Heartbeats
 
     /// Record that a message has been sent (to prevent needing to send a
@@ -107,9 +192,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
     /// bytes
     pub fn write_header(&mut self, packet_type: PacketType, writer: &mut BitWriter) {
         // Add header onto message!
-        self.ack_manager
-            .next_outgoing_packet_header(packet_type)
-            .ser(writer);
+        let header = self.ack_manager.next_outgoing_packet_header(packet_type);
+        if self.compact_headers {
+            header.ser_compact(writer);
+        } else {
+            header.ser(writer);
+        }
     }
 
     /// Get the next outgoing packet's index
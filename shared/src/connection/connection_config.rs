@@ -1,5 +1,7 @@
 use std::{default::Default, time::Duration};
 
+use super::send_rate::SendRateConfig;
+
 /// Contains Config properties which will be used by a Server or Client
 #[derive(Clone, Debug)]
 pub struct ConnectionConfig {
@@ -12,19 +14,59 @@ pub struct ConnectionConfig {
     /// The duration over which to measure bandwidth. Set to None to avoid
     /// measure bandwidth at all.
     pub bandwidth_measure_duration: Option<Duration>,
+    /// Whether to write packet headers in compact form, omitting fields which
+    /// hold their usual values. Headers in either form can always be read, so
+    /// this only needs to be enabled on the side(s) that should send them
+    pub compact_headers: bool,
+    /// Whether a newly connected remote host should receive the spawns for
+    /// its initial world ahead of updates to entities it already has. Once
+    /// every entity in scope has been spawned remotely, updates and spawns
+    /// are sent with equal priority again
+    pub prioritize_initial_replication: bool,
+    /// Maximum number of bits of entity updates to write each time outgoing
+    /// packets are sent. Updates which don't fit are deferred to later ticks,
+    /// with higher priority Entities sent first. Set to None to always send
+    /// every pending update
+    pub entity_update_bits_per_tick: Option<u32>,
+    /// Maximum number of received items (messages, component inserts and
+    /// updates) held while waiting on Entities which aren't in scope yet.
+    /// Once full, the oldest waiting item is dropped to make room, so a
+    /// remote host can't grow the waitlist without bound. Set to None for
+    /// no limit
+    pub entity_waitlist_capacity: Option<usize>,
+    /// Maximum number of bytes per second to send over each connection. As
+    /// the budget runs low, fewer entity updates are written, and once it is
+    /// spent, packets wait for it to refill. Set to None for no limit
+    pub outgoing_bandwidth_limit: Option<u32>,
+    /// How often to send packets, backing off while packets are being
+    /// dropped. Set to None to send whenever there is something to send
+    pub adaptive_send_rate: Option<SendRateConfig>,
 }
 
 impl ConnectionConfig {
     /// Creates a new ConnectionConfig, used to initialize a Connection
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         disconnection_timeout_duration: Duration,
         heartbeat_interval: Duration,
         bandwidth_measure_duration: Option<Duration>,
+        compact_headers: bool,
+        prioritize_initial_replication: bool,
+        entity_update_bits_per_tick: Option<u32>,
+        entity_waitlist_capacity: Option<usize>,
+        outgoing_bandwidth_limit: Option<u32>,
+        adaptive_send_rate: Option<SendRateConfig>,
     ) -> Self {
         ConnectionConfig {
             disconnection_timeout_duration,
             heartbeat_interval,
             bandwidth_measure_duration,
+            compact_headers,
+            prioritize_initial_replication,
+            entity_update_bits_per_tick,
+            entity_waitlist_capacity,
+            outgoing_bandwidth_limit,
+            adaptive_send_rate,
         }
     }
 }
@@ -35,6 +77,12 @@ impl Default for ConnectionConfig {
             disconnection_timeout_duration: Duration::from_secs(30),
             heartbeat_interval: Duration::from_secs(4),
             bandwidth_measure_duration: None,
+            compact_headers: false,
+            prioritize_initial_replication: true,
+            entity_update_bits_per_tick: None,
+            entity_waitlist_capacity: Some(1024),
+            outgoing_bandwidth_limit: None,
+            adaptive_send_rate: None,
         }
     }
 }
@@ -0,0 +1,24 @@
+/// Measurements of the network conditions over a connection
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectionStats {
+    /// Average round trip time, in milliseconds
+    pub rtt: f32,
+    /// Average deviation of the round trip time, in milliseconds
+    pub jitter: f32,
+    /// Share of recently sent packets which were dropped, from 0.0 to 1.0
+    pub loss: f32,
+    /// Total bytes received over the connection
+    pub bytes_in: u64,
+    /// Total bytes sent over the connection
+    pub bytes_out: u64,
+    /// Bytes received per second, averaged over the last
+    /// `ConnectionConfig::bandwidth_measure_duration`, or second if unset
+    pub bytes_in_per_second: f32,
+    /// Bytes sent per second, averaged over the same window
+    pub bytes_out_per_second: f32,
+    /// Messages waiting to be sent or, on reliable channels, acknowledged
+    pub queued_messages: usize,
+    /// Entities with updates deferred by the update or bandwidth budget,
+    /// still waiting to be written
+    pub update_backlog: usize,
+}
@@ -0,0 +1,19 @@
+use naia_serde::SerdeInternal;
+
+/// Why the Server closed a Client's connection
+#[derive(SerdeInternal, Debug, PartialEq, Eq, Clone)]
+pub enum DisconnectReason {
+    /// The Server is shutting down, with an optional message for the user,
+    /// i.e. "maintenance"
+    ServerClosed(Option<String>),
+}
+
+impl DisconnectReason {
+    /// Returns whether the Client should attempt to reconnect to the Server
+    /// after being disconnected for this reason
+    pub fn should_reconnect(&self) -> bool {
+        match self {
+            DisconnectReason::ServerClosed(_) => false,
+        }
+    }
+}
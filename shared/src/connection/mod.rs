@@ -1,12 +1,17 @@
 pub mod ack_manager;
+pub mod bandwidth_limiter;
 pub mod bandwidth_monitor;
 pub mod base_connection;
 pub mod compression_config;
 pub mod connection_config;
+pub mod connection_stats;
 pub mod decoder;
+pub mod disconnect_reason;
 pub mod encoder;
 pub mod packet_notifiable;
+pub mod packet_recording;
 pub mod packet_type;
 pub mod ping_store;
+pub mod send_rate;
 pub mod sequence_buffer;
 pub mod standard_header;
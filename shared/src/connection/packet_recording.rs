@@ -0,0 +1,268 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+    sync::Mutex,
+    time::Duration,
+};
+
+use log::warn;
+use naia_serde::MTU_SIZE_BYTES;
+use naia_socket_shared::Instant;
+
+const RECORDING_MAGIC: &[u8; 8] = b"NAIAREC1";
+
+/// Whether a recorded packet was being sent or received
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PacketDirection {
+    Outgoing,
+    Incoming,
+}
+
+/// A single packet captured during a recorded session
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PacketRecord {
+    /// Time since the start of the recording
+    pub elapsed: Duration,
+    pub direction: PacketDirection,
+    /// The remote address, if known to the recording side
+    pub address: Option<SocketAddr>,
+    /// The raw packet bytes, as they were on the wire
+    pub payload: Box<[u8]>,
+}
+
+impl PacketRecord {
+    fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let direction: u8 = match self.direction {
+            PacketDirection::Outgoing => 0,
+            PacketDirection::Incoming => 1,
+        };
+        writer.write_all(&[direction])?;
+        writer.write_all(&(self.elapsed.as_micros() as u64).to_le_bytes())?;
+
+        match self.address {
+            None => {
+                writer.write_all(&[0])?;
+            }
+            Some(SocketAddr::V4(address)) => {
+                writer.write_all(&[4])?;
+                writer.write_all(&address.ip().octets())?;
+                writer.write_all(&address.port().to_le_bytes())?;
+            }
+            Some(SocketAddr::V6(address)) => {
+                writer.write_all(&[6])?;
+                writer.write_all(&address.ip().octets())?;
+                writer.write_all(&address.port().to_le_bytes())?;
+            }
+        }
+
+        writer.write_all(&(self.payload.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.payload)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> io::Result<Option<Self>> {
+        let mut direction = [0; 1];
+        if reader.read(&mut direction)? == 0 {
+            return Ok(None);
+        }
+        let direction = match direction[0] {
+            0 => PacketDirection::Outgoing,
+            1 => PacketDirection::Incoming,
+            _ => return Err(invalid_data("invalid packet direction")),
+        };
+
+        let mut elapsed = [0; 8];
+        reader.read_exact(&mut elapsed)?;
+        let elapsed = Duration::from_micros(u64::from_le_bytes(elapsed));
+
+        let mut address_kind = [0; 1];
+        reader.read_exact(&mut address_kind)?;
+        let address = match address_kind[0] {
+            0 => None,
+            4 => {
+                let mut ip = [0; 4];
+                reader.read_exact(&mut ip)?;
+                let port = read_port(reader)?;
+                Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::from(ip)), port))
+            }
+            6 => {
+                let mut ip = [0; 16];
+                reader.read_exact(&mut ip)?;
+                let port = read_port(reader)?;
+                Some(SocketAddr::new(IpAddr::V6(Ipv6Addr::from(ip)), port))
+            }
+            _ => return Err(invalid_data("invalid address kind")),
+        };
+
+        let mut length = [0; 4];
+        reader.read_exact(&mut length)?;
+        let length = u32::from_le_bytes(length) as usize;
+        if length > MTU_SIZE_BYTES {
+            return Err(invalid_data("packet is larger than the MTU"));
+        }
+        let mut payload = vec![0; length];
+        reader.read_exact(&mut payload)?;
+
+        Ok(Some(Self {
+            elapsed,
+            direction,
+            address,
+            payload: payload.into_boxed_slice(),
+        }))
+    }
+}
+
+fn read_port(reader: &mut dyn Read) -> io::Result<u16> {
+    let mut port = [0; 2];
+    reader.read_exact(&mut port)?;
+    Ok(u16::from_le_bytes(port))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Writes timestamped packets to an output stream, for later replay
+pub struct PacketRecorder {
+    start: Instant,
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl PacketRecorder {
+    /// Create a new PacketRecorder which writes to the given output stream
+    pub fn new<W: Write + Send + 'static>(mut writer: W) -> io::Result<Self> {
+        writer.write_all(RECORDING_MAGIC)?;
+        Ok(Self {
+            start: Instant::now(),
+            writer: Mutex::new(Box::new(writer)),
+        })
+    }
+
+    /// Create a new PacketRecorder which writes to a newly created file
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Record a single packet, timestamped relative to the recorder's creation
+    pub fn record(&self, direction: PacketDirection, address: Option<&SocketAddr>, payload: &[u8]) {
+        let record = PacketRecord {
+            elapsed: self.start.elapsed(&Instant::now()),
+            direction,
+            address: address.copied(),
+            payload: payload.into(),
+        };
+
+        let Ok(mut writer) = self.writer.lock() else {
+            warn!("PacketRecorder: writer lock poisoned, dropping packet");
+            return;
+        };
+        if let Err(err) = record.write_to(writer.as_mut()) {
+            warn!("PacketRecorder: failed to write packet: {}", err);
+        }
+    }
+
+    /// Flush any buffered packets to the underlying output stream
+    pub fn flush(&self) -> io::Result<()> {
+        match self.writer.lock() {
+            Ok(mut writer) => writer.flush(),
+            Err(_) => Err(io::Error::other("PacketRecorder writer lock poisoned")),
+        }
+    }
+}
+
+impl Drop for PacketRecorder {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Read all packets from a stream previously written by a PacketRecorder
+pub fn read_packet_records<R: Read>(mut reader: R) -> io::Result<Vec<PacketRecord>> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != RECORDING_MAGIC {
+        return Err(invalid_data("not a naia packet recording"));
+    }
+
+    let mut output = Vec::new();
+    while let Some(record) = PacketRecord::read_from(&mut reader)? {
+        output.push(record);
+    }
+    Ok(output)
+}
+
+/// Read all packets from a file previously written by a PacketRecorder
+pub fn load_packet_records<P: AsRef<Path>>(path: P) -> io::Result<Vec<PacketRecord>> {
+    read_packet_records(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use super::{read_packet_records, PacketDirection, PacketRecorder};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn record_and_read() {
+        let buffer = SharedBuffer::default();
+        let address_v4: SocketAddr = "127.0.0.1:14191".parse().unwrap();
+        let address_v6: SocketAddr = "[::1]:14192".parse().unwrap();
+
+        {
+            let recorder = PacketRecorder::new(buffer.clone()).unwrap();
+            recorder.record(PacketDirection::Outgoing, None, &[1, 2, 3]);
+            recorder.record(PacketDirection::Incoming, Some(&address_v4), &[]);
+            recorder.record(PacketDirection::Incoming, Some(&address_v6), &[4; 300]);
+        }
+
+        let bytes = buffer.0.lock().unwrap().clone();
+        let records = read_packet_records(bytes.as_slice()).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].direction, PacketDirection::Outgoing);
+        assert_eq!(records[0].address, None);
+        assert_eq!(&*records[0].payload, &[1, 2, 3]);
+        assert_eq!(records[1].direction, PacketDirection::Incoming);
+        assert_eq!(records[1].address, Some(address_v4));
+        assert!(records[1].payload.is_empty());
+        assert_eq!(records[2].address, Some(address_v6));
+        assert_eq!(&*records[2].payload, &[4; 300]);
+        assert!(records[0].elapsed <= records[1].elapsed);
+        assert!(records[1].elapsed <= records[2].elapsed);
+    }
+
+    #[test]
+    fn rejects_foreign_data() {
+        assert!(read_packet_records(&b"NOTNAIA!"[..]).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_packet() {
+        let mut bytes = b"NAIAREC1".to_vec();
+        // outgoing, at time zero, with no address
+        bytes.push(0);
+        bytes.extend_from_slice(&0_u64.to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(read_packet_records(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use naia_socket_shared::Instant;
+
+// How quickly the send interval lengthens while packet loss is high (by half
+// again each time), and shortens again once it has fallen (by a tenth)
+const BACK_OFF_DIVISOR: u32 = 2;
+const RECOVER_DIVISOR: u32 = 10;
+
+/// Configures how often packets are sent over a connection, slowing down
+/// while the connection is dropping packets
+#[derive(Clone, Debug)]
+pub struct SendRateConfig {
+    /// The shortest time between outgoing packets, used while the connection
+    /// is healthy. A zero interval sends on every call to send updates
+    pub min_interval: Duration,
+    /// The longest time between outgoing packets, however many are dropped
+    pub max_interval: Duration,
+    /// The share of dropped packets, from 0.0 to 1.0, above which the send
+    /// interval lengthens. Once loss falls below half of it, the interval
+    /// shortens back towards `min_interval`
+    pub loss_threshold: f32,
+}
+
+impl Default for SendRateConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::ZERO,
+            max_interval: Duration::from_millis(200),
+            loss_threshold: 0.1,
+        }
+    }
+}
+
+/// Scales the interval between outgoing packets with the measured packet
+/// loss
+pub struct SendRateController {
+    config: SendRateConfig,
+    interval: Duration,
+    last_sent: Option<Instant>,
+}
+
+impl SendRateController {
+    pub fn new(config: &SendRateConfig) -> Self {
+        Self {
+            config: config.clone(),
+            interval: config.min_interval,
+            last_sent: None,
+        }
+    }
+
+    /// The current time between outgoing packets
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns whether packets are due, given the current packet loss. If
+    /// so, the interval is adjusted and restarted
+    pub fn should_send(&mut self, now: &Instant, packet_loss: f32) -> bool {
+        if let Some(last_sent) = &self.last_sent {
+            if last_sent.elapsed(now) < self.interval {
+                return false;
+            }
+        }
+        self.last_sent = Some(now.clone());
+
+        if packet_loss > self.config.loss_threshold {
+            let interval = self.interval.max(Duration::from_millis(1));
+            self.interval = (interval + interval / BACK_OFF_DIVISOR).min(self.config.max_interval);
+        } else if packet_loss < self.config.loss_threshold / 2.0 {
+            self.interval =
+                (self.interval - self.interval / RECOVER_DIVISOR).max(self.config.min_interval);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use naia_socket_shared::Instant;
+
+    use super::{SendRateConfig, SendRateController};
+
+    #[test]
+    fn interval_lengthens_with_loss_and_recovers() {
+        let config = SendRateConfig {
+            min_interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(40),
+            loss_threshold: 0.1,
+        };
+        let mut controller = SendRateController::new(&config);
+        let mut now = Instant::now();
+
+        assert!(controller.should_send(&now, 0.5));
+        assert_eq!(controller.interval(), Duration::from_millis(15));
+        now.add_millis(10);
+        assert!(!controller.should_send(&now, 0.5));
+
+        for _ in 0..10 {
+            now.add_millis(40);
+            assert!(controller.should_send(&now, 0.5));
+        }
+        assert_eq!(controller.interval(), config.max_interval);
+
+        // loss between half the threshold and the threshold holds the rate
+        now.add_millis(40);
+        assert!(controller.should_send(&now, 0.08));
+        assert_eq!(controller.interval(), config.max_interval);
+
+        for _ in 0..100 {
+            now.add_millis(40);
+            assert!(controller.should_send(&now, 0.0));
+        }
+        assert_eq!(controller.interval(), config.min_interval);
+    }
+}
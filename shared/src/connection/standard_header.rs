@@ -1,9 +1,14 @@
-use naia_serde::SerdeInternal;
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr, SignedInteger};
 
 use crate::{connection::packet_type::PacketType, types::PacketIndex};
 
+// Largest distance between the packet index and the ack index which can be
+// written as a short delta in a compact header
+const ACK_DELTA_BITS: u8 = 6;
+const ACK_DELTA_MAX: i16 = (1 << ACK_DELTA_BITS) - 1;
+
 // This header provides reliability information.
-#[derive(Copy, Debug, PartialEq, Clone, SerdeInternal)]
+#[derive(Copy, Debug, PartialEq, Clone)]
 pub struct StandardHeader {
     pub packet_type: PacketType,
     /// Packet index identifying this packet
@@ -34,4 +39,147 @@ impl StandardHeader {
             sender_ack_bitfield,
         }
     }
+
+    /// Writes the header in compact form. Rather than writing every field in
+    /// full, the ack index is written as a short delta from the packet index
+    /// when the two are close, and an ack bitfield with every bit set (the
+    /// usual case on a healthy connection) is written as a single flag. Each
+    /// header is self-contained, so a lost packet never affects how the next
+    /// one is read.
+    ///
+    /// For a 60Hz stream of Data packets with no loss this shrinks the header
+    /// from 66 bits to 27, saving roughly 290 bytes per second in each
+    /// direction.
+    pub fn ser_compact(&self, writer: &mut dyn BitWrite) {
+        self.packet_type.ser(writer);
+
+        // is compact
+        true.ser(writer);
+
+        self.sender_packet_index.ser(writer);
+
+        let ack_delta = self.sender_ack_index.wrapping_sub(self.sender_packet_index) as i16;
+        let ack_is_near = (-ACK_DELTA_MAX..=ACK_DELTA_MAX).contains(&ack_delta);
+        ack_is_near.ser(writer);
+        if ack_is_near {
+            SignedInteger::<ACK_DELTA_BITS>::new(ack_delta).ser(writer);
+        } else {
+            self.sender_ack_index.ser(writer);
+        }
+
+        let ack_bitfield_is_full = self.sender_ack_bitfield == u32::MAX;
+        ack_bitfield_is_full.ser(writer);
+        if !ack_bitfield_is_full {
+            self.sender_ack_bitfield.ser(writer);
+        }
+    }
+}
+
+impl Serde for StandardHeader {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.packet_type.ser(writer);
+
+        // is compact
+        false.ser(writer);
+
+        self.sender_packet_index.ser(writer);
+        self.sender_ack_index.ser(writer);
+        self.sender_ack_bitfield.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let packet_type = PacketType::de(reader)?;
+        let is_compact = bool::de(reader)?;
+        let sender_packet_index = PacketIndex::de(reader)?;
+
+        if !is_compact {
+            let sender_ack_index = PacketIndex::de(reader)?;
+            let sender_ack_bitfield = u32::de(reader)?;
+            return Ok(Self::new(
+                packet_type,
+                sender_packet_index,
+                sender_ack_index,
+                sender_ack_bitfield,
+            ));
+        }
+
+        let ack_is_near = bool::de(reader)?;
+        let sender_ack_index = if ack_is_near {
+            let ack_delta: i16 = SignedInteger::<ACK_DELTA_BITS>::de(reader)?.to();
+            sender_packet_index.wrapping_add(ack_delta as u16)
+        } else {
+            PacketIndex::de(reader)?
+        };
+
+        let ack_bitfield_is_full = bool::de(reader)?;
+        let sender_ack_bitfield = if ack_bitfield_is_full {
+            u32::MAX
+        } else {
+            u32::de(reader)?
+        };
+
+        Ok(Self::new(
+            packet_type,
+            sender_packet_index,
+            sender_ack_index,
+            sender_ack_bitfield,
+        ))
+    }
+
+    fn bit_length(&self) -> u32 {
+        self.packet_type.bit_length()
+            + true.bit_length()
+            + self.sender_packet_index.bit_length()
+            + self.sender_ack_index.bit_length()
+            + self.sender_ack_bitfield.bit_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWriter, Serde};
+
+    use super::StandardHeader;
+    use crate::PacketType;
+
+    fn round_trip(header: StandardHeader, compact: bool) -> (StandardHeader, u32) {
+        let mut writer = BitWriter::new();
+        if compact {
+            header.ser_compact(&mut writer);
+        } else {
+            header.ser(&mut writer);
+        }
+        let bits = writer.bits_free();
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+        (StandardHeader::de(&mut reader).unwrap(), bits)
+    }
+
+    #[test]
+    fn full_round_trip() {
+        let header = StandardHeader::new(PacketType::Ping, 12, 40000, 0b1011);
+        assert_eq!(round_trip(header, false).0, header);
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let headers = [
+            StandardHeader::new(PacketType::Data, 100, 98, u32::MAX),
+            StandardHeader::new(PacketType::Data, 5, 65530, u32::MAX),
+            StandardHeader::new(PacketType::Data, 100, 163, 0b1011),
+            StandardHeader::new(PacketType::Heartbeat, 100, 37, 0),
+            StandardHeader::new(PacketType::Data, 0, 30000, 7),
+        ];
+        for header in headers {
+            assert_eq!(round_trip(header, true).0, header);
+        }
+    }
+
+    #[test]
+    fn compact_is_smaller_for_steady_stream() {
+        let header = StandardHeader::new(PacketType::Data, 1000, 998, u32::MAX);
+        let (_, full_bits_free) = round_trip(header, false);
+        let (_, compact_bits_free) = round_trip(header, true);
+        assert_eq!(compact_bits_free - full_bits_free, 66 - 27);
+    }
 }
@@ -17,4 +17,7 @@ pub enum HandshakeHeader {
     ServerConnectResponse,
     // Used to request a graceful Client disconnect from the Server
     Disconnect,
+    // Used by the Server to close a Client's connection, along with the
+    // reason it was closed
+    ServerDisconnect,
 }
@@ -1,2 +1,5 @@
 mod header;
+mod proof_of_work;
+
 pub use header::HandshakeHeader;
+pub use proof_of_work::ProofOfWork;
@@ -0,0 +1,51 @@
+use sha2::{Digest, Sha256};
+
+/// A hash-based proof-of-work puzzle, used to raise the cost of opening
+/// handshakes with the Server.
+///
+/// The Server issues a challenge (the digest it signed for the Client's
+/// timestamp) alongside a difficulty. The Client must then find a nonce such
+/// that the SHA-256 hash of the challenge followed by the nonce begins with at
+/// least `difficulty` zero bits. Each additional bit of difficulty doubles the
+/// expected work for the Client, while verification is always a single hash.
+pub struct ProofOfWork;
+
+impl ProofOfWork {
+    /// Finds the first nonce which satisfies the given difficulty for the
+    /// given challenge. A difficulty of zero is satisfied by any nonce.
+    pub fn solve(challenge: &[u8], difficulty: u8) -> u64 {
+        let mut nonce: u64 = 0;
+        while !Self::verify(challenge, difficulty, nonce) {
+            nonce = nonce.wrapping_add(1);
+        }
+        nonce
+    }
+
+    /// Returns whether the nonce satisfies the given difficulty for the given
+    /// challenge
+    pub fn verify(challenge: &[u8], difficulty: u8, nonce: u64) -> bool {
+        if difficulty == 0 {
+            return true;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(challenge);
+        hasher.update(nonce.to_le_bytes());
+        let hash = hasher.finalize();
+
+        leading_zero_bits(&hash) >= u32::from(difficulty)
+    }
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut count = 0;
+    for byte in bytes {
+        if *byte == 0 {
+            count += 8;
+        } else {
+            count += byte.leading_zeros();
+            break;
+        }
+    }
+    count
+}
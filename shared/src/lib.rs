@@ -24,14 +24,16 @@ pub use naia_derive::{
     Channel, Message, MessageBevy, MessageHecs, Replicate, ReplicateBevy, ReplicateHecs,
 };
 pub use naia_serde::{
-    BitReader, BitWrite, BitWriter, ConstBitLength, FileBitWriter, OutgoingPacket, OwnedBitReader,
-    Serde, SerdeBevyClient, SerdeBevyServer, SerdeBevyShared, SerdeErr, SerdeHecs,
-    SerdeIntegerConversion, SerdeInternal, SignedInteger, SignedVariableInteger, UnsignedInteger,
-    UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
+    bench_roundtrip, serialized_bit_len, BitReader, BitWrite, BitWriter, ConstBitLength,
+    FileBitWriter, OutgoingPacket, OwnedBitReader, Serde, SerdeBevyClient, SerdeBevyServer,
+    SerdeBevyShared, SerdeErr, SerdeHecs, SerdeIntegerConversion, SerdeInternal, SignedInteger,
+    SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
 };
 pub use naia_socket_shared::{
-    generate_identity_token, link_condition_logic, IdentityToken, Instant, LinkConditionerConfig,
-    Random, SocketConfig, TimeQueue,
+    generate_identity_token, link_condition_logic, AllowedOrigin, DataChannelConfig,
+    DataChannelConfigError, IceServerConfig, IdentityReceiverResult, IdentityToken, Instant,
+    LinkConditionerConfig, LoopbackClient, LoopbackServer, LoopbackTransport, QueueOverflowPolicy,
+    Random, RejectReason, SocketConfig, TimeQueue, Transport,
 };
 
 mod backends;
@@ -51,44 +53,71 @@ mod wrapping_number;
 pub use backends::{Timer, Timestamp};
 pub use connection::{
     ack_manager::AckManager,
-    bandwidth_monitor::BandwidthMonitor,
+    bandwidth_limiter::BandwidthLimiter,
+    bandwidth_monitor::{BandwidthMonitor, ByteCounter},
     base_connection::BaseConnection,
     compression_config::{CompressionConfig, CompressionMode},
     connection_config::ConnectionConfig,
+    connection_stats::ConnectionStats,
     decoder::Decoder,
+    disconnect_reason::DisconnectReason,
     encoder::Encoder,
     packet_notifiable::PacketNotifiable,
+    packet_recording::{
+        load_packet_records, read_packet_records, PacketDirection, PacketRecord, PacketRecorder,
+    },
     packet_type::PacketType,
     ping_store::{PingIndex, PingStore},
+    send_rate::{SendRateConfig, SendRateController},
     standard_header::StandardHeader,
 };
 pub use messages::{
     channels::{
-        channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings, TickBufferSettings},
+        channel::{
+            Channel, ChannelDirection, ChannelMode, CoalesceSettings, CompressionAlgorithm,
+            CompressionSettings, OrderedUnreliableSettings, OverflowStrategy, ReliableSettings,
+            SendBufferSettings, TickBufferSettings, UnresolvedEntityPolicy,
+        },
         channel_kinds::{ChannelKind, ChannelKinds},
         default_channels,
+        file_transfer_channel::FileTransferChannel,
         receivers::{
             channel_receiver::ChannelReceiver, ordered_reliable_receiver::OrderedReliableReceiver,
             unordered_reliable_receiver::UnorderedReliableReceiver,
         },
         senders::{
-            channel_sender::{ChannelSender, MessageChannelSender},
+            channel_sender::{
+                ChannelFullError, ChannelSender, MessageChannelSender, MessageSendError,
+            },
             reliable_sender::ReliableSender,
             request_sender::LocalResponseId,
         },
         system_channel::SystemChannel,
     },
+    file_transfer::{
+        file_transfer_message::{
+            file_checksum, FileTransferAction, FileTransferId, FileTransferMessage,
+        },
+        file_transfer_receiver::{FileTransferReceiveEvent, FileTransferReceiver},
+        file_transfer_sender::{
+            FileTransferSender, FILE_TRANSFER_CHUNK_BYTES, FILE_TRANSFER_WINDOW_BYTES,
+        },
+    },
+    idempotent_message::IdempotentMessage,
     message::{Message, Message as MessageBevy, Message as MessageHecs, MessageBuilder},
     message_container::MessageContainer,
+    message_dependency::MessageDependency,
     message_kinds::{MessageKind, MessageKinds},
     message_manager::MessageManager,
     named::Named,
     request::{
-        GlobalRequestId, GlobalResponseId, Request, Response, ResponseReceiveKey, ResponseSendKey,
+        GlobalRequestId, GlobalResponseId, PendingRequest, Request, Response, ResponseReceiveError,
+        ResponseReceiveKey, ResponseSendKey,
     },
 };
 pub use world::{
     component::{
+        change_threshold::ChangeThreshold,
         component_kinds::{ComponentKind, ComponentKinds},
         component_update::{ComponentFieldUpdate, ComponentUpdate},
         diff_mask::DiffMask,
@@ -101,7 +130,8 @@ pub use world::{
             ReplicaRefTrait, ReplicaRefWrapper,
         },
         replicate::{
-            Replicate, Replicate as ReplicateHecs, Replicate as ReplicateBevy, ReplicateBuilder, ReplicatedComponent,
+            Replicate, Replicate as ReplicateHecs, Replicate as ReplicateBevy, ReplicateBuilder,
+            ReplicatedComponent,
         },
     },
     delegation::{
@@ -131,7 +161,9 @@ pub use world::{
     local_world_manager::LocalWorldManager,
     remote::{
         entity_action_event::EntityActionEvent,
+        entity_despawn_hook::EntityDespawnHook,
         entity_event::{EntityEvent, EntityResponseEvent},
+        entity_waitlist::{WaitlistEntry, WaitlistHandle, WaitlistStats},
         remote_world_manager::RemoteWorldManager,
     },
     shared_global_world_manager::SharedGlobalWorldManager,
@@ -142,7 +174,7 @@ pub use bigmap::{BigMap, BigMapKey};
 pub use game_time::{GameDuration, GameInstant, GAME_TIME_LIMIT};
 pub use key_generator::KeyGenerator;
 pub use messages::channels::senders::request_sender::{
-    LocalRequestOrResponseId, RequestOrResponse,
+    LocalRequestId, LocalRequestOrResponseId, RequestOrResponse,
 };
 pub use protocol::{Protocol, ProtocolPlugin};
 pub use types::{HostType, MessageIndex, PacketIndex, ShortMessageIndex, Tick};
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 // Channel Trait
 pub trait Channel: 'static {}
 
@@ -6,6 +8,11 @@ pub trait Channel: 'static {}
 pub struct ChannelSettings {
     pub mode: ChannelMode,
     pub direction: ChannelDirection,
+    pub coalesce: Option<CoalesceSettings>,
+    pub unresolved_entities: UnresolvedEntityPolicy,
+    pub send_buffer: Option<SendBufferSettings>,
+    pub max_message_size: Option<usize>,
+    pub compression: Option<CompressionSettings>,
 }
 
 impl ChannelSettings {
@@ -13,17 +20,113 @@ impl ChannelSettings {
         if mode.tick_buffered() && direction != ChannelDirection::ClientToServer {
             panic!("TickBuffered Messages are only allowed to be sent from Client to Server");
         }
+        if let ChannelMode::ExpiringReliable(settings) = &mode {
+            if settings.overflow_strategy == OverflowStrategy::DropOldest {
+                panic!("ExpiringReliable channels cannot use OverflowStrategy::DropOldest, as it renumbers the Messages being tracked for expiry");
+            }
+        }
+
+        Self {
+            mode,
+            direction,
+            coalesce: None,
+            unresolved_entities: UnresolvedEntityPolicy::Wait,
+            send_buffer: None,
+            max_message_size: None,
+            compression: None,
+        }
+    }
+
+    /// Holds back outgoing Messages on this channel so that Messages sent over
+    /// several ticks can share a packet
+    pub fn coalesce(mut self, settings: CoalesceSettings) -> Self {
+        if self.mode.tick_buffered() {
+            panic!("TickBuffered Messages are already sent once per Tick, and cannot be coalesced");
+        }
+
+        self.coalesce = Some(settings);
+        self
+    }
+
+    /// Describes what happens to an incoming Message on this channel which
+    /// references an Entity that is not yet in scope. Only unreliable channels
+    /// may stop waiting on such Entities
+    pub fn unresolved_entities(mut self, policy: UnresolvedEntityPolicy) -> Self {
+        if policy != UnresolvedEntityPolicy::Wait
+            && !matches!(
+                self.mode,
+                ChannelMode::UnorderedUnreliable
+                    | ChannelMode::SequencedUnreliable
+                    | ChannelMode::OrderedUnreliable(_)
+            )
+        {
+            panic!("Only unreliable Messages may be received without waiting on their Entities");
+        }
 
-        Self { mode, direction }
+        self.unresolved_entities = policy;
+        self
+    }
+
+    /// Bounds how many outgoing Messages an unreliable channel holds while
+    /// waiting to be written, so that a stalled connection sheds Messages
+    /// rather than growing the buffer forever. Reliable channels are bounded
+    /// through their `ReliableSettings` instead
+    pub fn send_buffer(mut self, settings: SendBufferSettings) -> Self {
+        if !matches!(
+            self.mode,
+            ChannelMode::UnorderedUnreliable
+                | ChannelMode::SequencedUnreliable
+                | ChannelMode::OrderedUnreliable(_)
+        ) {
+            panic!("Only unreliable channels may bound their send buffer with SendBufferSettings");
+        }
+
+        self.send_buffer = Some(settings);
+        self
+    }
+
+    /// Bounds the size, in bytes, of the Messages carried over this channel.
+    /// Sending a larger Message returns an error, and incoming Messages
+    /// claiming to be larger are dropped before their fragments are buffered.
+    /// Only reliable channels fragment Messages, so only they may carry
+    /// Messages this large
+    pub fn max_message_size(mut self, bytes: usize) -> Self {
+        if !self.reliable() {
+            panic!("Only reliable channels may set a max message size");
+        }
+
+        self.max_message_size = Some(bytes);
+        self
+    }
+
+    /// Compresses the Messages this channel writes into each packet, as one
+    /// block, before they are sent. TickBuffered channels are written
+    /// separately from other channels, so cannot be compressed
+    pub fn compression(mut self, settings: CompressionSettings) -> Self {
+        if self.mode.tick_buffered() {
+            panic!("TickBuffered channels cannot be compressed");
+        }
+        if !settings.algorithm.is_supported() {
+            panic!(
+                "{:?} channel compression requires the `{}` feature",
+                settings.algorithm,
+                settings.algorithm.feature()
+            );
+        }
+
+        self.compression = Some(settings);
+        self
     }
 
     pub fn reliable(&self) -> bool {
         match &self.mode {
             ChannelMode::UnorderedUnreliable => false,
             ChannelMode::SequencedUnreliable => false,
+            ChannelMode::OrderedUnreliable(_) => false,
             ChannelMode::UnorderedReliable(_) => true,
             ChannelMode::SequencedReliable(_) => true,
             ChannelMode::OrderedReliable(_) => true,
+            ChannelMode::ExpiringReliable(_) => true,
             ChannelMode::TickBuffered(_) => false,
         }
     }
@@ -56,12 +159,140 @@ impl ChannelSettings {
 #[derive(Clone)]
 pub struct ReliableSettings {
     pub rtt_resend_factor: f32,
+    /// Describes a maximum of unacknowledged messages that may be kept in the
+    /// send buffer. When `None`, the buffer is unbounded, and will keep growing
+    /// if messages are sent faster than the remote host acknowledges them
+    pub message_capacity: Option<usize>,
+    /// Describes what happens to a message sent while the buffer is full
+    pub overflow_strategy: OverflowStrategy,
+    /// Describes a maximum of messages which may be in flight, transmitted but
+    /// not yet acknowledged, at once. Further messages wait in the send buffer
+    /// until acknowledgements free up room in the window, so pair this with
+    /// `message_capacity` to bound memory use towards a stalled remote host.
+    /// When `None`, every buffered message is transmitted immediately
+    pub send_window: Option<usize>,
 }
 
 impl ReliableSettings {
     pub const fn default() -> Self {
         Self {
             rtt_resend_factor: 1.5,
+            message_capacity: None,
+            overflow_strategy: OverflowStrategy::Block,
+            send_window: None,
+        }
+    }
+}
+
+// OverflowStrategy
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowStrategy {
+    /// The new message is refused, and sending it returns an error
+    Block,
+    /// The oldest message which has not yet been transmitted is dropped to
+    /// make room, along with any other fragments of it. Messages already in
+    /// flight, Requests and Responses are never dropped, so if there are not
+    /// enough other untransmitted messages, the new message is refused.
+    /// Messages on such a channel are renumbered as others are dropped, so
+    /// they cannot be tracked with `send_tracked_message()`
+    DropOldest,
+    /// The new message is silently dropped. Requests and Responses are
+    /// refused instead
+    DropNewest,
+}
+
+// SendBufferSettings
+#[derive(Clone, Copy, Debug)]
+pub struct SendBufferSettings {
+    /// Describes a maximum of messages that may wait in the send buffer to be
+    /// written to the wire
+    pub message_capacity: usize,
+    /// Describes what happens to a message sent while the buffer is full
+    pub overflow_strategy: OverflowStrategy,
+}
+
+impl SendBufferSettings {
+    pub const fn new(message_capacity: usize, overflow_strategy: OverflowStrategy) -> Self {
+        Self {
+            message_capacity,
+            overflow_strategy,
+        }
+    }
+}
+
+// UnresolvedEntityPolicy
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnresolvedEntityPolicy {
+    /// The Message is held until every Entity it references comes into scope
+    Wait,
+    /// The Message is delivered immediately, and any reference to an Entity
+    /// not yet in scope resolves to `None`
+    Deliver,
+    /// The Message is dropped
+    Drop,
+}
+
+// CoalesceSettings
+#[derive(Clone, Copy, Debug)]
+pub struct CoalesceSettings {
+    /// Longest time a Message may be held back while waiting for more
+    /// Messages to send alongside it
+    pub max_delay: Duration,
+    /// Once this many bits of Messages are waiting to be sent, they are
+    /// released without waiting for `max_delay` to pass
+    pub flush_bits: u32,
+}
+
+impl CoalesceSettings {
+    pub const fn default() -> Self {
+        Self {
+            max_delay: Duration::from_millis(20),
+            flush_bits: 4000,
+        }
+    }
+}
+
+// CompressionSettings
+#[derive(Clone, Copy, Debug)]
+pub struct CompressionSettings {
+    pub algorithm: CompressionAlgorithm,
+    /// Messages which take up fewer bytes than this in a packet are sent
+    /// uncompressed, as compressing them would save little, if anything
+    pub min_bytes: usize,
+}
+
+impl CompressionSettings {
+    pub const fn new(algorithm: CompressionAlgorithm) -> Self {
+        Self {
+            algorithm,
+            min_bytes: 64,
+        }
+    }
+}
+
+// CompressionAlgorithm
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompressionAlgorithm {
+    /// Fast compression, requiring the `lz4_support` feature
+    Lz4,
+    /// Smaller output than Lz4 at a higher CPU cost, requiring the
+    /// `zstd_support` feature. The i32 parameter is the compression level
+    /// from -7 (fastest) to 22 (smallest)
+    Zstd(i32),
+}
+
+impl CompressionAlgorithm {
+    pub fn is_supported(&self) -> bool {
+        match self {
+            CompressionAlgorithm::Lz4 => cfg!(feature = "lz4_support"),
+            CompressionAlgorithm::Zstd(_) => cfg!(feature = "zstd_support"),
+        }
+    }
+
+    fn feature(&self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Lz4 => "lz4_support",
+            CompressionAlgorithm::Zstd(_) => "zstd_support",
         }
     }
 }
@@ -81,14 +312,40 @@ impl TickBufferSettings {
     }
 }
 
+#[derive(Clone)]
+pub struct OrderedUnreliableSettings {
+    /// Longest time a received Message is held back while waiting for the
+    /// Messages sent before it. Once passed, the missing Messages are skipped,
+    /// and dropped should they still arrive
+    pub max_delay: Duration,
+    /// Describes a maximum of Messages the oldest undelivered Message may be
+    /// behind the newest received one. Older Messages are skipped without
+    /// waiting for `max_delay` to pass
+    pub max_messages_behind: u16,
+}
+
+impl OrderedUnreliableSettings {
+    pub const fn default() -> Self {
+        Self {
+            max_delay: Duration::from_millis(100),
+            max_messages_behind: 32,
+        }
+    }
+}
+
 // ChannelMode
 #[derive(Clone)]
 pub enum ChannelMode {
     UnorderedUnreliable,
     SequencedUnreliable,
+    OrderedUnreliable(OrderedUnreliableSettings),
     UnorderedReliable(ReliableSettings),
     SequencedReliable(ReliableSettings),
     OrderedReliable(ReliableSettings),
+    /// Unordered & reliable, except that a Message sent with a time to live
+    /// stops being retransmitted once it has passed, and is handed back to the
+    /// sender as expired
+    ExpiringReliable(ReliableSettings),
     TickBuffered(TickBufferSettings),
 }
 
@@ -0,0 +1,134 @@
+use crate::messages::channels::channel::{CompressionAlgorithm, CompressionSettings};
+
+/// Compresses the Messages a channel writes into a packet, and decompresses
+/// those read from one. Channel data never takes up more than a packet, so
+/// decompressed data larger than that is rejected
+pub struct ChannelCompressor {
+    min_bytes: usize,
+    codec: Codec,
+}
+
+impl ChannelCompressor {
+    pub fn new(settings: &CompressionSettings) -> Self {
+        Self {
+            min_bytes: settings.min_bytes,
+            codec: Codec::new(&settings.algorithm),
+        }
+    }
+
+    /// Returns the compressed form of the given bytes, or None if they fall
+    /// under the size threshold for compression
+    pub fn compress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        if bytes.len() < self.min_bytes {
+            return None;
+        }
+        self.codec.compress(bytes)
+    }
+
+    /// Returns the decompressed form of the given bytes, or None if they are
+    /// not valid compressed channel data
+    pub fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        self.codec.decompress(bytes)
+    }
+}
+
+enum Codec {
+    #[cfg(feature = "lz4_support")]
+    Lz4,
+    // zstd's reusable contexts cannot be shared between threads, so a fresh
+    // one is made for each packet
+    #[cfg(feature = "zstd_support")]
+    Zstd(i32),
+}
+
+impl Codec {
+    fn new(algorithm: &CompressionAlgorithm) -> Self {
+        match algorithm {
+            #[cfg(feature = "lz4_support")]
+            CompressionAlgorithm::Lz4 => Codec::Lz4,
+            #[cfg(feature = "zstd_support")]
+            CompressionAlgorithm::Zstd(compression_level) => Codec::Zstd(*compression_level),
+            #[allow(unreachable_patterns)]
+            _ => panic!(
+                "{:?} channel compression is not supported without its feature enabled",
+                algorithm
+            ),
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn compress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        match *self {
+            #[cfg(feature = "lz4_support")]
+            Codec::Lz4 => Some(lz4_flex::block::compress(bytes)),
+            #[cfg(feature = "zstd_support")]
+            Codec::Zstd(compression_level) => zstd::bulk::compress(bytes, compression_level).ok(),
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        match *self {
+            #[cfg(feature = "lz4_support")]
+            Codec::Lz4 => {
+                let mut output = vec![0; naia_serde::MTU_SIZE_BYTES];
+                let length = lz4_flex::block::decompress_into(bytes, &mut output).ok()?;
+                output.truncate(length);
+                Some(output)
+            }
+            #[cfg(feature = "zstd_support")]
+            Codec::Zstd(_) => zstd::bulk::decompress(bytes, naia_serde::MTU_SIZE_BYTES).ok(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lz4_support"))]
+mod tests {
+    use super::ChannelCompressor;
+    use crate::messages::channels::channel::{CompressionAlgorithm, CompressionSettings};
+
+    fn compressor() -> ChannelCompressor {
+        ChannelCompressor::new(&CompressionSettings::new(CompressionAlgorithm::Lz4))
+    }
+
+    #[test]
+    fn compressed_bytes_decompress() {
+        let bytes = "grass,water,".repeat(20).into_bytes();
+        let compressor = compressor();
+
+        let compressed = compressor.compress(&bytes).unwrap();
+        assert!(compressed.len() < bytes.len());
+        assert_eq!(compressor.decompress(&compressed), Some(bytes));
+    }
+
+    #[test]
+    fn small_bytes_are_not_compressed() {
+        assert!(compressor().compress(&[7; 63]).is_none());
+    }
+
+    #[test]
+    fn oversized_bytes_are_rejected() {
+        let bytes = vec![0; naia_serde::MTU_SIZE_BYTES + 1];
+        let compressor = compressor();
+
+        let compressed = compressor.compress(&bytes).unwrap();
+        assert!(compressor.decompress(&compressed).is_none());
+    }
+}
+
+#[cfg(all(test, feature = "zstd_support"))]
+mod zstd_tests {
+    use super::ChannelCompressor;
+    use crate::messages::channels::channel::{CompressionAlgorithm, CompressionSettings};
+
+    #[test]
+    fn compressed_bytes_decompress() {
+        let bytes = "grass,water,".repeat(20).into_bytes();
+        let compressor =
+            ChannelCompressor::new(&CompressionSettings::new(CompressionAlgorithm::Zstd(3)));
+
+        let compressed = compressor.compress(&bytes).unwrap();
+        assert!(compressed.len() < bytes.len());
+        assert_eq!(compressor.decompress(&compressed), Some(bytes));
+    }
+}
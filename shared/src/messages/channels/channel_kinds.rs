@@ -2,7 +2,10 @@ use std::{any::TypeId, collections::HashMap};
 
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
 
-use crate::messages::channels::channel::{Channel, ChannelSettings};
+use crate::messages::channels::channel::{
+    Channel, ChannelSettings, CoalesceSettings, CompressionSettings, SendBufferSettings,
+    UnresolvedEntityPolicy,
+};
 
 type NetId = u16;
 
@@ -48,6 +51,7 @@ pub struct ChannelKinds {
     current_net_id: NetId,
     kind_map: HashMap<ChannelKind, (NetId, ChannelSettings)>,
     net_id_map: HashMap<NetId, ChannelKind>,
+    name_map: HashMap<ChannelKind, String>,
 }
 
 impl ChannelKinds {
@@ -56,6 +60,7 @@ impl ChannelKinds {
             current_net_id: 0,
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
+            name_map: HashMap::new(),
         }
     }
 
@@ -65,10 +70,69 @@ impl ChannelKinds {
         let net_id = self.current_net_id;
         self.kind_map.insert(channel_kind, (net_id, settings));
         self.net_id_map.insert(net_id, channel_kind);
+        self.name_map
+            .insert(channel_kind, default_channel_name::<C>());
         self.current_net_id += 1;
         //TODO: check for current_id overflow?
     }
 
+    pub fn coalesce_channel<C: Channel>(&mut self, coalesce_settings: CoalesceSettings) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some((_, settings)) = self.kind_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before configuring it to coalesce!");
+        };
+        *settings = settings.clone().coalesce(coalesce_settings);
+    }
+
+    pub fn set_unresolved_entity_policy<C: Channel>(&mut self, policy: UnresolvedEntityPolicy) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some((_, settings)) = self.kind_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before configuring how it handles unresolved Entities!");
+        };
+        *settings = settings.clone().unresolved_entities(policy);
+    }
+
+    pub fn set_send_buffer<C: Channel>(&mut self, send_buffer_settings: SendBufferSettings) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some((_, settings)) = self.kind_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before bounding its send buffer!");
+        };
+        *settings = settings.clone().send_buffer(send_buffer_settings);
+    }
+
+    pub fn set_max_message_size<C: Channel>(&mut self, bytes: usize) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some((_, settings)) = self.kind_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before bounding its message size!");
+        };
+        *settings = settings.clone().max_message_size(bytes);
+    }
+
+    pub fn set_compression<C: Channel>(&mut self, compression_settings: CompressionSettings) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some((_, settings)) = self.kind_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before configuring it to compress!");
+        };
+        *settings = settings.clone().compression(compression_settings);
+    }
+
+    pub fn set_channel_name<C: Channel>(&mut self, name: &str) {
+        let channel_kind = ChannelKind::of::<C>();
+        let Some(channel_name) = self.name_map.get_mut(&channel_kind) else {
+            panic!("Must add Channel with `add_channel()` before naming it!");
+        };
+        *channel_name = name.to_string();
+    }
+
+    /// Gets the human-readable name of a Channel, for use in diagnostics.
+    /// Defaults to the name of the Channel's type
+    pub fn kind_to_name(&self, channel_kind: &ChannelKind) -> String {
+        self.name_map
+            .get(channel_kind)
+            .cloned()
+            .unwrap_or_else(|| format!("{:?}", channel_kind))
+    }
+
     pub fn channels(&self) -> Vec<(ChannelKind, ChannelSettings)> {
         // TODO: is there a better way to do this without copying + cloning?
         // How to return a reference here (behind a Mutex ..)
@@ -100,3 +164,12 @@ impl ChannelKinds {
             .0;
     }
 }
+
+fn default_channel_name<C: Channel>() -> String {
+    let type_name = std::any::type_name::<C>();
+    type_name
+        .rsplit("::")
+        .next()
+        .unwrap_or(type_name)
+        .to_string()
+}
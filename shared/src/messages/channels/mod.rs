@@ -1,6 +1,8 @@
 pub mod channel;
+pub mod channel_compressor;
 pub mod channel_kinds;
 pub mod default_channels;
+pub mod file_transfer_channel;
 pub mod receivers;
 pub mod senders;
 pub mod system_channel;
@@ -1,20 +1,39 @@
 use std::collections::HashMap;
 
+use log::warn;
 use naia_serde::BitReader;
 
 use crate::{
+    constants::FRAGMENTATION_LIMIT_BYTES,
     messages::fragment::{FragmentId, FragmentedMessage},
+    sequence_less_than,
+    types::MessageIndex,
     LocalEntityAndGlobalEntityConverter, MessageContainer, MessageKinds,
 };
 
+// The message index of a partial Message's last fragment, the number of
+// fragments received, and the fragments themselves
+type PartialMessage = (MessageIndex, u32, Vec<Option<Box<[u8]>>>);
+
 pub struct FragmentReceiver {
-    map: HashMap<FragmentId, (u32, Vec<Box<[u8]>>)>,
+    map: HashMap<FragmentId, PartialMessage>,
+    max_fragments: Option<usize>,
 }
 
 impl FragmentReceiver {
     pub fn new() -> Self {
+        Self::bounded(None)
+    }
+
+    /// Drops fragments of any Message which would be larger than
+    /// `max_message_size` bytes once reassembled
+    pub fn bounded(max_message_size: Option<usize>) -> Self {
         Self {
             map: HashMap::new(),
+            // the Message Kind is written ahead of the Message, so allow one
+            // more fragment than the Message itself needs
+            max_fragments: max_message_size
+                .map(|bytes| bytes.div_ceil(FRAGMENTATION_LIMIT_BYTES) + 1),
         }
     }
 
@@ -22,6 +41,7 @@ impl FragmentReceiver {
         &mut self,
         message_kinds: &MessageKinds,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        message_index: MessageIndex,
         message: MessageContainer,
     ) -> Option<MessageContainer> {
         if !message.is_fragment() {
@@ -37,27 +57,154 @@ impl FragmentReceiver {
         let fragment_index = fragment.index();
         let fragment_total = fragment.total().as_usize();
         // info!("fragment_total: {fragment_total}");
-        if !self.map.contains_key(&fragment_id) {
-            self.map
-                .insert(fragment_id, (0, vec![Box::new([]); fragment_total]));
+        if self
+            .max_fragments
+            .is_some_and(|max_fragments| fragment_total > max_fragments)
+        {
+            warn!("Dropped fragment of a Message larger than the channel's max message size");
+            return None;
+        }
+        if fragment_index.as_usize() >= fragment_total {
+            warn!("Dropped fragment with an index beyond its Message's fragment count");
+            return None;
+        }
+        // a Message's fragments are sent with consecutive message indices
+        let last_message_index = message_index
+            .wrapping_sub(fragment_index.as_usize() as u16)
+            .wrapping_add(fragment_total as u16 - 1);
+        let (_, fragments_received, fragment_list) = self
+            .map
+            .entry(fragment_id)
+            .or_insert_with(|| (last_message_index, 0, vec![None; fragment_total]));
+        // the first fragment received decides the Message's fragment count
+        if fragment_list.len() != fragment_total {
+            warn!("Dropped fragment whose fragment count differs from the rest of its Message");
+            return None;
+        }
+        let slot = &mut fragment_list[fragment_index.as_usize()];
+        if slot.is_some() {
+            warn!("Dropped duplicate fragment");
+            return None;
         }
-        let (fragments_received, fragment_list) = self.map.get_mut(&fragment_id).unwrap();
-        fragment_list[fragment_index.as_usize()] = fragment.to_payload();
+        *slot = Some(fragment.to_payload());
         *fragments_received += 1;
         if *fragments_received != fragment_total as u32 {
             return None;
         }
 
         // we have received all fragments! put it all together
-        let (_, fragment_list) = self.map.remove(&fragment_id).unwrap();
-        let concat_list = fragment_list.concat();
+        let (_, _, fragment_list) = self.map.remove(&fragment_id).unwrap();
+        let concat_list: Vec<u8> = fragment_list.into_iter().flatten().flatten().collect();
         let mut reader = BitReader::new(&concat_list);
-        let full_message_result = message_kinds.read(&mut reader, converter);
-        if full_message_result.is_err() {
-            // TODO: bubble up error instead of panicking here
-            panic!("Cannot read fragmented message!");
-        }
-        let full_message = full_message_result.unwrap();
+        let Ok(full_message) = message_kinds.read(&mut reader, converter) else {
+            warn!("Dropped fragmented Message which could not be read");
+            return None;
+        };
         Some(full_message)
     }
+
+    /// Drops partial Messages which can no longer be completed, as their
+    /// missing fragments have message indices older than `oldest_message_index`,
+    /// the oldest one still waited on
+    pub(crate) fn drop_skipped(&mut self, oldest_message_index: MessageIndex) {
+        self.map.retain(|_, (last_message_index, _, _)| {
+            !sequence_less_than(*last_message_index, oldest_message_index)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FragmentReceiver;
+    use crate::{
+        constants::FRAGMENTATION_LIMIT_BYTES,
+        messages::fragment::{FragmentId, FragmentIndex, FragmentedMessage},
+        FakeEntityConverter, MessageContainer, MessageKinds,
+    };
+
+    fn fragment(index: u32, total: u32) -> MessageContainer {
+        let mut id = FragmentId::zero();
+        id.increment();
+        let mut fragment_index = FragmentIndex::zero();
+        for _ in 0..index {
+            fragment_index.increment();
+        }
+        let mut fragment_total = FragmentIndex::zero();
+        for _ in 0..total {
+            fragment_total.increment();
+        }
+        let mut fragment = FragmentedMessage::new(id, fragment_index, Box::new([0]));
+        fragment.set_total(fragment_total);
+        MessageContainer::from_write(Box::new(fragment), &mut FakeEntityConverter)
+    }
+
+    #[test]
+    fn fragments_of_oversized_messages_are_dropped() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::bounded(Some(FRAGMENTATION_LIMIT_BYTES * 2));
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 4));
+        assert!(receiver.map.is_empty());
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 3));
+        assert_eq!(receiver.map.len(), 1);
+    }
+
+    #[test]
+    fn fragments_beyond_their_total_are_dropped() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::new();
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(2, 2));
+        assert!(receiver.map.is_empty());
+    }
+
+    #[test]
+    fn fragments_with_a_mismatched_total_are_dropped() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::new();
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 2));
+        // a larger total & index for the same Message must not grow it
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(5, 8));
+        let (_, fragments_received, fragment_list) = receiver.map.values().next().unwrap();
+        assert_eq!(*fragments_received, 1);
+        assert_eq!(fragment_list.len(), 2);
+    }
+
+    #[test]
+    fn duplicate_fragments_do_not_complete_a_message() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::new();
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 2));
+        let output = receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 2));
+        assert!(output.is_none());
+        assert_eq!(receiver.map.len(), 1);
+    }
+
+    #[test]
+    fn skipped_partial_messages_are_dropped() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::new();
+
+        // the second fragment, at message index 11, is still to come
+        receiver.receive(&message_kinds, &FakeEntityConverter, 10, fragment(0, 2));
+        receiver.drop_skipped(11);
+        assert_eq!(receiver.map.len(), 1);
+
+        receiver.drop_skipped(12);
+        assert!(receiver.map.is_empty());
+    }
+
+    #[test]
+    fn unreadable_messages_are_dropped() {
+        let message_kinds = MessageKinds::new();
+        let mut receiver = FragmentReceiver::new();
+
+        receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(0, 2));
+        let output = receiver.receive(&message_kinds, &FakeEntityConverter, 0, fragment(1, 2));
+        assert!(output.is_none());
+        assert!(receiver.map.is_empty());
+    }
 }
@@ -0,0 +1,299 @@
+use std::{collections::VecDeque, mem};
+
+use naia_serde::{BitReader, SerdeErr};
+use naia_socket_shared::Instant;
+
+use crate::messages::channels::senders::request_sender::LocalRequestId;
+use crate::{
+    messages::{
+        channels::{
+            channel::{OrderedUnreliableSettings, UnresolvedEntityPolicy},
+            receivers::{
+                channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+                indexed_message_reader::IndexedMessageReader,
+            },
+        },
+        message_kinds::MessageKinds,
+    },
+    sequence_less_than,
+    types::MessageIndex,
+    world::remote::entity_waitlist::{EntityWaitlist, WaitlistStore},
+    wrapping_diff, LocalEntityAndGlobalEntityConverter, LocalResponseId, MessageContainer,
+};
+
+/// Delivers Messages in the order they were sent, holding back any that
+/// arrive ahead of a missing Message until it arrives, it falls too far
+/// behind, or the held Message has waited longer than allowed
+pub struct OrderedUnreliableReceiver {
+    settings: OrderedUnreliableSettings,
+    /// Index of the next Message to deliver
+    next_message_index: Option<MessageIndex>,
+    /// Messages waiting to be arranged, once the time they arrived is known
+    unarranged_messages: Vec<(MessageIndex, MessageContainer)>,
+    /// Messages received ahead of a missing Message, sorted by index, along
+    /// with the time they arrived
+    held_messages: VecDeque<(MessageIndex, Instant, MessageContainer)>,
+    incoming_messages: Vec<MessageContainer>,
+    waitlist_store: WaitlistStore<(MessageIndex, MessageContainer)>,
+    unresolved_entities: UnresolvedEntityPolicy,
+}
+
+impl OrderedUnreliableReceiver {
+    pub fn new(
+        settings: &OrderedUnreliableSettings,
+        unresolved_entities: UnresolvedEntityPolicy,
+    ) -> Self {
+        Self {
+            settings: settings.clone(),
+            next_message_index: None,
+            unarranged_messages: Vec::new(),
+            held_messages: VecDeque::new(),
+            incoming_messages: Vec::new(),
+            waitlist_store: WaitlistStore::new(),
+            unresolved_entities,
+        }
+    }
+
+    pub fn buffer_message(
+        &mut self,
+        entity_waitlist: &mut EntityWaitlist,
+        message_index: MessageIndex,
+        message: MessageContainer,
+    ) {
+        if let Some(entity_set) = message.relations_waiting() {
+            match self.unresolved_entities {
+                UnresolvedEntityPolicy::Wait => {
+                    entity_waitlist.queue(
+                        &entity_set,
+                        &mut self.waitlist_store,
+                        (message_index, message),
+                    );
+                    return;
+                }
+                UnresolvedEntityPolicy::Deliver => {}
+                UnresolvedEntityPolicy::Drop => {
+                    return;
+                }
+            }
+        }
+
+        self.unarranged_messages.push((message_index, message));
+    }
+
+    /// Holds a received Message until every Message before it has been
+    /// delivered or skipped. Messages behind those already delivered, or
+    /// received twice, are dropped
+    pub fn arrange_message(
+        &mut self,
+        now: &Instant,
+        message_index: MessageIndex,
+        message: MessageContainer,
+    ) {
+        let next_message_index = *self.next_message_index.get_or_insert(message_index);
+        if sequence_less_than(message_index, next_message_index) {
+            return;
+        }
+
+        let position = self
+            .held_messages
+            .iter()
+            .position(|(held_index, _, _)| !sequence_less_than(*held_index, message_index))
+            .unwrap_or(self.held_messages.len());
+        if let Some((held_index, _, _)) = self.held_messages.get(position) {
+            if *held_index == message_index {
+                return;
+            }
+        }
+        self.held_messages
+            .insert(position, (message_index, now.clone(), message));
+    }
+
+    /// Moves every Message which is next in order, or has waited long enough,
+    /// to be delivered
+    pub fn release_messages(&mut self, now: &Instant) {
+        let Some(mut next_message_index) = self.next_message_index else {
+            return;
+        };
+
+        // skip ahead so that the oldest Message is not too far behind
+        if let Some((newest_index, _, _)) = self.held_messages.back() {
+            let behind = wrapping_diff(next_message_index, *newest_index);
+            if behind > self.settings.max_messages_behind as i16 {
+                next_message_index = newest_index.wrapping_sub(self.settings.max_messages_behind);
+                while let Some((held_index, _, _)) = self.held_messages.front() {
+                    if !sequence_less_than(*held_index, next_message_index) {
+                        break;
+                    }
+                    self.held_messages.pop_front();
+                }
+            }
+        }
+
+        while let Some((held_index, arrived, _)) = self.held_messages.front() {
+            if *held_index != next_message_index {
+                if arrived.elapsed(now) < self.settings.max_delay {
+                    break;
+                }
+                // the Messages before this one are stale, skip them
+                next_message_index = *held_index;
+            }
+
+            let (_, _, message) = self.held_messages.pop_front().unwrap();
+            self.incoming_messages.push(message);
+            next_message_index = next_message_index.wrapping_add(1);
+        }
+
+        self.next_message_index = Some(next_message_index);
+    }
+}
+
+impl ChannelReceiver<MessageContainer> for OrderedUnreliableReceiver {
+    fn receive_messages(
+        &mut self,
+        _message_kinds: &MessageKinds,
+        now: &Instant,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Vec<MessageContainer> {
+        if let Some(list) = entity_waitlist.collect_ready_items(now, &mut self.waitlist_store) {
+            for (message_index, mut message) in list {
+                message.relations_complete(converter);
+                self.unarranged_messages.push((message_index, message));
+            }
+        }
+
+        for (message_index, message) in mem::take(&mut self.unarranged_messages) {
+            self.arrange_message(now, message_index, message);
+        }
+        self.release_messages(now);
+
+        mem::take(&mut self.incoming_messages)
+    }
+}
+
+impl MessageChannelReceiver for OrderedUnreliableReceiver {
+    /// Read messages and add them to the buffer, to be arranged in order once
+    /// received
+    fn read_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        let id_w_msgs = IndexedMessageReader::read_messages(message_kinds, converter, reader)?;
+        for (id, message) in id_w_msgs {
+            self.buffer_message(entity_waitlist, id, message);
+        }
+        Ok(())
+    }
+
+    fn receive_requests_and_responses(
+        &mut self,
+    ) -> (
+        Vec<(LocalResponseId, MessageContainer)>,
+        Vec<(LocalRequestId, MessageContainer)>,
+    ) {
+        panic!("OrderedUnreliable channels do not support requests");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use naia_socket_shared::Instant;
+
+    use super::OrderedUnreliableReceiver;
+    use crate::messages::channels::{
+        channel::{OrderedUnreliableSettings, UnresolvedEntityPolicy},
+        senders::unordered_unreliable_sender::tests::{number_message, number_value},
+    };
+
+    fn receiver(max_delay_millis: u64, max_messages_behind: u16) -> OrderedUnreliableReceiver {
+        OrderedUnreliableReceiver::new(
+            &OrderedUnreliableSettings {
+                max_delay: Duration::from_millis(max_delay_millis),
+                max_messages_behind,
+            },
+            UnresolvedEntityPolicy::Wait,
+        )
+    }
+
+    fn receive(receiver: &mut OrderedUnreliableReceiver, now: &Instant, indices: &[u16]) {
+        for index in indices {
+            receiver.arrange_message(now, *index, number_message(*index as u8));
+        }
+        receiver.release_messages(now);
+    }
+
+    fn delivered(receiver: &mut OrderedUnreliableReceiver) -> Vec<u8> {
+        receiver
+            .incoming_messages
+            .drain(..)
+            .map(|message| number_value(&message))
+            .collect()
+    }
+
+    #[test]
+    fn messages_are_delivered_in_order() {
+        let mut receiver = receiver(100, 32);
+        let now = Instant::now();
+
+        receive(&mut receiver, &now, &[0, 2, 3]);
+        assert_eq!(delivered(&mut receiver), vec![0]);
+
+        receive(&mut receiver, &now, &[1, 2]);
+        assert_eq!(delivered(&mut receiver), vec![1, 2, 3]);
+
+        // already delivered
+        receive(&mut receiver, &now, &[1]);
+        assert!(delivered(&mut receiver).is_empty());
+    }
+
+    #[test]
+    fn missing_messages_are_skipped_after_max_delay() {
+        let mut receiver = receiver(100, 32);
+        let mut now = Instant::now();
+
+        receive(&mut receiver, &now, &[0, 2, 4]);
+        assert_eq!(delivered(&mut receiver), vec![0]);
+
+        now.add_millis(50);
+        receive(&mut receiver, &now, &[]);
+        assert!(delivered(&mut receiver).is_empty());
+
+        now.add_millis(50);
+        receive(&mut receiver, &now, &[]);
+        assert_eq!(delivered(&mut receiver), vec![2, 4]);
+
+        // stale, arriving after it was skipped
+        receive(&mut receiver, &now, &[1, 3, 5]);
+        assert_eq!(delivered(&mut receiver), vec![5]);
+    }
+
+    #[test]
+    fn messages_too_far_behind_are_skipped() {
+        let mut receiver = receiver(100, 2);
+        let now = Instant::now();
+
+        receive(&mut receiver, &now, &[0]);
+        receive(&mut receiver, &now, &[2, 3]);
+        assert_eq!(delivered(&mut receiver), vec![0]);
+
+        receive(&mut receiver, &now, &[4]);
+        assert_eq!(delivered(&mut receiver), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn message_indices_wrap_around() {
+        let mut receiver = receiver(100, 32);
+        let now = Instant::now();
+
+        receive(&mut receiver, &now, &[65534, 0]);
+        assert_eq!(delivered(&mut receiver), vec![254]);
+
+        receive(&mut receiver, &now, &[65535]);
+        assert_eq!(delivered(&mut receiver), vec![255, 0]);
+    }
+}
@@ -55,17 +55,32 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         }
     }
 
+    /// Drops incoming Messages which would be larger than `max_message_size`
+    /// bytes once their fragments are reassembled
+    pub fn with_max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.fragment_receiver = FragmentReceiver::bounded(max_message_size);
+        self
+    }
+
+    /// Gives up on Messages still missing `max_gap` Messages behind the newest
+    /// one received, for channels whose sender may stop retransmitting them
+    pub fn skipping_gaps(mut self, max_gap: u16) -> Self {
+        self.reliable_receiver = ReliableReceiver::skipping_gaps(max_gap);
+        self
+    }
+
     fn push_message(
         &mut self,
         message_kinds: &MessageKinds,
         entity_waitlist: &mut EntityWaitlist,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        message_index: MessageIndex,
         message: MessageContainer,
     ) {
         let Some(full_message) = ({
             if message.is_fragment() {
                 self.fragment_receiver
-                    .receive(message_kinds, converter, message)
+                    .receive(message_kinds, converter, message_index, message)
             } else {
                 Some(message)
             }
@@ -105,9 +120,18 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         self.reliable_receiver
             .buffer_message(message_index, message);
         let received_messages = self.reliable_receiver.receive_messages();
-        for (_, received_message) in received_messages {
-            self.push_message(message_kinds, entity_waitlist, converter, received_message)
+        for (received_index, received_message) in received_messages {
+            self.push_message(
+                message_kinds,
+                entity_waitlist,
+                converter,
+                received_index,
+                received_message,
+            )
         }
+        // fragments skipped past will never arrive
+        self.fragment_receiver
+            .drop_skipped(self.reliable_receiver.oldest_waited_index());
     }
 
     fn receive_message(
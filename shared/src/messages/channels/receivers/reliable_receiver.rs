@@ -6,6 +6,10 @@ pub struct ReliableReceiver<M> {
     oldest_received_message_index: MessageIndex,
     record: VecDeque<(MessageIndex, bool)>,
     incoming_messages: Vec<(MessageIndex, M)>,
+    /// When set, messages still missing this many messages behind the newest
+    /// one received are given up on, as the sender may have stopped
+    /// retransmitting them
+    max_gap: Option<u16>,
 }
 
 impl<M> ReliableReceiver<M> {
@@ -14,59 +18,55 @@ impl<M> ReliableReceiver<M> {
             oldest_received_message_index: 0,
             record: VecDeque::default(),
             incoming_messages: Vec::default(),
+            max_gap: None,
+        }
+    }
+
+    pub fn skipping_gaps(max_gap: u16) -> Self {
+        Self {
+            max_gap: Some(max_gap),
+            ..Self::new()
         }
     }
 
     pub(crate) fn buffer_message(&mut self, message_index: MessageIndex, message: M) {
-        // moving from oldest incoming message to newest
-        // compare existing slots and see if the message_index has been instantiated
-        // already if it has, put the message into the slot
-        // otherwise, keep track of what the last message id was
-        // then add new empty slots at the end until getting to the incoming message id
-        // then, once you're there, put the new message in
+        // the record holds a slot for every message index from the oldest one
+        // not yet received, so find the incoming message's slot, adding empty
+        // slots at the end until getting to it
 
         if sequence_less_than(message_index, self.oldest_received_message_index) {
             // already moved sliding window past this message id
             return;
         }
 
-        let mut current_index = 0;
+        let slot = message_index.wrapping_sub(self.oldest_received_message_index) as usize;
+        while self.record.len() <= slot {
+            let next_message_index = self
+                .oldest_received_message_index
+                .wrapping_add(self.record.len() as u16);
+            self.record.push_back((next_message_index, false));
+        }
 
-        loop {
-            let mut should_push_message = false;
-            if current_index < self.record.len() {
-                if let Some((old_message_index, old_message)) = self.record.get_mut(current_index) {
-                    if *old_message_index == message_index {
-                        if !(*old_message) {
-                            *old_message = true;
-                            should_push_message = true;
-                        } else {
-                            // already received this message
-                            return;
-                        }
-                    }
-                }
-            } else {
-                let next_message_index = self
-                    .oldest_received_message_index
-                    .wrapping_add(current_index as u16);
-
-                if next_message_index == message_index {
-                    self.record.push_back((next_message_index, true));
-                    should_push_message = true;
-                } else {
-                    self.record.push_back((next_message_index, false));
-                    // keep filling up buffer
-                }
-            }
+        let (_, received) = self.record.get_mut(slot).unwrap();
+        if *received {
+            // already received this message
+            return;
+        }
+        *received = true;
 
-            if should_push_message {
-                self.incoming_messages.push((message_index, message));
-                self.clear_old_messages();
-                return;
-            }
+        self.incoming_messages.push((message_index, message));
+        self.skip_gaps();
+        self.clear_old_messages();
+    }
 
-            current_index += 1;
+    // Gives up on the oldest missing messages once they are too far behind
+    fn skip_gaps(&mut self) {
+        let Some(max_gap) = self.max_gap else {
+            return;
+        };
+        while self.record.len() > max_gap as usize {
+            self.record.pop_front();
+            self.oldest_received_message_index = self.oldest_received_message_index.wrapping_add(1);
         }
     }
 
@@ -87,7 +87,50 @@ impl<M> ReliableReceiver<M> {
         }
     }
 
+    /// The oldest message index still waited on. Messages older than it are
+    /// dropped if they arrive
+    pub(crate) fn oldest_waited_index(&self) -> MessageIndex {
+        self.oldest_received_message_index
+    }
+
     pub(crate) fn receive_messages(&mut self) -> Vec<(MessageIndex, M)> {
         std::mem::take(&mut self.incoming_messages)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ReliableReceiver;
+
+    fn received(receiver: &mut ReliableReceiver<u8>) -> Vec<u16> {
+        receiver
+            .receive_messages()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_and_old_messages_are_dropped() {
+        let mut receiver = ReliableReceiver::new();
+        for index in [1, 0, 1, 2, 0] {
+            receiver.buffer_message(index, 0);
+        }
+
+        assert_eq!(received(&mut receiver), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn missing_messages_are_skipped_past_max_gap() {
+        let mut receiver = ReliableReceiver::skipping_gaps(2);
+        for index in [1, 2, 3] {
+            receiver.buffer_message(index, 0);
+        }
+        assert_eq!(received(&mut receiver), vec![1, 2, 3]);
+        assert!(receiver.record.is_empty());
+
+        // message 0 arrives too late, and is dropped
+        receiver.buffer_message(0, 0);
+        assert!(received(&mut receiver).is_empty());
+    }
+}
@@ -6,9 +6,12 @@ use naia_socket_shared::Instant;
 use crate::messages::channels::senders::request_sender::LocalRequestId;
 use crate::{
     messages::{
-        channels::receivers::{
-            channel_receiver::{ChannelReceiver, MessageChannelReceiver},
-            indexed_message_reader::IndexedMessageReader,
+        channels::{
+            channel::UnresolvedEntityPolicy,
+            receivers::{
+                channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+                indexed_message_reader::IndexedMessageReader,
+            },
         },
         message_kinds::MessageKinds,
     },
@@ -22,14 +25,16 @@ pub struct SequencedUnreliableReceiver {
     newest_received_message_index: Option<MessageIndex>,
     incoming_messages: Vec<MessageContainer>,
     waitlist_store: WaitlistStore<(MessageIndex, MessageContainer)>,
+    unresolved_entities: UnresolvedEntityPolicy,
 }
 
 impl SequencedUnreliableReceiver {
-    pub fn new() -> Self {
+    pub fn new(unresolved_entities: UnresolvedEntityPolicy) -> Self {
         Self {
             newest_received_message_index: None,
             incoming_messages: Vec::new(),
             waitlist_store: WaitlistStore::new(),
+            unresolved_entities,
         }
     }
 
@@ -40,12 +45,20 @@ impl SequencedUnreliableReceiver {
         message: MessageContainer,
     ) {
         if let Some(entity_set) = message.relations_waiting() {
-            entity_waitlist.queue(
-                &entity_set,
-                &mut self.waitlist_store,
-                (message_index, message),
-            );
-            return;
+            match self.unresolved_entities {
+                UnresolvedEntityPolicy::Wait => {
+                    entity_waitlist.queue(
+                        &entity_set,
+                        &mut self.waitlist_store,
+                        (message_index, message),
+                    );
+                    return;
+                }
+                UnresolvedEntityPolicy::Deliver => {}
+                UnresolvedEntityPolicy::Drop => {
+                    return;
+                }
+            }
         }
 
         self.arrange_message(message_index, message);
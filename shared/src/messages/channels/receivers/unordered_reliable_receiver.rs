@@ -6,12 +6,23 @@ use crate::{
     MessageContainer,
 };
 
+// How far behind the newest received Message an expired Message may be
+// before it is no longer waited on. Messages further apart than half the
+// index range could no longer be told apart anyway
+const EXPIRED_MESSAGE_GAP: u16 = u16::MAX / 4;
+
 pub type UnorderedReliableReceiver = ReliableMessageReceiver<UnorderedArranger>;
 
 impl UnorderedReliableReceiver {
     pub fn new() -> Self {
         Self::with_arranger(UnorderedArranger)
     }
+
+    /// Receives an ExpiringReliable channel, whose sender stops
+    /// retransmitting Messages once they expire
+    pub fn expiring() -> Self {
+        Self::new().skipping_gaps(EXPIRED_MESSAGE_GAP)
+    }
 }
 
 // UnorderedArranger
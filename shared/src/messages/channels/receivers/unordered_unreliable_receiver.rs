@@ -6,7 +6,10 @@ use naia_socket_shared::Instant;
 use crate::messages::channels::senders::request_sender::LocalRequestId;
 use crate::{
     messages::{
-        channels::receivers::channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+        channels::{
+            channel::UnresolvedEntityPolicy,
+            receivers::channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+        },
         message_kinds::MessageKinds,
     },
     world::remote::entity_waitlist::{EntityWaitlist, WaitlistStore},
@@ -16,13 +19,15 @@ use crate::{
 pub struct UnorderedUnreliableReceiver {
     incoming_messages: VecDeque<MessageContainer>,
     waitlist_store: WaitlistStore<MessageContainer>,
+    unresolved_entities: UnresolvedEntityPolicy,
 }
 
 impl UnorderedUnreliableReceiver {
-    pub fn new() -> Self {
+    pub fn new(unresolved_entities: UnresolvedEntityPolicy) -> Self {
         Self {
             incoming_messages: VecDeque::new(),
             waitlist_store: WaitlistStore::new(),
+            unresolved_entities,
         }
     }
 
@@ -38,8 +43,16 @@ impl UnorderedUnreliableReceiver {
 
     fn recv_message(&mut self, entity_waitlist: &mut EntityWaitlist, message: MessageContainer) {
         if let Some(entity_set) = message.relations_waiting() {
-            entity_waitlist.queue(&entity_set, &mut self.waitlist_store, message);
-            return;
+            match self.unresolved_entities {
+                UnresolvedEntityPolicy::Wait => {
+                    entity_waitlist.queue(&entity_set, &mut self.waitlist_store, message);
+                    return;
+                }
+                UnresolvedEntityPolicy::Deliver => {}
+                UnresolvedEntityPolicy::Drop => {
+                    return;
+                }
+            }
         }
 
         self.incoming_messages.push_back(message);
@@ -95,3 +108,192 @@ impl MessageChannelReceiver for UnorderedUnreliableReceiver {
         panic!("UnorderedUnreliable channels do not support requests");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use naia_derive::MessageInternal;
+    use naia_serde::{BitReader, BitWriter, Serde};
+    use naia_socket_shared::Instant;
+
+    use super::UnorderedUnreliableReceiver;
+    use crate::{
+        messages::{
+            channels::{
+                channel::UnresolvedEntityPolicy,
+                receivers::channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+                senders::unordered_unreliable_sender::tests::{number_value, NumberMessage},
+            },
+            message_kinds::{MessageKind, MessageKinds},
+        },
+        world::remote::entity_waitlist::EntityWaitlist,
+        EntityDoesNotExistError, EntityProperty, FakeEntityConverter, GlobalEntity, HostEntity,
+        LocalEntityAndGlobalEntityConverter, OwnedLocalEntity, RemoteEntity,
+    };
+
+    #[derive(MessageInternal)]
+    pub struct TargetMessage {
+        pub target: EntityProperty,
+    }
+
+    // A receiver which has no Entities in scope yet
+    struct NoEntities;
+
+    impl LocalEntityAndGlobalEntityConverter for NoEntities {
+        fn global_entity_to_host_entity(
+            &self,
+            _: &GlobalEntity,
+        ) -> Result<HostEntity, EntityDoesNotExistError> {
+            Err(EntityDoesNotExistError)
+        }
+
+        fn global_entity_to_remote_entity(
+            &self,
+            _: &GlobalEntity,
+        ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+            Err(EntityDoesNotExistError)
+        }
+
+        fn global_entity_to_owned_entity(
+            &self,
+            _: &GlobalEntity,
+        ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+            Err(EntityDoesNotExistError)
+        }
+
+        fn host_entity_to_global_entity(
+            &self,
+            _: &HostEntity,
+        ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+            Err(EntityDoesNotExistError)
+        }
+
+        fn remote_entity_to_global_entity(
+            &self,
+            _: &RemoteEntity,
+        ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+            Err(EntityDoesNotExistError)
+        }
+    }
+
+    fn message_kinds() -> MessageKinds {
+        let mut message_kinds = MessageKinds::new();
+        message_kinds.add_message::<NumberMessage>();
+        message_kinds.add_message::<TargetMessage>();
+        message_kinds
+    }
+
+    // The channel's part of a packet, holding a NumberMessage with each value,
+    // then a TargetMessage for each of the sender's Entities
+    fn channel_bytes(message_kinds: &MessageKinds, values: &[u8], targets: &[u16]) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        for value in values {
+            true.ser(&mut writer);
+            MessageKind::of::<NumberMessage>().ser(message_kinds, &mut writer);
+            value.ser(&mut writer);
+        }
+        for target in targets {
+            true.ser(&mut writer);
+            MessageKind::of::<TargetMessage>().ser(message_kinds, &mut writer);
+            true.ser(&mut writer);
+            // the sender's own Entity, so remote to the receiver
+            OwnedLocalEntity::Remote(*target).ser(&mut writer);
+        }
+        false.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    fn read(
+        receiver: &mut UnorderedUnreliableReceiver,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        bytes: &[u8],
+    ) {
+        receiver
+            .read_messages(
+                message_kinds,
+                entity_waitlist,
+                &NoEntities,
+                &mut BitReader::new(bytes),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn messages_without_entities_are_delivered_immediately() {
+        let message_kinds = message_kinds();
+        let mut entity_waitlist = EntityWaitlist::new(None);
+        let mut receiver = UnorderedUnreliableReceiver::new(UnresolvedEntityPolicy::Wait);
+
+        let bytes = channel_bytes(&message_kinds, &[1, 2], &[]);
+        read(&mut receiver, &message_kinds, &mut entity_waitlist, &bytes);
+
+        let delivered: Vec<u8> = receiver
+            .receive_messages(
+                &message_kinds,
+                &Instant::now(),
+                &mut entity_waitlist,
+                &FakeEntityConverter,
+            )
+            .iter()
+            .map(number_value)
+            .collect();
+        assert_eq!(delivered, vec![1, 2]);
+    }
+
+    #[test]
+    fn messages_wait_for_their_entities() {
+        let message_kinds = message_kinds();
+        let mut entity_waitlist = EntityWaitlist::new(None);
+        let mut receiver = UnorderedUnreliableReceiver::new(UnresolvedEntityPolicy::Wait);
+
+        let bytes = channel_bytes(&message_kinds, &[1], &[7]);
+        read(&mut receiver, &message_kinds, &mut entity_waitlist, &bytes);
+
+        let now = Instant::now();
+        let delivered = receiver.receive_messages(
+            &message_kinds,
+            &now,
+            &mut entity_waitlist,
+            &FakeEntityConverter,
+        );
+        assert_eq!(
+            delivered.iter().map(number_value).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(entity_waitlist.waiting_entries().len(), 1);
+
+        entity_waitlist.add_entity(&RemoteEntity::new(7));
+        let delivered = receiver.receive_messages(
+            &message_kinds,
+            &now,
+            &mut entity_waitlist,
+            &FakeEntityConverter,
+        );
+        assert_eq!(delivered.len(), 1);
+        assert!(delivered[0].relations_waiting().is_none());
+    }
+
+    #[test]
+    fn unresolved_entity_policy_delivers_or_drops_waiting_messages() {
+        let message_kinds = message_kinds();
+        let bytes = channel_bytes(&message_kinds, &[], &[7]);
+
+        for (policy, delivered_count) in [
+            (UnresolvedEntityPolicy::Deliver, 1),
+            (UnresolvedEntityPolicy::Drop, 0),
+        ] {
+            let mut entity_waitlist = EntityWaitlist::new(None);
+            let mut receiver = UnorderedUnreliableReceiver::new(policy);
+            read(&mut receiver, &message_kinds, &mut entity_waitlist, &bytes);
+
+            let delivered = receiver.receive_messages(
+                &message_kinds,
+                &Instant::now(),
+                &mut entity_waitlist,
+                &FakeEntityConverter,
+            );
+            assert_eq!(delivered.len(), delivered_count);
+            assert!(entity_waitlist.waiting_entries().is_empty());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::{error::Error, fmt, time::Duration};
+
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
 
@@ -9,6 +11,47 @@ use crate::{
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
 };
 
+/// Returned when a Message is sent over a channel whose send buffer is full
+#[derive(Debug)]
+pub struct ChannelFullError;
+impl Error for ChannelFullError {}
+impl std::fmt::Display for ChannelFullError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "Error while attempting to send a Message: the channel's send buffer is full"
+        )
+    }
+}
+
+/// Returned when a Message cannot be queued to be sent
+#[derive(Debug)]
+pub enum MessageSendError {
+    /// The channel's send buffer is full, see
+    /// `ReliableSettings::message_capacity`
+    ChannelFull,
+    /// The Message is larger than the channel's max message size, see
+    /// `Protocol::set_max_message_size()`
+    MessageTooLarge,
+}
+impl Error for MessageSendError {}
+impl std::fmt::Display for MessageSendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::ChannelFull => fmt::Display::fmt(&ChannelFullError, f),
+            Self::MessageTooLarge => write!(
+                f,
+                "Error while attempting to send a Message: the Message is larger than the channel's max message size"
+            ),
+        }
+    }
+}
+impl From<ChannelFullError> for MessageSendError {
+    fn from(_: ChannelFullError) -> Self {
+        Self::ChannelFull
+    }
+}
+
 pub trait ChannelSender<P>: Send + Sync {
     /// Queues a Message to be transmitted to the remote host into an internal buffer
     fn send_message(&mut self, message: P);
@@ -16,6 +59,9 @@ pub trait ChannelSender<P>: Send + Sync {
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32);
     /// Returns true if there are queued Messages ready to be written
     fn has_messages(&self) -> bool;
+    /// Returns how many Messages are waiting to be sent or, on reliable
+    /// channels, to be acknowledged
+    fn queued_count(&self) -> usize;
     /// Called when it receives acknowledgement that a Message has been received
     fn notify_message_delivered(&mut self, message_index: &MessageIndex);
 }
@@ -30,13 +76,21 @@ pub trait MessageChannelSender: ChannelSender<MessageContainer> {
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>>;
 
-    /// Queues a Request to be transmitted to the remote host into an internal buffer
+    /// Makes room in the internal buffer for `count` more Messages. Returns
+    /// Ok(true) if they should be queued, or Ok(false) if they should be
+    /// silently dropped
+    fn reserve(&mut self, count: usize) -> Result<bool, ChannelFullError>;
+
+    /// Queues a Request to be transmitted to the remote host into an internal
+    /// buffer. If no Response arrives within `timeout`, the Request is
+    /// returned by `take_timed_out_requests()`
     fn send_outgoing_request(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         global_request_id: GlobalRequestId,
         request: MessageContainer,
+        timeout: Option<Duration>,
     );
 
     /// Queues a Response to be transmitted to the remote host into an internal buffer
@@ -53,4 +107,44 @@ pub trait MessageChannelSender: ChannelSender<MessageContainer> {
         &mut self,
         local_request_id: &LocalRequestId,
     ) -> Option<GlobalRequestId>;
+
+    /// Returns the Requests sent over this channel which have not yet received
+    /// a Response, along with the time each was sent
+    fn pending_requests(&self) -> Vec<(LocalRequestId, GlobalRequestId, Instant)>;
+
+    /// Stops waiting for a Response to a Request, freeing its local id, and
+    /// stops retransmitting it where the channel allows. Returns whether the
+    /// Request was awaiting a Response
+    fn cancel_request(&mut self, global_request_id: &GlobalRequestId) -> bool;
+
+    /// Returns the Requests which received no Response within their timeout
+    fn take_timed_out_requests(&mut self) -> Vec<GlobalRequestId>;
+
+    /// Returns the total bit length of the Messages waiting to be written
+    fn outgoing_bit_length(&self) -> u32;
+
+    /// Returns the number of Messages held in the send buffer. For reliable
+    /// channels, this includes Messages which have not yet been acknowledged
+    fn queued_len(&self) -> usize;
+
+    /// Returns the index of the most recently queued Message, if this channel
+    /// tracks the delivery of individual Messages
+    fn last_message_index(&self) -> Option<MessageIndex>;
+
+    /// Returns whether the Message with the given index, and every Message
+    /// queued before it, has been delivered to the remote host
+    fn is_delivered(&self, message_index: &MessageIndex) -> bool;
+
+    /// Stops retransmitting the last `message_count` Messages queued, which
+    /// together make up `message`, if they are not all delivered within `ttl`.
+    /// `message` is then returned by `take_expired_messages()`
+    fn expire_messages_after(
+        &mut self,
+        message_count: usize,
+        ttl: Duration,
+        message: MessageContainer,
+    );
+
+    /// Returns the Messages which expired before being delivered
+    fn take_expired_messages(&mut self) -> Vec<MessageContainer>;
 }
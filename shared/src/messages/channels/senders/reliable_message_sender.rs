@@ -1,3 +1,5 @@
+use std::{mem, time::Duration};
+
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
 
@@ -5,9 +7,12 @@ use crate::messages::channels::senders::request_sender::{LocalRequestId, Request
 use crate::messages::request::GlobalRequestId;
 use crate::{
     messages::{
-        channels::senders::{
-            channel_sender::{ChannelSender, MessageChannelSender},
-            indexed_message_writer::IndexedMessageWriter,
+        channels::{
+            channel::{OverflowStrategy, ReliableSettings},
+            senders::{
+                channel_sender::{ChannelFullError, ChannelSender, MessageChannelSender},
+                indexed_message_writer::IndexedMessageWriter,
+            },
         },
         message_container::MessageContainer,
         message_kinds::MessageKinds,
@@ -16,17 +21,94 @@ use crate::{
     LocalEntityAndGlobalEntityConverterMut, LocalResponseId, ReliableSender,
 };
 
+// A Message, possibly split into fragments, which is no longer sent once its
+// time to live has passed
+struct ExpiringMessage {
+    first_index: MessageIndex,
+    message_count: usize,
+    sent_at: Instant,
+    ttl: Duration,
+    message: MessageContainer,
+}
+
 // Sender
 pub struct ReliableMessageSender {
     reliable_sender: ReliableSender<MessageContainer>,
     request_sender: RequestSender,
+    expiring_messages: Vec<ExpiringMessage>,
+    expired_messages: Vec<MessageContainer>,
+    timed_out_requests: Vec<GlobalRequestId>,
+    /// Whether the remote host skips past Messages which are never delivered,
+    /// so that a Message may stop being retransmitted without holding up
+    /// every Message queued after it
+    can_skip_messages: bool,
+    /// Whether Messages keep the index they were queued with. Dropping the
+    /// oldest Messages renumbers those queued after them, so their indices
+    /// cannot be handed out to track delivery
+    keeps_message_indices: bool,
 }
 
 impl ReliableMessageSender {
-    pub fn new(rtt_resend_factor: f32) -> Self {
+    pub fn new(settings: &ReliableSettings) -> Self {
         Self {
-            reliable_sender: ReliableSender::new(rtt_resend_factor),
+            reliable_sender: ReliableSender::bounded(
+                settings.rtt_resend_factor,
+                settings.message_capacity,
+                settings.overflow_strategy,
+            )
+            .with_send_window(settings.send_window),
             request_sender: RequestSender::new(),
+            expiring_messages: Vec::new(),
+            expired_messages: Vec::new(),
+            timed_out_requests: Vec::new(),
+            can_skip_messages: false,
+            keeps_message_indices: settings.overflow_strategy != OverflowStrategy::DropOldest,
+        }
+    }
+
+    /// Creates a sender for an ExpiringReliable channel, whose receiver skips
+    /// past Messages which are never delivered
+    pub fn expiring(settings: &ReliableSettings) -> Self {
+        Self {
+            can_skip_messages: true,
+            ..Self::new(settings)
+        }
+    }
+
+    // Stops retransmitting Messages whose time to live has passed before they
+    // were delivered
+    fn expire_messages(&mut self, now: &Instant) {
+        for expiring in mem::take(&mut self.expiring_messages) {
+            if expiring.sent_at.elapsed(now) < expiring.ttl {
+                self.expiring_messages.push(expiring);
+                continue;
+            }
+
+            let mut undelivered = false;
+            for offset in 0..expiring.message_count {
+                let message_index = expiring.first_index.wrapping_add(offset as MessageIndex);
+                if self.reliable_sender.expire_message(&message_index) {
+                    undelivered = true;
+                }
+            }
+            if undelivered {
+                self.expired_messages.push(expiring.message);
+            }
+        }
+    }
+
+    fn time_out_requests(&mut self, now: &Instant) {
+        for (global_request_id, message_index) in self.request_sender.take_timed_out_requests(now) {
+            self.stop_sending_request(&message_index);
+            self.timed_out_requests.push(global_request_id);
+        }
+    }
+
+    // Requests which are no longer wanted are still delivered if the remote
+    // host would otherwise wait on them forever
+    fn stop_sending_request(&mut self, message_index: &MessageIndex) {
+        if self.can_skip_messages {
+            self.reliable_sender.expire_message(message_index);
         }
     }
 }
@@ -37,6 +119,8 @@ impl ChannelSender<MessageContainer> for ReliableMessageSender {
     }
 
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.expire_messages(now);
+        self.time_out_requests(now);
         self.reliable_sender.collect_messages(now, rtt_millis);
     }
 
@@ -44,6 +128,10 @@ impl ChannelSender<MessageContainer> for ReliableMessageSender {
         self.reliable_sender.has_messages()
     }
 
+    fn queued_count(&self) -> usize {
+        self.reliable_sender.queued_count()
+    }
+
     fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
         self.reliable_sender.notify_message_delivered(message_index);
     }
@@ -66,20 +154,34 @@ impl MessageChannelSender for ReliableMessageSender {
         )
     }
 
+    fn reserve(&mut self, count: usize) -> Result<bool, ChannelFullError> {
+        let reserved = self.reliable_sender.reserve(count)?;
+        for (old_index, new_index) in self.reliable_sender.take_renumbered_messages() {
+            self.request_sender.renumber_request(&old_index, new_index);
+        }
+        Ok(reserved)
+    }
+
     fn send_outgoing_request(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         global_request_id: GlobalRequestId,
         request: MessageContainer,
+        timeout: Option<Duration>,
     ) {
+        let message_index = self.reliable_sender.last_message_index().wrapping_add(1);
         let processed_request = self.request_sender.process_outgoing_request(
             message_kinds,
             converter,
             global_request_id,
             request,
+            message_index,
+            timeout,
         );
         self.send_message(processed_request);
+        // the remote host must receive the Request to ever answer it
+        self.reliable_sender.keep_last_message();
     }
 
     fn send_outgoing_response(
@@ -96,6 +198,8 @@ impl MessageChannelSender for ReliableMessageSender {
             response,
         );
         self.send_message(processed_response);
+        // the remote host is waiting on it
+        self.reliable_sender.keep_last_message();
     }
 
     fn process_incoming_response(
@@ -105,4 +209,203 @@ impl MessageChannelSender for ReliableMessageSender {
         self.request_sender
             .process_incoming_response(local_request_id)
     }
+
+    fn pending_requests(&self) -> Vec<(LocalRequestId, GlobalRequestId, Instant)> {
+        self.request_sender
+            .pending_requests()
+            .map(|(local_id, global_id, sent_at)| (*local_id, *global_id, sent_at.clone()))
+            .collect()
+    }
+
+    fn cancel_request(&mut self, global_request_id: &GlobalRequestId) -> bool {
+        let Some(message_index) = self.request_sender.cancel_request(global_request_id) else {
+            return false;
+        };
+        self.stop_sending_request(&message_index);
+        true
+    }
+
+    fn take_timed_out_requests(&mut self) -> Vec<GlobalRequestId> {
+        mem::take(&mut self.timed_out_requests)
+    }
+
+    fn outgoing_bit_length(&self) -> u32 {
+        self.reliable_sender
+            .outgoing_messages
+            .iter()
+            .map(|(_, message)| message.bit_length())
+            .sum()
+    }
+
+    fn queued_len(&self) -> usize {
+        self.reliable_sender.buffered_count()
+    }
+
+    fn last_message_index(&self) -> Option<MessageIndex> {
+        if !self.keeps_message_indices {
+            return None;
+        }
+        Some(self.reliable_sender.last_message_index())
+    }
+
+    fn is_delivered(&self, message_index: &MessageIndex) -> bool {
+        self.reliable_sender.is_delivered(message_index)
+    }
+
+    fn expire_messages_after(
+        &mut self,
+        message_count: usize,
+        ttl: Duration,
+        message: MessageContainer,
+    ) {
+        let last_index = self.reliable_sender.last_message_index();
+        self.expiring_messages.push(ExpiringMessage {
+            first_index: last_index.wrapping_sub(message_count as MessageIndex - 1),
+            message_count,
+            sent_at: Instant::now(),
+            ttl,
+            message,
+        });
+    }
+
+    fn take_expired_messages(&mut self) -> Vec<MessageContainer> {
+        mem::take(&mut self.expired_messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use naia_socket_shared::Instant;
+
+    use super::ReliableMessageSender;
+    use crate::{
+        messages::{
+            channels::{
+                channel::{OverflowStrategy, ReliableSettings},
+                senders::{
+                    channel_sender::{ChannelSender, MessageChannelSender},
+                    unordered_unreliable_sender::tests::{
+                        number_message, number_value, NumberMessage,
+                    },
+                },
+            },
+            message_kinds::MessageKinds,
+            request::GlobalRequestId,
+        },
+        FakeEntityConverter,
+    };
+
+    fn send_expiring(sender: &mut ReliableMessageSender, value: u8) {
+        sender.send_message(number_message(value));
+        sender.expire_messages_after(1, Duration::from_millis(100), number_message(value));
+    }
+
+    fn send_request(sender: &mut ReliableMessageSender, id: u64, timeout: Option<Duration>) {
+        let mut message_kinds = MessageKinds::new();
+        message_kinds.add_message::<NumberMessage>();
+        sender.send_outgoing_request(
+            &message_kinds,
+            &mut FakeEntityConverter,
+            GlobalRequestId::new(id),
+            number_message(0),
+            timeout,
+        );
+    }
+
+    #[test]
+    fn undelivered_messages_expire_after_ttl() {
+        let mut sender = ReliableMessageSender::new(&ReliableSettings::default());
+        send_expiring(&mut sender, 1);
+        send_expiring(&mut sender, 2);
+        sender.send_message(number_message(3));
+
+        let mut now = Instant::now();
+        sender.collect_messages(&now, &0.0);
+        sender.reliable_sender.take_next_messages();
+        sender.notify_message_delivered(&1);
+        assert_eq!(sender.queued_count(), 2);
+
+        now.add_millis(100);
+        sender.collect_messages(&now, &0.0);
+        let expired: Vec<u8> = sender
+            .take_expired_messages()
+            .iter()
+            .map(number_value)
+            .collect();
+        assert_eq!(expired, vec![1]);
+
+        // only the Message without a time to live is still resent
+        assert_eq!(sender.queued_count(), 1);
+        let resent: Vec<u16> = sender
+            .reliable_sender
+            .take_next_messages()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(resent, vec![2]);
+    }
+
+    #[test]
+    fn timed_out_request_stops_being_resent() {
+        let mut sender = ReliableMessageSender::expiring(&ReliableSettings::default());
+        send_request(&mut sender, 1, Some(Duration::from_millis(100)));
+        send_request(&mut sender, 2, None);
+
+        let mut now = Instant::now();
+        sender.collect_messages(&now, &0.0);
+        sender.reliable_sender.take_next_messages();
+
+        now.add_millis(100);
+        sender.collect_messages(&now, &0.0);
+        assert_eq!(
+            sender.take_timed_out_requests(),
+            vec![GlobalRequestId::new(1)]
+        );
+        assert_eq!(sender.pending_requests().len(), 1);
+
+        let resent: Vec<u16> = sender
+            .reliable_sender
+            .take_next_messages()
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(resent, vec![1]);
+    }
+
+    #[test]
+    fn drop_oldest_channel_keeps_requests_and_hides_indices() {
+        let settings = ReliableSettings {
+            message_capacity: Some(2),
+            overflow_strategy: OverflowStrategy::DropOldest,
+            ..ReliableSettings::default()
+        };
+        let mut sender = ReliableMessageSender::new(&settings);
+        assert!(sender.reserve(1).unwrap());
+        sender.send_message(number_message(1));
+        assert!(sender.reserve(1).unwrap());
+        send_request(&mut sender, 1, None);
+
+        // the plain Message is dropped, never the Request
+        assert!(sender.reserve(1).unwrap());
+        sender.send_message(number_message(3));
+        assert_eq!(sender.pending_requests().len(), 1);
+        assert_eq!(sender.queued_count(), 2);
+
+        assert_eq!(sender.last_message_index(), None);
+    }
+
+    #[test]
+    fn cancelled_request_is_still_delivered_if_channel_cannot_skip_it() {
+        let mut sender = ReliableMessageSender::new(&ReliableSettings::default());
+        send_request(&mut sender, 1, None);
+
+        assert!(sender.cancel_request(&GlobalRequestId::new(1)));
+        assert!(!sender.cancel_request(&GlobalRequestId::new(1)));
+        assert!(sender.pending_requests().is_empty());
+
+        // later Messages on the channel would otherwise wait on it forever
+        assert_eq!(sender.queued_count(), 1);
+    }
 }
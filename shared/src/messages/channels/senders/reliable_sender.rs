@@ -2,7 +2,21 @@ use std::{collections::VecDeque, mem, time::Duration};
 
 use naia_socket_shared::Instant;
 
-use crate::{messages::channels::senders::channel_sender::ChannelSender, types::MessageIndex};
+use crate::{
+    messages::channels::{
+        channel::OverflowStrategy,
+        senders::channel_sender::{ChannelFullError, ChannelSender},
+    },
+    types::MessageIndex,
+    wrapping_number::sequence_greater_than,
+};
+
+// A logical message, possibly split into fragments, which has not yet been
+// transmitted. Only tracked under OverflowStrategy::DropOldest
+struct UnsentMessage {
+    count: usize,
+    droppable: bool,
+}
 
 // Sender
 pub struct ReliableSender<P: Send + Sync> {
@@ -10,16 +24,194 @@ pub struct ReliableSender<P: Send + Sync> {
     sending_messages: VecDeque<Option<(MessageIndex, Option<Instant>, P)>>,
     next_send_message_index: MessageIndex,
     pub(crate) outgoing_messages: VecDeque<(MessageIndex, P)>,
+    message_capacity: Option<usize>,
+    overflow_strategy: OverflowStrategy,
+    buffered_count: usize,
+    send_window: Option<usize>,
+    in_flight_count: usize,
+    // the untransmitted messages at the back of `sending_messages`, oldest
+    // first, grouped into the logical messages they make up
+    unsent_messages: VecDeque<UnsentMessage>,
+    renumbered_messages: Vec<(MessageIndex, MessageIndex)>,
 }
 
 impl<P: Send + Sync> ReliableSender<P> {
     pub fn new(rtt_resend_factor: f32) -> Self {
+        Self::bounded(rtt_resend_factor, None, OverflowStrategy::Block)
+    }
+
+    pub fn bounded(
+        rtt_resend_factor: f32,
+        message_capacity: Option<usize>,
+        overflow_strategy: OverflowStrategy,
+    ) -> Self {
         Self {
             rtt_resend_factor,
             next_send_message_index: 0,
             sending_messages: VecDeque::new(),
             outgoing_messages: VecDeque::new(),
+            message_capacity,
+            overflow_strategy,
+            buffered_count: 0,
+            send_window: None,
+            in_flight_count: 0,
+            unsent_messages: VecDeque::new(),
+            renumbered_messages: Vec::new(),
+        }
+    }
+
+    /// Limits how many messages may be transmitted but not yet acknowledged at
+    /// once. Messages beyond the window stay in the send buffer until
+    /// acknowledgements make room
+    pub fn with_send_window(mut self, send_window: Option<usize>) -> Self {
+        self.send_window = send_window;
+        self
+    }
+
+    /// Makes room in the send buffer for `count` more messages, which
+    /// together make up one logical message, according to the overflow
+    /// strategy. Returns Ok(true) if the messages should be queued, or
+    /// Ok(false) if they should be silently dropped
+    pub fn reserve(&mut self, count: usize) -> Result<bool, ChannelFullError> {
+        if self.overflow_strategy == OverflowStrategy::DropOldest {
+            self.forget_transmitted_messages();
+        }
+        if let Some(message_capacity) = self.message_capacity {
+            let overflow = (self.buffered_count + count).saturating_sub(message_capacity);
+            if overflow > 0 {
+                match self.overflow_strategy {
+                    OverflowStrategy::Block => return Err(ChannelFullError),
+                    OverflowStrategy::DropNewest => return Ok(false),
+                    OverflowStrategy::DropOldest => {
+                        if count > message_capacity || !self.drop_oldest_unsent(overflow) {
+                            return Err(ChannelFullError);
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.overflow_strategy == OverflowStrategy::DropOldest {
+            self.unsent_messages.push_back(UnsentMessage {
+                count,
+                droppable: true,
+            });
+        }
+        Ok(true)
+    }
+
+    /// Keeps the most recently reserved logical message from ever being
+    /// dropped to make room for newer ones, as is needed for a Request the
+    /// remote host must answer
+    pub fn keep_last_message(&mut self) {
+        if let Some(unsent_message) = self.unsent_messages.back_mut() {
+            unsent_message.droppable = false;
+        }
+    }
+
+    /// Returns the old & new index of each message renumbered to fill the
+    /// gap left by dropped messages, since this was last called
+    pub fn take_renumbered_messages(&mut self) -> Vec<(MessageIndex, MessageIndex)> {
+        mem::take(&mut self.renumbered_messages)
+    }
+
+    // Stops tracking the logical messages which have been transmitted since
+    // they were queued. A logical message transmitted only in part can no
+    // longer be dropped, as the remote host would never complete it
+    fn forget_transmitted_messages(&mut self) {
+        let mut excess = self
+            .unsent_messages
+            .iter()
+            .map(|unsent_message| unsent_message.count)
+            .sum::<usize>()
+            .saturating_sub(self.unsent_count());
+        while excess > 0 {
+            let front = self.unsent_messages.front_mut().unwrap();
+            if front.count <= excess {
+                excess -= front.count;
+                self.unsent_messages.pop_front();
+            } else {
+                front.count -= excess;
+                front.droppable = false;
+                excess = 0;
+            }
+        }
+    }
+
+    // untransmitted messages are always at the back of the buffer
+    fn unsent_count(&self) -> usize {
+        self.sending_messages.len()
+            - self
+                .sending_messages
+                .iter()
+                .rposition(|message_opt| !matches!(message_opt, Some((_, None, _))))
+                .map_or(0, |index| index + 1)
+    }
+
+    // Drops the oldest droppable logical messages which have never been
+    // transmitted, whole, until at least `count` messages are dropped. The
+    // remaining untransmitted messages are renumbered so that the remote host
+    // never sees a gap in message indices. Returns false, changing nothing,
+    // if there are not enough droppable messages
+    fn drop_oldest_unsent(&mut self, count: usize) -> bool {
+        let mut drop_count = 0;
+        let mut dropped_messages = 0;
+        for unsent_message in &self.unsent_messages {
+            if drop_count >= count {
+                break;
+            }
+            if unsent_message.droppable {
+                drop_count += unsent_message.count;
+            }
+            dropped_messages += 1;
+        }
+        if drop_count < count {
+            return false;
+        }
+
+        let unsent_count = self.unsent_count();
+        let mut unsent = self
+            .sending_messages
+            .split_off(self.sending_messages.len() - unsent_count)
+            .into_iter()
+            .flatten();
+        self.next_send_message_index = self
+            .next_send_message_index
+            .wrapping_sub(unsent_count as MessageIndex);
+        self.buffered_count -= drop_count;
+
+        let mut kept_messages = VecDeque::new();
+        for (position, unsent_message) in
+            mem::take(&mut self.unsent_messages).into_iter().enumerate()
+        {
+            let drop = position < dropped_messages && unsent_message.droppable;
+            for (old_index, _, message) in unsent.by_ref().take(unsent_message.count) {
+                if drop {
+                    continue;
+                }
+                if old_index != self.next_send_message_index {
+                    self.renumbered_messages
+                        .push((old_index, self.next_send_message_index));
+                }
+                self.push_message(message);
+            }
+            if !drop {
+                kept_messages.push_back(unsent_message);
+            }
         }
+        self.unsent_messages = kept_messages;
+
+        true
+    }
+
+    pub fn buffered_count(&self) -> usize {
+        self.buffered_count
+    }
+
+    fn push_message(&mut self, message: P) {
+        self.sending_messages
+            .push_back(Some((self.next_send_message_index, None, message)));
+        self.next_send_message_index = self.next_send_message_index.wrapping_add(1);
     }
 
     fn cleanup_sent_messages(&mut self) {
@@ -39,6 +231,29 @@ impl<P: Send + Sync> ReliableSender<P> {
         }
     }
 
+    /// Returns the index of the most recently queued message
+    pub fn last_message_index(&self) -> MessageIndex {
+        self.next_send_message_index.wrapping_sub(1)
+    }
+
+    /// Returns whether the message with the given index, and every message
+    /// queued before it, has been delivered
+    pub fn is_delivered(&self, message_index: &MessageIndex) -> bool {
+        !self
+            .sending_messages
+            .iter()
+            .flatten()
+            .any(|(index, _, _)| !sequence_greater_than(*index, *message_index))
+    }
+
+    /// Stops sending a message which is no longer wanted. Returns whether it
+    /// was still waiting to be delivered
+    pub fn expire_message(&mut self, message_index: &MessageIndex) -> bool {
+        self.outgoing_messages
+            .retain(|(outgoing_index, _)| outgoing_index != message_index);
+        self.deliver_message(message_index).is_some()
+    }
+
     pub fn take_next_messages(&mut self) -> VecDeque<(MessageIndex, P)> {
         mem::take(&mut self.outgoing_messages)
     }
@@ -65,6 +280,10 @@ impl<P: Send + Sync> ReliableSender<P> {
                 // replace found message with nothing
                 let container = self.sending_messages.get_mut(index).unwrap();
                 let output = container.take();
+                self.buffered_count -= 1;
+                if let Some((_, Some(_), _)) = &output {
+                    self.in_flight_count -= 1;
+                }
 
                 self.cleanup_sent_messages();
 
@@ -79,9 +298,8 @@ impl<P: Send + Sync> ReliableSender<P> {
 
 impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
     fn send_message(&mut self, message: P) {
-        self.sending_messages
-            .push_back(Some((self.next_send_message_index, None, message)));
-        self.next_send_message_index = self.next_send_message_index.wrapping_add(1);
+        self.push_message(message);
+        self.buffered_count += 1;
     }
 
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
@@ -94,6 +312,14 @@ impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
                     should_send = true;
                 }
             } else {
+                // untransmitted messages are always at the back of the buffer,
+                // so once the window is full, nothing further can be sent
+                if let Some(send_window) = self.send_window {
+                    if self.in_flight_count >= send_window {
+                        break;
+                    }
+                }
+                self.in_flight_count += 1;
                 should_send = true;
             }
             if should_send {
@@ -108,7 +334,150 @@ impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
         !self.outgoing_messages.is_empty()
     }
 
+    fn queued_count(&self) -> usize {
+        self.buffered_count
+    }
+
     fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
         self.deliver_message(message_index);
     }
 }
+
+#[cfg(test)]
+mod overflow_tests {
+    use naia_socket_shared::Instant;
+
+    use super::ReliableSender;
+    use crate::messages::channels::{
+        channel::OverflowStrategy, senders::channel_sender::ChannelSender,
+    };
+
+    fn fill(sender: &mut ReliableSender<u8>, messages: &[u8]) {
+        for message in messages {
+            if sender.reserve(1).unwrap() {
+                sender.send_message(*message);
+            }
+        }
+    }
+
+    fn transmit(sender: &mut ReliableSender<u8>) -> Vec<(u16, u8)> {
+        sender.collect_messages(&Instant::now(), &0.0);
+        sender.take_next_messages().into_iter().collect()
+    }
+
+    #[test]
+    fn unbounded_never_overflows() {
+        let mut sender = ReliableSender::new(1.5);
+        fill(&mut sender, &[0; 100]);
+
+        assert_eq!(transmit(&mut sender).len(), 100);
+    }
+
+    #[test]
+    fn block_refuses_when_full() {
+        let mut sender = ReliableSender::bounded(1.5, Some(2), OverflowStrategy::Block);
+        fill(&mut sender, &[1, 2]);
+
+        assert!(sender.reserve(1).is_err());
+
+        // delivering a message frees up room
+        sender.deliver_message(&0);
+        assert!(sender.reserve(1).unwrap());
+    }
+
+    #[test]
+    fn drop_newest_discards_new_messages() {
+        let mut sender = ReliableSender::bounded(1.5, Some(2), OverflowStrategy::DropNewest);
+        fill(&mut sender, &[1, 2, 3]);
+
+        assert_eq!(transmit(&mut sender), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn drop_oldest_renumbers_unsent_messages() {
+        let mut sender = ReliableSender::bounded(1.5, Some(3), OverflowStrategy::DropOldest);
+        fill(&mut sender, &[1]);
+        assert_eq!(transmit(&mut sender), vec![(0, 1)]);
+
+        // message 1 is in flight, so only the untransmitted messages are dropped
+        fill(&mut sender, &[2, 3, 4, 5]);
+
+        assert_eq!(transmit(&mut sender), vec![(0, 1), (1, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn drop_oldest_drops_whole_fragmented_messages() {
+        let mut sender = ReliableSender::bounded(1.5, Some(4), OverflowStrategy::DropOldest);
+        assert!(sender.reserve(2).unwrap());
+        sender.send_message(1);
+        sender.send_message(2);
+        fill(&mut sender, &[3]);
+
+        // both fragments of the oldest message are dropped to make room
+        assert!(sender.reserve(2).unwrap());
+        sender.send_message(4);
+        sender.send_message(5);
+
+        assert_eq!(sender.take_renumbered_messages(), vec![(2, 0)]);
+        assert_eq!(transmit(&mut sender), vec![(0, 3), (1, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_requests() {
+        let mut sender = ReliableSender::bounded(1.5, Some(2), OverflowStrategy::DropOldest);
+        fill(&mut sender, &[1]);
+        sender.keep_last_message();
+        fill(&mut sender, &[2, 3]);
+
+        assert_eq!(transmit(&mut sender), vec![(0, 1), (1, 3)]);
+
+        // with only the kept message left untransmitted, there is no room
+        let mut sender = ReliableSender::bounded(1.5, Some(1), OverflowStrategy::DropOldest);
+        fill(&mut sender, &[1]);
+        sender.keep_last_message();
+        assert!(sender.reserve(1).is_err());
+    }
+
+    #[test]
+    fn drop_oldest_keeps_partly_transmitted_messages() {
+        let mut sender = ReliableSender::bounded(1.5, Some(3), OverflowStrategy::DropOldest)
+            .with_send_window(Some(1));
+        assert!(sender.reserve(2).unwrap());
+        sender.send_message(1);
+        sender.send_message(2);
+        assert_eq!(transmit(&mut sender), vec![(0, 1)]);
+
+        // the rest of the first message must still be sent, so the newer
+        // message is dropped instead
+        fill(&mut sender, &[3, 4]);
+
+        sender.deliver_message(&0);
+        assert_eq!(transmit(&mut sender), vec![(1, 2)]);
+        sender.deliver_message(&1);
+        assert_eq!(transmit(&mut sender), vec![(2, 4)]);
+    }
+
+    #[test]
+    fn drop_oldest_refuses_when_all_in_flight() {
+        let mut sender = ReliableSender::bounded(1.5, Some(2), OverflowStrategy::DropOldest);
+        fill(&mut sender, &[1, 2]);
+        transmit(&mut sender);
+
+        assert!(sender.reserve(1).is_err());
+    }
+
+    #[test]
+    fn send_window_holds_messages_until_acked() {
+        let mut sender = ReliableSender::new(1.5).with_send_window(Some(2));
+        fill(&mut sender, &[1, 2, 3, 4]);
+
+        assert_eq!(transmit(&mut sender), vec![(0, 1), (1, 2)]);
+
+        // the window is full, so only resends are collected
+        assert_eq!(transmit(&mut sender), vec![(0, 1), (1, 2)]);
+
+        // acknowledging a message frees up room for the next one
+        sender.deliver_message(&0);
+        assert_eq!(transmit(&mut sender), vec![(1, 2), (2, 3)]);
+    }
+}
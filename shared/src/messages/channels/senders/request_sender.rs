@@ -2,13 +2,25 @@ use std::{collections::HashMap, time::Duration};
 
 use naia_derive::MessageRequest;
 use naia_serde::{BitWriter, SerdeInternal};
+use naia_socket_shared::Instant;
 
 use crate::messages::request::GlobalRequestId;
-use crate::{KeyGenerator, LocalEntityAndGlobalEntityConverterMut, MessageContainer, MessageKinds};
+use crate::{
+    types::MessageIndex, KeyGenerator, LocalEntityAndGlobalEntityConverterMut, MessageContainer,
+    MessageKinds,
+};
+
+// A Request which is awaiting a Response
+struct SentRequest {
+    global_request_id: GlobalRequestId,
+    sent_at: Instant,
+    timeout: Option<Duration>,
+    message_index: MessageIndex,
+}
 
 pub struct RequestSender {
     local_key_generator: KeyGenerator<LocalRequestId>,
-    local_to_global_ids: HashMap<LocalRequestId, GlobalRequestId>,
+    local_to_global_ids: HashMap<LocalRequestId, SentRequest>,
 }
 
 impl RequestSender {
@@ -19,16 +31,28 @@ impl RequestSender {
         }
     }
 
+    /// Wraps a Request to be sent as the Message with index `message_index`.
+    /// If no Response arrives within `timeout`, the Request is returned by
+    /// `take_timed_out_requests()`
     pub(crate) fn process_outgoing_request(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         global_request_id: GlobalRequestId,
         request: MessageContainer,
+        message_index: MessageIndex,
+        timeout: Option<Duration>,
     ) -> MessageContainer {
         let local_request_id = self.local_key_generator.generate();
-        self.local_to_global_ids
-            .insert(local_request_id, global_request_id);
+        self.local_to_global_ids.insert(
+            local_request_id,
+            SentRequest {
+                global_request_id,
+                sent_at: Instant::now(),
+                timeout,
+                message_index,
+            },
+        );
 
         let mut writer = BitWriter::with_max_capacity();
         request.write(message_kinds, &mut writer, converter);
@@ -51,12 +75,88 @@ impl RequestSender {
         MessageContainer::from_write(Box::new(response_message), converter)
     }
 
+    /// Returns the id of the Request a Response answers, or None if the
+    /// Request was cancelled or timed out
     pub(crate) fn process_incoming_response(
         &mut self,
         local_request_id: &LocalRequestId,
     ) -> Option<GlobalRequestId> {
+        let sent_request = self.local_to_global_ids.remove(local_request_id)?;
         self.local_key_generator.recycle_key(local_request_id);
-        self.local_to_global_ids.remove(local_request_id)
+        Some(sent_request.global_request_id)
+    }
+
+    /// Stops waiting for a Response to a Request, freeing its local id.
+    /// Returns the index of the Message the Request was sent in, or None if
+    /// the Request is not awaiting a Response
+    pub(crate) fn cancel_request(
+        &mut self,
+        global_request_id: &GlobalRequestId,
+    ) -> Option<MessageIndex> {
+        let local_request_id = self
+            .local_to_global_ids
+            .iter()
+            .find(|(_, sent_request)| sent_request.global_request_id == *global_request_id)
+            .map(|(local_request_id, _)| *local_request_id)?;
+        self.local_key_generator.recycle_key(&local_request_id);
+        self.local_to_global_ids
+            .remove(&local_request_id)
+            .map(|sent_request| sent_request.message_index)
+    }
+
+    /// Stops waiting for a Response to every Request which has outlived its
+    /// timeout, returning the id of each along with the index of the Message
+    /// it was sent in
+    pub(crate) fn take_timed_out_requests(
+        &mut self,
+        now: &Instant,
+    ) -> Vec<(GlobalRequestId, MessageIndex)> {
+        let timed_out_ids: Vec<LocalRequestId> = self
+            .local_to_global_ids
+            .iter()
+            .filter(|(_, sent_request)| {
+                sent_request
+                    .timeout
+                    .is_some_and(|timeout| sent_request.sent_at.elapsed(now) >= timeout)
+            })
+            .map(|(local_request_id, _)| *local_request_id)
+            .collect();
+
+        let mut output = Vec::new();
+        for local_request_id in timed_out_ids {
+            self.local_key_generator.recycle_key(&local_request_id);
+            let sent_request = self.local_to_global_ids.remove(&local_request_id).unwrap();
+            output.push((sent_request.global_request_id, sent_request.message_index));
+        }
+        output
+    }
+
+    /// Follows the Message a Request was sent in to its new index, after
+    /// older Messages were dropped from the send buffer
+    pub(crate) fn renumber_request(&mut self, old_index: &MessageIndex, new_index: MessageIndex) {
+        if let Some(sent_request) = self
+            .local_to_global_ids
+            .values_mut()
+            .find(|sent_request| sent_request.message_index == *old_index)
+        {
+            sent_request.message_index = new_index;
+        }
+    }
+
+    /// Returns the Requests which are still awaiting a Response, along with
+    /// the time each was sent
+    pub(crate) fn pending_requests(
+        &self,
+    ) -> impl Iterator<Item = (&LocalRequestId, &GlobalRequestId, &Instant)> {
+        self.local_to_global_ids
+            .iter()
+            .map(|(local_id, sent_request)| {
+                (
+                    local_id,
+                    &sent_request.global_request_id,
+                    &sent_request.sent_at,
+                )
+            })
     }
 }
 
@@ -124,7 +224,7 @@ impl LocalRequestOrResponseId {
     }
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq, SerdeInternal)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, SerdeInternal)]
 pub struct LocalRequestId {
     id: u8,
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
@@ -7,9 +7,13 @@ use crate::messages::channels::senders::request_sender::LocalRequestId;
 use crate::messages::request::GlobalRequestId;
 use crate::{
     messages::{
-        channels::senders::{
-            channel_sender::{ChannelSender, MessageChannelSender},
-            indexed_message_writer::IndexedMessageWriter,
+        channels::{
+            channel::SendBufferSettings,
+            senders::{
+                channel_sender::{ChannelFullError, ChannelSender, MessageChannelSender},
+                indexed_message_writer::IndexedMessageWriter,
+                unordered_unreliable_sender::{make_room, reserve},
+            },
         },
         message_container::MessageContainer,
         message_kinds::MessageKinds,
@@ -23,19 +27,24 @@ pub struct SequencedUnreliableSender {
     outgoing_messages: VecDeque<(MessageIndex, MessageContainer)>,
     /// Next message id to use (not yet used in the buffer)
     next_send_message_index: MessageIndex,
+    send_buffer: Option<SendBufferSettings>,
 }
 
 impl SequencedUnreliableSender {
-    pub fn new() -> Self {
+    pub fn bounded(send_buffer: Option<SendBufferSettings>) -> Self {
         Self {
             outgoing_messages: VecDeque::new(),
             next_send_message_index: 0,
+            send_buffer,
         }
     }
 }
 
 impl ChannelSender<MessageContainer> for SequencedUnreliableSender {
     fn send_message(&mut self, message: MessageContainer) {
+        if !make_room(&mut self.outgoing_messages, &self.send_buffer) {
+            return;
+        }
         self.outgoing_messages
             .push_back((self.next_send_message_index, message));
         self.next_send_message_index = self.next_send_message_index.wrapping_add(1);
@@ -49,6 +58,10 @@ impl ChannelSender<MessageContainer> for SequencedUnreliableSender {
         !self.outgoing_messages.is_empty()
     }
 
+    fn queued_count(&self) -> usize {
+        self.outgoing_messages.len()
+    }
+
     fn notify_message_delivered(&mut self, _: &MessageIndex) {
         // not necessary for an unreliable channel
     }
@@ -73,12 +86,17 @@ impl MessageChannelSender for SequencedUnreliableSender {
         )
     }
 
+    fn reserve(&mut self, count: usize) -> Result<bool, ChannelFullError> {
+        reserve(self.outgoing_messages.len(), count, &self.send_buffer)
+    }
+
     fn send_outgoing_request(
         &mut self,
         _: &MessageKinds,
         _: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         _: GlobalRequestId,
         _: MessageContainer,
+        _: Option<Duration>,
     ) {
         panic!("SequencedUnreliable channel does not support requests");
     }
@@ -96,4 +114,100 @@ impl MessageChannelSender for SequencedUnreliableSender {
     fn process_incoming_response(&mut self, _: &LocalRequestId) -> Option<GlobalRequestId> {
         panic!("SequencedUnreliable channel does not support requests");
     }
+
+    fn pending_requests(&self) -> Vec<(LocalRequestId, GlobalRequestId, Instant)> {
+        Vec::new()
+    }
+
+    fn cancel_request(&mut self, _: &GlobalRequestId) -> bool {
+        false
+    }
+
+    fn take_timed_out_requests(&mut self) -> Vec<GlobalRequestId> {
+        Vec::new()
+    }
+
+    fn outgoing_bit_length(&self) -> u32 {
+        self.outgoing_messages
+            .iter()
+            .map(|(_, message)| message.bit_length())
+            .sum()
+    }
+
+    fn queued_len(&self) -> usize {
+        self.outgoing_messages.len()
+    }
+
+    fn last_message_index(&self) -> Option<MessageIndex> {
+        None
+    }
+
+    fn is_delivered(&self, _: &MessageIndex) -> bool {
+        true
+    }
+
+    fn expire_messages_after(&mut self, _: usize, _: Duration, _: MessageContainer) {
+        panic!("SequencedUnreliable channel does not support expiring Messages");
+    }
+
+    fn take_expired_messages(&mut self) -> Vec<MessageContainer> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SequencedUnreliableSender;
+    use crate::messages::channels::{
+        channel::{OverflowStrategy, SendBufferSettings},
+        senders::{
+            channel_sender::{ChannelSender, MessageChannelSender},
+            unordered_unreliable_sender::tests::{number_message, number_value},
+        },
+    };
+
+    fn fill(sender: &mut SequencedUnreliableSender, values: &[u8]) {
+        for value in values {
+            if sender.reserve(1).unwrap() {
+                sender.send_message(number_message(*value));
+            }
+        }
+    }
+
+    fn queued_values(sender: &SequencedUnreliableSender) -> Vec<(u16, u8)> {
+        sender
+            .outgoing_messages
+            .iter()
+            .map(|(index, message)| (*index, number_value(message)))
+            .collect()
+    }
+
+    #[test]
+    fn drop_oldest_keeps_newest_messages_in_sequence() {
+        let mut sender = SequencedUnreliableSender::bounded(Some(SendBufferSettings::new(
+            2,
+            OverflowStrategy::DropOldest,
+        )));
+        fill(&mut sender, &[1, 2, 3, 4]);
+
+        assert_eq!(sender.queued_len(), 2);
+        assert_eq!(queued_values(&sender), vec![(2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn drop_newest_keeps_oldest_messages_in_sequence() {
+        let mut sender = SequencedUnreliableSender::bounded(Some(SendBufferSettings::new(
+            2,
+            OverflowStrategy::DropNewest,
+        )));
+        fill(&mut sender, &[1, 2, 3, 4]);
+
+        assert_eq!(sender.queued_len(), 2);
+        assert_eq!(queued_values(&sender), vec![(0, 1), (1, 2)]);
+
+        // dropped Messages are never given a sequence number
+        sender.outgoing_messages.clear();
+        fill(&mut sender, &[5]);
+        assert_eq!(queued_values(&sender), vec![(2, 5)]);
+    }
 }
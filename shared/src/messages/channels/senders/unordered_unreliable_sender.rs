@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_serde::{BitWrite, BitWriter, Serde};
 use naia_socket_shared::Instant;
@@ -7,7 +7,10 @@ use crate::messages::channels::senders::request_sender::LocalRequestId;
 use crate::messages::request::GlobalRequestId;
 use crate::{
     messages::{
-        channels::senders::channel_sender::{ChannelSender, MessageChannelSender},
+        channels::{
+            channel::{OverflowStrategy, SendBufferSettings},
+            senders::channel_sender::{ChannelFullError, ChannelSender, MessageChannelSender},
+        },
         message_container::MessageContainer,
         message_kinds::MessageKinds,
     },
@@ -17,12 +20,14 @@ use crate::{
 
 pub struct UnorderedUnreliableSender {
     outgoing_messages: VecDeque<MessageContainer>,
+    send_buffer: Option<SendBufferSettings>,
 }
 
 impl UnorderedUnreliableSender {
-    pub fn new() -> Self {
+    pub fn bounded(send_buffer: Option<SendBufferSettings>) -> Self {
         Self {
             outgoing_messages: VecDeque::new(),
+            send_buffer,
         }
     }
 
@@ -46,6 +51,9 @@ impl UnorderedUnreliableSender {
 
 impl ChannelSender<MessageContainer> for UnorderedUnreliableSender {
     fn send_message(&mut self, message: MessageContainer) {
+        if !make_room(&mut self.outgoing_messages, &self.send_buffer) {
+            return;
+        }
         self.outgoing_messages.push_back(message);
     }
 
@@ -57,6 +65,10 @@ impl ChannelSender<MessageContainer> for UnorderedUnreliableSender {
         !self.outgoing_messages.is_empty()
     }
 
+    fn queued_count(&self) -> usize {
+        self.outgoing_messages.len()
+    }
+
     fn notify_message_delivered(&mut self, _: &MessageIndex) {
         // not necessary for an unreliable channel
     }
@@ -106,12 +118,17 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         None
     }
 
+    fn reserve(&mut self, count: usize) -> Result<bool, ChannelFullError> {
+        reserve(self.outgoing_messages.len(), count, &self.send_buffer)
+    }
+
     fn send_outgoing_request(
         &mut self,
         _: &MessageKinds,
         _: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         _: GlobalRequestId,
         _: MessageContainer,
+        _: Option<Duration>,
     ) {
         panic!("UnorderedUnreliable channel does not support requests");
     }
@@ -120,6 +137,45 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         panic!("UnorderedUnreliable channel does not support requests");
     }
 
+    fn pending_requests(&self) -> Vec<(LocalRequestId, GlobalRequestId, Instant)> {
+        Vec::new()
+    }
+
+    fn cancel_request(&mut self, _: &GlobalRequestId) -> bool {
+        false
+    }
+
+    fn take_timed_out_requests(&mut self) -> Vec<GlobalRequestId> {
+        Vec::new()
+    }
+
+    fn outgoing_bit_length(&self) -> u32 {
+        self.outgoing_messages
+            .iter()
+            .map(|message| message.bit_length())
+            .sum()
+    }
+
+    fn queued_len(&self) -> usize {
+        self.outgoing_messages.len()
+    }
+
+    fn last_message_index(&self) -> Option<MessageIndex> {
+        None
+    }
+
+    fn is_delivered(&self, _: &MessageIndex) -> bool {
+        true
+    }
+
+    fn expire_messages_after(&mut self, _: usize, _: Duration, _: MessageContainer) {
+        panic!("UnorderedUnreliable channel does not support expiring Messages");
+    }
+
+    fn take_expired_messages(&mut self) -> Vec<MessageContainer> {
+        Vec::new()
+    }
+
     fn send_outgoing_response(
         &mut self,
         _: &MessageKinds,
@@ -130,3 +186,131 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         panic!("UnorderedUnreliable channel does not support requests");
     }
 }
+
+// Returns whether a Message about to be sent should be queued, first dropping
+// the oldest queued Message if the send buffer is full and configured to
+pub(crate) fn make_room<T>(
+    outgoing_messages: &mut VecDeque<T>,
+    send_buffer: &Option<SendBufferSettings>,
+) -> bool {
+    let Some(send_buffer) = send_buffer else {
+        return true;
+    };
+    if outgoing_messages.len() < send_buffer.message_capacity {
+        return true;
+    }
+    match send_buffer.overflow_strategy {
+        OverflowStrategy::DropOldest if send_buffer.message_capacity > 0 => {
+            outgoing_messages.pop_front();
+            true
+        }
+        // a Blocking channel refuses in `reserve()` instead
+        _ => false,
+    }
+}
+
+// Only a Blocking send buffer refuses Messages up front, the others make room
+// for, or drop, each Message as it is sent
+pub(crate) fn reserve(
+    queued_len: usize,
+    count: usize,
+    send_buffer: &Option<SendBufferSettings>,
+) -> Result<bool, ChannelFullError> {
+    match send_buffer {
+        Some(send_buffer)
+            if send_buffer.overflow_strategy == OverflowStrategy::Block
+                && queued_len + count > send_buffer.message_capacity =>
+        {
+            Err(ChannelFullError)
+        }
+        _ => Ok(true),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use naia_derive::MessageInternal;
+
+    use super::UnorderedUnreliableSender;
+    use crate::{
+        messages::channels::{
+            channel::{OverflowStrategy, SendBufferSettings},
+            senders::channel_sender::{ChannelSender, MessageChannelSender},
+        },
+        FakeEntityConverter, MessageContainer,
+    };
+
+    #[derive(MessageInternal)]
+    pub struct NumberMessage {
+        pub value: u8,
+    }
+
+    pub fn number_message(value: u8) -> MessageContainer {
+        MessageContainer::from_write(Box::new(NumberMessage { value }), &mut FakeEntityConverter)
+    }
+
+    pub fn number_value(message: &MessageContainer) -> u8 {
+        message
+            .clone()
+            .to_boxed_any()
+            .downcast::<NumberMessage>()
+            .unwrap()
+            .value
+    }
+
+    fn fill(sender: &mut UnorderedUnreliableSender, values: &[u8]) {
+        for value in values {
+            if sender.reserve(1).unwrap() {
+                sender.send_message(number_message(*value));
+            }
+        }
+    }
+
+    fn queued_values(sender: &UnorderedUnreliableSender) -> Vec<u8> {
+        sender.outgoing_messages.iter().map(number_value).collect()
+    }
+
+    #[test]
+    fn unbounded_keeps_every_message() {
+        let mut sender = UnorderedUnreliableSender::bounded(None);
+        fill(&mut sender, &[0; 100]);
+
+        assert_eq!(sender.queued_len(), 100);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_newest_messages() {
+        let mut sender = UnorderedUnreliableSender::bounded(Some(SendBufferSettings::new(
+            3,
+            OverflowStrategy::DropOldest,
+        )));
+        fill(&mut sender, &[1, 2, 3, 4, 5]);
+
+        assert_eq!(sender.queued_len(), 3);
+        assert_eq!(queued_values(&sender), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn drop_newest_keeps_oldest_messages() {
+        let mut sender = UnorderedUnreliableSender::bounded(Some(SendBufferSettings::new(
+            3,
+            OverflowStrategy::DropNewest,
+        )));
+        fill(&mut sender, &[1, 2, 3, 4, 5]);
+
+        assert_eq!(sender.queued_len(), 3);
+        assert_eq!(queued_values(&sender), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn block_refuses_when_full() {
+        let mut sender = UnorderedUnreliableSender::bounded(Some(SendBufferSettings::new(
+            2,
+            OverflowStrategy::Block,
+        )));
+        fill(&mut sender, &[1, 2]);
+
+        assert!(sender.reserve(1).is_err());
+        assert_eq!(queued_values(&sender), vec![1, 2]);
+    }
+}
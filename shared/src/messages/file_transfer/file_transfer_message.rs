@@ -0,0 +1,56 @@
+use naia_derive::MessageInternal;
+use naia_serde::SerdeInternal;
+
+/// Identifies a file transfer within a single connection
+#[derive(SerdeInternal, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileTransferId {
+    id: u32,
+}
+
+impl FileTransferId {
+    pub(crate) fn new(id: u32) -> Self {
+        Self { id }
+    }
+}
+
+#[derive(MessageInternal)]
+pub struct FileTransferMessage {
+    pub id: FileTransferId,
+    pub action: FileTransferAction,
+}
+
+#[derive(SerdeInternal, Clone, Debug, PartialEq)]
+pub enum FileTransferAction {
+    /// Offers a file to the receiver, which answers with `Accept`
+    Start {
+        name: String,
+        size: u32,
+        checksum: u64,
+    },
+    /// Asks the sender to transmit the file from `offset` onwards, skipping
+    /// the bytes the receiver kept from an interrupted transfer
+    Accept { offset: u32 },
+    /// A contiguous slice of the file
+    Chunk { offset: u32, bytes: Box<[u8]> },
+    /// Reports how many bytes of the file the receiver holds, which paces
+    /// the sender
+    Received { offset: u32 },
+}
+
+impl FileTransferMessage {
+    pub fn new(id: FileTransferId, action: FileTransferAction) -> Self {
+        Self { id, action }
+    }
+}
+
+/// FNV-1a hash of a file's contents, used to tell whether bytes kept from an
+/// interrupted transfer belong to the file being offered again, and to
+/// verify the file once it has been reassembled
+pub fn file_checksum(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -0,0 +1,225 @@
+use std::{collections::HashMap, mem};
+
+use log::warn;
+
+use crate::messages::file_transfer::file_transfer_message::{file_checksum, FileTransferId};
+
+pub enum FileTransferReceiveEvent {
+    /// A file's name, how many of its bytes have been received, and its size
+    Progress(String, u32, u32),
+    /// A file's name and contents, once every byte has been received and the
+    /// contents match the checksum the sender offered
+    Complete(String, Vec<u8>),
+}
+
+struct IncomingFileTransfer {
+    size: u32,
+    checksum: u64,
+    data: Vec<u8>,
+}
+
+/// Reassembles files sent over the `FileTransferChannel`. Partially received
+/// files are kept, by name, across connections, so that when the same file
+/// is offered again after a reconnect only the missing bytes are requested
+#[derive(Default)]
+pub struct FileTransferReceiver {
+    transfers: HashMap<String, IncomingFileTransfer>,
+    /// Name of the file for each transfer offered on the current connection
+    names: HashMap<FileTransferId, String>,
+    /// Number of bytes held of each file which has received chunks since the
+    /// sender was last told
+    receipts: HashMap<FileTransferId, u32>,
+    events: Vec<FileTransferReceiveEvent>,
+}
+
+impl FileTransferReceiver {
+    pub fn new() -> Self {
+        Self {
+            transfers: HashMap::new(),
+            names: HashMap::new(),
+            receipts: HashMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Records an offered file, and returns the offset the sender should
+    /// begin transmitting from
+    pub fn start(&mut self, id: FileTransferId, name: &str, size: u32, checksum: u64) -> u32 {
+        self.names.insert(id, name.to_string());
+
+        let resumable = self
+            .transfers
+            .get(name)
+            .is_some_and(|transfer| transfer.size == size && transfer.checksum == checksum);
+        if !resumable {
+            self.transfers.insert(
+                name.to_string(),
+                IncomingFileTransfer {
+                    size,
+                    checksum,
+                    data: Vec::new(),
+                },
+            );
+        }
+
+        let offset = self.transfers.get(name).unwrap().data.len() as u32;
+        self.events.push(FileTransferReceiveEvent::Progress(
+            name.to_string(),
+            offset,
+            size,
+        ));
+        self.complete_if_received(name);
+
+        offset
+    }
+
+    /// Appends a chunk to the file it belongs to. Chunks which do not follow
+    /// on from the bytes already received are ignored
+    pub fn receive_chunk(&mut self, id: &FileTransferId, offset: u32, bytes: &[u8]) {
+        let Some(name) = self.names.get(id).cloned() else {
+            warn!("Received chunk of unknown file transfer {:?}", id);
+            return;
+        };
+        let Some(transfer) = self.transfers.get_mut(&name) else {
+            return;
+        };
+        if offset as usize != transfer.data.len()
+            || transfer.data.len() + bytes.len() > transfer.size as usize
+        {
+            return;
+        }
+
+        transfer.data.extend_from_slice(bytes);
+        let received = transfer.data.len() as u32;
+        let size = transfer.size;
+        self.receipts.insert(*id, received);
+        self.events.push(FileTransferReceiveEvent::Progress(
+            name.clone(),
+            received,
+            size,
+        ));
+        self.complete_if_received(&name);
+    }
+
+    /// Forgets the current connection's transfer ids, keeping the bytes
+    /// received so far for a later transfer of the same file to resume from
+    pub fn disconnect(&mut self) {
+        self.names.clear();
+        self.receipts.clear();
+    }
+
+    /// Discards the bytes received so far of the file with the given name
+    pub fn discard(&mut self, name: &str) {
+        self.transfers.remove(name);
+        self.names.retain(|_, transfer_name| transfer_name != name);
+    }
+
+    /// Returns the id and received byte count of every transfer which should
+    /// report its progress to the sender
+    pub fn take_receipts(&mut self) -> Vec<(FileTransferId, u32)> {
+        self.receipts.drain().collect()
+    }
+
+    pub fn take_events(&mut self) -> Vec<FileTransferReceiveEvent> {
+        mem::take(&mut self.events)
+    }
+
+    fn complete_if_received(&mut self, name: &str) {
+        let Some(transfer) = self.transfers.get(name) else {
+            return;
+        };
+        if transfer.data.len() < transfer.size as usize {
+            return;
+        }
+
+        let transfer = self.transfers.remove(name).unwrap();
+        self.names.retain(|_, transfer_name| transfer_name != name);
+        if file_checksum(&transfer.data) != transfer.checksum {
+            warn!(
+                "Discarded file `{}`, which did not match its checksum",
+                name
+            );
+            return;
+        }
+        self.events.push(FileTransferReceiveEvent::Complete(
+            name.to_string(),
+            transfer.data,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileTransferReceiveEvent, FileTransferReceiver};
+    use crate::messages::file_transfer::file_transfer_message::{file_checksum, FileTransferId};
+
+    fn completed(receiver: &mut FileTransferReceiver) -> Option<Vec<u8>> {
+        receiver
+            .take_events()
+            .into_iter()
+            .find_map(|event| match event {
+                FileTransferReceiveEvent::Complete(_, data) => Some(data),
+                FileTransferReceiveEvent::Progress(..) => None,
+            })
+    }
+
+    #[test]
+    fn file_is_reassembled_from_chunks() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut receiver = FileTransferReceiver::new();
+        let id = FileTransferId::new(0);
+
+        let offset = receiver.start(id, "level", 10, file_checksum(&data));
+        assert_eq!(offset, 0);
+        receiver.receive_chunk(&id, 0, &data[..6]);
+        assert!(completed(&mut receiver).is_none());
+
+        receiver.receive_chunk(&id, 6, &data[6..]);
+        assert_eq!(completed(&mut receiver), Some(data));
+    }
+
+    #[test]
+    fn interrupted_transfer_resumes() {
+        let data: Vec<u8> = (0..10).collect();
+        let checksum = file_checksum(&data);
+        let mut receiver = FileTransferReceiver::new();
+
+        let id = FileTransferId::new(0);
+        receiver.start(id, "level", 10, checksum);
+        receiver.receive_chunk(&id, 0, &data[..4]);
+        receiver.disconnect();
+
+        // chunks of the old transfer are no longer accepted
+        receiver.receive_chunk(&id, 4, &data[4..]);
+        assert!(completed(&mut receiver).is_none());
+
+        let id = FileTransferId::new(7);
+        let offset = receiver.start(id, "level", 10, checksum);
+        assert_eq!(offset, 4);
+        receiver.receive_chunk(&id, 4, &data[4..]);
+        assert_eq!(completed(&mut receiver), Some(data));
+    }
+
+    #[test]
+    fn changed_file_restarts() {
+        let mut receiver = FileTransferReceiver::new();
+        let id = FileTransferId::new(0);
+        receiver.start(id, "level", 10, 1);
+        receiver.receive_chunk(&id, 0, &[0; 4]);
+
+        let offset = receiver.start(FileTransferId::new(1), "level", 10, 2);
+        assert_eq!(offset, 0);
+    }
+
+    #[test]
+    fn corrupted_file_is_discarded() {
+        let data: Vec<u8> = (0..10).collect();
+        let mut receiver = FileTransferReceiver::new();
+        let id = FileTransferId::new(0);
+
+        receiver.start(id, "level", 10, file_checksum(&data) ^ 1);
+        receiver.receive_chunk(&id, 0, &data);
+        assert!(completed(&mut receiver).is_none());
+        assert_eq!(receiver.start(id, "level", 10, file_checksum(&data)), 0);
+    }
+}
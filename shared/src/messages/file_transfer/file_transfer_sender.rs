@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+
+use log::warn;
+
+use crate::{
+    messages::{
+        channels::{channel_kinds::ChannelKind, file_transfer_channel::FileTransferChannel},
+        file_transfer::file_transfer_message::{
+            file_checksum, FileTransferAction, FileTransferId, FileTransferMessage,
+        },
+        message_container::MessageContainer,
+        message_kinds::MessageKinds,
+        message_manager::MessageManager,
+    },
+    FakeEntityConverter,
+};
+
+/// Size, in bytes, of each chunk of a file. Small enough that a chunk never
+/// needs to be fragmented
+pub const FILE_TRANSFER_CHUNK_BYTES: usize = 256;
+/// Maximum number of bytes which may be sent but not yet reported received at
+/// once, across every transfer on a connection
+pub const FILE_TRANSFER_WINDOW_BYTES: usize = 16 * 1024;
+
+struct OutgoingFileTransfer {
+    id: FileTransferId,
+    data: Box<[u8]>,
+    /// Offset of the next chunk to send, or None until the receiver accepts
+    send_offset: Option<u32>,
+    /// Number of bytes the receiver has reported holding
+    received: u32,
+    reported: Option<u32>,
+}
+
+impl OutgoingFileTransfer {
+    fn size(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    fn in_flight(&self) -> usize {
+        self.send_offset
+            .map_or(0, |send_offset| (send_offset - self.received) as usize)
+    }
+}
+
+/// Splits outgoing files into chunks sent over the `FileTransferChannel`,
+/// keeping a bounded number of bytes in flight so that a large file never
+/// floods the send buffer
+#[derive(Default)]
+pub struct FileTransferSender {
+    next_id: u32,
+    transfers: VecDeque<OutgoingFileTransfer>,
+}
+
+impl FileTransferSender {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            transfers: VecDeque::new(),
+        }
+    }
+
+    /// Offers a file to the remote host. Nothing beyond the offer is sent
+    /// until the remote host accepts it
+    pub fn start(
+        &mut self,
+        message_kinds: &MessageKinds,
+        message_manager: &mut MessageManager,
+        name: &str,
+        data: Box<[u8]>,
+    ) -> FileTransferId {
+        let id = FileTransferId::new(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+
+        let action = FileTransferAction::Start {
+            name: name.to_string(),
+            size: data.len() as u32,
+            checksum: file_checksum(&data),
+        };
+        Self::send(message_kinds, message_manager, id, action);
+
+        self.transfers.push_back(OutgoingFileTransfer {
+            id,
+            data,
+            send_offset: None,
+            received: 0,
+            reported: None,
+        });
+
+        id
+    }
+
+    /// Begins sending an offered file from `offset`, which the remote host
+    /// chooses so as to resume an interrupted transfer
+    pub fn accept(&mut self, id: &FileTransferId, offset: u32) {
+        let Some(transfer) = self.transfer_mut(id) else {
+            return;
+        };
+        if transfer.send_offset.is_some() {
+            return;
+        }
+        let offset = offset.min(transfer.size());
+        transfer.send_offset = Some(offset);
+        transfer.received = offset;
+    }
+
+    /// Records how many bytes of a file the remote host holds, making room in
+    /// the window for further chunks
+    pub fn receive(&mut self, id: &FileTransferId, offset: u32) {
+        let Some(transfer) = self.transfer_mut(id) else {
+            return;
+        };
+        let Some(send_offset) = transfer.send_offset else {
+            return;
+        };
+        if offset > transfer.received && offset <= send_offset {
+            transfer.received = offset;
+        }
+    }
+
+    /// Sends the next chunks of accepted files while there is room in the
+    /// window, and returns the id, received byte count and size of every
+    /// transfer which has progressed since the last call. Transfers which
+    /// have been fully received are forgotten
+    pub fn send_chunks(
+        &mut self,
+        message_kinds: &MessageKinds,
+        message_manager: &mut MessageManager,
+    ) -> Vec<(FileTransferId, u32, u32)> {
+        let mut progress = Vec::new();
+        let mut in_flight: usize = self.transfers.iter().map(|t| t.in_flight()).sum();
+
+        for transfer in self.transfers.iter_mut() {
+            let Some(send_offset) = transfer.send_offset.as_mut() else {
+                continue;
+            };
+            while (*send_offset as usize) < transfer.data.len()
+                && in_flight < FILE_TRANSFER_WINDOW_BYTES
+            {
+                let start = *send_offset as usize;
+                let end = (start + FILE_TRANSFER_CHUNK_BYTES).min(transfer.data.len());
+                let action = FileTransferAction::Chunk {
+                    offset: *send_offset,
+                    bytes: transfer.data[start..end].into(),
+                };
+                Self::send(message_kinds, message_manager, transfer.id, action);
+                *send_offset = end as u32;
+                in_flight += end - start;
+            }
+
+            if transfer.reported != Some(transfer.received) {
+                transfer.reported = Some(transfer.received);
+                progress.push((transfer.id, transfer.received, transfer.size()));
+            }
+        }
+
+        self.transfers.retain(|transfer| {
+            transfer.send_offset.is_none() || transfer.received < transfer.size()
+        });
+
+        progress
+    }
+
+    fn transfer_mut(&mut self, id: &FileTransferId) -> Option<&mut OutgoingFileTransfer> {
+        let transfer = self
+            .transfers
+            .iter_mut()
+            .find(|transfer| transfer.id == *id);
+        if transfer.is_none() {
+            warn!("Received message for unknown file transfer {:?}", id);
+        }
+        transfer
+    }
+
+    // FileTransferChannel is unbounded, so queueing on it never fails
+    fn send(
+        message_kinds: &MessageKinds,
+        message_manager: &mut MessageManager,
+        id: FileTransferId,
+        action: FileTransferAction,
+    ) {
+        let mut converter = FakeEntityConverter;
+        let message = MessageContainer::from_write(
+            Box::new(FileTransferMessage::new(id, action)),
+            &mut converter,
+        );
+        let _ = message_manager.send_message(
+            message_kinds,
+            &mut converter,
+            &ChannelKind::of::<FileTransferChannel>(),
+            message,
+        );
+    }
+}
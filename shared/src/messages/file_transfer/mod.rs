@@ -0,0 +1,3 @@
+pub mod file_transfer_message;
+pub mod file_transfer_receiver;
+pub mod file_transfer_sender;
@@ -0,0 +1,136 @@
+use std::{any::Any, collections::HashSet};
+
+use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
+
+use crate::{
+    messages::named::Named, LocalEntityAndGlobalEntityConverter,
+    LocalEntityAndGlobalEntityConverterMut, Message, MessageBuilder, MessageContainer, MessageKind,
+    MessageKinds, RemoteEntity,
+};
+
+/// Wraps a Message with an application-defined key, so that the receiver can
+/// drop any copy of the Message whose key it has already processed. The
+/// wrapped Message is written in place, so any Entities it references are
+/// waited on by the receiver just as if it had been sent unwrapped
+#[derive(Clone)]
+pub struct IdempotentMessage {
+    key: u64,
+    inner: Box<dyn Message>,
+}
+
+impl IdempotentMessage {
+    pub fn wrap(
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        key: u64,
+        message: Box<dyn Message>,
+    ) -> MessageContainer {
+        let idempotent_message = Self {
+            key,
+            inner: message,
+        };
+        MessageContainer::from_write(Box::new(idempotent_message), converter)
+    }
+
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    pub fn unwrap(self) -> MessageContainer {
+        MessageContainer::from_read(self.inner)
+    }
+
+    /// Reads the rest of an IdempotentMessage once its MessageKind has been
+    /// read. Unlike other Messages this needs the MessageKinds, to read the
+    /// wrapped Message, so is called by `MessageKinds::read()` rather than
+    /// through a MessageBuilder
+    pub(crate) fn read(
+        message_kinds: &MessageKinds,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<MessageContainer, SerdeErr> {
+        let key = u64::de(reader)?;
+        let inner = message_kinds.read(reader, converter)?;
+        if inner.kind() == MessageKind::of::<Self>() {
+            // keyed Messages are never nested
+            return Err(SerdeErr);
+        }
+        let idempotent_message = Self {
+            key,
+            inner: inner.into_boxed_message(),
+        };
+        Ok(MessageContainer::from_read(Box::new(idempotent_message)))
+    }
+}
+
+struct IdempotentMessageBuilder;
+
+impl MessageBuilder for IdempotentMessageBuilder {
+    fn read(
+        &self,
+        _reader: &mut BitReader,
+        _converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<MessageContainer, SerdeErr> {
+        // see `IdempotentMessage::read()`
+        Err(SerdeErr)
+    }
+}
+
+impl Message for IdempotentMessage {
+    fn kind(&self) -> MessageKind {
+        MessageKind::of::<Self>()
+    }
+
+    fn to_boxed_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn create_builder() -> Box<dyn MessageBuilder>
+    where
+        Self: Sized,
+    {
+        Box::new(IdempotentMessageBuilder)
+    }
+
+    fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32 {
+        <MessageKind as ConstBitLength>::const_bit_length()
+            + self.key.bit_length()
+            + self.inner.bit_length(converter)
+    }
+
+    fn is_fragment(&self) -> bool {
+        false
+    }
+
+    fn is_request(&self) -> bool {
+        false
+    }
+
+    fn write(
+        &self,
+        message_kinds: &MessageKinds,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        self.kind().ser(message_kinds, writer);
+        self.key.ser(writer);
+        self.inner.write(message_kinds, writer, converter);
+    }
+
+    fn has_entity_properties(&self) -> bool {
+        self.inner.has_entity_properties()
+    }
+
+    fn relations_waiting(&self) -> Option<HashSet<RemoteEntity>> {
+        self.inner.relations_waiting()
+    }
+
+    fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
+        self.inner.relations_complete(converter);
+    }
+}
+
+impl Named for IdempotentMessage {
+    fn name(&self) -> String {
+        format!("IdempotentMessage<{}>", self.inner.name())
+    }
+}
@@ -39,12 +39,13 @@ pub trait Message: Send + Sync + Named + MessageClone + Any {
         writer: &mut dyn BitWrite,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
     );
+    /// Returns whether the Message has any EntityProperty fields, whose
+    /// serialized form differs between connections
+    fn has_entity_properties(&self) -> bool;
     /// Returns a list of LocalEntities contained within the Message's EntityProperty fields, which are waiting to be converted to GlobalEntities
     fn relations_waiting(&self) -> Option<HashSet<RemoteEntity>>;
     /// Converts any LocalEntities contained within the Message's EntityProperty fields to GlobalEntities
     fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter);
-    // /// Returns whether has any EntityRelations
-    // fn has_entity_relations(&self) -> bool;
     // /// Returns a list of Entities contained within the Message's EntityRelation fields
     // fn entities(&self) -> Vec<GlobalEntity>;
 }
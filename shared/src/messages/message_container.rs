@@ -1,10 +1,11 @@
-use std::{any::Any, collections::HashSet};
+use std::{any::Any, collections::HashSet, sync::Arc};
 
-use naia_serde::BitWrite;
+use naia_serde::{BitCounter, BitWrite, FileBitWriter};
 
 use crate::{
     world::entity::{
-        entity_converters::LocalEntityAndGlobalEntityConverterMut, local_entity::RemoteEntity,
+        entity_converters::{FakeEntityConverter, LocalEntityAndGlobalEntityConverterMut},
+        local_entity::RemoteEntity,
     },
     LocalEntityAndGlobalEntityConverter, Message, MessageKind, MessageKinds,
 };
@@ -13,6 +14,9 @@ use crate::{
 pub struct MessageContainer {
     inner: Box<dyn Message>,
     bit_length: Option<u32>,
+    /// The Message's bits, serialized ahead of time and shared between every
+    /// clone of this MessageContainer
+    serialized: Option<Arc<SerializedMessage>>,
 }
 
 impl MessageContainer {
@@ -24,6 +28,35 @@ impl MessageContainer {
         Self {
             inner: message,
             bit_length: Some(bit_length),
+            serialized: None,
+        }
+    }
+
+    /// Wraps a Message which references no Entities, serializing it up front.
+    /// Writing this MessageContainer, or any clone of it, copies those bits
+    /// rather than serializing the Message again, so a Message sent over many
+    /// connections is only serialized once
+    pub fn from_write_shared(message: Box<dyn Message>, message_kinds: &MessageKinds) -> Self {
+        if message.has_entity_properties() {
+            panic!(
+                "Message `{}` references Entities, so must be serialized for each connection",
+                message.name()
+            );
+        }
+
+        let mut converter = FakeEntityConverter;
+        let bit_length = message.bit_length(&mut converter);
+        let mut writer = FileBitWriter::new();
+        message.write(message_kinds, &mut writer, &mut converter);
+        let serialized = SerializedMessage {
+            bytes: writer.to_bytes(),
+            bit_length,
+        };
+
+        Self {
+            inner: message,
+            bit_length: Some(bit_length),
+            serialized: Some(Arc::new(serialized)),
         }
     }
 
@@ -31,6 +64,7 @@ impl MessageContainer {
         Self {
             inner: message,
             bit_length: None,
+            serialized: None,
         }
     }
 
@@ -50,11 +84,26 @@ impl MessageContainer {
     ) {
         if writer.is_counter() {
             writer.count_bits(self.bit_length());
+        } else if let Some(serialized) = &self.serialized {
+            serialized.write(writer);
         } else {
             self.inner.write(message_kinds, writer, converter);
         }
     }
 
+    /// Returns the number of bits this Message takes up once serialized, by
+    /// writing it into a BitCounter. Unlike `bit_length()`, this also works on
+    /// a MessageContainer that was created from a read operation
+    pub fn serialized_bit_len(
+        &self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> u32 {
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        self.inner.write(message_kinds, &mut counter, converter);
+        counter.bits_needed()
+    }
+
     pub fn is_fragment(&self) -> bool {
         return self.inner.is_fragment();
     }
@@ -67,6 +116,10 @@ impl MessageContainer {
         return self.inner.to_boxed_any();
     }
 
+    pub(crate) fn into_boxed_message(self) -> Box<dyn Message> {
+        return self.inner;
+    }
+
     pub fn kind(&self) -> MessageKind {
         return self.inner.kind();
     }
@@ -79,3 +132,55 @@ impl MessageContainer {
         self.inner.relations_complete(converter);
     }
 }
+
+// The bits of a Message serialized ahead of time
+struct SerializedMessage {
+    bytes: Box<[u8]>,
+    bit_length: u32,
+}
+
+impl SerializedMessage {
+    fn write(&self, writer: &mut dyn BitWrite) {
+        writer.write_bits(&self.bytes, self.bit_length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitWriter, Serde};
+
+    use super::MessageContainer;
+    use crate::{
+        messages::{
+            channels::senders::unordered_unreliable_sender::tests::NumberMessage,
+            message_kinds::MessageKinds,
+        },
+        FakeEntityConverter,
+    };
+
+    fn written_bytes(message: &MessageContainer, message_kinds: &MessageKinds) -> Box<[u8]> {
+        let mut writer = BitWriter::new();
+        // start off a byte boundary, as a Message in a packet usually does
+        true.ser(&mut writer);
+        message.write(message_kinds, &mut writer, &mut FakeEntityConverter);
+        writer.to_bytes()
+    }
+
+    #[test]
+    fn shared_message_writes_same_bits() {
+        let mut message_kinds = MessageKinds::new();
+        message_kinds.add_message::<NumberMessage>();
+
+        for value in [0, 1, 170, 255] {
+            let message = Box::new(NumberMessage { value });
+            let shared = MessageContainer::from_write_shared(message.clone(), &message_kinds);
+            let unshared = MessageContainer::from_write(message, &mut FakeEntityConverter);
+
+            assert_eq!(shared.bit_length(), unshared.bit_length());
+            assert_eq!(
+                written_bytes(&shared.clone(), &message_kinds),
+                written_bytes(&unshared, &message_kinds)
+            );
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{types::MessageIndex, ChannelKind};
+
+/// Identifies a Message queued on a reliable Channel, so that later Messages
+/// can be held back until it has been delivered to the remote host. Only
+/// meaningful for the connection it was returned from
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MessageDependency {
+    channel_kind: ChannelKind,
+    message_index: MessageIndex,
+}
+
+impl MessageDependency {
+    pub(crate) fn new(channel_kind: ChannelKind, message_index: MessageIndex) -> Self {
+        Self {
+            channel_kind,
+            message_index,
+        }
+    }
+
+    pub fn channel_kind(&self) -> ChannelKind {
+        self.channel_kind
+    }
+
+    pub(crate) fn message_index(&self) -> MessageIndex {
+        self.message_index
+    }
+}
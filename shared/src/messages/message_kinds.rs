@@ -2,7 +2,10 @@ use std::{any::TypeId, collections::HashMap};
 
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
 
-use crate::{LocalEntityAndGlobalEntityConverter, Message, MessageBuilder, MessageContainer};
+use crate::{
+    IdempotentMessage, LocalEntityAndGlobalEntityConverter, Message, MessageBuilder,
+    MessageContainer,
+};
 
 type NetId = u16;
 
@@ -33,7 +36,8 @@ impl MessageKind {
 
     pub fn de(message_kinds: &MessageKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(message_kinds.net_id_to_kind(&net_id))
+        // an unknown id can only come from a malformed or malicious packet
+        message_kinds.net_id_to_kind(&net_id).ok_or(SerdeErr)
     }
 }
 
@@ -76,13 +80,14 @@ impl MessageKinds {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<MessageContainer, SerdeErr> {
         let message_kind: MessageKind = MessageKind::de(self, reader)?;
+        if message_kind == MessageKind::of::<IdempotentMessage>() {
+            return IdempotentMessage::read(self, reader, converter);
+        }
         return self.kind_to_builder(&message_kind).read(reader, converter);
     }
 
-    fn net_id_to_kind(&self, net_id: &NetId) -> MessageKind {
-        return *self.net_id_map.get(net_id).expect(
-            "Must properly initialize Message with Protocol via `add_message()` function!",
-        );
+    fn net_id_to_kind(&self, net_id: &NetId) -> Option<MessageKind> {
+        return self.net_id_map.get(net_id).copied();
     }
 
     fn kind_to_net_id(&self, message_kind: &MessageKind) -> NetId {
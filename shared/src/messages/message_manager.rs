@@ -1,6 +1,13 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Duration,
+};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, ConstBitLength, Serde, SerdeErr};
+use naia_serde::{
+    BitReader, BitWrite, BitWriter, ConstBitLength, Serde, SerdeErr, UnsignedVariableInteger,
+    MTU_SIZE_BYTES,
+};
 use naia_socket_shared::Instant;
 
 use crate::{
@@ -8,25 +15,30 @@ use crate::{
     messages::{
         channels::{
             channel::ChannelMode,
-            channel::ChannelSettings,
+            channel::{ChannelSettings, CoalesceSettings},
+            channel_compressor::ChannelCompressor,
             channel_kinds::{ChannelKind, ChannelKinds},
             receivers::{
                 channel_receiver::MessageChannelReceiver,
                 ordered_reliable_receiver::OrderedReliableReceiver,
+                ordered_unreliable_receiver::OrderedUnreliableReceiver,
                 sequenced_reliable_receiver::SequencedReliableReceiver,
                 sequenced_unreliable_receiver::SequencedUnreliableReceiver,
                 unordered_reliable_receiver::UnorderedReliableReceiver,
                 unordered_unreliable_receiver::UnorderedUnreliableReceiver,
             },
             senders::{
-                channel_sender::MessageChannelSender, message_fragmenter::MessageFragmenter,
-                reliable_message_sender::ReliableMessageSender, request_sender::LocalResponseId,
+                channel_sender::{ChannelFullError, MessageChannelSender, MessageSendError},
+                message_fragmenter::MessageFragmenter,
+                reliable_message_sender::ReliableMessageSender,
+                request_sender::LocalResponseId,
                 sequenced_unreliable_sender::SequencedUnreliableSender,
                 unordered_unreliable_sender::UnorderedUnreliableSender,
             },
         },
         message_container::MessageContainer,
-        request::GlobalRequestId,
+        message_dependency::MessageDependency,
+        request::{GlobalRequestId, PendingRequest},
     },
     types::{HostType, MessageIndex, PacketIndex},
     world::{
@@ -45,6 +57,15 @@ pub struct MessageManager {
     channel_settings: HashMap<ChannelKind, ChannelSettings>,
     packet_to_message_map: HashMap<PacketIndex, Vec<(ChannelKind, Vec<MessageIndex>)>>,
     message_fragmenter: MessageFragmenter,
+    /// For each coalescing channel, the time its oldest unwritten Message
+    /// started waiting
+    coalesce_since: HashMap<ChannelKind, Option<Instant>>,
+    /// Coalescing channels whose Messages are being held back this tick
+    held_channels: HashSet<ChannelKind>,
+    /// Messages waiting for another Message to be delivered, along with the
+    /// channel each will be sent over
+    dependent_messages: Vec<(MessageDependency, ChannelKind, Vec<MessageContainer>)>,
+    channel_compressors: HashMap<ChannelKind, ChannelCompressor>,
 }
 
 impl MessageManager {
@@ -71,19 +92,31 @@ impl MessageManager {
 
             match &channel_settings.mode {
                 ChannelMode::UnorderedUnreliable => {
-                    channel_senders
-                        .insert(channel_kind, Box::new(UnorderedUnreliableSender::new()));
+                    channel_senders.insert(
+                        channel_kind,
+                        Box::new(UnorderedUnreliableSender::bounded(
+                            channel_settings.send_buffer,
+                        )),
+                    );
                 }
-                ChannelMode::SequencedUnreliable => {
-                    channel_senders
-                        .insert(channel_kind, Box::new(SequencedUnreliableSender::new()));
+                ChannelMode::SequencedUnreliable | ChannelMode::OrderedUnreliable(_) => {
+                    channel_senders.insert(
+                        channel_kind,
+                        Box::new(SequencedUnreliableSender::bounded(
+                            channel_settings.send_buffer,
+                        )),
+                    );
                 }
                 ChannelMode::UnorderedReliable(settings)
                 | ChannelMode::SequencedReliable(settings)
                 | ChannelMode::OrderedReliable(settings) => {
+                    channel_senders
+                        .insert(channel_kind, Box::new(ReliableMessageSender::new(settings)));
+                }
+                ChannelMode::ExpiringReliable(settings) => {
                     channel_senders.insert(
                         channel_kind,
-                        Box::new(ReliableMessageSender::new(settings.rtt_resend_factor)),
+                        Box::new(ReliableMessageSender::expiring(settings)),
                     );
                 }
                 ChannelMode::TickBuffered(_) => {
@@ -112,31 +145,62 @@ impl MessageManager {
                 ChannelMode::UnorderedUnreliable => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(UnorderedUnreliableReceiver::new()),
+                        Box::new(UnorderedUnreliableReceiver::new(
+                            channel_settings.unresolved_entities,
+                        )),
                     );
                 }
                 ChannelMode::SequencedUnreliable => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(SequencedUnreliableReceiver::new()),
+                        Box::new(SequencedUnreliableReceiver::new(
+                            channel_settings.unresolved_entities,
+                        )),
+                    );
+                }
+                ChannelMode::OrderedUnreliable(settings) => {
+                    channel_receivers.insert(
+                        channel_kind,
+                        Box::new(OrderedUnreliableReceiver::new(
+                            settings,
+                            channel_settings.unresolved_entities,
+                        )),
                     );
                 }
                 ChannelMode::UnorderedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(UnorderedReliableReceiver::new()),
+                        Box::new(
+                            UnorderedReliableReceiver::new()
+                                .with_max_message_size(channel_settings.max_message_size),
+                        ),
+                    );
+                }
+                ChannelMode::ExpiringReliable(_) => {
+                    channel_receivers.insert(
+                        channel_kind,
+                        Box::new(
+                            UnorderedReliableReceiver::expiring()
+                                .with_max_message_size(channel_settings.max_message_size),
+                        ),
                     );
                 }
                 ChannelMode::SequencedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(SequencedReliableReceiver::new()),
+                        Box::new(
+                            SequencedReliableReceiver::new()
+                                .with_max_message_size(channel_settings.max_message_size),
+                        ),
                     );
                 }
                 ChannelMode::OrderedReliable(_) => {
                     channel_receivers.insert(
                         channel_kind.clone(),
-                        Box::new(OrderedReliableReceiver::new()),
+                        Box::new(
+                            OrderedReliableReceiver::new()
+                                .with_max_message_size(channel_settings.max_message_size),
+                        ),
                     );
                 }
                 ChannelMode::TickBuffered(_) => {
@@ -147,7 +211,15 @@ impl MessageManager {
 
         // initialize settings
         let mut channel_settings_map = HashMap::new();
+        let mut coalesce_since = HashMap::new();
+        let mut channel_compressors = HashMap::new();
         for (channel_kind, channel_settings) in channel_kinds.channels() {
+            if channel_settings.coalesce.is_some() && channel_senders.contains_key(&channel_kind) {
+                coalesce_since.insert(channel_kind, None);
+            }
+            if let Some(compression) = &channel_settings.compression {
+                channel_compressors.insert(channel_kind, ChannelCompressor::new(compression));
+            }
             channel_settings_map.insert(channel_kind.clone(), channel_settings);
         }
 
@@ -157,44 +229,176 @@ impl MessageManager {
             channel_settings: channel_settings_map,
             packet_to_message_map: HashMap::new(),
             message_fragmenter: MessageFragmenter::new(),
+            coalesce_since,
+            held_channels: HashSet::new(),
+            dependent_messages: Vec::new(),
+            channel_compressors,
         }
     }
 
     // Outgoing Messages
 
-    /// Queues an Message to be transmitted to the remote host
+    /// Queues an Message to be transmitted to the remote host. Returns an
+    /// error if the channel's send buffer is full, or if the Message is
+    /// larger than the channel's max message size
     pub fn send_message(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         channel_kind: &ChannelKind,
         message: MessageContainer,
-    ) {
-        let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
+    ) -> Result<(), MessageSendError> {
+        let messages = self.prepare_message(message_kinds, converter, channel_kind, message)?;
+        let channel = self.channel_senders.get_mut(channel_kind).unwrap();
+        if channel.reserve(messages.len())? {
+            for message in messages {
+                channel.send_message(message);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Queues a Message to be transmitted to the remote host, which stops
+    /// being retransmitted if it is not delivered within `ttl`. It is then
+    /// returned by `take_expired_messages()`. The channel must be
+    /// ExpiringReliable
+    pub fn send_message_with_ttl(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        ttl: Duration,
+    ) -> Result<(), MessageSendError> {
+        let original_message = message.clone();
+        let messages = self.prepare_message(message_kinds, converter, channel_kind, message)?;
+        let message_count = messages.len();
+        let channel = self.channel_senders.get_mut(channel_kind).unwrap();
+        if channel.reserve(message_count)? {
+            for message in messages {
+                channel.send_message(message);
+            }
+            channel.expire_messages_after(message_count, ttl, original_message);
+        }
+
+        Ok(())
+    }
+
+    /// Queues a Message to be transmitted to the remote host only once the
+    /// Message identified by `dependency`, and everything queued before it on
+    /// that channel, has been delivered. Until then the Message is held back
+    /// and does not count against its channel's send buffer
+    pub fn send_message_ordered_after(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        dependency: &MessageDependency,
+    ) -> Result<(), MessageSendError> {
+        if self.is_delivered(dependency) && !self.has_dependent_messages(channel_kind) {
+            return self.send_message(message_kinds, converter, channel_kind, message);
+        }
+
+        let messages = self.prepare_message(message_kinds, converter, channel_kind, message)?;
+        self.dependent_messages
+            .push((*dependency, *channel_kind, messages));
+
+        Ok(())
+    }
+
+    /// Returns a handle to the Message most recently queued on the given
+    /// channel, or None if the channel does not track delivery of individual
+    /// Messages
+    pub fn last_message_dependency(&self, channel_kind: &ChannelKind) -> Option<MessageDependency> {
+        let channel = self.channel_senders.get(channel_kind)?;
+        let message_index = channel.last_message_index()?;
+        Some(MessageDependency::new(*channel_kind, message_index))
+    }
+
+    // Splits a Message into fragments if it is above the fragmentation limit
+    fn prepare_message(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+    ) -> Result<Vec<MessageContainer>, MessageSendError> {
+        if !self.channel_senders.contains_key(channel_kind) {
             panic!("Channel not configured correctly! Cannot send message.");
-        };
+        }
 
+        let Some(settings) = self.channel_settings.get(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
         let message_bit_length = message.bit_length();
+        if let Some(max_message_size) = settings.max_message_size {
+            if message_bit_length > (max_message_size as u32) * 8 {
+                return Err(MessageSendError::MessageTooLarge);
+            }
+        }
         if message_bit_length > FRAGMENTATION_LIMIT_BITS {
-            let Some(settings) = self.channel_settings.get(channel_kind) else {
-                panic!("Channel not configured correctly! Cannot send message.");
-            };
             if !settings.reliable() {
                 panic!("ERROR: Attempting to send Message above the fragmentation size limit over an unreliable Message channel! Slim down the size of your Message, or send this Message through a reliable message channel.");
             }
 
             // Now fragment this message ...
-            let messages =
-                self.message_fragmenter
-                    .fragment_message(message_kinds, converter, message);
-            for message_fragment in messages {
-                channel.send_message(message_fragment);
-            }
+            Ok(self
+                .message_fragmenter
+                .fragment_message(message_kinds, converter, message))
         } else {
-            channel.send_message(message);
+            Ok(vec![message])
         }
     }
 
+    fn is_delivered(&self, dependency: &MessageDependency) -> bool {
+        match self.channel_senders.get(&dependency.channel_kind()) {
+            Some(channel) => channel.is_delivered(&dependency.message_index()),
+            None => true,
+        }
+    }
+
+    fn has_dependent_messages(&self, channel_kind: &ChannelKind) -> bool {
+        self.dependent_messages
+            .iter()
+            .any(|(_, dependent_channel, _)| dependent_channel == channel_kind)
+    }
+
+    // Moves held Messages whose dependency has been delivered into their
+    // channels. Messages held for the same channel are released in the order
+    // they were sent, so a full channel holds back everything behind it
+    fn release_dependent_messages(&mut self) {
+        let mut blocked_channels = HashSet::new();
+        let mut still_waiting = Vec::new();
+        for (dependency, channel_kind, messages) in std::mem::take(&mut self.dependent_messages) {
+            if blocked_channels.contains(&channel_kind) || !self.is_delivered(&dependency) {
+                blocked_channels.insert(channel_kind);
+                still_waiting.push((dependency, channel_kind, messages));
+                continue;
+            }
+            let channel = self.channel_senders.get_mut(&channel_kind).unwrap();
+            match channel.reserve(messages.len()) {
+                Ok(true) => {
+                    for message in messages {
+                        channel.send_message(message);
+                    }
+                }
+                Ok(false) => {
+                    // the channel's overflow strategy drops this Message
+                }
+                Err(ChannelFullError) => {
+                    blocked_channels.insert(channel_kind);
+                    still_waiting.push((dependency, channel_kind, messages));
+                }
+            }
+        }
+        self.dependent_messages = still_waiting;
+    }
+
+    /// Queues a Request to be transmitted to the remote host. If `timeout` is
+    /// given and no Response arrives within it, the Request is returned by
+    /// `take_timed_out_requests()`
     pub fn send_request(
         &mut self,
         message_kinds: &MessageKinds,
@@ -202,11 +406,43 @@ impl MessageManager {
         channel_kind: &ChannelKind,
         global_request_id: GlobalRequestId,
         request: MessageContainer,
-    ) {
+        timeout: Option<Duration>,
+    ) -> Result<(), MessageSendError> {
         let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
             panic!("Channel not configured correctly! Cannot send message.");
         };
-        channel.send_outgoing_request(message_kinds, converter, global_request_id, request);
+        // a Request is never silently dropped, as it would be left waiting
+        // on a Response forever
+        if !channel.reserve(1)? {
+            return Err(MessageSendError::ChannelFull);
+        }
+        channel.send_outgoing_request(
+            message_kinds,
+            converter,
+            global_request_id,
+            request,
+            timeout,
+        );
+        Ok(())
+    }
+
+    /// Stops waiting for a Response to a Request, so that none is ever
+    /// reported. The Request also stops being retransmitted if it was sent
+    /// over an ExpiringReliable channel. Returns whether the Request was
+    /// awaiting a Response
+    pub fn cancel_request(&mut self, global_request_id: &GlobalRequestId) -> bool {
+        self.channel_senders
+            .values_mut()
+            .any(|channel| channel.cancel_request(global_request_id))
+    }
+
+    /// Returns the Requests which received no Response within their timeout
+    pub fn take_timed_out_requests(&mut self) -> Vec<GlobalRequestId> {
+        let mut output = Vec::new();
+        for channel in self.channel_senders.values_mut() {
+            output.append(&mut channel.take_timed_out_requests());
+        }
+        output
     }
 
     pub fn send_response(
@@ -216,23 +452,91 @@ impl MessageManager {
         channel_kind: &ChannelKind,
         local_response_id: LocalResponseId,
         response: MessageContainer,
-    ) {
+    ) -> Result<(), MessageSendError> {
         let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
             panic!("Channel not configured correctly! Cannot send message.");
         };
+        // nor is a Response, as the remote host is waiting on it
+        if !channel.reserve(1)? {
+            return Err(MessageSendError::ChannelFull);
+        }
         channel.send_outgoing_response(message_kinds, converter, local_response_id, response);
+        Ok(())
+    }
+
+    /// Returns the Requests sent to the remote host which have not yet
+    /// received a Response
+    pub fn pending_requests(&self) -> Vec<PendingRequest> {
+        let mut output = Vec::new();
+        for (channel_kind, channel) in &self.channel_senders {
+            for (local_request_id, request_id, sent_at) in channel.pending_requests() {
+                output.push(PendingRequest::new(
+                    request_id,
+                    local_request_id,
+                    *channel_kind,
+                    sent_at,
+                ));
+            }
+        }
+        output
     }
 
     pub fn collect_outgoing_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.release_dependent_messages();
+
         for channel in self.channel_senders.values_mut() {
             channel.collect_messages(now, rtt_millis);
         }
+
+        self.held_channels.clear();
+        for (channel_kind, since) in &mut self.coalesce_since {
+            let channel = self.channel_senders.get(channel_kind).unwrap();
+            if !channel.has_messages() {
+                *since = None;
+                continue;
+            }
+            let since = since.get_or_insert_with(|| now.clone());
+            let settings = self.channel_settings.get(channel_kind).unwrap();
+            if Self::should_hold(
+                settings.coalesce.as_ref().unwrap(),
+                since,
+                now,
+                channel.as_ref(),
+            ) {
+                self.held_channels.insert(*channel_kind);
+            }
+        }
+    }
+
+    fn should_hold(
+        settings: &CoalesceSettings,
+        since: &Instant,
+        now: &Instant,
+        channel: &dyn MessageChannelSender,
+    ) -> bool {
+        since.elapsed(now) < settings.max_delay
+            && channel.outgoing_bit_length() < settings.flush_bits
+    }
+
+    /// Returns the Messages sent with a time to live which expired before
+    /// being delivered, along with the channel each was sent over
+    pub fn take_expired_messages(&mut self) -> Vec<(ChannelKind, MessageContainer)> {
+        let mut output = Vec::new();
+        for (channel_kind, channel) in &mut self.channel_senders {
+            for message in channel.take_expired_messages() {
+                output.push((*channel_kind, message));
+            }
+        }
+        output
     }
 
     /// Returns whether the Manager has queued Messages that can be transmitted
     /// to the remote host
     pub fn has_outgoing_messages(&self) -> bool {
-        for channel in self.channel_senders.values() {
+        for (channel_kind, channel) in &self.channel_senders {
+            if self.held_channels.contains(channel_kind) {
+                continue;
+            }
             if channel.has_messages() {
                 return true;
             }
@@ -240,6 +544,15 @@ impl MessageManager {
         false
     }
 
+    /// Returns how many Messages, across every channel, are waiting to be
+    /// sent or acknowledged
+    pub fn queued_message_count(&self) -> usize {
+        self.channel_senders
+            .values()
+            .map(|channel| channel.queued_count())
+            .sum()
+    }
+
     pub fn write_messages(
         &mut self,
         protocol: &Protocol,
@@ -249,10 +562,12 @@ impl MessageManager {
         has_written: &mut bool,
     ) {
         for (channel_kind, channel) in &mut self.channel_senders {
-            if !channel.has_messages() {
+            if !channel.has_messages() || self.held_channels.contains(channel_kind) {
                 continue;
             }
 
+            let compressor = self.channel_compressors.get(channel_kind);
+
             // check that we can at least write a ChannelIndex and a MessageContinue bit
             let mut counter = writer.counter();
             // reserve MessageContinue bit
@@ -261,30 +576,50 @@ impl MessageManager {
             counter.write_bit(false);
             // write ChannelIndex
             counter.count_bits(<ChannelKind as ConstBitLength>::const_bit_length());
+            if compressor.is_some() {
+                // write Compressed bit
+                counter.write_bit(false);
+            }
             if counter.overflowed() {
                 break;
             }
 
-            // reserve MessageContinue bit
-            writer.reserve_bits(1);
             // write ChannelContinue bit
             true.ser(writer);
             // write ChannelIndex
             channel_kind.ser(&protocol.channel_kinds, writer);
             // write Messages
-            if let Some(message_indices) =
-                channel.write_messages(&protocol.message_kinds, converter, writer, has_written)
-            {
+            let written_indices = match compressor {
+                Some(compressor) => Self::write_compressed_messages(
+                    compressor,
+                    &protocol.message_kinds,
+                    converter,
+                    channel.as_mut(),
+                    writer,
+                    has_written,
+                ),
+                None => {
+                    // reserve MessageContinue bit
+                    writer.reserve_bits(1);
+                    let written_indices = channel.write_messages(
+                        &protocol.message_kinds,
+                        converter,
+                        writer,
+                        has_written,
+                    );
+                    // write MessageContinue finish bit, release
+                    writer.release_bits(1);
+                    false.ser(writer);
+                    written_indices
+                }
+            };
+            if let Some(message_indices) = written_indices {
                 self.packet_to_message_map
                     .entry(packet_index)
                     .or_insert_with(Vec::new);
                 let channel_list = self.packet_to_message_map.get_mut(&packet_index).unwrap();
                 channel_list.push((channel_kind.clone(), message_indices));
             }
-
-            // write MessageContinue finish bit, release
-            writer.release_bits(1);
-            false.ser(writer);
         }
 
         // write ChannelContinue finish bit, release
@@ -292,6 +627,55 @@ impl MessageManager {
         false.ser(writer);
     }
 
+    // Writes a channel's Messages, along with their MessageContinue finish
+    // bit, into a buffer of their own, then copies the buffer into the packet
+    // compressed if that makes it smaller, or as it is otherwise
+    fn write_compressed_messages(
+        compressor: &ChannelCompressor,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel: &mut dyn MessageChannelSender,
+        writer: &mut BitWriter,
+        has_written: &mut bool,
+    ) -> Option<Vec<MessageIndex>> {
+        // reserve Compressed bit
+        writer.reserve_bits(1);
+        let bit_capacity = writer.bits_free();
+        let mut channel_writer = BitWriter::with_capacity(bit_capacity);
+
+        // reserve MessageContinue bit
+        channel_writer.reserve_bits(1);
+        let written_indices =
+            channel.write_messages(message_kinds, converter, &mut channel_writer, has_written);
+        // write MessageContinue finish bit, release
+        channel_writer.release_bits(1);
+        false.ser(&mut channel_writer);
+
+        let bit_length = bit_capacity - channel_writer.bits_free();
+        let bytes = channel_writer.to_bytes();
+
+        // write Compressed bit, release
+        writer.release_bits(1);
+        match compressor.compress(&bytes) {
+            Some(compressed) if Self::compressed_bit_length(&compressed) < bit_length => {
+                true.ser(writer);
+                UnsignedVariableInteger::<7>::new(compressed.len() as u64).ser(writer);
+                writer.write_bits(&compressed, (compressed.len() * 8) as u32);
+            }
+            _ => {
+                false.ser(writer);
+                writer.write_bits(&bytes, bit_length);
+            }
+        }
+
+        written_indices
+    }
+
+    fn compressed_bit_length(compressed: &[u8]) -> u32 {
+        UnsignedVariableInteger::<7>::new(compressed.len() as u64).bit_length()
+            + (compressed.len() * 8) as u32
+    }
+
     // Incoming Messages
 
     pub fn read_messages<E: Copy + Eq + Hash + Send + Sync>(
@@ -314,12 +698,41 @@ impl MessageManager {
 
             // continue read inside channel
             let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
+            if let Some(compressor) = self.channel_compressors.get(&channel_kind) {
+                let compressed = bool::de(reader)?;
+                if compressed {
+                    let bytes = Self::read_compressed_messages(compressor, reader)?;
+                    let mut channel_reader = BitReader::new(&bytes);
+                    channel.read_messages(
+                        &protocol.message_kinds,
+                        entity_waitlist,
+                        &converter,
+                        &mut channel_reader,
+                    )?;
+                    continue;
+                }
+            }
             channel.read_messages(&protocol.message_kinds, entity_waitlist, &converter, reader)?;
         }
 
         Ok(())
     }
 
+    fn read_compressed_messages(
+        compressor: &ChannelCompressor,
+        reader: &mut BitReader,
+    ) -> Result<Vec<u8>, SerdeErr> {
+        let length = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+        if length > MTU_SIZE_BYTES {
+            return Err(SerdeErr);
+        }
+        let mut compressed = Vec::with_capacity(length);
+        for _ in 0..length {
+            compressed.push(u8::de(reader)?);
+        }
+        compressor.decompress(&compressed).ok_or(SerdeErr)
+    }
+
     /// Retrieve all messages from the channel buffers
     pub fn receive_messages<E: Eq + Copy + Hash>(
         &mut self,
@@ -373,9 +786,12 @@ impl MessageManager {
                     );
                 };
                 for (local_request_id, response) in responses {
-                    let global_request_id = channel_sender
-                        .process_incoming_response(&local_request_id)
-                        .unwrap();
+                    let Some(global_request_id) =
+                        channel_sender.process_incoming_response(&local_request_id)
+                    else {
+                        // the Request was cancelled or timed out
+                        continue;
+                    };
                     response_output.push((global_request_id, response));
                 }
             }
@@ -1,7 +1,10 @@
 pub mod channels;
+pub mod file_transfer;
 pub mod fragment;
+pub mod idempotent_message;
 pub mod message;
 pub mod message_container;
+pub mod message_dependency;
 pub mod message_kinds;
 pub mod message_manager;
 pub mod named;
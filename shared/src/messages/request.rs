@@ -1,6 +1,8 @@
-use std::marker::PhantomData;
+use std::{error::Error, fmt, marker::PhantomData, time::Duration};
 
-use crate::Message;
+use naia_socket_shared::Instant;
+
+use crate::{ChannelKind, LocalRequestId, Message};
 
 // Request
 pub trait Request: Message {
@@ -50,7 +52,25 @@ impl<S: Response> ResponseReceiveKey<S> {
     }
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+// ResponseReceiveError
+/// The reason a Request will never receive a Response
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResponseReceiveError {
+    /// No Response arrived within the timeout the Request was sent with
+    Timeout,
+}
+
+impl fmt::Display for ResponseReceiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Self::Timeout => write!(f, "Request timed out waiting for a Response"),
+        }
+    }
+}
+
+impl Error for ResponseReceiveError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct GlobalRequestId {
     id: u64,
 }
@@ -71,3 +91,55 @@ impl GlobalResponseId {
         Self { id }
     }
 }
+
+// PendingRequest
+/// A Request which has been sent to the remote host, but which has not yet
+/// received a Response
+#[derive(Clone)]
+pub struct PendingRequest {
+    request_id: GlobalRequestId,
+    local_request_id: LocalRequestId,
+    channel_kind: ChannelKind,
+    sent_at: Instant,
+}
+
+impl PendingRequest {
+    pub fn new(
+        request_id: GlobalRequestId,
+        local_request_id: LocalRequestId,
+        channel_kind: ChannelKind,
+        sent_at: Instant,
+    ) -> Self {
+        Self {
+            request_id,
+            local_request_id,
+            channel_kind,
+            sent_at,
+        }
+    }
+
+    /// The id of the Request, matching `ResponseReceiveKey::request_id()`
+    pub fn request_id(&self) -> GlobalRequestId {
+        self.request_id
+    }
+
+    /// The id the Request was sent to the remote host with
+    pub fn local_request_id(&self) -> LocalRequestId {
+        self.local_request_id
+    }
+
+    /// The Channel the Request was sent over
+    pub fn channel_kind(&self) -> ChannelKind {
+        self.channel_kind
+    }
+
+    /// The moment the Request was queued to be sent
+    pub fn sent_at(&self) -> &Instant {
+        &self.sent_at
+    }
+
+    /// How long the Request has been waiting for a Response
+    pub fn age(&self, now: &Instant) -> Duration {
+        self.sent_at.elapsed(now)
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use naia_socket_shared::{LinkConditionerConfig, SocketConfig};
 
@@ -6,16 +6,25 @@ use crate::{
     connection::compression_config::CompressionConfig,
     messages::{
         channels::{
-            channel::{Channel, ChannelDirection, ChannelMode, ChannelSettings},
+            channel::{
+                Channel, ChannelDirection, ChannelMode, ChannelSettings, CoalesceSettings,
+                CompressionSettings, SendBufferSettings, UnresolvedEntityPolicy,
+            },
             channel_kinds::ChannelKinds,
             default_channels::DefaultChannelsPlugin,
+            file_transfer_channel::FileTransferChannel,
             system_channel::SystemChannel,
         },
+        file_transfer::file_transfer_message::FileTransferMessage,
         fragment::FragmentedMessage,
+        idempotent_message::IdempotentMessage,
         message::Message,
         message_kinds::MessageKinds,
     },
-    world::component::{component_kinds::ComponentKinds, replicate::Replicate},
+    world::component::{
+        component_kinds::{ComponentKind, ComponentKinds},
+        replicate::Replicate,
+    },
     EntityEventMessage, ReliableSettings, Request, RequestOrResponse,
 };
 
@@ -37,6 +46,9 @@ pub struct Protocol {
     pub compression: Option<CompressionConfig>,
     /// Whether or not Client Authoritative Entities will be allowed
     pub client_authoritative_entities: bool,
+    /// Components which only the Server may change. Updates to these sent by
+    /// a Client, even one with authority over the Entity, are rejected
+    pub server_authoritative_components: HashSet<ComponentKind>,
     locked: bool,
 }
 
@@ -45,13 +57,19 @@ impl Default for Protocol {
         let mut message_kinds = MessageKinds::new();
         message_kinds.add_message::<FragmentedMessage>();
         message_kinds.add_message::<RequestOrResponse>();
+        message_kinds.add_message::<IdempotentMessage>();
         message_kinds.add_message::<EntityEventMessage>();
+        message_kinds.add_message::<FileTransferMessage>();
 
         let mut channel_kinds = ChannelKinds::new();
         channel_kinds.add_channel::<SystemChannel>(ChannelSettings::new(
             ChannelMode::OrderedReliable(ReliableSettings::default()),
             ChannelDirection::Bidirectional,
         ));
+        channel_kinds.add_channel::<FileTransferChannel>(ChannelSettings::new(
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+            ChannelDirection::Bidirectional,
+        ));
 
         Self {
             channel_kinds,
@@ -61,6 +79,7 @@ impl Default for Protocol {
             tick_interval: Duration::from_millis(50),
             compression: None,
             client_authoritative_entities: false,
+            server_authoritative_components: HashSet::new(),
             locked: false,
         }
     }
@@ -129,6 +148,69 @@ impl Protocol {
         self
     }
 
+    /// Gives a previously added Channel a human-readable name, used to refer
+    /// to it in logs and diagnostics. The name is never sent over the wire.
+    /// Channels are named after their type unless renamed here
+    pub fn name_channel<C: Channel>(&mut self, name: &str) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.set_channel_name::<C>(name);
+        self
+    }
+
+    /// Holds back outgoing Messages on a previously added Channel for up to
+    /// `settings.max_delay`, so that many small Messages can share a packet.
+    /// Channels do not coalesce unless configured to here
+    pub fn coalesce_channel<C: Channel>(&mut self, settings: CoalesceSettings) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.coalesce_channel::<C>(settings);
+        self
+    }
+
+    /// Compresses the Messages a previously added Channel writes into each
+    /// packet, unless they take up fewer than `settings.min_bytes`. Unlike
+    /// `compression()`, which compresses whole packets, this only spends CPU
+    /// on the Channels which carry compressible data. Compression shrinks the
+    /// packets a Channel's Messages are sent in, rather than fitting more
+    /// Messages into each. Channels are not compressed unless configured to
+    /// be here
+    pub fn compress_channel<C: Channel>(&mut self, settings: CompressionSettings) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.set_compression::<C>(settings);
+        self
+    }
+
+    /// Configures whether incoming Messages on a previously added unreliable
+    /// Channel are held until the Entities they reference come into scope.
+    /// Channels wait on their Entities unless configured otherwise here
+    pub fn set_unresolved_entity_policy<C: Channel>(
+        &mut self,
+        policy: UnresolvedEntityPolicy,
+    ) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.set_unresolved_entity_policy::<C>(policy);
+        self
+    }
+
+    /// Bounds the number of outgoing Messages a previously added unreliable
+    /// Channel may hold while waiting to be written. Unreliable Channels are
+    /// unbounded unless configured to be here
+    pub fn set_send_buffer<C: Channel>(&mut self, settings: SendBufferSettings) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.set_send_buffer::<C>(settings);
+        self
+    }
+
+    /// Bounds the size, in bytes, of the Messages a previously added reliable
+    /// Channel carries. Messages above the fragmentation limit are split into
+    /// fragments and reassembled on receipt, and this keeps a remote host from
+    /// making the receiver buffer fragments without limit. Reliable Channels
+    /// are unbounded unless configured to be here
+    pub fn set_max_message_size<C: Channel>(&mut self, bytes: usize) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.set_max_message_size::<C>(bytes);
+        self
+    }
+
     pub fn add_message<M: Message>(&mut self) -> &mut Self {
         self.check_lock();
         self.message_kinds.add_message::<M>();
@@ -149,6 +231,22 @@ impl Protocol {
         self
     }
 
+    /// Adds a Component which only the Server may change. A Client holding
+    /// authority over an Entity can still mutate its other Components, but
+    /// the Server rejects any update it sends for this one
+    pub fn add_server_authoritative_component<C: Replicate>(&mut self) -> &mut Self {
+        self.check_lock();
+        self.component_kinds.add_component::<C>();
+        self.server_authoritative_components
+            .insert(ComponentKind::of::<C>());
+        self
+    }
+
+    pub fn component_is_server_authoritative(&self, component_kind: &ComponentKind) -> bool {
+        self.server_authoritative_components
+            .contains(component_kind)
+    }
+
     pub fn lock(&mut self) {
         self.check_lock();
         self.locked = true;
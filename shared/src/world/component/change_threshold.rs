@@ -0,0 +1,23 @@
+/// Measures how far a Property's value has moved, so that changes smaller than
+/// a threshold can be withheld from replication. Used by Properties marked
+/// with `#[replicate_threshold(..)]`
+pub trait ChangeThreshold {
+    /// Returns whether the distance between this value and the last queued
+    /// value is greater than the given threshold
+    fn exceeds_threshold(&self, baseline: &Self, threshold: f64) -> bool;
+}
+
+macro_rules! impl_change_threshold {
+    ($($type:ty),*) => {
+        $(
+            impl ChangeThreshold for $type {
+                #[allow(trivial_numeric_casts)]
+                fn exceeds_threshold(&self, baseline: &Self, threshold: f64) -> bool {
+                    (*self as f64 - *baseline as f64).abs() > threshold
+                }
+            }
+        )*
+    };
+}
+
+impl_change_threshold!(f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
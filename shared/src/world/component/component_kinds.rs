@@ -115,6 +115,28 @@ impl ComponentKinds {
         return self.kind_to_builder(component_kind).name();
     }
 
+    /// Sorts the given ComponentKinds by the order in which they were
+    /// registered with the Protocol
+    pub fn sort_by_registration(&self, component_kinds: &mut [ComponentKind]) {
+        component_kinds.sort_by_key(|component_kind| self.kind_to_net_id(component_kind));
+    }
+
+    /// Returns a hash of every registered Component's name, in registration
+    /// order. Stable across builds, so it can be persisted alongside data
+    /// serialized with this set of Components
+    pub fn fingerprint(&self) -> u64 {
+        // FNV-1a
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for net_id in 0..self.current_net_id {
+            let name = self.kind_to_name(&self.net_id_to_kind(&net_id));
+            for byte in name.bytes().chain(std::iter::once(0)) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    }
+
     fn net_id_to_kind(&self, net_id: &NetId) -> ComponentKind {
         return *self.net_id_map.get(net_id).expect(
             "Must properly initialize Component with Protocol via `add_component()` function!",
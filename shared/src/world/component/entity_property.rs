@@ -635,6 +635,27 @@ impl EntityProperty {
         }
     }
 
+    /// Migrate freshly-read Remote Property to Host-Owned version. A relation
+    /// which could not be resolved on read becomes empty
+    pub fn host_own(&mut self, mutator_index: u8) {
+        let global_entity = match &self.inner {
+            EntityRelation::RemoteOwned(inner) => inner.global_entity,
+            EntityRelation::RemoteWaiting(_) | EntityRelation::Invalid => None,
+            EntityRelation::HostOwned(_)
+            | EntityRelation::RemotePublic(_)
+            | EntityRelation::Delegated(_)
+            | EntityRelation::Local(_) => {
+                panic!(
+                    "EntityProperty of type: `{:?}` should never be made host-owned.",
+                    self.inner.name()
+                );
+            }
+        };
+        let mut new_inner = HostOwnedRelation::with_mutator(mutator_index);
+        new_inner.global_entity = global_entity;
+        self.inner = EntityRelation::HostOwned(new_inner);
+    }
+
     /// Migrate Host Property to Local version
     pub fn localize(&mut self) {
         match &mut self.inner {
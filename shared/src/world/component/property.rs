@@ -1,5 +1,8 @@
 use log::warn;
-use std::ops::{Deref, DerefMut};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Mutex, MutexGuard},
+};
 
 use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
 
@@ -28,11 +31,62 @@ impl<T: Serde> PropertyImpl<T> {
     }
 }
 
+/// Withholds changes to a Property's value until they move past a threshold
+struct DeadBand<T: Serde> {
+    exceeds: fn(&T, &T) -> bool,
+    // the value last queued or written for update. Writing takes `&self`, so
+    // this is updated through a lock
+    baseline: Mutex<T>,
+}
+
+impl<T: Serde> DeadBand<T> {
+    fn new(exceeds: fn(&T, &T) -> bool, baseline: T) -> Self {
+        Self {
+            exceeds,
+            baseline: Mutex::new(baseline),
+        }
+    }
+
+    fn baseline(&self) -> MutexGuard<'_, T> {
+        self.baseline
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Whether the value has moved past the threshold, in which case it
+    /// becomes the new baseline
+    fn rebase(&self, value: &T) -> bool {
+        let mut baseline = self.baseline();
+        if !(self.exceeds)(value, &baseline) {
+            return false;
+        }
+        *baseline = value.clone();
+        true
+    }
+
+    /// Writes the value if it has moved past the threshold, otherwise the
+    /// baseline, so that changes within the threshold are never replicated
+    fn write(&self, value: &T, writer: &mut dyn BitWrite) {
+        if self.rebase(value) {
+            value.ser(writer);
+        } else {
+            self.baseline().ser(writer);
+        }
+    }
+}
+
+impl<T: Serde> Clone for DeadBand<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.exceeds, self.baseline().clone())
+    }
+}
+
 /// A Property of an Component/Message, that contains data
 /// which must be tracked for updates
 #[derive(Clone)]
 pub struct Property<T: Serde> {
     inner: PropertyImpl<T>,
+    dead_band: Option<DeadBand<T>>,
 }
 
 // should be shared
@@ -41,6 +95,7 @@ impl<T: Serde> Property<T> {
     pub fn new_local(value: T) -> Self {
         Self {
             inner: PropertyImpl::Local(LocalProperty::new(value)),
+            dead_band: None,
         }
     }
 
@@ -48,9 +103,20 @@ impl<T: Serde> Property<T> {
     pub fn host_owned(value: T, mutator_index: u8) -> Self {
         Self {
             inner: PropertyImpl::HostOwned(HostOwnedProperty::new(value, mutator_index)),
+            dead_band: None,
         }
     }
 
+    /// Gives the Property a change threshold, so that its value is only
+    /// replicated once `exceeds(new_value, baseline)` is true, where
+    /// `baseline` is the value last queued or written for update. Smaller
+    /// changes are still applied locally, but not replicated until they
+    /// accumulate past the threshold
+    pub fn with_change_threshold(mut self, exceeds: fn(&T, &T) -> bool) -> Self {
+        self.dead_band = Some(DeadBand::new(exceeds, self.inner().clone()));
+        self
+    }
+
     /// Given a cursor into incoming packet data, initializes the Property with
     /// the synced value
     pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
@@ -58,6 +124,7 @@ impl<T: Serde> Property<T> {
 
         Ok(Self {
             inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
+            dead_band: None,
         })
     }
 
@@ -84,9 +151,10 @@ impl<T: Serde> Property<T> {
     /// Writes contained value into outgoing byte stream
     pub fn write(&self, writer: &mut dyn BitWrite) {
         match &self.inner {
-            PropertyImpl::HostOwned(inner) => {
-                inner.write(writer);
-            }
+            PropertyImpl::HostOwned(inner) => match &self.dead_band {
+                Some(dead_band) => dead_band.write(&inner.inner, writer),
+                None => inner.write(writer),
+            },
             PropertyImpl::RemoteOwned(_) => {
                 panic!("Remote Private Property should never be written.");
             }
@@ -96,9 +164,13 @@ impl<T: Serde> Property<T> {
             PropertyImpl::Local(_) => {
                 panic!("Local Property should never be written.");
             }
-            PropertyImpl::Delegated(inner) => {
-                inner.write(writer);
-            }
+            PropertyImpl::Delegated(inner) => match &self.dead_band {
+                Some(dead_band) => {
+                    inner.check_write();
+                    dead_band.write(&inner.inner, writer);
+                }
+                None => inner.write(writer),
+            },
         }
     }
 
@@ -153,6 +225,31 @@ impl<T: Serde> Property<T> {
         self.inner() == other.inner()
     }
 
+    /// Set the Property's value, queueing it for update. If the Property has a
+    /// change threshold, the update is only queued when the new value is past
+    /// the threshold from the value last queued or written. Assigning through
+    /// `DerefMut` can't see the new value, so always queues an update, but
+    /// the threshold is still applied when the update is written
+    pub fn set(&mut self, value: T) {
+        if let Some(dead_band) = &self.dead_band {
+            if !dead_band.rebase(&value) {
+                match &mut self.inner {
+                    PropertyImpl::HostOwned(inner) => {
+                        inner.inner = value;
+                        return;
+                    }
+                    PropertyImpl::Delegated(inner) => {
+                        inner.check_mutate();
+                        inner.inner = value;
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        **self = value;
+    }
+
     /// Set value to the value of another Property, queues for update if value
     /// changes
     pub fn mirror(&mut self, other: &Self) {
@@ -297,6 +394,29 @@ impl<T: Serde> Property<T> {
         }
     }
 
+    /// Migrate freshly-read Remote Property to Host-Owned version
+    pub fn host_own(&mut self, mutator_index: u8) {
+        match &mut self.inner {
+            PropertyImpl::RemoteOwned(inner) => {
+                let inner_value = inner.inner.clone();
+                self.inner =
+                    PropertyImpl::HostOwned(HostOwnedProperty::new(inner_value, mutator_index));
+            }
+            PropertyImpl::HostOwned(_) => {
+                panic!("Host Property should never be made host-owned twice.");
+            }
+            PropertyImpl::RemotePublic(_) => {
+                panic!("Public Remote Property should never be made host-owned.");
+            }
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be made host-owned.");
+            }
+            PropertyImpl::Delegated(_) => {
+                panic!("Delegated Property should never be made host-owned.");
+            }
+        }
+    }
+
     /// Migrate Host Property to Local version
     pub fn localize(&mut self) {
         match &mut self.inner {
@@ -489,10 +609,14 @@ impl<T: Serde> DelegatedProperty<T> {
     }
 
     pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.check_write();
+        self.inner.ser(writer);
+    }
+
+    fn check_write(&self) {
         if !self.can_write() {
             panic!("Must have Authority over Entity before performing this operation. Current Authority: {:?}", self.auth_accessor.auth_status());
         }
-        self.inner.ser(writer);
     }
 
     pub fn mirror(&mut self, other: &T) {
@@ -501,10 +625,14 @@ impl<T: Serde> DelegatedProperty<T> {
     }
 
     fn mutate(&mut self) {
+        self.check_mutate();
+        let _success = self.mutator.mutate(self.index);
+    }
+
+    fn check_mutate(&self) {
         if !self.can_mutate() {
             panic!("Must request authority to mutate a Delegated Property.");
         }
-        let _success = self.mutator.mutate(self.index);
     }
 
     fn can_mutate(&self) -> bool {
@@ -519,3 +647,60 @@ impl<T: Serde> DelegatedProperty<T> {
         self.auth_accessor.auth_status().can_write()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use naia_serde::{BitReader, BitWriter, Serde};
+
+    use super::Property;
+    use crate::{
+        world::{
+            component::property_mutate::{PropertyMutate, PropertyMutator},
+            delegation::auth_channel::EntityAuthChannel,
+        },
+        HostType,
+    };
+
+    #[derive(Clone)]
+    struct CountingMutator(Arc<AtomicUsize>);
+
+    impl PropertyMutate for CountingMutator {
+        fn mutate(&mut self, _property_index: u8) -> bool {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            true
+        }
+    }
+
+    fn written(property: &Property<f32>) -> f32 {
+        let mut writer = BitWriter::new();
+        property.write(&mut writer);
+        let bytes = writer.to_bytes();
+        f32::de(&mut BitReader::new(&bytes)).unwrap()
+    }
+
+    #[test]
+    fn delegated_property_keeps_its_change_threshold() {
+        let mutations = Arc::new(AtomicUsize::new(0));
+        let mut property = Property::host_owned(0.0_f32, 0)
+            .with_change_threshold(|value, baseline| (value - baseline).abs() >= 0.5);
+        property.set_mutator(&PropertyMutator::new(CountingMutator(mutations.clone())));
+        let (_auth_mutator, auth_accessor) = EntityAuthChannel::new_channel(HostType::Server);
+        property.enable_delegation(&auth_accessor, None);
+
+        property.set(0.2);
+        assert_eq!(mutations.load(Ordering::Relaxed), 0);
+
+        *property = 0.3;
+        assert_eq!(mutations.load(Ordering::Relaxed), 1);
+        assert_eq!(written(&property), 0.0);
+
+        property.set(0.8);
+        assert_eq!(mutations.load(Ordering::Relaxed), 2);
+        assert_eq!(written(&property), 0.8);
+    }
+}
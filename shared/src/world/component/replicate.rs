@@ -1,6 +1,6 @@
 use std::{any::Any, collections::HashSet};
 
-use naia_serde::{BitReader, BitWrite, SerdeErr};
+use naia_serde::{BitCounter, BitReader, BitWrite, SerdeErr};
 
 use crate::{
     messages::named::Named,
@@ -75,6 +75,17 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
         writer: &mut dyn BitWrite,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
     );
+    /// Returns the number of bits the full Component takes up once serialized,
+    /// by writing it into a BitCounter
+    fn serialized_bit_len(
+        &self,
+        component_kinds: &ComponentKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> u32 {
+        let mut counter = BitCounter::new(0, 0, u32::MAX);
+        self.write(component_kinds, &mut counter, converter);
+        counter.bits_needed()
+    }
     /// Write data into an outgoing byte stream, sufficient only to update the
     /// mutated Properties of the Component on the client
     fn write_update(
@@ -113,6 +124,9 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
     fn disable_delegation(&mut self);
     /// Convert to Local Replicate
     fn localize(&mut self);
+    /// Convert a freshly-read Replicate to Host-Owned, so it can be replicated
+    /// from this host
+    fn host_own(&mut self);
 }
 
 cfg_if! {
@@ -162,11 +162,21 @@ impl<E: Copy + Hash + Eq> EntityChannel<E> {
 
         if !self.spawned {
             self.spawned = true;
-            outgoing_actions.push(EntityAction::SpawnEntity(self.entity, components));
+            outgoing_actions.push(EntityAction::SpawnEntity(self.entity, components.clone()));
 
             // pop ALL waiting spawns, despawns, inserts, and removes OLDER than spawn_index
             self.receive_canonical(action_index);
 
+            // components sent with the spawn are inserted along with it, so that a later
+            // remove of one of them isn't buffered forever
+            for component in components {
+                let component_state = self
+                    .components
+                    .entry(component)
+                    .or_insert_with(|| ComponentChannel::new(Some(action_index)));
+                component_state.inserted = true;
+            }
+
             // process any waiting despawns
             if let Some((despawn_index, _)) = self.waiting_despawns.inner.pop_front() {
                 self.receive_despawn_entity_action(despawn_index, outgoing_actions);
@@ -423,3 +433,24 @@ impl<P> OrderedIds<P> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use super::EntityActionReceiver;
+    use crate::{ComponentKind, EntityAction};
+
+    #[test]
+    fn component_spawned_with_entity_can_be_removed() {
+        let kind = ComponentKind::from(TypeId::of::<u8>());
+        let mut receiver = EntityActionReceiver::new();
+        receiver.buffer_action(0, EntityAction::SpawnEntity(1u8, vec![kind]));
+        receiver.buffer_action(1, EntityAction::RemoveComponent(1u8, kind));
+
+        let actions = receiver.receive_actions();
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(actions[0], EntityAction::SpawnEntity(1, _)));
+        assert!(matches!(actions[1], EntityAction::RemoveComponent(1, removed) if removed == kind));
+    }
+}
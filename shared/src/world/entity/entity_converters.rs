@@ -16,7 +16,7 @@ use crate::{
         },
         host::mut_channel::MutChannelType,
     },
-    ComponentKind, GlobalDiffHandler, LocalWorldManager, PropertyMutator,
+    ComponentKind, GlobalDiffHandler, LocalWorldManager, PropertyMutator, Tick,
 };
 
 pub trait GlobalWorldManagerType<E: Copy + Eq + Hash>: EntityAndGlobalEntityConverter<E> {
@@ -35,6 +35,17 @@ pub trait GlobalWorldManagerType<E: Copy + Eq + Hash>: EntityAndGlobalEntityConv
     fn get_entity_auth_accessor(&self, entity: &E) -> EntityAuthAccessor;
     fn entity_needs_mutator_for_delegation(&self, entity: &E) -> bool;
     fn entity_is_replicating(&self, entity: &E) -> bool;
+    /// How much an Entity's updates are favored when they cannot all be sent
+    /// at once. Higher values are sent more often
+    fn entity_replication_priority(&self, _entity: &E) -> f32 {
+        1.0
+    }
+    /// Whether an Entity's updates may be sent on the given tick. Entities
+    /// may be scheduled to only send updates on some ticks, in order to
+    /// spread many Entities' updates across the tick cycle
+    fn entity_update_is_due(&self, _entity: &E, _tick: &Tick) -> bool {
+        true
+    }
 }
 
 pub trait EntityAndGlobalEntityConverter<E: Copy + Eq + Hash> {
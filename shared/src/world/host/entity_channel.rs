@@ -153,7 +153,9 @@ impl EntityChannel {
                 return true;
             }
             Some(ComponentChannel::Inserting) => {
-                todo!();
+                // the removal is sent once the insert is delivered, see
+                // `WorldChannel::on_remote_insert_component()`
+                return false;
             }
             _ => {
                 return false;
@@ -225,3 +227,26 @@ impl Drop for EntityChannel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use super::EntityChannel;
+    use crate::ComponentKind;
+
+    #[test]
+    fn removal_waits_for_insert_to_be_delivered() {
+        let kind = ComponentKind::from(TypeId::of::<u8>());
+        let mut channel = EntityChannel::new_spawned();
+        channel.insert_component(&kind, false);
+
+        // the insert is still in flight, so no removal is sent yet
+        assert!(!channel.remove_component(&kind));
+        assert!(channel.component_is_inserting(&kind));
+
+        channel.component_insertion_complete(&kind);
+        assert!(channel.remove_component(&kind));
+        assert!(channel.component_is_removing(&kind));
+    }
+}
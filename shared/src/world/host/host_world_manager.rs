@@ -11,7 +11,7 @@ use crate::{
     world::{
         entity::entity_converters::GlobalWorldManagerType, local_world_manager::LocalWorldManager,
     },
-    ComponentKind, DiffMask, EntityAction, HostEntity, Instant, MessageIndex, PacketIndex,
+    ComponentKind, DiffMask, EntityAction, HostEntity, Instant, MessageIndex, PacketIndex, Tick,
     WorldRefType,
 };
 
@@ -36,11 +36,24 @@ pub struct HostWorldManager<E: Copy + Eq + Hash + Send + Sync> {
     pub sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, ComponentKind), DiffMask>)>,
     /// Last [`PacketIndex`] where a component update was written by the server
     pub last_update_packet_index: PacketIndex,
+    /// Whether the remote host is still receiving its initial world. Until it
+    /// has been delivered, spawns take priority over updates
+    catching_up: bool,
+    entity_update_bits_per_tick: Option<u32>,
+    /// Accumulated priority of each Entity with pending updates. Grows every
+    /// tick the Entity's updates are not sent, and resets once they are
+    update_priorities: HashMap<E, f32>,
 }
 
 pub struct HostWorldEvents<E: Copy + Eq + Hash + Send + Sync> {
     pub next_send_actions: VecDeque<(ActionId, EntityActionEvent<E>)>,
     pub next_send_updates: HashMap<E, HashSet<ComponentKind>>,
+    /// Whether entity updates should wait until all pending actions have been
+    /// written, so that the remote host receives its initial world quickly
+    pub prioritize_actions: bool,
+    /// How many more bits of entity updates may be written this tick, if
+    /// limited
+    pub update_bits_remaining: Option<u32>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> HostWorldEvents<E> {
@@ -54,6 +67,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
     pub fn new(
         address: &Option<SocketAddr>,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
+        prioritize_initial_replication: bool,
+        entity_update_bits_per_tick: Option<u32>,
     ) -> Self {
         HostWorldManager {
             // World
@@ -63,6 +78,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
             // Update
             sent_updates: HashMap::new(),
             last_update_packet_index: 0,
+            catching_up: prioritize_initial_replication,
+            entity_update_bits_per_tick,
+            update_priorities: HashMap::new(),
         }
     }
 
@@ -111,10 +129,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
             .host_remove_component(entity, component_kind);
     }
 
+    /// Queues the complete current state of every Component of the Entity to
+    /// be re-sent. Returns false if the Entity is not spawned remotely
+    pub fn resync_entity(&mut self, entity: &E) -> bool {
+        self.world_channel.resync_entity(entity)
+    }
+
     pub fn host_has_entity(&self, entity: &E) -> bool {
         self.world_channel.host_has_entity(entity)
     }
 
+    pub fn host_has_component(&self, entity: &E, component_kind: &ComponentKind) -> bool {
+        self.world_channel
+            .host_has_component(entity, component_kind)
+    }
+
     // used when Remote Entity gains Write Authority (delegation)
     pub fn track_remote_entity(
         &mut self,
@@ -240,14 +269,68 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         now: &Instant,
         rtt_millis: &f32,
+        tick: &Tick,
     ) -> HostWorldEvents<E> {
+        let next_send_actions = self.world_channel.take_next_actions(now, rtt_millis);
+
+        // the initial world has been delivered once there is at least one
+        // entity, and none of them are still waiting to be spawned
+        if self.catching_up
+            && next_send_actions.is_empty()
+            && self.world_channel.has_entities()
+            && !self.world_channel.has_spawning_entities()
+        {
+            self.catching_up = false;
+        }
+
+        let mut next_send_updates = self
+            .world_channel
+            .collect_next_updates(world, global_world_manager);
+
+        // Entities scheduled for a later tick keep their diff masks, and are
+        // collected again once they are due
+        next_send_updates
+            .retain(|entity, _| global_world_manager.entity_update_is_due(entity, tick));
+
+        // accumulate priority for every Entity waiting to send updates
+        self.update_priorities
+            .retain(|entity, _| next_send_updates.contains_key(entity));
+        for entity in next_send_updates.keys() {
+            *self.update_priorities.entry(*entity).or_insert(0.0) +=
+                global_world_manager.entity_replication_priority(entity);
+        }
+
         HostWorldEvents {
-            next_send_actions: self.world_channel.take_next_actions(now, rtt_millis),
-            next_send_updates: self
-                .world_channel
-                .collect_next_updates(world, global_world_manager),
+            next_send_actions,
+            next_send_updates,
+            prioritize_actions: self.catching_up,
+            update_bits_remaining: self.entity_update_bits_per_tick,
         }
     }
+
+    /// Returns the given Entities ordered from highest to lowest accumulated
+    /// priority
+    pub fn entities_by_priority(&self, mut entities: Vec<E>) -> Vec<E> {
+        let priority = |entity: &E| self.update_priorities.get(entity).copied().unwrap_or(0.0);
+        entities.sort_by(|a, b| priority(b).total_cmp(&priority(a)));
+        entities
+    }
+
+    /// Called once all pending updates for an Entity have been written
+    pub fn reset_update_priority(&mut self, entity: &E) {
+        self.update_priorities.remove(entity);
+    }
+
+    /// Returns how many Entities have updates which are still waiting to be
+    /// written, having been deferred by the update budget
+    pub fn update_backlog(&self) -> usize {
+        self.update_priorities.len()
+    }
+
+    /// Returns whether the remote host is still receiving its initial world
+    pub fn is_catching_up(&self) -> bool {
+        self.catching_up
+    }
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
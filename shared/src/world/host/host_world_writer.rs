@@ -44,6 +44,16 @@ impl HostWorldWriter {
         host_manager: &mut HostWorldManager<E>,
         world_events: &mut HostWorldEvents<E>,
     ) {
+        // while the remote host is catching up, updates wait until every
+        // pending action has been written
+        let mut deferred_updates = HashMap::new();
+        let next_send_updates =
+            if world_events.prioritize_actions && !world_events.next_send_actions.is_empty() {
+                &mut deferred_updates
+            } else {
+                &mut world_events.next_send_updates
+            };
+
         // write entity updates
         Self::write_updates(
             component_kinds,
@@ -55,7 +65,8 @@ impl HostWorldWriter {
             local_world_manager,
             has_written,
             host_manager,
-            &mut world_events.next_send_updates,
+            next_send_updates,
+            &mut world_events.update_bits_remaining,
         );
 
         // write entity actions
@@ -238,7 +249,6 @@ impl HostWorldWriter {
                 if !host_manager
                     .world_channel
                     .entity_channel_is_open(world_entity)
-                    || !world.has_component_of_kind(world_entity, component)
                 {
                     EntityActionType::Noop.ser(writer);
 
@@ -252,6 +262,20 @@ impl HostWorldWriter {
                             EntityAction::Noop,
                         );
                     }
+                } else if !world.has_component_of_kind(world_entity, component) {
+                    EntityActionType::Noop.ser(writer);
+
+                    // the Component was removed while being inserted, record the insert anyway
+                    // so that its delivery sends the pending removal
+                    if is_writing {
+                        // add it to action record
+                        Self::record_action_written(
+                            &mut host_manager.sent_action_packets,
+                            packet_index,
+                            action_id,
+                            EntityAction::InsertComponent(*world_entity, *component),
+                        );
+                    }
                 } else {
                     EntityActionType::InsertComponent.ser(writer);
 
@@ -387,10 +411,20 @@ impl HostWorldWriter {
         has_written: &mut bool,
         host_manager: &mut HostWorldManager<E>,
         next_send_updates: &mut HashMap<E, HashSet<ComponentKind>>,
+        update_bits_remaining: &mut Option<u32>,
     ) {
-        let all_update_entities: Vec<E> = next_send_updates.keys().copied().collect();
+        let all_update_entities =
+            host_manager.entities_by_priority(next_send_updates.keys().copied().collect());
 
         for entity in all_update_entities {
+            if *update_bits_remaining == Some(0) {
+                // out of budget for this tick, remaining updates stay in their
+                // diff masks and will be collected again next tick
+                next_send_updates.clear();
+                break;
+            }
+            let bits_free_before = writer.bits_free();
+
             // get LocalEntity
             let host_entity = local_world_manager.entity_to_host_entity(&entity).unwrap();
 
@@ -430,6 +464,11 @@ impl HostWorldWriter {
             // write ComponentContinue finish bit, release
             writer.release_bits(1);
             false.ser(writer);
+
+            if let Some(bits_remaining) = update_bits_remaining {
+                let bits_written = bits_free_before.saturating_sub(writer.bits_free());
+                *bits_remaining = bits_remaining.saturating_sub(bits_written);
+            }
         }
 
         // write EntityContinue finish bit, release
@@ -528,6 +567,7 @@ impl HostWorldWriter {
         }
         if update_kinds.is_empty() {
             next_send_updates.remove(entity);
+            host_manager.reset_update_priority(entity);
         }
     }
 
@@ -82,6 +82,20 @@ impl<E: Copy + Eq + Hash> UserDiffHandler<E> {
         receiver.or_mask(other_mask);
     }
 
+    /// Marks every Property of the Component as changed, so that its full
+    /// state is written with the next update
+    pub fn fill_diff_mask(&mut self, entity: &E, component_kind: &ComponentKind) {
+        let Some(receiver) = self.receivers.get_mut(&(*entity, *component_kind)) else {
+            panic!("Should not call this unless we're sure there's a receiver");
+        };
+        let byte_number = receiver.mask().byte_number();
+        let mut full_mask = DiffMask::new(byte_number);
+        for index in 0..(byte_number as u16 * 8) {
+            full_mask.set_bit(index as u8, true);
+        }
+        receiver.or_mask(&full_mask);
+    }
+
     pub fn clear_diff_mask(&mut self, entity: &E, component_kind: &ComponentKind) {
         let Some(receiver) = self.receivers.get_mut(&(*entity, *component_kind)) else {
             panic!("Should not call this unless we're sure there's a receiver");
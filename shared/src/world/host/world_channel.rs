@@ -65,6 +65,23 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         self.host_world.contains_key(entity)
     }
 
+    pub fn host_has_component(&self, entity: &E, component_kind: &ComponentKind) -> bool {
+        if let Some(component_kinds) = self.host_world.get(entity) {
+            return component_kinds.contains(component_kind);
+        }
+        return false;
+    }
+
+    pub fn has_entities(&self) -> bool {
+        self.entity_channels.len() > 0
+    }
+
+    pub fn has_spawning_entities(&self) -> bool {
+        self.entity_channels
+            .iter()
+            .any(|(_, entity_channel)| entity_channel.is_spawning())
+    }
+
     pub fn entity_channel_is_open(&self, entity: &E) -> bool {
         if let Some(entity_channel) = self.entity_channels.get(entity) {
             return entity_channel.is_spawned();
@@ -72,6 +89,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         return false;
     }
 
+    /// Marks every Component of a spawned Entity as fully changed, so that
+    /// their complete state is re-sent. Returns false if the Entity has not
+    /// been spawned on the remote host
+    pub fn resync_entity(&mut self, entity: &E) -> bool {
+        let Some(entity_channel) = self.entity_channels.get(entity) else {
+            return false;
+        };
+        if !entity_channel.is_spawned() {
+            return false;
+        }
+        for component_kind in entity_channel.inserted_components() {
+            if self.diff_handler.has_component(entity, &component_kind) {
+                self.diff_handler.fill_diff_mask(entity, &component_kind);
+            }
+        }
+        true
+    }
+
     pub fn host_component_kinds(&self, entity: &E) -> Vec<ComponentKind> {
         if let Some(component_kinds) = self.host_world.get(entity) {
             component_kinds.iter().cloned().collect()
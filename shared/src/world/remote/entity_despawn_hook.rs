@@ -0,0 +1,13 @@
+use crate::Replicate;
+
+/// Runs custom logic right before a remote Entity is despawned, while the
+/// Entity and its Components still hold their last-known values
+pub trait EntityDespawnHook<E>: Send + Sync {
+    fn before_despawn(&self, entity: &E, components: &[&dyn Replicate]);
+}
+
+impl<E, F: Fn(&E, &[&dyn Replicate]) + Send + Sync> EntityDespawnHook<E> for F {
+    fn before_despawn(&self, entity: &E, components: &[&dyn Replicate]) {
+        self(entity, components)
+    }
+}
@@ -3,12 +3,53 @@ use std::{
     time::Duration,
 };
 
+use log::warn;
 use naia_socket_shared::Instant;
 
 use crate::{KeyGenerator, RemoteEntity};
 
 pub type WaitlistHandle = u16;
 
+/// An item held in the EntityWaitlist until the Entities it references come
+/// into scope
+#[derive(Clone)]
+pub struct WaitlistEntry {
+    handle: WaitlistHandle,
+    awaiting_entities: Vec<RemoteEntity>,
+    queued_at: Instant,
+}
+
+impl WaitlistEntry {
+    /// The handle the item was queued with
+    pub fn handle(&self) -> WaitlistHandle {
+        self.handle
+    }
+
+    /// The Entities referenced by the item which are not yet in scope
+    pub fn awaiting_entities(&self) -> &[RemoteEntity] {
+        &self.awaiting_entities
+    }
+
+    /// The moment the item was queued
+    pub fn queued_at(&self) -> &Instant {
+        &self.queued_at
+    }
+
+    /// How long the item has been waiting on its Entities
+    pub fn age(&self, now: &Instant) -> Duration {
+        self.queued_at.elapsed(now)
+    }
+}
+
+/// A snapshot of how many items a connection's EntityWaitlist is holding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitlistStats {
+    /// Items currently waiting on Entities to come into scope
+    pub waiting: usize,
+    /// Items dropped so far because the waitlist was at capacity
+    pub evicted: u64,
+}
+
 pub struct EntityWaitlist {
     handle_store: KeyGenerator<WaitlistHandle>,
     handle_to_required_entities: HashMap<WaitlistHandle, HashSet<RemoteEntity>>,
@@ -18,10 +59,12 @@ pub struct EntityWaitlist {
     removed_handles: HashSet<WaitlistHandle>,
     handle_ttls: VecDeque<(Instant, WaitlistHandle)>,
     handle_ttl: Duration,
+    capacity: Option<usize>,
+    evicted_count: u64,
 }
 
 impl EntityWaitlist {
-    pub fn new() -> Self {
+    pub fn new(capacity: Option<usize>) -> Self {
         Self {
             handle_to_required_entities: HashMap::new(),
             handle_store: KeyGenerator::new(Duration::from_secs(60)),
@@ -31,6 +74,15 @@ impl EntityWaitlist {
             removed_handles: HashSet::new(),
             handle_ttls: VecDeque::new(),
             handle_ttl: Duration::from_secs(60),
+            capacity,
+            evicted_count: 0,
+        }
+    }
+
+    pub fn stats(&self) -> WaitlistStats {
+        WaitlistStats {
+            waiting: self.handle_ttls.len(),
+            evicted: self.evicted_count,
         }
     }
 
@@ -53,6 +105,21 @@ impl EntityWaitlist {
             return new_handle;
         }
 
+        // make room by dropping the oldest waiting items
+        if let Some(capacity) = self.capacity {
+            while self.handle_ttls.len() >= capacity {
+                let Some((_, handle)) = self.handle_ttls.pop_front() else {
+                    break;
+                };
+                self.removed_handles.insert(handle);
+                self.remove_waiting_handle(&handle);
+                self.evicted_count += 1;
+                warn!(
+                    "EntityWaitlist is at capacity ({capacity}), dropping the oldest waiting item"
+                );
+            }
+        }
+
         for entity in entities {
             if !self.waiting_entity_to_handles.contains_key(entity) {
                 self.waiting_entity_to_handles
@@ -116,6 +183,27 @@ impl EntityWaitlist {
         self.in_scope_entities.remove(entity);
     }
 
+    /// Returns the items still waiting on Entities to come into scope, oldest
+    /// first
+    pub fn waiting_entries(&self) -> Vec<WaitlistEntry> {
+        let mut output = Vec::new();
+        for (queued_at, handle) in &self.handle_ttls {
+            let Some(entities) = self.handle_to_required_entities.get(handle) else {
+                continue;
+            };
+            let awaiting_entities = entities
+                .difference(&self.in_scope_entities)
+                .copied()
+                .collect();
+            output.push(WaitlistEntry {
+                handle: *handle,
+                awaiting_entities,
+                queued_at: queued_at.clone(),
+            });
+        }
+        output
+    }
+
     pub fn remove_waiting_handle(&mut self, handle: &WaitlistHandle) {
         // remove handle from ttl list
         if let Some(ttl_index) = self
@@ -224,3 +312,38 @@ impl<T> WaitlistStore<T> {
         self.items.remove(handle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use naia_socket_shared::Instant;
+
+    use super::{EntityWaitlist, WaitlistStore};
+    use crate::RemoteEntity;
+
+    #[test]
+    fn waiting_entries_list_entities_not_yet_in_scope() {
+        let mut waitlist = EntityWaitlist::new(None);
+        let mut store = WaitlistStore::new();
+        let leader = RemoteEntity::new(1);
+        let follower = RemoteEntity::new(2);
+        waitlist.add_entity(&leader);
+
+        // an item only referencing Entities in scope doesn't wait
+        waitlist.queue(&HashSet::from([leader]), &mut store, "ready");
+        assert!(waitlist.waiting_entries().is_empty());
+
+        let handle = waitlist.queue(&HashSet::from([leader, follower]), &mut store, "waiting");
+        let entries = waitlist.waiting_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].handle(), handle);
+        assert_eq!(entries[0].awaiting_entities(), &[follower]);
+        assert!(entries[0].queued_at() <= &Instant::now());
+
+        waitlist.add_entity(&follower);
+        assert!(waitlist.waiting_entries().is_empty());
+        let ready = waitlist.collect_ready_items(&Instant::now(), &mut store);
+        assert_eq!(ready.map(|items| items.len()), Some(2));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod entity_action_event;
+pub mod entity_despawn_hook;
 pub mod entity_event;
 pub mod entity_waitlist;
 pub mod remote_world_manager;
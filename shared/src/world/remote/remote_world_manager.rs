@@ -11,6 +11,7 @@ use crate::{
         entity::local_entity::RemoteEntity,
         local_world_manager::LocalWorldManager,
         remote::{
+            entity_despawn_hook::EntityDespawnHook,
             entity_event::EntityEvent,
             entity_waitlist::{EntityWaitlist, WaitlistHandle, WaitlistStore},
             remote_world_reader::RemoteWorldEvents,
@@ -30,9 +31,9 @@ pub struct RemoteWorldManager<E: Copy + Eq + Hash + Send + Sync> {
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
-    pub fn new() -> Self {
+    pub fn new(entity_waitlist_capacity: Option<usize>) -> Self {
         Self {
-            entity_waitlist: EntityWaitlist::new(),
+            entity_waitlist: EntityWaitlist::new(entity_waitlist_capacity),
             insert_waitlist_store: WaitlistStore::new(),
             insert_waitlist_map: HashMap::new(),
             update_waitlist_store: WaitlistStore::new(),
@@ -57,6 +58,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
         world: &mut W,
         now: &Instant,
         world_events: RemoteWorldEvents<E>,
+        despawn_hook: Option<&dyn EntityDespawnHook<E>>,
     ) -> Vec<EntityEvent<E>> {
         self.process_updates(
             global_world_manager,
@@ -69,10 +71,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
         self.process_actions(
             global_world_manager,
             local_world_manager,
+            component_kinds,
             world,
             now,
             world_events.incoming_actions,
             world_events.incoming_components,
+            despawn_hook,
         );
 
         std::mem::take(&mut self.outgoing_events)
@@ -82,21 +86,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
     ///
     /// * Emits client events corresponding to any [`EntityAction`] received
     /// Store
+    /// * The Components of a newly spawned Entity are inserted in the order
+    ///   they were registered with the Protocol
     pub fn process_actions<W: WorldMutType<E>>(
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         local_world_manager: &mut LocalWorldManager<E>,
+        component_kinds: &ComponentKinds,
         world: &mut W,
         now: &Instant,
         incoming_actions: Vec<EntityAction<RemoteEntity>>,
         incoming_components: HashMap<(RemoteEntity, ComponentKind), Box<dyn Replicate>>,
+        despawn_hook: Option<&dyn EntityDespawnHook<E>>,
     ) {
         self.process_ready_actions(
             global_world_manager,
             local_world_manager,
+            component_kinds,
             world,
             incoming_actions,
             incoming_components,
+            despawn_hook,
         );
         self.process_waitlist_actions(global_world_manager, local_world_manager, world, now);
     }
@@ -107,14 +117,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
         &mut self,
         global_world_manager: &dyn GlobalWorldManagerType<E>,
         local_world_manager: &mut LocalWorldManager<E>,
+        component_kinds: &ComponentKinds,
         world: &mut W,
         incoming_actions: Vec<EntityAction<RemoteEntity>>,
         mut incoming_components: HashMap<(RemoteEntity, ComponentKind), Box<dyn Replicate>>,
+        despawn_hook: Option<&dyn EntityDespawnHook<E>>,
     ) {
         // execute the action and emit an event
         for action in incoming_actions {
             match action {
-                EntityAction::SpawnEntity(remote_entity, components) => {
+                EntityAction::SpawnEntity(remote_entity, mut components) => {
                     // set up entity
                     let world_entity = world.spawn_entity();
                     local_world_manager.insert_remote_entity(&world_entity, remote_entity);
@@ -122,7 +134,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
                     self.outgoing_events
                         .push(EntityEvent::<E>::SpawnEntity(world_entity));
 
-                    // read component list
+                    // read component list, in a deterministic order
+                    component_kinds.sort_by_registration(&mut components);
                     for component_kind in components {
                         let component = incoming_components
                             .remove(&(remote_entity, component_kind))
@@ -134,6 +147,22 @@ impl<E: Copy + Eq + Hash + Send + Sync> RemoteWorldManager<E> {
                 EntityAction::DespawnEntity(remote_entity) => {
                     let world_entity = local_world_manager.remove_by_remote_entity(&remote_entity);
 
+                    // Let the hook see the Entity before anything is torn down
+                    if let Some(hook) = despawn_hook {
+                        let component_kinds = global_world_manager
+                            .component_kinds(&world_entity)
+                            .unwrap_or_default();
+                        let components: Vec<_> = component_kinds
+                            .iter()
+                            .filter_map(|component_kind| {
+                                world.component_of_kind(&world_entity, component_kind)
+                            })
+                            .collect();
+                        let components: Vec<&dyn Replicate> =
+                            components.iter().map(|component| &**component).collect();
+                        hook.before_despawn(&world_entity, &components);
+                    }
+
                     // Generate event for each component, handing references off just in
                     // case
                     if let Some(component_kinds) =
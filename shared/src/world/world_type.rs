@@ -27,6 +27,8 @@ pub trait WorldRefType<E> {
     fn has_component<R: ReplicatedComponent>(&self, entity: &E) -> bool;
     /// check whether entity contains component, dynamically
     fn has_component_of_kind(&self, entity: &E, component_kind: &ComponentKind) -> bool;
+    /// gets the kinds of all of an Entity's Components
+    fn component_kinds(&self, entity: &E) -> Vec<ComponentKind>;
     /// gets an entity's component
     fn component<'a, R: ReplicatedComponent>(&'a self, entity: &E) -> Option<ReplicaRefWrapper<'a, R>>;
     /// gets an entity's component, dynamically
@@ -48,12 +50,18 @@ pub trait WorldMutType<E>: WorldRefType<E> {
     fn local_duplicate_entity(&mut self, entity: &E) -> E;
     /// make it so one entity has all the same components as another
     fn local_duplicate_components(&mut self, mutable_entity: &E, immutable_entity: &E);
+    /// make it so one entity has the same components as another, for only
+    /// the given kinds of component
+    fn local_duplicate_components_of_kinds(
+        &mut self,
+        mutable_entity: &E,
+        immutable_entity: &E,
+        component_kinds: &[ComponentKind],
+    );
     /// despawn an entity
     fn despawn_entity(&mut self, entity: &E);
 
     // Components
-    /// gets all of an Entity's Components
-    fn component_kinds(&mut self, entity: &E) -> Vec<ComponentKind>;
     /// gets an entity's component
     fn component_mut<'a, R: ReplicatedComponent>(
         &'a mut self,
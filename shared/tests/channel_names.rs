@@ -0,0 +1,57 @@
+mod some_protocol {
+    use naia_shared::Channel;
+
+    #[derive(Channel)]
+    pub struct ChatChannel;
+
+    #[derive(Channel)]
+    pub struct InputChannel;
+}
+
+use naia_shared::{ChannelDirection, ChannelKind, ChannelMode, Protocol};
+
+use some_protocol::{ChatChannel, InputChannel};
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .add_channel::<InputChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .name_channel::<InputChannel>("Player Input")
+        .build()
+}
+
+#[test]
+fn channels_are_named_after_their_type_by_default() {
+    let protocol = protocol();
+
+    assert_eq!(
+        protocol
+            .channel_kinds
+            .kind_to_name(&ChannelKind::of::<ChatChannel>()),
+        "ChatChannel"
+    );
+}
+
+#[test]
+fn channels_can_be_renamed() {
+    let protocol = protocol();
+
+    assert_eq!(
+        protocol
+            .channel_kinds
+            .kind_to_name(&ChannelKind::of::<InputChannel>()),
+        "Player Input"
+    );
+}
+
+#[test]
+#[should_panic]
+fn naming_an_unregistered_channel_panics() {
+    Protocol::builder().name_channel::<ChatChannel>("Chat");
+}
@@ -0,0 +1,86 @@
+mod some_protocol {
+    use naia_shared::{Channel, Message};
+
+    #[derive(Channel)]
+    pub struct ChattyChannel;
+
+    #[derive(Message)]
+    pub struct Chat {
+        pub text: String,
+    }
+}
+
+use std::time::Duration;
+
+use naia_shared::{
+    ChannelDirection, ChannelKind, ChannelMode, CoalesceSettings, FakeEntityConverter, HostType,
+    Instant, MessageContainer, MessageManager, Protocol,
+};
+
+use some_protocol::{Chat, ChattyChannel};
+
+fn setup(flush_bits: u32) -> (Protocol, MessageManager) {
+    let protocol = Protocol::builder()
+        .add_channel::<ChattyChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .coalesce_channel::<ChattyChannel>(CoalesceSettings {
+            max_delay: Duration::from_millis(20),
+            flush_bits,
+        })
+        .add_message::<Chat>()
+        .build();
+    let message_manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+    (protocol, message_manager)
+}
+
+fn send_chat(protocol: &Protocol, message_manager: &mut MessageManager, text: &str) {
+    let message = MessageContainer::from_write(
+        Box::new(Chat {
+            text: text.to_string(),
+        }),
+        &mut FakeEntityConverter,
+    );
+    message_manager
+        .send_message(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &ChannelKind::of::<ChattyChannel>(),
+            message,
+        )
+        .unwrap();
+}
+
+#[test]
+fn holds_messages_until_max_delay() {
+    let (protocol, mut message_manager) = setup(4000);
+    let mut now = Instant::now();
+
+    send_chat(&protocol, &mut message_manager, "hello");
+    message_manager.collect_outgoing_messages(&now, &0.0);
+    assert!(!message_manager.has_outgoing_messages());
+
+    now.add_millis(10);
+    send_chat(&protocol, &mut message_manager, "world");
+    message_manager.collect_outgoing_messages(&now, &0.0);
+    assert!(!message_manager.has_outgoing_messages());
+
+    now.add_millis(10);
+    message_manager.collect_outgoing_messages(&now, &0.0);
+    assert!(message_manager.has_outgoing_messages());
+}
+
+#[test]
+fn releases_messages_once_flush_size_reached() {
+    let (protocol, mut message_manager) = setup(200);
+    let now = Instant::now();
+
+    send_chat(&protocol, &mut message_manager, "hi");
+    message_manager.collect_outgoing_messages(&now, &0.0);
+    assert!(!message_manager.has_outgoing_messages());
+
+    send_chat(&protocol, &mut message_manager, &"a".repeat(32));
+    message_manager.collect_outgoing_messages(&now, &0.0);
+    assert!(message_manager.has_outgoing_messages());
+}
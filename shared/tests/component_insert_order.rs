@@ -0,0 +1,74 @@
+mod some_protocol {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<u16>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Velocity {
+        pub x: Property<u16>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Health {
+        pub value: Property<u8>,
+    }
+}
+
+use naia_shared::{ComponentKind, Protocol};
+
+use some_protocol::{Health, Position, Velocity};
+
+#[test]
+fn spawned_components_are_ordered_by_registration() {
+    let protocol = Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Velocity>()
+        .add_component::<Health>()
+        .build();
+
+    // the order a multi-component Entity's Components arrive in is arbitrary
+    let mut components = vec![
+        ComponentKind::of::<Health>(),
+        ComponentKind::of::<Position>(),
+        ComponentKind::of::<Velocity>(),
+    ];
+    protocol
+        .component_kinds
+        .sort_by_registration(&mut components);
+
+    assert_eq!(
+        components,
+        vec![
+            ComponentKind::of::<Position>(),
+            ComponentKind::of::<Velocity>(),
+            ComponentKind::of::<Health>(),
+        ]
+    );
+}
+
+#[test]
+fn registration_order_is_per_protocol() {
+    let protocol = Protocol::builder()
+        .add_component::<Health>()
+        .add_component::<Position>()
+        .build();
+
+    let mut components = vec![
+        ComponentKind::of::<Position>(),
+        ComponentKind::of::<Health>(),
+    ];
+    protocol
+        .component_kinds
+        .sort_by_registration(&mut components);
+
+    assert_eq!(
+        components,
+        vec![
+            ComponentKind::of::<Health>(),
+            ComponentKind::of::<Position>()
+        ]
+    );
+}
@@ -0,0 +1,112 @@
+mod some_array_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct TransformHolder {
+        pub matrix: Property<[f32; 16]>,
+        pub flags: Property<[bool; 3]>,
+        pub local_cache: [u8; 4],
+    }
+
+    impl TransformHolder {
+        pub fn new(matrix: [f32; 16], flags: [bool; 3]) -> Self {
+            return TransformHolder::new_complete(matrix, flags, [1, 2, 3, 4]);
+        }
+    }
+}
+
+use naia_shared::{BitReader, BitWriter, DiffMask, FakeEntityConverter, Protocol, Replicate};
+
+use some_array_replica::TransformHolder;
+
+fn identity_matrix() -> [f32; 16] {
+    let mut output = [0.0; 16];
+    for index in 0..4 {
+        output[index * 5] = 1.0;
+    }
+    output
+}
+
+#[test]
+fn read_write_array_replica() {
+    // Protocol
+    let protocol = Protocol::builder()
+        .add_component::<TransformHolder>()
+        .build();
+    let component_kinds = protocol.component_kinds;
+
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = TransformHolder::new(identity_matrix(), [true, false, true]);
+
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+
+    let typed_out_1 = out_1.downcast_ref::<TransformHolder>().unwrap();
+    assert_eq!(*typed_out_1.matrix, identity_matrix());
+    assert_eq!(*typed_out_1.flags, [true, false, true]);
+    assert_eq!(in_1.local_cache, [1, 2, 3, 4]);
+    assert_eq!(typed_out_1.local_cache, [0, 0, 0, 0]);
+}
+
+#[test]
+fn read_write_array_replica_update() {
+    // Protocol
+    let protocol = Protocol::builder()
+        .add_component::<TransformHolder>()
+        .build();
+    let component_kinds = protocol.component_kinds;
+
+    // Initial write, to create the remote replica
+    let mut in_1 = TransformHolder::new(identity_matrix(), [false, false, false]);
+
+    let mut writer = BitWriter::new();
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let mut out_1 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly");
+
+    // Mutate the whole matrix
+    let mut translated = identity_matrix();
+    translated[12] = 10.0;
+    translated[13] = -5.5;
+    translated[14] = 0.25;
+    *in_1.matrix = translated;
+
+    // Write only the matrix Property
+    let mut diff_mask = DiffMask::new(in_1.diff_mask_size());
+    diff_mask.set_bit(0, true);
+
+    let mut writer = BitWriter::new();
+    in_1.kind().ser(&component_kinds, &mut writer);
+    in_1.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    // Read & apply
+    let mut reader = BitReader::new(&bytes);
+    let update = component_kinds
+        .read_create_update(&mut reader)
+        .expect("should deserialize correctly");
+    out_1
+        .read_apply_update(&FakeEntityConverter, update)
+        .expect("should apply correctly");
+
+    let typed_out_1 = out_1.to_boxed_any();
+    let typed_out_1 = typed_out_1.downcast_ref::<TransformHolder>().unwrap();
+    assert_eq!(*typed_out_1.matrix, translated);
+    assert_eq!(*typed_out_1.flags, [false, false, false]);
+}
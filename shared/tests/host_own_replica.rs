@@ -0,0 +1,97 @@
+mod some_protocol {
+    use naia_shared::{EntityProperty, Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<u16>,
+        pub y: Property<u16>,
+    }
+
+    impl Position {
+        pub fn new(x: u16, y: u16) -> Self {
+            return Position::new_complete(x, y);
+        }
+    }
+
+    #[derive(Replicate)]
+    pub struct Target {
+        pub entity: EntityProperty,
+    }
+
+    impl Target {
+        pub fn new() -> Self {
+            return Target::new_complete();
+        }
+    }
+}
+
+use naia_shared::{BitReader, BitWriter, FakeEntityConverter, Protocol, Replicate};
+
+use some_protocol::{Position, Target};
+
+fn write_then_read(component: &dyn Replicate, protocol: &Protocol) -> Box<dyn Replicate> {
+    let mut writer = BitWriter::new();
+    component.write(
+        &protocol.component_kinds,
+        &mut writer,
+        &mut FakeEntityConverter,
+    );
+    let bytes = writer.to_bytes();
+    let mut reader = BitReader::new(&bytes);
+    protocol
+        .component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+}
+
+#[test]
+fn host_owned_replica_can_be_mutated_and_rewritten() {
+    let protocol = Protocol::builder().add_component::<Position>().build();
+
+    let mut out = write_then_read(&Position::new(3, 4), &protocol);
+    out.host_own();
+
+    let position = out.to_any_mut().downcast_mut::<Position>().unwrap();
+    *position.x = 5;
+
+    let out = write_then_read(position, &protocol);
+    let position = out.to_any().downcast_ref::<Position>().unwrap();
+    assert_eq!(*position.x, 5);
+    assert_eq!(*position.y, 4);
+}
+
+#[test]
+fn host_owned_replica_keeps_empty_entity_property() {
+    let protocol = Protocol::builder().add_component::<Target>().build();
+
+    let mut out = write_then_read(&Target::new(), &protocol);
+    out.host_own();
+
+    let out = write_then_read(out.as_ref(), &protocol);
+    assert!(out.to_any().downcast_ref::<Target>().is_some());
+}
+
+#[test]
+fn fingerprint_depends_on_component_order() {
+    let protocol_1 = Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Target>()
+        .build();
+    let protocol_2 = Protocol::builder()
+        .add_component::<Position>()
+        .add_component::<Target>()
+        .build();
+    let protocol_3 = Protocol::builder()
+        .add_component::<Target>()
+        .add_component::<Position>()
+        .build();
+
+    assert_eq!(
+        protocol_1.component_kinds.fingerprint(),
+        protocol_2.component_kinds.fingerprint()
+    );
+    assert_ne!(
+        protocol_1.component_kinds.fingerprint(),
+        protocol_3.component_kinds.fingerprint()
+    );
+}
@@ -0,0 +1,109 @@
+mod some_protocol {
+    use naia_shared::{Channel, Message};
+
+    #[derive(Channel)]
+    pub struct SpawnChannel;
+
+    #[derive(Channel)]
+    pub struct ActionChannel;
+
+    #[derive(Message)]
+    pub struct Event {
+        pub text: String,
+    }
+}
+
+use naia_shared::{
+    BitWriter, ChannelDirection, ChannelKind, ChannelMode, FakeEntityConverter, HostType, Instant,
+    MessageContainer, MessageManager, Protocol, ReliableSettings,
+};
+
+use some_protocol::{ActionChannel, Event, SpawnChannel};
+
+// high enough that nothing is resent during a test
+const RTT_MILLIS: f32 = 10000.0;
+
+fn setup() -> (Protocol, MessageManager) {
+    let protocol = Protocol::builder()
+        .add_channel::<SpawnChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedReliable(ReliableSettings::default()),
+        )
+        .add_channel::<ActionChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .add_message::<Event>()
+        .build();
+    let message_manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+    (protocol, message_manager)
+}
+
+fn event(text: &str) -> MessageContainer {
+    MessageContainer::from_write(
+        Box::new(Event {
+            text: text.to_string(),
+        }),
+        &mut FakeEntityConverter,
+    )
+}
+
+fn write_packet(protocol: &Protocol, message_manager: &mut MessageManager, packet_index: u16) {
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    message_manager.write_messages(
+        protocol,
+        &mut FakeEntityConverter,
+        &mut writer,
+        packet_index,
+        &mut has_written,
+    );
+    assert!(has_written);
+}
+
+#[test]
+fn holds_message_until_dependency_delivered() {
+    let (protocol, mut message_manager) = setup();
+    let now = Instant::now();
+
+    message_manager
+        .send_message(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &ChannelKind::of::<SpawnChannel>(),
+            event("spawned"),
+        )
+        .unwrap();
+    let dependency = message_manager
+        .last_message_dependency(&ChannelKind::of::<SpawnChannel>())
+        .unwrap();
+    message_manager
+        .send_message_ordered_after(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &ChannelKind::of::<ActionChannel>(),
+            event("acted"),
+            &dependency,
+        )
+        .unwrap();
+
+    // only the spawn goes out at first
+    message_manager.collect_outgoing_messages(&now, &RTT_MILLIS);
+    write_packet(&protocol, &mut message_manager, 0);
+    message_manager.collect_outgoing_messages(&now, &RTT_MILLIS);
+    assert!(!message_manager.has_outgoing_messages());
+
+    // the action is released once the spawn is acknowledged
+    message_manager.notify_packet_delivered(0);
+    message_manager.collect_outgoing_messages(&now, &RTT_MILLIS);
+    assert!(message_manager.has_outgoing_messages());
+}
+
+#[test]
+fn unreliable_channels_give_no_dependency() {
+    let (_, message_manager) = setup();
+
+    assert!(message_manager
+        .last_message_dependency(&ChannelKind::of::<ActionChannel>())
+        .is_none());
+}
@@ -0,0 +1,108 @@
+mod some_protocol {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct Position {
+        #[replicate_threshold(0.5)]
+        pub x: Property<f32>,
+        pub y: Property<f32>,
+    }
+
+    impl Position {
+        pub fn new(x: f32, y: f32) -> Self {
+            Self::new_complete(x, y)
+        }
+    }
+}
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use naia_shared::{
+    BitReader, BitWriter, Property, PropertyMutate, PropertyMutator, Replicate, Serde,
+};
+
+use some_protocol::Position;
+
+#[derive(Clone)]
+struct CountingMutator {
+    mutations: Arc<AtomicUsize>,
+}
+
+impl PropertyMutate for CountingMutator {
+    fn mutate(&mut self, _property_index: u8) -> bool {
+        self.mutations.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+fn position_with_counter() -> (Position, Arc<AtomicUsize>) {
+    let mutations = Arc::new(AtomicUsize::new(0));
+    let mut position = Position::new(0.0, 0.0);
+    position.set_mutator(&PropertyMutator::new(CountingMutator {
+        mutations: mutations.clone(),
+    }));
+    (position, mutations)
+}
+
+#[test]
+fn sub_threshold_changes_do_not_queue_updates() {
+    let (mut position, mutations) = position_with_counter();
+
+    position.x.set(0.2);
+    position.x.set(0.4);
+
+    assert_eq!(mutations.load(Ordering::Relaxed), 0);
+    assert_eq!(*position.x, 0.4);
+}
+
+#[test]
+fn changes_past_threshold_queue_updates() {
+    let (mut position, mutations) = position_with_counter();
+
+    position.x.set(0.2);
+    position.x.set(0.6);
+    assert_eq!(mutations.load(Ordering::Relaxed), 1);
+
+    // the threshold is now measured from the value last queued
+    position.x.set(0.9);
+    assert_eq!(mutations.load(Ordering::Relaxed), 1);
+    position.x.set(1.2);
+    assert_eq!(mutations.load(Ordering::Relaxed), 2);
+}
+
+// The value a Property writes for update
+fn written(property: &Property<f32>) -> f32 {
+    let mut writer = BitWriter::new();
+    property.write(&mut writer);
+    let bytes = writer.to_bytes();
+    f32::de(&mut BitReader::new(&bytes)).unwrap()
+}
+
+#[test]
+fn changes_through_deref_mut_are_written_once_past_threshold() {
+    let (mut position, mutations) = position_with_counter();
+
+    *position.x = 0.2;
+    assert_eq!(mutations.load(Ordering::Relaxed), 1);
+    assert_eq!(written(&position.x), 0.0);
+
+    *position.x = 0.7;
+    assert_eq!(written(&position.x), 0.7);
+
+    // the threshold is now measured from the value last written
+    *position.x = 1.0;
+    assert_eq!(written(&position.x), 0.7);
+    assert_eq!(*position.x, 1.0);
+}
+
+#[test]
+fn properties_without_threshold_always_queue_updates() {
+    let (mut position, mutations) = position_with_counter();
+
+    position.y.set(0.01);
+
+    assert_eq!(mutations.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,30 @@
+mod some_protocol {
+    use naia_shared::Message;
+
+    #[derive(Message)]
+    pub struct Greeting {
+        pub text: String,
+        pub count: u16,
+    }
+}
+
+use naia_shared::{FakeEntityConverter, MessageContainer, Protocol};
+
+use some_protocol::Greeting;
+
+#[test]
+fn message_container_serialized_bit_len() {
+    let protocol = Protocol::builder().add_message::<Greeting>().build();
+
+    let container = MessageContainer::from_write(
+        Box::new(Greeting {
+            text: "Hello world!".to_string(),
+            count: 3,
+        }),
+        &mut FakeEntityConverter,
+    );
+
+    let counted_bits =
+        container.serialized_bit_len(&protocol.message_kinds, &mut FakeEntityConverter);
+    assert_eq!(counted_bits, container.bit_length());
+}
@@ -0,0 +1,29 @@
+mod some_protocol {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<i16>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Health {
+        pub value: Property<u8>,
+    }
+}
+
+use naia_shared::{ComponentKind, Protocol};
+
+use some_protocol::{Health, Position};
+
+#[test]
+fn only_marked_components_are_server_authoritative() {
+    let protocol = Protocol::builder()
+        .enable_client_authoritative_entities()
+        .add_component::<Position>()
+        .add_server_authoritative_component::<Health>()
+        .build();
+
+    assert!(protocol.component_is_server_authoritative(&ComponentKind::of::<Health>()));
+    assert!(!protocol.component_is_server_authoritative(&ComponentKind::of::<Position>()));
+}
@@ -0,0 +1,46 @@
+mod some_protocol {
+    use naia_shared::Channel;
+
+    #[derive(Channel)]
+    pub struct EffectChannel;
+
+    #[derive(Channel)]
+    pub struct ChatChannel;
+}
+
+use naia_shared::{
+    ChannelDirection, ChannelKind, ChannelMode, Protocol, ReliableSettings, UnresolvedEntityPolicy,
+};
+
+use some_protocol::{ChatChannel, EffectChannel};
+
+#[test]
+fn unreliable_channel_accepts_policy() {
+    let protocol = Protocol::builder()
+        .add_channel::<EffectChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .set_unresolved_entity_policy::<EffectChannel>(UnresolvedEntityPolicy::Drop)
+        .build();
+
+    let settings = protocol
+        .channel_kinds
+        .channels()
+        .into_iter()
+        .find(|(kind, _)| *kind == ChannelKind::of::<EffectChannel>())
+        .map(|(_, settings)| settings)
+        .unwrap();
+    assert_eq!(settings.unresolved_entities, UnresolvedEntityPolicy::Drop);
+}
+
+#[test]
+#[should_panic]
+fn reliable_channel_rejects_policy() {
+    Protocol::builder()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .set_unresolved_entity_policy::<ChatChannel>(UnresolvedEntityPolicy::Deliver);
+}
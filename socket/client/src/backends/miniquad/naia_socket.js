@@ -7,7 +7,7 @@ const naia_socket = {
 
     plugin: function (importObject) {
         importObject.env.naia_is_connected = function () { return naia_socket.is_connected(); };
-        importObject.env.naia_connect = function (address, rtc_path, auth_str) { naia_socket.connect(address, rtc_path, auth_str); };
+        importObject.env.naia_connect = function (address, rtc_path, auth_str, data_channel_init, ice_servers) { naia_socket.connect(address, rtc_path, auth_str, data_channel_init, ice_servers); };
         importObject.env.naia_disconnect = function () { naia_socket.disconnect(); };
         importObject.env.naia_send = function (message) { return naia_socket.send(message); };
         importObject.env.naia_create_string = function (buf, max_len) { return naia_socket.js_create_string(buf, max_len); };
@@ -29,21 +29,18 @@ const naia_socket = {
         }
     },
 
-    connect: function (server_socket_address, rtc_path, auth_str) {
+    connect: function (server_socket_address, rtc_path, auth_str, data_channel_init, ice_servers) {
         let server_socket_address_string = naia_socket.get_js_object(server_socket_address);
         let rtc_path_string = naia_socket.get_js_object(rtc_path);
         let SESSION_ADDRESS = server_socket_address_string + rtc_path_string;
 
+        let ice_servers_list = JSON.parse(naia_socket.get_js_object(ice_servers));
         let peer = new RTCPeerConnection({
-            iceServers: [{
-                urls: ["stun:stun.l.google.com:19302"]
-            }]
+            iceServers: ice_servers_list
         });
 
-        this.channel = peer.createDataChannel("data", {
-            ordered: false,
-            maxRetransmits: 0
-        });
+        let data_channel_init_object = JSON.parse(naia_socket.get_js_object(data_channel_init));
+        this.channel = peer.createDataChannel("data", data_channel_init_object);
 
         this.channel.binaryType = "arraybuffer";
 
@@ -89,6 +86,12 @@ const naia_socket = {
                         }).catch(function(err) {
                             naia_socket.error("error during 'addIceCandidate'", err);
                         });
+                        let public_candidates = response.candidates || [];
+                        public_candidates.forEach(function(public_candidate) {
+                            peer.addIceCandidate(new RTCIceCandidate(public_candidate)).catch(function(err) {
+                                naia_socket.error("error during 'addIceCandidate'", err);
+                            });
+                        });
                     }).catch(function(err) {
                         naia_socket.error("error during 'setRemoteDescription'", err);
                     });
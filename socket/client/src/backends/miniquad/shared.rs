@@ -22,7 +22,13 @@ pub static mut SERVER_ADDR: ServerAddr = ServerAddr::Finding;
 // Javascript methods
 extern "C" {
     pub fn naia_is_connected() -> bool;
-    pub fn naia_connect(server_socket_address: JsObject, rtc_path: JsObject, auth_str: JsObject);
+    pub fn naia_connect(
+        server_socket_address: JsObject,
+        rtc_path: JsObject,
+        auth_str: JsObject,
+        data_channel_init: JsObject,
+        ice_servers: JsObject,
+    );
     pub fn naia_disconnect();
     pub fn naia_send(message: JsObject) -> bool;
     pub fn naia_free_object(js_object: JsObjectWeak);
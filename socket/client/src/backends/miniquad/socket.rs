@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use naia_socket_shared::{parse_server_url, SocketConfig};
+use naia_socket_shared::{parse_server_url, IceServerConfig, SocketConfig};
 
 use crate::{
     backends::socket::SocketTrait, conditioned_packet_receiver::ConditionedPacketReceiver,
@@ -102,6 +102,8 @@ impl Socket {
                 JsObject::string(server_url.to_string().as_str()),
                 JsObject::string(config.rtc_endpoint_path.as_str()),
                 JsObject::string(auth_str.as_str()),
+                JsObject::string(config.data_channel.to_init_json().as_str()),
+                JsObject::string(ice_servers_json(&config.ice_servers).as_str()),
             );
         }
 
@@ -152,3 +154,40 @@ impl SocketTrait for Socket {
         return Self::connect_with_auth(server_session_url, config, auth_bytes);
     }
 }
+
+// Serializes the IceServerConfigs into a JSON array of `RTCIceServer`
+// dictionaries
+fn ice_servers_json(ice_servers: &[IceServerConfig]) -> String {
+    let servers: Vec<String> = ice_servers
+        .iter()
+        .map(|ice_server| {
+            let urls: Vec<String> = ice_server.urls.iter().map(|url| json_string(url)).collect();
+            let mut fields = vec![format!("\"urls\":[{}]", urls.join(","))];
+            if let Some(username) = &ice_server.username {
+                fields.push(format!("\"username\":{}", json_string(username)));
+            }
+            if let Some(credential) = &ice_server.credential {
+                fields.push(format!("\"credential\":{}", json_string(credential)));
+            }
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+    format!("[{}]", servers.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            character if character.is_control() => {
+                output.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => output.push(character),
+        }
+    }
+    output.push('"');
+    output
+}
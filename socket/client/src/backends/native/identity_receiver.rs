@@ -1,8 +1,13 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
 
-use tokio::sync::oneshot;
+use naia_socket_shared::IdentityToken;
+use tokio::sync::oneshot::{self, error::TryRecvError};
 
-use crate::{identity_receiver::IdentityReceiver, IdentityReceiverResult};
+use super::runtime::get_runtime;
+use crate::{identity_receiver::IdentityReceiver, IdentityReceiverResult, NaiaClientSocketError};
 
 /// Handles receiving an IdentityToken from the Server through a given Client Socket
 #[derive(Clone)]
@@ -22,17 +27,95 @@ impl IdentityReceiverImpl {
 
 impl IdentityReceiver for IdentityReceiverImpl {
     fn receive(&mut self) -> IdentityReceiverResult {
-        if let Ok(mut receiver) = self.receiver_channel.lock() {
-            if let Ok(recv_result) = receiver.try_recv() {
-                return match recv_result {
-                    Ok(identity_token) => IdentityReceiverResult::Success(identity_token),
-                    Err(error_code) => IdentityReceiverResult::ErrorResponseCode(error_code),
-                };
-            } else {
-                return IdentityReceiverResult::Waiting;
-            }
+        // a poisoned lock would otherwise leave us Waiting forever, and the
+        // channel is safe to keep polling after a panic elsewhere
+        let mut receiver = self
+            .receiver_channel
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Ok(recv_result) = receiver.try_recv() {
+            return match recv_result {
+                Ok(identity_token) => IdentityReceiverResult::Success(identity_token),
+                Err(error_code) => IdentityReceiverResult::from_error_response(error_code, None),
+            };
         } else {
             return IdentityReceiverResult::Waiting;
         }
     }
+
+    fn receive_blocking(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<IdentityToken>, NaiaClientSocketError> {
+        let mut receiver = self
+            .receiver_channel
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // polling a oneshot Receiver after it has completed panics, so only
+        // wait on it while it is still empty
+        let recv_result = match receiver.try_recv() {
+            Ok(recv_result) => recv_result,
+            Err(TryRecvError::Closed) => return Err(closed_error()),
+            Err(TryRecvError::Empty) => {
+                let receiver = &mut *receiver;
+                // the timer must be created within the runtime's context
+                let timed_recv = async { tokio::time::timeout(timeout, receiver).await };
+                match get_runtime().block_on(timed_recv) {
+                    Ok(Ok(recv_result)) => recv_result,
+                    Ok(Err(_)) => return Err(closed_error()),
+                    Err(_) => return Ok(None),
+                }
+            }
+        };
+
+        match recv_result {
+            Ok(identity_token) => Ok(Some(identity_token)),
+            Err(error_code) => Err(NaiaClientSocketError::Message(format!(
+                "Server did not send an IdentityToken, responded with code {}",
+                error_code
+            ))),
+        }
+    }
+}
+
+fn closed_error() -> NaiaClientSocketError {
+    NaiaClientSocketError::Message(
+        "IdentityToken was already received, or never will be".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use tokio::sync::oneshot;
+
+    use super::IdentityReceiverImpl;
+    use crate::identity_receiver::IdentityReceiver;
+
+    #[test]
+    fn receive_blocking_times_out() {
+        let (_sender, receiver) = oneshot::channel::<Result<String, u16>>();
+        let mut id_receiver = IdentityReceiverImpl::new(receiver);
+
+        let result = id_receiver.receive_blocking(Duration::from_millis(20));
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn receive_blocking_returns_token_sent_before_timeout() {
+        let (sender, receiver) = oneshot::channel::<Result<String, u16>>();
+        let mut id_receiver = IdentityReceiverImpl::new(receiver);
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            let _ = sender.send(Ok("token".to_string()));
+        });
+
+        let result = id_receiver.receive_blocking(Duration::from_secs(5));
+
+        assert_eq!(result.unwrap(), Some("token".to_string()));
+    }
 }
@@ -3,6 +3,7 @@ mod packet_receiver;
 mod packet_sender;
 mod runtime;
 mod socket;
+mod udp;
 
 pub use identity_receiver::IdentityReceiverImpl;
 pub use packet_receiver::PacketReceiverImpl;
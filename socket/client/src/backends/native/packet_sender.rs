@@ -51,3 +51,35 @@ impl PacketSender for PacketSenderImpl {
         let _ = self.disconnect_channel.blocking_send(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use webrtc_unreliable_client::AddrCell;
+
+    use super::PacketSenderImpl;
+    use crate::packet_sender::PacketSender;
+
+    #[test]
+    fn clones_share_one_queue() {
+        let (to_server_sender, mut to_server_receiver) = mpsc::unbounded_channel();
+        let (disconnect_sender, _disconnect_receiver) = mpsc::channel(1);
+
+        let sender: Box<dyn PacketSender> = Box::new(PacketSenderImpl::new(
+            AddrCell::default(),
+            to_server_sender,
+            disconnect_sender,
+        ));
+        let sender_clone = sender.clone();
+
+        sender.send(&[1]).unwrap();
+        sender_clone.send(&[2]).unwrap();
+
+        assert_eq!(to_server_receiver.try_recv().unwrap().as_ref(), &[1]);
+        assert_eq!(to_server_receiver.try_recv().unwrap().as_ref(), &[2]);
+
+        drop(to_server_receiver);
+        assert!(!sender.connected());
+        assert!(!sender_clone.connected());
+    }
+}
@@ -1,11 +1,15 @@
-use naia_socket_shared::{parse_server_url, SocketConfig};
+use naia_socket_shared::{
+    parse_server_url, url_to_socket_addr, IdentityToken, RejectReason, SocketConfig, Transport,
+};
 
+use tokio::sync::oneshot;
 use webrtc_unreliable_client::Socket as RTCSocket;
 
-use super::{packet_receiver::PacketReceiverImpl, packet_sender::PacketSenderImpl};
+use super::{packet_receiver::PacketReceiverImpl, packet_sender::PacketSenderImpl, udp};
 use crate::{
     backends::{native::runtime::get_runtime, socket::SocketTrait},
     conditioned_packet_receiver::ConditionedPacketReceiver,
+    error::HandshakeError,
     identity_receiver::IdentityReceiver,
     packet_receiver::PacketReceiver,
     packet_sender::PacketSender,
@@ -74,6 +78,34 @@ impl Socket {
         );
     }
 
+    /// Connects to the given server address, resolving once the Server has
+    /// accepted the connection and sent back an IdentityToken. Unlike
+    /// `connect`, there is no IdentityReceiver to poll. Packets sent before
+    /// the data channel opens are queued until it does.
+    pub async fn connect_async(
+        server_session_url: &str,
+        config: &SocketConfig,
+    ) -> Result<
+        (
+            Box<dyn PacketSender>,
+            Box<dyn PacketReceiver>,
+            IdentityToken,
+        ),
+        HandshakeError,
+    > {
+        let (id_receiver, packet_sender, packet_receiver) =
+            Self::connect_io(server_session_url, config, None, None);
+
+        match id_receiver.await {
+            Ok(Ok(identity_token)) => Ok((packet_sender, packet_receiver, identity_token)),
+            Ok(Err(error_code)) => match RejectReason::from_response(error_code, None) {
+                Some(reason) => Err(HandshakeError::Rejected(reason)),
+                None => Err(HandshakeError::ErrorResponseCode(error_code)),
+            },
+            Err(_) => Err(HandshakeError::Disconnected),
+        }
+    }
+
     /// Connects to the given server address
     fn connect_inner(
         server_session_url: &str,
@@ -85,12 +117,51 @@ impl Socket {
         Box<dyn PacketSender>,
         Box<dyn PacketReceiver>,
     ) {
+        let (id_receiver, packet_sender, packet_receiver) =
+            Self::connect_io(server_session_url, config, auth_bytes_opt, auth_headers_opt);
+
+        // Setup Identity Receiver
+        let identity_receiver_impl = IdentityReceiverImpl::new(id_receiver);
+        let identity_receiver: Box<dyn IdentityReceiver> = Box::new(identity_receiver_impl);
+
+        return (identity_receiver, packet_sender, packet_receiver);
+    }
+
+    /// Starts connecting to the given server address, returning the channel
+    /// on which the IdentityToken will arrive. With `Transport::Udp` the
+    /// session is requested from the Server's data address directly, and
+    /// auth headers are not sent
+    fn connect_io(
+        server_session_url: &str,
+        config: &SocketConfig,
+        auth_bytes_opt: Option<Vec<u8>>,
+        auth_headers_opt: Option<Vec<(String, String)>>,
+    ) -> (
+        oneshot::Receiver<Result<String, u16>>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        let conditioner_config = config.link_condition.clone();
+
+        if config.transport == Transport::Udp {
+            let server_addr = url_to_socket_addr(&parse_server_url(server_session_url));
+            let (id_receiver, packet_sender, packet_receiver) =
+                udp::connect(server_addr, auth_bytes_opt);
+            let packet_receiver: Box<dyn PacketReceiver> = match &conditioner_config {
+                Some(config) => Box::new(ConditionedPacketReceiver::new(
+                    Box::new(packet_receiver),
+                    config,
+                )),
+                None => Box::new(packet_receiver),
+            };
+            return (id_receiver, Box::new(packet_sender), packet_receiver);
+        }
+
         let server_session_string = format!(
             "{}{}",
             parse_server_url(server_session_url),
             config.rtc_endpoint_path.clone()
         );
-        let conditioner_config = config.link_condition.clone();
 
         let (socket, io) = RTCSocket::new();
         get_runtime().spawn(async move {
@@ -118,11 +189,7 @@ impl Socket {
             }
         };
 
-        // Setup Identity Receiver
-        let identity_receiver_impl = IdentityReceiverImpl::new(io.to_client_id_receiver);
-        let identity_receiver: Box<dyn IdentityReceiver> = Box::new(identity_receiver_impl);
-
-        return (identity_receiver, packet_sender, packet_receiver);
+        return (io.to_client_id_receiver, packet_sender, packet_receiver);
     }
 }
 
@@ -151,3 +218,66 @@ impl SocketTrait for Socket {
         return Self::connect_with_auth(server_session_url, config, auth_bytes);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use naia_socket_shared::{RejectReason, SocketConfig};
+
+    use super::Socket;
+    use crate::{backends::native::runtime::get_runtime, HandshakeError};
+
+    fn connect_to_responder(response: &'static [u8]) -> Result<(), HandshakeError> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 4096];
+            let _ = stream.read(&mut request);
+            stream.write_all(response).unwrap();
+        });
+
+        let config = SocketConfig::new(None, None);
+        get_runtime()
+            .block_on(Socket::connect_async(&server_url, &config))
+            .map(|_| ())
+    }
+
+    #[test]
+    fn connect_async_reports_rejection() {
+        let result =
+            connect_to_responder(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n");
+
+        assert!(matches!(
+            result,
+            Err(HandshakeError::Rejected(RejectReason::Unauthorized))
+        ));
+    }
+
+    #[test]
+    fn connect_async_reports_rejection_reason() {
+        let result = connect_to_responder(
+            b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 11\r\n\r\nserver_full",
+        );
+
+        assert!(matches!(
+            result,
+            Err(HandshakeError::Rejected(RejectReason::ServerFull))
+        ));
+
+        let result = connect_to_responder(
+            b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n",
+        );
+
+        assert!(matches!(
+            result,
+            Err(HandshakeError::ErrorResponseCode(500))
+        ));
+    }
+}
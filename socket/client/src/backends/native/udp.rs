@@ -0,0 +1,346 @@
+use std::{
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::warn;
+use tokio::{
+    net::UdpSocket,
+    sync::{
+        mpsc::{self, error::SendError, Receiver, Sender, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
+};
+
+use naia_socket_shared::UdpPacketKind;
+
+use super::runtime::get_runtime;
+use crate::{
+    error::NaiaClientSocketError, packet_receiver::PacketReceiver, packet_sender::PacketSender,
+    server_addr::ServerAddr,
+};
+
+// How often a session request is sent until the Server answers it
+const SESSION_REQUEST_INTERVAL: Duration = Duration::from_millis(250);
+// Unanswered requests sent before giving up on the Server, about 10 seconds
+const MAX_SESSION_REQUESTS: usize = 40;
+// Reported when the Server never answers, as an HTTP session server would
+// report a request which timed out
+const SESSION_TIMEOUT_STATUS: u16 = 408;
+const MAX_DATAGRAM_BYTES: usize = 1500;
+
+/// Starts a session with the Server over plain UDP, returning the channel on
+/// which the IdentityToken, or the status of the rejection, will arrive
+pub(crate) fn connect(
+    server_addr: SocketAddr,
+    auth_bytes_opt: Option<Vec<u8>>,
+) -> (
+    oneshot::Receiver<Result<String, u16>>,
+    UdpPacketSender,
+    UdpPacketReceiver,
+) {
+    connect_with_max_requests(server_addr, auth_bytes_opt, MAX_SESSION_REQUESTS)
+}
+
+fn connect_with_max_requests(
+    server_addr: SocketAddr,
+    auth_bytes_opt: Option<Vec<u8>>,
+    max_session_requests: usize,
+) -> (
+    oneshot::Receiver<Result<String, u16>>,
+    UdpPacketSender,
+    UdpPacketReceiver,
+) {
+    let (id_sender, id_receiver) = oneshot::channel();
+    let (to_server_sender, to_server_receiver) = mpsc::unbounded_channel();
+    let (to_client_sender, to_client_receiver) = mpsc::unbounded_channel();
+    let (disconnect_sender, disconnect_receiver) = mpsc::channel(1);
+
+    get_runtime().spawn(async move {
+        if let Err(err) = run(
+            server_addr,
+            auth_bytes_opt.unwrap_or_default(),
+            max_session_requests,
+            id_sender,
+            to_server_receiver,
+            to_client_sender,
+            disconnect_receiver,
+        )
+        .await
+        {
+            warn!("UDP session with {} ended. Error: {}", server_addr, err);
+        }
+    });
+
+    (
+        id_receiver,
+        UdpPacketSender {
+            server_addr,
+            sender_channel: to_server_sender,
+            disconnect_channel: disconnect_sender,
+        },
+        UdpPacketReceiver {
+            server_addr,
+            receiver_channel: Arc::new(Mutex::new(to_client_receiver)),
+            receive_buffer: vec![0; MAX_DATAGRAM_BYTES],
+        },
+    )
+}
+
+/// Requests a session until the Server answers, then relays packets until
+/// the Client disconnects
+async fn run(
+    server_addr: SocketAddr,
+    auth_bytes: Vec<u8>,
+    max_session_requests: usize,
+    mut id_sender: oneshot::Sender<Result<String, u16>>,
+    mut to_server_receiver: UnboundedReceiver<Box<[u8]>>,
+    to_client_sender: UnboundedSender<Box<[u8]>>,
+    mut disconnect_receiver: Receiver<()>,
+) -> std::io::Result<()> {
+    let local_addr: SocketAddr = match server_addr {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local_addr).await?;
+    // only datagrams from the Server are received from here on
+    socket.connect(server_addr).await?;
+    let mut buffer = [0; MAX_DATAGRAM_BYTES];
+
+    let session_request = UdpPacketKind::SessionRequest.packet(&auth_bytes);
+    let mut session_requests = 0;
+    let identity_token = loop {
+        if session_requests == max_session_requests {
+            warn!("UDP session request to {} was never answered", server_addr);
+            let _ = id_sender.send(Err(SESSION_TIMEOUT_STATUS));
+            return Ok(());
+        }
+        socket.send(&session_request).await?;
+        session_requests += 1;
+
+        let answer = tokio::select! {
+            received = tokio::time::timeout(SESSION_REQUEST_INTERVAL, socket.recv(&mut buffer)) => {
+                match received {
+                    Ok(length) => UdpPacketKind::split(&buffer[..length?]),
+                    // no answer yet, so ask again
+                    Err(_) => None,
+                }
+            }
+            _ = id_sender.closed() => return Ok(()),
+            // there's no session to end yet, and the Server forgets
+            // unanswered requests on its own
+            _ = disconnect_receiver.recv() => return Ok(()),
+        };
+        match answer {
+            Some((UdpPacketKind::SessionAccept, payload)) => {
+                let identity_token = String::from_utf8_lossy(payload).into_owned();
+                let _ = id_sender.send(Ok(identity_token));
+                // proves the Disconnect comes from this Client
+                break payload.to_vec();
+            }
+            Some((UdpPacketKind::SessionReject, payload)) => {
+                let status = match payload {
+                    [high, low, ..] => u16::from_be_bytes([*high, *low]),
+                    _ => 401,
+                };
+                let _ = id_sender.send(Err(status));
+                return Ok(());
+            }
+            _ => {}
+        }
+    };
+
+    loop {
+        tokio::select! {
+            received = socket.recv(&mut buffer) => {
+                if let Some((UdpPacketKind::Data, payload)) = UdpPacketKind::split(&buffer[..received?]) {
+                    if to_client_sender.send(payload.into()).is_err() {
+                        break;
+                    }
+                }
+            }
+            to_server = to_server_receiver.recv() => {
+                let Some(payload) = to_server else {
+                    // every PacketSender was dropped
+                    break;
+                };
+                socket.send(&UdpPacketKind::Data.packet(&payload)).await?;
+            }
+            _ = disconnect_receiver.recv() => break,
+        }
+    }
+
+    socket
+        .send(&UdpPacketKind::Disconnect.packet(&identity_token))
+        .await?;
+    Ok(())
+}
+
+/// Handles sending messages to the Server over plain UDP
+#[derive(Clone)]
+pub struct UdpPacketSender {
+    server_addr: SocketAddr,
+    sender_channel: UnboundedSender<Box<[u8]>>,
+    disconnect_channel: Sender<()>,
+}
+
+impl PacketSender for UdpPacketSender {
+    /// Send a Packet to the Server
+    fn send(&self, payload: &[u8]) -> Result<(), NaiaClientSocketError> {
+        self.sender_channel
+            .send(payload.into())
+            .map_err(|_err: SendError<_>| NaiaClientSocketError::SendError)
+    }
+
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(self.server_addr)
+    }
+
+    fn connected(&self) -> bool {
+        !self.sender_channel.is_closed()
+    }
+
+    fn disconnect(&mut self) {
+        let _ = self.disconnect_channel.try_send(());
+    }
+}
+
+/// Handles receiving messages from the Server over plain UDP
+#[derive(Clone)]
+pub struct UdpPacketReceiver {
+    server_addr: SocketAddr,
+    receiver_channel: Arc<Mutex<UnboundedReceiver<Box<[u8]>>>>,
+    receive_buffer: Vec<u8>,
+}
+
+impl PacketReceiver for UdpPacketReceiver {
+    fn receive(&mut self) -> Result<Option<&[u8]>, NaiaClientSocketError> {
+        if let Ok(mut receiver) = self.receiver_channel.lock() {
+            if let Ok(bytes) = receiver.try_recv() {
+                let length = bytes.len();
+                self.receive_buffer[..length].clone_from_slice(&bytes);
+                return Ok(Some(&self.receive_buffer[..length]));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the Server's Socket address
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Found(self.server_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::UdpSocket, thread, time::Duration};
+
+    use naia_socket_shared::UdpPacketKind;
+
+    use super::{connect, connect_with_max_requests};
+    use crate::{
+        backends::native::runtime::get_runtime, packet_receiver::PacketReceiver,
+        packet_sender::PacketSender,
+    };
+
+    #[test]
+    fn session_is_requested_until_accepted_then_relays_packets() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let (id_receiver, mut packet_sender, mut packet_receiver) =
+            connect(server.local_addr().unwrap(), Some(b"player".to_vec()));
+        // queued until the session is accepted
+        packet_sender.send(b"early").unwrap();
+
+        let mut buffer = [0; 1500];
+        // ignore the first request, as if it were lost
+        let (length, client_addr) = server.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], b"\x01player");
+        let (length, client_addr_again) = server.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], b"\x01player");
+        assert_eq!(client_addr, client_addr_again);
+
+        server
+            .send_to(&UdpPacketKind::SessionAccept.packet(b"token"), client_addr)
+            .unwrap();
+        let identity_token = get_runtime().block_on(id_receiver).unwrap();
+        assert_eq!(identity_token, Ok("token".to_string()));
+
+        // later session requests may still arrive before the queued packet
+        let data = loop {
+            let (length, _) = server.recv_from(&mut buffer).unwrap();
+            if buffer[0] == UdpPacketKind::Data as u8 {
+                break buffer[1..length].to_vec();
+            }
+        };
+        assert_eq!(data, b"early");
+
+        server
+            .send_to(&UdpPacketKind::Data.packet(b"hello"), client_addr)
+            .unwrap();
+        let received = (0..500).find_map(|_| {
+            let received = packet_receiver.receive().unwrap().map(<[u8]>::to_vec);
+            if received.is_none() {
+                thread::sleep(Duration::from_millis(10));
+            }
+            received
+        });
+        assert_eq!(received.as_deref(), Some(&b"hello"[..]));
+
+        packet_sender.disconnect();
+        let (length, _) = server.recv_from(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..length],
+            &UdpPacketKind::Disconnect.packet(b"token")[..]
+        );
+    }
+
+    #[test]
+    fn rejection_status_is_reported() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let (id_receiver, _packet_sender, _packet_receiver) =
+            connect(server.local_addr().unwrap(), None);
+
+        let mut buffer = [0; 1500];
+        let (length, client_addr) = server.recv_from(&mut buffer).unwrap();
+        assert_eq!(&buffer[..length], &[UdpPacketKind::SessionRequest as u8]);
+
+        let mut payload = 503u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(b"server_full");
+        server
+            .send_to(&UdpPacketKind::SessionReject.packet(&payload), client_addr)
+            .unwrap();
+
+        assert_eq!(get_runtime().block_on(id_receiver).unwrap(), Err(503));
+    }
+
+    #[test]
+    fn unanswered_session_requests_time_out() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let (id_receiver, _packet_sender, _packet_receiver) =
+            connect_with_max_requests(server.local_addr().unwrap(), None, 3);
+
+        let mut buffer = [0; 1500];
+        for _ in 0..3 {
+            let (length, _) = server.recv_from(&mut buffer).unwrap();
+            assert_eq!(&buffer[..length], &[UdpPacketKind::SessionRequest as u8]);
+        }
+
+        assert_eq!(get_runtime().block_on(id_receiver).unwrap(), Err(408));
+        // no more requests are sent once the Client gives up
+        server
+            .set_read_timeout(Some(Duration::from_millis(600)))
+            .unwrap();
+        assert!(server.recv_from(&mut buffer).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use js_sys::{Map, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::RtcPeerConnection;
+
+use crate::candidate_pair::{Candidate, CandidatePair, CandidateType};
+
+use super::shared_cell::SharedCell;
+
+// CandidatePairCell
+#[derive(Clone)]
+pub struct CandidatePairCell {
+    cell: SharedCell<Option<CandidatePair>>,
+}
+
+impl CandidatePairCell {
+    pub fn new() -> Self {
+        Self {
+            cell: SharedCell::new(None),
+        }
+    }
+
+    pub fn get(&self) -> Option<CandidatePair> {
+        match self.cell.try_borrow_mut() {
+            Some(pair) => pair.clone(),
+            None => None,
+        }
+    }
+
+    /// Requests the peer connection's stats, storing the selected candidate
+    /// pair once they resolve
+    pub fn refresh(&self, peer: &RtcPeerConnection) {
+        let cell = self.cell.clone();
+        let stats_func: Box<dyn FnMut(JsValue)> = Box::new(move |report: JsValue| {
+            if let Some(pair) = selected_candidate_pair(report.unchecked_ref()) {
+                *cell.borrow_mut() = Some(pair);
+            }
+        });
+        let stats_callback = Closure::wrap(stats_func);
+        let _ = peer.get_stats().then(&stats_callback);
+        stats_callback.forget();
+    }
+}
+
+fn selected_candidate_pair(report: &Map) -> Option<CandidatePair> {
+    // Chromium & Safari report the selected pair on the transport stats
+    let mut pair_id: Option<JsValue> = None;
+    report.for_each(&mut |stats, _| {
+        if pair_id.is_none() && stats_str(&stats, "type").as_deref() == Some("transport") {
+            pair_id = stats_value(&stats, "selectedCandidatePairId");
+        }
+    });
+
+    // Firefox has no transport stats, so fall back to the nominated pair
+    if pair_id.is_none() {
+        report.for_each(&mut |stats, id| {
+            if pair_id.is_none()
+                && stats_str(&stats, "type").as_deref() == Some("candidate-pair")
+                && stats_value(&stats, "nominated").and_then(|value| value.as_bool()) == Some(true)
+                && stats_str(&stats, "state").as_deref() == Some("succeeded")
+            {
+                pair_id = Some(id);
+            }
+        });
+    }
+
+    let pair = report.get(&pair_id?);
+    let local = candidate(&report.get(&stats_value(&pair, "localCandidateId")?))?;
+    let remote = candidate(&report.get(&stats_value(&pair, "remoteCandidateId")?))?;
+
+    Some(CandidatePair { local, remote })
+}
+
+fn candidate(stats: &JsValue) -> Option<Candidate> {
+    let candidate_type = CandidateType::from_stats_str(&stats_str(stats, "candidateType")?)?;
+    // older implementations name the address field "ip"
+    let address = stats_str(stats, "address").or_else(|| stats_str(stats, "ip"));
+    let port = stats_value(stats, "port")
+        .and_then(|value| value.as_f64())
+        .map(|port| port as u16);
+    let protocol = stats_str(stats, "protocol");
+
+    Some(Candidate {
+        candidate_type,
+        address,
+        port,
+        protocol,
+    })
+}
+
+fn stats_value(stats: &JsValue, key: &str) -> Option<JsValue> {
+    let value = Reflect::get(stats, &JsValue::from(key)).ok()?;
+    if value.is_undefined() || value.is_null() {
+        return None;
+    }
+    Some(value)
+}
+
+fn stats_str(stats: &JsValue, key: &str) -> Option<String> {
+    stats_value(stats, key)?.as_string()
+}
@@ -1,6 +1,6 @@
 extern crate log;
 
-use std::{cell::RefCell, net::SocketAddr, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, net::SocketAddr, rc::Rc};
 
 use js_sys::{Array, Object, Reflect};
 use log::info;
@@ -14,20 +14,27 @@ use web_sys::{
 
 use naia_socket_shared::{parse_server_url, IdentityToken, SocketConfig};
 
-use super::{addr_cell::AddrCell, data_port::DataPort};
+use super::{
+    addr_cell::AddrCell, candidate_pair_cell::CandidatePairCell, data_port::DataPort,
+    handshake_cell::HandshakeCell, web_transport::WebTransportSession,
+};
 use crate::{IdentityReceiverImpl, ServerAddr};
 
 // FindAddrFuncInner
 pub struct FindAddrFuncInner(pub Box<dyn FnMut(SocketAddr)>);
 
 // PeerConnection
+#[derive(Clone)]
 pub struct DataChannel {
+    config: SocketConfig,
     server_session_url: String,
     auth_bytes_opt: Option<Vec<u8>>,
     auth_headers_opt: Option<Vec<(String, String)>>,
     message_channel: MessageChannel,
     addr_cell: AddrCell,
+    candidate_pair_cell: CandidatePairCell,
     id_cell: IdentityReceiverImpl,
+    handshake_cell: HandshakeCell,
     find_addr_func: Rc<RefCell<FindAddrFuncInner>>,
 }
 
@@ -39,14 +46,18 @@ impl DataChannel {
         auth_headers_opt: Option<Vec<(String, String)>>,
     ) -> Self {
         let server_url = parse_server_url(server_session_url);
+        let handshake_cell = HandshakeCell::new();
 
         Self {
+            config: config.clone(),
             server_session_url: format!("{}{}", server_url, config.rtc_endpoint_path.clone()),
             auth_bytes_opt,
             auth_headers_opt,
             message_channel: MessageChannel::new().expect("can't create message channel"),
             addr_cell: AddrCell::new(),
-            id_cell: IdentityReceiverImpl::new(),
+            candidate_pair_cell: CandidatePairCell::new(),
+            id_cell: IdentityReceiverImpl::new(handshake_cell.clone()),
+            handshake_cell,
             find_addr_func: Rc::new(RefCell::new(FindAddrFuncInner(Box::new(move |_| {})))),
         }
     }
@@ -55,14 +66,26 @@ impl DataChannel {
         self.addr_cell.clone()
     }
 
+    pub fn candidate_pair_cell(&self) -> CandidatePairCell {
+        self.candidate_pair_cell.clone()
+    }
+
     pub fn data_port(&self) -> DataPort {
         DataPort::new(self.message_channel.port1())
     }
 
+    pub fn data_port_with_packet_handler(&self, handler: Box<dyn FnMut(&[u8])>) -> DataPort {
+        DataPort::with_packet_handler(self.message_channel.port1(), handler)
+    }
+
     pub fn id_receiver(&self) -> IdentityReceiverImpl {
         self.id_cell.clone()
     }
 
+    pub fn handshake_cell(&self) -> HandshakeCell {
+        self.handshake_cell.clone()
+    }
+
     pub fn on_find_addr(&mut self, func: Box<dyn FnMut(SocketAddr)>) {
         self.find_addr_func
             .as_ref()
@@ -71,21 +94,67 @@ impl DataChannel {
             .0 = func;
     }
 
-    #[allow(unused_must_use)]
+    /// Opens a WebTransport session if the Server has a WebTransport address
+    /// and the browser supports it, falling back to requesting a WebRTC
+    /// session otherwise
     pub fn start(&self) {
-        // Set up Ice Servers
-        let ice_server_config_urls = Array::new();
-        ice_server_config_urls.push(&JsValue::from("stun:stun.l.google.com:19302"));
+        if let Some(webtransport_url) = &self.config.webtransport_url {
+            if self.auth_headers_opt.is_some() {
+                info!("Auth headers can't be sent over WebTransport, using WebRTC");
+            } else if !WebTransportSession::is_supported() {
+                info!("WebTransport is not supported by this browser, using WebRTC");
+            } else {
+                let data_channel = self.clone();
+                WebTransportSession::new(
+                    &self.config,
+                    webtransport_url,
+                    self.auth_bytes_opt.as_deref(),
+                    self.message_channel.port2(),
+                    self.addr_cell.clone(),
+                    self.id_cell.clone(),
+                    self.handshake_cell.clone(),
+                )
+                .start(Box::new(move || data_channel.start_webrtc()));
+                return;
+            }
+        }
 
-        let ice_server_config = Object::new();
-        Reflect::set(
-            &ice_server_config,
-            &JsValue::from("urls"),
-            &JsValue::from(&ice_server_config_urls),
-        );
+        self.start_webrtc();
+    }
 
+    #[allow(unused_must_use)]
+    fn start_webrtc(&self) {
+        // Set up Ice Servers
         let ice_server_config_list = Array::new();
-        ice_server_config_list.push(&ice_server_config);
+        for ice_server in &self.config.ice_servers {
+            let ice_server_config_urls = Array::new();
+            for url in &ice_server.urls {
+                ice_server_config_urls.push(&JsValue::from(url.as_str()));
+            }
+
+            let ice_server_config = Object::new();
+            Reflect::set(
+                &ice_server_config,
+                &JsValue::from("urls"),
+                &JsValue::from(&ice_server_config_urls),
+            );
+            if let Some(username) = &ice_server.username {
+                Reflect::set(
+                    &ice_server_config,
+                    &JsValue::from("username"),
+                    &JsValue::from(username.as_str()),
+                );
+            }
+            if let Some(credential) = &ice_server.credential {
+                Reflect::set(
+                    &ice_server_config,
+                    &JsValue::from("credential"),
+                    &JsValue::from(credential.as_str()),
+                );
+            }
+
+            ice_server_config_list.push(&ice_server_config);
+        }
 
         // Set up RtcConfiguration
         let mut peer_config: RtcConfiguration = RtcConfiguration::new();
@@ -95,8 +164,14 @@ impl DataChannel {
         match RtcPeerConnection::new_with_configuration(&peer_config) {
             Ok(peer) => {
                 let mut data_channel_config: RtcDataChannelInit = RtcDataChannelInit::new();
-                data_channel_config.ordered(false);
-                data_channel_config.max_retransmits(0);
+                data_channel_config.ordered(self.config.data_channel.ordered());
+                if let Some(max_retransmits) = self.config.data_channel.max_retransmits() {
+                    data_channel_config.max_retransmits(max_retransmits);
+                }
+                if let Some(max_packet_life_time) = self.config.data_channel.max_packet_life_time()
+                {
+                    data_channel_config.max_packet_life_time(max_packet_life_time);
+                }
 
                 let channel: RtcDataChannel =
                     peer.create_data_channel_with_data_channel_dict("data", &data_channel_config);
@@ -109,6 +184,26 @@ impl DataChannel {
                 channel.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
                 onerror_callback.forget();
 
+                let handshake_cell_open = self.handshake_cell.clone();
+                let candidate_pair_cell_open = self.candidate_pair_cell.clone();
+                let peer_open = peer.clone();
+                let onopen_func: Box<dyn FnMut(JsValue)> = Box::new(move |_: JsValue| {
+                    handshake_cell_open.channel_opened();
+                    // ICE has settled on a pair by the time the channel opens
+                    candidate_pair_cell_open.refresh(&peer_open);
+                });
+                let onopen_callback = Closure::wrap(onopen_func);
+                channel.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
+                onopen_callback.forget();
+
+                let handshake_cell_close = self.handshake_cell.clone();
+                let onclose_func: Box<dyn FnMut(JsValue)> = Box::new(move |_: JsValue| {
+                    handshake_cell_close.channel_closed();
+                });
+                let onclose_callback = Closure::wrap(onclose_func);
+                channel.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
+                onclose_callback.forget();
+
                 let peer_2 = peer.clone();
                 let addr_cell_2 = self.addr_cell.clone();
                 let addr_func_2 = self.find_addr_func.clone();
@@ -155,7 +250,8 @@ impl DataChannel {
                         let id_sender_4 = id_sender_3.clone();
                         let request_func: Box<dyn FnMut(ProgressEvent)> = Box::new(
                             move |_: ProgressEvent| {
-                                if request_2.status().unwrap() == 200 {
+                                let status = request_2.status().unwrap();
+                                if status == 200 {
                                     let response_string =
                                         request_2.response_text().unwrap().unwrap();
 
@@ -227,6 +323,19 @@ impl DataChannel {
                                                 peer_add_failure_callback.as_ref().unchecked_ref());
                                             peer_add_success_callback.forget();
                                             peer_add_failure_callback.forget();
+
+                                            // Add any additional public candidates of the server
+                                            for public_candidate in
+                                                &session_response.public_candidates
+                                            {
+                                                let mut public_candidate_init_dict =
+                                                    RtcIceCandidateInit::new(public_candidate);
+                                                public_candidate_init_dict
+                                                    .sdp_m_line_index(Some(0));
+                                                peer_5.add_ice_candidate_with_opt_rtc_ice_candidate_init(
+                                                    Some(&public_candidate_init_dict),
+                                                );
+                                            }
                                         },
                                     );
                                     let remote_desc_callback = Closure::wrap(remote_desc_func);
@@ -242,6 +351,11 @@ impl DataChannel {
                                         .then(&remote_desc_callback);
 
                                     remote_desc_callback.forget();
+                                } else {
+                                    id_sender_4.send_error(
+                                        status,
+                                        request_2.response_text().ok().flatten(),
+                                    );
                                 }
                             },
                         );
@@ -335,6 +449,7 @@ pub struct JsSessionResponse {
     pub id_token: IdentityToken,
     pub answer: SessionAnswer,
     pub candidate: SessionCandidate,
+    pub public_candidates: Vec<String>,
 }
 
 fn get_session_response(input: &str) -> JsSessionResponse {
@@ -355,6 +470,17 @@ fn get_session_response(input: &str) -> JsSessionResponse {
     let id_token_opt: Option<&String> = json_obj["id"].get();
     let id_token: String = id_token_opt.unwrap().clone();
 
+    // Servers may advertise additional public candidates
+    let mut public_candidates = Vec::new();
+    let json_map_opt: Option<&HashMap<String, JsonValue>> = json_obj.get();
+    if let Some(candidates_value) = json_map_opt.and_then(|json_map| json_map.get("candidates")) {
+        let candidates_opt: Option<&Vec<JsonValue>> = candidates_value.get();
+        for candidate_value in candidates_opt.unwrap() {
+            let candidate_opt: Option<&String> = candidate_value["candidate"].get();
+            public_candidates.push(candidate_opt.unwrap().clone());
+        }
+    }
+
     JsSessionResponse {
         id_token,
         answer: SessionAnswer { sdp },
@@ -363,5 +489,6 @@ fn get_session_response(input: &str) -> JsSessionResponse {
             sdp_m_line_index,
             sdp_mid,
         },
+        public_candidates,
     }
 }
@@ -1,21 +1,20 @@
-use std::{
-    collections::VecDeque,
-    sync::{Arc, Mutex},
-};
-
 use wasm_bindgen::{closure::Closure, JsCast};
 use web_sys::{MessageEvent, MessagePort};
 
+use crate::packet_queue::PacketQueue;
+
+use super::shared_cell::SharedCell;
+
 // DataChannel
 #[derive(Clone)]
 pub struct DataPort {
     message_port: MessagePort,
-    message_queue: Arc<Mutex<VecDeque<Box<[u8]>>>>,
+    message_queue: SharedCell<PacketQueue>,
 }
 
 impl DataPort {
     pub fn new(message_port: MessagePort) -> Self {
-        let message_queue = Arc::new(Mutex::new(VecDeque::new()));
+        let message_queue = SharedCell::new(PacketQueue::new());
 
         let message_queue_2 = message_queue.clone();
         let port_onmsg_func: Box<dyn FnMut(MessageEvent)> = Box::new(move |evt: MessageEvent| {
@@ -23,10 +22,7 @@ impl DataPort {
                 let uarray: js_sys::Uint8Array = js_sys::Uint8Array::new(&arraybuf);
                 let mut body = vec![0; uarray.length() as usize];
                 uarray.copy_to(&mut body[..]);
-                message_queue_2
-                    .lock()
-                    .expect("can't borrow 'message_queue_2' to retrieve message!")
-                    .push_back(body.into_boxed_slice());
+                message_queue_2.borrow_mut().push(body.into_boxed_slice());
             }
         });
         let port_onmsg_closure = Closure::wrap(port_onmsg_func);
@@ -40,11 +36,41 @@ impl DataPort {
         }
     }
 
+    /// Creates a DataPort which passes each incoming packet straight to the
+    /// given handler from within the `onmessage` callback, instead of
+    /// buffering it in the message queue. The handler is handed a view into a
+    /// scratch buffer which is reused between packets, so it must copy out
+    /// anything it wants to keep.
+    pub fn with_packet_handler(
+        message_port: MessagePort,
+        mut handler: Box<dyn FnMut(&[u8])>,
+    ) -> Self {
+        let mut scratch: Vec<u8> = Vec::new();
+        let port_onmsg_func: Box<dyn FnMut(MessageEvent)> = Box::new(move |evt: MessageEvent| {
+            if let Ok(arraybuf) = evt.data().dyn_into::<js_sys::ArrayBuffer>() {
+                let uarray: js_sys::Uint8Array = js_sys::Uint8Array::new(&arraybuf);
+                scratch.resize(uarray.length() as usize, 0);
+                uarray.copy_to(&mut scratch[..]);
+                handler(&scratch);
+            }
+        });
+        let port_onmsg_closure = Closure::wrap(port_onmsg_func);
+
+        message_port.set_onmessage(Some(port_onmsg_closure.as_ref().unchecked_ref()));
+        port_onmsg_closure.forget();
+
+        Self {
+            message_port,
+            // never filled, as packets are handed to the handler instead
+            message_queue: SharedCell::new(PacketQueue::new()),
+        }
+    }
+
     pub fn message_port(&self) -> MessagePort {
         self.message_port.clone()
     }
 
-    pub fn message_queue(&self) -> Arc<Mutex<VecDeque<Box<[u8]>>>> {
+    pub fn message_queue(&self) -> SharedCell<PacketQueue> {
         self.message_queue.clone()
     }
 }
@@ -0,0 +1,65 @@
+use std::task::Waker;
+
+use super::shared_cell::{SharedCell, SharedRefMut};
+
+// HandshakeState
+struct HandshakeState {
+    channel_open: bool,
+    channel_closed: bool,
+    waker: Option<Waker>,
+}
+
+// HandshakeCell
+/// Tracks the data channel's progress during the handshake, and wakes the
+/// task awaiting `Socket::connect_async` whenever that progress changes
+#[derive(Clone)]
+pub struct HandshakeCell {
+    cell: SharedCell<HandshakeState>,
+}
+
+impl HandshakeCell {
+    pub fn new() -> Self {
+        Self {
+            cell: SharedCell::new(HandshakeState {
+                channel_open: false,
+                channel_closed: false,
+                waker: None,
+            }),
+        }
+    }
+
+    pub fn channel_opened(&self) {
+        self.state().channel_open = true;
+        self.wake();
+    }
+
+    pub fn channel_closed(&self) {
+        self.state().channel_closed = true;
+        self.wake();
+    }
+
+    pub fn channel_is_open(&self) -> bool {
+        self.state().channel_open
+    }
+
+    pub fn channel_is_closed(&self) -> bool {
+        self.state().channel_closed
+    }
+
+    pub fn register_waker(&self, waker: &Waker) {
+        self.state().waker = Some(waker.clone());
+    }
+
+    pub fn wake(&self) {
+        // take the waker out before waking, so that a task polled
+        // synchronously by the waker can register itself again
+        let waker_opt = self.state().waker.take();
+        if let Some(waker) = waker_opt {
+            waker.wake();
+        }
+    }
+
+    fn state(&self) -> SharedRefMut<'_, HandshakeState> {
+        self.cell.borrow_mut()
+    }
+}
@@ -1,47 +1,57 @@
-use std::sync::{Arc, Mutex};
-
 use naia_socket_shared::IdentityToken;
 
+use super::{handshake_cell::HandshakeCell, shared_cell::SharedCell};
 use crate::{identity_receiver::IdentityReceiver, IdentityReceiverResult};
 
 /// Handles receiving an IdentityToken from the Server through a given Client Socket
 #[derive(Clone)]
 pub struct IdentityReceiverImpl {
-    id_cell: Arc<Mutex<Option<Result<String, u16>>>>,
+    id_cell: SharedCell<Option<Result<String, (u16, Option<String>)>>>,
+    handshake_cell: HandshakeCell,
 }
 
 impl IdentityReceiverImpl {
     /// Create a new IdentityReceiver, if supplied with the Server's address & a
     /// reference back to the parent Socket
-    pub fn new() -> Self {
+    pub fn new(handshake_cell: HandshakeCell) -> Self {
         Self {
-            id_cell: Arc::new(Mutex::new(None)),
+            id_cell: SharedCell::new(None),
+            handshake_cell,
         }
     }
 
     // this is for the DataChannel to send the IdentityToken to be picked up by the IdentityReceiver
     pub fn send(&self, id_token: IdentityToken) {
-        let mut token_guard = self
-            .id_cell
-            .lock()
-            .expect("This should never happen, message_queue should always be available in a single-threaded context");
+        let mut token_guard = self.id_cell.borrow_mut();
 
         *token_guard = Some(Ok(id_token));
+        drop(token_guard);
+
+        self.handshake_cell.wake();
+    }
+
+    // this is for the DataChannel to report that the Server rejected the session
+    pub fn send_error(&self, error_code: u16, body: Option<String>) {
+        let mut token_guard = self.id_cell.borrow_mut();
+
+        *token_guard = Some(Err((error_code, body)));
+        drop(token_guard);
+
+        self.handshake_cell.wake();
     }
 }
 
 impl IdentityReceiver for IdentityReceiverImpl {
     fn receive(&mut self) -> IdentityReceiverResult {
-        let mut token_guard = self
-            .id_cell
-            .lock()
-            .expect("This should never happen, message_queue should always be available in a single-threaded context");
+        let mut token_guard = self.id_cell.borrow_mut();
 
         if token_guard.is_some() {
             let token_result = token_guard.take().unwrap();
             match token_result {
                 Ok(token) => return IdentityReceiverResult::Success(token),
-                Err(error_code) => return IdentityReceiverResult::ErrorResponseCode(error_code),
+                Err((error_code, body)) => {
+                    return IdentityReceiverResult::from_error_response(error_code, body.as_deref())
+                }
             }
         } else {
             return IdentityReceiverResult::Waiting;
@@ -0,0 +1,37 @@
+use std::ops::Deref;
+
+use wasm_bindgen::JsCast;
+
+// JS objects such as a MessagePort live in the JS realm of the thread which
+// created them, and so can never genuinely be shared across threads. Without
+// shared-memory threads a Wasm module only ever has that one thread, which is
+// what makes the `Send` & `Sync` impls below sound.
+#[cfg(target_feature = "atomics")]
+compile_error!("The 'wbindgen' backend of Naia Client Socket does not yet support Wasm built with shared-memory threads (the 'atomics' target feature).");
+
+// MainThreadCell
+/// Holds a JS object that may only be used from the thread that created it,
+/// allowing it to be stored in `Send + Sync` types
+#[derive(Clone)]
+pub struct MainThreadCell<T> {
+    inner: T,
+}
+
+impl<T> MainThreadCell<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> Deref for MainThreadCell<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[cfg(not(target_feature = "atomics"))]
+unsafe impl<T: JsCast> Send for MainThreadCell<T> {}
+#[cfg(not(target_feature = "atomics"))]
+unsafe impl<T: JsCast> Sync for MainThreadCell<T> {}
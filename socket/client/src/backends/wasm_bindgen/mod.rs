@@ -1,11 +1,16 @@
 mod addr_cell;
+mod candidate_pair_cell;
 mod data_channel;
 mod data_port;
+mod handshake_cell;
+mod main_thread_cell;
+mod shared_cell;
 
 mod identity_receiver;
 mod packet_receiver;
 mod packet_sender;
 mod socket;
+mod web_transport;
 
 pub use data_channel::DataChannel;
 pub use data_port::DataPort;
@@ -1,29 +1,42 @@
-use std::{
-    collections::VecDeque,
-    sync::{Arc, Mutex},
-};
+use naia_socket_shared::SocketConfig;
 
 use crate::{
-    error::NaiaClientSocketError, packet_receiver::PacketReceiver, server_addr::ServerAddr,
+    candidate_pair::CandidatePair, error::NaiaClientSocketError, packet_queue::PacketQueue,
+    packet_receiver::PacketReceiver, server_addr::ServerAddr,
 };
 
-use super::{addr_cell::AddrCell, data_port::DataPort};
+use super::{
+    addr_cell::AddrCell, candidate_pair_cell::CandidatePairCell, data_port::DataPort,
+    shared_cell::SharedCell,
+};
 
 /// Handles receiving messages from the Server through a given Client Socket
 #[derive(Clone)]
 pub struct PacketReceiverImpl {
-    message_queue: Arc<Mutex<VecDeque<Box<[u8]>>>>,
+    message_queue: SharedCell<PacketQueue>,
     server_addr: AddrCell,
+    candidate_pair: CandidatePairCell,
     last_payload: Option<Box<[u8]>>,
 }
 
 impl PacketReceiverImpl {
     /// Create a new PacketReceiver, if supplied with the RtcDataChannel and a
     /// reference to a list of dropped messages
-    pub fn new(data_port: &DataPort, addr_cell: &AddrCell) -> Self {
+    pub fn new(
+        config: &SocketConfig,
+        data_port: &DataPort,
+        addr_cell: &AddrCell,
+        candidate_pair_cell: &CandidatePairCell,
+    ) -> Self {
+        let message_queue = data_port.message_queue();
+        message_queue
+            .borrow_mut()
+            .set_capacity(config.receive_queue_capacity, config.receive_queue_overflow);
+
         Self {
-            message_queue: data_port.message_queue(),
+            message_queue,
             server_addr: addr_cell.clone(),
+            candidate_pair: candidate_pair_cell.clone(),
             last_payload: None,
         }
     }
@@ -31,12 +44,14 @@ impl PacketReceiverImpl {
 
 impl PacketReceiver for PacketReceiverImpl {
     fn receive(&mut self) -> Result<Option<&[u8]>, NaiaClientSocketError> {
-        match self
-            .message_queue
-            .lock()
-            .expect("This should never happen, message_queue should always be available in a single-threaded context")
-            .pop_front()
-        {
+        // The queue is filled from data channel callbacks, so a reentrant
+        // access may find it already borrowed. Rather than panicking, defer to
+        // the next poll.
+        let Some(mut message_queue) = self.message_queue.try_borrow_mut() else {
+            return Ok(None);
+        };
+
+        match message_queue.pop() {
             Some(payload) => {
                 self.last_payload = Some(payload);
                 Ok(Some(self.last_payload.as_ref().unwrap()))
@@ -49,4 +64,17 @@ impl PacketReceiver for PacketReceiverImpl {
     fn server_addr(&self) -> ServerAddr {
         self.server_addr.get()
     }
+
+    /// Get the ICE candidate pair selected for the connection
+    fn selected_candidate_pair(&self) -> Option<CandidatePair> {
+        self.candidate_pair.get()
+    }
+
+    fn queued_len(&self) -> usize {
+        self.message_queue.borrow_mut().len()
+    }
+
+    fn dropped_count(&self) -> u64 {
+        self.message_queue.borrow_mut().dropped_count()
+    }
 }
@@ -1,25 +1,32 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
 use js_sys::Uint8Array;
 use web_sys::MessagePort;
 
 use crate::{error::NaiaClientSocketError, packet_sender::PacketSender, server_addr::ServerAddr};
 
-use super::{addr_cell::AddrCell, data_port::DataPort};
+use super::{addr_cell::AddrCell, data_port::DataPort, main_thread_cell::MainThreadCell};
 
 /// Handles sending messages to the Server for a given Client Socket
 #[derive(Clone)]
 pub struct PacketSenderImpl {
-    message_port: MessagePort,
+    message_port: MainThreadCell<MessagePort>,
     server_addr: AddrCell,
-    connected: bool,
+    // shared between clones, so that disconnecting through one handle is
+    // seen by every other handle to the same port
+    connected: Arc<AtomicBool>,
 }
 
 impl PacketSenderImpl {
     /// Create a new PacketSender
     pub fn new(data_port: &DataPort, addr_cell: &AddrCell) -> Self {
         PacketSenderImpl {
-            message_port: data_port.message_port(),
+            message_port: MainThreadCell::new(data_port.message_port()),
             server_addr: addr_cell.clone(),
-            connected: true,
+            connected: Arc::new(AtomicBool::new(true)),
         }
     }
 }
@@ -27,7 +34,7 @@ impl PacketSenderImpl {
 impl PacketSender for PacketSenderImpl {
     /// Send a Packet to the Server
     fn send(&self, payload: &[u8]) -> Result<(), NaiaClientSocketError> {
-        if self.connected {
+        if self.connected.load(Ordering::Relaxed) {
             let uarray: Uint8Array = payload.into();
             self.message_port
                 .post_message(&uarray)
@@ -44,16 +51,12 @@ impl PacketSender for PacketSenderImpl {
     }
 
     fn connected(&self) -> bool {
-        self.connected
+        self.connected.load(Ordering::Relaxed)
     }
 
     fn disconnect(&mut self) {
-        if self.connected {
-            self.connected = false;
+        if self.connected.swap(false, Ordering::Relaxed) {
             self.message_port.close();
         }
     }
 }
-
-unsafe impl Send for PacketSenderImpl {}
-unsafe impl Sync for PacketSenderImpl {}
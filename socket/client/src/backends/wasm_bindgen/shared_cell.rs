@@ -0,0 +1,87 @@
+#[cfg(target_feature = "atomics")]
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
+#[cfg(not(target_feature = "atomics"))]
+use std::{
+    cell::{RefCell, RefMut},
+    rc::Rc,
+};
+
+// SharedCell
+/// A value shared between the socket's handles and the JS callbacks which
+/// fill it. Without shared-memory threads a Wasm module only ever has one
+/// thread, so this is an `Rc<RefCell>`; with the `atomics` target feature it
+/// is an `Arc<Mutex>` instead.
+pub struct SharedCell<T> {
+    #[cfg(not(target_feature = "atomics"))]
+    inner: Rc<RefCell<T>>,
+    #[cfg(target_feature = "atomics")]
+    inner: Arc<Mutex<T>>,
+}
+
+#[cfg(not(target_feature = "atomics"))]
+pub type SharedRefMut<'a, T> = RefMut<'a, T>;
+#[cfg(target_feature = "atomics")]
+pub type SharedRefMut<'a, T> = MutexGuard<'a, T>;
+
+impl<T> SharedCell<T> {
+    #[cfg(not(target_feature = "atomics"))]
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(value)),
+        }
+    }
+
+    #[cfg(target_feature = "atomics")]
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// Borrows the value. Panics if it is already borrowed, which can only
+    /// happen if a JS callback runs while the value is in use
+    #[cfg(not(target_feature = "atomics"))]
+    pub fn borrow_mut(&self) -> SharedRefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+
+    /// Borrows the value, waiting for any other thread using it
+    #[cfg(target_feature = "atomics")]
+    pub fn borrow_mut(&self) -> SharedRefMut<'_, T> {
+        // every value kept here stays consistent between operations, so is
+        // safe to keep using after a panic elsewhere poisoned the lock
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Borrows the value, or returns None if it is already in use
+    #[cfg(not(target_feature = "atomics"))]
+    pub fn try_borrow_mut(&self) -> Option<SharedRefMut<'_, T>> {
+        self.inner.try_borrow_mut().ok()
+    }
+
+    /// Borrows the value, or returns None if it is already in use
+    #[cfg(target_feature = "atomics")]
+    pub fn try_borrow_mut(&self) -> Option<SharedRefMut<'_, T>> {
+        match self.inner.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+impl<T> Clone for SharedCell<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+// An `Rc<RefCell>` can only be shared between the handles of a single thread,
+// and without shared-memory threads there is only ever that one thread. With
+// the `atomics` target feature the `Arc<Mutex>` is `Send + Sync` by itself.
+#[cfg(not(target_feature = "atomics"))]
+unsafe impl<T: Send> Send for SharedCell<T> {}
+#[cfg(not(target_feature = "atomics"))]
+unsafe impl<T: Send> Sync for SharedCell<T> {}
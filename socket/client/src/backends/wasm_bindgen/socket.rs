@@ -1,12 +1,20 @@
-use naia_socket_shared::SocketConfig;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use naia_socket_shared::{IdentityToken, SocketConfig};
 
 use super::{
-    addr_cell::AddrCell, data_channel::DataChannel, data_port::DataPort,
+    addr_cell::AddrCell, candidate_pair_cell::CandidatePairCell, data_channel::DataChannel,
+    data_port::DataPort, handshake_cell::HandshakeCell, identity_receiver::IdentityReceiverImpl,
     packet_receiver::PacketReceiverImpl, packet_sender::PacketSenderImpl,
 };
 use crate::{
     backends::socket::SocketTrait, conditioned_packet_receiver::ConditionedPacketReceiver,
-    packet_receiver::PacketReceiver, packet_sender::PacketSender, IdentityReceiver,
+    error::HandshakeError, packet_receiver::PacketReceiver, packet_sender::PacketSender,
+    IdentityReceiver, IdentityReceiverResult,
 };
 
 /// A client-side socket which communicates with an underlying unordered &
@@ -71,6 +79,73 @@ impl Socket {
         );
     }
 
+    /// Connects to the given server address, handing each incoming packet
+    /// directly to `packet_handler` as it arrives rather than queueing it to
+    /// be pulled from a PacketReceiver. This skips the intermediate buffer and
+    /// copy, at the cost of running user code inside the data channel
+    /// callback. The slice passed to the handler is only valid for the
+    /// duration of the call.
+    ///
+    /// Link conditioning from `config` is not applied to handled packets.
+    pub fn connect_with_packet_handler(
+        server_session_url: &str,
+        config: &SocketConfig,
+        packet_handler: Box<dyn FnMut(&[u8])>,
+    ) -> (Box<dyn IdentityReceiver>, Box<dyn PacketSender>) {
+        let data_channel = DataChannel::new(config, server_session_url, None, None);
+
+        let data_port = data_channel.data_port_with_packet_handler(packet_handler);
+        let addr_cell = data_channel.addr_cell();
+
+        // Setup Packet Sender
+        let packet_sender: Box<dyn PacketSender> =
+            Box::new(PacketSenderImpl::new(&data_port, &addr_cell));
+
+        // Setup Identity Receiver
+        let id_receiver: Box<dyn IdentityReceiver> = Box::new(data_channel.id_receiver());
+
+        data_channel.start();
+
+        return (id_receiver, packet_sender);
+    }
+
+    /// Connects to the given server address, resolving once the Server has
+    /// sent back an IdentityToken and the data channel is open. Unlike
+    /// `connect`, there is no IdentityReceiver to poll.
+    pub async fn connect_async(
+        server_session_url: &str,
+        config: &SocketConfig,
+    ) -> Result<
+        (
+            Box<dyn PacketSender>,
+            Box<dyn PacketReceiver>,
+            IdentityToken,
+        ),
+        HandshakeError,
+    > {
+        let data_channel = DataChannel::new(config, server_session_url, None, None);
+
+        let data_port = data_channel.data_port();
+        let addr_cell = data_channel.addr_cell();
+
+        let candidate_pair_cell = data_channel.candidate_pair_cell();
+
+        let (packet_sender, packet_receiver) =
+            Socket::setup_io(config, &addr_cell, &candidate_pair_cell, &data_port);
+
+        let handshake = Handshake {
+            id_receiver: data_channel.id_receiver(),
+            handshake_cell: data_channel.handshake_cell(),
+            identity_token: None,
+        };
+
+        data_channel.start();
+
+        let identity_token = handshake.await?;
+
+        return Ok((packet_sender, packet_receiver, identity_token));
+    }
+
     /// Connects to the given server address
     fn connect_inner(
         server_session_url: &str,
@@ -88,7 +163,10 @@ impl Socket {
         let data_port = data_channel.data_port();
         let addr_cell = data_channel.addr_cell();
 
-        let (packet_sender, packet_receiver) = Socket::setup_io(config, &addr_cell, &data_port);
+        let candidate_pair_cell = data_channel.candidate_pair_cell();
+
+        let (packet_sender, packet_receiver) =
+            Socket::setup_io(config, &addr_cell, &candidate_pair_cell, &data_port);
 
         // Setup Identity Receiver
         let id_receiver: Box<dyn IdentityReceiver> = Box::new(data_channel.id_receiver());
@@ -105,12 +183,14 @@ impl Socket {
         data_port: &DataPort,
     ) -> (Box<dyn PacketSender>, Box<dyn PacketReceiver>) {
         let addr_cell = AddrCell::new();
-        return Socket::setup_io(config, &addr_cell, data_port);
+        let candidate_pair_cell = CandidatePairCell::new();
+        return Socket::setup_io(config, &addr_cell, &candidate_pair_cell, data_port);
     }
 
     fn setup_io(
         config: &SocketConfig,
         addr_cell: &AddrCell,
+        candidate_pair_cell: &CandidatePairCell,
         data_port: &DataPort,
     ) -> (Box<dyn PacketSender>, Box<dyn PacketReceiver>) {
         // Setup Packet Sender
@@ -119,7 +199,8 @@ impl Socket {
         let packet_sender: Box<dyn PacketSender> = Box::new(packet_sender_impl);
 
         // Setup Packet Receiver
-        let packet_receiver_impl = PacketReceiverImpl::new(&data_port, addr_cell);
+        let packet_receiver_impl =
+            PacketReceiverImpl::new(config, &data_port, addr_cell, candidate_pair_cell);
 
         let packet_receiver: Box<dyn PacketReceiver> = {
             let inner_receiver = Box::new(packet_receiver_impl);
@@ -134,6 +215,50 @@ impl Socket {
     }
 }
 
+// Handshake
+struct Handshake {
+    id_receiver: IdentityReceiverImpl,
+    handshake_cell: HandshakeCell,
+    identity_token: Option<IdentityToken>,
+}
+
+impl Future for Handshake {
+    type Output = Result<IdentityToken, HandshakeError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // register before checking, so that progress made in between still
+        // wakes this task
+        self.handshake_cell.register_waker(cx.waker());
+
+        if self.identity_token.is_none() {
+            match self.id_receiver.receive() {
+                IdentityReceiverResult::Success(identity_token) => {
+                    self.identity_token = Some(identity_token);
+                }
+                IdentityReceiverResult::Rejected(reason) => {
+                    return Poll::Ready(Err(HandshakeError::Rejected(reason)));
+                }
+                IdentityReceiverResult::ErrorResponseCode(error_code) => {
+                    return Poll::Ready(Err(HandshakeError::ErrorResponseCode(error_code)));
+                }
+                IdentityReceiverResult::Waiting => {}
+            }
+        }
+
+        if self.handshake_cell.channel_is_closed() {
+            return Poll::Ready(Err(HandshakeError::Disconnected));
+        }
+
+        if self.handshake_cell.channel_is_open() {
+            if let Some(identity_token) = self.identity_token.take() {
+                return Poll::Ready(Ok(identity_token));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
 impl SocketTrait for Socket {
     /// Connects to the given server address
     fn connect(
@@ -0,0 +1,304 @@
+use std::{
+    cell::RefCell,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    rc::Rc,
+};
+
+use js_sys::{Array, Object, Promise, Reflect, Uint8Array};
+use log::info;
+use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
+use web_sys::{MessageEvent, MessagePort};
+
+use naia_socket_shared::{parse_server_url, SocketConfig};
+
+use super::{addr_cell::AddrCell, handshake_cell::HandshakeCell};
+use crate::IdentityReceiverImpl;
+
+// Declared here rather than taken from `web_sys`, whose WebTransport bindings
+// are only built with `--cfg=web_sys_unstable_apis`
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = WebTransport)]
+    #[derive(Clone)]
+    type JsWebTransport;
+
+    #[wasm_bindgen(catch, constructor, js_class = "WebTransport")]
+    fn new(url: &str, options: &Object) -> Result<JsWebTransport, JsValue>;
+
+    #[wasm_bindgen(method, getter)]
+    fn ready(this: &JsWebTransport) -> Promise;
+
+    #[wasm_bindgen(method, getter)]
+    fn closed(this: &JsWebTransport) -> Promise;
+
+    #[wasm_bindgen(method, getter)]
+    fn datagrams(this: &JsWebTransport) -> JsDatagramDuplexStream;
+
+    #[wasm_bindgen(method, getter, js_name = incomingUnidirectionalStreams)]
+    fn incoming_unidirectional_streams(this: &JsWebTransport) -> JsReadableStream;
+
+    #[wasm_bindgen(js_name = WebTransportDatagramDuplexStream)]
+    type JsDatagramDuplexStream;
+
+    #[wasm_bindgen(method, getter)]
+    fn readable(this: &JsDatagramDuplexStream) -> JsReadableStream;
+
+    #[wasm_bindgen(method, getter)]
+    fn writable(this: &JsDatagramDuplexStream) -> JsWritableStream;
+
+    #[wasm_bindgen(js_name = ReadableStream)]
+    type JsReadableStream;
+
+    #[wasm_bindgen(method, js_name = getReader)]
+    fn get_reader(this: &JsReadableStream) -> JsReadableStreamReader;
+
+    #[wasm_bindgen(js_name = ReadableStreamDefaultReader)]
+    #[derive(Clone)]
+    type JsReadableStreamReader;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &JsReadableStreamReader) -> Promise;
+
+    #[wasm_bindgen(js_name = WritableStream)]
+    type JsWritableStream;
+
+    #[wasm_bindgen(method, js_name = getWriter)]
+    fn get_writer(this: &JsWritableStream) -> JsWritableStreamWriter;
+
+    #[wasm_bindgen(js_name = WritableStreamDefaultWriter)]
+    type JsWritableStreamWriter;
+
+    #[wasm_bindgen(method)]
+    fn write(this: &JsWritableStreamWriter, chunk: &JsValue) -> Promise;
+}
+
+/// A WebTransport session with the Server, which exchanges packets as
+/// datagrams through the same MessagePort a WebRTC data channel would use
+pub struct WebTransportSession {
+    session_url: String,
+    server_addr: SocketAddr,
+    certificate_hashes: Vec<[u8; 32]>,
+    main_port: MessagePort,
+    addr_cell: AddrCell,
+    id_cell: IdentityReceiverImpl,
+    handshake_cell: HandshakeCell,
+}
+
+impl WebTransportSession {
+    /// Whether the browser supports WebTransport
+    pub fn is_supported() -> bool {
+        Reflect::has(&js_sys::global(), &JsValue::from("WebTransport")).unwrap_or(false)
+    }
+
+    pub fn new(
+        config: &SocketConfig,
+        webtransport_url: &str,
+        auth_bytes_opt: Option<&[u8]>,
+        main_port: MessagePort,
+        addr_cell: AddrCell,
+        id_cell: IdentityReceiverImpl,
+        handshake_cell: HandshakeCell,
+    ) -> Self {
+        let server_url = parse_server_url(webtransport_url);
+
+        // browsers can't set headers on a WebTransport request, so auth is
+        // sent in the query string instead
+        let mut session_url = format!("{}{}", server_url, config.rtc_endpoint_path);
+        if let Some(auth_bytes) = auth_bytes_opt {
+            session_url.push_str("?auth=");
+            session_url.push_str(&base64::encode_config(auth_bytes, base64::URL_SAFE_NO_PAD));
+        }
+
+        // packets are only matched to the Server by address, so a hostname
+        // which can't be resolved here is stood in for by the unspecified
+        // address
+        let server_ip = server_url
+            .host_str()
+            .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+            .and_then(|host| host.parse::<IpAddr>().ok())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        let server_port = server_url.port_or_known_default().unwrap_or(443);
+
+        Self {
+            session_url,
+            server_addr: SocketAddr::new(server_ip, server_port),
+            certificate_hashes: config.webtransport_certificate_hashes.clone(),
+            main_port,
+            addr_cell,
+            id_cell,
+            handshake_cell,
+        }
+    }
+
+    /// Opens the session, calling `fallback` instead if it can't be set up,
+    /// (i.e. the Server doesn't accept WebTransport, or rejected the Client's
+    /// auth) so that a WebRTC session can be requested in its place
+    pub fn start(self, fallback: Box<dyn FnOnce()>) {
+        let transport = match JsWebTransport::new(&self.session_url, &self.options()) {
+            Ok(transport) => transport,
+            Err(err) => {
+                info!(
+                    "Unable to open WebTransport session, falling back to WebRTC. Error: {:?}",
+                    err
+                );
+                fallback();
+                return;
+            }
+        };
+
+        let mut fallback = Some(fallback);
+        let ready_failure_func: Box<dyn FnMut(JsValue)> = Box::new(move |err: JsValue| {
+            info!(
+                "WebTransport session failed, falling back to WebRTC. Error: {:?}",
+                err
+            );
+            if let Some(fallback) = fallback.take() {
+                fallback();
+            }
+        });
+        let ready_failure_callback = Closure::wrap(ready_failure_func);
+
+        let transport_ready = transport.clone();
+        let mut session = Some(self);
+        let ready_func: Box<dyn FnMut(JsValue)> = Box::new(move |_: JsValue| {
+            if let Some(session) = session.take() {
+                session.on_ready(&transport_ready);
+            }
+        });
+        let ready_callback = Closure::wrap(ready_func);
+
+        let _ = transport
+            .ready()
+            .then2(&ready_callback, &ready_failure_callback);
+        ready_callback.forget();
+        ready_failure_callback.forget();
+    }
+
+    fn options(&self) -> Object {
+        let options = Object::new();
+        if !self.certificate_hashes.is_empty() {
+            let hashes = Array::new();
+            for certificate_hash in &self.certificate_hashes {
+                let hash = Object::new();
+                let _ = Reflect::set(&hash, &"algorithm".into(), &"sha-256".into());
+                let _ = Reflect::set(
+                    &hash,
+                    &"value".into(),
+                    &Uint8Array::from(&certificate_hash[..]),
+                );
+                hashes.push(&hash);
+            }
+            let _ = Reflect::set(&options, &"serverCertificateHashes".into(), &hashes);
+        }
+        options
+    }
+
+    fn on_ready(mut self, transport: &JsWebTransport) {
+        self.addr_cell.set_addr(&self.server_addr);
+
+        // read the IdentityToken from the first stream the Server opens
+        let id_cell = self.id_cell.clone();
+        let incoming_streams = transport.incoming_unidirectional_streams().get_reader();
+        let stream_func: Box<dyn FnMut(JsValue)> = Box::new(move |result: JsValue| {
+            let Some(stream) = read_result_value(&result) else {
+                return;
+            };
+            let id_cell = id_cell.clone();
+            let mut token_bytes = Vec::new();
+            read_each(
+                stream.unchecked_into::<JsReadableStream>().get_reader(),
+                move |chunk: Option<Uint8Array>| match chunk {
+                    Some(chunk) => token_bytes.extend(chunk.to_vec()),
+                    None => match String::from_utf8(std::mem::take(&mut token_bytes)) {
+                        Ok(identity_token) => id_cell.send(identity_token),
+                        Err(_) => info!("WebTransport session sent an invalid IdentityToken"),
+                    },
+                },
+            );
+        });
+        let stream_callback = Closure::wrap(stream_func);
+        let _ = incoming_streams.read().then(&stream_callback);
+        stream_callback.forget();
+
+        // forward datagrams from the Server to the DataPort
+        let main_port = self.main_port.clone();
+        let datagrams = transport.datagrams();
+        read_each(
+            datagrams.readable().get_reader(),
+            move |chunk: Option<Uint8Array>| {
+                if let Some(chunk) = chunk {
+                    // copied, as the chunk may be a view into a larger buffer
+                    let _ = main_port.post_message(&Uint8Array::new(&chunk).buffer());
+                }
+            },
+        );
+
+        // forward packets from the DataPort to the Server as datagrams
+        let writer = datagrams.writable().get_writer();
+        let port_onmsg_func: Box<dyn FnMut(MessageEvent)> = Box::new(move |evt: MessageEvent| {
+            if evt.data().is_instance_of::<Uint8Array>() {
+                let _ = writer.write(&evt.data());
+            }
+        });
+        let port_onmsg_closure = Closure::wrap(port_onmsg_func);
+        self.main_port
+            .set_onmessage(Some(port_onmsg_closure.as_ref().unchecked_ref()));
+        port_onmsg_closure.forget();
+
+        let handshake_cell_close = self.handshake_cell.clone();
+        let closed_func: Box<dyn FnMut(JsValue)> = Box::new(move |_: JsValue| {
+            handshake_cell_close.channel_closed();
+        });
+        let closed_callback = Closure::wrap(closed_func);
+        let _ = transport.closed().then2(&closed_callback, &closed_callback);
+        closed_callback.forget();
+
+        self.handshake_cell.channel_opened();
+    }
+}
+
+// Called with each result of a read, and with any error reading
+type ReadCallbacks = (Closure<dyn FnMut(JsValue)>, Closure<dyn FnMut(JsValue)>);
+
+// Calls `on_chunk` with every chunk read from the stream, then with None once
+// it ends or errors. The same callbacks are reused for every read, so that a
+// long-lived stream doesn't leak one per chunk
+fn read_each(reader: JsReadableStreamReader, on_chunk: impl FnMut(Option<Uint8Array>) + 'static) {
+    let on_chunk = Rc::new(RefCell::new(on_chunk));
+    let callbacks: Rc<RefCell<Option<ReadCallbacks>>> = Rc::new(RefCell::new(None));
+
+    let on_chunk_read = on_chunk.clone();
+    let callbacks_read = callbacks.clone();
+    let reader_read = reader.clone();
+    let read_func: Box<dyn FnMut(JsValue)> =
+        Box::new(move |result: JsValue| match read_result_value(&result) {
+            Some(chunk) => {
+                (on_chunk_read.borrow_mut())(Some(chunk.unchecked_into()));
+                if let Some((read_callback, error_callback)) = callbacks_read.borrow().as_ref() {
+                    let _ = reader_read.read().then2(read_callback, error_callback);
+                }
+            }
+            None => (on_chunk_read.borrow_mut())(None),
+        });
+    let error_func: Box<dyn FnMut(JsValue)> = Box::new(move |_: JsValue| {
+        (on_chunk.borrow_mut())(None);
+    });
+
+    let read_callback = Closure::wrap(read_func);
+    let error_callback = Closure::wrap(error_func);
+    let _ = reader.read().then2(&read_callback, &error_callback);
+    *callbacks.borrow_mut() = Some((read_callback, error_callback));
+}
+
+// The value of a `ReadableStreamDefaultReader.read()` result, or None once the
+// stream is done
+fn read_result_value(result: &JsValue) -> Option<JsValue> {
+    let done = Reflect::get(result, &"done".into())
+        .ok()
+        .and_then(|done| done.as_bool())
+        .unwrap_or(true);
+    if done {
+        return None;
+    }
+    Reflect::get(result, &"value".into()).ok()
+}
@@ -0,0 +1,58 @@
+/// The type of an ICE candidate, as reported by the WebRTC implementation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CandidateType {
+    /// An address on one of the peer's own network interfaces
+    Host,
+    /// The peer's public address, as discovered through a STUN server
+    ServerReflexive,
+    /// The peer's public address, as discovered from incoming traffic
+    PeerReflexive,
+    /// An address allocated on a TURN relay server
+    Relay,
+}
+
+impl CandidateType {
+    /// Parses a `candidateType` value from WebRTC stats
+    pub fn from_stats_str(value: &str) -> Option<Self> {
+        match value {
+            "host" => Some(Self::Host),
+            "srflx" => Some(Self::ServerReflexive),
+            "prflx" => Some(Self::PeerReflexive),
+            "relay" => Some(Self::Relay),
+            _ => None,
+        }
+    }
+}
+
+/// One end of an ICE candidate pair
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Candidate {
+    /// How the candidate was gathered
+    pub candidate_type: CandidateType,
+    /// The candidate's address. This may be a hostname, as browsers can
+    /// obfuscate local addresses behind mDNS names.
+    pub address: Option<String>,
+    /// The candidate's port
+    pub port: Option<u16>,
+    /// The transport protocol of the candidate, usually "udp"
+    pub protocol: Option<String>,
+}
+
+/// The ICE candidate pair a WebRTC connection has selected to carry its
+/// traffic
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CandidatePair {
+    /// The Client's end of the pair
+    pub local: Candidate,
+    /// The Server's end of the pair
+    pub remote: Candidate,
+}
+
+impl CandidatePair {
+    /// Whether traffic is being relayed through a TURN server, rather than
+    /// travelling directly between Client and Server
+    pub fn is_relayed(&self) -> bool {
+        self.local.candidate_type == CandidateType::Relay
+            || self.remote.candidate_type == CandidateType::Relay
+    }
+}
@@ -1,7 +1,8 @@
 use naia_socket_shared::{link_condition_logic, Instant, LinkConditionerConfig, TimeQueue};
 
 use super::{
-    error::NaiaClientSocketError, packet_receiver::PacketReceiver, server_addr::ServerAddr,
+    candidate_pair::CandidatePair, error::NaiaClientSocketError, packet_receiver::PacketReceiver,
+    server_addr::ServerAddr,
 };
 
 /// Used to receive packets from the Client Socket
@@ -63,4 +64,16 @@ impl PacketReceiver for ConditionedPacketReceiver {
     fn server_addr(&self) -> ServerAddr {
         self.inner_receiver.server_addr()
     }
+
+    fn selected_candidate_pair(&self) -> Option<CandidatePair> {
+        self.inner_receiver.selected_candidate_pair()
+    }
+
+    fn queued_len(&self) -> usize {
+        self.inner_receiver.queued_len()
+    }
+
+    fn dropped_count(&self) -> u64 {
+        self.inner_receiver.dropped_count()
+    }
 }
@@ -1,5 +1,7 @@
 use std::{error::Error, fmt};
 
+use naia_socket_shared::RejectReason;
+
 /// An Error type specifically related to the Naia Client Socket
 /// This is under construction and needs to be cleaned up
 #[derive(Debug)]
@@ -20,3 +22,39 @@ impl fmt::Display for NaiaClientSocketError {
 }
 
 impl Error for NaiaClientSocketError {}
+
+/// An Error returned when the Client Socket is unable to finish connecting to
+/// the Server
+#[derive(Debug)]
+pub enum HandshakeError {
+    /// The Server rejected the connection, for the given reason
+    Rejected(RejectReason),
+    /// The Server could not answer the session request, with the given
+    /// response code
+    ErrorResponseCode(u16),
+    /// The connection closed before the Server responded
+    Disconnected,
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            HandshakeError::Rejected(reason) => write!(
+                f,
+                "Naia Client Socket Handshake Error: rejected by Server: {:?}",
+                reason
+            ),
+            HandshakeError::ErrorResponseCode(code) => write!(
+                f,
+                "Naia Client Socket Handshake Error: Server responded with code {}",
+                code
+            ),
+            HandshakeError::Disconnected => write!(
+                f,
+                "Naia Client Socket Handshake Error: disconnected before Server responded"
+            ),
+        }
+    }
+}
+
+impl Error for HandshakeError {}
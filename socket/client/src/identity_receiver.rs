@@ -1,15 +1,26 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+pub use naia_socket_shared::IdentityReceiverResult;
+#[cfg(not(target_arch = "wasm32"))]
 use naia_socket_shared::IdentityToken;
 
-pub enum IdentityReceiverResult {
-    Waiting,
-    Success(IdentityToken),
-    ErrorResponseCode(u16),
-}
+#[cfg(not(target_arch = "wasm32"))]
+use crate::NaiaClientSocketError;
 
 /// Used to receive an IdentityToken from the Client Socket
 pub trait IdentityReceiver: IdentityReceiverClone + Send + Sync {
     /// Receives an IdentityToken from the Client Socket
     fn receive(&mut self) -> IdentityReceiverResult;
+    /// Blocks until an IdentityToken is received from the Client Socket, or
+    /// until `timeout` elapses, in which case `Ok(None)` is returned. Returns
+    /// an error if the Server did not send an IdentityToken. Must not be
+    /// called from within an async runtime
+    #[cfg(not(target_arch = "wasm32"))]
+    fn receive_blocking(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<IdentityToken>, NaiaClientSocketError>;
 }
 
 /// Used to clone Box<dyn IdentityReceiver>
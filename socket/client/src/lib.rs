@@ -16,6 +16,7 @@ cfg_if! {
 }
 
 mod backends;
+mod candidate_pair;
 mod conditioned_packet_receiver;
 mod error;
 mod identity_receiver;
@@ -23,10 +24,14 @@ mod packet_receiver;
 mod packet_sender;
 mod server_addr;
 
+#[cfg(any(test, all(target_arch = "wasm32", feature = "wbindgen")))]
+mod packet_queue;
+
 pub use naia_socket_shared as shared;
 
 pub use backends::*;
-pub use error::NaiaClientSocketError;
+pub use candidate_pair::{Candidate, CandidatePair, CandidateType};
+pub use error::{HandshakeError, NaiaClientSocketError};
 pub use identity_receiver::{IdentityReceiver, IdentityReceiverResult};
 pub use packet_receiver::PacketReceiver;
 pub use packet_sender::PacketSender;
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use naia_socket_shared::QueueOverflowPolicy;
+
+/// A queue of received packets waiting to be read, which drops packets
+/// according to its overflow policy rather than growing past its capacity
+pub struct PacketQueue {
+    packets: VecDeque<Box<[u8]>>,
+    capacity: usize,
+    overflow: QueueOverflowPolicy,
+    dropped_count: u64,
+}
+
+impl PacketQueue {
+    /// Creates an empty queue, which is unbounded until `set_capacity()`
+    pub fn new() -> Self {
+        Self {
+            packets: VecDeque::new(),
+            capacity: usize::MAX,
+            overflow: QueueOverflowPolicy::DropOldest,
+            dropped_count: 0,
+        }
+    }
+
+    /// Bounds the queue to `capacity` packets, dropping any packets already
+    /// over it
+    pub fn set_capacity(&mut self, capacity: usize, overflow: QueueOverflowPolicy) {
+        self.capacity = capacity;
+        self.overflow = overflow;
+        while self.packets.len() > self.capacity {
+            match self.overflow {
+                QueueOverflowPolicy::DropOldest => self.packets.pop_front(),
+                QueueOverflowPolicy::DropNewest => self.packets.pop_back(),
+            };
+            self.dropped_count += 1;
+        }
+    }
+
+    pub fn push(&mut self, packet: Box<[u8]>) {
+        if self.packets.len() >= self.capacity {
+            self.dropped_count += 1;
+            match self.overflow {
+                QueueOverflowPolicy::DropOldest => {
+                    if self.packets.pop_front().is_none() {
+                        // a capacity of zero holds nothing
+                        return;
+                    }
+                }
+                QueueOverflowPolicy::DropNewest => return,
+            }
+        }
+        self.packets.push_back(packet);
+    }
+
+    pub fn pop(&mut self) -> Option<Box<[u8]>> {
+        self.packets.pop_front()
+    }
+
+    /// The number of packets waiting to be read
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// The number of packets dropped because the queue was full
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_socket_shared::QueueOverflowPolicy;
+
+    use super::PacketQueue;
+
+    fn fill(overflow: QueueOverflowPolicy) -> PacketQueue {
+        let mut queue = PacketQueue::new();
+        queue.set_capacity(2, overflow);
+        for packet in 1..=4u8 {
+            queue.push(Box::new([packet]));
+        }
+        queue
+    }
+
+    #[test]
+    fn drop_oldest_keeps_newest_packets() {
+        let mut queue = fill(QueueOverflowPolicy::DropOldest);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped_count(), 2);
+        assert_eq!(queue.pop().as_deref(), Some(&[3][..]));
+        assert_eq!(queue.pop().as_deref(), Some(&[4][..]));
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn drop_newest_keeps_oldest_packets() {
+        let mut queue = fill(QueueOverflowPolicy::DropNewest);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped_count(), 2);
+        assert_eq!(queue.pop().as_deref(), Some(&[1][..]));
+        assert_eq!(queue.pop().as_deref(), Some(&[2][..]));
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn set_capacity_trims_queued_packets() {
+        let mut queue = PacketQueue::new();
+        for packet in 1..=3u8 {
+            queue.push(Box::new([packet]));
+        }
+        queue.set_capacity(1, QueueOverflowPolicy::DropOldest);
+
+        assert_eq!(queue.dropped_count(), 2);
+        assert_eq!(queue.pop().as_deref(), Some(&[3][..]));
+    }
+}
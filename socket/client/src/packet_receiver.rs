@@ -1,4 +1,4 @@
-use super::{error::NaiaClientSocketError, server_addr::ServerAddr};
+use super::{candidate_pair::CandidatePair, error::NaiaClientSocketError, server_addr::ServerAddr};
 
 /// Used to receive packets from the Client Socket
 pub trait PacketReceiver: PacketReceiverClone + Send + Sync {
@@ -6,6 +6,22 @@ pub trait PacketReceiver: PacketReceiverClone + Send + Sync {
     fn receive(&mut self) -> Result<Option<&[u8]>, NaiaClientSocketError>;
     /// Get the Server's Socket address
     fn server_addr(&self) -> ServerAddr;
+    /// Get the ICE candidate pair selected for the connection, if the
+    /// underlying transport uses ICE and a pair has been selected
+    fn selected_candidate_pair(&self) -> Option<CandidatePair> {
+        None
+    }
+    /// Get the number of received packets waiting to be read, if the
+    /// underlying transport buffers them. A growing count means packets are
+    /// arriving faster than they are read
+    fn queued_len(&self) -> usize {
+        0
+    }
+    /// Get the number of received packets dropped because the receive queue
+    /// was full
+    fn dropped_count(&self) -> u64 {
+        0
+    }
 }
 
 /// Used to clone Box<dyn PacketReceiver>
@@ -1,6 +1,8 @@
 use super::{error::NaiaClientSocketError, server_addr::ServerAddr};
 
-/// Used to send packets from the Client Socket
+/// Used to send packets from the Client Socket. Clones of a PacketSender all
+/// send through the same underlying connection, so separate systems can each
+/// hold their own handle.
 pub trait PacketSender: PacketSenderClone + Send + Sync {
     /// Sends a packet from the Client Socket
     fn send(&self, payload: &[u8]) -> Result<(), NaiaClientSocketError>;
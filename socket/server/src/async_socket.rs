@@ -1,22 +1,48 @@
-use std::{io::Error as IoError, net::SocketAddr};
+use std::{
+    io::Error as IoError,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
-use futures_util::{pin_mut, select, FutureExt, StreamExt};
+use futures_util::{future, pin_mut, select, FutureExt, StreamExt};
+use log::warn;
 use webrtc_unreliable::{
     MessageResult, MessageType, SendError, Server as InnerRtcServer, SessionEndpoint,
 };
 
-use naia_socket_shared::{parse_server_url, url_to_socket_addr, IdentityToken, SocketConfig};
+use naia_socket_shared::{
+    parse_server_url, url_to_socket_addr, IdentityToken, RejectReason, SocketConfig, Transport,
+};
+
+use super::session::{setup_auth_mux, start_session_server, SessionServerShutdown};
+#[cfg(feature = "webtransport")]
+use crate::webtransport::WebTransportServer;
+use crate::{
+    error::NaiaServerSocketError,
+    metrics::{start_metrics_server, MetricsEndpoint, SocketMetrics},
+    server_addrs::ServerAddrs,
+    udp::UdpServer,
+};
 
-use super::session::start_session_server;
-use crate::{error::NaiaServerSocketError, server_addrs::ServerAddrs};
+// How often the number of open sessions is counted for the metrics
+const ACTIVE_CONNECTIONS_INTERVAL: Duration = Duration::from_secs(1);
 
 /// A socket which communicates with clients using an underlying
 /// unordered & unreliable network protocol
 
 pub struct Socket {
-    rtc_server: RtcServer,
+    // None when using `Transport::Udp`
+    rtc_server: Option<RtcServer>,
+    udp_server: Option<UdpServer>,
+    #[cfg(feature = "webtransport")]
+    webtransport_server: Option<WebTransportServer>,
     to_client_sender: smol::channel::Sender<(SocketAddr, Box<[u8]>)>,
     to_client_receiver: smol::channel::Receiver<(SocketAddr, Box<[u8]>)>,
+    metrics: SocketMetrics,
+    last_connection_count: Option<Instant>,
+    // keeps the session server accepting connections for as long as the
+    // Socket lives
+    _session_shutdown: Option<SessionServerShutdown>,
 }
 
 impl Socket {
@@ -28,32 +54,88 @@ impl Socket {
             smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
         >,
         to_session_all_auth_receiver: Option<
-            smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+            smol::channel::Receiver<(SocketAddr, Result<IdentityToken, RejectReason>)>,
         >,
     ) -> Self {
         let (to_client_sender, to_client_receiver) = smol::channel::unbounded();
 
-        let rtc_server = RtcServer::new(
-            server_addrs.webrtc_listen_addr,
-            url_to_socket_addr(&parse_server_url(&server_addrs.public_webrtc_url)),
-        )
-        .await;
+        let metrics = server_addrs.metrics.clone();
+        let shutdown = server_addrs.shutdown.clone();
+
+        match MetricsEndpoint::new(&server_addrs, &config) {
+            MetricsEndpoint::Listener(metrics_listen_addr) => {
+                start_metrics_server(metrics_listen_addr, metrics.clone(), shutdown.clone());
+            }
+            // there is no session server to serve them on
+            MetricsEndpoint::SessionServer if config.transport == Transport::Udp => {
+                warn!(
+                    "Metrics are not served over the UDP transport without an address from \
+                     `ServerAddrs::enable_metrics()`"
+                );
+            }
+            MetricsEndpoint::SessionServer | MetricsEndpoint::Disabled => {}
+        }
+
+        let auth_mux_sender = to_session_all_auth_receiver.map(|to_session_all_auth_receiver| {
+            setup_auth_mux(to_session_all_auth_receiver, shutdown.clone())
+        });
+
+        #[cfg(feature = "webtransport")]
+        let webtransport_server = server_addrs.webtransport.clone().map(|webtransport| {
+            WebTransportServer::listen(
+                webtransport,
+                &config.rtc_endpoint_path,
+                from_client_auth_sender.clone(),
+                auth_mux_sender.clone(),
+                metrics.clone(),
+                shutdown.clone(),
+            )
+        });
+
+        let (rtc_server, udp_server, session_shutdown) = match config.transport {
+            Transport::WebRtc => {
+                let rtc_server = RtcServer::new(
+                    server_addrs.webrtc_listen_addr,
+                    url_to_socket_addr(&parse_server_url(&server_addrs.public_webrtc_url)),
+                )
+                .await;
+                let session_shutdown = start_session_server(
+                    server_addrs,
+                    config,
+                    rtc_server.session_endpoint(),
+                    from_client_auth_sender,
+                    auth_mux_sender,
+                );
+                (Some(rtc_server), None, Some(session_shutdown))
+            }
+            // clients send their session requests straight to the UDP
+            // address, so there is no session server
+            Transport::Udp => {
+                let udp_listen_addr = server_addrs
+                    .udp_listen_addr
+                    .expect("Transport::Udp needs an address from `ServerAddrs::enable_udp()`");
+                let udp_server = UdpServer::listen(
+                    udp_listen_addr,
+                    from_client_auth_sender,
+                    auth_mux_sender,
+                    metrics.clone(),
+                    shutdown,
+                );
+                (None, Some(udp_server), None)
+            }
+        };
 
-        let socket = Socket {
+        Socket {
             rtc_server,
+            udp_server,
+            #[cfg(feature = "webtransport")]
+            webtransport_server,
             to_client_sender,
             to_client_receiver,
-        };
-
-        start_session_server(
-            server_addrs,
-            config,
-            socket.rtc_server.session_endpoint(),
-            from_client_auth_sender,
-            to_session_all_auth_receiver,
-        );
-
-        socket
+            metrics,
+            last_connection_count: None,
+            _session_shutdown: session_shutdown,
+        }
     }
 
     pub async fn receive(&mut self) -> Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError> {
@@ -63,14 +145,36 @@ impl Socket {
         }
 
         loop {
+            self.count_active_connections();
+
             let next = {
                 let to_client_receiver_next = self.to_client_receiver.next().fuse();
                 pin_mut!(to_client_receiver_next);
 
-                let rtc_server = &mut self.rtc_server;
-                let from_client_message_receiver_next = rtc_server.recv().fuse();
+                let from_client_message_receiver_next = match &mut self.rtc_server {
+                    Some(rtc_server) => rtc_server.recv().left_future(),
+                    None => future::pending().right_future(),
+                }
+                .fuse();
                 pin_mut!(from_client_message_receiver_next);
 
+                let from_udp_client_next = match &self.udp_server {
+                    Some(udp_server) => udp_server.recv().left_future(),
+                    None => future::pending().right_future(),
+                }
+                .fuse();
+                pin_mut!(from_udp_client_next);
+
+                #[cfg(feature = "webtransport")]
+                let from_webtransport_client_next = match &self.webtransport_server {
+                    Some(webtransport_server) => webtransport_server.recv().left_future(),
+                    None => future::pending().right_future(),
+                }
+                .fuse();
+                #[cfg(not(feature = "webtransport"))]
+                let from_webtransport_client_next = future::pending().fuse();
+                pin_mut!(from_webtransport_client_next);
+
                 select! {
                     from_client_result = from_client_message_receiver_next => {
                         Next::FromClientMessage(
@@ -82,6 +186,12 @@ impl Socket {
                             }
                         )
                     }
+                    from_client_message = from_webtransport_client_next => {
+                        Next::FromClientMessage(Ok(from_client_message))
+                    }
+                    from_client_message = from_udp_client_next => {
+                        Next::FromClientMessage(Ok(from_client_message))
+                    }
                     to_client_message = to_client_receiver_next => {
                         Next::ToClientMessage(
                             to_client_message.expect("to server message receiver closed")
@@ -93,6 +203,7 @@ impl Socket {
             match next {
                 Next::FromClientMessage(from_client_message) => match from_client_message {
                     Ok((address, payload)) => {
+                        self.metrics.packet_received(payload.len());
                         return Ok((address, payload));
                     }
                     Err(err) => {
@@ -100,17 +211,101 @@ impl Socket {
                     }
                 },
                 Next::ToClientMessage((address, payload)) => {
-                    if (self
-                        .rtc_server
-                        .send(&payload, MessageType::Binary, &address)
-                        .await)
-                        .is_err()
-                    {
-                        return Err(NaiaServerSocketError::SendError(address));
-                    }
+                    self.send_to_client(&address, &payload).await?;
+                }
+            }
+        }
+    }
+
+    async fn send_to_client(
+        &mut self,
+        address: &SocketAddr,
+        payload: &[u8],
+    ) -> Result<(), NaiaServerSocketError> {
+        #[cfg(feature = "webtransport")]
+        if let Some(webtransport_server) = &self.webtransport_server {
+            if webtransport_server.has_session(address) {
+                if let Err(err) = webtransport_server.send(address, payload) {
+                    self.metrics.packet_dropped_sending();
+                    return Err(err);
                 }
+                self.metrics.packet_sent(payload.len());
+                return Ok(());
+            }
+        }
+
+        if let Some(udp_server) = &self.udp_server {
+            if let Err(err) = udp_server.send(address, payload).await {
+                self.metrics.packet_dropped_sending();
+                return Err(err);
+            }
+            self.metrics.packet_sent(payload.len());
+            return Ok(());
+        }
+
+        let Some(rtc_server) = &mut self.rtc_server else {
+            self.metrics.packet_dropped_sending();
+            return Err(NaiaServerSocketError::SendError(*address));
+        };
+        if (rtc_server.send(payload, MessageType::Binary, address).await).is_err() {
+            self.metrics.packet_dropped_sending();
+            return Err(NaiaServerSocketError::SendError(*address));
+        }
+        self.metrics.packet_sent(payload.len());
+        Ok(())
+    }
+
+    /// Sends every packet still queued for clients, then the disconnect
+    /// packet, if any, to each client with an open session. The Socket's
+    /// listeners are closed once it is dropped afterwards
+    pub async fn shutdown(&mut self, disconnect_packet: Option<Box<[u8]>>) {
+        while let Ok((address, payload)) = self.to_client_receiver.try_recv() {
+            if self.send_to_client(&address, &payload).await.is_err() {
+                warn!("Unable to send queued packet to {} on shutdown", address);
             }
         }
+
+        let Some(disconnect_packet) = disconnect_packet else {
+            return;
+        };
+        for address in self.session_addrs() {
+            if self
+                .send_to_client(&address, &disconnect_packet)
+                .await
+                .is_err()
+            {
+                warn!("Unable to send disconnect packet to {}", address);
+            }
+        }
+    }
+
+    /// The addresses of every client with an open session
+    fn session_addrs(&self) -> Vec<SocketAddr> {
+        let mut addresses: Vec<SocketAddr> = self
+            .rtc_server
+            .as_ref()
+            .map(RtcServer::connected_clients)
+            .unwrap_or_default();
+        if let Some(udp_server) = &self.udp_server {
+            addresses.extend(udp_server.session_addrs());
+        }
+        #[cfg(feature = "webtransport")]
+        if let Some(webtransport_server) = &self.webtransport_server {
+            addresses.extend(webtransport_server.session_addrs());
+        }
+        addresses
+    }
+
+    fn count_active_connections(&mut self) {
+        if self
+            .last_connection_count
+            .is_some_and(|last_count| last_count.elapsed() < ACTIVE_CONNECTIONS_INTERVAL)
+        {
+            return;
+        }
+        self.last_connection_count = Some(Instant::now());
+        self.metrics
+            .set_active_connections(self.session_addrs().len());
     }
 
     pub fn sender(&self) -> smol::channel::Sender<(SocketAddr, Box<[u8]>)> {
@@ -135,6 +330,10 @@ impl RtcServer {
         self.inner.session_endpoint()
     }
 
+    pub fn connected_clients(&self) -> Vec<SocketAddr> {
+        self.inner.connected_clients().copied().collect()
+    }
+
     pub async fn recv(&mut self) -> Result<MessageResult<'_>, IoError> {
         self.inner.recv().await
     }
@@ -2,7 +2,7 @@ use std::net::SocketAddr;
 
 use smol::channel::{Sender, TrySendError};
 
-use naia_socket_shared::IdentityToken;
+use naia_socket_shared::{IdentityToken, RejectReason};
 
 use crate::NaiaServerSocketError;
 
@@ -14,30 +14,35 @@ pub trait AuthSender: AuthSenderClone + Send + Sync {
         address: &SocketAddr,
         identity_token: &IdentityToken,
     ) -> Result<(), NaiaServerSocketError>;
-    /// Rejects an incoming connection from the Server Socket
-    fn reject(&self, address: &SocketAddr) -> Result<(), NaiaServerSocketError>;
+    /// Rejects an incoming connection from the Server Socket, telling the
+    /// Client why
+    fn reject(
+        &self,
+        address: &SocketAddr,
+        reason: &RejectReason,
+    ) -> Result<(), NaiaServerSocketError>;
 }
 
 // Impl
 /// Used to send Auth messages to the Server Socket
 #[derive(Clone)]
 pub struct AuthSenderImpl {
-    channel_sender: Sender<(SocketAddr, Option<IdentityToken>)>,
+    channel_sender: Sender<(SocketAddr, Result<IdentityToken, RejectReason>)>,
 }
 
 impl AuthSenderImpl {
     /// Creates a new AuthSender
-    pub fn new(channel_sender: Sender<(SocketAddr, Option<IdentityToken>)>) -> Self {
+    pub fn new(channel_sender: Sender<(SocketAddr, Result<IdentityToken, RejectReason>)>) -> Self {
         Self { channel_sender }
     }
 
     fn send(
         &self,
         address: &SocketAddr,
-        accept: Option<IdentityToken>,
+        answer: Result<IdentityToken, RejectReason>,
     ) -> Result<(), NaiaServerSocketError> {
         self.channel_sender
-            .try_send((*address, accept))
+            .try_send((*address, answer))
             .map_err(|err| match err {
                 TrySendError::Full(_) => unreachable!("the channel is expected to be unbound"),
                 TrySendError::Closed(_) => NaiaServerSocketError::SendError(*address),
@@ -52,12 +57,17 @@ impl AuthSender for AuthSenderImpl {
         address: &SocketAddr,
         identity_token: &IdentityToken,
     ) -> Result<(), NaiaServerSocketError> {
-        self.send(address, Some(identity_token.clone()))
+        self.send(address, Ok(identity_token.clone()))
     }
 
-    /// Rejects an incoming connection from the Server Socket
-    fn reject(&self, address: &SocketAddr) -> Result<(), NaiaServerSocketError> {
-        self.send(address, None)
+    /// Rejects an incoming connection from the Server Socket, telling the
+    /// Client why
+    fn reject(
+        &self,
+        address: &SocketAddr,
+        reason: &RejectReason,
+    ) -> Result<(), NaiaServerSocketError> {
+        self.send(address, Err(reason.clone()))
     }
 }
 
@@ -20,3 +20,9 @@ impl fmt::Display for NaiaServerSocketError {
 }
 
 impl Error for NaiaServerSocketError {}
+
+impl From<std::io::Error> for NaiaServerSocketError {
+    fn from(err: std::io::Error) -> Self {
+        NaiaServerSocketError::Wrapped(Box::new(err))
+    }
+}
@@ -0,0 +1,610 @@
+use std::{collections::HashMap, fmt, future::Future, sync::Arc, time::Duration};
+
+use http::{header, HeaderValue, Method, Response, StatusCode};
+use smol::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    Timer,
+};
+
+// How long a kept-alive connection may sit idle between requests before it is
+// closed
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(5);
+// Upper bound on the number of header lines in a request, so that many small
+// headers can't exhaust memory
+const MAX_HEADER_COUNT: usize = 64;
+// Initial capacity of the buffer each request line is read into, enough for
+// typical headers so it rarely needs to grow
+const LINE_CAPACITY: usize = 256;
+// Upper bound on how much of the body buffer is allocated up front from the
+// Content-Length header, so a bogus header can't force a huge allocation
+const MAX_BODY_PREALLOCATION: usize = 16 * 1024;
+
+/// Content type of the plain text bodies returned by route handlers
+pub(crate) const TEXT_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+/// Answers a `GET` request to a route registered with the Server Socket,
+/// returning the body of the response
+pub(crate) type RouteHandler = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// A request read from a client
+pub(crate) struct HttpRequest {
+    pub method: Method,
+    /// The request target, without any query string
+    pub path: String,
+    pub body: Vec<u8>,
+    headers: Vec<(String, String)>,
+    http_1_0: bool,
+}
+
+impl HttpRequest {
+    /// The value of the first header with the given name, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the client wants the connection kept open after the response
+    pub fn keep_alive(&self) -> bool {
+        match self.header("connection") {
+            Some(value) if value.eq_ignore_ascii_case("close") => false,
+            Some(value) if value.eq_ignore_ascii_case("keep-alive") => true,
+            _ => !self.http_1_0,
+        }
+    }
+}
+
+/// Why a request could not be read
+#[derive(Debug)]
+pub(crate) enum HttpError {
+    Io(std::io::Error),
+    /// The request was not valid HTTP/1.x
+    Malformed(&'static str),
+    /// A header line or the body exceeded the maximum request size
+    TooLarge,
+}
+
+impl HttpError {
+    /// The status to answer the request with, if the connection is still
+    /// usable
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Io(_) => None,
+            Self::Malformed(_) => Some(StatusCode::BAD_REQUEST),
+            Self::TooLarge => Some(StatusCode::PAYLOAD_TOO_LARGE),
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => fmt::Display::fmt(err, f),
+            Self::Malformed(reason) => write!(f, "malformed request, {}", reason),
+            Self::TooLarge => f.write_str("request exceeds the maximum request size"),
+        }
+    }
+}
+
+impl From<std::io::Error> for HttpError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// The `GET` routes answered by an HTTP server, besides any it handles itself
+#[derive(Clone, Default)]
+pub(crate) struct Router {
+    routes: HashMap<String, (&'static str, RouteHandler)>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers `GET <path>` with the handler's body, sent with the given
+    /// content type. Replaces any handler already registered at the path
+    pub fn route(&mut self, path: &str, content_type: &'static str, handler: RouteHandler) {
+        self.routes
+            .insert(path.to_string(), (content_type, handler));
+    }
+
+    /// Answers the request if it is for a registered route
+    pub fn handle(&self, request: &HttpRequest) -> Option<Response<Vec<u8>>> {
+        let (content_type, handler) = self.routes.get(&request.path)?;
+        let response = if request.method == Method::GET || request.method == Method::HEAD {
+            let mut response = Response::new(handler().into_bytes());
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+            response
+        } else {
+            let mut response = empty_response(StatusCode::METHOD_NOT_ALLOWED);
+            response
+                .headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static("GET, HEAD"));
+            response
+        };
+        Some(response)
+    }
+}
+
+/// A response with the given status and no body
+pub(crate) fn empty_response(status: StatusCode) -> Response<Vec<u8>> {
+    let mut response = Response::new(Vec::new());
+    *response.status_mut() = status;
+    response
+}
+
+/// Reads requests from the connection, answering each with the response
+/// returned by `handle`, until either side asks to close it. Requests which
+/// can't be read are answered with an error status before closing. The stream
+/// is read from through one clone and written to through another, so that
+/// plain and TLS connections are served alike
+pub(crate) async fn serve_connection<S, F, Fut>(
+    stream: S,
+    max_request_bytes: usize,
+    mut handle: F,
+) -> Result<(), HttpError>
+where
+    S: AsyncRead + AsyncWrite + Clone + Unpin,
+    F: FnMut(HttpRequest) -> Fut,
+    Fut: Future<Output = Response<Vec<u8>>>,
+{
+    let mut reader = smol::io::BufReader::new(stream.clone());
+    let mut writer = stream;
+    let mut first_request = true;
+
+    let result = loop {
+        let next_request = read_request(&mut reader, max_request_bytes);
+        let request = if first_request {
+            next_request.await
+        } else {
+            // a kept-alive connection may go quiet, so stop waiting eventually
+            smol::future::or(next_request, async {
+                Timer::after(KEEP_ALIVE_TIMEOUT).await;
+                Ok(None)
+            })
+            .await
+        };
+        first_request = false;
+
+        let request = match request {
+            Ok(Some(request)) => request,
+            Ok(None) => break Ok(()),
+            Err(err) => {
+                if let Some(status) = err.status() {
+                    let mut response = empty_response(status);
+                    close_after(&mut response);
+                    let _ = write_response(&mut writer, &response).await;
+                }
+                break Err(err);
+            }
+        };
+
+        let is_head = request.method == Method::HEAD;
+        let keep_alive = request.keep_alive();
+        let mut response = handle(request).await;
+        let closing = !keep_alive || closes_after(&response);
+        if closing {
+            close_after(&mut response);
+        }
+        if is_head {
+            write_head(&mut writer, &response).await?;
+        } else {
+            write_response(&mut writer, &response).await?;
+        }
+        if closing {
+            break Ok(());
+        }
+    };
+
+    writer.flush().await?;
+    writer.close().await?;
+    result
+}
+
+/// Marks the response as the last on its connection
+pub(crate) fn close_after<T>(response: &mut Response<T>) {
+    response
+        .headers_mut()
+        .insert(header::CONNECTION, HeaderValue::from_static("close"));
+}
+
+fn closes_after<T>(response: &Response<T>) -> bool {
+    response
+        .headers()
+        .get(header::CONNECTION)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"close"))
+}
+
+/// Reads the next request from the client, or None if the client closed the
+/// connection before sending one. No header line or body larger than
+/// `max_request_bytes` is buffered
+pub(crate) async fn read_request<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    max_request_bytes: usize,
+) -> Result<Option<HttpRequest>, HttpError> {
+    let mut line = Vec::with_capacity(LINE_CAPACITY);
+
+    // request line, skipping any empty lines left over from a previous request
+    let request_line = loop {
+        if !read_line(reader, &mut line, max_request_bytes).await? {
+            if line.is_empty() {
+                return Ok(None);
+            }
+            return Err(HttpError::Malformed(
+                "request ended before its request line",
+            ));
+        }
+        if !line.is_empty() {
+            break line_to_str(&line)?.to_string();
+        }
+    };
+    let mut parts = request_line.split(' ');
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(HttpError::Malformed(
+            "request line is not 'METHOD TARGET VERSION'",
+        ));
+    };
+    let method = Method::from_bytes(method.as_bytes())
+        .map_err(|_| HttpError::Malformed("method is not a valid token"))?;
+    let http_1_0 = match version {
+        "HTTP/1.1" => false,
+        "HTTP/1.0" => true,
+        _ => return Err(HttpError::Malformed("version is not HTTP/1.0 or HTTP/1.1")),
+    };
+    let path = target
+        .split_once('?')
+        .map_or(target, |(path, _query)| path)
+        .to_string();
+
+    // headers
+    let mut headers = Vec::new();
+    loop {
+        if !read_line(reader, &mut line, max_request_bytes).await? {
+            return Err(HttpError::Malformed("request ended before its headers"));
+        }
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() >= MAX_HEADER_COUNT {
+            return Err(HttpError::TooLarge);
+        }
+        let Some((name, value)) = line_to_str(&line)?.split_once(':') else {
+            return Err(HttpError::Malformed("header line has no ':'"));
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = HttpRequest {
+        method,
+        path,
+        body: Vec::new(),
+        headers,
+        http_1_0,
+    };
+
+    // body
+    let chunked = request
+        .header("transfer-encoding")
+        .is_some_and(|value| value.to_ascii_lowercase().contains("chunked"));
+    if chunked {
+        request.body = read_chunked_body(reader, &mut line, max_request_bytes).await?;
+    } else if let Some(content_length) = request.header("content-length") {
+        let content_length = content_length
+            .parse::<usize>()
+            .map_err(|_| HttpError::Malformed("Content-Length is not a number"))?;
+        if content_length > max_request_bytes {
+            return Err(HttpError::TooLarge);
+        }
+        let mut body = Vec::with_capacity(content_length.min(MAX_BODY_PREALLOCATION));
+        reader
+            .take(content_length as u64)
+            .read_to_end(&mut body)
+            .await?;
+        if body.len() < content_length {
+            return Err(HttpError::Malformed("request ended before its body"));
+        }
+        request.body = body;
+    }
+
+    Ok(Some(request))
+}
+
+async fn read_chunked_body<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut Vec<u8>,
+    max_request_bytes: usize,
+) -> Result<Vec<u8>, HttpError> {
+    let mut body = Vec::new();
+    loop {
+        if !read_line(reader, line, max_request_bytes).await? {
+            return Err(HttpError::Malformed("request ended before its chunk size"));
+        }
+        let size = line_to_str(line)?;
+        let size = size
+            .split_once(';')
+            .map_or(size, |(size, _extensions)| size);
+        let size = usize::from_str_radix(size.trim(), 16)
+            .map_err(|_| HttpError::Malformed("chunk size is not a hex number"))?;
+        if size == 0 {
+            break;
+        }
+        if body.len() + size > max_request_bytes {
+            return Err(HttpError::TooLarge);
+        }
+        let start = body.len();
+        reader.take(size as u64).read_to_end(&mut body).await?;
+        if body.len() - start < size {
+            return Err(HttpError::Malformed("request ended inside a chunk"));
+        }
+        if !read_line(reader, line, max_request_bytes).await? || !line.is_empty() {
+            return Err(HttpError::Malformed(
+                "chunk is not followed by a line ending",
+            ));
+        }
+    }
+
+    // trailers are read and ignored
+    loop {
+        if !read_line(reader, line, max_request_bytes).await? {
+            return Err(HttpError::Malformed("request ended before its trailers"));
+        }
+        if line.is_empty() {
+            return Ok(body);
+        }
+    }
+}
+
+// Reads a line into `line` without its line ending, returning false if the
+// stream ended before one was found
+async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut Vec<u8>,
+    max_line_bytes: usize,
+) -> Result<bool, HttpError> {
+    line.clear();
+    // one more byte than the limit is allowed, for the '\n'
+    (&mut *reader)
+        .take(max_line_bytes as u64 + 1)
+        .read_until(b'\n', line)
+        .await?;
+    if line.last() != Some(&b'\n') {
+        if line.len() > max_line_bytes {
+            return Err(HttpError::TooLarge);
+        }
+        return Ok(false);
+    }
+    line.pop();
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(true)
+}
+
+fn line_to_str(line: &[u8]) -> Result<&str, HttpError> {
+    std::str::from_utf8(line).map_err(|_| HttpError::Malformed("line is not valid UTF-8"))
+}
+
+/// Writes the response, setting its Content-Length from its body
+pub(crate) async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &Response<Vec<u8>>,
+) -> std::io::Result<()> {
+    let mut out = response_head_to_vec(response);
+    out.extend_from_slice(response.body());
+    writer.write_all(&out).await
+}
+
+async fn write_head<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &Response<Vec<u8>>,
+) -> std::io::Result<()> {
+    writer.write_all(&response_head_to_vec(response)).await
+}
+
+fn response_head_to_vec(response: &Response<Vec<u8>>) -> Vec<u8> {
+    let status = response.status();
+    let mut out = Vec::with_capacity(120);
+    out.extend_from_slice(b"HTTP/1.1 ");
+    out.extend_from_slice(status.as_str().as_bytes());
+    out.push(b' ');
+    out.extend_from_slice(status.canonical_reason().unwrap_or("Unknown").as_bytes());
+    out.extend_from_slice(b"\r\n");
+    for (name, value) in response.headers() {
+        if name == header::CONTENT_LENGTH {
+            continue;
+        }
+        out.extend_from_slice(name.as_str().as_bytes());
+        out.extend_from_slice(b": ");
+        out.extend_from_slice(value.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(format!("content-length: {}\r\n\r\n", response.body().len()).as_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        io,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    };
+
+    use http::{Method, StatusCode};
+    use smol::io::{AsyncRead, BufReader};
+
+    use super::{read_request, response_head_to_vec, HttpError, HttpRequest, Router};
+
+    fn read(bytes: &[u8], max_request_bytes: usize) -> Result<Option<HttpRequest>, HttpError> {
+        let mut reader = bytes;
+        smol::block_on(read_request(&mut reader, max_request_bytes))
+    }
+
+    // Hands out each chunk in turn, with a pending read before every chunk
+    // after the first, as a socket does when a request arrives over several
+    // TCP segments
+    struct SplitReader {
+        chunks: VecDeque<Vec<u8>>,
+        pending: bool,
+        pending_reads: usize,
+    }
+
+    impl AsyncRead for SplitReader {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            if self.pending {
+                self.pending = false;
+                self.pending_reads += 1;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let Some(mut chunk) = self.chunks.pop_front() else {
+                return Poll::Ready(Ok(0));
+            };
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            if len < chunk.len() {
+                self.chunks.push_front(chunk.split_off(len));
+            } else {
+                self.pending = true;
+            }
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    #[test]
+    fn reads_request_with_content_length() {
+        let request = read(
+            b"POST /rtc_session?a=1 HTTP/1.1\r\nAUTHORIZATION: abc\r\nContent-Length: 4\r\n\r\nv=0\n",
+            1024,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.path, "/rtc_session");
+        assert_eq!(request.header("authorization"), Some("abc"));
+        assert_eq!(request.body, b"v=0\n");
+        assert!(request.keep_alive());
+    }
+
+    #[test]
+    fn reads_chunked_body() {
+        let request = read(
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nv=0\n\r\n3;ext=1\r\nabc\r\n0\r\nTrailer: x\r\n\r\n",
+            1024,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.body, b"v=0\nabc");
+    }
+
+    #[test]
+    fn reads_body_split_by_pending_read() {
+        let chunks: [&[u8]; 3] = [
+            b"POST /rtc_session HTTP/1.1\r\nContent-Le",
+            b"ngth: 8\r\n\r\nv=0\no",
+            b"=-\n",
+        ];
+        let mut reader = BufReader::new(SplitReader {
+            chunks: chunks.iter().map(|chunk| chunk.to_vec()).collect(),
+            pending: false,
+            pending_reads: 0,
+        });
+
+        let request = smol::block_on(read_request(&mut reader, 1024))
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.path, "/rtc_session");
+        assert_eq!(request.body, b"v=0\no=-\n");
+        assert_eq!(reader.get_ref().pending_reads, 2);
+    }
+
+    #[test]
+    fn reads_nothing_from_closed_connection() {
+        assert!(read(b"", 1024).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        for bytes in [
+            &b"\xff\xfe\xfd\r\n"[..],
+            b"POST /rtc_session HTTP/1.1\r\nContent-",
+            b"GET /\r\n\r\n",
+            b"GET / HTTP/2\r\n\r\n",
+            b"GET / HTTP/1.1\r\nHost\r\n\r\n",
+            b"POST / HTTP/1.1\r\nContent-Length: x\r\n\r\n",
+            b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc",
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nz\r\n",
+        ] {
+            assert!(
+                matches!(read(bytes, 1024), Err(HttpError::Malformed(_))),
+                "{:?}",
+                String::from_utf8_lossy(bytes)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_requests() {
+        let mut long_header = b"GET / HTTP/1.1\r\nAuthorization: ".to_vec();
+        long_header.resize(long_header.len() + 64, b'a');
+        for bytes in [
+            &long_header[..],
+            b"POST / HTTP/1.1\r\nContent-Length: 65\r\n\r\n",
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n41\r\n",
+        ] {
+            assert!(matches!(read(bytes, 64), Err(HttpError::TooLarge)));
+        }
+    }
+
+    #[test]
+    fn keep_alive_follows_version_and_connection_header() {
+        let keep_alive = |bytes: &[u8]| read(bytes, 1024).unwrap().unwrap().keep_alive();
+        assert!(keep_alive(b"GET / HTTP/1.1\r\n\r\n"));
+        assert!(!keep_alive(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n"));
+        assert!(!keep_alive(b"GET / HTTP/1.0\r\n\r\n"));
+        assert!(keep_alive(
+            b"GET / HTTP/1.0\r\nConnection: Keep-Alive\r\n\r\n"
+        ));
+    }
+
+    #[test]
+    fn router_answers_registered_routes() {
+        let mut router = Router::new();
+        router.route("/health", "text/plain", Arc::new(|| "ok".to_string()));
+
+        let request = read(b"GET /health HTTP/1.1\r\n\r\n", 1024)
+            .unwrap()
+            .unwrap();
+        let response = router.handle(&request).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), b"ok");
+        let head = String::from_utf8(response_head_to_vec(&response)).unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.contains("content-type: text/plain\r\n"));
+        assert!(head.ends_with("content-length: 2\r\n\r\n"));
+
+        let request = read(b"POST /health HTTP/1.1\r\n\r\n", 1024)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            router.handle(&request).unwrap().status(),
+            StatusCode::METHOD_NOT_ALLOWED
+        );
+
+        let request = read(b"GET /other HTTP/1.1\r\n\r\n", 1024).unwrap().unwrap();
+        assert!(router.handle(&request).is_none());
+    }
+}
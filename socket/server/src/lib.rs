@@ -18,11 +18,19 @@ mod auth_receiver;
 mod auth_sender;
 mod conditioned_packet_receiver;
 mod error;
+mod http_server;
+mod metrics;
 mod packet_receiver;
 mod packet_sender;
 mod server_addrs;
 mod session;
+#[cfg(feature = "tls")]
+mod session_tls;
+mod shutdown;
 mod socket;
+mod udp;
+#[cfg(feature = "webtransport")]
+mod webtransport;
 
 /// Executor for Server
 pub mod executor;
@@ -30,8 +38,14 @@ pub mod executor;
 pub use auth_receiver::AuthReceiver;
 pub use auth_sender::AuthSender;
 pub use error::NaiaServerSocketError;
+pub use metrics::{MetricsEndpoint, SocketMetrics};
 pub use naia_socket_shared as shared;
 pub use packet_receiver::PacketReceiver;
 pub use packet_sender::PacketSender;
 pub use server_addrs::ServerAddrs;
+#[cfg(feature = "tls")]
+pub use session_tls::SessionTlsConfig;
+pub use shutdown::SocketShutdown;
 pub use socket::Socket;
+#[cfg(feature = "webtransport")]
+pub use webtransport::WebTransportConfig;
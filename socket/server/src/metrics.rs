@@ -0,0 +1,434 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    net::{SocketAddr, TcpListener},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use http::StatusCode;
+use log::{info, warn};
+use smol::Async;
+
+use naia_socket_shared::SocketConfig;
+
+use crate::{
+    executor,
+    http_server::{self, empty_response, Router},
+    server_addrs::ServerAddrs,
+    session::SessionOutcome,
+    shutdown::SocketShutdown,
+};
+
+pub(crate) const METRICS_PATH: &str = "/metrics";
+pub(crate) const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+// Scrape requests are small, so anything larger is refused
+const MAX_METRICS_REQUEST_BYTES: usize = 4096;
+
+/// Counters kept by a Server Socket, which can be scraped in the Prometheus
+/// text format from `GET /metrics`, see `MetricsEndpoint`. The naia Server
+/// publishes its own counters here too, such as its active users and the
+/// Messages sent over each Channel. Cloning shares the same counters
+#[derive(Clone, Default)]
+pub struct SocketMetrics {
+    inner: Arc<SocketMetricsInner>,
+}
+
+#[derive(Default)]
+struct SocketMetricsInner {
+    session_requests: AtomicU64,
+    sessions_accepted: AtomicU64,
+    sessions_rejected: AtomicU64,
+    sessions_failed: AtomicU64,
+    packets_received: AtomicU64,
+    bytes_received: AtomicU64,
+    packets_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    packets_dropped_sending: AtomicU64,
+    packets_dropped_receiving: AtomicU64,
+    active_connections: AtomicU64,
+    families: Mutex<BTreeMap<String, MetricFamily>>,
+}
+
+// A metric set from outside the Socket, with one sample per set of labels
+struct MetricFamily {
+    metric_type: &'static str,
+    help: Option<String>,
+    samples: BTreeMap<String, f64>,
+}
+
+impl SocketMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an application-defined gauge, such as the number of players in a
+    /// match, to be served alongside the Socket's own counters. `name` should
+    /// be a valid Prometheus metric name
+    pub fn set_gauge(&self, name: &str, value: f64) {
+        self.set_sample(name, "gauge", None, &[], value);
+    }
+
+    /// Sets a counter kept outside the Socket, such as by the naia Server, to
+    /// be served alongside the Socket's own counters. Each distinct set of
+    /// `labels` is served as its own sample of the counter. `name` should be
+    /// a valid Prometheus metric name
+    pub fn set_counter(&self, name: &str, help: &str, labels: &[(&str, &str)], value: u64) {
+        self.set_sample(name, "counter", Some(help), labels, value as f64);
+    }
+
+    fn set_sample(
+        &self,
+        name: &str,
+        metric_type: &'static str,
+        help: Option<&str>,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        let mut families = self
+            .inner
+            .families
+            .lock()
+            .expect("metrics families lock poisoned");
+        let family = families
+            .entry(name.to_string())
+            .or_insert_with(|| MetricFamily {
+                metric_type,
+                help: help.map(str::to_string),
+                samples: BTreeMap::new(),
+            });
+        family.samples.insert(render_labels(labels), value);
+    }
+
+    pub(crate) fn record_session(&self, outcome: SessionOutcome) {
+        let counter = match outcome {
+            SessionOutcome::Preflight => return,
+            SessionOutcome::Accepted => &self.inner.sessions_accepted,
+            SessionOutcome::Rejected => &self.inner.sessions_rejected,
+            SessionOutcome::Failed => &self.inner.sessions_failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.inner.session_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn packet_received(&self, bytes: usize) {
+        self.inner.packets_received.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn packet_sent(&self, bytes: usize) {
+        self.inner.packets_sent.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .bytes_sent
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Counts a packet which could not be sent to a client
+    pub(crate) fn packet_dropped_sending(&self) {
+        self.inner
+            .packets_dropped_sending
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a packet received from a client which was discarded before
+    /// reaching the Server, i.e. because the client had no session
+    pub(crate) fn packet_dropped_receiving(&self) {
+        self.inner
+            .packets_dropped_receiving
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_active_connections(&self, count: usize) {
+        self.inner
+            .active_connections
+            .store(count as u64, Ordering::Relaxed);
+    }
+
+    /// Renders every metric in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let counters: [(&str, &str, &AtomicU64); 8] = [
+            (
+                "naia_session_requests_total",
+                "Session requests received",
+                &self.inner.session_requests,
+            ),
+            (
+                "naia_sessions_accepted_total",
+                "Session requests answered with a session",
+                &self.inner.sessions_accepted,
+            ),
+            (
+                "naia_sessions_rejected_total",
+                "Session requests whose auth was rejected",
+                &self.inner.sessions_rejected,
+            ),
+            (
+                "naia_sessions_failed_total",
+                "Session requests which were malformed or could not be answered",
+                &self.inner.sessions_failed,
+            ),
+            (
+                "naia_packets_received_total",
+                "Packets received from clients",
+                &self.inner.packets_received,
+            ),
+            (
+                "naia_bytes_received_total",
+                "Bytes received from clients",
+                &self.inner.bytes_received,
+            ),
+            (
+                "naia_packets_sent_total",
+                "Packets sent to clients",
+                &self.inner.packets_sent,
+            ),
+            (
+                "naia_bytes_sent_total",
+                "Bytes sent to clients",
+                &self.inner.bytes_sent,
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, help, counter) in counters {
+            let _ = writeln!(output, "# HELP {} {}", name, help);
+            let _ = writeln!(output, "# TYPE {} counter", name);
+            let _ = writeln!(output, "{} {}", name, counter.load(Ordering::Relaxed));
+        }
+        let _ = writeln!(
+            output,
+            "# HELP naia_packets_dropped_total Packets which could not be sent to a client, or \
+             were received from a client without a session"
+        );
+        let _ = writeln!(output, "# TYPE naia_packets_dropped_total counter");
+        let _ = writeln!(
+            output,
+            "naia_packets_dropped_total{{direction=\"sent\"}} {}",
+            self.inner.packets_dropped_sending.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            output,
+            "naia_packets_dropped_total{{direction=\"received\"}} {}",
+            self.inner.packets_dropped_receiving.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            output,
+            "# HELP naia_active_connections Clients with an open session"
+        );
+        let _ = writeln!(output, "# TYPE naia_active_connections gauge");
+        let _ = writeln!(
+            output,
+            "naia_active_connections {}",
+            self.inner.active_connections.load(Ordering::Relaxed)
+        );
+        let families = self
+            .inner
+            .families
+            .lock()
+            .expect("metrics families lock poisoned");
+        for (name, family) in families.iter() {
+            if let Some(help) = &family.help {
+                let _ = writeln!(output, "# HELP {} {}", name, help);
+            }
+            let _ = writeln!(output, "# TYPE {} {}", name, family.metric_type);
+            for (labels, value) in &family.samples {
+                let _ = writeln!(output, "{}{} {}", name, labels, value);
+            }
+        }
+        output
+    }
+}
+
+// Renders labels as `{name="value",...}`, escaping each value as the
+// Prometheus text format requires
+fn render_labels(labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let mut output = String::from("{");
+    for (index, (name, value)) in labels.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+        let value = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        let _ = write!(output, "{}=\"{}\"", name, value);
+    }
+    output.push('}');
+    output
+}
+
+/// Where a Server Socket serves its metrics. There is only ever one
+/// endpoint: a separate listener if `ServerAddrs::enable_metrics()` was
+/// given an address, which should usually be internal-only, otherwise the
+/// session server if `SocketConfig::metrics_enabled` is set
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsEndpoint {
+    Listener(SocketAddr),
+    SessionServer,
+    Disabled,
+}
+
+impl MetricsEndpoint {
+    pub fn new(server_addrs: &ServerAddrs, config: &SocketConfig) -> Self {
+        if let Some(metrics_listen_addr) = server_addrs.metrics_listen_addr {
+            return Self::Listener(metrics_listen_addr);
+        }
+        if config.metrics_enabled {
+            return Self::SessionServer;
+        }
+        Self::Disabled
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::Disabled
+    }
+}
+
+/// Answers `GET /metrics` with the given metrics
+pub(crate) fn route_metrics(router: &mut Router, metrics: SocketMetrics) {
+    router.route(
+        METRICS_PATH,
+        PROMETHEUS_CONTENT_TYPE,
+        Arc::new(move || metrics.to_prometheus()),
+    );
+}
+
+/// Serves `GET /metrics` on its own listener, so that it can be bound to an
+/// internal-only address separate from the public session server
+pub(crate) fn start_metrics_server(
+    listen_addr: SocketAddr,
+    metrics: SocketMetrics,
+    shutdown: SocketShutdown,
+) {
+    let task_guard = shutdown.task_guard();
+    executor::spawn(async move {
+        let _task_guard = task_guard;
+        let listener = Async::<TcpListener>::bind(listen_addr)
+            .expect("unable to bind a TCP Listener to the supplied metrics address");
+        info!("Metrics available at GET http://{}/metrics", listen_addr);
+
+        let mut router = Router::new();
+        route_metrics(&mut router, metrics);
+        let router = Arc::new(router);
+
+        while let Some(accepted) = shutdown.or_requested(listener.accept()).await {
+            let Ok((stream, remote_addr)) = accepted else {
+                warn!("Unable to accept an incoming metrics request");
+                continue;
+            };
+            let router = router.clone();
+            executor::spawn(async move {
+                let result = http_server::serve_connection(
+                    async_dup::Arc::new(stream),
+                    MAX_METRICS_REQUEST_BYTES,
+                    |request| {
+                        let response = router
+                            .handle(&request)
+                            .unwrap_or_else(|| empty_response(StatusCode::NOT_FOUND));
+                        std::future::ready(response)
+                    },
+                )
+                .await;
+                if let Err(err) = result {
+                    warn!(
+                        "Unable to answer metrics request from {}. Error: {}",
+                        remote_addr, err
+                    );
+                }
+            })
+            .detach();
+        }
+    })
+    .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_socket_shared::SocketConfig;
+
+    use super::{MetricsEndpoint, SocketMetrics};
+    use crate::{server_addrs::ServerAddrs, session::SessionOutcome};
+
+    #[test]
+    fn renders_counters_and_gauges() {
+        let metrics = SocketMetrics::new();
+        metrics.record_session(SessionOutcome::Preflight);
+        metrics.record_session(SessionOutcome::Accepted);
+        metrics.packet_received(40);
+        metrics.packet_received(2);
+        metrics.packet_dropped_sending();
+        metrics.packet_dropped_receiving();
+        metrics.packet_dropped_receiving();
+        metrics.set_active_connections(5);
+        metrics.set_gauge("naia_active_users", 3.0);
+
+        let output = metrics.to_prometheus();
+        assert!(output.contains("# TYPE naia_session_requests_total counter\n"));
+        assert!(output.contains("naia_session_requests_total 1\n"));
+        assert!(output.contains("naia_sessions_accepted_total 1\n"));
+        assert!(output.contains("naia_sessions_rejected_total 0\n"));
+        assert!(output.contains("naia_packets_received_total 2\n"));
+        assert!(output.contains("naia_bytes_received_total 42\n"));
+        assert!(output.contains("naia_packets_dropped_total{direction=\"sent\"} 1\n"));
+        assert!(output.contains("naia_packets_dropped_total{direction=\"received\"} 2\n"));
+        assert!(
+            output.contains("# TYPE naia_active_connections gauge\nnaia_active_connections 5\n")
+        );
+        assert!(output.contains("# TYPE naia_active_users gauge\nnaia_active_users 3\n"));
+    }
+
+    #[test]
+    fn renders_labelled_counters_once_per_family() {
+        let metrics = SocketMetrics::new();
+        let help = "Messages sent over each Channel";
+        metrics.set_counter("naia_messages_sent_total", help, &[("channel", "Chat")], 4);
+        metrics.set_counter("naia_messages_sent_total", help, &[("channel", "Input")], 1);
+        metrics.set_counter("naia_messages_sent_total", help, &[("channel", "Chat")], 6);
+        metrics.set_counter("naia_messages_sent_total", help, &[("channel", "a\"b")], 0);
+
+        let output = metrics.to_prometheus();
+        assert_eq!(output.matches("# TYPE naia_messages_sent_total").count(), 1);
+        assert!(output.contains(
+            "# HELP naia_messages_sent_total Messages sent over each Channel\n\
+             # TYPE naia_messages_sent_total counter\n\
+             naia_messages_sent_total{channel=\"Chat\"} 6\n\
+             naia_messages_sent_total{channel=\"Input\"} 1\n\
+             naia_messages_sent_total{channel=\"a\\\"b\"} 0\n"
+        ));
+    }
+
+    #[test]
+    fn metrics_are_served_from_one_endpoint() {
+        let mut server_addrs = ServerAddrs::default();
+        let mut config = SocketConfig::default();
+        assert_eq!(
+            MetricsEndpoint::new(&server_addrs, &config),
+            MetricsEndpoint::Disabled
+        );
+
+        config.metrics_enabled = true;
+        assert_eq!(
+            MetricsEndpoint::new(&server_addrs, &config),
+            MetricsEndpoint::SessionServer
+        );
+
+        let metrics_listen_addr = "127.0.0.1:14195".parse().unwrap();
+        server_addrs.enable_metrics(metrics_listen_addr);
+        assert_eq!(
+            MetricsEndpoint::new(&server_addrs, &config),
+            MetricsEndpoint::Listener(metrics_listen_addr)
+        );
+        config.metrics_enabled = false;
+        assert_eq!(
+            MetricsEndpoint::new(&server_addrs, &config),
+            MetricsEndpoint::Listener(metrics_listen_addr)
+        );
+    }
+}
@@ -1,14 +1,50 @@
-use std::{default::Default, net::SocketAddr};
+use std::{default::Default, net::SocketAddr, sync::Arc};
+
+#[cfg(feature = "tls")]
+use crate::session_tls::SessionTlsConfig;
+#[cfg(feature = "webtransport")]
+use crate::webtransport::WebTransportConfig;
+use crate::{http_server::RouteHandler, metrics::SocketMetrics, shutdown::SocketShutdown};
 
 /// List of addresses needed to start listening on a ServerSocket
 #[derive(Clone)]
 pub struct ServerAddrs {
     /// IP Address to listen on for the signaling portion of WebRTC
     pub session_listen_addr: SocketAddr,
-    /// IP Address to listen on for UDP WebRTC data channels
+    /// IP Address to bind to for UDP WebRTC data channels. Behind a NAT or
+    /// cloud load balancer this is the internal address, which usually
+    /// differs from the advertised one
     pub webrtc_listen_addr: SocketAddr,
-    /// The public WebRTC IP address to advertise
+    /// The public WebRTC IP address to advertise to clients as the primary
+    /// ICE candidate, which must forward to `webrtc_listen_addr`. Clients
+    /// behind restrictive NATs reach it through the TURN servers in their own
+    /// `SocketConfig::ice_servers`
     pub public_webrtc_url: String,
+    /// Additional public addresses to advertise as ICE candidates, for servers
+    /// which are reachable through more than one external address (i.e. both
+    /// an IPv4 and IPv6 address) that all forward to `webrtc_listen_addr`
+    pub public_webrtc_candidates: Vec<SocketAddr>,
+    /// IP Address to bind to for plain UDP sessions with native clients,
+    /// used instead of the WebRTC addresses with `Transport::Udp`
+    pub udp_listen_addr: Option<SocketAddr>,
+    /// IP Address to serve Prometheus metrics on at `GET /metrics`, instead
+    /// of on the session server. This should usually be an internal-only
+    /// address
+    pub metrics_listen_addr: Option<SocketAddr>,
+    /// Counters kept by the Socket listening on these addresses
+    pub metrics: SocketMetrics,
+    /// Stops the Socket listening on these addresses
+    pub shutdown: SocketShutdown,
+    /// Certificate to serve the session endpoint over HTTPS with, or None to
+    /// serve it over plain HTTP
+    #[cfg(feature = "tls")]
+    pub session_tls: Option<SessionTlsConfig>,
+    /// Where to accept WebTransport sessions from browser clients, alongside
+    /// WebRTC sessions, or None to only accept WebRTC sessions
+    #[cfg(feature = "webtransport")]
+    pub webtransport: Option<WebTransportConfig>,
+    /// Extra `GET` routes answered by the session server
+    pub(crate) routes: Vec<(String, RouteHandler)>,
 }
 
 impl ServerAddrs {
@@ -23,8 +59,63 @@ impl ServerAddrs {
             session_listen_addr,
             webrtc_listen_addr,
             public_webrtc_url: public_webrtc_url.to_string(),
+            public_webrtc_candidates: Vec::new(),
+            udp_listen_addr: None,
+            metrics_listen_addr: None,
+            metrics: SocketMetrics::new(),
+            shutdown: SocketShutdown::new(),
+            #[cfg(feature = "tls")]
+            session_tls: None,
+            #[cfg(feature = "webtransport")]
+            webtransport: None,
+            routes: Vec::new(),
         }
     }
+
+    /// Advertise an additional public address as an ICE candidate
+    pub fn add_public_candidate(&mut self, public_candidate_addr: SocketAddr) {
+        self.public_webrtc_candidates.push(public_candidate_addr);
+    }
+
+    /// Accept plain UDP sessions from native clients on the given address,
+    /// for use with `Transport::Udp`
+    pub fn enable_udp(&mut self, udp_listen_addr: SocketAddr) {
+        self.udp_listen_addr = Some(udp_listen_addr);
+    }
+
+    /// Answer `GET <path>` on the session server with the plain text returned
+    /// by the handler, i.e. to report the server's status. The session server
+    /// already answers `GET /health` with `ok`
+    pub fn add_route(&mut self, path: &str, handler: impl Fn() -> String + Send + Sync + 'static) {
+        self.routes.push((path.to_string(), Arc::new(handler)));
+    }
+
+    /// Serve Prometheus metrics on the given address, rather than on the
+    /// session server, returning a handle to the counters which can also be
+    /// used to publish application gauges
+    pub fn enable_metrics(&mut self, metrics_listen_addr: SocketAddr) -> SocketMetrics {
+        self.metrics_listen_addr = Some(metrics_listen_addr);
+        self.metrics.clone()
+    }
+
+    /// A handle which shuts down the Socket listening on these addresses. Take
+    /// it before passing these addresses to `Socket::listen()`
+    pub fn shutdown_handle(&self) -> SocketShutdown {
+        self.shutdown.clone()
+    }
+
+    /// Serve the session endpoint over HTTPS with the given certificate
+    #[cfg(feature = "tls")]
+    pub fn enable_tls(&mut self, session_tls: SessionTlsConfig) {
+        self.session_tls = Some(session_tls);
+    }
+
+    /// Accept WebTransport sessions from browser clients, alongside WebRTC
+    /// sessions
+    #[cfg(feature = "webtransport")]
+    pub fn enable_webtransport(&mut self, webtransport: WebTransportConfig) {
+        self.webtransport = Some(webtransport);
+    }
 }
 
 impl Default for ServerAddrs {
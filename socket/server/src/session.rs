@@ -1,29 +1,52 @@
 use std::{
     collections::HashMap,
-    net::{SocketAddr, TcpListener, TcpStream},
-    pin::Pin,
-    task::{Context, Poll},
+    net::{SocketAddr, TcpListener},
 };
 
 use async_dup::Arc;
 use futures_core::Stream;
-use http::{header, HeaderValue, Response};
+use http::{header, HeaderValue, Method, Response, StatusCode};
 use log::{info, warn};
-use once_cell::sync::OnceCell;
 use smol::{
-    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines},
+    io::{AsyncRead, AsyncWrite},
     lock::Mutex,
-    stream::StreamExt,
     Async,
 };
 use webrtc_unreliable::SessionEndpoint;
 
-use naia_socket_shared::{IdentityToken, SocketConfig};
+use naia_socket_shared::{AllowedOrigin, IdentityToken, RejectReason, SocketConfig};
 
-use crate::{executor, server_addrs::ServerAddrs, NaiaServerSocketError};
+use crate::{
+    executor,
+    http_server::{self, close_after, empty_response, HttpRequest, Router, TEXT_CONTENT_TYPE},
+    metrics::{route_metrics, MetricsEndpoint, SocketMetrics},
+    server_addrs::ServerAddrs,
+    shutdown::SocketShutdown,
+    NaiaServerSocketError,
+};
+
+/// Path answered with `ok` for as long as the session server is running, for
+/// load balancer health checks
+const HEALTH_PATH: &str = "/health";
+
+/// How a request to the session server was answered
+pub(crate) enum SessionOutcome {
+    /// A CORS preflight for the session endpoint
+    Preflight,
+    /// A WebRTC session was set up
+    Accepted,
+    /// The server app rejected the request's auth
+    Rejected,
+    /// The request was malformed, or could not be answered
+    Failed,
+}
 
-static RTC_URL_POST_PATH: OnceCell<String> = OnceCell::new();
-static RTC_URL_OPTIONS_PATH: OnceCell<String> = OnceCell::new();
+/// Stops a session server from accepting new connections once dropped,
+/// releasing its listener. Requests already being served are allowed to
+/// finish
+pub struct SessionServerShutdown {
+    _sender: smol::channel::Sender<()>,
+}
 
 pub fn start_session_server(
     server_addrs: ServerAddrs,
@@ -32,27 +55,28 @@ pub fn start_session_server(
     from_client_auth_sender: Option<
         smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
     >,
-    to_session_all_auth_receiver: Option<
-        smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
-    >,
-) {
-    RTC_URL_POST_PATH
-        .set(format!("POST /{}", config.rtc_endpoint_path))
-        .expect("unable to set the URL Path");
-    RTC_URL_OPTIONS_PATH
-        .set(format!("OPTIONS /{}", config.rtc_endpoint_path))
-        .expect("unable to set the URL Path");
+    auth_mux_sender: Option<AuthMuxSender>,
+) -> SessionServerShutdown {
+    let (shutdown_sender, shutdown_receiver) = smol::channel::bounded(1);
+    let task_guard = server_addrs.shutdown.task_guard();
+
     executor::spawn(async move {
+        let _task_guard = task_guard;
         listen(
             server_addrs,
             config,
             session_endpoint.clone(),
             from_client_auth_sender,
-            to_session_all_auth_receiver,
+            auth_mux_sender,
+            shutdown_receiver,
         )
         .await;
     })
     .detach();
+
+    SessionServerShutdown {
+        _sender: shutdown_sender,
+    }
 }
 
 /// Listens for incoming connections and serves them.
@@ -63,16 +87,24 @@ async fn listen(
     from_client_auth_sender: Option<
         smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
     >,
-    to_session_all_auth_receiver: Option<
-        smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
-    >,
+    auth_mux_sender: Option<AuthMuxSender>,
+    shutdown_receiver: smol::channel::Receiver<()>,
 ) {
     let socket_address = server_addrs.session_listen_addr;
 
     let listener = Async::<TcpListener>::bind(socket_address)
         .expect("unable to bind a TCP Listener to the supplied socket address");
+    #[cfg(feature = "tls")]
+    let scheme = if server_addrs.session_tls.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    #[cfg(not(feature = "tls"))]
+    let scheme = "http";
     info!(
-        "Session initiator available at POST http://{}/{}",
+        "Session initiator available at POST {}://{}/{}",
+        scheme,
         listener
             .get_ref()
             .local_addr()
@@ -80,49 +112,97 @@ async fn listen(
         config.rtc_endpoint_path
     );
 
-    let mut auth_mux_sender_opt =
-        if let Some(to_session_all_auth_receiver) = to_session_all_auth_receiver {
-            Some(setup_auth_mux(to_session_all_auth_receiver).await)
-        } else {
-            None
-        };
-
-    loop {
-        // Accept the next connection.
-        let (response_stream, remote_addr) = listener
-            .accept()
-            .await
-            .expect("was not able to accept the incoming stream from the listener");
+    let mut router = Router::new();
+    router.route(
+        HEALTH_PATH,
+        TEXT_CONTENT_TYPE,
+        std::sync::Arc::new(|| "ok".to_string()),
+    );
+    if MetricsEndpoint::new(&server_addrs, &config) == MetricsEndpoint::SessionServer {
+        route_metrics(&mut router, server_addrs.metrics.clone());
+    }
+    for (path, handler) in &server_addrs.routes {
+        router.route(path, TEXT_CONTENT_TYPE, handler.clone());
+    }
 
-        let session_endpoint_clone = session_endpoint.clone();
+    let context = Arc::new(SessionContext {
+        session_endpoint,
+        public_candidates: public_candidates_json(&server_addrs.public_webrtc_candidates),
+        rtc_path: format!("/{}", config.rtc_endpoint_path),
+        validate_sdp_offers: config.validate_sdp_offers,
+        allowed_origin: config.allowed_origin.clone(),
+        router,
+        auth: from_client_auth_sender.zip(auth_mux_sender),
+    });
 
-        let (to_session_single_auth_sender, to_session_single_auth_receiver) =
-            if from_client_auth_sender.is_some() {
-                let (sender, receiver) = futures_channel::oneshot::channel();
-                (Some(sender), Some(receiver))
-            } else {
-                (None, None)
-            };
-        if let Some(to_session_single_auth_sender) = to_session_single_auth_sender {
-            let result = auth_mux_sender_opt
-                .as_mut()
-                .unwrap()
-                .send((remote_addr, to_session_single_auth_sender))
-                .await;
-            if result.is_err() {
-                warn!("Unable to send auth sender to auth mux");
+    loop {
+        // Accept the next connection, unless the server is shutting down.
+        let accepted = smol::future::or(async { Some(listener.accept().await) }, async {
+            // only ever closed, never sent to
+            let _ = smol::future::or(shutdown_receiver.recv(), async {
+                server_addrs.shutdown.requested().await;
+                Ok(())
+            })
+            .await;
+            None
+        })
+        .await;
+        let Some(accepted) = accepted else {
+            info!("Session initiator at {} shut down", socket_address);
+            break;
+        };
+        let (response_stream, remote_addr) = match accepted {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                warn!(
+                    "Unable to accept an incoming WebRTC session request: {}",
+                    err
+                );
                 continue;
             }
-        }
+        };
+
+        let context = context.clone();
+        let max_request_bytes = config.max_session_request_bytes;
+        let metrics = server_addrs.metrics.clone();
+        let task_guard = server_addrs.shutdown.task_guard();
+        #[cfg(feature = "tls")]
+        let session_tls = server_addrs.session_tls.clone();
 
-        let from_client_auth_sender = from_client_auth_sender.clone();
-        // Spawn a background task serving this connection.
+        // Spawn a background task serving this connection. Requests already
+        // being served are answered even once the Socket is shutting down
         executor::spawn(async move {
+            let _task_guard = task_guard;
+            #[cfg(feature = "tls")]
+            if let Some(session_tls) = session_tls {
+                match session_tls.acceptor().accept(response_stream).await {
+                    Ok(tls_stream) => {
+                        serve(
+                            context,
+                            metrics,
+                            max_request_bytes,
+                            Arc::new(async_dup::Mutex::new(tls_stream)),
+                            remote_addr,
+                        )
+                        .await
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Dropped WebRTC session request from {}. TLS error: {}",
+                            remote_addr, err
+                        );
+                        metrics.record_session(SessionOutcome::Failed);
+                    }
+                }
+                return;
+            }
+
             serve(
-                session_endpoint_clone,
+                context,
+                metrics,
+                max_request_bytes,
                 Arc::new(response_stream),
-                from_client_auth_sender,
-                to_session_single_auth_receiver,
+                remote_addr,
             )
             .await;
         })
@@ -130,50 +210,121 @@ async fn listen(
     }
 }
 
-async fn setup_auth_mux(
-    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
-) -> smol::channel::Sender<(
+/// Passes the auth bytes of each session request to the server app
+pub(crate) type ClientAuthSender =
+    smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>;
+
+/// Hands the answer for each auth request to whichever task is waiting on it,
+/// keyed by the address the request came from
+pub(crate) type AuthMuxSender = smol::channel::Sender<(
     SocketAddr,
-    futures_channel::oneshot::Sender<Option<IdentityToken>>,
-)> {
+    futures_channel::oneshot::Sender<Result<IdentityToken, RejectReason>>,
+)>;
+
+/// Routes the server app's auth answers back to the sessions waiting on them.
+/// Shared by every listener which accepts auth from clients
+pub(crate) fn setup_auth_mux(
+    to_session_all_auth_receiver: smol::channel::Receiver<(
+        SocketAddr,
+        Result<IdentityToken, RejectReason>,
+    )>,
+    shutdown: SocketShutdown,
+) -> AuthMuxSender {
     let (sender_sender, sender_receiver) = smol::channel::unbounded();
 
     let map_1 = Arc::new(Mutex::new(HashMap::new()));
     let map_2 = map_1.clone();
 
-    // Spawn a background task for muxing in
+    // Spawn a background task for muxing in. Once it stops, any session still
+    // waiting on an answer is dropped along with the map
+    let shutdown_1 = shutdown.clone();
+    let task_guard = shutdown.task_guard();
     executor::spawn(async move {
-        serve_auth_mux_in(map_1, to_session_all_auth_receiver).await;
+        let _task_guard = task_guard;
+        shutdown_1
+            .or_requested(serve_auth_mux_in(map_1, to_session_all_auth_receiver))
+            .await;
     })
     .detach();
 
     // Spawn a background task for muxing out
+    let task_guard = shutdown.task_guard();
     executor::spawn(async move {
-        serve_auth_mux_out(map_2, sender_receiver).await;
+        let _task_guard = task_guard;
+        shutdown
+            .or_requested(serve_auth_mux_out(map_2, sender_receiver))
+            .await;
     })
     .detach();
 
     sender_sender
 }
 
+/// Sends a client's auth to the server app and waits for its answer. Shared
+/// by listeners which, unlike the session server, have no HTTP status to
+/// report failures with, so any failure is a rejection
+pub(crate) async fn ask_server_app(
+    remote_addr: SocketAddr,
+    auth_bytes: Box<[u8]>,
+    from_client_auth_sender: Option<
+        &smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+    >,
+    auth_mux_sender: Option<&AuthMuxSender>,
+) -> Result<IdentityToken, RejectReason> {
+    let (Some(from_client_auth_sender), Some(auth_mux_sender)) =
+        (from_client_auth_sender, auth_mux_sender)
+    else {
+        warn!(
+            "Invalid session request from {}. Error: missing auth sender",
+            remote_addr
+        );
+        return Err(RejectReason::Unauthorized);
+    };
+
+    let (to_session_auth_sender, to_session_auth_receiver) = futures_channel::oneshot::channel();
+    if auth_mux_sender
+        .send((remote_addr, to_session_auth_sender))
+        .await
+        .is_err()
+    {
+        warn!("Unable to send auth sender to auth mux");
+        return Err(RejectReason::Unauthorized);
+    }
+    if from_client_auth_sender
+        .send(Ok((remote_addr, auth_bytes)))
+        .await
+        .is_err()
+    {
+        warn!("Unable to send auth string to server app");
+        return Err(RejectReason::Unauthorized);
+    }
+
+    to_session_auth_receiver
+        .await
+        .unwrap_or(Err(RejectReason::Unauthorized))
+}
+
 async fn serve_auth_mux_in(
     map: Arc<
         Mutex<
             HashMap<
                 SocketAddr,
                 (
-                    Option<futures_channel::oneshot::Sender<Option<IdentityToken>>>,
-                    Option<Option<IdentityToken>>,
+                    Option<futures_channel::oneshot::Sender<Result<IdentityToken, RejectReason>>>,
+                    Option<Result<IdentityToken, RejectReason>>,
                 ),
             >,
         >,
     >,
-    to_session_all_auth_receiver: smol::channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+    to_session_all_auth_receiver: smol::channel::Receiver<(
+        SocketAddr,
+        Result<IdentityToken, RejectReason>,
+    )>,
 ) {
     loop {
         let Ok((addr, answer)) = to_session_all_auth_receiver.recv().await else {
-            warn!("Unable to receive auth from session");
-            continue;
+            // every AuthSender was dropped
+            break;
         };
 
         // info!("received auth answer from app, for addr: {}, answer: {:?}", addr, answer);
@@ -200,21 +351,21 @@ async fn serve_auth_mux_out(
             HashMap<
                 SocketAddr,
                 (
-                    Option<futures_channel::oneshot::Sender<Option<IdentityToken>>>,
-                    Option<Option<IdentityToken>>,
+                    Option<futures_channel::oneshot::Sender<Result<IdentityToken, RejectReason>>>,
+                    Option<Result<IdentityToken, RejectReason>>,
                 ),
             >,
         >,
     >,
     sender_receiver: smol::channel::Receiver<(
         SocketAddr,
-        futures_channel::oneshot::Sender<Option<IdentityToken>>,
+        futures_channel::oneshot::Sender<Result<IdentityToken, RejectReason>>,
     )>,
 ) {
     loop {
         let Ok((addr, sender)) = sender_receiver.recv().await else {
-            warn!("Unable to receive auth sender from session");
-            continue;
+            // every listener accepting auth has stopped
+            break;
         };
 
         // info!("received auth answer sender, for addr: {}", addr);
@@ -237,362 +388,753 @@ async fn serve_auth_mux_out(
     }
 }
 
-/// Reads a request from the client and sends it a response.
-async fn serve(
-    mut session_endpoint: SessionEndpoint,
-    mut stream: Arc<Async<TcpStream>>,
-    from_client_auth_sender: Option<
+/// Everything a session server needs to answer a request, shared by all of
+/// its connections
+#[allow(clippy::type_complexity)]
+struct SessionContext {
+    session_endpoint: SessionEndpoint,
+    public_candidates: String,
+    rtc_path: String,
+    validate_sdp_offers: bool,
+    allowed_origin: AllowedOrigin,
+    router: Router,
+    auth: Option<(
         smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
-    >,
-    to_session_single_auth_receiver: Option<
-        futures_channel::oneshot::Receiver<Option<IdentityToken>>,
-    >,
+        AuthMuxSender,
+    )>,
+}
+
+/// Answers each request the client sends until the connection is closed. A
+/// request which can't be read only drops this connection
+async fn serve<S: AsyncRead + AsyncWrite + Clone + Unpin>(
+    context: Arc<SessionContext>,
+    metrics: SocketMetrics,
+    max_request_bytes: usize,
+    stream: S,
+    remote_addr: SocketAddr,
 ) {
-    let remote_addr = stream
-        .get_ref()
-        .peer_addr()
-        .expect("stream does not have a local address");
-
-    info!("Incoming WebRTC session request from {}", remote_addr);
-
-    let mut success: bool = false;
-    let mut headers_been_read: bool = false;
-    let mut content_length: Option<usize> = None;
-    let mut auth_string: Option<String> = None;
-    let mut rtc_url_matched = false;
-    let mut is_options: bool = false;
-    let mut body: Vec<u8> = Vec::new();
-    let mut identity_token_opt = None;
-
-    let buf_reader = BufReader::new(stream.clone());
-    let mut bytes = buf_reader.bytes();
-    {
-        let mut line: Vec<u8> = Vec::new();
-        while let Some(byte) = bytes.next().await {
-            let byte = byte.expect("unable to read a byte from incoming stream");
+    let result = http_server::serve_connection(stream, max_request_bytes, |request| {
+        let context = context.clone();
+        let metrics = metrics.clone();
+        async move {
+            let (outcome, response) = context.answer(request, remote_addr).await;
+            if let Some(outcome) = outcome {
+                metrics.record_session(outcome);
+            }
+            response
+        }
+    })
+    .await;
+
+    if let Err(err) = result {
+        warn!(
+            "Dropped WebRTC session request from {}. Error: {}",
+            remote_addr, err
+        );
+        if err.status().is_some() {
+            metrics.record_session(SessionOutcome::Failed);
+        }
+    }
+}
 
-            if headers_been_read {
-                if let Some(content_length) = content_length {
-                    body.push(byte);
+impl SessionContext {
+    /// Answers a request, returning how it was answered if it was for the
+    /// session endpoint
+    async fn answer(
+        &self,
+        request: HttpRequest,
+        remote_addr: SocketAddr,
+    ) -> (Option<SessionOutcome>, Response<Vec<u8>>) {
+        let origin = request.header("origin").map(str::to_string);
+
+        let (outcome, mut response) = if request.path != self.rtc_path {
+            let response = self
+                .router
+                .handle(&request)
+                .unwrap_or_else(|| empty_response(StatusCode::NOT_FOUND));
+            (None, response)
+        } else if request.method == Method::OPTIONS {
+            (Some(SessionOutcome::Preflight), preflight_response())
+        } else if request.method == Method::POST {
+            let (outcome, response) = self.answer_session(request, remote_addr).await;
+            (Some(outcome), response)
+        } else {
+            let mut response = empty_response(StatusCode::METHOD_NOT_ALLOWED);
+            response
+                .headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static("POST, OPTIONS"));
+            (None, response)
+        };
 
-                    if body.len() >= content_length {
-                        // info!("read body finished");
-                        success = true;
-                        break;
-                    }
-                } else {
-                    info!("request was missing Content-Length header");
-                    break;
-                }
-            }
+        insert_allow_origin(
+            response.headers_mut(),
+            &self.allowed_origin,
+            origin.as_deref(),
+        );
+        (outcome, response)
+    }
 
-            if byte == b'\r' {
-                continue;
-            } else if byte == b'\n' {
-                let mut str = String::from_utf8(line.clone())
-                    .expect("unable to parse string from UTF-8 bytes");
-                line.clear();
-
-                if rtc_url_matched {
-                    if str.to_lowercase().starts_with("content-length: ") {
-                        let (_, last) = str.split_at(16);
-                        str = last.to_string();
-                        content_length = str.parse::<usize>().ok();
-                        // info!("read content length header: {:?}", content_length);
-                    } else if str.to_lowercase().starts_with("authorization: ") {
-                        let (_, last) = str.split_at(15);
-                        auth_string = Some(last.to_string());
-                        // info!("read authorization header: {:?}", auth_string);
-                    } else if str.is_empty() {
-                        // info!("read headers finished");
-                        headers_been_read = true;
-
-                        if is_options {
-                            success = true;
-                            break;
-                        }
-                    } else {
-                        // info!("read leftover line 1: {}", str);
-                    }
-                } else if str.starts_with(
-                    RTC_URL_POST_PATH
-                        .get()
-                        .expect("unable to retrieve URL path, was it not configured?"),
-                ) {
-                    // info!("starting to match to RTC URL");
-                    rtc_url_matched = true;
-                } else if str.starts_with(
-                    RTC_URL_OPTIONS_PATH
-                        .get()
-                        .expect("unable to retrieve URL path, was it not configured?"),
-                ) {
-                    // info!("matched OPTIONS request for RTC URL");
-                    rtc_url_matched = true;
-                    is_options = true;
-                } else {
-                    // info!("read leftover line 2: {}", str);
-                }
-            } else {
-                line.push(byte);
+    /// Checks the auth of a WebRTC session request with the server app, then
+    /// answers its SDP offer
+    async fn answer_session(
+        &self,
+        request: HttpRequest,
+        remote_addr: SocketAddr,
+    ) -> (SessionOutcome, Response<Vec<u8>>) {
+        info!("Incoming WebRTC session request from {}", remote_addr);
+
+        // check the body looks like an SDP offer
+        if self.validate_sdp_offers {
+            if let Some(reason) = sdp_offer_rejection(&request.body) {
+                warn!(
+                    "Invalid WebRTC session request from {}. Error: body is not an SDP offer, {}",
+                    remote_addr, reason
+                );
+                return failed(StatusCode::BAD_REQUEST);
             }
         }
 
-        // handle OPTIONS request
-        if success && is_options {
-            let mut resp = Response::<String>::new("".to_string());
-            resp.headers_mut().insert(
-                header::ACCESS_CONTROL_ALLOW_ORIGIN,
-                HeaderValue::from_static("*"),
-            );
-            resp.headers_mut().insert(
-                header::ACCESS_CONTROL_ALLOW_METHODS,
-                HeaderValue::from_static("POST"),
+        // handle auth
+        let Some((from_client_auth_sender, auth_mux_sender)) = &self.auth else {
+            warn!(
+                "Invalid WebRTC session request from {}. Error: missing auth sender",
+                remote_addr
             );
-            resp.headers_mut().insert(
-                header::ACCESS_CONTROL_ALLOW_HEADERS,
-                HeaderValue::from_static("Authorization, Content-Length"),
+            return rejected(remote_addr, RejectReason::Unauthorized);
+        };
+        let Some(auth_string) = request.header("authorization") else {
+            warn!(
+                "Invalid WebRTC session request from {}. Error: missing auth string",
+                remote_addr
             );
-            resp.headers_mut().insert(
-                header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
-                HeaderValue::from_static("true"),
+            return failed(StatusCode::BAD_REQUEST);
+        };
+        let Ok(auth_bytes) = base64::decode(auth_string) else {
+            warn!(
+                "Invalid WebRTC session request from {}. Error: unable to decode auth string",
+                remote_addr
             );
+            return failed(StatusCode::BAD_REQUEST);
+        };
 
-            let mut out = response_header_to_vec(&resp);
-            out.extend_from_slice(resp.body().as_bytes());
+        let (to_session_auth_sender, to_session_auth_receiver) =
+            futures_channel::oneshot::channel();
+        if auth_mux_sender
+            .send((remote_addr, to_session_auth_sender))
+            .await
+            .is_err()
+        {
+            warn!("Unable to send auth sender to auth mux");
+            return failed(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        if from_client_auth_sender
+            .send(Ok((remote_addr, auth_bytes.into())))
+            .await
+            .is_err()
+        {
+            warn!("Unable to send auth string to server app");
+            return failed(StatusCode::INTERNAL_SERVER_ERROR);
+        }
 
-            // info!("OPTIONS request from {}", remote_addr);
+        // wait for response from app
+        let identity_token = match to_session_auth_receiver.await {
+            Ok(Ok(identity_token)) => identity_token,
+            Ok(Err(reason)) => return rejected(remote_addr, reason),
+            Err(_) => {
+                warn!("Server app never answered auth from {}", remote_addr);
+                return failed(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
 
-            stream
-                .write_all(&out)
-                .await
-                .expect("found an error while writing to a stream");
-        }
+        // init session
+        let body_stream = request_body_stream(request.body);
+        match self
+            .session_endpoint
+            .clone()
+            .http_session_request(body_stream)
+            .await
+        {
+            Ok(resp) => {
+                let (_head, body) = resp.into_parts();
+                let public_candidates = &self.public_candidates;
+                let body = format!(
+                    "{{\
+                    \"sdp\":{body},\
+                    \"id\":\"{identity_token}\",\
+                    \"candidates\":{public_candidates}\
+                    }}",
+                );
 
-        // handle auth
-        if success && !is_options {
-            if let Some(from_client_auth_sender) = from_client_auth_sender {
-                success = false;
-
-                let to_session_auth_receiver = to_session_single_auth_receiver.unwrap();
-
-                // check auth
-                if let Some(auth_string) = auth_string {
-                    match base64::decode(&auth_string) {
-                        Ok(decoded_bytes) => {
-                            if from_client_auth_sender
-                                .send(Ok((remote_addr, decoded_bytes.into())))
-                                .await
-                                .is_err()
-                            {
-                                warn!("Unable to send auth string to server app");
-                            } else {
-                                // info!("Sent auth bytes to server app");
-
-                                // wait for response from app
-                                if let Ok(identity_token_inner_opt) = to_session_auth_receiver.await
-                                {
-                                    if let Some(identity_token) = identity_token_inner_opt {
-                                        // info!("Server app accepted auth with identity token: {}", identity_token);
-                                        identity_token_opt = Some(identity_token);
-                                        success = true;
-                                    } else {
-                                        // warn!("Server app rejected auth");
-                                        identity_token_opt = None;
-                                        success = true;
-                                    }
-                                }
-                            }
-                        }
-                        Err(_) => {
-                            warn!("Invalid WebRTC session request from {}. Error: unable to decode auth string", remote_addr);
-                        }
-                    }
-                } else {
-                    warn!(
-                        "Invalid WebRTC session request from {}. Error: missing auth string",
-                        remote_addr
-                    );
-                }
-            } else {
+                let mut response = Response::new(body.into_bytes());
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                );
+
+                info!("Successful WebRTC session request from {}", remote_addr);
+                (SessionOutcome::Accepted, response)
+            }
+            Err(err) => {
                 warn!(
-                    "Invalid WebRTC session request from {}. Error: missing auth sender",
-                    remote_addr
+                    "Invalid WebRTC session request from {}. Error: {}",
+                    remote_addr, err
                 );
+                failed(StatusCode::BAD_REQUEST)
             }
         }
+    }
+}
 
-        // read body and init session
-        if success && !is_options {
-            success = false;
+fn preflight_response() -> Response<Vec<u8>> {
+    let mut response = empty_response(StatusCode::OK);
+    let headers = response.headers_mut();
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("POST"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("Authorization, Content-Length"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+        HeaderValue::from_static("true"),
+    );
+    response
+}
 
-            // info!("reading identity token");
+// A session request which could not be answered, after which the connection
+// is closed
+fn failed(status: StatusCode) -> (SessionOutcome, Response<Vec<u8>>) {
+    let mut response = empty_response(status);
+    close_after(&mut response);
+    (SessionOutcome::Failed, response)
+}
 
-            if let Some(identity_token) = identity_token_opt.take() {
-                // info!("identity token: {:?}", identity_token);
+// A session request whose auth the server app rejected, after which the
+// connection is closed
+fn rejected(remote_addr: SocketAddr, reason: RejectReason) -> (SessionOutcome, Response<Vec<u8>>) {
+    info!(
+        "Rejected WebRTC session request from {}. Reason: {:?}",
+        remote_addr, reason
+    );
+    let mut response = Response::new(reason.to_body().into_bytes());
+    *response.status_mut() =
+        StatusCode::from_u16(reason.status_code()).unwrap_or(StatusCode::UNAUTHORIZED);
+    close_after(&mut response);
+    (SessionOutcome::Rejected, response)
+}
 
-                let mut lines = body.lines();
-                let buf = RequestBuffer::new(&mut lines);
+// Serializes the additional public addresses of the server into a JSON array
+// of `RTCIceCandidateInit` dictionaries, sent alongside the session answer.
+// These are given low priorities so the primary candidate is preferred
+fn public_candidates_json(public_candidates: &[SocketAddr]) -> String {
+    let count = public_candidates.len();
+    let candidates: Vec<String> = public_candidates
+        .iter()
+        .enumerate()
+        .map(|(index, address)| {
+            format!(
+                "{{\
+                \"candidate\":\"candidate:{foundation} 1 UDP {priority} {ip} {port} typ host\",\
+                \"sdpMLineIndex\":0\
+                }}",
+                foundation = index + 2,
+                priority = count - index,
+                ip = address.ip(),
+                port = address.port(),
+            )
+        })
+        .collect();
+    format!("[{}]", candidates.join(","))
+}
+
+// Lightweight check that a request body is shaped like an SDP offer for a
+// data channel, returning the reason it isn't. This is not a full SDP parser
+fn sdp_offer_rejection(body: &[u8]) -> Option<&'static str> {
+    let Ok(body) = std::str::from_utf8(body) else {
+        return Some("it is not valid UTF-8");
+    };
+    if !body.starts_with("v=") {
+        return Some("it does not start with a version line");
+    }
+    if !body.lines().any(|line| line.starts_with("m=application")) {
+        return Some("it has no application media section");
+    }
+    None
+}
 
-                match session_endpoint.http_session_request(buf).await {
-                    Ok(resp) => {
-                        // info!("Successful WebRTC session request");
+// Sets the Access-Control-Allow-Origin header for a request from the given
+// origin, if that origin is allowed
+fn insert_allow_origin(
+    headers: &mut http::HeaderMap,
+    allowed_origin: &AllowedOrigin,
+    request_origin: Option<&str>,
+) {
+    if let Some(value) = allowed_origin.header_value(request_origin) {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+        }
+    }
+    if let AllowedOrigin::Exact(_) = allowed_origin {
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+    }
+}
 
-                        success = true;
+type ReqError = std::io::Error; //Box<dyn error::Error + Send + Sync>;
 
-                        let (_head, body) = resp.into_parts();
+// Passes the request body to the session endpoint verbatim, preserving its
+// original line endings. The SDP parser only reads a line once it sees a line
+// terminator, so a body missing one on its final line gets a single `\n`
+// appended rather than having that line dropped
+fn request_body_stream(body: Vec<u8>) -> impl Stream<Item = Result<Vec<u8>, ReqError>> {
+    smol::stream::iter(request_body_chunks(body).into_iter().map(Ok))
+}
 
-                        let body = format!(
-                            "{{\
-                        \"sdp\":{body},\
-                        \"id\":\"{identity_token}\"\
-                        }}",
-                        );
+fn request_body_chunks(body: Vec<u8>) -> Vec<Vec<u8>> {
+    let terminated = matches!(body.last(), None | Some(b'\n') | Some(b'\r'));
+    let mut chunks = vec![body];
+    if !terminated {
+        chunks.push(b"\n".to_vec());
+    }
+    chunks
+}
 
-                        let response = Response::builder()
-                            .header(header::CONTENT_TYPE, "application/json")
-                            .header(
-                                header::ACCESS_CONTROL_ALLOW_ORIGIN,
-                                HeaderValue::from_static("*"),
-                            )
-                            .body(body)
-                            .expect("could not combine sdp response with id token");
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    use naia_socket_shared::{AllowedOrigin, IdentityToken, RejectReason, SocketConfig};
+
+    use super::{
+        public_candidates_json, request_body_chunks, sdp_offer_rejection, setup_auth_mux,
+        start_session_server, SessionServerShutdown,
+    };
+    use crate::{
+        auth_sender::{AuthSender, AuthSenderImpl},
+        server_addrs::ServerAddrs,
+        shutdown::SocketShutdown,
+        NaiaServerSocketError,
+    };
+
+    // Starts a session server on a free port, returning its address along with
+    // the WebRTC server and shutdown handle which must be kept alive while it
+    // runs
+    fn start_test_server(
+        config: SocketConfig,
+    ) -> (SocketAddr, webrtc_unreliable::Server, SessionServerShutdown) {
+        start_test_server_with_auth(config, None, None)
+    }
 
-                        let mut out = response_header_to_vec(&response);
-                        out.extend_from_slice(response.body().as_bytes());
+    fn start_test_server_with_auth(
+        config: SocketConfig,
+        from_client_auth_sender: Option<
+            smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        >,
+        to_session_all_auth_receiver: Option<
+            smol::channel::Receiver<(SocketAddr, Result<IdentityToken, RejectReason>)>,
+        >,
+    ) -> (SocketAddr, webrtc_unreliable::Server, SessionServerShutdown) {
+        let session_listen_addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let rtc_server = smol::block_on(webrtc_unreliable::Server::new(
+            "127.0.0.1:0".parse().unwrap(),
+            "127.0.0.1:0".parse().unwrap(),
+        ))
+        .unwrap();
+
+        let mut server_addrs = ServerAddrs::default();
+        server_addrs.session_listen_addr = session_listen_addr;
+        let shutdown = start_session_server(
+            server_addrs,
+            config,
+            rtc_server.session_endpoint(),
+            from_client_auth_sender,
+            to_session_all_auth_receiver.map(|to_session_all_auth_receiver| {
+                setup_auth_mux(to_session_all_auth_receiver, SocketShutdown::new())
+            }),
+        );
 
-                        info!("Successful WebRTC session request from {}", remote_addr);
+        (session_listen_addr, rtc_server, shutdown)
+    }
 
-                        stream
-                            .write_all(&out)
-                            .await
-                            .expect("found an error while writing to a stream");
-                    }
-                    Err(err) => {
-                        warn!(
-                            "Invalid WebRTC session request from {}. Error: {}",
-                            remote_addr, err
-                        );
-                    }
-                }
-            } else {
-                // Server rejected auth!
-                let response = Response::builder()
-                    .status(401)
-                    .body("".to_string())
-                    .expect("could not build 401 response");
+    // Sends the bytes, closing the write half only if `finish` is set, and
+    // returns the whole response
+    fn request(address: SocketAddr, bytes: &[u8], finish: bool) -> String {
+        // the session server starts listening in the background, so retry
+        let mut stream = (0..100)
+            .find_map(|_| {
+                TcpStream::connect(address).ok().or_else(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    None
+                })
+            })
+            .expect("session server should accept connections");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream.write_all(bytes).unwrap();
+        if finish {
+            stream.shutdown(Shutdown::Write).unwrap();
+        }
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .expect("session server should answer and close the connection");
+        String::from_utf8_lossy(&response).into_owned()
+    }
 
-                let mut out = response_header_to_vec(&response);
-                out.extend_from_slice(response.body().as_bytes());
+    #[test]
+    fn session_server_survives_garbage_requests() {
+        let (address, _rtc_server, _shutdown) = start_test_server(SocketConfig::default());
 
-                info!("Rejected WebRTC session request from {}", remote_addr);
+        let response = request(address, &[0xff, 0xfe, 0xfd, b'\r', b'\n'], true);
+        assert!(response.starts_with("HTTP/1.1 400"));
+        let response = request(address, b"POST /rtc_session HTTP/1.1\r\nContent-", true);
+        assert!(response.starts_with("HTTP/1.1 400"));
+        let response = request(address, b"GET / HTTP/1.1\r\n\r\n", true);
+        assert!(response.contains("404"));
+    }
 
-                stream
-                    .write_all(&out)
-                    .await
-                    .expect("found an error while writing to a stream");
+    fn preflight(address: SocketAddr, origin: &str) -> String {
+        let bytes = format!(
+            "OPTIONS /rtc_session HTTP/1.1\r\nOrigin: {}\r\nConnection: close\r\n\r\n",
+            origin
+        );
+        request(address, bytes.as_bytes(), false).to_lowercase()
+    }
+
+    #[test]
+    fn session_servers_answer_only_their_own_path() {
+        let path_config = |path: &str| SocketConfig {
+            rtc_endpoint_path: path.to_string(),
+            ..SocketConfig::default()
+        };
+        let (first, _first_rtc_server, _first_shutdown) = start_test_server(path_config("first"));
+        let (second, _second_rtc_server, _second_shutdown) =
+            start_test_server(path_config("second"));
+
+        let preflight = |address, path: &str| {
+            let bytes = format!("OPTIONS /{} HTTP/1.1\r\nConnection: close\r\n\r\n", path);
+            request(address, bytes.as_bytes(), false)
+        };
+        assert!(preflight(first, "first").starts_with("HTTP/1.1 200"));
+        assert!(preflight(first, "second").starts_with("HTTP/1.1 404"));
+        assert!(preflight(second, "second").starts_with("HTTP/1.1 200"));
+        assert!(preflight(second, "first").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn session_server_allows_any_origin_by_default() {
+        let (address, _rtc_server, _shutdown) = start_test_server(SocketConfig::default());
+
+        let response = preflight(address, "https://example.com");
+        assert!(response.contains("access-control-allow-origin: *\r\n"));
+        let response = request(address, b"GET / HTTP/1.1\r\n\r\n", true);
+        assert!(response.contains("access-control-allow-origin: *\r\n"));
+    }
+
+    #[test]
+    fn session_server_allows_exact_origin() {
+        let config = SocketConfig {
+            allowed_origin: AllowedOrigin::Exact("https://example.com".to_string()),
+            ..SocketConfig::default()
+        };
+        let (address, _rtc_server, _shutdown) = start_test_server(config);
+
+        let response = preflight(address, "https://example.com");
+        assert!(response.contains("access-control-allow-origin: https://example.com\r\n"));
+        let response = preflight(address, "https://other.com");
+        assert!(!response.contains("access-control-allow-origin"));
+        let response = request(address, b"GET / HTTP/1.1\r\n\r\n", true);
+        assert!(!response.contains("access-control-allow-origin"));
+    }
+
+    #[test]
+    fn session_server_sends_reject_reason() {
+        let (from_client_auth_sender, from_client_auth_receiver) = smol::channel::unbounded();
+        let (to_session_all_auth_sender, to_session_all_auth_receiver) = smol::channel::unbounded();
+        let (address, _rtc_server, _shutdown) = start_test_server_with_auth(
+            SocketConfig::default(),
+            Some(from_client_auth_sender),
+            Some(to_session_all_auth_receiver),
+        );
+
+        // the server app bans whoever asks
+        let auth_sender = AuthSenderImpl::new(to_session_all_auth_sender);
+        thread::spawn(move || {
+            if let Ok(Ok((client_addr, _auth_bytes))) =
+                smol::block_on(from_client_auth_receiver.recv())
+            {
+                let _ = auth_sender.reject(&client_addr, &RejectReason::Banned);
             }
-        }
+        });
+
+        let offer = "v=0\r\nm=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n";
+        let bytes = format!(
+            "POST /rtc_session HTTP/1.1\r\nAuthorization: {}\r\nContent-Length: {}\r\n\r\n{}",
+            base64::encode("player"),
+            offer.len(),
+            offer
+        );
+        let response = request(address, bytes.as_bytes(), false);
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status_code = head[9..12].parse::<u16>().unwrap();
+
+        assert_eq!(
+            RejectReason::from_response(status_code, Some(body)),
+            Some(RejectReason::Banned)
+        );
+        assert_eq!(
+            RejectReason::from_response(status_code, None),
+            Some(RejectReason::Banned)
+        );
     }
 
-    // info!("Closing WebRTC session request from {}", remote_addr);
+    #[test]
+    fn session_server_serves_metrics_only_when_enabled() {
+        let (address, _rtc_server, _shutdown) = start_test_server(SocketConfig::default());
+        let response = request(address, b"GET /metrics HTTP/1.1\r\n\r\n", true);
+        assert!(response.contains("404"));
 
-    if !success {
-        stream.write_all(RESPONSE_BAD).await.expect("found");
+        let config = SocketConfig {
+            metrics_enabled: true,
+            ..SocketConfig::default()
+        };
+        let (address, _rtc_server, _shutdown) = start_test_server(config);
+        let response = request(address, b"GET /metrics HTTP/1.1\r\n\r\n", true);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("naia_session_requests_total 0\n"));
+        assert!(response.contains("naia_active_connections 0\n"));
     }
 
-    stream.flush().await.expect("unable to flush the stream");
-    stream.close().await.expect("unable to close the stream");
-}
+    #[test]
+    fn session_server_releases_listener_on_shutdown() {
+        let (address, _rtc_server, shutdown) = start_test_server(SocketConfig::default());
 
-const RESPONSE_BAD: &[u8] = br#"
-HTTP/1.1 404 NOT FOUND
-Content-Type: text/html
-Content-Length: 0
-Access-Control-Allow-Origin: *
-"#;
+        let response = request(address, b"GET / HTTP/1.1\r\n\r\n", true);
+        assert!(response.contains("404"));
 
-struct RequestBuffer<'a, R: AsyncBufRead + Unpin> {
-    buffer: &'a mut Lines<R>,
-    add_newline: bool,
-}
+        drop(shutdown);
 
-impl<'a, R: AsyncBufRead + Unpin> RequestBuffer<'a, R> {
-    fn new(buf: &'a mut Lines<R>) -> Self {
-        RequestBuffer {
-            add_newline: false,
-            buffer: buf,
-        }
+        // the accept loop exits in the background, so retry
+        let rebound = (0..100).find_map(|_| {
+            TcpListener::bind(address).ok().or_else(|| {
+                thread::sleep(Duration::from_millis(10));
+                None
+            })
+        });
+        assert!(rebound.is_some());
     }
-}
 
-type ReqError = std::io::Error; //Box<dyn error::Error + Send + Sync>;
+    #[test]
+    fn session_server_rejects_oversized_content_length() {
+        let config = SocketConfig {
+            max_session_request_bytes: 64,
+            ..SocketConfig::default()
+        };
+        let (address, _rtc_server, _shutdown) = start_test_server(config);
+
+        let response = request(
+            address,
+            b"POST /rtc_session HTTP/1.1\r\nContent-Length: 65\r\n\r\n",
+            false,
+        );
+        assert!(response.starts_with("HTTP/1.1 413"));
+    }
 
-const NEWLINE_STR: &str = "\n";
+    #[test]
+    fn session_server_rejects_oversized_stream() {
+        let config = SocketConfig {
+            max_session_request_bytes: 64,
+            ..SocketConfig::default()
+        };
+        let (address, _rtc_server, _shutdown) = start_test_server(config);
 
-impl<'a, R: AsyncBufRead + Unpin> Stream for RequestBuffer<'a, R> {
-    type Item = Result<String, ReqError>;
+        let mut bytes = b"POST /rtc_session HTTP/1.1\r\nAuthorization: ".to_vec();
+        bytes.resize(bytes.len() + 64, b'a');
+        let response = request(address, &bytes, false);
+        assert!(response.starts_with("HTTP/1.1 413"));
+    }
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        if self.add_newline {
-            self.add_newline = false;
-            Poll::Ready(Some(Ok(String::from(NEWLINE_STR))))
-        } else {
-            unsafe {
-                let mut_ref = Pin::new_unchecked(&mut self.buffer);
-                match Stream::poll_next(mut_ref, cx) {
-                    Poll::Ready(Some(item)) => {
-                        self.add_newline = true;
-                        Poll::Ready(Some(item))
-                    }
-                    Poll::Ready(None) => Poll::Ready(None),
-                    Poll::Pending => {
-                        // TODO: This could be catastrophic.. I don't understand futures very
-                        // well!
-                        Poll::Ready(None)
+    #[cfg(feature = "tls")]
+    #[test]
+    fn session_server_answers_over_tls() {
+        use std::sync::Arc;
+
+        use futures_rustls::{
+            rustls::{self, Certificate, RootCertStore, ServerName},
+            TlsConnector,
+        };
+        use smol::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            Async,
+        };
+
+        use crate::session_tls::SessionTlsConfig;
+
+        let certificate =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let session_tls = SessionTlsConfig::from_pem(
+            certificate.serialize_pem().unwrap().as_bytes(),
+            certificate.serialize_private_key_pem().as_bytes(),
+        )
+        .unwrap();
+
+        let session_listen_addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let rtc_server = smol::block_on(webrtc_unreliable::Server::new(
+            "127.0.0.1:0".parse().unwrap(),
+            "127.0.0.1:0".parse().unwrap(),
+        ))
+        .unwrap();
+        let mut server_addrs = ServerAddrs::default();
+        server_addrs.session_listen_addr = session_listen_addr;
+        server_addrs.enable_tls(session_tls);
+        let _shutdown = start_session_server(
+            server_addrs,
+            SocketConfig::default(),
+            rtc_server.session_endpoint(),
+            None,
+            None,
+        );
+
+        let mut root_certs = RootCertStore::empty();
+        root_certs
+            .add(&Certificate(certificate.serialize_der().unwrap()))
+            .unwrap();
+        let connector = TlsConnector::from(Arc::new(
+            rustls::ClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(root_certs)
+                .with_no_client_auth(),
+        ));
+        let tls_request = |bytes: &[u8]| -> String {
+            smol::block_on(async {
+                // the session server starts listening in the background, so retry
+                let mut tcp_stream = None;
+                for _ in 0..100 {
+                    if let Ok(stream) = Async::<TcpStream>::connect(session_listen_addr).await {
+                        tcp_stream = Some(stream);
+                        break;
                     }
+                    thread::sleep(Duration::from_millis(10));
                 }
-            }
-        }
+                let tcp_stream = tcp_stream.expect("session server should accept connections");
+                let server_name = ServerName::try_from("localhost").unwrap();
+                let mut tls_stream = connector
+                    .connect(server_name, tcp_stream)
+                    .await
+                    .expect("session server should complete the TLS handshake");
+                tls_stream.write_all(bytes).await.unwrap();
+                tls_stream.flush().await.unwrap();
+                let mut response = Vec::new();
+                tls_stream
+                    .read_to_end(&mut response)
+                    .await
+                    .expect("session server should answer and close the connection");
+                String::from_utf8_lossy(&response).into_owned()
+            })
+        };
+
+        let response = tls_request(b"OPTIONS /rtc_session HTTP/1.1\r\nConnection: close\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response
+            .to_lowercase()
+            .contains("access-control-allow-methods: post\r\n"));
+
+        // without an auth receiver, the server app rejects every session
+        let offer = "v=0\r\nm=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n";
+        let response = tls_request(
+            format!(
+                "POST /rtc_session HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+                offer.len(),
+                offer
+            )
+            .as_bytes(),
+        );
+        assert!(response.starts_with("HTTP/1.1 401"));
     }
-}
 
-fn response_header_to_vec<T>(r: &Response<T>) -> Vec<u8> {
-    let v = Vec::with_capacity(120);
-    let mut c = std::io::Cursor::new(v);
-    write_response_header(r, &mut c).expect("unable to write response header to stream");
-    c.into_inner()
-}
+    #[test]
+    fn session_server_keeps_connection_alive() {
+        let (address, _rtc_server, _shutdown) = start_test_server(SocketConfig::default());
+
+        let response = request(
+            address,
+            b"GET /health HTTP/1.1\r\n\r\nGET /missing HTTP/1.1\r\nConnection: close\r\n\r\n",
+            false,
+        );
+        let (first, second) = response.split_once("\r\n\r\nok").unwrap();
+        assert!(first.starts_with("HTTP/1.1 200"));
+        assert!(!first.contains("connection: close"));
+        assert!(second.starts_with("HTTP/1.1 404"));
+        assert!(second.contains("connection: close\r\n"));
+    }
 
-fn write_response_header<T>(
-    r: &Response<T>,
-    mut io: impl std::io::Write,
-) -> std::io::Result<usize> {
-    let mut len = 0;
-    macro_rules! w {
-        ($x:expr) => {
-            io.write_all($x)?;
-            len += $x.len();
-        };
+    #[test]
+    fn session_server_reads_chunked_body() {
+        let (address, _rtc_server, _shutdown) = start_test_server(SocketConfig::default());
+
+        // without an auth receiver, the server app rejects every session, but
+        // only once the whole body has been read
+        let offer = "v=0\r\nm=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n";
+        let bytes = format!(
+            "POST /rtc_session HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            offer.len(),
+            offer
+        );
+        let response = request(address, bytes.as_bytes(), false);
+        assert!(response.starts_with("HTTP/1.1 401"));
     }
 
-    let status = r.status();
-    let code = status.as_str();
-    let reason = status.canonical_reason().unwrap_or("Unknown");
-    let headers = r.headers();
+    #[test]
+    fn request_body_keeps_original_line_endings() {
+        let body = b"v=0\r\na=ice-ufrag:abc\r\n".to_vec();
+        assert_eq!(request_body_chunks(body.clone()), vec![body]);
+    }
 
-    w!(b"HTTP/1.1 ");
-    w!(code.as_bytes());
-    w!(b" ");
-    w!(reason.as_bytes());
-    w!(b"\r\n");
+    #[test]
+    fn request_body_terminates_final_line() {
+        let body = b"v=0\r\na=mid:0".to_vec();
+        assert_eq!(
+            request_body_chunks(body.clone()),
+            vec![body, b"\n".to_vec()]
+        );
+    }
 
-    for (hn, hv) in headers {
-        w!(hn.as_str().as_bytes());
-        w!(b": ");
-        w!(hv.as_bytes());
-        w!(b"\r\n");
+    #[test]
+    fn public_candidates_are_advertised_in_order() {
+        assert_eq!(public_candidates_json(&[]), "[]");
+
+        let candidates = [
+            "203.0.113.1:14192".parse().unwrap(),
+            "[2001:db8::1]:14192".parse().unwrap(),
+        ];
+        assert_eq!(
+            public_candidates_json(&candidates),
+            "[\
+            {\"candidate\":\"candidate:2 1 UDP 2 203.0.113.1 14192 typ host\",\"sdpMLineIndex\":0},\
+            {\"candidate\":\"candidate:3 1 UDP 1 2001:db8::1 14192 typ host\",\"sdpMLineIndex\":0}\
+            ]"
+        );
     }
 
-    w!(b"\r\n");
-    Ok(len)
+    #[test]
+    fn sdp_offer_rejection_reasons() {
+        let offer = b"v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\nm=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n";
+        assert_eq!(sdp_offer_rejection(offer), None);
+        assert!(sdp_offer_rejection(&[0xff, 0xfe]).is_some());
+        assert!(sdp_offer_rejection(b"GET / HTTP/1.1").is_some());
+        assert!(sdp_offer_rejection(b"v=0\r\nm=audio 9 RTP/AVP 0\r\n").is_some());
+    }
 }
@@ -0,0 +1,103 @@
+use std::{fs, io::BufReader, path::Path, sync::Arc};
+
+use futures_rustls::{
+    rustls::{self, Certificate, PrivateKey},
+    TlsAcceptor,
+};
+use rustls_pemfile::Item;
+
+use crate::NaiaServerSocketError;
+
+/// Certificate and private key used to serve the session endpoint over HTTPS,
+/// so that pages served over HTTPS may reach it without a separate
+/// TLS-terminating proxy
+#[derive(Clone)]
+pub struct SessionTlsConfig {
+    acceptor: TlsAcceptor,
+}
+
+impl SessionTlsConfig {
+    /// Create a new SessionTlsConfig from a PEM-encoded certificate chain and
+    /// a PEM-encoded PKCS#8, RSA or EC private key
+    pub fn from_pem(
+        cert_chain_pem: &[u8],
+        private_key_pem: &[u8],
+    ) -> Result<Self, NaiaServerSocketError> {
+        let cert_chain = rustls_pemfile::certs(&mut BufReader::new(cert_chain_pem))?
+            .into_iter()
+            .map(Certificate)
+            .collect::<Vec<_>>();
+        if cert_chain.is_empty() {
+            return Err(wrapped_error("no certificates found in PEM"));
+        }
+
+        let mut key_reader = BufReader::new(private_key_pem);
+        let private_key = loop {
+            match rustls_pemfile::read_one(&mut key_reader)? {
+                Some(Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key)) => {
+                    break PrivateKey(key);
+                }
+                Some(_) => continue,
+                None => return Err(wrapped_error("no private key found in PEM")),
+            }
+        };
+
+        let server_config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(cert_chain, private_key)
+            .map_err(|err| NaiaServerSocketError::Wrapped(Box::new(err)))?;
+
+        Ok(Self::from_rustls(Arc::new(server_config)))
+    }
+
+    /// Create a new SessionTlsConfig from the PEM files at the given paths,
+    /// such as those written by certbot
+    pub fn from_pem_files(
+        cert_chain_path: impl AsRef<Path>,
+        private_key_path: impl AsRef<Path>,
+    ) -> Result<Self, NaiaServerSocketError> {
+        Self::from_pem(&fs::read(cert_chain_path)?, &fs::read(private_key_path)?)
+    }
+
+    /// Create a new SessionTlsConfig from an existing rustls configuration
+    pub fn from_rustls(server_config: Arc<rustls::ServerConfig>) -> Self {
+        Self {
+            acceptor: TlsAcceptor::from(server_config),
+        }
+    }
+
+    pub(crate) fn acceptor(&self) -> &TlsAcceptor {
+        &self.acceptor
+    }
+}
+
+fn wrapped_error(message: &str) -> NaiaServerSocketError {
+    NaiaServerSocketError::Wrapped(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::SessionTlsConfig;
+
+    #[test]
+    fn from_pem_files_reads_certificate_and_key() {
+        let certificate =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let dir = std::env::temp_dir().join(format!("naia-session-tls-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let cert_path = dir.join("cert.pem");
+        let key_path = dir.join("key.pem");
+        fs::write(&cert_path, certificate.serialize_pem().unwrap()).unwrap();
+        fs::write(&key_path, certificate.serialize_private_key_pem()).unwrap();
+
+        assert!(SessionTlsConfig::from_pem_files(&cert_path, &key_path).is_ok());
+        // the certificate is not a private key
+        assert!(SessionTlsConfig::from_pem_files(&cert_path, &cert_path).is_err());
+        assert!(SessionTlsConfig::from_pem_files(dir.join("missing.pem"), &key_path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use smol::channel;
+
+/// Stops a Server Socket listening on the `ServerAddrs` it was taken from.
+/// Once shut down, the session listener stops accepting connections, every
+/// connected client is sent a final disconnect packet, and each task the
+/// Socket spawned winds down. Cloning shares the same Socket
+#[derive(Clone)]
+pub struct SocketShutdown {
+    inner: Arc<SocketShutdownInner>,
+}
+
+struct SocketShutdownInner {
+    // closed, never sent to, once shutdown is requested
+    requested_sender: Mutex<Option<channel::Sender<()>>>,
+    requested_receiver: channel::Receiver<()>,
+    // closed, never sent to, once every task holding a TaskGuard has finished
+    finished_sender: Mutex<Option<channel::Sender<()>>>,
+    finished_receiver: channel::Receiver<()>,
+    disconnect_packet: Mutex<Option<Box<[u8]>>>,
+}
+
+impl SocketShutdown {
+    pub fn new() -> Self {
+        let (requested_sender, requested_receiver) = channel::bounded(1);
+        let (finished_sender, finished_receiver) = channel::bounded(1);
+        Self {
+            inner: Arc::new(SocketShutdownInner {
+                requested_sender: Mutex::new(Some(requested_sender)),
+                requested_receiver,
+                finished_sender: Mutex::new(Some(finished_sender)),
+                finished_receiver,
+                disconnect_packet: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Shuts down the Socket, first sending `disconnect_packet`, if given, to
+    /// every client with an open session. Packets already handed to the
+    /// Socket's `PacketSender` are sent before it. The returned future
+    /// resolves once every connection being served has been answered and all
+    /// of the Socket's tasks have finished
+    pub fn shutdown(&self, disconnect_packet: Option<&[u8]>) -> impl Future<Output = ()> {
+        if let Some(requested_sender) = lock(&self.inner.requested_sender).take() {
+            *lock(&self.inner.disconnect_packet) = disconnect_packet.map(Into::into);
+            drop(requested_sender);
+        }
+        lock(&self.inner.finished_sender).take();
+
+        let finished_receiver = self.inner.finished_receiver.clone();
+        async move {
+            let _ = finished_receiver.recv().await;
+        }
+    }
+
+    /// Whether `shutdown()` has been called
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.requested_receiver.is_closed()
+    }
+
+    /// Resolves once shutdown is requested
+    pub(crate) async fn requested(&self) {
+        let _ = self.inner.requested_receiver.recv().await;
+    }
+
+    /// Runs the future until it completes, or until shutdown is requested, in
+    /// which case None is returned
+    pub(crate) async fn or_requested<T>(&self, future: impl Future<Output = T>) -> Option<T> {
+        smol::future::or(async { Some(future.await) }, async {
+            self.requested().await;
+            None
+        })
+        .await
+    }
+
+    /// Keeps the future returned by `shutdown()` from resolving for as long as
+    /// the guard lives. Each task spawned by the Socket should hold one
+    pub(crate) fn task_guard(&self) -> TaskGuard {
+        TaskGuard {
+            _sender: lock(&self.inner.finished_sender).clone(),
+        }
+    }
+
+    /// The packet to send each connected client as the Socket shuts down
+    pub(crate) fn disconnect_packet(&self) -> Option<Box<[u8]>> {
+        lock(&self.inner.disconnect_packet).clone()
+    }
+}
+
+impl Default for SocketShutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Held by a task spawned by the Socket, see `SocketShutdown::task_guard()`
+pub(crate) struct TaskGuard {
+    _sender: Option<channel::Sender<()>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{TcpListener, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    use naia_socket_shared::SocketConfig;
+
+    use super::SocketShutdown;
+    use crate::{server_addrs::ServerAddrs, socket::Socket};
+
+    #[test]
+    fn shutdown_waits_for_task_guards() {
+        let shutdown = SocketShutdown::new();
+        let task_guard = shutdown.task_guard();
+        assert!(!shutdown.is_shutdown());
+
+        let mut finished = Box::pin(shutdown.shutdown(None));
+        assert!(shutdown.is_shutdown());
+        assert!(smol::block_on(smol::future::poll_once(&mut finished)).is_none());
+
+        drop(task_guard);
+        assert!(smol::block_on(smol::future::poll_once(&mut finished)).is_some());
+
+        // tasks started afterwards don't hold up shutdown
+        let _late_guard = shutdown.task_guard();
+        smol::block_on(shutdown.shutdown(None));
+    }
+
+    #[test]
+    fn socket_releases_listener_and_finishes_on_shutdown() {
+        let session_listen_addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let server_addrs = ServerAddrs::new(
+            session_listen_addr,
+            "127.0.0.1:0".parse().unwrap(),
+            "http://127.0.0.1:0",
+        );
+        let shutdown = server_addrs.shutdown_handle();
+        let (_packet_sender, _packet_receiver) =
+            Socket::listen(&server_addrs, &SocketConfig::default());
+
+        // the session server starts listening in the background, so retry
+        (0..100)
+            .find_map(|_| {
+                TcpStream::connect(session_listen_addr).ok().or_else(|| {
+                    thread::sleep(Duration::from_millis(10));
+                    None
+                })
+            })
+            .expect("session server should accept connections");
+
+        let finished = smol::block_on(smol::future::or(
+            async {
+                shutdown.shutdown(Some(b"bye")).await;
+                true
+            },
+            async {
+                smol::Timer::after(Duration::from_secs(5)).await;
+                false
+            },
+        ));
+        assert!(finished, "every task should finish after shutdown");
+
+        assert!(TcpListener::bind(session_listen_addr).is_ok());
+    }
+}
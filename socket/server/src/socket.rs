@@ -2,7 +2,7 @@ use std::net::SocketAddr;
 
 use smol::channel;
 
-use naia_socket_shared::{IdentityToken, SocketConfig};
+use naia_socket_shared::{IdentityToken, RejectReason, SocketConfig};
 
 use super::{
     async_socket::Socket as AsyncSocket,
@@ -34,10 +34,17 @@ impl Socket {
         server_addrs: &ServerAddrs,
         config: &SocketConfig,
     ) -> (Box<dyn PacketSender>, Box<dyn PacketReceiver>) {
+        let (sender_loop_sender, sender_loop_receiver) = channel::bounded(1);
         let (from_client_receiver, sender_receiver) =
-            Self::setup_receiver_loop(server_addrs, config, None, None);
+            Self::setup_receiver_loop(server_addrs, config, None, None, sender_loop_receiver);
 
-        Self::setup_sender_loop(config, from_client_receiver, sender_receiver)
+        Self::setup_sender_loop(
+            server_addrs,
+            config,
+            from_client_receiver,
+            sender_receiver,
+            sender_loop_sender,
+        )
     }
     /// Listens on the Socket for incoming communication from Clients
     pub fn listen_with_auth(
@@ -54,15 +61,22 @@ impl Socket {
         let from_client_auth_sender = Some(from_client_auth_sender);
         let to_session_all_auth_receiver = Some(to_session_all_auth_receiver);
 
+        let (sender_loop_sender, sender_loop_receiver) = channel::bounded(1);
         let (from_client_receiver, sender_receiver) = Self::setup_receiver_loop(
             server_addrs,
             config,
             from_client_auth_sender,
             to_session_all_auth_receiver,
+            sender_loop_receiver,
         );
 
-        let (packet_sender, packet_receiver) =
-            Self::setup_sender_loop(config, from_client_receiver, sender_receiver);
+        let (packet_sender, packet_receiver) = Self::setup_sender_loop(
+            server_addrs,
+            config,
+            from_client_receiver,
+            sender_receiver,
+            sender_loop_sender,
+        );
 
         // Setup Sender
         let auth_sender_impl = AuthSenderImpl::new(to_session_all_auth_sender);
@@ -83,8 +97,10 @@ impl Socket {
             channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
         >,
         to_session_all_auth_receiver: Option<
-            channel::Receiver<(SocketAddr, Option<IdentityToken>)>,
+            channel::Receiver<(SocketAddr, Result<IdentityToken, RejectReason>)>,
         >,
+        // closed once the sender loop has finished
+        sender_loop_receiver: channel::Receiver<()>,
     ) -> (
         channel::Receiver<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
         channel::Receiver<channel::Sender<(SocketAddr, Box<[u8]>)>>,
@@ -95,8 +111,12 @@ impl Socket {
 
         let server_addrs_clone = server_addrs.clone();
         let config_clone = config.clone();
+        let shutdown = server_addrs.shutdown.clone();
+        let task_guard = shutdown.task_guard();
 
         executor::spawn(async move {
+            let _task_guard = task_guard;
+
             // Create async socket
             let mut async_socket = AsyncSocket::listen(
                 server_addrs_clone,
@@ -106,14 +126,21 @@ impl Socket {
             )
             .await;
 
-            sender_sender.send(async_socket.sender()).await.unwrap();
-            //TODO: handle result..
+            if sender_sender.send(async_socket.sender()).await.is_err() {
+                return;
+            }
 
-            loop {
-                let out_message = async_socket.receive().await;
-                from_client_sender.send(out_message).await.unwrap();
-                //TODO: handle result..
+            while let Some(out_message) = shutdown.or_requested(async_socket.receive()).await {
+                if from_client_sender.send(out_message).await.is_err() {
+                    // the PacketReceiver was dropped
+                    break;
+                }
             }
+
+            // wait for the sender loop to pass on every packet sent before
+            // shutdown, then send them and the disconnect packet
+            let _ = sender_loop_receiver.recv().await;
+            async_socket.shutdown(shutdown.disconnect_packet()).await;
         })
         .detach();
 
@@ -121,23 +148,47 @@ impl Socket {
     }
 
     fn setup_sender_loop(
+        server_addrs: &ServerAddrs,
         config: &SocketConfig,
         from_client_receiver: channel::Receiver<
             Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>,
         >,
         sender_receiver: channel::Receiver<channel::Sender<(SocketAddr, Box<[u8]>)>>,
+        // dropped once the sender loop has finished
+        sender_loop_sender: channel::Sender<()>,
     ) -> (Box<dyn PacketSender>, Box<dyn PacketReceiver>) {
         // Set up sender loop
         let (to_client_sender, to_client_receiver) = channel::unbounded();
+        let shutdown = server_addrs.shutdown.clone();
+        let task_guard = shutdown.task_guard();
 
         executor::spawn(async move {
+            let _task_guard = task_guard;
+            let _sender_loop_sender = sender_loop_sender;
+
             // Create async socket
-            let async_sender = sender_receiver.recv().await.unwrap();
+            let Ok(async_sender) = sender_receiver.recv().await else {
+                return;
+            };
 
             loop {
-                if let Ok(msg) = to_client_receiver.recv().await {
-                    async_sender.send(msg).await.unwrap();
-                    //TODO: handle result..
+                match shutdown.or_requested(to_client_receiver.recv()).await {
+                    Some(Ok(msg)) => {
+                        if async_sender.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    // every PacketSender was dropped
+                    Some(Err(_)) => break,
+                    None => {
+                        // pass on whatever was sent before shutdown
+                        while let Ok(msg) = to_client_receiver.try_recv() {
+                            if async_sender.send(msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        break;
+                    }
                 }
             }
         })
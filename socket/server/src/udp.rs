@@ -0,0 +1,476 @@
+use std::{
+    collections::HashMap,
+    net::{SocketAddr, UdpSocket},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use log::{info, warn};
+use smol::Async;
+
+use naia_socket_shared::{IdentityToken, UdpPacketKind};
+
+use crate::{
+    executor,
+    metrics::SocketMetrics,
+    session::{ask_server_app, AuthMuxSender, ClientAuthSender, SessionOutcome},
+    shutdown::SocketShutdown,
+    NaiaServerSocketError,
+};
+
+// Larger than any packet naia sends, so no datagram is truncated
+const MAX_DATAGRAM_BYTES: usize = 1500;
+// Sessions not heard from for this long are forgotten, for clients which
+// never sent a Disconnect
+const SESSION_TIMEOUT: Duration = Duration::from_secs(60);
+// Requests the server app hasn't answered within this long are forgotten,
+// so that requests from spoofed addresses can't pile up
+const PENDING_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(5);
+
+// A session requested by a client over plain UDP
+enum Session {
+    // the server app has yet to answer the client's auth
+    Pending {
+        requested: Instant,
+    },
+    Accepted {
+        identity_token: IdentityToken,
+        last_heard: Instant,
+    },
+}
+
+type Sessions = Arc<Mutex<HashMap<SocketAddr, Session>>>;
+
+/// Accepts sessions from native clients using `Transport::Udp`, exchanging
+/// packets with them as plain datagrams on the Server's data address
+pub(crate) struct UdpServer {
+    socket: Arc<Async<UdpSocket>>,
+    sessions: Sessions,
+    from_client_receiver: smol::channel::Receiver<(SocketAddr, Box<[u8]>)>,
+}
+
+impl UdpServer {
+    pub fn listen(
+        listen_addr: SocketAddr,
+        from_client_auth_sender: Option<ClientAuthSender>,
+        auth_mux_sender: Option<AuthMuxSender>,
+        metrics: SocketMetrics,
+        shutdown: SocketShutdown,
+    ) -> Self {
+        let socket = Arc::new(
+            Async::<UdpSocket>::bind(listen_addr)
+                .expect("unable to bind a UDP socket to the supplied data address"),
+        );
+        info!(
+            "UDP sessions available at {}",
+            socket
+                .get_ref()
+                .local_addr()
+                .expect("UDP socket does not have a local address")
+        );
+
+        let sessions: Sessions = Arc::new(Mutex::new(HashMap::new()));
+        let (from_client_sender, from_client_receiver) = smol::channel::unbounded();
+
+        let socket_clone = socket.clone();
+        let sessions_clone = sessions.clone();
+        let task_guard = shutdown.task_guard();
+        executor::spawn(async move {
+            let _task_guard = task_guard;
+            let mut buffer = [0; MAX_DATAGRAM_BYTES];
+            let mut last_expiry = Instant::now();
+            loop {
+                let Some(received) = shutdown
+                    .or_requested(socket_clone.recv_from(&mut buffer))
+                    .await
+                else {
+                    break;
+                };
+                let (length, remote_addr) = match received {
+                    Ok(received) => received,
+                    Err(err) => {
+                        warn!("Unable to receive a UDP datagram: {}", err);
+                        continue;
+                    }
+                };
+
+                if last_expiry.elapsed() >= EXPIRY_INTERVAL {
+                    expire_sessions(&sessions_clone);
+                    last_expiry = Instant::now();
+                }
+
+                let Some((kind, payload)) = UdpPacketKind::split(&buffer[..length]) else {
+                    continue;
+                };
+                match kind {
+                    UdpPacketKind::Data => {
+                        let accepted = match lock(&sessions_clone).get_mut(&remote_addr) {
+                            Some(Session::Accepted { last_heard, .. }) => {
+                                *last_heard = Instant::now();
+                                true
+                            }
+                            _ => false,
+                        };
+                        if !accepted {
+                            metrics.packet_dropped_receiving();
+                        } else if from_client_sender
+                            .send((remote_addr, payload.into()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    UdpPacketKind::SessionRequest => {
+                        let answered = match lock(&sessions_clone).get(&remote_addr) {
+                            // the client missed the accept, so send it again
+                            Some(Session::Accepted { identity_token, .. }) => {
+                                Some(identity_token.clone())
+                            }
+                            Some(Session::Pending { .. }) => continue,
+                            None => None,
+                        };
+                        if let Some(identity_token) = answered {
+                            let packet =
+                                UdpPacketKind::SessionAccept.packet(identity_token.as_bytes());
+                            let _ = socket_clone.send_to(&packet, remote_addr).await;
+                            continue;
+                        }
+
+                        info!("Incoming UDP session request from {}", remote_addr);
+                        lock(&sessions_clone).insert(
+                            remote_addr,
+                            Session::Pending {
+                                requested: Instant::now(),
+                            },
+                        );
+                        let task_guard = shutdown.task_guard();
+                        let socket = socket_clone.clone();
+                        let sessions = sessions_clone.clone();
+                        let auth_bytes: Box<[u8]> = payload.into();
+                        let from_client_auth_sender = from_client_auth_sender.clone();
+                        let auth_mux_sender = auth_mux_sender.clone();
+                        let metrics = metrics.clone();
+                        executor::spawn(async move {
+                            let _task_guard = task_guard;
+                            answer_session(
+                                &socket,
+                                &sessions,
+                                remote_addr,
+                                auth_bytes,
+                                from_client_auth_sender,
+                                auth_mux_sender,
+                                &metrics,
+                            )
+                            .await;
+                        })
+                        .detach();
+                    }
+                    UdpPacketKind::Disconnect => {
+                        let mut sessions = lock(&sessions_clone);
+                        // only the client given the session's IdentityToken
+                        // may end it, so a spoofed Disconnect can't
+                        let authentic = matches!(
+                            sessions.get(&remote_addr),
+                            Some(Session::Accepted { identity_token, .. })
+                                if identity_token.as_bytes() == payload
+                        );
+                        if authentic {
+                            sessions.remove(&remote_addr);
+                            info!("UDP session with {} closed", remote_addr);
+                        }
+                    }
+                    // only ever sent by the server
+                    UdpPacketKind::SessionAccept | UdpPacketKind::SessionReject => {}
+                }
+            }
+        })
+        .detach();
+
+        Self {
+            socket,
+            sessions,
+            from_client_receiver,
+        }
+    }
+
+    pub async fn recv(&self) -> (SocketAddr, Box<[u8]>) {
+        self.from_client_receiver
+            .recv()
+            .await
+            .expect("UDP receiver closed")
+    }
+
+    /// The addresses of every client with an open session
+    pub fn session_addrs(&self) -> Vec<SocketAddr> {
+        lock(&self.sessions)
+            .iter()
+            .filter(|(_, session)| matches!(session, Session::Accepted { .. }))
+            .map(|(address, _)| *address)
+            .collect()
+    }
+
+    pub async fn send(
+        &self,
+        address: &SocketAddr,
+        payload: &[u8],
+    ) -> Result<(), NaiaServerSocketError> {
+        if !matches!(
+            lock(&self.sessions).get(address),
+            Some(Session::Accepted { .. })
+        ) {
+            return Err(NaiaServerSocketError::SendError(*address));
+        }
+        self.socket
+            .send_to(&UdpPacketKind::Data.packet(payload), *address)
+            .await
+            .map(|_| ())
+            .map_err(|_| NaiaServerSocketError::SendError(*address))
+    }
+}
+
+/// Checks the auth of a UDP session request with the server app, then sends
+/// the client its answer
+async fn answer_session(
+    socket: &Async<UdpSocket>,
+    sessions: &Sessions,
+    remote_addr: SocketAddr,
+    auth_bytes: Box<[u8]>,
+    from_client_auth_sender: Option<ClientAuthSender>,
+    auth_mux_sender: Option<AuthMuxSender>,
+    metrics: &SocketMetrics,
+) {
+    let answer = ask_server_app(
+        remote_addr,
+        auth_bytes,
+        from_client_auth_sender.as_ref(),
+        auth_mux_sender.as_ref(),
+    )
+    .await;
+
+    let packet = match answer {
+        Ok(identity_token) => {
+            let packet = UdpPacketKind::SessionAccept.packet(identity_token.as_bytes());
+            {
+                let mut sessions = lock(sessions);
+                let Some(session @ Session::Pending { .. }) = sessions.get_mut(&remote_addr) else {
+                    // the request expired, or the client disconnected, while
+                    // the server app was answering it
+                    return;
+                };
+                *session = Session::Accepted {
+                    identity_token,
+                    last_heard: Instant::now(),
+                };
+            }
+            info!("Successful UDP session request from {}", remote_addr);
+            metrics.record_session(SessionOutcome::Accepted);
+            packet
+        }
+        Err(reason) => {
+            info!(
+                "Rejected UDP session request from {}. Reason: {:?}",
+                remote_addr, reason
+            );
+            lock(sessions).remove(&remote_addr);
+            metrics.record_session(SessionOutcome::Rejected);
+            let mut payload = reason.status_code().to_be_bytes().to_vec();
+            payload.extend_from_slice(reason.to_body().as_bytes());
+            UdpPacketKind::SessionReject.packet(&payload)
+        }
+    };
+
+    if let Err(err) = socket.send_to(&packet, remote_addr).await {
+        warn!(
+            "Unable to answer UDP session request from {}. Error: {}",
+            remote_addr, err
+        );
+    }
+}
+
+fn expire_sessions(sessions: &Sessions) {
+    expire_sessions_after(sessions, PENDING_SESSION_TIMEOUT, SESSION_TIMEOUT);
+}
+
+fn expire_sessions_after(sessions: &Sessions, pending_timeout: Duration, timeout: Duration) {
+    lock(sessions).retain(|address, session| match session {
+        Session::Pending { requested } => requested.elapsed() < pending_timeout,
+        Session::Accepted { last_heard, .. } if last_heard.elapsed() >= timeout => {
+            info!("UDP session with {} timed out", address);
+            false
+        }
+        Session::Accepted { .. } => true,
+    });
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        net::UdpSocket,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use naia_socket_shared::{RejectReason, UdpPacketKind};
+
+    use super::{expire_sessions_after, lock, Session, UdpServer};
+    use crate::{
+        executor, metrics::SocketMetrics, session::setup_auth_mux, shutdown::SocketShutdown,
+    };
+
+    fn listen() -> (UdpServer, UdpSocket) {
+        let (from_client_auth_sender, from_client_auth_receiver) = smol::channel::unbounded();
+        let (to_session_all_auth_sender, to_session_all_auth_receiver) = smol::channel::unbounded();
+        let server = UdpServer::listen(
+            "127.0.0.1:0".parse().unwrap(),
+            Some(from_client_auth_sender),
+            Some(setup_auth_mux(
+                to_session_all_auth_receiver,
+                SocketShutdown::new(),
+            )),
+            SocketMetrics::new(),
+            SocketShutdown::new(),
+        );
+
+        // answer auth as the server app would
+        executor::spawn(async move {
+            while let Ok(Ok((address, auth_bytes))) = from_client_auth_receiver.recv().await {
+                let answer = match &*auth_bytes {
+                    b"player" => Ok("token".to_string()),
+                    _ => Err(RejectReason::ServerFull),
+                };
+                to_session_all_auth_sender
+                    .send((address, answer))
+                    .await
+                    .unwrap();
+            }
+        })
+        .detach();
+
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        client
+            .connect(server.socket.get_ref().local_addr().unwrap())
+            .unwrap();
+        (server, client)
+    }
+
+    #[test]
+    fn accepted_session_relays_packets() {
+        let (server, client) = listen();
+        let mut buffer = [0; 1500];
+
+        // data sent before the session is accepted is dropped
+        client
+            .send(&UdpPacketKind::Data.packet(b"too early"))
+            .unwrap();
+        client
+            .send(&UdpPacketKind::SessionRequest.packet(b"player"))
+            .unwrap();
+        let length = client.recv(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..length],
+            &UdpPacketKind::SessionAccept.packet(b"token")[..]
+        );
+        let client_addr = client.local_addr().unwrap();
+        assert_eq!(server.session_addrs(), vec![client_addr]);
+
+        // a repeated request is answered again, without asking the server app
+        client
+            .send(&UdpPacketKind::SessionRequest.packet(b"player"))
+            .unwrap();
+        let length = client.recv(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..length],
+            &UdpPacketKind::SessionAccept.packet(b"token")[..]
+        );
+
+        client.send(&UdpPacketKind::Data.packet(b"hello")).unwrap();
+        let (address, payload) = smol::block_on(server.recv());
+        assert_eq!((address, &*payload), (client_addr, &b"hello"[..]));
+
+        smol::block_on(server.send(&client_addr, b"welcome")).unwrap();
+        let length = client.recv(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..length],
+            &UdpPacketKind::Data.packet(b"welcome")[..]
+        );
+
+        // a Disconnect without the session's IdentityToken is ignored
+        client
+            .send(&UdpPacketKind::Disconnect.packet(b"forged"))
+            .unwrap();
+        client
+            .send(&UdpPacketKind::Data.packet(b"still here"))
+            .unwrap();
+        let (address, payload) = smol::block_on(server.recv());
+        assert_eq!((address, &*payload), (client_addr, &b"still here"[..]));
+
+        client
+            .send(&UdpPacketKind::Disconnect.packet(b"token"))
+            .unwrap();
+        client.send(&UdpPacketKind::Data.packet(b"after")).unwrap();
+        // the server socket handles datagrams in order, so once the session
+        // is gone nothing more is relayed
+        while !server.session_addrs().is_empty() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(server.from_client_receiver.try_recv().is_err());
+        assert!(smol::block_on(server.send(&client_addr, b"gone")).is_err());
+    }
+
+    #[test]
+    fn rejected_session_is_answered_with_reason() {
+        let (server, client) = listen();
+        let mut buffer = [0; 1500];
+
+        client
+            .send(&UdpPacketKind::SessionRequest.packet(b"stranger"))
+            .unwrap();
+        let length = client.recv(&mut buffer).unwrap();
+        let (kind, payload) = UdpPacketKind::split(&buffer[..length]).unwrap();
+        assert_eq!(kind, UdpPacketKind::SessionReject);
+        assert_eq!(&payload[..2], &503u16.to_be_bytes());
+        assert_eq!(&payload[2..], b"server_full");
+        assert!(server.session_addrs().is_empty());
+    }
+
+    #[test]
+    fn unanswered_requests_expire() {
+        let sessions = Arc::new(Mutex::new(HashMap::new()));
+        let pending_addr = "127.0.0.1:1".parse().unwrap();
+        let accepted_addr = "127.0.0.1:2".parse().unwrap();
+        lock(&sessions).insert(
+            pending_addr,
+            Session::Pending {
+                requested: Instant::now(),
+            },
+        );
+        lock(&sessions).insert(
+            accepted_addr,
+            Session::Accepted {
+                identity_token: "token".to_string(),
+                last_heard: Instant::now(),
+            },
+        );
+
+        expire_sessions_after(&sessions, Duration::from_secs(60), Duration::from_secs(60));
+        assert_eq!(lock(&sessions).len(), 2);
+
+        // a pending request is forgotten long before an accepted session
+        std::thread::sleep(Duration::from_millis(10));
+        expire_sessions_after(&sessions, Duration::ZERO, Duration::from_secs(60));
+        let remaining: Vec<_> = lock(&sessions).keys().copied().collect();
+        assert_eq!(remaining, vec![accepted_addr]);
+    }
+}
@@ -0,0 +1,652 @@
+use std::{
+    collections::HashMap,
+    io::BufReader,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use h3::server::RequestStream;
+use http1::{Method, Request, Response, StatusCode};
+use log::{info, warn};
+use quinn::crypto::rustls::QuicServerConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls_pemfile::Item;
+
+use naia_socket_shared::{IdentityToken, RejectReason};
+
+use crate::{
+    executor,
+    metrics::SocketMetrics,
+    session::{ask_server_app, AuthMuxSender, SessionOutcome},
+    shutdown::SocketShutdown,
+    NaiaServerSocketError,
+};
+
+// Stream type which opens a unidirectional stream belonging to a WebTransport
+// session, followed by the session's id
+const WEBTRANSPORT_UNI_STREAM_TYPE: u64 = 0x54;
+const AUTH_QUERY_KEY: &str = "auth";
+
+/// Address and certificate to accept WebTransport (HTTP/3) sessions with, so
+/// that browsers supporting it can connect without a WebRTC session request
+#[derive(Clone)]
+pub struct WebTransportConfig {
+    /// IP Address to bind to for QUIC. Browsers connect to it at
+    /// `https://<public address>/<rtc_endpoint_path>`
+    pub listen_addr: SocketAddr,
+    server_config: quinn::ServerConfig,
+}
+
+impl WebTransportConfig {
+    /// Create a new WebTransportConfig from a PEM-encoded certificate chain
+    /// and a PEM-encoded PKCS#8, RSA or EC private key. Browsers only accept
+    /// self-signed certificates which are ECDSA, valid for no more than 14
+    /// days, and whose hash is given to the client in
+    /// `SocketConfig::webtransport_certificate_hashes`
+    pub fn from_pem(
+        listen_addr: SocketAddr,
+        cert_chain_pem: &[u8],
+        private_key_pem: &[u8],
+    ) -> Result<Self, NaiaServerSocketError> {
+        let cert_chain = rustls_pemfile::certs(&mut BufReader::new(cert_chain_pem))?
+            .into_iter()
+            .map(CertificateDer::from)
+            .collect::<Vec<_>>();
+        if cert_chain.is_empty() {
+            return Err(wrapped_error("no certificates found in PEM"));
+        }
+
+        let mut key_reader = BufReader::new(private_key_pem);
+        let private_key = loop {
+            match rustls_pemfile::read_one(&mut key_reader)? {
+                Some(Item::PKCS8Key(key)) => break PrivateKeyDer::Pkcs8(key.into()),
+                Some(Item::RSAKey(key)) => break PrivateKeyDer::Pkcs1(key.into()),
+                Some(Item::ECKey(key)) => break PrivateKeyDer::Sec1(key.into()),
+                Some(_) => continue,
+                None => return Err(wrapped_error("no private key found in PEM")),
+            }
+        };
+
+        let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(|err| NaiaServerSocketError::Wrapped(Box::new(err)))?
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, private_key)
+        .map_err(|err| NaiaServerSocketError::Wrapped(Box::new(err)))?;
+
+        Self::from_rustls(listen_addr, server_config)
+    }
+
+    /// Create a new WebTransportConfig from an existing rustls configuration,
+    /// which must allow TLS 1.3. Its ALPN protocols are replaced with HTTP/3's
+    pub fn from_rustls(
+        listen_addr: SocketAddr,
+        mut server_config: rustls::ServerConfig,
+    ) -> Result<Self, NaiaServerSocketError> {
+        server_config.alpn_protocols = vec![b"h3".to_vec()];
+        let quic_config = QuicServerConfig::try_from(server_config)
+            .map_err(|err| NaiaServerSocketError::Wrapped(Box::new(err)))?;
+
+        Ok(Self {
+            listen_addr,
+            server_config: quinn::ServerConfig::with_crypto(Arc::new(quic_config)),
+        })
+    }
+}
+
+// A WebTransport session set up with a client
+struct Session {
+    connection: quinn::Connection,
+    // the session's quarter stream id, which prefixes each of its datagrams
+    datagram_prefix: Vec<u8>,
+}
+
+type Sessions = Arc<Mutex<HashMap<SocketAddr, Session>>>;
+
+/// Accepts WebTransport sessions from browser clients, exchanging packets
+/// with them as QUIC datagrams
+pub(crate) struct WebTransportServer {
+    endpoint: quinn::Endpoint,
+    sessions: Sessions,
+    from_client_receiver: smol::channel::Receiver<(SocketAddr, Box<[u8]>)>,
+}
+
+impl WebTransportServer {
+    pub fn listen(
+        config: WebTransportConfig,
+        rtc_endpoint_path: &str,
+        from_client_auth_sender: Option<
+            smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+        >,
+        auth_mux_sender: Option<AuthMuxSender>,
+        metrics: SocketMetrics,
+        shutdown: SocketShutdown,
+    ) -> Self {
+        let endpoint = quinn::Endpoint::server(config.server_config, config.listen_addr)
+            .expect("unable to bind a QUIC endpoint to the supplied WebTransport address");
+        info!(
+            "WebTransport sessions available at https://{}/{}",
+            config.listen_addr, rtc_endpoint_path
+        );
+
+        let sessions: Sessions = Arc::new(Mutex::new(HashMap::new()));
+        let (from_client_sender, from_client_receiver) = smol::channel::unbounded();
+
+        let session_path = format!("/{}", rtc_endpoint_path);
+        let endpoint_clone = endpoint.clone();
+        let sessions_clone = sessions.clone();
+        let task_guard = shutdown.task_guard();
+        executor::spawn(async move {
+            let _task_guard = task_guard;
+            // accepting stops once the endpoint is closed, as the Socket drops
+            while let Some(incoming) = endpoint_clone.accept().await {
+                let session_path = session_path.clone();
+                let sessions = sessions_clone.clone();
+                let from_client_sender = from_client_sender.clone();
+                let from_client_auth_sender = from_client_auth_sender.clone();
+                let auth_mux_sender = auth_mux_sender.clone();
+                let metrics = metrics.clone();
+                let task_guard = shutdown.task_guard();
+                executor::spawn(async move {
+                    let _task_guard = task_guard;
+                    let connection = match incoming.await {
+                        Ok(connection) => connection,
+                        Err(err) => {
+                            warn!("Unable to accept an incoming QUIC connection: {}", err);
+                            return;
+                        }
+                    };
+                    serve(
+                        connection,
+                        &session_path,
+                        sessions,
+                        from_client_sender,
+                        from_client_auth_sender,
+                        auth_mux_sender,
+                        metrics,
+                    )
+                    .await;
+                })
+                .detach();
+            }
+        })
+        .detach();
+
+        Self {
+            endpoint,
+            sessions,
+            from_client_receiver,
+        }
+    }
+
+    pub async fn recv(&self) -> (SocketAddr, Box<[u8]>) {
+        self.from_client_receiver
+            .recv()
+            .await
+            .expect("WebTransport receiver closed")
+    }
+
+    /// The addresses of every client with an open session
+    pub fn session_addrs(&self) -> Vec<SocketAddr> {
+        self.sessions().keys().copied().collect()
+    }
+
+    /// Whether packets to the given address should be sent over WebTransport
+    pub fn has_session(&self, address: &SocketAddr) -> bool {
+        self.sessions().contains_key(address)
+    }
+
+    pub fn send(&self, address: &SocketAddr, payload: &[u8]) -> Result<(), NaiaServerSocketError> {
+        let sessions = self.sessions();
+        let Some(session) = sessions.get(address) else {
+            return Err(NaiaServerSocketError::SendError(*address));
+        };
+        let mut datagram = Vec::with_capacity(session.datagram_prefix.len() + payload.len());
+        datagram.extend_from_slice(&session.datagram_prefix);
+        datagram.extend_from_slice(payload);
+        session
+            .connection
+            .send_datagram(Bytes::from(datagram))
+            .map_err(|_| NaiaServerSocketError::SendError(*address))
+    }
+
+    fn sessions(&self) -> std::sync::MutexGuard<'_, HashMap<SocketAddr, Session>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for WebTransportServer {
+    fn drop(&mut self) {
+        self.endpoint.close(0u32.into(), b"server closed");
+    }
+}
+
+/// Sets up the HTTP/3 connection, answers its WebTransport session request,
+/// and then relays its datagrams until the connection closes
+async fn serve(
+    connection: quinn::Connection,
+    session_path: &str,
+    sessions: Sessions,
+    from_client_sender: smol::channel::Sender<(SocketAddr, Box<[u8]>)>,
+    from_client_auth_sender: Option<
+        smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+    >,
+    auth_mux_sender: Option<AuthMuxSender>,
+    metrics: SocketMetrics,
+) {
+    let remote_addr = connection.remote_address();
+    info!("Incoming WebTransport session request from {}", remote_addr);
+
+    let mut h3_connection: h3::server::Connection<h3_quinn::Connection, Bytes> =
+        match h3::server::builder()
+            .enable_webtransport(true)
+            .enable_extended_connect(true)
+            .enable_datagram(true)
+            .max_webtransport_sessions(1)
+            .send_grease(true)
+            .build(h3_quinn::Connection::new(connection.clone()))
+            .await
+        {
+            Ok(h3_connection) => h3_connection,
+            Err(err) => {
+                warn!(
+                    "Dropped WebTransport session request from {}. HTTP/3 error: {}",
+                    remote_addr, err
+                );
+                metrics.record_session(SessionOutcome::Failed);
+                return;
+            }
+        };
+
+    // the first request on the connection must be its session request
+    let resolved = match h3_connection.accept().await {
+        Ok(Some(resolver)) => resolver.resolve_request().await.ok(),
+        _ => None,
+    };
+    let Some((request, mut stream)) = resolved else {
+        warn!(
+            "Dropped WebTransport session request from {}. Error: no request received",
+            remote_addr
+        );
+        metrics.record_session(SessionOutcome::Failed);
+        return;
+    };
+
+    if !is_session_request(&request, session_path) {
+        warn!(
+            "Invalid WebTransport session request from {}. Error: not a CONNECT to {}",
+            remote_addr, session_path
+        );
+        let _ = respond(&mut stream, StatusCode::NOT_FOUND).await;
+        metrics.record_session(SessionOutcome::Failed);
+        return;
+    }
+
+    let identity_token = match authenticate(
+        remote_addr,
+        &request,
+        from_client_auth_sender,
+        auth_mux_sender,
+    )
+    .await
+    {
+        Ok(identity_token) => identity_token,
+        Err(reason) => {
+            info!(
+                "Rejected WebTransport session request from {}. Reason: {:?}",
+                remote_addr, reason
+            );
+            let status = StatusCode::from_u16(reason.status_code())
+                .expect("reject reasons have valid status codes");
+            let _ = respond(&mut stream, status).await;
+            metrics.record_session(SessionOutcome::Rejected);
+            return;
+        }
+    };
+
+    let session_id = stream.id().into_inner();
+    if respond(&mut stream, StatusCode::OK).await.is_err()
+        || send_identity_token(&connection, session_id, &identity_token)
+            .await
+            .is_err()
+    {
+        warn!(
+            "Dropped WebTransport session request from {}. Error: unable to answer",
+            remote_addr
+        );
+        metrics.record_session(SessionOutcome::Failed);
+        return;
+    }
+    info!(
+        "Successful WebTransport session request from {}",
+        remote_addr
+    );
+    metrics.record_session(SessionOutcome::Accepted);
+
+    let mut datagram_prefix = Vec::new();
+    encode_varint(session_id / 4, &mut datagram_prefix);
+    sessions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(
+            remote_addr,
+            Session {
+                connection: connection.clone(),
+                datagram_prefix: datagram_prefix.clone(),
+            },
+        );
+
+    // keep the HTTP/3 connection serviced, refusing any further requests,
+    // while datagrams are relayed
+    let serve_requests = async {
+        while let Ok(Some(resolver)) = h3_connection.accept().await {
+            if let Ok((_, mut stream)) = resolver.resolve_request().await {
+                let _ = respond(&mut stream, StatusCode::NOT_FOUND).await;
+            }
+        }
+    };
+    let relay_datagrams = async {
+        while let Ok(datagram) = connection.read_datagram().await {
+            let Some(payload) = datagram.strip_prefix(datagram_prefix.as_slice()) else {
+                continue;
+            };
+            if from_client_sender
+                .send((remote_addr, payload.into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    };
+    smol::future::or(serve_requests, relay_datagrams).await;
+
+    sessions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&remote_addr);
+    connection.close(0u32.into(), b"session closed");
+    // keep the session's request stream open until the session ends
+    drop(stream);
+    info!("WebTransport session with {} closed", remote_addr);
+}
+
+fn is_session_request(request: &Request<()>, session_path: &str) -> bool {
+    request.method() == Method::CONNECT
+        && request.extensions().get::<h3::ext::Protocol>()
+            == Some(&h3::ext::Protocol::WEB_TRANSPORT)
+        && request.uri().path() == session_path
+}
+
+/// Sends the client's auth, taken from the session URL's query string as
+/// browsers can't set headers on a WebTransport request, to the server app
+/// and waits for its answer
+async fn authenticate(
+    remote_addr: SocketAddr,
+    request: &Request<()>,
+    from_client_auth_sender: Option<
+        smol::channel::Sender<Result<(SocketAddr, Box<[u8]>), NaiaServerSocketError>>,
+    >,
+    auth_mux_sender: Option<AuthMuxSender>,
+) -> Result<IdentityToken, RejectReason> {
+    let Some(auth_bytes) = request
+        .uri()
+        .query()
+        .and_then(|query| query_value(query, AUTH_QUERY_KEY))
+        .and_then(|auth_string| base64::decode_config(auth_string, base64::URL_SAFE_NO_PAD).ok())
+    else {
+        warn!(
+            "Invalid WebTransport session request from {}. Error: missing or undecodable auth string",
+            remote_addr
+        );
+        return Err(RejectReason::Unauthorized);
+    };
+
+    ask_server_app(
+        remote_addr,
+        auth_bytes.into(),
+        from_client_auth_sender.as_ref(),
+        auth_mux_sender.as_ref(),
+    )
+    .await
+}
+
+async fn respond(
+    stream: &mut RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>,
+    status: StatusCode,
+) -> Result<(), h3::error::StreamError> {
+    let response = Response::builder()
+        .status(status)
+        .header("sec-webtransport-http3-draft", "draft02")
+        .body(())
+        .expect("could not build WebTransport response");
+    stream.send_response(response).await?;
+    if status != StatusCode::OK {
+        stream.finish().await?;
+    }
+    Ok(())
+}
+
+/// Sends the IdentityToken to the client on a unidirectional stream of the
+/// session, which the client reads in full before sending its first packet
+async fn send_identity_token(
+    connection: &quinn::Connection,
+    session_id: u64,
+    identity_token: &IdentityToken,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut header = Vec::new();
+    encode_varint(WEBTRANSPORT_UNI_STREAM_TYPE, &mut header);
+    encode_varint(session_id, &mut header);
+
+    let mut send_stream = connection.open_uni().await?;
+    send_stream.write_all(&header).await?;
+    send_stream.write_all(identity_token.as_bytes()).await?;
+    send_stream.finish()?;
+    Ok(())
+}
+
+fn query_value<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query.split('&').find_map(|pair| {
+        let (pair_key, value) = pair.split_once('=')?;
+        (pair_key == key).then_some(value)
+    })
+}
+
+// Appends a QUIC variable-length integer (RFC 9000, section 16)
+fn encode_varint(value: u64, out: &mut Vec<u8>) {
+    if value < (1 << 6) {
+        out.push(value as u8);
+    } else if value < (1 << 14) {
+        out.extend_from_slice(&(value as u16 | 0x4000).to_be_bytes());
+    } else if value < (1 << 30) {
+        out.extend_from_slice(&(value as u32 | 0x8000_0000).to_be_bytes());
+    } else {
+        assert!(value < (1 << 62), "value too large for a QUIC varint");
+        out.extend_from_slice(&(value | 0xc000_0000_0000_0000).to_be_bytes());
+    }
+}
+
+fn wrapped_error(message: &str) -> NaiaServerSocketError {
+    NaiaServerSocketError::Wrapped(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use http1::{Method, Request, StatusCode};
+    use quinn::crypto::rustls::QuicClientConfig;
+    use rustls::{pki_types::CertificateDer, RootCertStore};
+
+    use super::{
+        encode_varint, query_value, WebTransportConfig, WebTransportServer,
+        WEBTRANSPORT_UNI_STREAM_TYPE,
+    };
+    use crate::{
+        executor, metrics::SocketMetrics, session::setup_auth_mux, shutdown::SocketShutdown,
+    };
+
+    fn encoded(value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_varint(value, &mut out);
+        out
+    }
+
+    #[test]
+    fn varints_encode_to_shortest_form() {
+        // examples from RFC 9000, appendix A.1
+        assert_eq!(encoded(37), vec![0x25]);
+        assert_eq!(encoded(15293), vec![0x7b, 0xbd]);
+        assert_eq!(encoded(494878333), vec![0x9d, 0x7f, 0x3e, 0x7d]);
+        assert_eq!(
+            encoded(151288809941952652),
+            vec![0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c]
+        );
+    }
+
+    #[test]
+    fn query_value_finds_key() {
+        assert_eq!(query_value("auth=cGxheWVy", "auth"), Some("cGxheWVy"));
+        assert_eq!(query_value("a=1&auth=x&b=2", "auth"), Some("x"));
+        assert_eq!(query_value("author=x", "auth"), None);
+        assert_eq!(query_value("", "auth"), None);
+    }
+
+    #[test]
+    fn session_sends_identity_token_and_relays_datagrams() {
+        let certificate =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let config = WebTransportConfig::from_pem(
+            "127.0.0.1:0".parse().unwrap(),
+            certificate.serialize_pem().unwrap().as_bytes(),
+            certificate.serialize_private_key_pem().as_bytes(),
+        )
+        .unwrap();
+
+        let (from_client_auth_sender, from_client_auth_receiver) = smol::channel::unbounded();
+        let (to_session_all_auth_sender, to_session_all_auth_receiver) = smol::channel::unbounded();
+        let server = WebTransportServer::listen(
+            config,
+            "rtc_session",
+            Some(from_client_auth_sender),
+            Some(setup_auth_mux(
+                to_session_all_auth_receiver,
+                SocketShutdown::new(),
+            )),
+            SocketMetrics::new(),
+            SocketShutdown::new(),
+        );
+        let server_addr = server.endpoint.local_addr().unwrap();
+
+        // answer auth as the server app would
+        executor::spawn(async move {
+            let (address, auth_bytes) = from_client_auth_receiver.recv().await.unwrap().unwrap();
+            assert_eq!(&*auth_bytes, b"player");
+            to_session_all_auth_sender
+                .send((address, Ok("token".to_string())))
+                .await
+                .unwrap();
+        })
+        .detach();
+
+        smol::block_on(async {
+            let mut root_certs = RootCertStore::empty();
+            root_certs
+                .add(CertificateDer::from(certificate.serialize_der().unwrap()))
+                .unwrap();
+            let mut client_crypto = rustls::ClientConfig::builder_with_provider(Arc::new(
+                rustls::crypto::ring::default_provider(),
+            ))
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .unwrap()
+            .with_root_certificates(root_certs)
+            .with_no_client_auth();
+            client_crypto.alpn_protocols = vec![b"h3".to_vec()];
+
+            let mut endpoint = quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+            endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(
+                QuicClientConfig::try_from(client_crypto).unwrap(),
+            )));
+            let connection = endpoint
+                .connect(server_addr, "localhost")
+                .unwrap()
+                .await
+                .unwrap();
+
+            // the identity token arrives on a session stream, among the
+            // server's HTTP/3 control streams
+            let (token_sender, token_receiver) = smol::channel::unbounded();
+            let connection_clone = connection.clone();
+            executor::spawn(async move {
+                while let Ok(mut uni_stream) = connection_clone.accept_uni().await {
+                    let token_sender = token_sender.clone();
+                    executor::spawn(async move {
+                        let mut stream_type = [0; 2];
+                        uni_stream.read_exact(&mut stream_type).await.unwrap();
+                        if stream_type == [0x40, WEBTRANSPORT_UNI_STREAM_TYPE as u8] {
+                            let rest = uni_stream.read_to_end(1024).await.unwrap();
+                            token_sender.send(rest).await.unwrap();
+                        }
+                    })
+                    .detach();
+                }
+            })
+            .detach();
+
+            let (_driver, mut send_request) = h3::client::builder()
+                .enable_extended_connect(true)
+                .enable_datagram(true)
+                .build::<_, _, Bytes>(h3_quinn::Connection::new(connection.clone()))
+                .await
+                .unwrap();
+
+            let mut request = Request::builder()
+                .method(Method::CONNECT)
+                .uri(format!(
+                    "https://localhost:{}/rtc_session?auth={}",
+                    server_addr.port(),
+                    base64::encode_config("player", base64::URL_SAFE_NO_PAD)
+                ))
+                .body(())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(h3::ext::Protocol::WEB_TRANSPORT);
+            let mut request_stream = send_request.send_request(request).await.unwrap();
+            let response = request_stream.recv_response().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let session_id = request_stream.id().into_inner();
+            let mut expected_token = Vec::new();
+            encode_varint(session_id, &mut expected_token);
+            expected_token.extend_from_slice(b"token");
+            assert_eq!(token_receiver.recv().await.unwrap(), expected_token);
+
+            let mut datagram_prefix = Vec::new();
+            encode_varint(session_id / 4, &mut datagram_prefix);
+
+            let mut datagram = datagram_prefix.clone();
+            datagram.extend_from_slice(b"ping");
+            connection.send_datagram(Bytes::from(datagram)).unwrap();
+
+            let (address, payload) = server.recv().await;
+            assert_eq!(address, endpoint.local_addr().unwrap());
+            assert_eq!(&*payload, b"ping");
+            assert!(server.has_session(&address));
+
+            server.send(&address, b"pong").unwrap();
+            let mut expected_datagram = datagram_prefix;
+            expected_datagram.extend_from_slice(b"pong");
+            assert_eq!(
+                connection.read_datagram().await.unwrap(),
+                Bytes::from(expected_datagram)
+            );
+        });
+    }
+}
@@ -0,0 +1,29 @@
+/// Which web origins may initiate WebRTC sessions with a Server, as reported
+/// to browsers through the `Access-Control-Allow-Origin` response header
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum AllowedOrigin {
+    /// Any origin may initiate a session. The header is sent as `*`
+    #[default]
+    Any,
+    /// Only the given origin, such as `https://example.com`, may initiate a
+    /// session. The header echoes the origin of requests which match it, and
+    /// is left out otherwise
+    Exact(String),
+}
+
+impl AllowedOrigin {
+    /// Returns the `Access-Control-Allow-Origin` header value to answer a
+    /// request from `request_origin` with, if any
+    pub fn header_value(&self, request_origin: Option<&str>) -> Option<&str> {
+        match self {
+            Self::Any => Some("*"),
+            Self::Exact(origin) => {
+                if request_origin == Some(origin.as_str()) {
+                    Some(origin.as_str())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::error::Error;
+
+/// Contains the reliability settings used to open the WebRTC data channel,
+/// mirroring the browser's `RTCDataChannelInit` dictionary
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataChannelConfig {
+    ordered: bool,
+    max_retransmits: Option<u16>,
+    max_packet_life_time: Option<u16>,
+}
+
+impl DataChannelConfig {
+    /// Creates a new DataChannelConfig. `max_retransmits` and
+    /// `max_packet_life_time` cannot both be set
+    pub fn new(
+        ordered: bool,
+        max_retransmits: Option<u16>,
+        max_packet_life_time: Option<u16>,
+    ) -> Result<Self, DataChannelConfigError> {
+        if max_retransmits.is_some() && max_packet_life_time.is_some() {
+            return Err(DataChannelConfigError::ConflictingRetransmitLimits);
+        }
+
+        Ok(Self {
+            ordered,
+            max_retransmits,
+            max_packet_life_time,
+        })
+    }
+
+    /// Whether the data channel guarantees in-order delivery of packets
+    pub fn ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// The maximum number of times a lost packet will be retransmitted
+    pub fn max_retransmits(&self) -> Option<u16> {
+        self.max_retransmits
+    }
+
+    /// The maximum time in milliseconds during which a lost packet may be
+    /// retransmitted
+    pub fn max_packet_life_time(&self) -> Option<u16> {
+        self.max_packet_life_time
+    }
+
+    /// Serializes the config into a JSON `RTCDataChannelInit` dictionary
+    pub fn to_init_json(&self) -> String {
+        let mut fields = vec![format!("\"ordered\":{}", self.ordered)];
+        if let Some(max_retransmits) = self.max_retransmits {
+            fields.push(format!("\"maxRetransmits\":{}", max_retransmits));
+        }
+        if let Some(max_packet_life_time) = self.max_packet_life_time {
+            fields.push(format!("\"maxPacketLifeTime\":{}", max_packet_life_time));
+        }
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+impl Default for DataChannelConfig {
+    /// An unordered channel which never retransmits, leaving reliability to
+    /// naia's own channels
+    fn default() -> Self {
+        Self {
+            ordered: false,
+            max_retransmits: Some(0),
+            max_packet_life_time: None,
+        }
+    }
+}
+
+/// Returned when a DataChannelConfig's settings can't be used together
+#[derive(Debug, Eq, PartialEq)]
+pub enum DataChannelConfigError {
+    /// `max_retransmits` and `max_packet_life_time` were both set
+    ConflictingRetransmitLimits,
+}
+impl Error for DataChannelConfigError {}
+impl std::fmt::Display for DataChannelConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            DataChannelConfigError::ConflictingRetransmitLimits => write!(
+                f,
+                "DataChannelConfig: `max_retransmits` and `max_packet_life_time` cannot both be set"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{DataChannelConfig, DataChannelConfigError};
+
+    // Parses an `RTCDataChannelInit` dictionary back into a DataChannelConfig,
+    // as a browser would read it
+    fn from_init_json(json: &str) -> DataChannelConfig {
+        let dictionary: HashMap<String, JsonValue> =
+            json.parse::<JsonValue>().unwrap().try_into().unwrap();
+        let number = |key: &str| {
+            dictionary.get(key).map(|value| {
+                let number: f64 = value.clone().try_into().unwrap();
+                number as u16
+            })
+        };
+        let ordered: bool = dictionary["ordered"].clone().try_into().unwrap();
+        DataChannelConfig::new(
+            ordered,
+            number("maxRetransmits"),
+            number("maxPacketLifeTime"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn configs_round_trip_through_init_json() {
+        for config in [
+            DataChannelConfig::default(),
+            DataChannelConfig::new(true, None, None).unwrap(),
+            DataChannelConfig::new(false, Some(3), None).unwrap(),
+            DataChannelConfig::new(true, None, Some(500)).unwrap(),
+        ] {
+            assert_eq!(from_init_json(&config.to_init_json()), config);
+        }
+    }
+
+    #[test]
+    fn conflicting_retransmit_limits_are_refused() {
+        assert_eq!(
+            DataChannelConfig::new(false, Some(3), Some(500)),
+            Err(DataChannelConfigError::ConflictingRetransmitLimits)
+        );
+    }
+}
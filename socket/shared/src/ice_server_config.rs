@@ -0,0 +1,42 @@
+const DEFAULT_STUN_URL: &str = "stun:stun.l.google.com:19302";
+
+/// Contains a STUN or TURN server used by WebRTC clients to traverse NATs,
+/// mirroring the browser's `RTCIceServer` dictionary
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IceServerConfig {
+    /// The urls of the server, i.e. `stun:stun.example.com:3478` or
+    /// `turn:turn.example.com:3478?transport=udp`
+    pub urls: Vec<String>,
+    /// The username to authenticate with, required for TURN servers
+    pub username: Option<String>,
+    /// The credential to authenticate with, required for TURN servers
+    pub credential: Option<String>,
+}
+
+impl IceServerConfig {
+    /// Creates a new IceServerConfig for a STUN server, which requires no
+    /// authentication
+    pub fn stun(url: &str) -> Self {
+        Self {
+            urls: vec![url.to_string()],
+            username: None,
+            credential: None,
+        }
+    }
+
+    /// Creates a new IceServerConfig for an authenticated TURN relay
+    pub fn turn(url: &str, username: &str, credential: &str) -> Self {
+        Self {
+            urls: vec![url.to_string()],
+            username: Some(username.to_string()),
+            credential: Some(credential.to_string()),
+        }
+    }
+}
+
+impl Default for IceServerConfig {
+    /// A public STUN server
+    fn default() -> Self {
+        Self::stun(DEFAULT_STUN_URL)
+    }
+}
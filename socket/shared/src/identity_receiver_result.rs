@@ -0,0 +1,21 @@
+use crate::{IdentityToken, RejectReason};
+
+/// What a Client has heard back from the Server about its session request
+pub enum IdentityReceiverResult {
+    Waiting,
+    Success(IdentityToken),
+    /// The Server rejected the connection during auth
+    Rejected(RejectReason),
+    ErrorResponseCode(u16),
+}
+
+impl IdentityReceiverResult {
+    /// Maps an unsuccessful session response to either a rejection, if the
+    /// Server gave a reason, or a bare response code
+    pub fn from_error_response(status_code: u16, body: Option<&str>) -> Self {
+        match RejectReason::from_response(status_code, body) {
+            Some(reason) => Self::Rejected(reason),
+            None => Self::ErrorResponseCode(status_code),
+        }
+    }
+}
@@ -16,18 +16,34 @@ extern crate cfg_if;
 /// conditions
 pub mod link_condition_logic;
 
+mod allowed_origin;
 mod backends;
+mod data_channel_config;
+mod ice_server_config;
+mod identity_receiver_result;
 mod identity_token;
 mod link_conditioner_config;
+mod loopback;
+mod queue_overflow_policy;
+mod reject_reason;
 mod socket_config;
 mod time_queue;
+mod transport;
 mod url_parse;
 
+pub use allowed_origin::AllowedOrigin;
 pub use backends::{Instant, Random};
+pub use data_channel_config::{DataChannelConfig, DataChannelConfigError};
+pub use ice_server_config::IceServerConfig;
+pub use identity_receiver_result::IdentityReceiverResult;
 pub use identity_token::*;
 pub use link_conditioner_config::LinkConditionerConfig;
+pub use loopback::{LoopbackClient, LoopbackServer, LoopbackTransport};
+pub use queue_overflow_policy::QueueOverflowPolicy;
+pub use reject_reason::RejectReason;
 pub use socket_config::SocketConfig;
 pub use time_queue::TimeQueue;
+pub use transport::{Transport, UdpPacketKind};
 pub use url_parse::{parse_server_url, url_to_socket_addr};
 
 #[derive(Debug, Eq, PartialEq)]
@@ -7,24 +7,117 @@ use crate::Random;
 /// Given a config object which describes the network conditions to be
 /// simulated, process an incoming packet, adding it to a TimeQueue at the
 /// correct timestamp
-pub fn process_packet<T: Eq>(
+pub fn process_packet<T: Eq + Clone>(
     config: &LinkConditionerConfig,
     time_queue: &mut TimeQueue<T>,
     packet: T,
 ) {
-    if Random::gen_range_f32(0.0, 1.0) <= config.incoming_loss {
+    process_packet_with_random(
+        config,
+        &mut || Random::gen_range_f32(0.0, 1.0),
+        time_queue,
+        packet,
+    );
+}
+
+/// Same as `process_packet()`, but draws every random decision from `random`,
+/// which must return values between 0 and 1. Supplying a seeded source makes
+/// the simulated conditions reproducible
+pub fn process_packet_with_random<T: Eq + Clone>(
+    config: &LinkConditionerConfig,
+    random: &mut dyn FnMut() -> f32,
+    time_queue: &mut TimeQueue<T>,
+    packet: T,
+) {
+    if random() <= config.incoming_loss {
         // drop the packet
         return;
     }
+    let duplicate = random() < config.duplicate_chance;
+    let reorder = random() < config.reorder_chance;
+
     let mut latency: u32 = config.incoming_latency;
     if config.incoming_jitter > 0 {
-        if Random::gen_bool() {
-            latency += Random::gen_range_u32(0, config.incoming_jitter);
+        let jitter = (random() * config.incoming_jitter as f32) as u32;
+        if random() < 0.5 {
+            latency += jitter;
         } else {
-            latency -= Random::gen_range_u32(0, config.incoming_jitter);
+            latency -= jitter;
         }
     }
+    if reorder {
+        // hold the packet back, so that packets received after it overtake it
+        latency += config.incoming_latency.max(1) + config.incoming_jitter;
+    }
+
     let mut packet_timestamp = Instant::now();
     packet_timestamp.add_millis(latency);
+    if duplicate {
+        time_queue.add_item(packet_timestamp.clone(), packet.clone());
+    }
     time_queue.add_item(packet_timestamp, packet);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::process_packet_with_random;
+    use crate::{Instant, LinkConditionerConfig, TimeQueue};
+
+    // Returns the given rolls in order, as a seeded random source would
+    fn rolls(values: &[f32]) -> impl FnMut() -> f32 + '_ {
+        let mut values = values.iter();
+        move || *values.next().expect("ran out of rolls")
+    }
+
+    fn drain(time_queue: &mut TimeQueue<u8>) -> Vec<u8> {
+        let mut later = Instant::now();
+        later.add_millis(10_000);
+        let mut packets = Vec::new();
+        while let Some(packet) = time_queue.pop_item(&later) {
+            packets.push(packet);
+        }
+        packets
+    }
+
+    fn config(reorder_chance: f32, duplicate_chance: f32) -> LinkConditionerConfig {
+        LinkConditionerConfig {
+            reorder_chance,
+            duplicate_chance,
+            ..LinkConditionerConfig::new(10, 0, 0.0)
+        }
+    }
+
+    #[test]
+    fn zero_chances_keep_packets_in_order() {
+        let config = config(0.0, 0.0);
+        let mut time_queue = TimeQueue::new();
+
+        // loss, duplicate, reorder
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.0, 0.0]), &mut time_queue, 1);
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.0, 0.0]), &mut time_queue, 2);
+
+        assert_eq!(drain(&mut time_queue), vec![1, 2]);
+    }
+
+    #[test]
+    fn duplicated_packet_received_twice() {
+        let config = config(0.0, 0.5);
+        let mut time_queue = TimeQueue::new();
+
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.1, 0.9]), &mut time_queue, 1);
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.9, 0.9]), &mut time_queue, 2);
+
+        assert_eq!(drain(&mut time_queue), vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn reordered_packet_overtaken_by_later_packet() {
+        let config = config(0.5, 0.0);
+        let mut time_queue = TimeQueue::new();
+
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.9, 0.1]), &mut time_queue, 1);
+        process_packet_with_random(&config, &mut rolls(&[0.5, 0.9, 0.9]), &mut time_queue, 2);
+
+        assert_eq!(drain(&mut time_queue), vec![2, 1]);
+    }
+}
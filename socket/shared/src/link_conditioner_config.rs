@@ -10,6 +10,13 @@ pub struct LinkConditionerConfig {
     /// The % chance that an incoming packet will be dropped.
     /// Represented as a value between 0 and 1
     pub incoming_loss: f32,
+    /// The % chance that an incoming packet will be held back, so that
+    /// packets received after it are delivered before it.
+    /// Represented as a value between 0 and 1
+    pub reorder_chance: f32,
+    /// The % chance that an incoming packet will be delivered twice.
+    /// Represented as a value between 0 and 1
+    pub duplicate_chance: f32,
 }
 
 impl LinkConditionerConfig {
@@ -19,6 +26,8 @@ impl LinkConditionerConfig {
             incoming_latency,
             incoming_jitter,
             incoming_loss,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -27,6 +36,8 @@ impl LinkConditionerConfig {
             incoming_latency: 1,
             incoming_jitter: 0,
             incoming_loss: 0.0,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -37,6 +48,8 @@ impl LinkConditionerConfig {
             incoming_latency: 12,
             incoming_jitter: 3,
             incoming_loss: 0.001,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -47,6 +60,8 @@ impl LinkConditionerConfig {
             incoming_latency: 40,
             incoming_jitter: 10,
             incoming_loss: 0.002,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -57,6 +72,8 @@ impl LinkConditionerConfig {
             incoming_latency: 100,
             incoming_jitter: 25,
             incoming_loss: 0.02,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -67,6 +84,8 @@ impl LinkConditionerConfig {
             incoming_latency: 200,
             incoming_jitter: 50,
             incoming_loss: 0.04,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 
@@ -77,6 +96,8 @@ impl LinkConditionerConfig {
             incoming_latency: 300,
             incoming_jitter: 75,
             incoming_loss: 0.06,
+            reorder_chance: 0.0,
+            duplicate_chance: 0.0,
         }
     }
 }
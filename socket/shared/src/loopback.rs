@@ -0,0 +1,319 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::{ChannelClosedError, IdentityReceiverResult, IdentityToken, RejectReason};
+
+// Simulated addresses, never bound. Clients are given the ports after the
+// Server's, in the order they connect
+const LOOPBACK_SERVER_PORT: u16 = 1;
+
+/// Connects Clients to a Server within the same process, passing packets
+/// between them through in-memory queues rather than a network socket. Used
+/// for integration tests, and singleplayer or listen-server modes. Cloning
+/// shares the same Server
+#[derive(Clone, Default)]
+pub struct LoopbackTransport {
+    hub: Arc<Mutex<Hub>>,
+}
+
+#[derive(Default)]
+struct Hub {
+    connected_count: u16,
+    // auth bytes of each session request, waiting for the Server to read
+    auth_requests: VecDeque<(SocketAddr, Box<[u8]>)>,
+    to_server: VecDeque<(SocketAddr, Box<[u8]>)>,
+    sessions: HashMap<SocketAddr, Session>,
+}
+
+#[derive(Default)]
+struct Session {
+    // the Server's answer, until the Client reads it
+    answer: Option<IdentityReceiverResult>,
+    accepted: bool,
+    to_client: VecDeque<Box<[u8]>>,
+}
+
+impl LoopbackTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The simulated address of the Server
+    pub fn server_addr(&self) -> SocketAddr {
+        (Ipv4Addr::LOCALHOST, LOOPBACK_SERVER_PORT).into()
+    }
+
+    /// Requests a session with the Server, given a simulated address of its
+    /// own. As with the WebRTC session server, a request without auth bytes
+    /// is refused with a 400 response code
+    pub fn connect(&self, auth_bytes: Option<&[u8]>) -> LoopbackClient {
+        let mut hub = self.lock();
+        hub.connected_count = hub.connected_count.wrapping_add(1);
+        let addr: SocketAddr = (
+            Ipv4Addr::LOCALHOST,
+            LOOPBACK_SERVER_PORT.wrapping_add(hub.connected_count),
+        )
+            .into();
+
+        let mut session = Session::default();
+        match auth_bytes {
+            Some(auth_bytes) => hub.auth_requests.push_back((addr, auth_bytes.into())),
+            None => session.answer = Some(IdentityReceiverResult::ErrorResponseCode(400)),
+        }
+        hub.sessions.insert(addr, session);
+
+        LoopbackClient {
+            transport: self.clone(),
+            addr,
+        }
+    }
+
+    /// The Server's end of the transport
+    pub fn server(&self) -> LoopbackServer {
+        LoopbackServer {
+            transport: self.clone(),
+        }
+    }
+
+    /// Drops every packet not yet received by either end, simulating their
+    /// loss
+    pub fn drop_in_flight(&self) {
+        let mut hub = self.lock();
+        hub.to_server.clear();
+        for session in hub.sessions.values_mut() {
+            session.to_client.clear();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Hub> {
+        self.hub
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A Client's end of a `LoopbackTransport`. Cloning shares the same session
+#[derive(Clone)]
+pub struct LoopbackClient {
+    transport: LoopbackTransport,
+    addr: SocketAddr,
+}
+
+impl LoopbackClient {
+    /// The Client's simulated address, which the Server sees its packets
+    /// coming from
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn server_addr(&self) -> SocketAddr {
+        self.transport.server_addr()
+    }
+
+    /// The Server's answer to the session request, returned once
+    pub fn receive_identity(&self) -> IdentityReceiverResult {
+        self.transport
+            .lock()
+            .sessions
+            .get_mut(&self.addr)
+            .and_then(|session| session.answer.take())
+            .unwrap_or(IdentityReceiverResult::Waiting)
+    }
+
+    /// Sends a packet to the Server, failing once the session has ended
+    pub fn send(&self, payload: &[u8]) -> Result<(), ChannelClosedError<()>> {
+        let mut hub = self.transport.lock();
+        if !hub.sessions.contains_key(&self.addr) {
+            return Err(ChannelClosedError(()));
+        }
+        hub.to_server.push_back((self.addr, payload.into()));
+        Ok(())
+    }
+
+    /// Receives the next packet the Server sent, if any
+    pub fn receive(&self) -> Option<Box<[u8]>> {
+        self.transport
+            .lock()
+            .sessions
+            .get_mut(&self.addr)
+            .and_then(|session| session.to_client.pop_front())
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.transport.lock().sessions.contains_key(&self.addr)
+    }
+
+    /// Ends the session, after which nothing more is sent either way
+    pub fn disconnect(&self) {
+        self.transport.lock().sessions.remove(&self.addr);
+    }
+}
+
+/// The Server's end of a `LoopbackTransport`
+#[derive(Clone)]
+pub struct LoopbackServer {
+    transport: LoopbackTransport,
+}
+
+impl LoopbackServer {
+    /// Receives the next session request's address and auth bytes, if any
+    pub fn receive_auth(&self) -> Option<(SocketAddr, Box<[u8]>)> {
+        self.transport.lock().auth_requests.pop_front()
+    }
+
+    /// Accepts the session requested from the given address, sending the
+    /// Client its IdentityToken
+    pub fn accept(
+        &self,
+        address: &SocketAddr,
+        identity_token: &IdentityToken,
+    ) -> Result<(), ChannelClosedError<()>> {
+        let mut hub = self.transport.lock();
+        let session = hub
+            .sessions
+            .get_mut(address)
+            .ok_or(ChannelClosedError(()))?;
+        session.answer = Some(IdentityReceiverResult::Success(identity_token.clone()));
+        session.accepted = true;
+        Ok(())
+    }
+
+    /// Refuses the session requested from the given address, ending it
+    pub fn reject(
+        &self,
+        address: &SocketAddr,
+        reason: &RejectReason,
+    ) -> Result<(), ChannelClosedError<()>> {
+        let mut hub = self.transport.lock();
+        let session = hub
+            .sessions
+            .get_mut(address)
+            .ok_or(ChannelClosedError(()))?;
+        session.answer = Some(IdentityReceiverResult::Rejected(reason.clone()));
+        // keep the session until the Client reads the answer, but send it
+        // nothing more
+        session.accepted = false;
+        session.to_client.clear();
+        hub.to_server.retain(|(from, _)| from != address);
+        Ok(())
+    }
+
+    /// Receives the next packet sent by a Client, if any
+    pub fn receive(&self) -> Option<(SocketAddr, Box<[u8]>)> {
+        self.transport.lock().to_server.pop_front()
+    }
+
+    /// Sends a packet to the Client at the given address, failing unless its
+    /// session was accepted and has not ended
+    pub fn send(&self, address: &SocketAddr, payload: &[u8]) -> Result<(), ChannelClosedError<()>> {
+        let mut hub = self.transport.lock();
+        match hub.sessions.get_mut(address) {
+            Some(session) if session.accepted => {
+                session.to_client.push_back(payload.into());
+                Ok(())
+            }
+            _ => Err(ChannelClosedError(())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoopbackTransport;
+    use crate::{IdentityReceiverResult, RejectReason};
+
+    #[test]
+    fn accepted_client_exchanges_packets_with_server() {
+        let transport = LoopbackTransport::new();
+        let server = transport.server();
+        let client = transport.connect(Some(b"player"));
+        assert_ne!(client.addr(), client.server_addr());
+
+        let (address, auth_bytes) = server.receive_auth().unwrap();
+        assert_eq!((address, &*auth_bytes), (client.addr(), &b"player"[..]));
+        assert!(matches!(
+            client.receive_identity(),
+            IdentityReceiverResult::Waiting
+        ));
+        // nothing is sent to a Client before it is accepted
+        assert!(server.send(&address, b"too early").is_err());
+
+        server.accept(&address, &"token".to_string()).unwrap();
+        assert!(matches!(
+            client.receive_identity(),
+            IdentityReceiverResult::Success(token) if token == "token"
+        ));
+        assert!(matches!(
+            client.receive_identity(),
+            IdentityReceiverResult::Waiting
+        ));
+
+        client.send(b"ping").unwrap();
+        assert_eq!(
+            server.receive(),
+            Some((client.addr(), b"ping".to_vec().into()))
+        );
+        server.send(&address, b"pong").unwrap();
+        assert_eq!(client.receive(), Some(b"pong".to_vec().into()));
+        assert_eq!(client.receive(), None);
+
+        client.disconnect();
+        assert!(!client.is_connected());
+        assert!(client.send(b"gone").is_err());
+        assert!(server.send(&address, b"gone").is_err());
+    }
+
+    #[test]
+    fn clients_are_given_distinct_addresses() {
+        let transport = LoopbackTransport::new();
+        let first = transport.connect(Some(b""));
+        let second = transport.connect(Some(b""));
+        assert_ne!(first.addr(), second.addr());
+    }
+
+    #[test]
+    fn rejected_and_unauthenticated_clients_are_told_why() {
+        let transport = LoopbackTransport::new();
+        let server = transport.server();
+
+        let client = transport.connect(Some(b"stranger"));
+        let (address, _) = server.receive_auth().unwrap();
+        server.reject(&address, &RejectReason::Banned).unwrap();
+        assert!(matches!(
+            client.receive_identity(),
+            IdentityReceiverResult::Rejected(RejectReason::Banned)
+        ));
+
+        let client = transport.connect(None);
+        assert!(server.receive_auth().is_none());
+        assert!(matches!(
+            client.receive_identity(),
+            IdentityReceiverResult::ErrorResponseCode(400)
+        ));
+    }
+
+    #[test]
+    fn dropped_packets_are_never_received() {
+        let transport = LoopbackTransport::new();
+        let server = transport.server();
+        let client = transport.connect(Some(b""));
+        let (address, _) = server.receive_auth().unwrap();
+        server.accept(&address, &"token".to_string()).unwrap();
+
+        client.send(b"ping").unwrap();
+        server.send(&address, b"pong").unwrap();
+        transport.drop_in_flight();
+        assert_eq!(server.receive(), None);
+        assert_eq!(client.receive(), None);
+
+        // packets sent afterwards still arrive
+        client.send(b"ping").unwrap();
+        assert_eq!(
+            server.receive(),
+            Some((client.addr(), b"ping".to_vec().into()))
+        );
+    }
+}
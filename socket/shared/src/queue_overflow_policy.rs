@@ -0,0 +1,10 @@
+/// What to do with an incoming packet when the queue it would be buffered in
+/// is full
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum QueueOverflowPolicy {
+    /// The oldest buffered packet is dropped to make room
+    #[default]
+    DropOldest,
+    /// The new packet is dropped
+    DropNewest,
+}
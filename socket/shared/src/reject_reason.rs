@@ -0,0 +1,94 @@
+const CUSTOM_PREFIX: &str = "custom:";
+
+/// Why a Server rejected a Client's request to connect, sent to the Client in
+/// the response to its session request
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RejectReason {
+    /// The Client's auth was not accepted
+    Unauthorized,
+    /// The Server has no room for another Client
+    ServerFull,
+    /// The Client is not allowed to connect to the Server
+    Banned,
+    /// An application-defined reason. Clients which are only able to read the
+    /// response's status code, rather than its body, receive `Unauthorized`
+    Custom(String),
+}
+
+impl RejectReason {
+    /// The HTTP status code the session response is sent with
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Unauthorized | Self::Custom(_) => 401,
+            Self::Banned => 403,
+            Self::ServerFull => 503,
+        }
+    }
+
+    /// The body the session response is sent with
+    pub fn to_body(&self) -> String {
+        match self {
+            Self::Unauthorized => "unauthorized".to_string(),
+            Self::ServerFull => "server_full".to_string(),
+            Self::Banned => "banned".to_string(),
+            Self::Custom(message) => format!("{}{}", CUSTOM_PREFIX, message),
+        }
+    }
+
+    /// Reads the RejectReason from a session response, preferring its body
+    /// when available. Returns None for responses which are not rejections
+    pub fn from_response(status_code: u16, body: Option<&str>) -> Option<Self> {
+        if let Some(body) = body {
+            match body {
+                "unauthorized" => return Some(Self::Unauthorized),
+                "server_full" => return Some(Self::ServerFull),
+                "banned" => return Some(Self::Banned),
+                _ => {
+                    if let Some(message) = body.strip_prefix(CUSTOM_PREFIX) {
+                        return Some(Self::Custom(message.to_string()));
+                    }
+                }
+            }
+        }
+        match status_code {
+            401 => Some(Self::Unauthorized),
+            403 => Some(Self::Banned),
+            503 => Some(Self::ServerFull),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RejectReason;
+
+    #[test]
+    fn reasons_round_trip() {
+        for reason in [
+            RejectReason::Unauthorized,
+            RejectReason::ServerFull,
+            RejectReason::Banned,
+            RejectReason::Custom("wrong version".to_string()),
+        ] {
+            let body = reason.to_body();
+            assert_eq!(
+                RejectReason::from_response(reason.status_code(), Some(&body)),
+                Some(reason.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn reasons_read_from_status_code_alone() {
+        assert_eq!(
+            RejectReason::from_response(503, None),
+            Some(RejectReason::ServerFull)
+        );
+        assert_eq!(
+            RejectReason::from_response(403, Some("")),
+            Some(RejectReason::Banned)
+        );
+        assert_eq!(RejectReason::from_response(500, None), None);
+    }
+}
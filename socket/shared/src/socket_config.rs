@@ -1,16 +1,65 @@
 use std::default::Default;
 
-use super::link_conditioner_config::LinkConditionerConfig;
+use super::{
+    allowed_origin::AllowedOrigin, data_channel_config::DataChannelConfig,
+    ice_server_config::IceServerConfig, link_conditioner_config::LinkConditionerConfig,
+    queue_overflow_policy::QueueOverflowPolicy, transport::Transport,
+};
 
 const DEFAULT_RTC_PATH: &str = "rtc_session";
+const DEFAULT_MAX_SESSION_REQUEST_BYTES: usize = 4 * 1024;
+const DEFAULT_RECEIVE_QUEUE_CAPACITY: usize = 4096;
 
 /// Contains Config properties which will be shared by Server and Client sockets
 #[derive(Clone)]
 pub struct SocketConfig {
+    /// How the Server and native Clients exchange packets. Browser clients
+    /// always use WebRTC (or WebTransport)
+    pub transport: Transport,
     /// Configuration used to simulate network conditions
     pub link_condition: Option<LinkConditionerConfig>,
-    /// The endpoint URL path to use for initiating new WebRTC sessions
+    /// The endpoint URL path to use for initiating new WebRTC sessions. Each
+    /// Server answers only its own path, so several Servers may run in one
+    /// process, but each needs its own session & WebRTC ports: one port can't
+    /// yet route Clients to different Protocols
     pub rtc_endpoint_path: String,
+    /// Reliability settings of the WebRTC data channel opened by browser clients
+    pub data_channel: DataChannelConfig,
+    /// STUN & TURN servers used by browser clients to traverse NATs. The
+    /// Server ignores these: it answers as an ICE-lite peer which never
+    /// relays through TURN itself, and is reached at the public addresses
+    /// advertised in its `ServerAddrs`
+    pub ice_servers: Vec<IceServerConfig>,
+    /// Whether the Server should check that WebRTC session request bodies
+    /// look like an SDP offer before handing them to the session endpoint,
+    /// logging the reason for any it rejects
+    pub validate_sdp_offers: bool,
+    /// Largest WebRTC session request body, or single header line, the Server
+    /// will read. Larger requests are answered with an error without being
+    /// buffered
+    pub max_session_request_bytes: usize,
+    /// Which web origins may initiate WebRTC sessions with the Server
+    pub allowed_origin: AllowedOrigin,
+    /// How many received packets a browser client will buffer until they are
+    /// read, so that a stalled game loop can't exhaust memory
+    pub receive_queue_capacity: usize,
+    /// Which packet a browser client drops when its receive queue is full
+    pub receive_queue_overflow: QueueOverflowPolicy,
+    /// The Server's WebTransport address (i.e. "https://127.0.0.1:14193"),
+    /// which browser clients that support WebTransport connect to instead of
+    /// requesting a WebRTC session. Clients fall back to WebRTC if the
+    /// WebTransport session can't be set up. None to only use WebRTC
+    pub webtransport_url: Option<String>,
+    /// SHA-256 hashes of certificates browser clients will accept from the
+    /// WebTransport address without them being signed by a trusted authority,
+    /// for servers using a short-lived self-signed certificate
+    pub webtransport_certificate_hashes: Vec<[u8; 32]>,
+    /// Whether the Server's session server also answers `GET /metrics` with
+    /// its counters in the Prometheus text format. Only enable this when the
+    /// session address isn't public, otherwise use
+    /// `ServerAddrs::enable_metrics()` to serve them on a separate address,
+    /// in which case the session server doesn't serve them
+    pub metrics_enabled: bool,
 }
 
 impl SocketConfig {
@@ -28,8 +77,61 @@ impl SocketConfig {
         };
 
         SocketConfig {
+            transport: Transport::WebRtc,
             link_condition,
             rtc_endpoint_path: endpoint_path,
+            data_channel: DataChannelConfig::default(),
+            ice_servers: vec![IceServerConfig::default()],
+            validate_sdp_offers: false,
+            max_session_request_bytes: DEFAULT_MAX_SESSION_REQUEST_BYTES,
+            allowed_origin: AllowedOrigin::Any,
+            receive_queue_capacity: DEFAULT_RECEIVE_QUEUE_CAPACITY,
+            receive_queue_overflow: QueueOverflowPolicy::DropOldest,
+            webtransport_url: None,
+            webtransport_certificate_hashes: Vec::new(),
+            metrics_enabled: false,
+        }
+    }
+
+    /// Creates a SocketConfig suited to peers on the same local network.
+    ///
+    /// Sets `link_condition` to `None`, `ice_servers` to an empty list (no
+    /// STUN/TURN is needed to reach a LAN address) and `validate_sdp_offers`
+    /// to `false`. All other fields use their defaults.
+    pub fn lan() -> Self {
+        Self {
+            ice_servers: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a SocketConfig suited to clients connecting over the public
+    /// internet.
+    ///
+    /// Sets `link_condition` to `None`, `ice_servers` to the default STUN
+    /// server so browser clients can traverse NATs, and `validate_sdp_offers`
+    /// to `true` so malformed session requests are rejected early. All other
+    /// fields use their defaults.
+    pub fn internet() -> Self {
+        Self {
+            ice_servers: vec![IceServerConfig::default()],
+            validate_sdp_offers: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a SocketConfig for local testing under simulated network
+    /// conditions.
+    ///
+    /// Sets `link_condition` to [`LinkConditionerConfig::average_condition`],
+    /// `ice_servers` to an empty list and `validate_sdp_offers` to `true`.
+    /// All other fields use their defaults.
+    pub fn testing() -> Self {
+        Self {
+            link_condition: Some(LinkConditionerConfig::average_condition()),
+            ice_servers: Vec::new(),
+            validate_sdp_offers: true,
+            ..Self::default()
         }
     }
 }
@@ -37,8 +139,19 @@ impl SocketConfig {
 impl Default for SocketConfig {
     fn default() -> Self {
         Self {
+            transport: Transport::WebRtc,
             link_condition: None,
             rtc_endpoint_path: DEFAULT_RTC_PATH.to_string(),
+            data_channel: DataChannelConfig::default(),
+            ice_servers: vec![IceServerConfig::default()],
+            validate_sdp_offers: false,
+            max_session_request_bytes: DEFAULT_MAX_SESSION_REQUEST_BYTES,
+            allowed_origin: AllowedOrigin::Any,
+            receive_queue_capacity: DEFAULT_RECEIVE_QUEUE_CAPACITY,
+            receive_queue_overflow: QueueOverflowPolicy::DropOldest,
+            webtransport_url: None,
+            webtransport_certificate_hashes: Vec::new(),
+            metrics_enabled: false,
         }
     }
 }
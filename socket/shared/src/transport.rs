@@ -0,0 +1,81 @@
+/// How the Server and native Clients exchange packets
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Transport {
+    /// WebRTC data channels, set up through the Server's session endpoint.
+    /// Required for browser clients
+    #[default]
+    WebRtc,
+    /// Plain UDP datagrams sent straight to the Server's UDP address (see
+    /// `ServerAddrs::enable_udp()`), with
+    /// the session handshake carried in the datagrams themselves. Only native
+    /// Clients can use it, but without the overhead of WebRTC signaling and
+    /// DTLS, i.e. for server-to-server links
+    Udp,
+}
+
+/// The first byte of every datagram sent with `Transport::Udp`, which says
+/// what the rest of it holds
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum UdpPacketKind {
+    /// An application packet, only accepted from a Client once the Server has
+    /// accepted its session
+    Data = 0,
+    /// Sent by a Client, followed by its auth bytes, until the Server answers
+    SessionRequest = 1,
+    /// Sent by the Server, followed by the Client's IdentityToken
+    SessionAccept = 2,
+    /// Sent by the Server, followed by the big-endian status code and body of
+    /// the `RejectReason`
+    SessionReject = 3,
+    /// Sent by a Client as it disconnects, followed by its IdentityToken,
+    /// ending its session
+    Disconnect = 4,
+}
+
+impl UdpPacketKind {
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Data),
+            1 => Some(Self::SessionRequest),
+            2 => Some(Self::SessionAccept),
+            3 => Some(Self::SessionReject),
+            4 => Some(Self::Disconnect),
+            _ => None,
+        }
+    }
+
+    /// A datagram of this kind carrying the given payload
+    pub fn packet(self, payload: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(payload.len() + 1);
+        packet.push(self as u8);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    /// Splits a datagram into its kind and payload
+    pub fn split(datagram: &[u8]) -> Option<(Self, &[u8])> {
+        let (kind, payload) = datagram.split_first()?;
+        Some((Self::from_byte(*kind)?, payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UdpPacketKind;
+
+    #[test]
+    fn udp_packets_split_into_kind_and_payload() {
+        let packet = UdpPacketKind::SessionAccept.packet(b"token");
+        assert_eq!(
+            UdpPacketKind::split(&packet),
+            Some((UdpPacketKind::SessionAccept, &b"token"[..]))
+        );
+        assert_eq!(
+            UdpPacketKind::split(&[0]),
+            Some((UdpPacketKind::Data, &[][..]))
+        );
+        assert_eq!(UdpPacketKind::split(&[]), None);
+        assert_eq!(UdpPacketKind::split(&[9, 1]), None);
+    }
+}
@@ -0,0 +1,223 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, EntityAuthGrantedEvent, EntityAuthResetEvent,
+    Events as ClientEvents, ReplicationConfig as ClientReplicationConfig, SpawnEntityEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, DisconnectEvent as ServerDisconnectEvent, EntityAuthGrantEvent,
+    EntityAuthResetEvent as ServerEntityAuthResetEvent, ReplicationConfig, Server, ServerConfig,
+    UserKey,
+};
+use naia_shared::{EntityAuthStatus, Protocol};
+use naia_test::Auth;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .enable_client_authoritative_entities()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    client: Client<Entity>,
+    client_world: World,
+    user_key: UserKey,
+}
+
+impl Test {
+    // Connects a Client to the Server over a loopback transport
+    fn connect(transport: &LoopbackTransport) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(ServerSocket::new(transport));
+        let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+        client.auth(Auth::new("alice", "secret"));
+        client.connect(ClientSocket::new(transport)).unwrap();
+
+        let mut server_world = World::default();
+        let mut client_world = World::default();
+        let mut user_key = None;
+        for _ in 0..1000 {
+            let mut events = server.receive(server_world.proxy_mut());
+            for (key, _) in events.read::<AuthEvent<Auth>>() {
+                server.accept_connection(&key);
+                user_key = Some(key);
+            }
+            server.send_all_updates(server_world.proxy());
+
+            let mut events = client.receive(client_world.proxy_mut());
+            if events.read::<ClientConnectEvent>().next().is_some() {
+                return Self {
+                    server,
+                    server_world,
+                    client,
+                    client_world,
+                    user_key: user_key.unwrap(),
+                };
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Client never connected to the Server");
+    }
+
+    // Spawns a Delegated Entity on the Server, in scope for the Client,
+    // returning it along with the Client's copy of it once that has spawned
+    fn spawn_delegated(&mut self) -> (Entity, Entity) {
+        let server_entity = self.server.spawn_entity(self.server_world.proxy_mut()).id();
+        let room_key = self.server.make_room().key();
+        self.server
+            .room_mut(&room_key)
+            .add_user(&self.user_key)
+            .add_entity(&server_entity);
+        self.server
+            .user_scope_mut(&self.user_key)
+            .include(&server_entity);
+        self.server
+            .entity_mut(self.server_world.proxy_mut(), &server_entity)
+            .configure_replication(ReplicationConfig::Delegated);
+
+        let mut client_entity = None;
+        self.run_until(|client, events| {
+            for entity in events.read::<SpawnEntityEvent>() {
+                client_entity = Some(entity);
+            }
+            client_entity.is_some_and(|entity| {
+                client.entity_replication_config(&entity)
+                    == Some(ClientReplicationConfig::Delegated)
+            })
+        });
+        (server_entity, client_entity.unwrap())
+    }
+
+    // Runs the Server & Client until the given check passes
+    fn run_until(
+        &mut self,
+        mut check: impl FnMut(&Client<Entity>, &mut ClientEvents<Entity>) -> bool,
+    ) {
+        for _ in 0..400 {
+            self.server.receive(self.server_world.proxy_mut());
+            self.server.send_all_updates(self.server_world.proxy());
+
+            let mut events = self.client.receive(self.client_world.proxy_mut());
+            if check(&self.client, &mut events) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Server & Client never reached the expected state");
+    }
+}
+
+#[test]
+fn granted_authority_reaches_client_and_can_be_revoked() {
+    let transport = LoopbackTransport::new();
+    let mut test = Test::connect(&transport);
+    let (server_entity, client_entity) = test.spawn_delegated();
+
+    test.server
+        .grant_authority(&server_entity, &test.user_key)
+        .unwrap();
+    let mut server_events = test.server.receive(test.server_world.proxy_mut());
+    let grants: Vec<_> = server_events.read::<EntityAuthGrantEvent>().collect();
+    assert!(grants == vec![(test.user_key, server_entity)]);
+
+    let mut granted = false;
+    test.run_until(|client, events| {
+        granted |= events
+            .read::<EntityAuthGrantedEvent>()
+            .any(|e| e == client_entity);
+        granted && client.entity_authority_status(&client_entity) == Some(EntityAuthStatus::Granted)
+    });
+    // the Client's reply to the grant leaves it holding authority
+    let mut ticks = 0;
+    test.run_until(|_, _| {
+        ticks += 1;
+        ticks == 20
+    });
+    assert!(test.server.entity_controlling_user(&server_entity) == Some(test.user_key));
+
+    test.server.revoke_authority(&server_entity).unwrap();
+    assert!(test
+        .server
+        .entity_controlling_user(&server_entity)
+        .is_none());
+
+    let mut reset = false;
+    test.run_until(|client, events| {
+        reset |= events
+            .read::<EntityAuthResetEvent>()
+            .any(|e| e == client_entity);
+        reset && client.entity_authority_status(&client_entity) == Some(EntityAuthStatus::Available)
+    });
+}
+
+#[test]
+fn granting_authority_needs_a_delegated_entity_in_scope() {
+    let transport = LoopbackTransport::new();
+    let mut test = Test::connect(&transport);
+
+    let public_entity = test.server.spawn_entity(test.server_world.proxy_mut()).id();
+    assert!(test
+        .server
+        .grant_authority(&public_entity, &test.user_key)
+        .is_err());
+    assert!(test.server.revoke_authority(&public_entity).is_err());
+
+    // Delegated, but never added to a Room the User is in
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &public_entity)
+        .configure_replication(ReplicationConfig::Delegated);
+    assert!(test
+        .server
+        .grant_authority(&public_entity, &test.user_key)
+        .is_err());
+}
+
+#[test]
+fn disconnecting_releases_granted_authority() {
+    let transport = LoopbackTransport::new();
+    let mut test = Test::connect(&transport);
+    let (server_entity, client_entity) = test.spawn_delegated();
+
+    test.server
+        .grant_authority(&server_entity, &test.user_key)
+        .unwrap();
+    test.run_until(|client, _| {
+        client.entity_authority_status(&client_entity) == Some(EntityAuthStatus::Granted)
+    });
+
+    test.client.disconnect().unwrap();
+    let mut disconnected = false;
+    let mut reset = false;
+    for _ in 0..400 {
+        let mut events = test.server.receive(test.server_world.proxy_mut());
+        disconnected |= events
+            .read::<ServerDisconnectEvent>()
+            .any(|(user_key, _)| user_key == test.user_key);
+        reset |= events
+            .read::<ServerEntityAuthResetEvent>()
+            .any(|entity| entity == server_entity);
+        test.server.send_all_updates(test.server_world.proxy());
+        if disconnected {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(disconnected);
+    assert!(reset);
+
+    // the Entity is Available again, so it may be given to another User
+    assert!(test
+        .server
+        .entity_controlling_user(&server_entity)
+        .is_none());
+    assert_eq!(
+        test.server.entity_authority_status(&server_entity),
+        Some(EntityAuthStatus::Available)
+    );
+}
@@ -0,0 +1,152 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{Channel, ChannelDirection, ChannelMode, Protocol, ReliableSettings};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct ChatChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+// Connects a Client for each username to the Server over a loopback
+// transport, all at once, returning each Client along with the key of its User
+fn connect(
+    transport: &LoopbackTransport,
+    server: &mut Server<Entity>,
+    server_world: &mut World,
+    usernames: &[&str],
+) -> Vec<(Client<Entity>, World, UserKey)> {
+    let mut clients: Vec<_> = usernames
+        .iter()
+        .map(|username| {
+            let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+            client.auth(Auth::new(username, "secret"));
+            client.connect(ClientSocket::new(transport)).unwrap();
+            (client, World::default(), None)
+        })
+        .collect();
+
+    let mut user_keys = Vec::new();
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, auth) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+            user_keys.push((auth.username, key));
+        }
+        server.send_all_updates(server_world.proxy());
+
+        for (username, (client, client_world, user_key)) in usernames.iter().zip(&mut clients) {
+            let mut events = client.receive(client_world.proxy_mut());
+            if events.read::<ClientConnectEvent>().next().is_some() {
+                *user_key = user_keys
+                    .iter()
+                    .find(|(name, _)| name == username)
+                    .map(|(_, key)| *key);
+            }
+        }
+        if clients.iter().all(|(_, _, user_key)| user_key.is_some()) {
+            return clients
+                .into_iter()
+                .map(|(client, world, user_key)| (client, world, user_key.unwrap()))
+                .collect();
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Clients never connected to the Server");
+}
+
+// Runs the Server & Clients until each Client has received the given number
+// of chat messages, returning the usernames of the messages each received
+fn run(
+    server: &mut Server<Entity>,
+    server_world: &mut World,
+    clients: &mut [(Client<Entity>, World, UserKey)],
+    expected_counts: &[usize],
+) -> Vec<Vec<String>> {
+    let mut received = vec![Vec::new(); clients.len()];
+    for _ in 0..200 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+
+        for (index, (client, client_world, _)) in clients.iter_mut().enumerate() {
+            let mut events = client.receive(client_world.proxy_mut());
+            for message in events.read::<MessageEvent<ChatChannel, Auth>>() {
+                received[index].push(message.username);
+            }
+        }
+        let counts: Vec<usize> = received.iter().map(Vec::len).collect();
+        if counts == expected_counts {
+            return received;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!(
+        "Clients never received the expected chat messages, got: {:?}",
+        received
+    );
+}
+
+#[test]
+fn broadcast_reaches_every_user() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    let mut clients = connect(
+        &transport,
+        &mut server,
+        &mut server_world,
+        &["alice", "bob", "charlie"],
+    );
+
+    server.broadcast_message::<ChatChannel, Auth>(&Auth::new("hello", ""));
+
+    let received = run(&mut server, &mut server_world, &mut clients, &[1, 1, 1]);
+    for messages in received {
+        assert_eq!(messages, vec!["hello".to_string()]);
+    }
+}
+
+#[test]
+fn broadcast_except_skips_one_user() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    let mut clients = connect(
+        &transport,
+        &mut server,
+        &mut server_world,
+        &["alice", "bob", "charlie"],
+    );
+
+    // the skipped User is sent a message of their own afterwards, so that
+    // once it arrives, any broadcast they were wrongly sent would have too
+    let sender = clients[1].2;
+    server.broadcast_message_except::<ChatChannel, Auth>(&sender, &Auth::new("bob says", ""));
+    server
+        .send_message::<ChatChannel, Auth>(&sender, &Auth::new("only bob", ""))
+        .unwrap();
+
+    let received = run(&mut server, &mut server_world, &mut clients, &[1, 1, 1]);
+    assert_eq!(received[0], vec!["bob says".to_string()]);
+    assert_eq!(received[1], vec!["only bob".to_string()]);
+    assert_eq!(received[2], vec!["bob says".to_string()]);
+}
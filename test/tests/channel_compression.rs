@@ -0,0 +1,144 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent as ClientMessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, MessageEvent as ServerMessageEvent, Server, ServerConfig,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, CompressionAlgorithm, CompressionSettings, Protocol,
+    ReliableSettings,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct Lz4Channel;
+
+#[derive(Channel)]
+struct ChatChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<Lz4Channel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .add_channel::<ChatChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedReliable(ReliableSettings::default()),
+        )
+        .compress_channel::<Lz4Channel>(CompressionSettings::new(CompressionAlgorithm::Lz4))
+        .compress_channel::<ChatChannel>(CompressionSettings {
+            algorithm: CompressionAlgorithm::Lz4,
+            min_bytes: 16,
+        })
+        .build()
+}
+
+// Connects a Client to a Server over a loopback transport
+fn connect(
+    transport: &LoopbackTransport,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> (Server<Entity>, Client<Entity>) {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (server, client);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn compressed_channels_deliver_messages_to_client() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+    let user_key = server.user_keys()[0];
+
+    // large enough to be fragmented, and compressed one packet at a time
+    let map = "grass,grass,water,".repeat(500);
+    server
+        .send_message::<Lz4Channel, Auth>(&user_key, &Auth::new(&map, "map"))
+        .unwrap();
+    // small enough to be sent uncompressed
+    server
+        .send_message::<Lz4Channel, Auth>(&user_key, &Auth::new("hi", ""))
+        .unwrap();
+    server
+        .send_message::<ChatChannel, Auth>(&user_key, &Auth::new(&map, "map"))
+        .unwrap();
+
+    let mut lz4_received = Vec::new();
+    let mut chat_received = Vec::new();
+    for _ in 0..200 {
+        server.send_all_updates(server_world.proxy());
+        server.receive(server_world.proxy_mut());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for message in events.read::<ClientMessageEvent<Lz4Channel, Auth>>() {
+            lz4_received.push(message.username);
+        }
+        for message in events.read::<ClientMessageEvent<ChatChannel, Auth>>() {
+            chat_received.push(message.username);
+        }
+        if lz4_received.len() == 2 && chat_received.len() == 1 {
+            assert_eq!(lz4_received, vec![map.clone(), "hi".to_string()]);
+            assert_eq!(chat_received, vec![map]);
+            return;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never received the compressed Messages");
+}
+
+#[test]
+fn compressed_channel_delivers_messages_to_server() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+
+    let chat = "gg ".repeat(100);
+    client
+        .send_message::<ChatChannel, Auth>(&Auth::new(&chat, "chat"))
+        .unwrap();
+
+    for _ in 0..200 {
+        client.receive(client_world.proxy_mut());
+
+        let mut events = server.receive(server_world.proxy_mut());
+        if let Some((_, message)) = events
+            .read::<ServerMessageEvent<ChatChannel, Auth>>()
+            .next()
+        {
+            assert_eq!(message.username, chat);
+            assert_eq!(message.password, "chat");
+            return;
+        }
+        server.send_all_updates(server_world.proxy());
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Server never received the compressed Message");
+}
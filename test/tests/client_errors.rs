@@ -0,0 +1,90 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig, ClientError,
+    ConnectEvent as ClientConnectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Server, ServerConfig,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, Message, OverflowStrategy, Protocol, ReliableSettings,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct CommandChannel;
+
+#[derive(Message)]
+struct Ping {
+    index: u8,
+}
+
+fn protocol() -> Protocol {
+    let mut settings = ReliableSettings::default();
+    settings.message_capacity = Some(1);
+    settings.overflow_strategy = OverflowStrategy::Block;
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_message::<Ping>()
+        .add_channel::<CommandChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::OrderedReliable(settings),
+        )
+        .build()
+}
+
+#[test]
+fn client_reports_misuse_as_errors() {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+
+    // there is no connection to close yet
+    assert!(client.server_address().is_err());
+    assert!(matches!(
+        client.disconnect(),
+        Err(ClientError::NotConnected)
+    ));
+
+    client.connect(ClientSocket::new(&transport)).unwrap();
+    assert!(matches!(
+        client.connect(ClientSocket::new(&transport)),
+        Err(ClientError::AlreadyConnected)
+    ));
+
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let mut connected = false;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            connected = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(connected, "Client never connected to the Server");
+
+    // the Channel holds one unacknowledged Message
+    client
+        .send_message::<CommandChannel, _>(&Ping { index: 1 })
+        .unwrap();
+    assert!(matches!(
+        client.send_message::<CommandChannel, _>(&Ping { index: 2 }),
+        Err(ClientError::ChannelFull)
+    ));
+
+    assert!(client.disconnect().is_ok());
+}
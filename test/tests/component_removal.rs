@@ -0,0 +1,192 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, SpawnEntityEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{ComponentKind, Property, Protocol, Replicate, WorldMutType};
+use naia_test::Auth;
+
+#[derive(Replicate)]
+struct Position {
+    x: Property<i32>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    client: Client<Entity>,
+    client_world: World,
+    user_key: UserKey,
+}
+
+impl Test {
+    // Connects a Client to the Server over a loopback transport
+    fn connect(transport: &LoopbackTransport) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(ServerSocket::new(transport));
+        // the Client acks what it receives with its heartbeats, so sends
+        // them often
+        let mut client_config = ClientConfig::default();
+        client_config.connection.heartbeat_interval = Duration::from_millis(10);
+        let mut client = Client::<Entity>::new(client_config, protocol());
+        client.auth(Auth::new("alice", "secret"));
+        client.connect(ClientSocket::new(transport)).unwrap();
+
+        let mut server_world = World::default();
+        let mut client_world = World::default();
+        let mut user_key = None;
+        for _ in 0..1000 {
+            let mut events = server.receive(server_world.proxy_mut());
+            for (key, _) in events.read::<AuthEvent<Auth>>() {
+                server.accept_connection(&key);
+                user_key = Some(key);
+            }
+            server.send_all_updates(server_world.proxy());
+
+            let mut events = client.receive(client_world.proxy_mut());
+            if events.read::<ClientConnectEvent>().next().is_some() {
+                return Self {
+                    server,
+                    server_world,
+                    client,
+                    client_world,
+                    user_key: user_key.unwrap(),
+                };
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Client never connected to the Server");
+    }
+
+    // Spawns an Entity on the Server, in scope for the Client, returning it
+    // along with the Client's copy of it once that has spawned
+    fn spawn(&mut self) -> (Entity, Entity) {
+        let server_entity = self.server.spawn_entity(self.server_world.proxy_mut()).id();
+        let room_key = self.server.make_room().key();
+        self.server
+            .room_mut(&room_key)
+            .add_user(&self.user_key)
+            .add_entity(&server_entity);
+        self.server
+            .user_scope_mut(&self.user_key)
+            .include(&server_entity);
+
+        let mut client_entity = None;
+        self.run_until(|test| {
+            client_entity = client_entity.or(test.spawned.first().copied());
+            client_entity.is_some()
+        });
+        // let the Server read the Client's ack of the spawn
+        for _ in 0..10 {
+            self.step();
+            thread::sleep(Duration::from_millis(5));
+        }
+        (server_entity, client_entity.unwrap())
+    }
+
+    fn insert_position(&mut self, entity: &Entity, x: i32) {
+        let mut component: Box<dyn Replicate> = Box::new(Position::new_complete(x));
+        self.server
+            .insert_component_worldless(entity, component.as_mut());
+        self.server_world
+            .proxy_mut()
+            .insert_boxed_component(entity, component);
+    }
+
+    fn remove_position(&mut self, entity: &Entity) {
+        let kind = ComponentKind::of::<Position>();
+        self.server.remove_component_worldless(entity, &kind);
+        self.server_world
+            .proxy_mut()
+            .remove_component_of_kind(entity, &kind);
+    }
+
+    // Runs the Server & Client once, returning the Entities spawned on the Client
+    fn step(&mut self) -> Vec<Entity> {
+        self.server.receive(self.server_world.proxy_mut());
+        self.server.send_all_updates(self.server_world.proxy());
+
+        let mut events = self.client.receive(self.client_world.proxy_mut());
+        events.read::<SpawnEntityEvent>().collect()
+    }
+
+    // Runs the Server & Client until the given check passes
+    fn run_until(&mut self, mut check: impl FnMut(&Step) -> bool) {
+        for _ in 0..400 {
+            let spawned = self.step();
+            let step = Step {
+                spawned,
+                client: &self.client,
+                client_world: &self.client_world,
+            };
+            if check(&step) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Server & Client never reached the expected state");
+    }
+}
+
+struct Step<'t> {
+    spawned: Vec<Entity>,
+    client: &'t Client<Entity>,
+    client_world: &'t World,
+}
+
+impl Step<'_> {
+    fn position(&self, entity: &Entity) -> Option<i32> {
+        let entity_ref = self.client.entity(self.client_world.proxy(), entity);
+        let position = entity_ref.component_of_kind(&ComponentKind::of::<Position>())?;
+        let x = *position.to_any().downcast_ref::<Position>()?.x;
+        Some(x)
+    }
+}
+
+#[test]
+fn component_removed_before_its_insert_is_resent_can_be_inserted_again() {
+    let transport = LoopbackTransport::new();
+    let mut test = Test::connect(&transport);
+    let (server_entity, client_entity) = test.spawn();
+
+    // the insert is lost, and the Component is gone before it's resent
+    test.insert_position(&server_entity, 1);
+    test.server.send_all_updates(test.server_world.proxy());
+    transport.drop_in_flight();
+    test.remove_position(&server_entity);
+    // long enough for the lost insert to be resent
+    for _ in 0..200 {
+        test.step();
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    test.insert_position(&server_entity, 2);
+    test.run_until(|step| step.position(&client_entity) == Some(2));
+}
+
+#[test]
+fn component_removed_while_its_insert_is_in_flight_is_removed() {
+    let transport = LoopbackTransport::new();
+    let mut test = Test::connect(&transport);
+    let (server_entity, client_entity) = test.spawn();
+
+    test.insert_position(&server_entity, 1);
+    test.run_until(|step| step.position(&client_entity).is_some());
+
+    // the insert has been received, but the Server hasn't read its ack yet
+    test.remove_position(&server_entity);
+    test.run_until(|step| step.position(&client_entity).is_none());
+}
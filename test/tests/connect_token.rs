@@ -0,0 +1,147 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, RejectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, ConnectToken, ConnectTokenConfig, Server, ServerConfig,
+};
+use naia_shared::{Protocol, RejectReason};
+use naia_test::Auth;
+
+const KEY: &[u8] = b"a key shared with the game backend";
+
+fn protocol() -> Protocol {
+    Protocol::builder().add_message::<Auth>().build()
+}
+
+fn server(transport: &LoopbackTransport) -> Server<Entity> {
+    let server_config = ServerConfig {
+        connect_token: Some(ConnectTokenConfig {
+            key: KEY.to_vec(),
+            server_addr: transport.server_addr(),
+        }),
+        ..Default::default()
+    };
+    let mut server = Server::new(server_config, protocol());
+    server.listen(ServerSocket::new(transport));
+    server
+}
+
+fn signed_token(transport: &LoopbackTransport, key: &[u8], expires_in: u64) -> Vec<u8> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    ConnectToken {
+        client_id: 42,
+        expires_at: now + expires_in,
+        server_addr: transport.server_addr(),
+    }
+    .sign(key)
+}
+
+// Runs the Server & Client until the Client hears back. The Server app never
+// answers auth itself
+fn run(client: &mut Client<Entity>, server: &mut Server<Entity>) -> Result<(), RejectReason> {
+    let mut client_world = World::default();
+    let mut server_world = World::default();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        assert_eq!(events.read::<AuthEvent<Auth>>().count(), 0);
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if let Some(reason) = events.read::<RejectEvent>().next() {
+            return Err(reason);
+        }
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never heard back from the Server");
+}
+
+#[test]
+fn client_with_signed_token_is_accepted_without_auth_event() {
+    let transport = LoopbackTransport::new();
+    let mut server = server(&transport);
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.connect_token(signed_token(&transport, KEY, 30));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+
+    assert_eq!(run(&mut client, &mut server), Ok(()));
+    let user_keys = server.user_keys();
+    assert_eq!(user_keys.len(), 1);
+    assert_eq!(server.user(&user_keys[0]).client_id(), Some(42));
+}
+
+#[test]
+fn client_with_forged_token_is_rejected() {
+    let transport = LoopbackTransport::new();
+    let mut server = server(&transport);
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.connect_token(signed_token(&transport, b"a guessed key", 30));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+
+    assert_eq!(
+        run(&mut client, &mut server),
+        Err(RejectReason::Unauthorized)
+    );
+    assert_eq!(server.users_count(), 0);
+}
+
+#[test]
+fn replayed_token_is_rejected() {
+    let transport = LoopbackTransport::new();
+    let mut server = server(&transport);
+    let token = signed_token(&transport, KEY, 30);
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.connect_token(token.clone());
+    client.connect(ClientSocket::new(&transport)).unwrap();
+    assert_eq!(run(&mut client, &mut server), Ok(()));
+
+    // the same token, captured & presented by another Client
+    let mut replaying_client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    replaying_client.connect_token(token);
+    replaying_client
+        .connect(ClientSocket::new(&transport))
+        .unwrap();
+    assert_eq!(
+        run(&mut replaying_client, &mut server),
+        Err(RejectReason::Unauthorized)
+    );
+    assert_eq!(server.users_count(), 1);
+}
+
+#[test]
+fn token_for_connected_client_id_is_rejected() {
+    let transport = LoopbackTransport::new();
+    let mut server = server(&transport);
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.connect_token(signed_token(&transport, KEY, 30));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+    assert_eq!(run(&mut client, &mut server), Ok(()));
+
+    // a different token, for the same client id
+    let mut other_client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    other_client.connect_token(signed_token(&transport, KEY, 60));
+    other_client.connect(ClientSocket::new(&transport)).unwrap();
+    assert_eq!(
+        run(&mut other_client, &mut server),
+        Err(RejectReason::Unauthorized)
+    );
+    assert_eq!(server.users_count(), 1);
+}
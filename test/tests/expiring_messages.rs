@@ -0,0 +1,127 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, MessageExpiredEvent, Server, ServerConfig,
+};
+use naia_shared::{Channel, ChannelDirection, ChannelMode, Protocol, ReliableSettings};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct ExpiringChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<ExpiringChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::ExpiringReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+// Connects a Client to a Server over a loopback transport
+fn connect(
+    transport: &LoopbackTransport,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> (Server<Entity>, Client<Entity>) {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (server, client);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn undelivered_message_expires() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, _client) = connect(&transport, &mut server_world, &mut client_world);
+    let user_key = server.user_keys()[0];
+
+    server
+        .send_message_with_ttl::<ExpiringChannel, Auth>(
+            &user_key,
+            &Auth::new("late", "order"),
+            Duration::from_millis(50),
+        )
+        .unwrap();
+
+    // the Client never receives, so the Message is never acknowledged
+    for _ in 0..100 {
+        server.send_all_updates(server_world.proxy());
+        let mut events = server.receive(server_world.proxy_mut());
+        if let Some((expired_user_key, message)) = events
+            .read::<MessageExpiredEvent<ExpiringChannel, Auth>>()
+            .next()
+        {
+            assert_eq!(expired_user_key, user_key);
+            assert_eq!(message.username, "late");
+            return;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Message never expired");
+}
+
+#[test]
+fn delivered_message_does_not_expire() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+    let user_key = server.user_keys()[0];
+
+    server
+        .send_message_with_ttl::<ExpiringChannel, Auth>(
+            &user_key,
+            &Auth::new("prompt", "order"),
+            Duration::from_millis(200),
+        )
+        .unwrap();
+
+    let mut delivered = false;
+    for _ in 0..60 {
+        server.send_all_updates(server_world.proxy());
+        let events = server.receive(server_world.proxy_mut());
+        assert!(!events.has::<MessageExpiredEvent<ExpiringChannel, Auth>>());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events
+            .read::<MessageEvent<ExpiringChannel, Auth>>()
+            .next()
+            .is_some()
+        {
+            delivered = true;
+            // replying acknowledges the Message sooner than a heartbeat would
+            client
+                .send_message::<ExpiringChannel, Auth>(&Auth::new("reply", ""))
+                .unwrap();
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(delivered);
+}
@@ -0,0 +1,188 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, DisconnectEvent as ClientDisconnectEvent,
+    FileTransferCompleteEvent as ClientFileTransferCompleteEvent,
+    FileTransferProgressEvent as ClientFileTransferProgressEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, FileTransferCompleteEvent, FileTransferProgressEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::Protocol;
+use naia_test::Auth;
+
+fn protocol() -> Protocol {
+    Protocol::builder().add_message::<Auth>().build()
+}
+
+fn level_data() -> Vec<u8> {
+    (0..40_000).map(|i| (i % 251) as u8).collect()
+}
+
+// Connects the Client to the Server over a loopback transport, returning the
+// key of the new User
+fn connect(
+    transport: &LoopbackTransport,
+    server: &mut Server<Entity>,
+    client: &mut Client<Entity>,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> UserKey {
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    let mut user_key = None;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+            user_key = Some(key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return user_key.unwrap();
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn file_is_transferred_in_chunks() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    let user_key = connect(
+        &transport,
+        &mut server,
+        &mut client,
+        &mut server_world,
+        &mut client_world,
+    );
+
+    let data = level_data();
+    let transfer_id = server.send_file(&user_key, "level", data.clone()).unwrap();
+
+    let mut progress_updates = 0;
+    let mut received_file = None;
+    let mut server_completed = false;
+    for _ in 0..2000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        progress_updates += events.read::<FileTransferProgressEvent>().count();
+        for (completed_user_key, completed_id) in events.read::<FileTransferCompleteEvent>() {
+            assert_eq!(completed_user_key, user_key);
+            assert_eq!(completed_id, transfer_id);
+            server_completed = true;
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if let Some((name, file)) = events.read::<ClientFileTransferCompleteEvent>().next() {
+            assert_eq!(name, "level");
+            received_file = Some(file);
+        }
+        if received_file.is_some() && server_completed {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert_eq!(received_file, Some(data));
+    assert!(server_completed);
+    assert!(progress_updates > 1);
+}
+
+#[test]
+fn interrupted_transfer_resumes_after_reconnect() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    // the Client only notices the Server dropping it once it times out
+    let mut client_config = ClientConfig::default();
+    client_config.connection.disconnection_timeout_duration = Duration::from_millis(500);
+    let mut client = Client::<Entity>::new(client_config, protocol());
+    let user_key = connect(
+        &transport,
+        &mut server,
+        &mut client,
+        &mut server_world,
+        &mut client_world,
+    );
+
+    let data = level_data();
+    server.send_file(&user_key, "level", data.clone()).unwrap();
+
+    // receive part of the file
+    let mut received_before_disconnect = 0;
+    for _ in 0..2000 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for (_, received, _) in events.read::<ClientFileTransferProgressEvent>() {
+            received_before_disconnect = received;
+        }
+        assert!(!events.has::<ClientFileTransferCompleteEvent>());
+        if received_before_disconnect > 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(received_before_disconnect > 0);
+
+    server
+        .user_mut(&user_key)
+        .disconnect(server_world.proxy_mut());
+    let mut client_disconnected = false;
+    for _ in 0..1000 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientDisconnectEvent>().next().is_some() {
+            client_disconnected = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(client_disconnected);
+
+    // offering the same file again resumes where the Client left off
+    let user_key = connect(
+        &transport,
+        &mut server,
+        &mut client,
+        &mut server_world,
+        &mut client_world,
+    );
+    server.send_file(&user_key, "level", data.clone()).unwrap();
+
+    let mut resumed_from = None;
+    let mut received_file = None;
+    for _ in 0..2000 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for (_, received, _) in events.read::<ClientFileTransferProgressEvent>() {
+            resumed_from.get_or_insert(received);
+        }
+        if let Some((_, file)) = events.read::<ClientFileTransferCompleteEvent>().next() {
+            received_file = Some(file);
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert!(resumed_from.unwrap() >= received_before_disconnect);
+    assert_eq!(received_file, Some(data));
+}
@@ -0,0 +1,97 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Server, ServerConfig,
+};
+use naia_shared::{Channel, ChannelDirection, ChannelMode, Protocol, ReliableSettings};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct BlobChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<BlobChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .set_max_message_size::<BlobChannel>(16 * 1024)
+        .build()
+}
+
+// Connects a Client to a Server over a loopback transport
+fn connect(
+    transport: &LoopbackTransport,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> (Server<Entity>, Client<Entity>) {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (server, client);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn large_message_is_fragmented_and_reassembled() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+    let user_key = server.user_keys()[0];
+
+    let blob = "x".repeat(8 * 1024);
+    server
+        .send_message::<BlobChannel, Auth>(&user_key, &Auth::new(&blob, "map"))
+        .unwrap();
+
+    for _ in 0..200 {
+        server.send_all_updates(server_world.proxy());
+        server.receive(server_world.proxy_mut());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if let Some(message) = events.read::<MessageEvent<BlobChannel, Auth>>().next() {
+            assert_eq!(message.username, blob);
+            assert_eq!(message.password, "map");
+            return;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never received the large Message");
+}
+
+#[test]
+fn message_above_max_message_size_is_refused() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, _client) = connect(&transport, &mut server_world, &mut client_world);
+    let user_key = server.user_keys()[0];
+
+    let blob = "x".repeat(32 * 1024);
+    let result = server.send_message::<BlobChannel, Auth>(&user_key, &Auth::new(&blob, "map"));
+    assert!(result.is_err());
+}
@@ -0,0 +1,188 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, MessageEvent, Server, ServerConfig, SpawnEntityEvent,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, EntityProperty, Message, Protocol, ReliableSettings,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct CommandChannel;
+
+#[derive(Message)]
+struct Select {
+    entity: EntityProperty,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_message::<Select>()
+        .add_channel::<CommandChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .enable_client_authoritative_entities()
+        .build()
+}
+
+// Connects a new Client to the Server over a loopback transport
+fn connect(
+    transport: &LoopbackTransport,
+    server: &mut Server<Entity>,
+    server_world: &mut World,
+) -> (Client<Entity>, World) {
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+    let mut client_world = World::default();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (client, client_world);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+// Runs the Server & Client for a while, returning the payloads of the
+// commands the Server received
+fn run(
+    server: &mut Server<Entity>,
+    server_world: &mut World,
+    client: &mut Client<Entity>,
+    client_world: &mut World,
+) -> Vec<String> {
+    let mut received = Vec::new();
+    for _ in 0..100 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (_, command) in events.read::<MessageEvent<CommandChannel, Auth>>() {
+            received.push(command.username);
+        }
+        server.send_all_updates(server_world.proxy());
+        client.receive(client_world.proxy_mut());
+        thread::sleep(Duration::from_millis(5));
+    }
+    received
+}
+
+fn server() -> (LoopbackTransport, Server<Entity>, World) {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    (transport, server, World::default())
+}
+
+#[test]
+fn retried_keyed_message_is_processed_once() {
+    let (transport, mut server, mut server_world) = server();
+    let (mut client, mut client_world) = connect(&transport, &mut server, &mut server_world);
+
+    client
+        .send_message_with_key::<CommandChannel, Auth>(7, &Auth::new("jump", ""))
+        .unwrap();
+    client
+        .send_message_with_key::<CommandChannel, Auth>(7, &Auth::new("jump", ""))
+        .unwrap();
+    client
+        .send_message_with_key::<CommandChannel, Auth>(8, &Auth::new("duck", ""))
+        .unwrap();
+    client
+        .send_message::<CommandChannel, Auth>(&Auth::new("unkeyed", ""))
+        .unwrap();
+    client
+        .send_message::<CommandChannel, Auth>(&Auth::new("unkeyed", ""))
+        .unwrap();
+
+    let received = run(
+        &mut server,
+        &mut server_world,
+        &mut client,
+        &mut client_world,
+    );
+    assert_eq!(received, vec!["jump", "duck", "unkeyed", "unkeyed"]);
+}
+
+#[test]
+fn keyed_message_retried_after_reconnecting_is_dropped() {
+    let (transport, mut server, mut server_world) = server();
+    let (mut client, mut client_world) = connect(&transport, &mut server, &mut server_world);
+    client
+        .send_message_with_key::<CommandChannel, Auth>(7, &Auth::new("jump", ""))
+        .unwrap();
+    let received = run(
+        &mut server,
+        &mut server_world,
+        &mut client,
+        &mut client_world,
+    );
+    assert_eq!(received, vec!["jump"]);
+
+    client.disconnect().unwrap();
+    let (mut client, mut client_world) = connect(&transport, &mut server, &mut server_world);
+    client
+        .send_message_with_key::<CommandChannel, Auth>(7, &Auth::new("jump", ""))
+        .unwrap();
+    client
+        .send_message_with_key::<CommandChannel, Auth>(8, &Auth::new("duck", ""))
+        .unwrap();
+    let received = run(
+        &mut server,
+        &mut server_world,
+        &mut client,
+        &mut client_world,
+    );
+    assert_eq!(received, vec!["duck"]);
+}
+
+#[test]
+fn keyed_message_waits_for_the_entity_it_references() {
+    let (transport, mut server, mut server_world) = server();
+    let (mut client, mut client_world) = connect(&transport, &mut server, &mut server_world);
+
+    // the message is sent before the Server has heard of the Entity
+    let client_entity = client.spawn_entity(client_world.proxy_mut()).id();
+    let mut select = Select {
+        entity: EntityProperty::new(),
+    };
+    select.entity.set(&client, &client_entity);
+    client
+        .send_message_with_key::<CommandChannel, Select>(7, &select)
+        .unwrap();
+
+    let mut spawned = None;
+    let mut selected = None;
+    for _ in 0..100 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (_, entity) in events.read::<SpawnEntityEvent>() {
+            spawned = Some(entity);
+        }
+        for (_, select) in events.read::<MessageEvent<CommandChannel, Select>>() {
+            selected = Some(select.entity.get(&server));
+        }
+        server.send_all_updates(server_world.proxy());
+        client.receive(client_world.proxy_mut());
+        if selected.is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    let spawned = spawned.expect("Server never received the Entity");
+    assert!(selected == Some(Some(spawned)));
+}
@@ -0,0 +1,121 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, AuthenticatedEvent, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, RejectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, ConnectEvent as ServerConnectEvent, Server, ServerConfig,
+};
+use naia_shared::{Protocol, RejectReason};
+use naia_test::Auth;
+
+fn protocol() -> Protocol {
+    Protocol::builder().add_message::<Auth>().build()
+}
+
+// Runs the Server & Client until the Client hears back, accepting Clients
+// whose password is "secret"
+fn run(client: &mut Client<Entity>, server: &mut Server<Entity>) -> Result<(), RejectReason> {
+    let mut client_world = World::default();
+    let mut server_world = World::default();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, auth) in events.read::<AuthEvent<Auth>>() {
+            if auth.password == "secret" {
+                server.accept_connection(&user_key);
+            } else {
+                server.reject_connection_with_reason(&user_key, RejectReason::Banned);
+            }
+        }
+        for user_key in events.read::<ServerConnectEvent>() {
+            assert!(server.user_exists(&user_key));
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if let Some(reason) = events.read::<RejectEvent>().next() {
+            return Err(reason);
+        }
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never heard back from the Server");
+}
+
+#[test]
+fn client_connects_to_server_in_same_process() {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+
+    assert_eq!(run(&mut client, &mut server), Ok(()));
+    assert_eq!(server.users_count(), 1);
+
+    let user_key = server.user_keys()[0];
+    let server_stats = server.connection_stats(&user_key).unwrap();
+    let client_stats = client.connection_stats().unwrap();
+    assert!(client_stats.bytes_out > 0 && client_stats.bytes_in > 0);
+    assert!(server_stats.bytes_out > 0);
+    assert_eq!(server_stats.loss, 0.0);
+    assert!(server_stats.bytes_out_per_second > 0.0);
+    assert_eq!(server_stats.queued_messages, 0);
+    assert_eq!(server_stats.update_backlog, 0);
+}
+
+#[test]
+fn client_rejected_by_server_in_same_process() {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("mallory", "guess"));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+
+    assert_eq!(run(&mut client, &mut server), Err(RejectReason::Banned));
+    assert_eq!(server.users_count(), 0);
+}
+
+#[test]
+fn client_is_issued_an_identity_token_before_connecting() {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+    assert!(client.identity_token().is_none());
+
+    let mut client_world = World::default();
+    let mut server_world = World::default();
+    let mut identity_tokens = Vec::new();
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        identity_tokens.extend(events.read::<AuthenticatedEvent>());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert_eq!(identity_tokens.len(), 1);
+    assert_eq!(client.identity_token(), identity_tokens.pop());
+    assert!(client.connection_status().is_connected());
+}
@@ -0,0 +1,163 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent as ClientMessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::{
+        loopback::{LoopbackTransport, Socket as LoopbackSocket},
+        AuthReceiver, AuthSender, MetricsSink, PacketReceiver, PacketSender,
+        Socket as TransportSocket,
+    },
+    AuthEvent, ChannelMetrics, MessageEvent, Server, ServerConfig, ServerMetrics,
+};
+use naia_shared::{Channel, ChannelDirection, ChannelMode, Protocol, ReliableSettings};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct ChatChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .name_channel::<ChatChannel>("chat")
+        .build()
+}
+
+// Keeps the last metrics the Server published
+#[derive(Clone, Default)]
+struct RecordedMetrics(Arc<Mutex<Option<ServerMetrics>>>);
+
+impl MetricsSink for RecordedMetrics {
+    fn record(&self, metrics: &ServerMetrics) {
+        *self.0.lock().unwrap() = Some(metrics.clone());
+    }
+}
+
+// A loopback transport which serves metrics
+struct MeteredSocket {
+    inner: LoopbackSocket,
+    metrics: RecordedMetrics,
+}
+
+impl From<MeteredSocket> for Box<dyn TransportSocket> {
+    fn from(socket: MeteredSocket) -> Self {
+        Box::new(socket)
+    }
+}
+
+impl TransportSocket for MeteredSocket {
+    fn listen(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn AuthSender>,
+        Box<dyn AuthReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        Box::new(self.inner).listen()
+    }
+
+    fn metrics_sink(&self) -> Option<Box<dyn MetricsSink>> {
+        Some(Box::new(self.metrics.clone()))
+    }
+}
+
+#[test]
+fn server_publishes_its_own_metrics_to_the_transport() {
+    let transport = LoopbackTransport::new();
+    let recorded = RecordedMetrics::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(MeteredSocket {
+        inner: LoopbackSocket::new(&transport),
+        metrics: recorded.clone(),
+    });
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("alice", "secret"));
+    client.connect(ClientSocket::new(&transport)).unwrap();
+    let mut client_world = World::default();
+
+    let mut user_key = None;
+    let mut connected = false;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+            user_key = Some(key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            connected = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(connected);
+    let user_key = user_key.unwrap();
+
+    for text in ["hi", "there"] {
+        client
+            .send_message::<ChatChannel, Auth>(&Auth::new(text, ""))
+            .unwrap();
+    }
+    server
+        .send_message::<ChatChannel, Auth>(&user_key, &Auth::new("welcome", ""))
+        .unwrap();
+
+    let mut server_received = 0;
+    let mut client_received = 0;
+    for _ in 0..200 {
+        let mut events = server.receive(server_world.proxy_mut());
+        server_received += events.read::<MessageEvent<ChatChannel, Auth>>().count();
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        client_received += events
+            .read::<ClientMessageEvent<ChatChannel, Auth>>()
+            .count();
+        if server_received == 2 && client_received == 1 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!((server_received, client_received), (2, 1));
+    server.receive(server_world.proxy_mut());
+
+    let metrics = recorded
+        .0
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("Server never published its metrics");
+    assert_eq!(metrics, server.metrics());
+    assert_eq!(metrics.active_users, 1);
+    assert_eq!(metrics.handshake, server.handshake_metrics());
+    assert_eq!(metrics.handshake.validate_successes, 1);
+    assert_eq!(metrics.packets_dropped, 0);
+    let chat = metrics
+        .channels
+        .iter()
+        .find(|channel| channel.channel == "chat");
+    assert_eq!(
+        chat,
+        Some(&ChannelMetrics {
+            channel: "chat".to_string(),
+            messages_sent: 1,
+            messages_received: 2,
+        })
+    );
+}
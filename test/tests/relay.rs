@@ -0,0 +1,253 @@
+use std::{thread, time::Duration};
+
+use naia_client::{transport::loopback::Socket as ClientSocket, Client, ClientConfig};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Relay, RoomKey, Server, ServerConfig, UserKey,
+};
+use naia_shared::{ComponentKind, EntityProperty, Property, Protocol, Replicate, WorldMutType};
+use naia_test::Auth;
+
+#[derive(Replicate)]
+struct Position {
+    x: Property<i32>,
+}
+
+#[derive(Replicate)]
+struct Follow {
+    target: EntityProperty,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .add_component::<Follow>()
+        .build()
+}
+
+// A Server which puts every User it accepts into one Room, in scope of
+// every Entity in it
+struct Shard {
+    server: Server<Entity>,
+    world: World,
+    room_key: RoomKey,
+    user_key: Option<UserKey>,
+}
+
+impl Shard {
+    fn listen(transport: &LoopbackTransport) -> Self {
+        let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+        server.listen(ServerSocket::new(transport));
+        let room_key = server.make_room().key();
+        Self {
+            server,
+            world: World::default(),
+            room_key,
+            user_key: None,
+        }
+    }
+
+    fn update(&mut self) {
+        let mut events = self.server.receive(self.world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            self.server.accept_connection(&user_key);
+            self.server.room_mut(&self.room_key).add_user(&user_key);
+            self.user_key = Some(user_key);
+        }
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+    }
+}
+
+// An upstream Server, a Server relaying its Entities, and a Client of the
+// relaying Server
+struct Test {
+    upstream: Shard,
+    relay: Relay<Entity>,
+    relay_world: World,
+    shard: Shard,
+    client: Client<Entity>,
+    client_world: World,
+}
+
+impl Test {
+    fn connect() -> Self {
+        let upstream_transport = LoopbackTransport::new();
+        let upstream = Shard::listen(&upstream_transport);
+        let mut relay = Relay::<Entity>::new(ClientConfig::default(), protocol());
+        relay.auth(Auth::new("shard", "secret"));
+        relay
+            .connect(ClientSocket::new(&upstream_transport))
+            .unwrap();
+
+        let transport = LoopbackTransport::new();
+        let shard = Shard::listen(&transport);
+        let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+        client.auth(Auth::new("alice", "secret"));
+        client.connect(ClientSocket::new(&transport)).unwrap();
+
+        let mut test = Self {
+            upstream,
+            relay,
+            relay_world: World::default(),
+            shard,
+            client,
+            client_world: World::default(),
+        };
+        let mut connected = false;
+        test.run_until(|test| {
+            connected |= test.upstream.user_key.is_some() && test.shard.user_key.is_some();
+            connected
+        });
+        test
+    }
+
+    fn step(&mut self) {
+        self.upstream.update();
+
+        let mut events = self.relay.receive(self.relay_world.proxy_mut());
+        let spawned = self.relay.mirror_entities(
+            &mut self.shard.server,
+            &mut events,
+            self.relay_world.proxy(),
+            self.shard.world.proxy_mut(),
+        );
+        for entity in spawned {
+            self.shard
+                .server
+                .room_mut(&self.shard.room_key)
+                .add_entity(&entity);
+        }
+        self.shard.update();
+
+        self.client.receive(self.client_world.proxy_mut());
+    }
+
+    fn run_until(&mut self, mut check: impl FnMut(&mut Self) -> bool) {
+        for _ in 0..1000 {
+            self.step();
+            if check(self) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Servers & Client never reached the expected state");
+    }
+
+    // Spawns an Entity on the upstream Server, in scope for the relaying
+    // Server
+    fn spawn_upstream(&mut self, component: impl Replicate) -> Entity {
+        let upstream = &mut self.upstream;
+        let entity = upstream
+            .server
+            .spawn_entity(upstream.world.proxy_mut())
+            .id();
+        let mut component: Box<dyn Replicate> = Box::new(component);
+        upstream
+            .server
+            .insert_component_worldless(&entity, component.as_mut());
+        upstream
+            .world
+            .proxy_mut()
+            .insert_boxed_component(&entity, component);
+        upstream
+            .server
+            .room_mut(&upstream.room_key)
+            .add_entity(&entity);
+        entity
+    }
+
+    // The Client's Entity with a Position, and its x
+    fn client_position(&self) -> Option<(Entity, i32)> {
+        self.client
+            .entities(&self.client_world.proxy())
+            .into_iter()
+            .find_map(|entity| {
+                let entity_ref = self.client.entity(self.client_world.proxy(), &entity);
+                let position = entity_ref.component_of_kind(&ComponentKind::of::<Position>())?;
+                let x = *position.to_any().downcast_ref::<Position>()?.x;
+                Some((entity, x))
+            })
+    }
+
+    // The Entity the Client's Follow component points at
+    fn client_follow_target(&self) -> Option<Entity> {
+        self.client
+            .entities(&self.client_world.proxy())
+            .into_iter()
+            .find_map(|entity| {
+                let entity_ref = self.client.entity(self.client_world.proxy(), &entity);
+                let follow = entity_ref.component_of_kind(&ComponentKind::of::<Follow>())?;
+                let follow = follow.to_any().downcast_ref::<Follow>()?;
+                follow.target.get(&self.client)
+            })
+    }
+}
+
+#[test]
+fn relayed_entities_reach_the_relaying_servers_clients() {
+    let mut test = Test::connect();
+
+    let leader = test.spawn_upstream(Position::new_complete(3));
+    let mut follow = Follow::new_complete();
+    follow.target.set(&test.upstream.server, &leader);
+    let follower = test.spawn_upstream(follow);
+
+    // the follower's target is translated at each hop
+    test.run_until(|test| {
+        test.client_position()
+            .is_some_and(|(entity, x)| x == 3 && test.client_follow_target() == Some(entity))
+    });
+    let upstream_leader = test
+        .relay
+        .client()
+        .entities(&test.relay_world.proxy())
+        .into_iter()
+        .find(|entity| {
+            test.relay
+                .client()
+                .entity(test.relay_world.proxy(), entity)
+                .component_kinds()
+                .contains(&ComponentKind::of::<Position>())
+        })
+        .unwrap();
+    let mirrored_leader = test.relay.local_entity(&upstream_leader).unwrap();
+    assert!(test.relay.upstream_entity(&mirrored_leader) == Some(upstream_leader));
+
+    // updates are relayed
+    *test
+        .upstream
+        .server
+        .entity_mut(test.upstream.world.proxy_mut(), &leader)
+        .component_of_kind(&ComponentKind::of::<Position>())
+        .unwrap()
+        .to_any_mut()
+        .downcast_mut::<Position>()
+        .unwrap()
+        .x = 7;
+    test.run_until(|test| test.client_position().map(|(_, x)| x) == Some(7));
+
+    // as are removed Components
+    let follow_kind = ComponentKind::of::<Follow>();
+    test.upstream
+        .server
+        .remove_component_worldless(&follower, &follow_kind);
+    test.upstream
+        .world
+        .proxy_mut()
+        .remove_component_of_kind(&follower, &follow_kind);
+    test.run_until(|test| test.client_follow_target().is_none());
+
+    // so are despawns
+    test.upstream
+        .server
+        .entity_mut(test.upstream.world.proxy_mut(), &leader)
+        .despawn();
+    test.run_until(|test| test.client_position().is_none());
+    assert!(test.relay.local_entity(&upstream_leader).is_none());
+    assert_eq!(test.client.entities(&test.client_world.proxy()).len(), 1);
+}
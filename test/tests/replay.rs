@@ -0,0 +1,160 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use naia_client::{
+    transport::{
+        loopback::Socket as ClientSocket, replay::Socket as ClientReplaySocket,
+        tap::Socket as ClientTapSocket,
+    },
+    Client, ClientConfig, ConnectEvent as ClientConnectEvent, MessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, Server, ServerConfig,
+};
+use naia_shared::{
+    read_packet_records, Channel, ChannelDirection, ChannelMode, IdentityToken, PacketRecord,
+    PacketRecorder, Protocol, ReliableSettings,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct NoteChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<NoteChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+// A recording's output stream, which can be read back while still recording
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+const NOTES: [&str; 3] = ["one", "two", "three"];
+
+// Whether the Client connected, and the notes it received, in order
+#[derive(Debug, Default, PartialEq)]
+struct Received {
+    connected: bool,
+    notes: Vec<String>,
+}
+
+impl Received {
+    fn read(&mut self, client: &mut Client<Entity>, world: &mut World) {
+        let mut events = client.receive(world.proxy_mut());
+        self.connected |= events.read::<ClientConnectEvent>().next().is_some();
+        for note in events.read::<MessageEvent<NoteChannel, Auth>>() {
+            self.notes.push(note.username);
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.notes.len() == NOTES.len()
+    }
+}
+
+// Records a Client's session, in which the Server sends it some notes,
+// returning the recording, the Client's IdentityToken, and what it received
+fn record_session() -> (Vec<PacketRecord>, IdentityToken, Received) {
+    let transport = LoopbackTransport::new();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&transport));
+    let mut server_world = World::default();
+
+    let recording = SharedBuffer::default();
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client
+        .connect(ClientTapSocket::new(
+            ClientSocket::new(&transport),
+            PacketRecorder::new(recording.clone()).unwrap(),
+        ))
+        .unwrap();
+    let mut client_world = World::default();
+
+    let mut received = Received::default();
+    let mut sent = false;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        if received.connected && !sent {
+            let user_key = server.user_keys()[0];
+            for note in NOTES {
+                server
+                    .send_message::<NoteChannel, Auth>(&user_key, &Auth::new(note, ""))
+                    .unwrap();
+            }
+            sent = true;
+        }
+        server.send_all_updates(server_world.proxy());
+
+        received.read(&mut client, &mut client_world);
+        if received.is_complete() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(received.connected && received.is_complete());
+
+    let records = read_packet_records(recording.0.lock().unwrap().as_slice()).unwrap();
+    (records, client.identity_token().unwrap(), received)
+}
+
+// Replays a recording to a new Client, with no Server, returning what it
+// received
+fn replay_session(
+    records: Vec<PacketRecord>,
+    identity_token: IdentityToken,
+    speed: f32,
+) -> Received {
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client
+        .connect(ClientReplaySocket::new(records, identity_token, speed))
+        .unwrap();
+    let mut client_world = World::default();
+
+    let mut received = Received::default();
+    for _ in 0..1000 {
+        received.read(&mut client, &mut client_world);
+        if received.is_complete() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    received
+}
+
+#[test]
+fn session_replayed_at_recorded_timing_yields_the_recorded_events() {
+    let (records, identity_token, recorded) = record_session();
+    assert_eq!(replay_session(records, identity_token, 1.0), recorded);
+}
+
+#[test]
+fn session_replayed_as_fast_as_possible_yields_the_recorded_events() {
+    let (records, identity_token, recorded) = record_session();
+    assert_eq!(replay_session(records, identity_token, 0.0), recorded);
+}
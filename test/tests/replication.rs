@@ -0,0 +1,279 @@
+use std::{collections::HashMap, thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, RoomKey, Server, ServerConfig, UserKey,
+};
+use naia_shared::{ComponentKind, Property, Protocol, Replicate, WorldMutType};
+use naia_test::Auth;
+
+#[derive(Replicate)]
+struct Position {
+    x: Property<i32>,
+}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_component::<Position>()
+        .build()
+}
+
+// A Server & a connected Client, with every Entity in the Server's one Room
+// in scope for the Client
+struct Test {
+    server: Server<Entity>,
+    server_world: World,
+    room_key: RoomKey,
+    user_key: UserKey,
+    client: Client<Entity>,
+    client_world: World,
+}
+
+impl Test {
+    fn connect(server_config: ServerConfig) -> Self {
+        let transport = LoopbackTransport::new();
+        let mut server = Server::<Entity>::new(server_config, protocol());
+        server.listen(ServerSocket::new(&transport));
+        let room_key = server.make_room().key();
+
+        let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+        client.auth(Auth::new("charlie", "secret"));
+        client.connect(ClientSocket::new(&transport)).unwrap();
+
+        let mut server_world = World::default();
+        let mut client_world = World::default();
+        for _ in 0..1000 {
+            let mut events = server.receive(server_world.proxy_mut());
+            for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+                server.accept_connection(&user_key);
+                server.room_mut(&room_key).add_user(&user_key);
+            }
+            server.send_all_updates(server_world.proxy());
+
+            let mut events = client.receive(client_world.proxy_mut());
+            if events.read::<ClientConnectEvent>().next().is_some() {
+                let user_key = server.user_keys()[0];
+                return Self {
+                    server,
+                    server_world,
+                    room_key,
+                    user_key,
+                    client,
+                    client_world,
+                };
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Client never connected to the Server");
+    }
+
+    // Runs the Server & Client once, returning the Client's Entities whose
+    // Position was updated
+    fn step(&mut self) -> Vec<Entity> {
+        self.server.receive(self.server_world.proxy_mut());
+        for (_, user_key, entity) in self.server.scope_checks() {
+            self.server.user_scope_mut(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.server_world.proxy());
+
+        let mut events = self.client.receive(self.client_world.proxy_mut());
+        let mut updates = events.take_updates().unwrap_or_default();
+        let updated = updates
+            .remove(&ComponentKind::of::<Position>())
+            .unwrap_or_default();
+        updated.into_iter().map(|(_, entity)| entity).collect()
+    }
+
+    // Steps until the check, given the Client's Entities updated that step,
+    // passes
+    fn run_until(&mut self, mut check: impl FnMut(&mut Self, Vec<Entity>) -> bool) {
+        for _ in 0..1000 {
+            let updated = self.step();
+            if check(self, updated) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Server & Client never reached the expected state");
+    }
+
+    fn spawn(&mut self, x: i32) -> Entity {
+        let entity = self.server.spawn_entity(self.server_world.proxy_mut()).id();
+        let mut component: Box<dyn Replicate> = Box::new(Position::new_complete(x));
+        self.server
+            .insert_component_worldless(&entity, component.as_mut());
+        self.server_world
+            .proxy_mut()
+            .insert_boxed_component(&entity, component);
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    fn set_x(&mut self, entity: &Entity, x: i32) {
+        *self
+            .server
+            .entity_mut(self.server_world.proxy_mut(), entity)
+            .component_of_kind(&ComponentKind::of::<Position>())
+            .unwrap()
+            .to_any_mut()
+            .downcast_mut::<Position>()
+            .unwrap()
+            .x = x;
+    }
+
+    // The Client's Entity whose Position satisfies the given check
+    fn client_entity(&self, check: impl Fn(i32) -> bool) -> Option<Entity> {
+        self.client
+            .entities(&self.client_world.proxy())
+            .into_iter()
+            .find(|entity| self.client_x(entity).is_some_and(&check))
+    }
+
+    fn client_x(&self, entity: &Entity) -> Option<i32> {
+        let entity_ref = self.client.entity(self.client_world.proxy(), entity);
+        let position = entity_ref.component_of_kind(&ComponentKind::of::<Position>())?;
+        Some(*position.to_any().downcast_ref::<Position>()?.x)
+    }
+}
+
+#[test]
+fn higher_priority_entities_are_updated_more_often_without_starving_others() {
+    // leaves room for about one Entity's updates per tick
+    let mut server_config = ServerConfig::default();
+    server_config.connection.entity_update_bits_per_tick = Some(1);
+    let mut test = Test::connect(server_config);
+
+    let urgent = test.spawn(1000);
+    let idle = test.spawn(0);
+    test.server.set_entity_priority(&urgent, 4.0);
+
+    // updates flow once the Client has acknowledged both spawns
+    test.run_until(|test, _| {
+        test.set_x(&urgent, 1001);
+        test.set_x(&idle, 1);
+        test.client_entity(|x| x == 1001).is_some() && test.client_entity(|x| x == 1).is_some()
+    });
+    let client_urgent = test.client_entity(|x| x >= 1000).unwrap();
+    let client_idle = test.client_entity(|x| x < 1000).unwrap();
+
+    // both Entities change every tick, but only one fits in each
+    let mut update_counts: HashMap<Entity, usize> = HashMap::new();
+    for tick in 2..=100 {
+        test.set_x(&urgent, 1000 + tick);
+        test.set_x(&idle, tick);
+        for entity in test.step() {
+            *update_counts.entry(entity).or_default() += 1;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    let urgent_updates = update_counts.get(&client_urgent).copied().unwrap_or(0);
+    let idle_updates = update_counts.get(&client_idle).copied().unwrap_or(0);
+    assert!(idle_updates > 0, "low priority Entity was starved");
+    assert!(urgent_updates > idle_updates * 2);
+}
+
+#[test]
+fn resynced_entities_are_resent_in_full() {
+    let mut test = Test::connect(ServerConfig::default());
+    let entity = test.spawn(3);
+    test.run_until(|test, _| {
+        if test.client_entity(|x| x == 4).is_some() {
+            return true;
+        }
+        test.set_x(&entity, 4);
+        false
+    });
+    let client_entity = test.client_entity(|x| x == 4).unwrap();
+
+    // nothing changes, so nothing is sent
+    for _ in 0..20 {
+        assert!(test.step().is_empty());
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert!(test.server.resync_entity(&test.user_key, &entity));
+    test.run_until(|_, updated| updated.contains(&client_entity));
+    assert_eq!(test.client_x(&client_entity), Some(4));
+}
+
+#[test]
+fn frozen_entities_stop_updating_until_unfrozen() {
+    let mut test = Test::connect(ServerConfig::default());
+    let entity = test.spawn(3);
+    test.run_until(|test, _| {
+        if test.client_entity(|x| x == 4).is_some() {
+            return true;
+        }
+        test.set_x(&entity, 4);
+        false
+    });
+    let client_entity = test.client_entity(|x| x == 4).unwrap();
+
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &entity)
+        .freeze_replication();
+    assert_eq!(
+        test.server
+            .entity(test.server_world.proxy(), &entity)
+            .replication_is_frozen(),
+        Some(true)
+    );
+
+    // changes made while frozen stay on the Server
+    for x in 5..25 {
+        test.set_x(&entity, x);
+        assert!(test.step().is_empty());
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(test.client_x(&client_entity), Some(4));
+
+    // the Entity is still spawned, and picks up its current state
+    test.server
+        .entity_mut(test.server_world.proxy_mut(), &entity)
+        .unfreeze_replication();
+    test.run_until(|test, _| test.client_x(&client_entity) == Some(24));
+}
+
+#[test]
+fn updates_deferred_by_the_bandwidth_budget_are_reported_as_backlog() {
+    let mut server_config = ServerConfig::default();
+    server_config.connection.entity_update_bits_per_tick = Some(1);
+    let mut test = Test::connect(server_config);
+
+    let first = test.spawn(1000);
+    let second = test.spawn(0);
+    test.run_until(|test, _| {
+        test.set_x(&first, 1001);
+        test.set_x(&second, 1);
+        test.client_entity(|x| x == 1001).is_some() && test.client_entity(|x| x == 1).is_some()
+    });
+
+    // only one Entity's updates fit in each tick, so the other waits
+    let mut max_backlog = 0;
+    for tick in 2..=20 {
+        test.set_x(&first, 1000 + tick);
+        test.set_x(&second, tick);
+        test.step();
+        let stats = test.server.connection_stats(&test.user_key).unwrap();
+        max_backlog = max_backlog.max(stats.update_backlog);
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(max_backlog > 0);
+
+    // once changes stop, the backlog drains
+    test.run_until(|test, _| {
+        let stats = test.server.connection_stats(&test.user_key).unwrap();
+        stats.update_backlog == 0
+            && test.client_entity(|x| x == 1020).is_some()
+            && test.client_entity(|x| x == 20).is_some()
+    });
+    let stats = test.server.connection_stats(&test.user_key).unwrap();
+    assert!(stats.bytes_out_per_second > 0.0);
+}
@@ -0,0 +1,181 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, RequestEvent, Server, ServerConfig,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, Message, Protocol, ReliableSettings, Request, Response,
+    ResponseReceiveError,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct RequestChannel;
+
+#[derive(Message)]
+struct Ping {
+    index: u8,
+}
+
+impl Request for Ping {
+    type Response = Pong;
+}
+
+#[derive(Message)]
+struct Pong {
+    index: u8,
+}
+
+impl Response for Pong {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_request::<Ping>()
+        .add_channel::<RequestChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::ExpiringReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+// Connects a Client to a Server over a loopback transport
+fn connect(
+    transport: &LoopbackTransport,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> (Server<Entity>, Client<Entity>) {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (server, client);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn unanswered_request_times_out() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+
+    let response_key = client
+        .send_request_with_timeout::<RequestChannel, Ping>(
+            &Ping { index: 1 },
+            Duration::from_millis(100),
+        )
+        .unwrap();
+
+    // the Server never answers
+    for _ in 0..200 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+
+        client.receive(client_world.proxy_mut());
+        if let Some(result) = client.receive_response(&response_key) {
+            assert_eq!(result.err(), Some(ResponseReceiveError::Timeout));
+            assert!(client.pending_requests().is_empty());
+            return;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Request never timed out");
+}
+
+#[test]
+fn answered_request_does_not_time_out() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+
+    let response_key = client
+        .send_request_with_timeout::<RequestChannel, Ping>(
+            &Ping { index: 2 },
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+    for _ in 0..200 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (_, response_send_key, request) in events.read::<RequestEvent<RequestChannel, Ping>>() {
+            let response = Pong {
+                index: request.index,
+            };
+            assert!(server.send_response(&response_send_key, &response));
+        }
+        server.send_all_updates(server_world.proxy());
+
+        client.receive(client_world.proxy_mut());
+        if let Some(result) = client.receive_response(&response_key) {
+            assert_eq!(result.unwrap().index, 2);
+            return;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Response never arrived");
+}
+
+#[test]
+fn cancelled_request_discards_late_response() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client) = connect(&transport, &mut server_world, &mut client_world);
+
+    let response_key = client
+        .send_request::<RequestChannel, Ping>(&Ping { index: 3 })
+        .unwrap();
+
+    // the Server holds on to the Request until the Client has cancelled it
+    let mut held_response_key = None;
+    for _ in 0..200 {
+        let mut events = server.receive(server_world.proxy_mut());
+        if let Some((_, response_send_key, _)) =
+            events.read::<RequestEvent<RequestChannel, Ping>>().next()
+        {
+            held_response_key = Some(response_send_key);
+            break;
+        }
+        server.send_all_updates(server_world.proxy());
+        client.receive(client_world.proxy_mut());
+        thread::sleep(Duration::from_millis(5));
+    }
+    let held_response_key = held_response_key.expect("Request never arrived");
+
+    assert!(client.cancel_request(response_key.request_id()));
+    assert!(!client.cancel_request(response_key.request_id()));
+    assert!(client.pending_requests().is_empty());
+
+    assert!(server.send_response(&held_response_key, &Pong { index: 3 }));
+    for _ in 0..50 {
+        server.receive(server_world.proxy_mut());
+        server.send_all_updates(server_world.proxy());
+
+        client.receive(client_world.proxy_mut());
+        assert!(client.receive_response(&response_key).is_none());
+        thread::sleep(Duration::from_millis(5));
+    }
+}
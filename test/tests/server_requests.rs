@@ -0,0 +1,124 @@
+use std::{thread, time::Duration};
+
+use naia_client::{
+    transport::loopback::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, RequestEvent as ClientRequestEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::loopback::{LoopbackTransport, Socket as ServerSocket},
+    AuthEvent, ResponseEvent, Server, ServerConfig, UserKey,
+};
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, Message, Protocol, ReliableSettings, Request, Response,
+};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct SettingsChannel;
+
+#[derive(Message)]
+struct SettingsQuery {
+    name: String,
+}
+
+impl Request for SettingsQuery {
+    type Response = SettingsAnswer;
+}
+
+#[derive(Message)]
+struct SettingsAnswer {
+    value: String,
+}
+
+impl Response for SettingsAnswer {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_request::<SettingsQuery>()
+        .add_channel::<SettingsChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+// Connects a Client to a Server over a loopback transport, returning the key
+// of the new User
+fn connect(
+    transport: &LoopbackTransport,
+    server_world: &mut World,
+    client_world: &mut World,
+) -> (Server<Entity>, Client<Entity>, UserKey) {
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(transport));
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client.connect(ClientSocket::new(transport)).unwrap();
+
+    let mut user_key = None;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&key);
+            user_key = Some(key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            return (server, client, user_key.unwrap());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never connected to the Server");
+}
+
+#[test]
+fn server_request_is_answered_by_client() {
+    let transport = LoopbackTransport::new();
+    let mut server_world = World::default();
+    let mut client_world = World::default();
+    let (mut server, mut client, user_key) =
+        connect(&transport, &mut server_world, &mut client_world);
+
+    let response_key = server
+        .send_request::<SettingsChannel, SettingsQuery>(
+            &user_key,
+            &SettingsQuery {
+                name: "fov".to_string(),
+            },
+        )
+        .unwrap();
+
+    for _ in 0..200 {
+        let mut events = server.receive(server_world.proxy_mut());
+        if let Some((answering_user_key, answered_key)) =
+            events.read::<ResponseEvent<SettingsAnswer>>().next()
+        {
+            assert_eq!(answering_user_key, user_key);
+            assert_eq!(answered_key.request_id(), response_key.request_id());
+
+            let (answering_user_key, answer) = server.receive_response(&answered_key).unwrap();
+            assert_eq!(answering_user_key, user_key);
+            assert_eq!(answer.value, "fov = 90");
+            assert!(server.pending_requests(&user_key).is_empty());
+            return;
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for (response_send_key, query) in
+            events.read::<ClientRequestEvent<SettingsChannel, SettingsQuery>>()
+        {
+            let answer = SettingsAnswer {
+                value: format!("{} = 90", query.name),
+            };
+            client.send_response(&response_send_key, &answer).unwrap();
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Client never answered the Request");
+}
@@ -0,0 +1,105 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use naia_client::{
+    transport::{
+        loopback::Socket as ClientSocket,
+        tap::{PacketTap as ClientPacketTap, Socket as ClientTapSocket},
+    },
+    Client, ClientConfig, ConnectEvent as ClientConnectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::{
+        loopback::{LoopbackTransport, Socket as ServerSocket},
+        tap::{PacketTap as ServerPacketTap, Socket as ServerTapSocket},
+    },
+    AuthEvent, Server, ServerConfig,
+};
+use naia_shared::Protocol;
+use naia_test::Auth;
+
+fn protocol() -> Protocol {
+    Protocol::builder().add_message::<Auth>().build()
+}
+
+// Keeps every packet it observes
+#[derive(Clone, Default)]
+struct Packets {
+    outgoing: Arc<Mutex<Vec<Vec<u8>>>>,
+    incoming: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl ClientPacketTap for Packets {
+    fn on_outgoing(&self, payload: &[u8]) {
+        self.outgoing.lock().unwrap().push(payload.to_vec());
+    }
+    fn on_incoming(&self, payload: &[u8]) {
+        self.incoming.lock().unwrap().push(payload.to_vec());
+    }
+}
+
+impl ServerPacketTap for Packets {
+    fn on_outgoing(&self, _address: &SocketAddr, payload: &[u8]) {
+        self.outgoing.lock().unwrap().push(payload.to_vec());
+    }
+    fn on_incoming(&self, _address: &SocketAddr, payload: &[u8]) {
+        self.incoming.lock().unwrap().push(payload.to_vec());
+    }
+}
+
+#[test]
+fn taps_observe_every_packet_between_client_and_server() {
+    let transport = LoopbackTransport::new();
+    let server_packets = Packets::default();
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerTapSocket::new(
+        ServerSocket::new(&transport),
+        server_packets.clone(),
+    ));
+
+    let client_packets = Packets::default();
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("charlie", "secret"));
+    client
+        .connect(ClientTapSocket::new(
+            ClientSocket::new(&transport),
+            client_packets.clone(),
+        ))
+        .unwrap();
+
+    let mut client_world = World::default();
+    let mut server_world = World::default();
+    let mut connected = false;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (user_key, _) in events.read::<AuthEvent<Auth>>() {
+            server.accept_connection(&user_key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            connected = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(connected, "Client never connected to the Server");
+
+    // the loopback transport delivers packets unchanged, so each side sees
+    // exactly what the other sent, in the order it was sent
+    let client_outgoing = client_packets.outgoing.lock().unwrap();
+    let server_incoming = server_packets.incoming.lock().unwrap();
+    assert!(!server_incoming.is_empty());
+    assert!(client_outgoing.starts_with(&server_incoming));
+
+    let server_outgoing = server_packets.outgoing.lock().unwrap();
+    let client_incoming = client_packets.incoming.lock().unwrap();
+    assert!(!client_incoming.is_empty());
+    assert!(server_outgoing.starts_with(&client_incoming));
+}
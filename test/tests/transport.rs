@@ -0,0 +1,107 @@
+use naia_client::{
+    transport::{
+        IdentityReceiver, IdentityReceiverResult, PacketReceiver, PacketSender, RecvError,
+        SendError, ServerAddr, Socket,
+    },
+    Client, ClientConfig, RejectEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_shared::{Protocol, RejectReason};
+
+// A transport implemented outside of naia, whose Server is always full
+struct FullServerSocket;
+
+#[derive(Clone)]
+struct FullServerIdentityReceiver;
+
+impl IdentityReceiver for FullServerIdentityReceiver {
+    fn receive(&mut self) -> IdentityReceiverResult {
+        IdentityReceiverResult::Rejected(RejectReason::ServerFull)
+    }
+}
+
+#[derive(Clone)]
+struct NoPackets;
+
+impl PacketSender for NoPackets {
+    fn send(&self, _payload: &[u8]) -> Result<(), SendError> {
+        Err(SendError)
+    }
+
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Finding
+    }
+}
+
+impl PacketReceiver for NoPackets {
+    fn receive(&mut self) -> Result<Option<&[u8]>, RecvError> {
+        Ok(None)
+    }
+
+    fn server_addr(&self) -> ServerAddr {
+        ServerAddr::Finding
+    }
+}
+
+impl Socket for FullServerSocket {
+    fn connect(
+        self: Box<Self>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        (
+            Box::new(FullServerIdentityReceiver),
+            Box::new(NoPackets),
+            Box::new(NoPackets),
+        )
+    }
+
+    fn connect_with_auth(
+        self: Box<Self>,
+        _auth_bytes: Vec<u8>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect()
+    }
+
+    fn connect_with_auth_headers(
+        self: Box<Self>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect()
+    }
+
+    fn connect_with_auth_and_headers(
+        self: Box<Self>,
+        _auth_bytes: Vec<u8>,
+        _auth_headers: Vec<(String, String)>,
+    ) -> (
+        Box<dyn IdentityReceiver>,
+        Box<dyn PacketSender>,
+        Box<dyn PacketReceiver>,
+    ) {
+        self.connect()
+    }
+}
+
+#[test]
+fn client_connects_through_custom_transport() {
+    let mut client = Client::<Entity>::new(ClientConfig::default(), Protocol::builder().build());
+    let mut world = World::default();
+
+    let socket: Box<dyn Socket> = Box::new(FullServerSocket);
+    client.connect(socket).unwrap();
+
+    let mut events = client.receive(world.proxy_mut());
+    let rejections: Vec<RejectReason> = events.read::<RejectEvent>().collect();
+    assert_eq!(rejections, vec![RejectReason::ServerFull]);
+}
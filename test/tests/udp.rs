@@ -0,0 +1,99 @@
+use std::{net::UdpSocket, thread, time::Duration};
+
+use naia_client::{
+    transport::udp::Socket as ClientSocket, Client, ClientConfig,
+    ConnectEvent as ClientConnectEvent, MessageEvent as ClientMessageEvent,
+};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    transport::udp::Socket as ServerSocket, AuthEvent, ConnectEvent, MessageEvent, Server,
+    ServerConfig,
+};
+use naia_shared::{Channel, ChannelDirection, ChannelMode, Protocol, ReliableSettings};
+use naia_test::Auth;
+
+#[derive(Channel)]
+struct ChatChannel;
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Auth>()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+#[test]
+fn client_connects_and_exchanges_messages_over_udp() {
+    // find a free port for the Server
+    let server_addr = UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let mut server = Server::<Entity>::new(ServerConfig::default(), protocol());
+    server.listen(ServerSocket::new(&server_addr, None));
+    let mut server_world = World::default();
+
+    let mut client = Client::<Entity>::new(ClientConfig::default(), protocol());
+    client.auth(Auth::new("alice", "secret"));
+    client
+        .connect(ClientSocket::new(&server_addr, None))
+        .unwrap();
+    let mut client_world = World::default();
+
+    let mut user_key = None;
+    let mut client_connected = false;
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (key, auth) in events.read::<AuthEvent<Auth>>() {
+            assert_eq!(auth.username, "alice");
+            server.accept_connection(&key);
+        }
+        for key in events.read::<ConnectEvent>() {
+            user_key = Some(key);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        if events.read::<ClientConnectEvent>().next().is_some() {
+            client_connected = true;
+        }
+        if client_connected && user_key.is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(client_connected, "Client never connected over UDP");
+    let user_key = user_key.expect("Server never saw the Client connect");
+
+    client
+        .send_message::<ChatChannel, Auth>(&Auth::new("hello", ""))
+        .unwrap();
+    server
+        .send_message::<ChatChannel, Auth>(&user_key, &Auth::new("welcome", ""))
+        .unwrap();
+
+    let mut server_received = Vec::new();
+    let mut client_received = Vec::new();
+    for _ in 0..1000 {
+        let mut events = server.receive(server_world.proxy_mut());
+        for (_, message) in events.read::<MessageEvent<ChatChannel, Auth>>() {
+            server_received.push(message.username);
+        }
+        server.send_all_updates(server_world.proxy());
+
+        let mut events = client.receive(client_world.proxy_mut());
+        for message in events.read::<ClientMessageEvent<ChatChannel, Auth>>() {
+            client_received.push(message.username);
+        }
+        if !server_received.is_empty() && !client_received.is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(server_received, vec!["hello"]);
+    assert_eq!(client_received, vec!["welcome"]);
+}